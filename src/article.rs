@@ -0,0 +1,652 @@
+//! Main article extraction for `tts --url` and `audio-native create --url`
+//!
+//! A small readability-style pass: paragraphs are scored by length and comma
+//! count, the scores flow up to their parent and grandparent elements, and
+//! the text under the best scoring element is kept. Navigation, sidebars and
+//! link-heavy blocks are dropped.
+
+use crate::client::create_http_client;
+use crate::text_clean::decode_entities;
+use anyhow::{Context, Result};
+
+/// Elements whose content is never text
+const SKIP_CONTENT: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "canvas",
+];
+
+/// Page chrome; text inside is ignored
+const CHROME: &[&str] = &[
+    "nav",
+    "header",
+    "footer",
+    "aside",
+    "form",
+    "button",
+    "select",
+    "menu",
+    "figcaption",
+];
+
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements that collect the text inside them as one block
+const BLOCKS: &[&str] = &[
+    "p",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "blockquote",
+    "pre",
+    "td",
+    "th",
+    "dd",
+    "dt",
+    "div",
+    "section",
+    "article",
+    "main",
+    "body",
+];
+
+/// Blocks whose score goes to the enclosing element rather than themselves
+const PARAGRAPHS: &[&str] = &["p", "pre", "blockquote", "td"];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// class/id words that mark page chrome
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment",
+    "footer",
+    "sidebar",
+    "nav",
+    "menu",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "advert",
+    "sponsor",
+    "cookie",
+    "banner",
+    "subscribe",
+    "newsletter",
+    "popup",
+    "breadcrumb",
+    "widget",
+];
+
+/// class/id words that mark the article body
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "content", "post", "entry", "main", "story", "body", "text", "blog",
+];
+
+/// Paragraphs shorter than this do not count towards a container's score
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Extracted article text
+#[derive(Debug, Default)]
+pub struct Article {
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub paragraphs: Vec<String>,
+}
+
+impl Article {
+    /// Plain text for synthesis: the title, then each paragraph
+    pub fn text(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(title) = &self.title {
+            parts.push(as_sentence(title));
+        }
+        parts.extend(self.paragraphs.iter().cloned());
+        parts.join("\n\n")
+    }
+
+    /// A minimal HTML page with only the article, for uploads that expect HTML
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html><body>\n");
+        if let Some(title) = &self.title {
+            html.push_str(&format!("<h1>{}</h1>\n", escape_html(title)));
+        }
+        for paragraph in &self.paragraphs {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(paragraph)));
+        }
+        html.push_str("</body></html>\n");
+        html
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.paragraphs
+            .iter()
+            .map(|p| p.split_whitespace().count())
+            .sum()
+    }
+}
+
+/// Download a page and extract its main article
+pub async fn fetch_article(url: &str) -> Result<Article> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| anyhow::anyhow!("Invalid URL: '{}'", url))?;
+    if parsed.scheme() != "https" && parsed.scheme() != "http" {
+        return Err(anyhow::anyhow!("URL must use HTTP or HTTPS scheme"));
+    }
+
+    let response = create_http_client()
+        .get(parsed)
+        .header(
+            reqwest::header::USER_AGENT,
+            concat!("elevenlabs-cli/", env!("CARGO_PKG_VERSION")),
+        )
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch {}: HTTP {}",
+            url,
+            response.status()
+        ));
+    }
+
+    let plain_text = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/plain"));
+    let body = response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {}", url))?;
+
+    let article = if plain_text {
+        Article {
+            paragraphs: body
+                .split("\n\n")
+                .map(normalize_space)
+                .filter(|p| !p.is_empty())
+                .collect(),
+            ..Default::default()
+        }
+    } else {
+        extract_article(&body)
+    };
+    if article.paragraphs.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Could not find any article text at {}",
+            url
+        ));
+    }
+    Ok(article)
+}
+
+struct Node {
+    tag: String,
+    parent: Option<usize>,
+    /// Inside page chrome; text is dropped
+    ignored: bool,
+    /// class/id hint bonus or penalty
+    weight: f64,
+    score: Option<f64>,
+}
+
+struct Block {
+    node: usize,
+    text: String,
+    link_chars: usize,
+}
+
+impl Block {
+    fn link_density(&self) -> f64 {
+        let len = self.text.chars().count();
+        if len == 0 {
+            0.0
+        } else {
+            self.link_chars as f64 / len as f64
+        }
+    }
+}
+
+/// Extract the main article from an HTML page
+pub fn extract_article(html: &str) -> Article {
+    let mut nodes = vec![Node {
+        tag: String::new(),
+        parent: None,
+        ignored: false,
+        weight: 0.0,
+        score: None,
+    }];
+    let mut stack: Vec<usize> = vec![0];
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut link_depth = 0usize;
+    let mut page_title = None;
+    let mut og_title = None;
+    let mut byline = None;
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            add_text(&mut blocks, &nodes, &stack, rest, link_depth);
+            break;
+        };
+        if lt > 0 {
+            add_text(&mut blocks, &nodes, &stack, &rest[..lt], link_depth);
+            rest = &rest[lt..];
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or("");
+            continue;
+        }
+        let Some(tag) = parse_tag(rest) else {
+            add_text(&mut blocks, &nodes, &stack, "<", link_depth);
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[tag.len..];
+
+        if tag.closing {
+            if tag.name == "a" {
+                link_depth = link_depth.saturating_sub(1);
+            }
+            if let Some(pos) = stack.iter().rposition(|&n| nodes[n].tag == tag.name) {
+                stack.truncate(pos.max(1));
+            }
+            continue;
+        }
+
+        if SKIP_CONTENT.contains(&tag.name.as_str()) || tag.name == "title" {
+            let (content, after) = split_at_closing(rest, &tag.name);
+            if tag.name == "title" && page_title.is_none() {
+                page_title = Some(normalize_space(&decode_entities(content)));
+            }
+            rest = after;
+            continue;
+        }
+        if tag.name == "meta" {
+            let key = attr(tag.attrs, "property").or_else(|| attr(tag.attrs, "name"));
+            let content = attr(tag.attrs, "content").map(|c| normalize_space(&decode_entities(&c)));
+            match key.as_deref() {
+                Some("og:title") => og_title = content,
+                Some("author") => byline = content,
+                _ => {}
+            }
+            continue;
+        }
+        if VOID.contains(&tag.name.as_str()) || tag.self_closing {
+            if tag.name == "br" {
+                add_text(&mut blocks, &nodes, &stack, " ", link_depth);
+            }
+            continue;
+        }
+
+        // Unclosed <p> and <li> end where the next one starts
+        let top = *stack.last().unwrap_or(&0);
+        if (tag.name == "p" || tag.name == "li") && nodes[top].tag == tag.name {
+            stack.pop();
+        }
+        if tag.name == "a" {
+            link_depth += 1;
+        }
+
+        let parent = *stack.last().unwrap_or(&0);
+        let hints = format!(
+            "{} {}",
+            attr(tag.attrs, "class").unwrap_or_default(),
+            attr(tag.attrs, "id").unwrap_or_default()
+        )
+        .to_lowercase();
+        let negative = NEGATIVE_HINTS.iter().any(|h| hints.contains(h));
+        let positive = POSITIVE_HINTS.iter().any(|h| hints.contains(h));
+        let mut weight = match (positive, negative) {
+            (true, false) => 25.0,
+            (false, true) => -25.0,
+            _ => 0.0,
+        };
+        if tag.name == "article" {
+            weight += 10.0;
+        }
+        nodes.push(Node {
+            ignored: nodes[parent].ignored
+                || CHROME.contains(&tag.name.as_str())
+                || (negative && !positive),
+            tag: tag.name,
+            parent: Some(parent),
+            weight,
+            score: None,
+        });
+        stack.push(nodes.len() - 1);
+    }
+
+    for block in &mut blocks {
+        block.text = normalize_space(&decode_entities(&block.text));
+    }
+
+    // Score containers by the paragraphs inside them
+    for block in &blocks {
+        let tag = nodes[block.node].tag.as_str();
+        let len = block.text.chars().count();
+        if !(PARAGRAPHS.contains(&tag) || tag == "div")
+            || len < MIN_PARAGRAPH_CHARS
+            || block.link_density() > 0.5
+        {
+            continue;
+        }
+        let score = 1.0 + block.text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+        let container = if PARAGRAPHS.contains(&tag) {
+            nodes[block.node].parent.unwrap_or(0)
+        } else {
+            block.node
+        };
+        add_score(&mut nodes, container, score);
+        if let Some(grandparent) = nodes[container].parent {
+            add_score(&mut nodes, grandparent, score / 2.0);
+        }
+    }
+
+    let top = nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, n)| n.score.map(|s| (i, s)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i);
+
+    let within = |mut node: usize, ancestor: usize| loop {
+        if node == ancestor {
+            return true;
+        }
+        match nodes[node].parent {
+            Some(parent) => node = parent,
+            None => return false,
+        }
+    };
+
+    let mut title = None;
+    let mut paragraphs = Vec::new();
+    for block in &blocks {
+        if block.text.is_empty() || top.is_some_and(|top| !within(block.node, top)) {
+            continue;
+        }
+        let tag = nodes[block.node].tag.as_str();
+        if HEADINGS.contains(&tag) {
+            if tag == "h1" && title.is_none() && paragraphs.is_empty() {
+                title = Some(block.text.clone());
+            } else {
+                paragraphs.push(as_sentence(&block.text));
+            }
+        } else if block.link_density() <= 0.5 {
+            paragraphs.push(block.text.clone());
+        }
+    }
+
+    Article {
+        title: title.or(og_title).or(page_title).filter(|t| !t.is_empty()),
+        byline: byline.filter(|b| !b.is_empty()),
+        paragraphs,
+    }
+}
+
+/// `href` values of the links in an HTML page, in document order
+pub fn extract_links(html: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = html;
+    while let Some(lt) = rest.find('<') {
+        rest = &rest[lt..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or("");
+            continue;
+        }
+        let Some(tag) = parse_tag(rest) else {
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[tag.len..];
+        if tag.closing {
+            continue;
+        }
+        if SKIP_CONTENT.contains(&tag.name.as_str()) && !tag.self_closing {
+            rest = split_at_closing(rest, &tag.name).1;
+        } else if tag.name == "a" {
+            if let Some(href) = attr(tag.attrs, "href") {
+                links.push(decode_entities(href.trim()));
+            }
+        }
+    }
+    links
+}
+
+fn add_score(nodes: &mut [Node], node: usize, score: f64) {
+    let weight = nodes[node].weight;
+    *nodes[node].score.get_or_insert(weight) += score;
+}
+
+/// Append a text run to the innermost block element that is not page chrome
+fn add_text(blocks: &mut Vec<Block>, nodes: &[Node], stack: &[usize], text: &str, links: usize) {
+    let top = *stack.last().unwrap_or(&0);
+    if nodes[top].ignored {
+        return;
+    }
+    let node = stack
+        .iter()
+        .rev()
+        .copied()
+        .find(|&n| BLOCKS.contains(&nodes[n].tag.as_str()))
+        .unwrap_or(0);
+    let link_chars = if links > 0 {
+        text.split_whitespace().map(|w| w.chars().count()).sum()
+    } else {
+        0
+    };
+
+    match blocks.last_mut() {
+        Some(last) if last.node == node => {
+            last.text.push_str(text);
+            last.link_chars += link_chars;
+        }
+        _ => blocks.push(Block {
+            node,
+            text: text.to_string(),
+            link_chars,
+        }),
+    }
+}
+
+struct Tag<'a> {
+    name: String,
+    attrs: &'a str,
+    closing: bool,
+    self_closing: bool,
+    /// Bytes consumed, including `<` and `>`
+    len: usize,
+}
+
+/// Parse the tag at the start of `input` (which begins with `<`)
+fn parse_tag(input: &str) -> Option<Tag<'_>> {
+    let inner = &input[1..];
+    let closing = inner.starts_with('/');
+    let body = inner.trim_start_matches('/');
+    let first = body.chars().next()?;
+    if !(first.is_ascii_alphabetic() || first == '!' || first == '?') {
+        return None;
+    }
+
+    // Find the closing `>`, skipping quoted attribute values
+    let mut quote = None;
+    let end = inner.char_indices().find_map(|(i, c)| {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+        None
+    })?;
+
+    let content = &inner[..end];
+    let offset = if closing { 1 } else { 0 };
+    let name_len = content[offset..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':' || c == '!'))
+        .unwrap_or(content.len() - offset);
+    Some(Tag {
+        name: content[offset..offset + name_len].to_lowercase(),
+        attrs: &content[offset + name_len..],
+        closing,
+        self_closing: content.ends_with('/') || first == '!' || first == '?',
+        len: end + 2,
+    })
+}
+
+/// Split at the matching `</name>`: (content, rest after the closing tag)
+fn split_at_closing<'a>(input: &'a str, name: &str) -> (&'a str, &'a str) {
+    let closing = format!("</{}", name);
+    match input
+        .as_bytes()
+        .windows(closing.len())
+        .position(|w| w.eq_ignore_ascii_case(closing.as_bytes()))
+    {
+        Some(pos) => {
+            let after = input[pos..]
+                .find('>')
+                .map(|gt| &input[pos + gt + 1..])
+                .unwrap_or("");
+            (&input[..pos], after)
+        }
+        None => (input, ""),
+    }
+}
+
+/// Value of an attribute in a tag's attribute string
+fn attr(attrs: &str, name: &str) -> Option<String> {
+    let lower = attrs.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find(name) {
+        let start = from + found;
+        from = start + name.len();
+        let boundary = start == 0 || lower.as_bytes()[start - 1].is_ascii_whitespace();
+        let rest = attrs[from..].trim_start();
+        let Some(value) = rest.strip_prefix('=').filter(|_| boundary) else {
+            continue;
+        };
+        let value = value.trim_start();
+        return Some(match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or("").to_string(),
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("")
+                .to_string(),
+        });
+    }
+    None
+}
+
+fn normalize_space(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// End a heading or title with a full stop so it reads as its own sentence
+pub(crate) fn as_sentence(text: &str) -> String {
+    if text.ends_with(['.', '!', '?', ':']) {
+        text.to_string()
+    } else {
+        format!("{}.", text)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><head>
+  <title>Why we moved to Rust | Example Blog</title>
+  <meta name="author" content="Sam Lee">
+  <script>var x = "<p>not text</p>";</script>
+</head>
+<body>
+  <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+  <div class="sidebar"><p>Subscribe to our newsletter for weekly posts, tips, and more.</p></div>
+  <article class="post">
+    <h1>Why we moved to Rust</h1>
+    <p>We rewrote the ingestion service last year, and it changed how we work.</p>
+    <p>Memory use dropped by half, latency became predictable, and on-call got quiet.
+    <p>The hardest part was the learning curve &mdash; not the compiler.</p>
+    <h2>What&#8217;s next</h2>
+    <ul><li><a href="/a">Read part one</a></li><li><a href="/b">Read part two</a></li></ul>
+    <p>We plan to move the billing pipeline next, once the team is comfortable.</p>
+  </article>
+  <footer><p>Copyright 2025, Example Inc. All rights reserved, everywhere.</p></footer>
+</body></html>"#;
+
+    #[test]
+    fn test_extracts_main_article() {
+        let article = extract_article(PAGE);
+        assert_eq!(article.title.as_deref(), Some("Why we moved to Rust"));
+        assert_eq!(article.byline.as_deref(), Some("Sam Lee"));
+        assert_eq!(
+            article.paragraphs,
+            vec![
+                "We rewrote the ingestion service last year, and it changed how we work.",
+                "Memory use dropped by half, latency became predictable, and on-call got quiet.",
+                "The hardest part was the learning curve \u{2014} not the compiler.",
+                "What\u{2019}s next.",
+                "We plan to move the billing pipeline next, once the team is comfortable.",
+            ]
+        );
+        assert!(article
+            .text()
+            .starts_with("Why we moved to Rust.\n\nWe rewrote"));
+    }
+
+    #[test]
+    fn test_falls_back_to_page_title() {
+        let article = extract_article(
+            "<title>Notes</title><div><p>Just one short page of notes, really.</p></div>",
+        );
+        assert_eq!(article.title.as_deref(), Some("Notes"));
+        assert_eq!(article.paragraphs.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_links() {
+        assert_eq!(extract_links(PAGE), vec!["/", "/blog", "/a", "/b"]);
+        assert_eq!(
+            extract_links(r#"<!-- <a href="/hidden"> --><A HREF="/x?a=1&amp;b=2">x</A>"#),
+            vec!["/x?a=1&b=2"]
+        );
+    }
+
+    #[test]
+    fn test_attr_and_html_output() {
+        assert_eq!(
+            attr(r#" data-class="x" class='a b' id=main"#, "class").as_deref(),
+            Some("a b")
+        );
+        assert_eq!(attr(r#" id=main"#, "id").as_deref(), Some("main"));
+        let article = Article {
+            title: Some("A < B".to_string()),
+            paragraphs: vec!["Fish & chips".to_string()],
+            ..Default::default()
+        };
+        assert!(article
+            .to_html()
+            .contains("<h1>A &lt; B</h1>\n<p>Fish &amp; chips</p>"));
+    }
+}
@@ -29,16 +29,23 @@ pub mod audio_io {
     /// Create with `new()`, then call `send_chunk()` from async context,
     /// and `finish()` when done. Runs playback in separate thread.
     pub struct StreamingPlayer {
-        sender: Option<Sender<Vec<u8>>>,
+        sender: Option<Sender<PlayerInput>>,
         handle: Option<thread::JoinHandle<()>>,
     }
 
+    /// What the playback thread receives
+    enum PlayerInput {
+        Audio(Vec<u8>),
+        /// Drop everything queued (e.g. the listener interrupted)
+        Clear,
+    }
+
     impl StreamingPlayer {
         /// Create a new streaming player and start playback thread
         pub fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
             use rodio::{Decoder, OutputStream, Sink};
 
-            let (tx, rx) = mpsc::channel::<Vec<u8>>();
+            let (tx, rx) = mpsc::channel::<PlayerInput>();
 
             let handle = thread::spawn(move || {
                 let (_stream, stream_handle) = OutputStream::try_default().unwrap();
@@ -51,7 +58,12 @@ pub mod audio_io {
                 loop {
                     // Try to receive with timeout
                     match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                        Ok(chunk) => {
+                        Ok(PlayerInput::Clear) => {
+                            buffer.clear();
+                            sink.clear();
+                            sink.play();
+                        }
+                        Ok(PlayerInput::Audio(chunk)) => {
                             buffer.extend_from_slice(&chunk);
 
                             // Try to decode and play when we have enough data
@@ -110,13 +122,87 @@ pub mod audio_io {
             })
         }
 
+        /// Create a player for headerless mono 16-bit PCM at `sample_rate`
+        ///
+        /// Each chunk is queued as it arrives, so playback is gapless.
+        pub fn pcm(sample_rate: u32) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+            use rodio::buffer::SamplesBuffer;
+            use rodio::{OutputStream, Sink};
+
+            let (tx, rx) = mpsc::channel::<PlayerInput>();
+            let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+
+            let handle = thread::spawn(move || {
+                let output = OutputStream::try_default()
+                    .map_err(|e| e.to_string())
+                    .and_then(|(stream, handle)| {
+                        Sink::try_new(&handle)
+                            .map(|sink| (stream, sink))
+                            .map_err(|e| e.to_string())
+                    });
+                let (_stream, sink) = match output {
+                    Ok(output) => {
+                        let _ = ready_tx.send(Ok(()));
+                        output
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                // An odd trailing byte waits for the next chunk
+                let mut pending = Vec::new();
+                while let Ok(input) = rx.recv() {
+                    match input {
+                        PlayerInput::Audio(chunk) => {
+                            pending.extend_from_slice(&chunk);
+                            let whole = pending.len() & !1;
+                            let samples: Vec<i16> = pending[..whole]
+                                .chunks_exact(2)
+                                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                                .collect();
+                            pending.drain(..whole);
+                            if !samples.is_empty() {
+                                sink.append(SamplesBuffer::new(1, sample_rate, samples));
+                            }
+                        }
+                        PlayerInput::Clear => {
+                            pending.clear();
+                            sink.clear();
+                            sink.play();
+                        }
+                    }
+                }
+
+                sink.sleep_until_end();
+            });
+
+            ready_rx
+                .recv()
+                .map_err(|_| "Audio output thread exited")?
+                .map_err(|e| format!("No audio output device: {}", e))?;
+            Ok(Self {
+                sender: Some(tx),
+                handle: Some(handle),
+            })
+        }
+
         /// Send an audio chunk to be played
         pub fn send_chunk(
             &self,
             chunk: &[u8],
         ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             if let Some(ref sender) = self.sender {
-                sender.send(chunk.to_vec())?;
+                sender.send(PlayerInput::Audio(chunk.to_vec()))?;
+            }
+            Ok(())
+        }
+
+        /// Stop playback and drop any queued audio; later chunks play normally
+        pub fn clear(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            if let Some(ref sender) = self.sender {
+                sender.send(PlayerInput::Clear)?;
             }
             Ok(())
         }
@@ -381,15 +467,17 @@ pub mod audio_io {
 
     /// Stream from microphone with callback and optional VAD
     ///
+    /// - `device_name`: Input device (partial name match), or the default one
     /// - `chunk_duration`: How often to call callback (in seconds)
-    /// - `callback`: Called with each chunk of audio
+    /// - `callback`: Called with each chunk of 16-bit PCM, the sample rate and
+    ///   the channel count; returning false stops streaming
     /// - `use_vad`: If true, stops streaming when speech ends
     /// - `silence_threshold`: Energy threshold for VAD (0.0-1.0)
     /// - `silence_duration`: Seconds of silence before stopping
-    #[allow(dead_code)]
     pub fn stream_from_microphone_with_vad(
+        device_name: Option<&str>,
         chunk_duration_secs: f32,
-        mut callback: impl FnMut(&[u8]) + Send + 'static,
+        mut callback: impl FnMut(&[u8], u32, u16) -> bool + Send + 'static,
         use_vad: bool,
         silence_threshold: f32,
         silence_duration_secs: f32,
@@ -400,17 +488,22 @@ pub mod audio_io {
         use std::sync::Arc;
         use std::time::Instant;
 
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .ok_or("No input device available")?;
+        let device = match device_name {
+            Some(name) => get_input_device(name)?
+                .ok_or_else(|| format!("Input device '{}' not found", name))?,
+            None => cpal::default_host()
+                .default_input_device()
+                .ok_or("No input device available")?,
+        };
 
         let config = device
             .default_input_config()
             .map_err(|e| format!("Failed to get default input config: {}", e))?;
 
-        let sample_rate = config.sample_rate().0 as f32;
-        let channels = config.channels() as usize;
+        let rate = config.sample_rate().0;
+        let channel_count = config.channels();
+        let sample_rate = rate as f32;
+        let channels = channel_count as usize;
         let chunk_size = (chunk_duration_secs * sample_rate * channels as f32) as usize * 2;
 
         let chunk_buffer = Arc::new(std::sync::Mutex::new(Vec::with_capacity(chunk_size)));
@@ -517,20 +610,21 @@ pub mod audio_io {
 
         let chunk_interval = std::time::Duration::from_secs_f32(chunk_duration_secs);
 
-        while streaming.load(Ordering::Relaxed) {
+        let mut wanted = true;
+        while wanted && streaming.load(Ordering::Relaxed) {
             std::thread::sleep(chunk_interval);
             let mut chunk = chunk_buffer.lock().unwrap();
             if !chunk.is_empty() {
-                callback(&chunk);
+                wanted = callback(&chunk, rate, channel_count);
                 chunk.clear();
             }
         }
 
         // Final chunk
-        {
+        if wanted {
             let chunk = chunk_buffer.lock().unwrap();
             if !chunk.is_empty() {
-                callback(&chunk);
+                callback(&chunk, rate, channel_count);
             }
         }
 
@@ -572,6 +666,21 @@ pub mod audio_io {
         Ok(devices)
     }
 
+    /// Sample rate and channel count that `record_from_microphone` and
+    /// `record_with_vad` capture at (the default input device's config)
+    #[allow(dead_code)]
+    pub fn default_input_format() -> Result<(u32, u16), Box<dyn std::error::Error + Send + Sync>> {
+        use cpal::traits::{DeviceTrait, HostTrait};
+
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or("No input device available")?;
+        let config = device
+            .default_input_config()
+            .map_err(|e| format!("Failed to get default input config: {}", e))?;
+        Ok((config.sample_rate().0, config.channels()))
+    }
+
     /// Get input device by name (partial match)
     #[allow(dead_code)]
     pub fn get_input_device(
@@ -630,12 +739,18 @@ pub mod audio_io {
         pub fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
             Err("Audio feature not enabled. Rebuild with --features audio".into())
         }
+        pub fn pcm(_sample_rate: u32) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+            Err("Audio feature not enabled. Rebuild with --features audio".into())
+        }
         pub fn send_chunk(
             &self,
             _chunk: &[u8],
         ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             Err("Audio feature not enabled".into())
         }
+        pub fn clear(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Err("Audio feature not enabled".into())
+        }
         pub fn finish(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             Err("Audio feature not enabled".into())
         }
@@ -667,10 +782,10 @@ pub mod audio_io {
     }
 
     /// Stub for stream_from_microphone_with_vad
-    #[allow(dead_code)]
     pub fn stream_from_microphone_with_vad(
+        _device_name: Option<&str>,
         _chunk_duration_secs: f32,
-        _callback: impl FnMut(&[u8]) + Send + 'static,
+        _callback: impl FnMut(&[u8], u32, u16) -> bool + Send + 'static,
         _use_vad: bool,
         _silence_threshold: f32,
         _silence_duration_secs: f32,
@@ -690,6 +805,12 @@ pub mod audio_io {
         Err("Audio feature not enabled. Rebuild with --features audio".into())
     }
 
+    /// Stub for default_input_format
+    #[allow(dead_code)]
+    pub fn default_input_format() -> Result<(u32, u16), Box<dyn std::error::Error + Send + Sync>> {
+        Err("Audio feature not enabled. Rebuild with --features audio".into())
+    }
+
     /// Stub for get_input_device
     #[allow(dead_code)]
     pub fn get_input_device(
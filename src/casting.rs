@@ -0,0 +1,132 @@
+//! Casting files for recurring productions (`--casting casting.yaml`)
+//!
+//! A casting file maps each character to a voice, optional voice settings and
+//! a dictionary of words that character pronounces their own way:
+//!
+//! ```yaml
+//! characters:
+//!   Narrator:
+//!     voice: JBFqnCBsd6RMkjVDRZzb
+//!     stability: 0.6
+//!   Aeryn:
+//!     voice: Rachel
+//!     style: 0.3
+//!     dictionary:
+//!       Aeryn: Air-in
+//! ```
+//!
+//! `tts` speaker markers and `dialogue` inputs then name characters instead
+//! of voices, so a series keeps the same casting across sessions. Settings
+//! sit between the voice's stored defaults and the command line flags, and
+//! dictionary words are replaced (whole words, any case) in the character's
+//! lines before they are sent.
+
+use crate::config::VoiceDefaults;
+use crate::validation::validate_voice_settings;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Casting {
+    pub characters: BTreeMap<String, Character>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Character {
+    /// Voice ID (or name where the command resolves names)
+    pub voice: String,
+    #[serde(flatten)]
+    pub settings: VoiceDefaults,
+    /// Word to the text sent in its place
+    #[serde(default)]
+    pub dictionary: BTreeMap<String, String>,
+}
+
+impl Casting {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(Path::new(path))
+            .with_context(|| format!("Failed to read casting file {}", path))?;
+        Self::parse(&contents).with_context(|| format!("Invalid casting file {}", path))
+    }
+
+    fn parse(yaml: &str) -> Result<Self> {
+        let casting: Casting = serde_yaml::from_str(yaml)?;
+        for (name, character) in &casting.characters {
+            if character.voice.trim().is_empty() {
+                return Err(anyhow::anyhow!("Character '{}' has no voice", name));
+            }
+            let settings = &character.settings;
+            validate_voice_settings(
+                settings.stability,
+                settings.similarity_boost,
+                settings.style,
+            )
+            .with_context(|| format!("Character '{}'", name))?;
+        }
+        Ok(casting)
+    }
+
+    /// The character called `name` (case-insensitive)
+    pub fn character(&self, name: &str) -> Option<&Character> {
+        self.characters
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, character)| character)
+    }
+}
+
+impl Character {
+    /// `text` with the character's dictionary words replaced
+    pub fn apply_dictionary(&self, text: &str) -> String {
+        self.dictionary
+            .iter()
+            .fold(text.to_string(), |text, (word, spoken)| {
+                crate::commands::tts::replace_word(&text, word, |_| spoken.clone())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASTING: &str = r#"
+characters:
+  Narrator:
+    voice: JBFqnCBsd6RMkjVDRZzb
+    stability: 0.6
+  Aeryn:
+    voice: Rachel
+    style: 0.3
+    speaker_boost: true
+    dictionary:
+      Aeryn: Air-in
+      Tlön: Tlern
+"#;
+
+    #[test]
+    fn test_parse_casting() {
+        let casting = Casting::parse(CASTING).unwrap();
+        let narrator = casting.character("narrator").unwrap();
+        assert_eq!(narrator.voice, "JBFqnCBsd6RMkjVDRZzb");
+        assert_eq!(narrator.settings.stability, Some(0.6));
+        assert!(narrator.dictionary.is_empty());
+
+        let aeryn = casting.character("Aeryn").unwrap();
+        assert_eq!(aeryn.settings.speaker_boost, Some(true));
+        assert_eq!(
+            aeryn.apply_dictionary("I'm aeryn, from Tlön. Aeryns are rare."),
+            "I'm Air-in, from Tlern. Aeryns are rare."
+        );
+        assert!(casting.character("Bob").is_none());
+    }
+
+    #[test]
+    fn test_invalid_casting() {
+        assert!(Casting::parse("characters:\n  A:\n    voice: ''\n").is_err());
+        assert!(Casting::parse("characters:\n  A:\n    voice: x\n    stability: 2\n").is_err());
+        assert!(Casting::parse("characters: []\n").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Agent CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// Agent management arguments
@@ -36,6 +37,12 @@ pub enum AgentCommands {
         #[arg(short, long)]
         limit: Option<u32>,
     },
+    /// Overview of all agents with recent conversation counts and last activity
+    Overview {
+        /// Look-back window for conversation stats (e.g. 24h, 7d, 4w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+    },
     /// Get agent details
     Get {
         /// Agent ID
@@ -86,6 +93,32 @@ pub enum AgentCommands {
         /// Agent ID
         agent_id: String,
     },
+    /// Export an agent's configuration as YAML or JSON (for version control or another workspace)
+    Export {
+        /// Agent ID
+        agent_id: String,
+
+        /// Output file; .json writes JSON, anything else YAML (default: stdout)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Create an agent from an exported configuration, or update one with --agent-id
+    Import {
+        /// YAML or JSON file written by 'agent export'
+        file: String,
+
+        /// Update this agent instead of creating a new one
+        #[arg(long, value_name = "AGENT_ID")]
+        agent_id: Option<String>,
+
+        /// Agent name (default: the name in the file)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Leave out knowledge base documents and tools that don't exist in this workspace
+        #[arg(long)]
+        drop_missing: bool,
+    },
     /// Duplicate an agent
     Duplicate {
         /// Agent ID to duplicate
@@ -110,6 +143,11 @@ pub enum AgentCommands {
         #[arg(short, long)]
         name: String,
     },
+    /// Manage agent branches (create, delete, promote)
+    Branch {
+        #[command(subcommand)]
+        command: AgentBranchCommands,
+    },
     /// List batch calls
     BatchList {
         /// Page size
@@ -121,6 +159,26 @@ pub enum AgentCommands {
         /// Batch call ID
         batch_id: String,
     },
+    /// Submit a batch of outbound calls from a recipients CSV
+    BatchCreate {
+        /// Agent ID
+        agent_id: String,
+        /// Phone number ID to call from
+        #[arg(long)]
+        phone_number_id: String,
+        /// CSV with a phone_number column; other columns become per-recipient dynamic variables
+        #[arg(long, value_name = "FILE")]
+        recipients: String,
+        /// Batch name
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Dynamic variable applied to every recipient (repeatable)
+        #[arg(long = "dynamic-var", value_name = "KEY=VALUE")]
+        dynamic_vars: Vec<String>,
+
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
     /// Delete a batch call
     BatchDelete {
         /// Batch call ID
@@ -131,11 +189,36 @@ pub enum AgentCommands {
         /// Agent ID
         agent_id: String,
         /// User message to simulate
-        #[arg(short, long)]
-        message: String,
+        #[arg(
+            short,
+            long,
+            required_unless_present = "script",
+            conflicts_with = "script"
+        )]
+        message: Option<String>,
+        /// Scripted conversation file (JSON: turns, tool_mocks, evaluation_criteria)
+        #[arg(long, value_name = "FILE")]
+        script: Option<String>,
+        /// Extra evaluation criterion for this run (repeatable)
+        #[arg(long = "criterion", value_name = "ID=PROMPT")]
+        criteria: Vec<String>,
         /// Maximum turns in the simulation
         #[arg(long, default_value = "5")]
         max_turns: u32,
+        /// Dynamic variable for the agent's {{placeholders}} (repeatable)
+        #[arg(long = "dynamic-var", value_name = "KEY=VALUE")]
+        dynamic_vars: Vec<String>,
+    },
+    /// Health-check an agent: config, referenced voices/documents/tools, one simulated turn
+    Smoke {
+        /// Agent ID
+        agent_id: String,
+        /// User message for the simulated turn
+        #[arg(short, long, default_value = "Hello, can you help me?")]
+        message: String,
+        /// Skip the simulated conversation turn
+        #[arg(long)]
+        no_simulate: bool,
     },
     /// Update agent turn configuration
     UpdateTurn {
@@ -148,6 +231,21 @@ pub enum AgentCommands {
         #[arg(long)]
         silence_threshold_ms: Option<u32>,
     },
+    /// Configure transfers to other agents and to phone numbers
+    Transfer {
+        #[command(subcommand)]
+        command: AgentTransferCommands,
+    },
+    /// Manage per-language presets (voice and first message for each language)
+    Languages {
+        #[command(subcommand)]
+        command: AgentLanguageCommands,
+    },
+    /// Show or change conversation data retention settings
+    Privacy {
+        #[command(subcommand)]
+        command: AgentPrivacyCommands,
+    },
     /// List WhatsApp accounts connected to agents
     WhatsappList,
     /// Get agent widget configuration
@@ -164,3 +262,177 @@ pub enum AgentCommands {
         avatar_file: String,
     },
 }
+
+/// How a phone transfer hands over the call
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum PhoneTransferType {
+    /// Agent stays on the line until the human joins
+    Conference,
+    /// Call is handed over immediately
+    Blind,
+}
+
+#[derive(Subcommand)]
+pub enum AgentTransferCommands {
+    /// List an agent's transfer rules
+    List {
+        /// Agent ID
+        agent_id: String,
+    },
+    /// Add a transfer rule, replacing any existing rule for the same target
+    Set {
+        /// Agent ID
+        agent_id: String,
+        /// Agent to transfer to
+        #[arg(
+            long,
+            value_name = "AGENT_ID",
+            required_unless_present = "to_number",
+            conflicts_with = "to_number"
+        )]
+        to_agent: Option<String>,
+        /// Phone number (E.164) to transfer to, e.g. a human support line
+        #[arg(long, value_name = "PHONE")]
+        to_number: Option<String>,
+        /// When the agent should transfer, in plain language
+        #[arg(long)]
+        condition: String,
+        /// Message spoken before an agent transfer
+        #[arg(long, requires = "to_agent")]
+        message: Option<String>,
+        /// Delay before an agent transfer, in milliseconds
+        #[arg(long, requires = "to_agent")]
+        delay_ms: Option<u32>,
+        /// Phone transfer type
+        #[arg(long, value_enum, requires = "to_number")]
+        transfer_type: Option<PhoneTransferType>,
+    },
+    /// Remove the transfer rule for a target
+    Remove {
+        /// Agent ID
+        agent_id: String,
+        /// Agent the rule transfers to
+        #[arg(
+            long,
+            value_name = "AGENT_ID",
+            required_unless_present = "to_number",
+            conflicts_with = "to_number"
+        )]
+        to_agent: Option<String>,
+        /// Phone number the rule transfers to
+        #[arg(long, value_name = "PHONE")]
+        to_number: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AgentLanguageCommands {
+    /// List an agent's language presets
+    List {
+        /// Agent ID
+        agent_id: String,
+    },
+    /// Add, change or remove language presets
+    Set {
+        /// Agent ID
+        agent_id: String,
+        /// Add a language preset, e.g. es or pt-br (repeatable)
+        #[arg(long, value_name = "LANG")]
+        add: Vec<String>,
+        /// Remove a language preset (repeatable)
+        #[arg(long, value_name = "LANG")]
+        remove: Vec<String>,
+        /// Voice for a language, e.g. es=<VOICE_ID> (repeatable; adds the language)
+        #[arg(long, value_name = "LANG=VOICE_ID")]
+        voice_for: Vec<String>,
+        /// First message for a language, e.g. es="Hola" (repeatable; adds the language)
+        #[arg(long, value_name = "LANG=TEXT")]
+        first_message_for: Vec<String>,
+    },
+}
+
+/// Conversation data retention settings (unset flags are left unchanged)
+#[derive(Args, Clone, Debug)]
+pub struct PrivacySettingsArgs {
+    /// Days to keep conversations, or "unlimited"
+    #[arg(long, value_name = "DAYS")]
+    pub retention_days: Option<String>,
+    /// Save call audio
+    #[arg(long, value_name = "BOOL")]
+    pub audio_save: Option<bool>,
+    /// Zero-retention mode: store no transcripts, audio or analysis at all
+    #[arg(long, value_name = "BOOL")]
+    pub zero_retention: Option<bool>,
+    /// Delete transcripts and PII once the retention period ends
+    #[arg(long, value_name = "BOOL")]
+    pub delete_transcripts: Option<bool>,
+    /// Also apply the retention period to existing conversations
+    #[arg(long)]
+    pub apply_to_existing: bool,
+}
+
+#[derive(Subcommand)]
+pub enum AgentPrivacyCommands {
+    /// Show an agent's privacy settings
+    Get {
+        /// Agent ID
+        agent_id: String,
+    },
+    /// Change an agent's privacy settings
+    Set {
+        /// Agent ID
+        agent_id: String,
+        #[command(flatten)]
+        settings: PrivacySettingsArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AgentBranchCommands {
+    /// List agent branches
+    List {
+        /// Agent ID
+        agent_id: String,
+    },
+    /// Create a branch
+    Create {
+        /// Agent ID
+        agent_id: String,
+        /// Branch name
+        #[arg(short, long)]
+        name: String,
+        /// Branch (ID or name) to branch from (defaults to main)
+        #[arg(long, value_name = "BRANCH")]
+        from: Option<String>,
+        /// Branch description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+    /// Rename a branch
+    Rename {
+        /// Agent ID
+        agent_id: String,
+        /// Branch ID or name
+        branch: String,
+        /// New branch name
+        #[arg(short, long)]
+        name: String,
+    },
+    /// Delete a branch
+    Delete {
+        /// Agent ID
+        agent_id: String,
+        /// Branch ID or name
+        branch: String,
+    },
+    /// Merge a branch into another branch (main by default)
+    Promote {
+        /// Agent ID
+        agent_id: String,
+        /// Branch ID or name to promote
+        branch: String,
+        /// Target branch ID or name
+        #[arg(long, default_value = "main", value_name = "BRANCH")]
+        to: String,
+    },
+}
@@ -0,0 +1,23 @@
+//! Raw API call CLI arguments
+
+use clap::Args;
+
+/// Raw API call arguments
+#[derive(Args)]
+pub struct ApiArgs {
+    /// HTTP method
+    #[arg(value_name = "METHOD", value_parser = ["GET", "POST", "PUT", "PATCH", "DELETE"], ignore_case = true)]
+    pub method: String,
+
+    /// Endpoint path, e.g. /v1/voices (add parameters with --query)
+    #[arg(value_name = "PATH")]
+    pub path: String,
+
+    /// JSON request body, or @FILE to read it from a file (@- for stdin)
+    #[arg(short, long, value_name = "JSON")]
+    pub data: Option<String>,
+
+    /// Write the response body to a file instead of printing it
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+}
@@ -4,15 +4,24 @@ use clap::Subcommand;
 
 // Import subcommand types from other modules
 use super::agent::*;
+use super::api::*;
 use super::audio::*;
+use super::backup::*;
+use super::completions::*;
 use super::config::*;
 use super::conversation::*;
+use super::daemon::*;
 use super::design::*;
 use super::dialogue::*;
 use super::dubbing::*;
 use super::history::*;
+use super::ivr::*;
+use super::jobs::*;
 use super::knowledge::*;
 use super::library::*;
+use super::limits::*;
+#[cfg(feature = "mcp")]
+use super::mcp::*;
 use super::models::*;
 use super::music::*;
 use super::native::*;
@@ -21,7 +30,12 @@ use super::projects::*;
 use super::pronunciation::*;
 use super::rag::*;
 use super::samples::*;
+use super::schedule::*;
+use super::search::*;
+#[cfg(feature = "serve")]
+use super::serve::*;
 use super::stt::*;
+use super::support::*;
 use super::tools::*;
 use super::tts::*;
 use super::update::*;
@@ -31,7 +45,75 @@ use super::voice::*;
 use super::webhook::*;
 use super::workspace::*;
 
+/// When to use colored output
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and NO_COLOR is unset
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(anyhow::anyhow!(
+                "Invalid color setting '{}'. Valid values are: auto, always, never",
+                s
+            )),
+        }
+    }
+}
+
+/// How default output files are grouped into subdirectories
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Organize {
+    /// Year and month of generation (2025-06)
+    #[value(name = "by-date")]
+    Date,
+    /// Voice used for the generation
+    #[value(name = "by-voice")]
+    Voice,
+    /// Project name given with --project
+    #[value(name = "by-project")]
+    Project,
+}
+
+impl std::str::FromStr for Organize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "by-date" => Ok(Self::Date),
+            "by-voice" => Ok(Self::Voice),
+            "by-project" => Ok(Self::Project),
+            _ => Err(anyhow::anyhow!(
+                "Invalid organize setting '{}'. Valid values are: by-date, by-voice, by-project",
+                s
+            )),
+        }
+    }
+}
+
+/// Parse a comma-separated organize setting such as "by-date,by-voice"
+pub fn parse_organize(value: &str) -> anyhow::Result<Vec<Organize>> {
+    value
+        .split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
 /// Main command enum for all CLI subcommands
+// Parsed once per run, so the size of `TextToSpeech` does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Commands {
     /// Text-to-Speech operations
@@ -54,6 +136,10 @@ pub enum Commands {
     #[command(name = "sfx")]
     SoundEffects(SoundEffectsArgs),
 
+    /// Generate tones and silence locally (no API key needed)
+    #[command(name = "audio")]
+    Audio(AudioArgs),
+
     /// Voice changer (speech-to-speech)
     #[command(name = "voice-changer", alias = "vc")]
     VoiceChanger(VoiceChangerArgs),
@@ -158,13 +244,49 @@ pub enum Commands {
     #[command(name = "phone")]
     Phone(PhoneArgs),
 
-    /// Generate shell completions
+    /// Build telephony prompt packs for IVR systems
+    #[command(name = "ivr")]
+    Ivr(IvrArgs),
+
+    /// Search cached voices, history and agents by name or text
+    #[command(name = "search", alias = "find")]
+    Search(SearchArgs),
+
+    /// Back up voices, agents, knowledge, dictionaries, webhooks and settings to a directory
+    #[command(name = "export")]
+    Export(ExportArgs),
+
+    /// Re-create resources from an export backup in the current account
+    #[command(name = "import")]
+    Import(ImportArgs),
+
+    /// Show plan limits, the API's concurrency limit and observed throttling (429s)
+    #[command(name = "limits")]
+    Limits(LimitsArgs),
+
+    /// Run queued job files from a directory
+    #[command(name = "daemon")]
+    Daemon(DaemonArgs),
+
+    /// Run CLI commands on a recurring schedule
+    #[command(name = "schedule")]
+    Schedule(ScheduleArgs),
+
+    /// Track dubbing, voice training, conversions and other remote jobs
+    #[command(name = "jobs")]
+    Jobs(JobsArgs),
+
+    /// Call an API endpoint the CLI doesn't wrap yet and print the response
+    #[command(name = "api")]
+    Api(ApiArgs),
+
+    /// Generate or install shell completions
     #[command(name = "completions")]
-    Completions {
-        /// Shell to generate completions for
-        #[arg(value_enum)]
-        shell: clap_complete::Shell,
-    },
+    Completions(CompletionsArgs),
+
+    /// Collect version info, redacted config and recent errors into a zip for bug reports
+    #[command(name = "support-bundle")]
+    SupportBundle(SupportBundleArgs),
 
     /// Update the CLI to the latest version
     #[command(name = "update")]
@@ -174,10 +296,18 @@ pub enum Commands {
     #[command(name = "interactive", alias = "repl")]
     Interactive,
 
+    /// Serve a local REST API (POST /tts, /stt, /sfx, GET /voices)
+    #[cfg(feature = "serve")]
+    #[command(name = "serve")]
+    Serve(ServeArgs),
+
     /// Run as MCP (Model Context Protocol) server for AI assistants
     #[cfg(feature = "mcp")]
     #[command(name = "mcp")]
     Mcp {
+        #[command(subcommand)]
+        command: Option<McpCommands>,
+
         /// Comma-separated list of tools to enable (e.g., "tts,stt,voice")
         #[arg(long)]
         enable_tools: Option<String>,
@@ -194,7 +324,8 @@ pub enum Commands {
         #[arg(long)]
         disable_destructive: bool,
 
-        /// Read-only mode: disable all write operations (same as --disable-admin)
+        /// Read-only mode: keep only lookup tools, dropping every tool that changes the
+        /// account, spends credits or writes files, even ones named in --enable-tools
         #[arg(long)]
         read_only: bool,
     },
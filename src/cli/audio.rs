@@ -1,6 +1,6 @@
-//! Audio processing CLI arguments (isolation, SFX, voice changer)
+//! Audio processing CLI arguments (isolation, SFX, voice changer, generators)
 
-use clap::Args;
+use clap::{Args, Subcommand};
 
 /// Audio isolation arguments
 #[derive(Args)]
@@ -29,11 +29,66 @@ pub struct SoundEffectsArgs {
     #[arg(short, long, value_name = "FLOAT")]
     pub influence: Option<f32>,
 
+    /// Ask the model for a seamlessly looping sound (eleven_text_to_sound_v2)
+    #[arg(long = "loop")]
+    pub looping: bool,
+
+    /// Build a seamless loop locally by crossfading the end into the start (writes WAV)
+    #[arg(long, value_name = "MS")]
+    pub crossfade: Option<u32>,
+
     /// Output file path
     #[arg(short, long, value_name = "OUTPUT")]
     pub output: Option<String>,
 }
 
+/// Local audio generator arguments
+#[derive(Args)]
+pub struct AudioArgs {
+    #[command(subcommand)]
+    pub command: AudioCommands,
+}
+
+#[derive(Subcommand)]
+pub enum AudioCommands {
+    /// Generate a sine tone, e.g. a beep for IVR prompts or a test fixture
+    Tone {
+        /// Frequency in Hz
+        #[arg(long, default_value = "440")]
+        freq: f64,
+
+        /// Duration in seconds
+        #[arg(short, long, default_value = "1")]
+        duration: f64,
+
+        /// Volume (0.0-1.0 of full scale)
+        #[arg(long, value_name = "0.0-1.0", default_value = "0.5")]
+        volume: f64,
+
+        /// Output format: wav_<rate>, pcm_<rate>, ulaw_8000 or alaw_8000
+        #[arg(long, default_value = "wav_44100")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<String>,
+    },
+    /// Generate silence for padding between clips
+    Silence {
+        /// Duration in seconds
+        #[arg(short, long, default_value = "1")]
+        duration: f64,
+
+        /// Output format: wav_<rate>, pcm_<rate>, ulaw_8000 or alaw_8000
+        #[arg(long, default_value = "wav_44100")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<String>,
+    },
+}
+
 /// Voice changer arguments
 #[derive(Args)]
 pub struct VoiceChangerArgs {
@@ -76,6 +131,10 @@ pub struct VoiceChangerArgs {
     /// Voice settings style
     #[arg(long, value_name = "FLOAT")]
     pub style: Option<f32>,
+
+    /// Strip background noise and music instead of keeping them (file input only)
+    #[arg(long)]
+    pub remove_background_noise: bool,
 }
 
 /// WebSocket TTS arguments for real-time streaming
@@ -108,4 +167,17 @@ pub struct RealtimeTtsArgs {
     /// Output format
     #[arg(long, default_value = "mp3_44100_128")]
     pub output_format: String,
+
+    /// Latency optimization: 0 (best quality) to 4 (fastest, disables text normalization)
+    #[arg(
+        long = "optimize-streaming-latency",
+        visible_alias = "latency",
+        value_name = "0-4",
+        value_parser = clap::value_parser!(u8).range(0..=4)
+    )]
+    pub latency: Option<u8>,
+
+    /// Characters buffered before each audio chunk, e.g. 50,90,120 (lower is faster)
+    #[arg(long, value_name = "N,N,...", value_delimiter = ',')]
+    pub chunk_schedule: Vec<u32>,
 }
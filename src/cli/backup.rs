@@ -0,0 +1,50 @@
+//! Account backup CLI arguments
+
+use clap::Args;
+
+/// Resource types included in a backup
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum BackupSection {
+    Voices,
+    Agents,
+    Knowledge,
+    Dictionaries,
+    Webhooks,
+    Settings,
+}
+
+/// Export arguments
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Backup directory (default: elevenlabs-backup-<timestamp>)
+    #[arg(long, value_name = "DIR")]
+    pub out: Option<String>,
+
+    /// Only export these resource types (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<BackupSection>,
+
+    /// Skip downloading voice sample audio (metadata only)
+    #[arg(long)]
+    pub no_samples: bool,
+}
+
+/// Import arguments
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Backup directory written by `export`
+    #[arg(value_name = "DIR")]
+    pub dir: String,
+
+    /// Reuse resources that already exist with the same name instead of creating duplicates
+    #[arg(long)]
+    pub skip_existing: bool,
+
+    /// Only import these resource types (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<BackupSection>,
+
+    /// Show what would be created without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,29 @@
+//! Shell completions CLI arguments
+
+use clap::{Args, Subcommand};
+
+/// Shell completions arguments
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for (prints the script to stdout)
+    #[arg(value_enum)]
+    pub shell: Option<clap_complete::Shell>,
+
+    #[command(subcommand)]
+    pub command: Option<CompletionsCommands>,
+}
+
+#[derive(Subcommand)]
+pub enum CompletionsCommands {
+    /// Install the completion script into the per-user completions directory
+    Install {
+        /// Shell to install completions for (detected from $SHELL if omitted)
+        #[arg(long, value_enum)]
+        shell: Option<clap_complete::Shell>,
+
+        /// Install into this directory instead of the detected one
+        #[arg(long, value_name = "DIR")]
+        dir: Option<String>,
+    },
+}
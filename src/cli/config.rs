@@ -13,16 +13,76 @@ pub struct ConfigArgs {
 pub enum ConfigCommands {
     /// Show current configuration
     Show,
+    /// Show where config, cache and data files are stored
+    Paths,
     /// Set configuration value
     Set {
-        /// Configuration key (api_key, default_voice, default_model)
+        /// Configuration key (api_key, default_voice, default_model, default_output_format,
+        /// color, ascii_symbols, output_dir, organize, player_cmd, clean_input)
         key: String,
         /// Configuration value
         value: String,
     },
+    /// Store the API key, in the OS keyring when the build supports it
+    SetKey {
+        /// API key (prompted for without echo when omitted)
+        key: Option<String>,
+
+        /// Keep the key in the config file even if the keyring is available
+        #[arg(long)]
+        plaintext: bool,
+    },
     /// Remove configuration value
     Unset {
         /// Configuration key
         key: String,
     },
+    /// Manage named profiles for several API keys or workspaces
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// Create a profile, or change the values of an existing one
+    Add {
+        /// Profile name
+        name: String,
+
+        /// API key used with this profile (stored in the OS keyring when
+        /// the build supports it)
+        #[arg(long, value_name = "KEY")]
+        key: Option<String>,
+
+        /// Keep --key in the config file even if the keyring is available
+        #[arg(long, requires = "key")]
+        plaintext: bool,
+
+        /// Default voice
+        #[arg(long, value_name = "VOICE")]
+        default_voice: Option<String>,
+
+        /// Default model
+        #[arg(long, value_name = "MODEL")]
+        default_model: Option<String>,
+
+        /// Default output format
+        #[arg(long, value_name = "FORMAT")]
+        default_output_format: Option<String>,
+    },
+    /// List profiles
+    List,
+    /// Use a profile whenever --profile and ELEVENLABS_PROFILE are not given
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// Delete a profile
+    #[command(alias = "rm")]
+    Remove {
+        /// Profile name
+        name: String,
+    },
 }
@@ -12,12 +12,24 @@ pub struct ConversationArgs {
 #[derive(Args)]
 pub struct ConverseArgs {
     /// Agent ID to converse with
-    #[arg(long)]
+    #[arg(long, visible_alias = "agent")]
     pub agent_id: String,
 
-    /// Initial user message
+    /// User message to send before reading the terminal (repeatable)
     #[arg(short, long)]
-    pub message: Option<String>,
+    pub message: Vec<String>,
+
+    /// Send the messages, wait for each reply, print the transcript and exit
+    #[arg(long)]
+    pub non_interactive: bool,
+
+    /// File with one user message per line (with --non-interactive)
+    #[arg(long, value_name = "FILE", requires = "non_interactive")]
+    pub messages_file: Option<String>,
+
+    /// Seconds to wait for the agent to answer each message (with --non-interactive)
+    #[arg(long, default_value = "30", value_name = "SECS")]
+    pub turn_timeout: u64,
 
     /// Audio input file (for testing)
     #[arg(long, value_name = "FILE")]
@@ -34,6 +46,68 @@ pub struct ConverseArgs {
     /// Output audio device name
     #[arg(long, value_name = "DEVICE")]
     pub output_device: Option<String>,
+
+    /// Override the agent's system prompt with the contents of a file
+    #[arg(long, value_name = "FILE")]
+    pub system_prompt_file: Option<String>,
+
+    /// Override the agent's first message
+    #[arg(long)]
+    pub first_message: Option<String>,
+
+    /// Override the conversation language (e.g., en, es, fr)
+    #[arg(long, value_name = "CODE")]
+    pub language: Option<String>,
+
+    /// Dynamic variable for the agent's {{placeholders}} (repeatable)
+    #[arg(long = "dynamic-var", value_name = "KEY=VALUE")]
+    pub dynamic_vars: Vec<String>,
+
+    /// Record the agent's audio (agent_audio.wav) and a transcript of both
+    /// sides (transcript.json) into this directory. Chat input is typed, so
+    /// your side is only in the transcript; there is no microphone audio.
+    #[arg(long = "record-agent", value_name = "DIR")]
+    pub record: Option<String>,
+
+    /// Connect through a signed URL fetched with your API key (for agents requiring auth)
+    #[arg(long, conflicts_with = "token")]
+    pub use_signed_url: bool,
+
+    /// Connect with a short-lived conversation token fetched with your API key
+    #[arg(long)]
+    pub token: bool,
+
+    /// Maximum consecutive reconnection attempts after a dropped connection (0 disables)
+    #[arg(long, default_value = "5", value_name = "N")]
+    pub max_reconnects: u32,
+}
+
+/// Voice conversation arguments
+#[derive(Args)]
+pub struct ConverseTalkArgs {
+    /// Agent ID to talk to
+    #[arg(value_name = "AGENT_ID")]
+    pub agent_id: String,
+
+    /// Input audio device name (see `stt --list-input-devices`)
+    #[arg(long, value_name = "DEVICE")]
+    pub device: Option<String>,
+
+    /// Override the conversation language (e.g., en, es, fr)
+    #[arg(long, value_name = "CODE")]
+    pub language: Option<String>,
+
+    /// Dynamic variable for the agent's {{placeholders}} (repeatable)
+    #[arg(long = "dynamic-var", value_name = "KEY=VALUE")]
+    pub dynamic_vars: Vec<String>,
+
+    /// Connect through a signed URL fetched with your API key (for agents requiring auth)
+    #[arg(long, conflicts_with = "token")]
+    pub use_signed_url: bool,
+
+    /// Connect with a short-lived conversation token fetched with your API key
+    #[arg(long)]
+    pub token: bool,
 }
 
 #[derive(Subcommand)]
@@ -42,6 +116,9 @@ pub enum ConversationCommands {
     #[command(name = "chat")]
     Converse(ConverseArgs),
 
+    /// Talk to an agent by voice: microphone in, agent audio out (needs the audio feature)
+    Talk(ConverseTalkArgs),
+
     /// List conversations
     List {
         /// Filter by agent ID
@@ -55,6 +132,22 @@ pub enum ConversationCommands {
         /// Page size
         #[arg(short, long)]
         limit: Option<u32>,
+
+        /// Only show conversations with this status (filtered locally)
+        #[arg(long, value_parser = ["initiated", "in-progress", "processing", "done", "failed"])]
+        status: Option<String>,
+
+        /// Only show calls the evaluation marked successful (true) or failed (false)
+        #[arg(long, value_name = "BOOL")]
+        call_successful: Option<bool>,
+
+        /// Only calls started after this date or relative time (YYYY-MM-DD, 7d, 12h)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// Only calls started before this date or relative time (YYYY-MM-DD, 7d, 12h)
+        #[arg(long, value_name = "WHEN")]
+        until: Option<String>,
     },
 
     /// Get conversation details
@@ -97,6 +190,10 @@ pub enum ConversationCommands {
         /// Output file path
         #[arg(short, long, value_name = "OUTPUT")]
         output: Option<String>,
+
+        /// Print a waveform and level summary of the recording
+        #[arg(long)]
+        waveform: bool,
     },
 
     /// Send feedback on a conversation
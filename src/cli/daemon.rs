@@ -0,0 +1,31 @@
+//! Daemon CLI arguments
+
+use clap::Args;
+
+/// Job queue daemon arguments
+#[derive(Args)]
+pub struct DaemonArgs {
+    /// Directory to watch for job files (*.json)
+    #[arg(long, value_name = "DIR")]
+    pub queue_dir: String,
+
+    /// Maximum number of jobs to run at the same time
+    #[arg(short, long, default_value = "1", value_name = "INT")]
+    pub concurrency: usize,
+
+    /// Keep --concurrency fixed instead of backing off on rate limits (429)
+    #[arg(long)]
+    pub no_auto_tune: bool,
+
+    /// Maximum number of jobs started per minute
+    #[arg(long, value_name = "INT")]
+    pub rate: Option<u32>,
+
+    /// Seconds between scans of the queue directory
+    #[arg(long, default_value = "2", value_name = "SECS")]
+    pub poll: u64,
+
+    /// Process the jobs already queued, then exit
+    #[arg(long)]
+    pub once: bool,
+}
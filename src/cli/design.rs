@@ -1,17 +1,21 @@
 //! Voice Design CLI arguments
 
-use clap::Args;
+use clap::{Args, Subcommand};
 
 /// Voice Design arguments
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct VoiceDesignArgs {
+    #[command(subcommand)]
+    pub command: Option<VoiceDesignCommands>,
+
     /// Voice description/prompt
-    #[arg(short, long)]
-    pub description: String,
+    #[arg(short, long, required = true)]
+    pub description: Option<String>,
 
     /// Text to use for preview (100-1000 characters)
-    #[arg(short, long)]
-    pub text: String,
+    #[arg(short, long, required = true)]
+    pub text: Option<String>,
 
     /// Output file path
     #[arg(short, long, value_name = "OUTPUT")]
@@ -21,3 +25,82 @@ pub struct VoiceDesignArgs {
     #[arg(long, default_value = "mp3_44100_128")]
     pub format: String,
 }
+
+#[derive(Subcommand)]
+pub enum VoiceDesignCommands {
+    /// Refine a voice over several rounds: generate, rate, remix, then save the best preview
+    Iterate(VoiceDesignIterateArgs),
+}
+
+/// A preview rating given as `<PREVIEW>=<1-5>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewRating {
+    pub preview: String,
+    pub stars: u8,
+}
+
+impl std::str::FromStr for PreviewRating {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (preview, stars) = s
+            .rsplit_once('=')
+            .ok_or_else(|| format!("Invalid rating '{}' (expected PREVIEW=1-5)", s))?;
+        let stars: u8 = stars
+            .trim()
+            .parse()
+            .ok()
+            .filter(|n| (1..=5).contains(n))
+            .ok_or_else(|| format!("Invalid rating '{}' (stars must be 1-5)", s))?;
+        let preview = preview.trim();
+        if preview.is_empty() {
+            return Err(format!("Invalid rating '{}' (missing preview)", s));
+        }
+        Ok(Self {
+            preview: preview.to_string(),
+            stars,
+        })
+    }
+}
+
+/// Voice design iteration arguments
+///
+/// Previews are referred to by generated voice ID or by `<round>.<n>`, e.g. `2.1`.
+#[derive(Args)]
+pub struct VoiceDesignIterateArgs {
+    /// Session name; each session keeps its own prompts, previews and ratings
+    #[arg(long, default_value = "default", value_name = "NAME")]
+    pub session: String,
+
+    /// Voice description for a new round, or the change to make with --remix
+    #[arg(short, long, value_name = "PROMPT")]
+    pub prompt: Option<String>,
+
+    /// Preview text (100-1000 characters); kept for later rounds, generated by the API when unset
+    #[arg(short, long)]
+    pub text: Option<String>,
+
+    /// Remix this preview with --prompt instead of designing from scratch
+    #[arg(long, value_name = "PREVIEW", requires = "prompt")]
+    pub remix: Option<String>,
+
+    /// Rate previews, e.g. --rate 1.2=4 (repeatable)
+    #[arg(long, value_name = "PREVIEW=STARS")]
+    pub rate: Vec<PreviewRating>,
+
+    /// Save this preview as a voice in your account, which ends the session
+    #[arg(long, value_name = "PREVIEW", requires = "name", conflicts_with_all = ["prompt", "remix"])]
+    pub save: Option<String>,
+
+    /// Name of the saved voice
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Directory for preview audio (default: voice-design-<session>)
+    #[arg(long, value_name = "DIR")]
+    pub out_dir: Option<String>,
+
+    /// Delete the session and start over
+    #[arg(long, conflicts_with_all = ["prompt", "remix", "rate", "save"])]
+    pub reset: bool,
+}
@@ -9,6 +9,10 @@ pub struct DialogueArgs {
     #[arg(short, long, value_delimiter = ',', value_name = "TEXT:VOICE_ID")]
     pub inputs: Vec<String>,
 
+    /// Casting file: inputs name characters (text:character) instead of voices
+    #[arg(long, value_name = "FILE")]
+    pub casting: Option<String>,
+
     /// Model to use
     #[arg(short, long, default_value = "eleven_v3")]
     pub model: String,
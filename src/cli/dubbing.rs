@@ -1,5 +1,6 @@
 //! Dubbing CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// Dubbing arguments
@@ -32,24 +33,163 @@ pub enum DubbingCommands {
         /// Watermark the audio
         #[arg(long)]
         watermark: bool,
+
+        /// YAML file assigning detected speakers to voices, e.g. `1: <VOICE_ID>` or
+        /// `"Speaker 2": original`; waits for the dub, then re-dubs those speakers
+        #[arg(long, value_name = "FILE")]
+        speaker_map: Option<String>,
+
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Estimate the credit cost of dubbing a file
+    Estimate {
+        /// Media file to dub
+        file: String,
+
+        /// Target language codes (comma-separated)
+        #[arg(
+            short,
+            long,
+            value_name = "CODES",
+            value_delimiter = ',',
+            required = true
+        )]
+        target_lang: Vec<String>,
+
+        /// Media duration in seconds (skips probing with ffprobe)
+        #[arg(long, value_name = "SECS")]
+        duration: Option<f64>,
+
+        /// Credits per minute of media per language, as priced for your plan
+        /// and output (see https://elevenlabs.io/pricing)
+        #[arg(long, value_name = "CREDITS", required = true)]
+        credits_per_minute: u64,
     },
     /// Get dubbing status
     Status {
         /// Dubbing ID
         dubbing_id: String,
     },
-    /// Download dubbed audio
+    /// Download the dubbed audio or video
     Download {
         /// Dubbing ID
         dubbing_id: String,
 
-        /// Output file path
+        /// Target language to download (defaults to the first dubbed language)
+        #[arg(short, long, value_name = "CODE", alias = "lang")]
+        language: Option<String>,
+
+        /// Output file path (default: <dubbing_id>_<language>.mp4 or .mp3)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<String>,
+    },
+    /// Export the dubbed transcript as subtitles
+    Transcript {
+        /// Dubbing ID
+        dubbing_id: String,
+
+        /// Target language (defaults to the first dubbed language)
+        #[arg(short, long, value_name = "CODE", alias = "lang")]
+        language: Option<String>,
+
+        /// Subtitle format
+        #[arg(short, long, value_parser = ["srt", "vtt"], default_value = "srt")]
+        format: String,
+
+        /// Output file path (default: stdout)
         #[arg(short, long, value_name = "OUTPUT")]
         output: Option<String>,
     },
+    /// Burn the dubbed subtitles into the dubbed video (requires ffmpeg)
+    #[command(name = "burn-captions")]
+    BurnCaptions {
+        /// Dubbing ID
+        dubbing_id: String,
+
+        /// Target language to burn in (defaults to the first dubbed language)
+        #[arg(short, long, value_name = "CODE")]
+        lang: Option<String>,
+
+        /// Output video path
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<String>,
+    },
+    /// Regenerate only the segments inside time ranges (Dubbing Studio projects)
+    Redo {
+        /// Dubbing ID
+        dubbing_id: String,
+
+        /// Time range, e.g. 00:02:10-00:02:40, 2:10-2:40 or 130-160 (repeatable)
+        #[arg(short, long, value_name = "START-END", required = true)]
+        range: Vec<TimeRange>,
+
+        /// Target languages to redo (comma-separated; default: all)
+        #[arg(short, long, value_name = "CODES", value_delimiter = ',')]
+        lang: Vec<String>,
+
+        /// Translate the segments again before dubbing them
+        #[arg(long)]
+        retranslate: bool,
+
+        /// Render format for the updated dub
+        #[arg(long, value_parser = ["mp4", "aac", "mp3", "wav"], default_value = "mp4")]
+        render: String,
+
+        /// Skip rendering; the changes are included in the next render
+        #[arg(long)]
+        no_render: bool,
+
+        /// Only list the segments that would be redone
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Delete a dubbing project
     Delete {
         /// Dubbing ID
         dubbing_id: String,
     },
 }
+
+/// A `START-END` span of media time in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl std::str::FromStr for TimeRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid range '{}' (expected START-END)", s))?;
+        let start = parse_timestamp(start)
+            .ok_or_else(|| format!("Invalid start time '{}' in range '{}'", start, s))?;
+        let end = parse_timestamp(end)
+            .ok_or_else(|| format!("Invalid end time '{}' in range '{}'", end, s))?;
+        if end <= start {
+            return Err(format!("Range '{}' ends before it starts", s));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+/// Parse `HH:MM:SS`, `MM:SS` or plain seconds, each with optional fractions
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let mut seconds = 0.0;
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    for (i, part) in parts.iter().enumerate() {
+        let number: f64 = part.parse().ok().filter(|n: &f64| *n >= 0.0)?;
+        // Minutes and seconds after a colon stay below 60; only the last part has a fraction
+        if (i > 0 && number >= 60.0) || (i + 1 < parts.len() && number.fract() != 0.0) {
+            return None;
+        }
+        seconds = seconds * 60.0 + number;
+    }
+    Some(seconds)
+}
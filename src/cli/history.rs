@@ -2,6 +2,39 @@
 
 use clap::{Args, Subcommand};
 
+/// Fields `history list` can show
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HistoryColumn {
+    /// History item ID
+    Id,
+    /// When the item was generated, e.g. "2h ago"
+    Date,
+    /// Voice name
+    Voice,
+    /// Model ID
+    Model,
+    /// Characters billed for the generation
+    Chars,
+    /// Audio length (estimated from the text unless --fetch-durations)
+    Duration,
+    /// Start of the source text
+    Text,
+    /// Generation state
+    State,
+    /// Where the generation came from (TTS, STS, ...)
+    Source,
+}
+
+/// Columns shown when --columns is not given
+pub const DEFAULT_HISTORY_COLUMNS: &[HistoryColumn] = &[
+    HistoryColumn::Id,
+    HistoryColumn::Date,
+    HistoryColumn::Voice,
+    HistoryColumn::Chars,
+    HistoryColumn::Duration,
+    HistoryColumn::Text,
+];
+
 /// History arguments
 #[derive(Args)]
 pub struct HistoryArgs {
@@ -20,6 +53,14 @@ pub enum HistoryCommands {
         /// Show all details
         #[arg(short, long)]
         detailed: bool,
+
+        /// Columns to show (comma-separated): id, date, voice, model, chars, duration, text, state, source
+        #[arg(long, value_enum, value_name = "COLUMNS", value_delimiter = ',')]
+        columns: Vec<HistoryColumn>,
+
+        /// Download each item's audio to measure its duration instead of estimating it
+        #[arg(long)]
+        fetch_durations: bool,
     },
     /// Get history item details
     Get {
@@ -39,6 +80,43 @@ pub enum HistoryCommands {
         /// Output file path
         #[arg(short, long, value_name = "OUTPUT")]
         output: Option<String>,
+
+        /// Embed ID3 tags (title, voice, model) into the downloaded MP3
+        #[arg(long)]
+        tag: bool,
+
+        /// Album/project name to embed when tagging
+        #[arg(long, value_name = "NAME", requires = "tag")]
+        album: Option<String>,
+
+        /// Print a waveform and level summary of the audio
+        #[arg(long)]
+        waveform: bool,
+    },
+    /// Download every generation not yet in a local directory
+    ///
+    /// Audio is saved as <history_item_id>.<ext> next to an index.json with
+    /// each item's metadata. Items already in the directory are skipped, so
+    /// rerunning only fetches what is new.
+    Sync {
+        /// Directory to sync into (created if missing)
+        dir: String,
+
+        /// Only items generated since a date (YYYY-MM-DD) or a relative time (7d, 12h)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only items generated with this voice
+        #[arg(long, value_name = "VOICE_ID")]
+        voice: Option<String>,
+
+        /// Download at most this many new items
+        #[arg(short, long)]
+        limit: Option<usize>,
+
+        /// Number of downloads to run at the same time
+        #[arg(short, long, default_value = "4", value_name = "INT")]
+        concurrency: usize,
     },
     /// Submit feedback on generated audio
     Feedback {
@@ -53,4 +131,15 @@ pub enum HistoryCommands {
         #[arg(long)]
         feedback: Option<String>,
     },
+    /// Submit feedback for many history items from a CSV file
+    ///
+    /// Columns: history_item_id, rating (up/down) and optional feedback.
+    FeedbackBatch {
+        /// CSV file with one rating per row
+        file: String,
+
+        /// Validate the file and show what would be sent without submitting
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
@@ -0,0 +1,49 @@
+//! IVR prompt CLI arguments
+
+use clap::{Args, Subcommand};
+
+/// IVR prompt arguments
+#[derive(Args)]
+pub struct IvrArgs {
+    #[command(subcommand)]
+    pub command: IvrCommands,
+}
+
+#[derive(Subcommand)]
+pub enum IvrCommands {
+    /// Generate telephony-ready prompts from a YAML file, plus a manifest.json
+    Build(IvrBuildArgs),
+}
+
+/// IVR prompt pack arguments
+///
+/// The prompts file has a `prompts` map of id to text, or a list of
+/// `{id, text, voice}` entries when some prompts need a different voice.
+#[derive(Args)]
+pub struct IvrBuildArgs {
+    /// YAML (or JSON) file describing the prompts
+    #[arg(value_name = "PROMPTS")]
+    pub prompts: String,
+
+    /// Voice name or ID (default: the file's `voice`, then Brian)
+    #[arg(long)]
+    pub voice: Option<String>,
+
+    /// Model to use (default: the file's `model`, then eleven_multilingual_v2)
+    #[arg(short, long)]
+    pub model: Option<String>,
+
+    /// Directory for the prompt files and manifest.json
+    #[arg(long, value_name = "DIR", default_value = "ivr")]
+    pub out_dir: String,
+
+    /// Audio format: pcm_8000 and pcm_16000 are written as WAV, ulaw_8000 and
+    /// alaw_8000 as raw G.711 (Asterisk .ulaw/.alaw)
+    #[arg(long, default_value = "ulaw_8000",
+          value_parser = ["pcm_8000", "pcm_16000", "ulaw_8000", "alaw_8000"])]
+    pub format: String,
+
+    /// Regenerate prompts that already have a file in --out-dir
+    #[arg(long)]
+    pub force: bool,
+}
@@ -0,0 +1,79 @@
+//! Remote job journal CLI arguments
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+/// Kinds of asynchronous remote jobs the CLI tracks
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobKind {
+    /// Dubbing projects (`dub create`)
+    Dubbing,
+    /// Professional voice clone training (`voice fine-tune start`)
+    Pvc,
+    /// Project conversions (`projects convert`)
+    Project,
+    /// Batch calls (`agent batch-create`)
+    BatchCall,
+    /// RAG index builds (`rag create`, `rag rebuild`)
+    RagIndex,
+}
+
+/// `--wait` for commands that start a remote job
+#[derive(Args, Clone, Debug)]
+pub struct WaitArgs {
+    /// Wait for the job to finish; fails when the job fails
+    #[arg(long)]
+    pub wait: bool,
+
+    /// Seconds between status checks with --wait
+    #[arg(long, default_value = "10", value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..), requires = "wait")]
+    pub interval: u64,
+
+    /// Give up waiting after this many seconds
+    #[arg(long, value_name = "SECS", requires = "wait")]
+    pub timeout: Option<u64>,
+}
+
+/// Remote job arguments
+#[derive(Args)]
+pub struct JobsArgs {
+    #[command(subcommand)]
+    pub command: JobsCommands,
+}
+
+#[derive(Subcommand)]
+pub enum JobsCommands {
+    /// List jobs started by this CLI, refreshing the ones still running
+    List {
+        /// Only jobs that are still running
+        #[arg(long)]
+        active: bool,
+
+        /// Only jobs of this kind
+        #[arg(long, value_enum)]
+        kind: Option<JobKind>,
+
+        /// Show the last known states without asking the API
+        #[arg(long)]
+        no_refresh: bool,
+    },
+    /// Show the current state of a job
+    Status {
+        /// Job ID
+        job_id: String,
+    },
+    /// Wait for a job to finish; fails when the job fails
+    Wait {
+        /// Job ID
+        job_id: String,
+
+        /// Seconds between status checks
+        #[arg(long, default_value = "10", value_name = "SECS")]
+        interval: u64,
+
+        /// Give up after this many seconds
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+    },
+}
@@ -1,5 +1,6 @@
 //! Knowledge base CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// Knowledge base arguments
@@ -21,19 +22,43 @@ pub enum KnowledgeCommands {
         #[arg(short, long)]
         offset: Option<u32>,
     },
-    /// Add document from URL
+    /// Add document from URL, or one document per page found by crawling it
     AddFromUrl {
         /// URL to fetch document from
         #[arg(short, long)]
         url: String,
 
-        /// Document name
+        /// Document name (prefixed to the page path when adding several pages)
         #[arg(short, long)]
         name: String,
 
         /// Description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// Follow same-site links this many levels deep from the URL
+        #[arg(long, value_name = "DEPTH", default_value = "0")]
+        crawl_depth: u32,
+
+        /// Add the pages listed in the site's sitemap (the URL itself when it ends in .xml)
+        #[arg(long, conflicts_with = "crawl_depth")]
+        sitemap: bool,
+
+        /// Only add pages whose path matches, e.g. '/docs/*' (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        include: Vec<String>,
+
+        /// Skip pages whose path matches, e.g. '*/changelog*' (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+
+        /// Stop after this many pages
+        #[arg(long, value_name = "N", default_value = "50")]
+        max_pages: usize,
+
+        /// List the pages that would be added without adding them
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Add document from text
     AddFromText {
@@ -59,6 +84,30 @@ pub enum KnowledgeCommands {
         #[arg(short, long)]
         name: Option<String>,
     },
+    /// Replace a document's content from a file and relink the agents using it
+    ///
+    /// The API cannot change a document's content, so the file is uploaded as
+    /// a new document, every agent referencing the old one is pointed at it,
+    /// and the old document is deleted. The document ID changes.
+    Update {
+        /// Document ID
+        document_id: String,
+
+        /// File with the new content
+        #[arg(long, value_name = "FILE")]
+        file: String,
+
+        /// Document name (defaults to the current name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Don't rebuild the RAG index of the new document
+        #[arg(long, conflicts_with = "wait")]
+        no_rebuild: bool,
+
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
     /// Get document details
     Get {
         /// Document ID
@@ -73,6 +73,34 @@ pub enum VoiceLibraryCommands {
         #[arg(short, long)]
         name: String,
     },
+    /// Share one of your voices in the library, or update its listing
+    ///
+    /// Professional clones and designed voices can be shared. Run it again on
+    /// a shared voice to change the listing; unchanged settings are kept.
+    Publish {
+        /// ID of your voice
+        voice_id: String,
+
+        /// Library category (default: from the voice)
+        #[arg(short, long, value_parser = ["professional", "high_quality", "generated"])]
+        category: Option<String>,
+
+        /// Library labels (comma-separated): accent, gender, age, language, use_case, descriptive
+        #[arg(short, long, value_name = "KEY=VALUE", value_delimiter = ',')]
+        labels: Vec<String>,
+
+        /// Days users keep access to the voice after you stop sharing it
+        #[arg(long, value_name = "DAYS")]
+        notice_period: Option<u32>,
+
+        /// Name shown in the library (default: the voice's name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Description shown in the library
+        #[arg(short, long)]
+        description: Option<String>,
+    },
     /// List voice collections
     Collections {
         /// Page size
@@ -0,0 +1,11 @@
+//! Rate limit CLI arguments
+
+use clap::Args;
+
+/// Rate limit status arguments
+#[derive(Args)]
+pub struct LimitsArgs {
+    /// Clear the locally recorded throttling history
+    #[arg(long)]
+    pub reset: bool,
+}
@@ -0,0 +1,28 @@
+//! MCP server CLI arguments
+
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum McpCommands {
+    /// Inspect the log of tool calls made through the MCP server
+    Audit {
+        #[command(subcommand)]
+        command: McpAuditCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum McpAuditCommands {
+    /// Show the most recent tool calls
+    Tail {
+        /// Number of calls to show
+        #[arg(short = 'n', long, default_value = "20")]
+        lines: usize,
+        /// Only calls to this tool
+        #[arg(long)]
+        tool: Option<String>,
+        /// Only failed calls
+        #[arg(long)]
+        failed: bool,
+    },
+}
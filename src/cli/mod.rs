@@ -1,16 +1,25 @@
 //! CLI argument definitions organized by domain
 
 mod agent;
+mod api;
 mod args;
 mod audio;
+mod backup;
+mod completions;
 mod config;
 mod conversation;
+mod daemon;
 mod design;
 mod dialogue;
 mod dubbing;
 mod history;
+mod ivr;
+mod jobs;
 mod knowledge;
 mod library;
+mod limits;
+#[cfg(feature = "mcp")]
+mod mcp;
 mod models;
 mod music;
 mod native;
@@ -19,7 +28,12 @@ mod projects;
 mod pronunciation;
 mod rag;
 mod samples;
+mod schedule;
+mod search;
+#[cfg(feature = "serve")]
+mod serve;
 mod stt;
+mod support;
 mod tools;
 mod tts;
 mod update;
@@ -31,16 +45,25 @@ mod workspace;
 
 // Re-export all types at module level
 pub use agent::*;
+pub use api::*;
 pub use args::*;
 pub use audio::*;
+pub use backup::*;
+pub use completions::*;
 pub use config::*;
 pub use conversation::*;
+pub use daemon::*;
 pub use design::*;
 pub use dialogue::*;
 pub use dubbing::*;
 pub use history::*;
+pub use ivr::*;
+pub use jobs::*;
 pub use knowledge::*;
 pub use library::*;
+pub use limits::*;
+#[cfg(feature = "mcp")]
+pub use mcp::*;
 pub use models::*;
 pub use music::*;
 pub use native::*;
@@ -49,7 +72,12 @@ pub use projects::*;
 pub use pronunciation::*;
 pub use rag::*;
 pub use samples::*;
+pub use schedule::*;
+pub use search::*;
+#[cfg(feature = "serve")]
+pub use serve::*;
 pub use stt::*;
+pub use support::*;
 pub use tools::*;
 pub use tts::*;
 pub use update::*;
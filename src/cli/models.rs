@@ -15,4 +15,23 @@ pub enum ModelsCommands {
     List,
     /// Get model pricing/rates
     Rates,
+    /// Estimate credits and cost of synthesizing text with a model
+    Cost {
+        /// Model ID
+        #[arg(short, long)]
+        model: String,
+
+        /// Number of characters to synthesize
+        #[arg(short, long, required_unless_present = "file", conflicts_with = "file")]
+        characters: Option<u64>,
+
+        /// Count the characters of a text file, or `-` for stdin
+        #[arg(short = 'i', long, value_name = "FILE")]
+        file: Option<String>,
+
+        /// Price against one plan instead of all of them (monthly list prices
+        /// from https://elevenlabs.io/pricing, as of 2025)
+        #[arg(long, value_parser = ["free", "starter", "creator", "pro", "scale", "business"])]
+        tier: Option<String>,
+    },
 }
@@ -34,6 +34,18 @@ pub enum MusicCommands {
         /// Page size
         #[arg(short, long)]
         limit: Option<u32>,
+
+        /// Only show tracks whose title or prompt contains this text
+        #[arg(short, long)]
+        search: Option<String>,
+
+        /// Only show tracks with this tag (repeatable; all must match)
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Only show local favorites
+        #[arg(long)]
+        favorites: bool,
     },
     /// Get music details
     Get {
@@ -54,4 +66,26 @@ pub enum MusicCommands {
         /// Music ID
         music_id: String,
     },
+    /// Mark a track as a local favorite
+    Favorite {
+        /// Music ID
+        music_id: String,
+
+        /// Remove the track from favorites instead
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Add or remove local tags on a track
+    Tag {
+        /// Music ID
+        music_id: String,
+
+        /// Tag to add (repeatable)
+        #[arg(long, value_name = "TAG")]
+        add: Vec<String>,
+
+        /// Tag to remove (repeatable)
+        #[arg(long, value_name = "TAG")]
+        remove: Vec<String>,
+    },
 }
@@ -9,6 +9,8 @@ pub struct AudioNativeArgs {
     pub command: AudioNativeCommands,
 }
 
+// Parsed once per run, so the size of `Create` does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum AudioNativeCommands {
     /// List all audio native projects
@@ -56,6 +58,10 @@ pub enum AudioNativeCommands {
         #[arg(long)]
         file: Option<String>,
 
+        /// Web page whose main article text is converted (title and author default from the page)
+        #[arg(long, value_name = "URL", conflicts_with = "file")]
+        url: Option<String>,
+
         /// Use small player
         #[arg(long)]
         small: bool,
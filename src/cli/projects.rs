@@ -1,5 +1,6 @@
 //! Projects CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// Projects API arguments
@@ -17,10 +18,18 @@ pub enum ProjectsCommands {
         #[arg(short, long)]
         limit: Option<u32>,
     },
-    /// Get project details
+    /// Get project details with per-chapter conversion status
     Get {
         /// Project ID
         project_id: String,
+
+        /// Follow conversion progress until every chapter is done
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between checks with --watch
+        #[arg(long, default_value = "5", value_name = "SECS")]
+        interval: u64,
     },
     /// Delete a project
     Delete {
@@ -31,6 +40,9 @@ pub enum ProjectsCommands {
     Convert {
         /// Project ID
         project_id: String,
+
+        #[command(flatten)]
+        wait: WaitArgs,
     },
     /// Get project snapshots
     Snapshots {
@@ -64,4 +64,16 @@ pub enum PronunciationCommands {
         #[arg(short, long)]
         output: String,
     },
+    /// Diff a dictionary's remote PLS against a local PLS file
+    Diff {
+        /// Dictionary ID
+        dictionary_id: String,
+
+        /// Local PLS file to compare against
+        local_file: String,
+
+        /// Exit with status 1 when differences are found (useful in CI)
+        #[arg(long)]
+        exit_code: bool,
+    },
 }
@@ -1,5 +1,6 @@
 //! RAG CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// RAG Index arguments
@@ -20,6 +21,9 @@ pub enum RagCommands {
         /// Embedding model
         #[arg(short, long, default_value = "e5_mistil_7b_instruct")]
         model: String,
+
+        #[command(flatten)]
+        wait: WaitArgs,
     },
     /// Get RAG index status (requires rag_index_id)
     Status {
@@ -46,6 +50,9 @@ pub enum RagCommands {
         /// Document ID
         #[arg(short, long)]
         document_id: String,
+
+        #[command(flatten)]
+        wait: WaitArgs,
     },
     /// Check RAG index status for a document
     IndexStatus {
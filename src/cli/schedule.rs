@@ -0,0 +1,38 @@
+//! Scheduler CLI arguments
+
+use clap::{Args, Subcommand};
+
+/// Recurring command arguments
+#[derive(Args)]
+pub struct ScheduleArgs {
+    #[command(subcommand)]
+    pub command: ScheduleCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleCommands {
+    /// Run a CLI command on a cron schedule
+    Add {
+        /// Cron expression in UTC, e.g. "0 9 * * *" or @daily
+        #[arg(long, value_name = "EXPR")]
+        cron: String,
+
+        /// CLI command to run, e.g. "usage alert --threshold 80"
+        #[arg(long, value_name = "COMMAND")]
+        cmd: String,
+
+        /// Name shown in `schedule list`
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List scheduled commands with their next and last runs
+    List,
+    /// Remove a scheduled command
+    #[command(alias = "rm")]
+    Remove {
+        /// Schedule ID
+        id: String,
+    },
+    /// Run scheduled commands as they come due (stop with Ctrl-C)
+    Run,
+}
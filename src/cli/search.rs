@@ -0,0 +1,31 @@
+//! Local search CLI arguments
+
+use clap::Args;
+
+/// Kinds of items kept in the local search index
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum SearchKind {
+    Voice,
+    History,
+    Agent,
+}
+
+/// Local search arguments
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Text to look for (case-insensitive; every word must match)
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+
+    /// Only search these kinds (comma-separated)
+    #[arg(long = "type", value_enum, value_delimiter = ',')]
+    pub kinds: Vec<SearchKind>,
+
+    /// Rebuild the local index from the API before searching
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Maximum number of results
+    #[arg(short, long, default_value = "20")]
+    pub limit: usize,
+}
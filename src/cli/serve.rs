@@ -0,0 +1,35 @@
+//! HTTP API server CLI arguments
+
+use clap::Args;
+
+/// Local REST API server arguments
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to bind
+    #[arg(long, default_value = "127.0.0.1", value_name = "HOST")]
+    pub host: String,
+
+    /// Port to listen on
+    #[arg(short, long, default_value = "3123", value_name = "PORT")]
+    pub port: u16,
+
+    /// Require `Authorization: Bearer <TOKEN>` on every request (needed unless --no-auth)
+    #[arg(long, env = "ELEVENLABS_SERVE_TOKEN", value_name = "TOKEN")]
+    pub token: Option<String>,
+
+    /// Serve without a token; any local process can then spend your credits
+    #[arg(long, conflicts_with = "token")]
+    pub no_auth: bool,
+
+    /// Maximum requests per minute across all clients
+    #[arg(long, default_value = "60", value_name = "INT")]
+    pub rate: u32,
+
+    /// Number of generated responses kept in the in-memory cache (0 disables caching)
+    #[arg(long, default_value = "100", value_name = "INT")]
+    pub cache_size: usize,
+
+    /// Seconds a cached response stays valid
+    #[arg(long, default_value = "3600", value_name = "SECS")]
+    pub cache_ttl: u64,
+}
@@ -1,10 +1,14 @@
 //! Speech-to-Text CLI arguments
 
-use clap::Args;
+use clap::{Args, Subcommand};
 
 /// Speech-to-Text arguments
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct SpeechToTextArgs {
+    #[command(subcommand)]
+    pub command: Option<SttCommands>,
+
     /// Audio file to transcribe
     #[arg(value_name = "FILE")]
     pub file: Option<String>,
@@ -13,6 +17,16 @@ pub struct SpeechToTextArgs {
     #[arg(long)]
     pub record: bool,
 
+    /// Transcribe long files in chunks, writing each chunk's transcript as it
+    /// completes (requires ffmpeg; JSON is written as one object per chunk)
+    #[arg(long, conflicts_with = "record")]
+    pub stream: bool,
+
+    /// Chunk length in seconds for --stream
+    #[arg(long, value_name = "SECONDS", default_value = "60", requires = "stream",
+          value_parser = clap::value_parser!(u32).range(10..=3600))]
+    pub chunk_duration: u32,
+
     /// Recording duration in seconds (use with --record)
     #[arg(long, default_value = "5")]
     pub duration: f32,
@@ -34,6 +48,23 @@ pub struct SpeechToTextArgs {
     #[arg(short, long, value_name = "CODE")]
     pub language: Option<String>,
 
+    /// Languages the recording may contain (e.g. en,de,fr); a detection
+    /// outside the list is retried with each of them and the most confident
+    /// transcript kept
+    #[arg(
+        long,
+        value_name = "CODES",
+        value_delimiter = ',',
+        conflicts_with = "language"
+    )]
+    pub languages: Vec<String>,
+
+    /// Write each chunk to a file for its detected language (out.txt becomes
+    /// out.en.txt, out.de.txt, ...); use a short --chunk-duration for
+    /// recordings that switch language often
+    #[arg(long, requires_all = ["stream", "output"])]
+    pub split_by_language: bool,
+
     /// Tag audio events
     #[arg(long, default_value = "true")]
     pub tag_audio_events: bool,
@@ -57,4 +88,57 @@ pub struct SpeechToTextArgs {
     /// Output file (default: stdout)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<String>,
+
+    /// File of terms to bias recognition towards, one per line (# for comments)
+    #[arg(long, value_name = "FILE")]
+    pub keywords: Option<String>,
+
+    /// Term to bias recognition towards (repeatable)
+    #[arg(long = "keyword", value_name = "TERM")]
+    pub keyword: Vec<String>,
+
+    /// Confidence (0-1) below which words are flagged for review
+    #[arg(long, value_name = "FLOAT", default_value = "0.85")]
+    pub min_confidence: f64,
+
+    /// Write segments below --min-confidence to a JSON report
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum SttCommands {
+    /// Transcribe the microphone live, one utterance at a time
+    Live(SttLiveArgs),
+}
+
+/// Live transcription arguments
+///
+/// Audio is split into utterances on silence and each one is transcribed as
+/// soon as it ends; Ctrl+C stops.
+#[derive(Args)]
+pub struct SttLiveArgs {
+    /// Input audio device name (see `stt --list-input-devices`)
+    #[arg(long, value_name = "DEVICE")]
+    pub device: Option<String>,
+
+    /// Level (0.0-1.0) below which audio counts as silence; raise it in noisy rooms
+    #[arg(long, value_name = "0.0-1.0", default_value = "0.02")]
+    pub vad_threshold: f32,
+
+    /// Seconds of silence that end an utterance
+    #[arg(long, value_name = "SECONDS", default_value = "0.8")]
+    pub silence_duration: f32,
+
+    /// Language code (auto-detected if not specified)
+    #[arg(short, long, value_name = "CODE")]
+    pub language: Option<String>,
+
+    /// Model to use
+    #[arg(short, long, default_value = "scribe_v1")]
+    pub model: String,
+
+    /// Also append each utterance's text to this file
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
 }
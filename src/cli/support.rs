@@ -0,0 +1,11 @@
+//! Support bundle CLI arguments
+
+use clap::Args;
+
+/// Support bundle arguments
+#[derive(Args)]
+pub struct SupportBundleArgs {
+    /// Output zip path (default: elevenlabs-support-<timestamp>.zip)
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: Option<String>,
+}
@@ -62,4 +62,22 @@ pub enum ToolsCommands {
         /// Tool ID
         tool_id: String,
     },
+    /// Create server (webhook) tools from the operations of an OpenAPI spec
+    ImportOpenapi {
+        /// OpenAPI 3 spec file (YAML or JSON)
+        #[arg(value_name = "SPEC")]
+        spec: String,
+
+        /// Only import operations whose path matches, e.g. '/v1/orders*' (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        filter: Vec<String>,
+
+        /// Base URL for the tools (default: the spec's first server)
+        #[arg(long, value_name = "URL")]
+        base_url: Option<String>,
+
+        /// Print the generated tool definitions without creating them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
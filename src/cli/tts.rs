@@ -1,18 +1,85 @@
 //! Text-to-Speech CLI arguments
 
-use clap::Args;
+use clap::{Args, Subcommand};
+
+/// Delivery presets mapped to eleven_v3 audio tags
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Delivery {
+    /// Energetic, upbeat delivery
+    Excited,
+    /// Quiet, whispered delivery
+    Whisper,
+    /// Subdued, sorrowful delivery
+    Sad,
+    /// Calm, even storytelling delivery
+    Narration,
+}
+
+/// Preprocessing rules applied to input text before synthesis
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum CleanRule {
+    /// Markdown formatting; fenced code blocks are dropped
+    Markdown,
+    /// HTML tags and entities
+    Html,
+    /// Web links (http://, https://, www.)
+    Urls,
+    /// Emoji and pictographs
+    Emojis,
+    /// Turn off rules set with `config set clean_input`
+    None,
+}
+
+impl std::str::FromStr for CleanRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "urls" => Ok(Self::Urls),
+            "emojis" => Ok(Self::Emojis),
+            "none" => Ok(Self::None),
+            _ => Err(anyhow::anyhow!(
+                "Invalid clean_input rule '{}'. Valid values are: markdown, html, urls, emojis, none",
+                s
+            )),
+        }
+    }
+}
+
+/// Parse a comma-separated rule list such as "markdown,urls"
+pub fn parse_clean_rules(value: &str) -> anyhow::Result<Vec<CleanRule>> {
+    value
+        .split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
 
 /// Text-to-Speech arguments
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct TextToSpeechArgs {
-    /// Text to convert to speech (or use --file)
+    #[command(subcommand)]
+    pub command: Option<TtsCommands>,
+
+    /// Text to convert to speech (or use --file / --url)
     #[arg(value_name = "TEXT")]
     pub text: Option<String>,
 
-    /// Read text from file
+    /// Read text from file, or `-` for stdin (`@Speaker: text` lines produce multi-voice narration)
     #[arg(short = 'i', long, value_name = "FILE")]
     pub file: Option<String>,
 
+    /// Fetch a web page and read its main article text
+    #[arg(long, value_name = "URL", conflicts_with_all = ["text", "file"])]
+    pub url: Option<String>,
+
+    /// Clean input text before synthesis: markdown, html, urls, emojis, none (comma-separated; default: config clean_input)
+    #[arg(long, value_enum, value_name = "RULES", value_delimiter = ',')]
+    pub clean_input: Vec<CleanRule>,
+
     /// Voice ID to use (default: Brian)
     #[arg(long, default_value = "Brian")]
     pub voice: String,
@@ -21,10 +88,27 @@ pub struct TextToSpeechArgs {
     #[arg(short, long, default_value = "eleven_multilingual_v2")]
     pub model: String,
 
+    /// Voices to try in order when the voice is not found (comma-separated or repeated)
+    #[arg(long, value_name = "VOICE", value_delimiter = ',')]
+    pub fallback_voice: Vec<String>,
+
+    /// Models to try in order when the model keeps failing (comma-separated or repeated)
+    #[arg(long, value_name = "MODEL", value_delimiter = ',')]
+    pub fallback_model: Vec<String>,
+
+    /// Casting file mapping `@Character:` markers to voices, settings and pronunciations
+    #[arg(long, value_name = "FILE")]
+    pub casting: Option<String>,
+
     /// Output file path
     #[arg(short, long, value_name = "OUTPUT")]
     pub output: Option<String>,
 
+    /// Also write these formats next to the output (comma-separated); PCM, WAV,
+    /// μ-law and A-law are derived locally, MP3 and Opus are requested again
+    #[arg(long, value_name = "FORMATS", value_delimiter = ',')]
+    pub also_format: Vec<String>,
+
     /// Play audio after generation
     #[arg(long)]
     pub play: bool,
@@ -50,10 +134,14 @@ pub struct TextToSpeechArgs {
     #[arg(long, value_name = "0.0-1.0")]
     pub style: Option<f32>,
 
-    /// Use speaker boost
-    #[arg(long)]
+    /// Use speaker boost, overriding the voice's stored setting
+    #[arg(long, overrides_with = "no_speaker_boost")]
     pub speaker_boost: bool,
 
+    /// Turn speaker boost off, overriding the voice's stored setting
+    #[arg(long, overrides_with = "speaker_boost")]
+    pub no_speaker_boost: bool,
+
     /// Language code (e.g., en, es, fr)
     #[arg(long, value_name = "CODE")]
     pub language: Option<String>,
@@ -61,6 +149,119 @@ pub struct TextToSpeechArgs {
     /// Seed for deterministic generation
     #[arg(long, value_name = "INT")]
     pub seed: Option<u32>,
+
+    /// Embed ID3 tags (title, voice, model) into the MP3 output
+    #[arg(long)]
+    pub tag: bool,
+
+    /// Album/project name to embed when tagging
+    #[arg(long, value_name = "NAME", requires = "tag")]
+    pub album: Option<String>,
+
+    /// Insert a pause after every sentence (e.g. 400ms, 1.5s; max 3s)
+    #[arg(long, value_name = "DURATION")]
+    pub sentence_pause: Option<String>,
+
+    /// Pronounce a word with a phoneme: word=/ipa/ or word=ARPABET (repeatable)
+    #[arg(long, value_name = "WORD=RENDITION")]
+    pub phoneme: Vec<String>,
+
+    /// Expressive delivery preset, applied as audio tags (eleven_v3 only)
+    #[arg(long, value_enum)]
+    pub delivery: Option<Delivery>,
+
+    /// Project to file this generation under in the local ledger (see `usage projects`)
+    #[arg(long, value_name = "ID")]
+    pub project_id: Option<String>,
+
+    /// Metadata to record with the generation (repeatable)
+    #[arg(long = "meta", value_name = "KEY=VALUE")]
+    pub meta: Vec<String>,
+}
+
+impl TextToSpeechArgs {
+    /// Speaker boost from `--speaker-boost`/`--no-speaker-boost`, if either
+    /// was given
+    pub fn speaker_boost(&self) -> Option<bool> {
+        if self.speaker_boost {
+            Some(true)
+        } else if self.no_speaker_boost {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum TtsCommands {
+    /// Narrate the newest items of an RSS or Atom feed into tagged audio files
+    Feed(TtsFeedArgs),
+    /// Speak lines from the terminal or stdin over one websocket connection
+    Session(TtsSessionArgs),
+}
+
+/// Feed narration arguments
+#[derive(Args)]
+pub struct TtsFeedArgs {
+    /// RSS or Atom feed URL
+    #[arg(value_name = "FEED_URL")]
+    pub url: String,
+
+    /// Number of newest items to narrate
+    #[arg(long, default_value = "3")]
+    pub latest: usize,
+
+    /// Directory for episode files
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    pub out_dir: String,
+
+    /// Voice name or ID
+    #[arg(long, default_value = "Brian")]
+    pub voice: String,
+
+    /// Model to use
+    #[arg(short, long, default_value = "eleven_multilingual_v2")]
+    pub model: String,
+
+    /// Read the feed's summaries instead of fetching full articles
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Regenerate episodes that already have a file in --out-dir
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Interactive TTS session arguments
+///
+/// Each line read from stdin is spoken as its own utterance; `/quit` or
+/// end of input ends the session.
+#[derive(Args)]
+pub struct TtsSessionArgs {
+    /// Voice name or ID
+    #[arg(long, default_value = "Brian")]
+    pub voice: String,
+
+    /// Model to use (Flash gives the lowest latency)
+    #[arg(short, long, default_value = "eleven_flash_v2_5")]
+    pub model: String,
+
+    /// Stability (0.0-1.0)
+    #[arg(long, value_name = "0.0-1.0")]
+    pub stability: Option<f32>,
+
+    /// Similarity boost (0.0-1.0)
+    #[arg(long, value_name = "0.0-1.0")]
+    pub similarity_boost: Option<f32>,
+
+    /// Save each utterance to this directory as 001.mp3, 002.mp3, ...
+    #[arg(long, value_name = "DIR")]
+    pub out_dir: Option<String>,
+
+    /// Do not play utterances (requires --out-dir)
+    #[arg(long, requires = "out_dir")]
+    pub no_play: bool,
 }
 
 /// TTS with Timestamps arguments
@@ -70,10 +271,14 @@ pub struct TtsTimestampsArgs {
     #[arg(value_name = "TEXT")]
     pub text: Option<String>,
 
-    /// Read text from file
+    /// Read text from file, or `-` for stdin
     #[arg(short = 'i', long, value_name = "FILE")]
     pub file: Option<String>,
 
+    /// Clean input text before synthesis: markdown, html, urls, emojis, none (comma-separated; default: config clean_input)
+    #[arg(long, value_enum, value_name = "RULES", value_delimiter = ',')]
+    pub clean_input: Vec<CleanRule>,
+
     /// Voice ID to use
     #[arg(long, default_value = "Brian")]
     pub voice: String,
@@ -126,6 +331,16 @@ pub struct TtsStreamArgs {
     #[arg(long)]
     pub play: bool,
 
+    /// Write raw audio to stdout as it arrives, with no other output (e.g. `| ffplay -`).
+    /// pcm_* formats are headerless PCM
+    #[arg(long, conflicts_with_all = ["output", "play"])]
+    pub stdout: bool,
+
+    /// Send audio as it arrives to fifo:PATH, udp:HOST:PORT or rtp:HOST:PORT.
+    /// UDP and RTP send 20 ms packets in real time and need a raw format (pcm_*, ulaw_8000, alaw_8000)
+    #[arg(long, value_name = "SINK", conflicts_with_all = ["output", "play", "stdout"])]
+    pub sink: Option<String>,
+
     /// Output audio device name
     /// Use --list-output-devices to see available devices
     #[arg(long, value_name = "DEVICE")]
@@ -135,10 +350,25 @@ pub struct TtsStreamArgs {
     #[arg(long)]
     pub list_output_devices: bool,
 
-    /// Latency optimization (0-4)
-    #[arg(long, value_name = "0-4")]
+    /// Latency optimization: 0 (best quality) to 4 (fastest, disables text normalization)
+    #[arg(
+        long = "optimize-streaming-latency",
+        visible_alias = "latency",
+        value_name = "0-4",
+        value_parser = clap::value_parser!(u8).range(0..=4)
+    )]
     pub latency: Option<u8>,
 
+    /// Characters buffered before each audio chunk, e.g. 50,90,120 (lower is
+    /// faster); streams over the WebSocket API, which is the only one that takes it
+    #[arg(
+        long,
+        value_name = "N,N,...",
+        value_delimiter = ',',
+        conflicts_with = "sink"
+    )]
+    pub chunk_schedule: Vec<u32>,
+
     /// Output format
     #[arg(long, default_value = "mp3_44100_128")]
     pub output_format: String,
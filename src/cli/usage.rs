@@ -25,4 +25,20 @@ pub enum UsageCommands {
         #[arg(short, long)]
         breakdown: Option<String>,
     },
+    /// Characters generated per `tts --project-id`, from the local ledger
+    Projects {
+        /// Only count generations since a date (YYYY-MM-DD) or a relative time (7d, 12h)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// List the generations of one project instead of the totals
+        #[arg(long, value_name = "ID")]
+        project_id: Option<String>,
+    },
+    /// Exit with an error when character usage reaches a share of the plan limit
+    Alert {
+        /// Percentage of the character limit that triggers the alert
+        #[arg(long, default_value = "80", value_name = "PERCENT")]
+        threshold: f64,
+    },
 }
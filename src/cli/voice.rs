@@ -1,5 +1,6 @@
 //! Voice management CLI arguments
 
+use super::jobs::WaitArgs;
 use clap::{Args, Subcommand};
 
 /// Voice management arguments
@@ -108,6 +109,113 @@ pub enum VoiceCommands {
         #[arg(long)]
         text: Option<String>,
     },
+    /// Edit voice labels
+    Label {
+        #[command(subcommand)]
+        command: VoiceLabelCommands,
+    },
+    /// Verify a cloned voice by reading its captcha text aloud
+    Verify {
+        /// Voice ID
+        voice_id: String,
+
+        /// Submit an existing recording instead of using the microphone
+        #[arg(long, value_name = "FILE")]
+        recording: Option<String>,
+
+        /// Seconds to record from the microphone
+        #[arg(long, default_value = "10", value_name = "SECONDS")]
+        duration: f32,
+    },
+    /// Compare a generated clip with a reference recording (runs locally)
+    Score {
+        /// Recording of the original speaker
+        #[arg(long, value_name = "FILE")]
+        reference: String,
+
+        /// Generated audio to compare against it
+        #[arg(long, value_name = "FILE")]
+        candidate: String,
+    },
+    /// Settings applied automatically whenever tts or dialogue uses a voice
+    Defaults {
+        #[command(subcommand)]
+        command: VoiceDefaultsCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VoiceDefaultsCommands {
+    /// Store settings for a voice (merged with any already stored)
+    Set {
+        /// Voice name or ID, as passed to --voice
+        voice: String,
+
+        /// Stability (0-1)
+        #[arg(long, value_name = "FLOAT")]
+        stability: Option<f32>,
+
+        /// Similarity boost (0-1)
+        #[arg(long, value_name = "FLOAT")]
+        similarity_boost: Option<f32>,
+
+        /// Style (0-1)
+        #[arg(long, value_name = "FLOAT")]
+        style: Option<f32>,
+
+        /// Use speaker boost
+        #[arg(long, value_name = "BOOL")]
+        speaker_boost: Option<bool>,
+    },
+    /// List voices with stored settings
+    List,
+    /// Forget the settings stored for a voice
+    #[command(alias = "remove")]
+    Rm {
+        /// Voice name or ID
+        voice: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VoiceLabelCommands {
+    /// Add or change labels on a voice
+    Set {
+        /// Voice ID
+        voice_id: String,
+
+        /// Labels to set
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        labels: Vec<String>,
+    },
+    /// Remove labels from a voice
+    #[command(alias = "remove")]
+    Rm {
+        /// Voice ID
+        voice_id: String,
+
+        /// Label keys to remove
+        #[arg(required = true, value_name = "KEY")]
+        keys: Vec<String>,
+    },
+    /// Change labels on every voice matching a filter
+    Bulk {
+        /// Match voices by category or label (repeatable; all must match)
+        #[arg(long = "filter", required = true, value_name = "KEY=VALUE")]
+        filters: Vec<String>,
+
+        /// Labels to set on matching voices (repeatable)
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+
+        /// Label keys to remove from matching voices (repeatable)
+        #[arg(long = "rm", value_name = "KEY")]
+        remove: Vec<String>,
+
+        /// Show the changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -124,6 +232,9 @@ pub enum FineTuneCommands {
         /// Description of the fine-tuned voice
         #[arg(short, long)]
         description: Option<String>,
+
+        #[command(flatten)]
+        wait: WaitArgs,
     },
     /// Check fine-tuning status
     Status {
@@ -1,5 +1,6 @@
 //! Workspace CLI arguments
 
+use super::agent::PrivacySettingsArgs;
 use clap::{Args, Subcommand};
 
 /// Workspace arguments
@@ -52,6 +53,11 @@ pub enum WorkspaceCommands {
         /// Secret name
         name: String,
     },
+    /// Inspect, update, or find references to a workspace secret
+    Secret {
+        #[command(subcommand)]
+        command: SecretCommands,
+    },
     /// Share a workspace resource
     Share {
         /// Resource type (agent, knowledge_base, etc.)
@@ -68,4 +74,53 @@ pub enum WorkspaceCommands {
         /// Resource ID to unshare
         resource_id: String,
     },
+    /// Export a workspace-wide audit report (members, keys, secrets, agents, webhooks, usage)
+    Audit {
+        /// Time window for usage data (e.g. 30d, 12h, 2w)
+        #[arg(long, default_value = "30d")]
+        since: String,
+
+        /// Output file (.csv or .json); prints a summary table if omitted
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<String>,
+    },
+    /// Show or change privacy settings across every agent in the workspace
+    Privacy {
+        #[command(subcommand)]
+        command: WorkspacePrivacyCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WorkspacePrivacyCommands {
+    /// Show the privacy settings of every agent
+    Get,
+    /// Apply privacy settings to every agent
+    Set {
+        #[command(flatten)]
+        settings: PrivacySettingsArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SecretCommands {
+    /// Show secret metadata (the value is never displayed)
+    Get {
+        /// Secret name
+        name: String,
+    },
+    /// Update a secret value (read from stdin unless --value-file is given)
+    Update {
+        /// Secret name
+        name: String,
+
+        /// Read the new value from a file instead of stdin
+        #[arg(long, value_name = "FILE")]
+        value_file: Option<String>,
+    },
+    /// List agents and tools referencing a secret
+    WhereUsed {
+        /// Secret name
+        name: String,
+    },
 }
@@ -1,8 +1,60 @@
 //! Unified API client for ElevenLabs
 
+use crate::output::print_warning;
 use crate::utils::DEFAULT_TIMEOUT_SECS;
-use reqwest::Client;
+use elevenlabs_rs::ElevenLabsClient;
+use reqwest::{Client, Response};
+use std::sync::{Once, OnceLock};
 use std::time::Duration;
+use tokio_tungstenite::tungstenite::handshake::client::Response as WsResponse;
+
+/// Production API endpoint
+const DEFAULT_API_BASE: &str = "https://api.elevenlabs.io";
+
+/// Environment variable overriding the API endpoint (proxies, mock servers in tests)
+pub const API_BASE_ENV: &str = "ELEVENLABS_API_BASE_URL";
+
+/// Environment variable overriding the websocket endpoint (defaults to the API base)
+pub const WS_BASE_ENV: &str = "ELEVENLABS_WS_BASE_URL";
+
+/// Response headers that identify a request to ElevenLabs support
+const REQUEST_ID_HEADERS: &[&str] = &["request-id", "x-request-id"];
+const HISTORY_ITEM_ID_HEADER: &str = "history-item-id";
+
+/// Base URL for REST requests, without a trailing slash
+pub fn api_base() -> &'static str {
+    static BASE: OnceLock<String> = OnceLock::new();
+    BASE.get_or_init(|| {
+        std::env::var(API_BASE_ENV)
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_API_BASE.to_string())
+    })
+}
+
+/// Base URL for websocket connections: the API base with a `ws`/`wss` scheme
+pub fn ws_base() -> &'static str {
+    static BASE: OnceLock<String> = OnceLock::new();
+    BASE.get_or_init(|| {
+        std::env::var(WS_BASE_ENV)
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| websocket_url(api_base()))
+    })
+}
+
+/// Swap an HTTP(S) URL to the matching websocket scheme
+fn websocket_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
 
 /// Create an HTTP client with proper timeout configuration
 pub fn create_http_client() -> Client {
@@ -12,3 +64,110 @@ pub fn create_http_client() -> Client {
         .build()
         .unwrap_or_else(|_| Client::new())
 }
+
+/// Client for requests made through the elevenlabs_rs SDK
+///
+/// The SDK builds and sends its own requests, so `--header`/`--query` can't
+/// be added to them; the first SDK client of a run says so.
+pub fn create_sdk_client(api_key: &str) -> ElevenLabsClient {
+    static WARNED: Once = Once::new();
+    if super::passthrough::has_request_extras() {
+        WARNED.call_once(|| {
+            print_warning(
+                "--header and --query are not applied to this command's SDK requests; \
+                 use 'elevenlabs-cli api' to send a request with them",
+            )
+        });
+    }
+    ElevenLabsClient::new(api_key)
+}
+
+/// Turn a failed API response into an error, keeping the IDs support asks for
+pub async fn api_error(response: Response) -> anyhow::Error {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let request_id = REQUEST_ID_HEADERS.iter().find_map(|name| header(name));
+    let history_item_id = header(HISTORY_ITEM_ID_HEADER);
+    let body = response.text().await.unwrap_or_default();
+
+    anyhow::anyhow!(
+        "{}",
+        format_api_error(&body, request_id.as_deref(), history_item_id.as_deref())
+    )
+}
+
+/// The request ID of a websocket handshake, for errors reported later on
+/// the connection
+pub fn ws_request_id(response: &WsResponse) -> Option<String> {
+    REQUEST_ID_HEADERS.iter().find_map(|name| {
+        response
+            .headers()
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    })
+}
+
+/// Turn a rejected websocket handshake into an error, like [`api_error`]
+pub fn ws_handshake_error(response: &WsResponse) -> anyhow::Error {
+    let body = response
+        .body()
+        .as_deref()
+        .map(String::from_utf8_lossy)
+        .unwrap_or_default();
+    ws_api_error(&body, ws_request_id(response).as_deref())
+}
+
+/// Turn an error message sent over a websocket into an error, with the
+/// request ID of the connection's handshake
+pub fn ws_api_error(message: &str, request_id: Option<&str>) -> anyhow::Error {
+    anyhow::anyhow!("{}", format_api_error(message, request_id, None))
+}
+
+/// Format an API error body with any request identifiers appended
+fn format_api_error(body: &str, request_id: Option<&str>, history_item_id: Option<&str>) -> String {
+    let mut ids = Vec::new();
+    if let Some(id) = request_id {
+        ids.push(format!("request-id: {}", id));
+    }
+    if let Some(id) = history_item_id {
+        ids.push(format!("history-item-id: {}", id));
+    }
+
+    if ids.is_empty() {
+        format!("API error: {}", body)
+    } else {
+        format!("API error: {} ({})", body, ids.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_api_error() {
+        assert_eq!(format_api_error("boom", None, None), "API error: boom");
+        assert_eq!(
+            format_api_error("boom", Some("req_1"), Some("hist_2")),
+            "API error: boom (request-id: req_1, history-item-id: hist_2)"
+        );
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        assert_eq!(
+            websocket_url("https://api.elevenlabs.io"),
+            "wss://api.elevenlabs.io"
+        );
+        assert_eq!(
+            websocket_url("http://127.0.0.1:8080"),
+            "ws://127.0.0.1:8080"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Rate-limit tracking and adaptive concurrency
+//!
+//! ElevenLabs reports concurrency on API responses
+//! (`current-concurrent-requests`, `maximum-concurrent-requests`) and answers
+//! 429 when a limit is exceeded. Observations are kept in the local data
+//! directory so `limits` can show them and batch commands can start at a
+//! concurrency the account actually allows.
+
+use crate::utils::{unix_now, with_file_lock, write_atomic};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+const CURRENT_CONCURRENCY_HEADER: &str = "current-concurrent-requests";
+const MAX_CONCURRENCY_HEADER: &str = "maximum-concurrent-requests";
+const RETRY_AFTER_HEADER: &str = "retry-after";
+
+/// Set once this process has logged a 429, so it is only counted once
+static THROTTLE_RECORDED: AtomicBool = AtomicBool::new(false);
+
+/// Rate-limit observations persisted between runs
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitLog {
+    /// Concurrency limit last reported by the API
+    #[serde(default)]
+    pub max_concurrent: Option<u32>,
+    /// Highest concurrency the API reported in use
+    #[serde(default)]
+    pub peak_concurrent: Option<u32>,
+    /// When concurrency headers were last seen (Unix seconds)
+    #[serde(default)]
+    pub last_seen: Option<u64>,
+    /// Number of 429 responses seen
+    #[serde(default)]
+    pub throttled: u64,
+    /// When the last 429 was seen (Unix seconds)
+    #[serde(default)]
+    pub last_throttled: Option<u64>,
+    /// `retry-after` of the last 429, in seconds
+    #[serde(default)]
+    pub last_retry_after: Option<u64>,
+}
+
+/// Location of the rate-limit log
+fn rate_limit_log_path() -> Option<PathBuf> {
+    crate::paths::data_file("rate-limits.json")
+}
+
+/// Read the rate-limit log (empty when missing or unreadable)
+pub fn load_rate_limits() -> RateLimitLog {
+    rate_limit_log_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Delete the rate-limit log
+pub fn reset_rate_limits() -> std::io::Result<()> {
+    match rate_limit_log_path() {
+        Some(path) if path.exists() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// Update the log under its lock, re-reading it first so concurrent runs
+/// merge their observations; bookkeeping failures never fail the command
+fn update_rate_limits(apply: impl FnOnce(&mut RateLimitLog)) {
+    let Some(path) = rate_limit_log_path() else {
+        return;
+    };
+    let _ = with_file_lock(&path, || {
+        let mut log = load_rate_limits();
+        let before = log.clone();
+        apply(&mut log);
+        if log != before {
+            write_atomic(&path, serde_json::to_string_pretty(&log)?.as_bytes())?;
+        }
+        Ok(())
+    });
+}
+
+/// Record the concurrency headers and any 429 of an API response
+pub(super) fn record_response(response: &Response) {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let current = header(CURRENT_CONCURRENCY_HEADER);
+    let max = header(MAX_CONCURRENCY_HEADER);
+    let throttled = response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    if current.is_none() && max.is_none() && !throttled {
+        return;
+    }
+    if throttled {
+        THROTTLE_RECORDED.store(true, Ordering::Relaxed);
+    }
+    let retry_after = header(RETRY_AFTER_HEADER);
+
+    update_rate_limits(|log| {
+        let now = unix_now();
+        if max.is_some() || current.is_some() {
+            // Only refresh the timestamp occasionally to avoid a write per request
+            if log
+                .last_seen
+                .is_none_or(|seen| now.saturating_sub(seen) >= 60)
+            {
+                log.last_seen = Some(now);
+            }
+        }
+        if let Some(max) = max {
+            log.max_concurrent = Some(max as u32);
+        }
+        if let Some(current) = current {
+            log.peak_concurrent = Some(log.peak_concurrent.unwrap_or(0).max(current as u32));
+        }
+        if throttled {
+            log.throttled += 1;
+            log.last_throttled = Some(now);
+            log.last_retry_after = retry_after;
+        }
+    });
+}
+
+/// Record a 429 that surfaced as an error outside the raw HTTP paths (SDK calls)
+pub fn record_throttle() {
+    if THROTTLE_RECORDED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    update_rate_limits(|log| {
+        log.throttled += 1;
+        log.last_throttled = Some(unix_now());
+        log.last_retry_after = None;
+    });
+}
+
+/// Starting concurrency for a batch: the requested value, capped by the
+/// limit the API last reported
+pub fn initial_concurrency(requested: usize) -> usize {
+    match load_rate_limits().max_concurrent {
+        Some(max) if max > 0 => requested.min(max as usize).max(1),
+        _ => requested.max(1),
+    }
+}
+
+#[derive(Debug)]
+struct Tuning {
+    /// Current number of tasks allowed at once
+    limit: usize,
+    /// Upper bound (the requested concurrency)
+    max: usize,
+    /// Successes since the last change
+    successes: usize,
+    /// Permits to retire as tasks finish after a reduction
+    to_forget: usize,
+}
+
+/// Concurrency limit that halves on 429 responses and grows back by one
+/// after a full round of successful tasks (additive increase, multiplicative
+/// decrease)
+#[derive(Clone)]
+pub struct AdaptiveConcurrency {
+    semaphore: Arc<Semaphore>,
+    tuning: Arc<Mutex<Tuning>>,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(initial: usize, max: usize) -> Self {
+        let max = max.max(1);
+        let initial = initial.clamp(1, max);
+        Self {
+            semaphore: Arc::new(Semaphore::new(initial)),
+            tuning: Arc::new(Mutex::new(Tuning {
+                limit: initial,
+                max,
+                successes: 0,
+                to_forget: 0,
+            })),
+        }
+    }
+
+    /// Wait for a free slot
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed")
+    }
+
+    /// Return a slot after a task finished; returns the new limit when it changed
+    pub fn release(&self, permit: OwnedSemaphorePermit, throttled: bool) -> Option<usize> {
+        let Ok(mut tuning) = self.tuning.lock() else {
+            return None;
+        };
+        let before = tuning.limit;
+
+        if throttled {
+            let reduced = (tuning.limit / 2).max(1);
+            tuning.to_forget += tuning.limit - reduced;
+            tuning.limit = reduced;
+            tuning.successes = 0;
+        } else {
+            tuning.successes += 1;
+            if tuning.successes >= tuning.limit && tuning.limit < tuning.max {
+                tuning.successes = 0;
+                tuning.limit += 1;
+                if tuning.to_forget > 0 {
+                    tuning.to_forget -= 1;
+                } else {
+                    self.semaphore.add_permits(1);
+                }
+            }
+        }
+
+        if tuning.to_forget > 0 {
+            tuning.to_forget -= 1;
+            permit.forget();
+        } else {
+            drop(permit);
+        }
+        (tuning.limit != before).then_some(tuning.limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_halves_and_recovers() {
+        let limiter = AdaptiveConcurrency::new(4, 4);
+        let permits: Vec<_> = acquire_all(&limiter, 4).await;
+        assert_eq!(limiter.semaphore.available_permits(), 0);
+
+        let mut permits = permits.into_iter();
+        assert_eq!(limiter.release(permits.next().unwrap(), true), Some(2));
+        // Two slots are retired as the remaining tasks finish
+        assert_eq!(limiter.release(permits.next().unwrap(), false), None);
+        assert_eq!(limiter.release(permits.next().unwrap(), false), Some(3));
+        assert_eq!(limiter.release(permits.next().unwrap(), false), None);
+        assert_eq!(limiter.semaphore.available_permits(), 3);
+        assert_eq!(limiter.tuning.lock().unwrap().limit, 3);
+
+        // Never below one slot
+        let limiter = AdaptiveConcurrency::new(1, 8);
+        let permit = limiter.acquire().await;
+        assert_eq!(limiter.release(permit, true), None);
+        assert_eq!(limiter.semaphore.available_permits(), 1);
+    }
+
+    async fn acquire_all(limiter: &AdaptiveConcurrency, n: usize) -> Vec<OwnedSemaphorePermit> {
+        let mut permits = Vec::new();
+        for _ in 0..n {
+            permits.push(limiter.acquire().await);
+        }
+        permits
+    }
+}
@@ -1,6 +1,24 @@
 //! API client abstractions
 
 mod api;
+mod limits;
+mod passthrough;
 mod retry;
+mod schema;
 
-pub use api::create_http_client;
+pub use api::{
+    api_base, api_error, create_http_client, create_sdk_client, ws_api_error, ws_base,
+    ws_handshake_error, ws_request_id,
+};
+pub use limits::{
+    initial_concurrency, load_rate_limits, record_throttle, reset_rate_limits, AdaptiveConcurrency,
+    RateLimitLog,
+};
+#[cfg(feature = "mcp")]
+pub use passthrough::has_request_extras;
+pub use passthrough::{parse_header, parse_query, set_request_extras, RequestExtras};
+pub use retry::{
+    retry_sdk, set_retry_policy, RetryPolicy, SendWithRetry, DEFAULT_MAX_RETRIES,
+    DEFAULT_RETRY_DELAY_SECS,
+};
+pub use schema::{read_json, set_warn_unknown_fields};
@@ -0,0 +1,154 @@
+//! `--header` and `--query` passthrough
+//!
+//! Extra headers and query parameters given on the command line are added to
+//! every HTTP request the CLI sends to the API through [`super::SendWithRetry`]
+//! (which the `api` command uses too), so endpoints or options the CLI
+//! doesn't know about yet can still be reached. Requests to other hosts are
+//! left alone, and a `--header` replaces a header of the same name, API key
+//! included. Requests made through the elevenlabs_rs SDK are built inside the
+//! SDK and can't carry the extras; [`super::create_sdk_client`] warns about
+//! that instead of dropping them silently.
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Request;
+use std::sync::OnceLock;
+
+static EXTRAS: OnceLock<RequestExtras> = OnceLock::new();
+
+/// Headers and query parameters added to API requests
+#[derive(Debug, Default, Clone)]
+pub struct RequestExtras {
+    pub headers: HeaderMap,
+    pub query: Vec<(String, String)>,
+}
+
+impl RequestExtras {
+    pub fn new(headers: &[(String, String)], query: &[(String, String)]) -> anyhow::Result<Self> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        Ok(Self {
+            headers: map,
+            query: query.to_vec(),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.query.is_empty()
+    }
+
+    /// `request` with the extras added if it is bound for `api_base`
+    fn apply(&self, mut request: Request, api_base: &str) -> Request {
+        if self.is_empty() || !request.url().as_str().starts_with(api_base) {
+            return request;
+        }
+        if !self.query.is_empty() {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .extend_pairs(&self.query);
+        }
+        for name in self.headers.keys() {
+            request.headers_mut().remove(name);
+        }
+        for (name, value) in &self.headers {
+            request.headers_mut().append(name, value.clone());
+        }
+        request
+    }
+}
+
+/// Set the extras for this process (first call wins)
+pub fn set_request_extras(extras: RequestExtras) {
+    let _ = EXTRAS.set(extras);
+}
+
+/// Whether `--header` or `--query` was given
+pub fn has_request_extras() -> bool {
+    EXTRAS.get().is_some_and(|extras| !extras.is_empty())
+}
+
+/// `request` with the process's extras added
+pub(super) fn with_extras(request: Request) -> Request {
+    match EXTRAS.get() {
+        Some(extras) => extras.apply(request, super::api_base()),
+        None => request,
+    }
+}
+
+/// Parse `NAME:VALUE` for `--header`
+pub fn parse_header(arg: &str) -> Result<(String, String), String> {
+    let (name, value) = arg
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid header '{}' (expected NAME:VALUE)", arg))?;
+    let (name, value) = (name.trim(), value.trim());
+    HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| format!("Invalid header name '{}'", name))?;
+    HeaderValue::from_str(value).map_err(|_| format!("Invalid value for header '{}'", name))?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Parse `KEY=VALUE` for `--query`
+pub fn parse_query(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!(
+            "Invalid query parameter '{}' (expected KEY=VALUE)",
+            arg
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header_and_query() {
+        assert_eq!(
+            parse_header("X-Trace: abc:1").unwrap(),
+            ("X-Trace".to_string(), "abc:1".to_string())
+        );
+        assert!(parse_header("no-colon").is_err());
+        assert!(parse_header("bad name:1").is_err());
+        assert_eq!(
+            parse_query("page_size=5").unwrap(),
+            ("page_size".to_string(), "5".to_string())
+        );
+        assert_eq!(
+            parse_query("filter=a=b").unwrap(),
+            ("filter".to_string(), "a=b".to_string())
+        );
+        assert!(parse_query("=5").is_err());
+        assert!(parse_query("flag").is_err());
+    }
+
+    #[test]
+    fn test_extras_only_reach_the_api() {
+        let extras = RequestExtras::new(
+            &[("xi-api-key".to_string(), "other".to_string())],
+            &[("page_size".to_string(), "5".to_string())],
+        )
+        .unwrap();
+        let client = reqwest::Client::new();
+        let request = client
+            .get("https://api.example.com/v1/voices?show_legacy=true")
+            .header("xi-api-key", "main")
+            .build()
+            .unwrap();
+        let request = extras.apply(request, "https://api.example.com");
+        assert_eq!(request.url().query(), Some("show_legacy=true&page_size=5"));
+        assert_eq!(request.headers()["xi-api-key"], "other");
+
+        let elsewhere = client.get("https://github.com/releases").build().unwrap();
+        let elsewhere = extras.apply(elsewhere, "https://api.example.com");
+        assert_eq!(elsewhere.url().query(), None);
+        assert!(elsewhere.headers().get("xi-api-key").is_none());
+    }
+}
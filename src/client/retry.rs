@@ -1,3 +1,346 @@
-//! Retry logic for API calls
+//! Automatic retries for rate-limited and failing requests
 //!
-//! Re-exports from the main api module to maintain backwards compatibility
+//! Connections that could not be made and 429 responses (which the API
+//! sends before doing any work) are retried for every request; 408 and 5xx
+//! responses only for idempotent methods, because a POST that failed on the
+//! server may still have been processed and billed. Retries
+//! use jittered exponential backoff: retry `n` waits a random time between
+//! half and all of `retry_delay * 2^n`, unless the response carries a
+//! `Retry-After`, which is honored instead. `--max-retries` and
+//! `--retry-delay` set the policy for the whole process. Raw requests go
+//! through [`SendWithRetry`] (which also adds the `--header`/`--query`
+//! passthrough) and SDK calls through [`retry_sdk`]. Bodies that cannot be
+//! replayed (multipart uploads, streams) are sent once.
+
+use crate::output::is_quiet_mode;
+use anyhow::Result;
+use colored::*;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Retries per request unless `--max-retries` says otherwise
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// First backoff unless `--retry-delay` says otherwise
+pub const DEFAULT_RETRY_DELAY_SECS: f64 = 1.0;
+
+/// Longest backoff between two attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Longest `Retry-After` honored
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+static POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+
+/// How failed requests are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: Duration::from_secs_f64(DEFAULT_RETRY_DELAY_SECS),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, retry_delay_secs: f64) -> Result<Self> {
+        if !retry_delay_secs.is_finite() || retry_delay_secs < 0.0 {
+            return Err(anyhow::anyhow!(
+                "--retry-delay must be a number of seconds (0 or more)"
+            ));
+        }
+        Ok(Self {
+            max_retries,
+            base_delay: Duration::from_secs_f64(retry_delay_secs).min(MAX_BACKOFF),
+        })
+    }
+
+    /// Wait before retry `attempt` (0 for the first); `jitter` in `0..=1`
+    /// picks a point between half and all of the exponential delay
+    fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let full = self
+            .base_delay
+            .saturating_mul(1 << attempt.min(16))
+            .min(MAX_BACKOFF);
+        full.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+/// Set the policy for this process (first call wins)
+pub fn set_retry_policy(policy: RetryPolicy) {
+    let _ = POLICY.set(policy);
+}
+
+/// The policy set by `--max-retries`/`--retry-delay`, or the defaults
+pub fn retry_policy() -> RetryPolicy {
+    POLICY.get().copied().unwrap_or_default()
+}
+
+/// A random number in `0..=1`, from the standard library's per-hasher keys
+fn jitter() -> f64 {
+    let random = std::collections::hash_map::RandomState::new().hash_one(0u8);
+    random as f64 / u64::MAX as f64
+}
+
+/// Whether a response with `status` can be sent again: 429 always, 408 and
+/// 5xx only when the method is idempotent
+fn is_retryable_status(status: StatusCode, idempotent: bool) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (idempotent && matches!(status.as_u16(), 408 | 500 | 502 | 503 | 504))
+}
+
+/// `Retry-After` in seconds (the API does not send HTTP dates)
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| Duration::from_secs_f64(secs).min(MAX_RETRY_AFTER))
+}
+
+fn print_retry(reason: &str, delay: Duration, attempt: u32, max_retries: u32) {
+    if is_quiet_mode() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "{}; retrying in {:.1}s ({}/{})",
+            reason,
+            delay.as_secs_f64(),
+            attempt + 1,
+            max_retries
+        )
+        .yellow()
+    );
+}
+
+/// `send()` with the process retry policy
+pub trait SendWithRetry {
+    fn send_with_retry(self) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl SendWithRetry for RequestBuilder {
+    fn send_with_retry(self) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        send(self, retry_policy())
+    }
+}
+
+async fn send(request: RequestBuilder, policy: RetryPolicy) -> reqwest::Result<Response> {
+    let (client, request) = request.build_split();
+    let request = super::passthrough::with_extras(request?);
+    let idempotent = request.method().is_idempotent();
+    let mut attempt = 0;
+    loop {
+        // The last attempt (and any body that can't be replayed) uses the original
+        let Some(copy) = (attempt < policy.max_retries)
+            .then(|| request.try_clone())
+            .flatten()
+        else {
+            return record(client.execute(request).await);
+        };
+        let delay = match record(client.execute(copy).await) {
+            Ok(response) if is_retryable_status(response.status(), idempotent) => {
+                let delay =
+                    retry_after(&response).unwrap_or_else(|| policy.backoff(attempt, jitter()));
+                print_retry(
+                    &format!("{} from {}", response.status(), response.url().path()),
+                    delay,
+                    attempt,
+                    policy.max_retries,
+                );
+                delay
+            }
+            // Only failures to connect: a timed-out request may have been processed
+            Err(e) if e.is_connect() => {
+                let delay = policy.backoff(attempt, jitter());
+                print_retry("Could not connect", delay, attempt, policy.max_retries);
+                delay
+            }
+            result => return result,
+        };
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Note the rate-limit headers of every response the API sends
+fn record(result: reqwest::Result<Response>) -> reqwest::Result<Response> {
+    if let Ok(response) = &result {
+        super::limits::record_response(response);
+    }
+    result
+}
+
+/// SDK error statuses the API sends with 429 responses
+const RATE_LIMITED_STATUSES: &[&str] = &["too_many_concurrent_requests", "system_busy"];
+
+/// Whether a failed SDK call can be retried: a connection that was never
+/// made or a 429 always, a 408/5xx only when the method is idempotent
+fn is_transient(error: &(dyn std::error::Error + 'static), idempotent: bool) -> bool {
+    use elevenlabs_rs::error::Error as SdkError;
+
+    // The SDK's reqwest is a different version from ours; its error type is
+    // named through the variant that wraps it
+    fn downcast<'a, T: std::error::Error + 'static>(
+        _: fn(T) -> SdkError,
+        error: &'a (dyn std::error::Error + 'static),
+    ) -> Option<&'a T> {
+        error.downcast_ref::<T>()
+    }
+    let request_error = downcast(SdkError::ReqwestError, error).or_else(|| {
+        match error.downcast_ref::<SdkError>() {
+            Some(SdkError::ReqwestError(e)) => Some(e),
+            _ => None,
+        }
+    });
+    if let Some(e) = request_error {
+        let status = e
+            .status()
+            .and_then(|s| StatusCode::from_u16(s.as_u16()).ok());
+        return e.is_connect() || status.is_some_and(|s| is_retryable_status(s, idempotent));
+    }
+    // The SDK keeps only the body of a failed response
+    match error.downcast_ref::<SdkError>() {
+        Some(SdkError::HttpError(body)) => body["detail"]["status"]
+            .as_str()
+            .is_some_and(|status| RATE_LIMITED_STATUSES.contains(&status)),
+        _ => false,
+    }
+}
+
+/// Run an SDK call under the process retry policy; `call` builds a fresh
+/// request for each attempt and `method` is the endpoint's HTTP method
+pub async fn retry_sdk<T, F, Fut>(method: Method, call: F) -> elevenlabs_rs::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = elevenlabs_rs::Result<T>>,
+{
+    retry_sdk_with(retry_policy(), method, call).await
+}
+
+async fn retry_sdk_with<T, F, Fut>(
+    policy: RetryPolicy,
+    method: Method,
+    mut call: F,
+) -> elevenlabs_rs::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = elevenlabs_rs::Result<T>>,
+{
+    let idempotent = method.is_idempotent();
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(e) if attempt < policy.max_retries && is_transient(e.as_ref(), idempotent) => {
+                let delay = policy.backoff(attempt, jitter());
+                print_retry(&e.to_string(), delay, attempt, policy.max_retries);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_within_jitter_bounds() {
+        let policy = RetryPolicy::new(5, 1.0).unwrap();
+        assert_eq!(policy.backoff(0, 0.0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(0, 1.0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2, 1.0), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3, 0.5), Duration::from_secs(6));
+        assert_eq!(policy.backoff(30, 1.0), MAX_BACKOFF);
+        for _ in 0..100 {
+            let j = jitter();
+            assert!((0.0..=1.0).contains(&j));
+        }
+        assert_eq!(
+            RetryPolicy::new(0, 0.0).unwrap().backoff(3, 1.0),
+            Duration::ZERO
+        );
+        assert!(RetryPolicy::new(3, -1.0).is_err());
+        assert!(RetryPolicy::new(3, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        let status = |code| StatusCode::from_u16(code).unwrap();
+        for code in [408, 429, 500, 502, 503, 504] {
+            assert!(is_retryable_status(status(code), true));
+        }
+        for code in [200, 400, 401, 404, 422, 501] {
+            assert!(!is_retryable_status(status(code), true));
+        }
+        // Only a 429 is known to have been rejected before any work was done
+        assert!(is_retryable_status(status(429), false));
+        for code in [408, 500, 502, 503, 504] {
+            assert!(!is_retryable_status(status(code), false));
+        }
+    }
+
+    fn http_error(status: &str) -> elevenlabs_rs::error::Error {
+        elevenlabs_rs::error::Error::HttpError(serde_json::json!({
+            "detail": { "status": status, "message": "..." }
+        }))
+    }
+
+    #[test]
+    fn test_is_transient_is_typed_and_method_aware() {
+        let busy = http_error("too_many_concurrent_requests");
+        assert!(is_transient(&busy, true));
+        assert!(is_transient(&busy, false));
+        assert!(!is_transient(&http_error("voice_not_found"), true));
+        // Message text alone is never enough
+        let message: Box<dyn std::error::Error + Send + Sync> =
+            "503 Service Unavailable: connection timeout".into();
+        assert!(!is_transient(message.as_ref(), true));
+    }
+
+    #[tokio::test]
+    async fn test_retry_sdk_stops_after_max_retries() {
+        let policy = RetryPolicy::new(2, 0.0).unwrap();
+        let mut calls = 0;
+        let result: elevenlabs_rs::Result<()> = retry_sdk_with(policy, Method::GET, || {
+            calls += 1;
+            async { Err(http_error("system_busy").into()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        // A rate-limited POST was rejected before any work, so it is sent again
+        calls = 0;
+        let result: elevenlabs_rs::Result<()> = retry_sdk_with(policy, Method::POST, || {
+            calls += 1;
+            async { Err(http_error("system_busy").into()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        calls = 0;
+        let result: elevenlabs_rs::Result<()> = retry_sdk_with(policy, Method::GET, || {
+            calls += 1;
+            async { Err(http_error("invalid_voice_settings").into()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,299 @@
+//! Response parsing with schema drift reporting
+//!
+//! The API gains fields faster than the CLI's response structs do, and serde
+//! drops whatever a struct doesn't declare. Raw responses are parsed through
+//! [`read_json`], which with `--warn-unknown-fields` deserializes via a
+//! wrapper that collects every object key the target type doesn't know,
+//! nested ones included (`voices[].sharing.new_flag`), and warns once per
+//! endpoint and field. Types that take any JSON (`Value`, maps, flattened
+//! fields) accept everything, so they never report. Calls made through the
+//! SDK are not covered.
+
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Response;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static WARN_UNKNOWN_FIELDS: AtomicBool = AtomicBool::new(false);
+
+/// Endpoint/field pairs already warned about in this process
+static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Turn unknown field warnings on or off for this process
+pub fn set_warn_unknown_fields(enabled: bool) {
+    WARN_UNKNOWN_FIELDS.store(enabled, Ordering::SeqCst);
+}
+
+fn warn_unknown_fields() -> bool {
+    WARN_UNKNOWN_FIELDS.load(Ordering::SeqCst)
+}
+
+/// Parse a JSON response body, reporting fields `T` doesn't declare when
+/// `--warn-unknown-fields` is set
+pub async fn read_json<T: DeserializeOwned>(response: Response) -> Result<T> {
+    if !warn_unknown_fields() {
+        return response.json().await.context("Failed to parse response");
+    }
+    let endpoint = response.url().path().to_string();
+    let body: Value = response.json().await.context("Failed to parse response")?;
+    let (value, unknown) = from_value_tracked(&body).context("Failed to parse response")?;
+    report_unknown(&endpoint, &unknown);
+    Ok(value)
+}
+
+/// Deserialize `T` from `value`, also returning the paths of object keys
+/// that `T` ignored
+pub fn from_value_tracked<T: DeserializeOwned>(
+    value: &Value,
+) -> serde_json::Result<(T, Vec<String>)> {
+    let unknown = RefCell::new(Vec::new());
+    let parsed = T::deserialize(Tracked {
+        value,
+        path: String::new(),
+        unknown: &unknown,
+    })?;
+    let mut unknown = unknown.into_inner();
+    unknown.sort();
+    unknown.dedup();
+    Ok((parsed, unknown))
+}
+
+fn report_unknown(endpoint: &str, unknown: &[String]) {
+    let Ok(mut warned) = WARNED.lock() else {
+        return;
+    };
+    let warned = warned.get_or_insert_with(HashSet::new);
+    let fresh: Vec<&str> = unknown
+        .iter()
+        .filter(|field| warned.insert(format!("{} {}", endpoint, field)))
+        .map(String::as_str)
+        .collect();
+    if fresh.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "Unknown fields in {} response: {}",
+            endpoint,
+            fresh.join(", ")
+        )
+        .yellow()
+    );
+}
+
+/// A JSON value being deserialized, with the path it sits at
+struct Tracked<'a> {
+    value: &'a Value,
+    path: String,
+    unknown: &'a RefCell<Vec<String>>,
+}
+
+impl<'a> Tracked<'a> {
+    fn child_path(&self, segment: &str) -> String {
+        if self.path.is_empty() || segment.starts_with('[') {
+            format!("{}{}", self.path, segment)
+        } else {
+            format!("{}.{}", self.path, segment)
+        }
+    }
+
+    fn child(&self, value: &'a Value, segment: &str) -> Self {
+        Tracked {
+            value,
+            path: self.child_path(segment),
+            unknown: self.unknown,
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Object(map) => visitor.visit_map(TrackedMap {
+                parent: &self,
+                entries: map.iter(),
+                pending: None,
+            }),
+            Value::Array(items) => visitor.visit_seq(TrackedSeq {
+                parent: &self,
+                items: items.iter(),
+            }),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        if let Value::Object(map) = self.value {
+            let mut unknown = self.unknown.borrow_mut();
+            for key in map.keys().filter(|key| !fields.contains(&key.as_str())) {
+                unknown.push(self.child_path(key));
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_unit(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+struct TrackedMap<'p, 'a> {
+    parent: &'p Tracked<'a>,
+    entries: serde_json::map::Iter<'a>,
+    pending: Option<(&'a str, &'a Value)>,
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'_, 'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> serde_json::Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.pending = Some((key, value));
+                seed.deserialize(BorrowedStrDeserializer::<serde_json::Error>::new(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> serde_json::Result<V::Value> {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| serde::de::Error::custom("value requested before key"))?;
+        seed.deserialize(self.parent.child(value, key))
+    }
+}
+
+struct TrackedSeq<'p, 'a> {
+    parent: &'p Tracked<'a>,
+    items: std::slice::Iter<'a, Value>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'_, 'de> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> serde_json::Result<Option<T::Value>> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(self.parent.child(item, "[]")).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Voice {
+        voice_id: String,
+        #[serde(alias = "title")]
+        name: String,
+        #[serde(default)]
+        labels: Option<Labels>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Labels {
+        accent: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct VoicesResponse {
+        voices: Vec<Voice>,
+    }
+
+    #[test]
+    fn test_unknown_fields_are_collected_with_paths() {
+        let body = json!({
+            "voices": [
+                { "voice_id": "v1", "name": "Rachel", "labels": { "accent": "us", "age": "young" } },
+                { "voice_id": "v2", "title": "Adam", "labels": null, "is_new": true }
+            ],
+            "has_more": false
+        });
+        let (parsed, unknown) = from_value_tracked::<VoicesResponse>(&body).unwrap();
+        assert_eq!(parsed.voices[1].name, "Adam");
+        assert_eq!(
+            parsed.voices[0].labels,
+            Some(Labels {
+                accent: Some("us".to_string())
+            })
+        );
+        assert_eq!(
+            unknown,
+            ["has_more", "voices[].is_new", "voices[].labels.age"]
+        );
+    }
+
+    #[test]
+    fn test_open_types_report_nothing() {
+        let body = json!({ "anything": { "goes": [1, 2] } });
+        let (parsed, unknown) = from_value_tracked::<Value>(&body).unwrap();
+        assert_eq!(parsed, body);
+        assert!(unknown.is_empty());
+        let (_, unknown) =
+            from_value_tracked::<std::collections::HashMap<String, Value>>(&body).unwrap();
+        assert!(unknown.is_empty());
+    }
+}
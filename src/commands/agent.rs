@@ -1,19 +1,35 @@
-use crate::cli::{AgentArgs, AgentCommands, SpellingPatience};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::cli::{
+    AgentArgs, AgentBranchCommands, AgentCommands, JobKind, SpellingPatience, WaitArgs,
+};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::agent_languages::languages_command;
+use crate::commands::agent_privacy::agent_privacy;
+use crate::commands::agent_transfer::transfer_command;
+use crate::output::{is_json_mode, print_info, print_success, print_warning, symbol};
+use crate::utils::{
+    confirm_overwrite, dynamic_var_value, format_relative_time, parse_duration_spec,
+    parse_dynamic_vars,
+};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub async fn execute(args: AgentArgs, api_key: &str) -> Result<()> {
+/// Upper bound on conversation pages fetched for `agent overview`
+const OVERVIEW_MAX_PAGES: usize = 50;
+
+pub async fn execute(args: AgentArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     let client = create_http_client();
 
     match args.command {
         AgentCommands::List { limit } => list_agents(&client, api_key, limit).await,
         AgentCommands::Summaries { limit } => list_agent_summaries(&client, api_key, limit).await,
+        AgentCommands::Overview { since } => agent_overview(&client, api_key, &since).await,
         AgentCommands::Get { agent_id } => get_agent(&client, api_key, &agent_id).await,
         AgentCommands::Create {
             name,
@@ -49,6 +65,25 @@ pub async fn execute(args: AgentArgs, api_key: &str) -> Result<()> {
         }
         AgentCommands::Delete { agent_id } => delete_agent(&client, api_key, &agent_id).await,
         AgentCommands::Link { agent_id } => get_agent_link(&client, api_key, &agent_id).await,
+        AgentCommands::Export { agent_id, output } => {
+            export_agent(&client, api_key, &agent_id, output.as_deref(), assume_yes).await
+        }
+        AgentCommands::Import {
+            file,
+            agent_id,
+            name,
+            drop_missing,
+        } => {
+            import_agent(
+                &client,
+                api_key,
+                &file,
+                agent_id.as_deref(),
+                name.as_deref(),
+                drop_missing,
+            )
+            .await
+        }
         AgentCommands::Duplicate { agent_id, name } => {
             duplicate_agent(&client, api_key, &agent_id, &name).await
         }
@@ -60,18 +95,62 @@ pub async fn execute(args: AgentArgs, api_key: &str) -> Result<()> {
             branch_id,
             name,
         } => rename_agent_branch(&client, api_key, &agent_id, &branch_id, &name).await,
+        AgentCommands::Branch { command } => branch_command(&client, api_key, command).await,
         AgentCommands::BatchList { limit } => list_batch_calls(&client, api_key, limit).await,
         AgentCommands::BatchStatus { batch_id } => {
             get_batch_call_status(&client, api_key, &batch_id).await
         }
+        AgentCommands::BatchCreate {
+            agent_id,
+            phone_number_id,
+            recipients,
+            name,
+            dynamic_vars,
+            wait,
+        } => {
+            create_batch_call(
+                &client,
+                api_key,
+                &agent_id,
+                &phone_number_id,
+                &recipients,
+                name.as_deref(),
+                &dynamic_vars,
+                &wait,
+            )
+            .await
+        }
         AgentCommands::BatchDelete { batch_id } => {
             delete_batch_call(&client, api_key, &batch_id).await
         }
         AgentCommands::Simulate {
             agent_id,
             message,
+            script,
+            criteria,
             max_turns,
-        } => simulate_conversation(&client, api_key, &agent_id, &message, max_turns).await,
+            dynamic_vars,
+        } => {
+            let mut script = match (script, message) {
+                (Some(path), _) => load_simulation_script(Path::new(&path))?,
+                (None, Some(message)) => SimulationScript::from_message(message),
+                (None, None) => return Err(anyhow::anyhow!("Provide --message or --script")),
+            };
+            script
+                .dynamic_variables
+                .extend(parse_dynamic_vars(&dynamic_vars)?);
+            for criterion in &criteria {
+                script
+                    .evaluation_criteria
+                    .push(parse_evaluation_criterion(criterion)?);
+            }
+            simulate_conversation(&client, api_key, &agent_id, &script, max_turns).await
+        }
+        AgentCommands::Smoke {
+            agent_id,
+            message,
+            no_simulate,
+        } => smoke_test_agent(&client, api_key, &agent_id, &message, no_simulate).await,
         AgentCommands::UpdateTurn {
             agent_id,
             spelling_patience,
@@ -86,6 +165,9 @@ pub async fn execute(args: AgentArgs, api_key: &str) -> Result<()> {
             )
             .await
         }
+        AgentCommands::Transfer { command } => transfer_command(&client, api_key, command).await,
+        AgentCommands::Languages { command } => languages_command(&client, api_key, command).await,
+        AgentCommands::Privacy { command } => agent_privacy(&client, api_key, command).await,
         AgentCommands::WhatsappList => list_whatsapp_accounts(&client, api_key).await,
         AgentCommands::WidgetGet { agent_id } => {
             get_agent_widget(&client, api_key, &agent_id).await
@@ -97,7 +179,7 @@ pub async fn execute(args: AgentArgs, api_key: &str) -> Result<()> {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AgentSummary {
     agent_id: String,
     name: String,
@@ -105,7 +187,7 @@ struct AgentSummary {
     created_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AgentDetail {
     agent_id: String,
     name: String,
@@ -132,55 +214,314 @@ struct LinkResponse {
 async fn list_agents(client: &Client, api_key: &str, limit: Option<u32>) -> Result<()> {
     print_info("Fetching agents...");
 
-    let url = "https://api.elevenlabs.io/v1/agents";
+    let agents = fetch_agents(client, api_key, limit).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&agents)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Name", "Created"]);
+
+    for agent in &agents {
+        table.add_row(vec![
+            agent.agent_id.yellow(),
+            agent.name.cyan(),
+            agent.created_at.clone().unwrap_or_default().as_str().into(),
+        ]);
+    }
+
+    println!("{}", table);
+    println!("\nTotal agents: {}", agents.len().to_string().green());
+
+    Ok(())
+}
+
+async fn fetch_agents(
+    client: &Client,
+    api_key: &str,
+    limit: Option<u32>,
+) -> Result<Vec<AgentSummary>> {
+    let url = format!("{}/v1/agents", api_base());
     let mut request = client.get(url).header("xi-api-key", api_key);
 
     if let Some(lim) = limit {
         request = request.query(&[("limit", lim.to_string())]);
     }
 
-    let response = request.send().await.context("Failed to fetch agents")?;
+    let response = request
+        .send_with_retry()
+        .await
+        .context("Failed to fetch agents")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    read_json(response).await
+}
 
+/// The full configuration of one agent
+pub(crate) async fn fetch_agent(client: &Client, api_key: &str, agent_id: &str) -> Result<Value> {
+    let response = client
+        .get(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch agent")?;
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
+    read_json(response).await.context("Failed to parse agent")
+}
 
-    let agents: Vec<AgentSummary> = response.json().await.context("Failed to parse response")?;
+/// Every agent in the workspace, following `next_cursor` while `has_more`
+pub(crate) async fn fetch_all_agents(client: &Client, api_key: &str) -> Result<Vec<Value>> {
+    let mut agents = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = client
+            .get(format!("{}/v1/agents", api_base()))
+            .header("xi-api-key", api_key)
+            .query(&[("page_size", "100")]);
+        if let Some(c) = &cursor {
+            request = request.query(&[("cursor", c)]);
+        }
+        let response = request
+            .send_with_retry()
+            .await
+            .context("Failed to fetch agents")?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let page: Value = read_json(response).await?;
+        match &page {
+            Value::Array(items) => agents.extend(items.iter().cloned()),
+            _ => agents.extend(page["agents"].as_array().into_iter().flatten().cloned()),
+        }
+
+        cursor = page["next_cursor"].as_str().map(str::to_string);
+        if !page["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            return Ok(agents);
+        }
+    }
+}
+
+/// Per-agent conversation statistics for `agent overview`
+#[derive(Debug, Default, Clone, PartialEq)]
+struct AgentActivity {
+    conversations: u32,
+    failed: u32,
+    last_activity: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct AgentOverviewRow {
+    agent_id: String,
+    name: String,
+    conversations: u32,
+    failed: u32,
+    last_activity_unix: Option<u64>,
+    health: &'static str,
+}
+
+/// Classify an agent from its recent activity
+fn agent_health(activity: &AgentActivity) -> &'static str {
+    if activity.conversations == 0 {
+        "idle"
+    } else if activity.failed * 2 >= activity.conversations {
+        "failing"
+    } else {
+        "ok"
+    }
+}
+
+/// Aggregate conversation list items (raw JSON) into per-agent activity
+fn aggregate_agent_activity(conversations: &[Value]) -> HashMap<String, AgentActivity> {
+    let mut activity: HashMap<String, AgentActivity> = HashMap::new();
+    for conversation in conversations {
+        let Some(agent_id) = conversation.get("agent_id").and_then(Value::as_str) else {
+            continue;
+        };
+        let entry = activity.entry(agent_id.to_string()).or_default();
+        entry.conversations += 1;
+
+        let failed = conversation.get("call_successful").and_then(Value::as_str) == Some("failure")
+            || conversation.get("status").and_then(Value::as_str) == Some("failed");
+        if failed {
+            entry.failed += 1;
+        }
+
+        if let Some(started) = conversation
+            .get("start_time_unix_secs")
+            .and_then(Value::as_u64)
+        {
+            entry.last_activity = Some(entry.last_activity.map_or(started, |t| t.max(started)));
+        }
+    }
+    activity
+}
+
+/// Fetch all conversations started after `after_unix`, following pagination cursors
+async fn fetch_conversations_since(
+    client: &Client,
+    api_key: &str,
+    after_unix: u64,
+) -> Result<Vec<Value>> {
+    let mut conversations = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..OVERVIEW_MAX_PAGES {
+        let mut request = client
+            .get(format!("{}/v1/convai/conversations", api_base()))
+            .header("xi-api-key", api_key)
+            .query(&[
+                ("call_start_after_unix", after_unix.to_string()),
+                ("page_size", "100".to_string()),
+            ]);
+        if let Some(c) = &cursor {
+            request = request.query(&[("cursor", c)]);
+        }
+
+        let response = request
+            .send_with_retry()
+            .await
+            .context("Failed to fetch conversations")?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let page: Value = read_json(response).await?;
+        if let Some(items) = page.get("conversations").and_then(Value::as_array) {
+            conversations.extend(items.iter().cloned());
+        }
+
+        let has_more = page
+            .get("has_more")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        cursor = page
+            .get("next_cursor")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        if !has_more || cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(conversations)
+}
+
+async fn agent_overview(client: &Client, api_key: &str, since: &str) -> Result<()> {
+    let window = parse_duration_spec(since)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let after = now.saturating_sub(window.as_secs());
+
+    print_info(&format!(
+        "Building agent overview (conversations in the last {})...",
+        since.cyan()
+    ));
+
+    let agents = fetch_agents(client, api_key, None).await?;
+    let conversations = fetch_conversations_since(client, api_key, after).await?;
+    let activity = aggregate_agent_activity(&conversations);
+
+    let mut rows: Vec<AgentOverviewRow> = agents
+        .iter()
+        .map(|agent| {
+            let stats = activity.get(&agent.agent_id).cloned().unwrap_or_default();
+            AgentOverviewRow {
+                agent_id: agent.agent_id.clone(),
+                name: agent.name.clone(),
+                conversations: stats.conversations,
+                failed: stats.failed,
+                last_activity_unix: stats.last_activity,
+                health: agent_health(&stats),
+            }
+        })
+        .collect();
+    // Most recently active first; idle agents sink to the bottom
+    rows.sort_by_key(|r| std::cmp::Reverse(r.last_activity_unix));
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        print_info("No agents found");
+        return Ok(());
+    }
 
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Name", "Created"]);
+    table.set_header(vec![
+        "ID",
+        "Name",
+        &format!("Conversations ({})", since),
+        "Failed",
+        "Last Activity",
+        "Health",
+    ]);
 
-    for agent in &agents {
+    for row in &rows {
+        let last_activity = row
+            .last_activity_unix
+            .map(|t| format_relative_time(now.saturating_sub(t)))
+            .unwrap_or_else(|| "-".to_string());
+        let health = match row.health {
+            "ok" => row.health.green(),
+            "failing" => row.health.red(),
+            _ => row.health.yellow(),
+        };
         table.add_row(vec![
-            agent.agent_id.yellow(),
-            agent.name.cyan(),
-            agent.created_at.clone().unwrap_or_default().as_str().into(),
+            row.agent_id.yellow(),
+            row.name.cyan(),
+            row.conversations.to_string().into(),
+            row.failed.to_string().into(),
+            last_activity.into(),
+            health,
         ]);
     }
 
     println!("{}", table);
-    println!("\nTotal agents: {}", agents.len().to_string().green());
 
+    let idle = rows.iter().filter(|r| r.health == "idle").count();
+    let failing = rows.iter().filter(|r| r.health == "failing").count();
+    print_success(&format!(
+        "{} agent(s), {} conversation(s), {} idle, {} failing",
+        rows.len(),
+        conversations.len(),
+        idle,
+        failing
+    ));
     Ok(())
 }
 
 async fn get_agent(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
     print_info(&format!("Fetching agent '{}'...", agent_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}", agent_id);
+    let url = format!("{}/v1/agents/{}", api_base(), agent_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let agent: AgentDetail = response.json().await?;
+    let agent: AgentDetail = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&agent)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -243,20 +584,19 @@ async fn create_agent(
         body["conversation_config"] = conversation_config;
     }
 
-    let url = "https://api.elevenlabs.io/v1/agents";
+    let url = format!("{}/v1/agents", api_base());
     let response = client
         .post(url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateAgentResponse = response.json().await?;
+    let result: CreateAgentResponse = read_json(response).await?;
     print_success("Agent created successfully!");
     print_info(&format!("Agent ID: {}", result.agent_id.yellow()));
 
@@ -281,17 +621,16 @@ async fn update_agent(
         body["description"] = json!(d);
     }
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}", agent_id);
+    let url = format!("{}/v1/agents/{}", api_base(), agent_id);
     let response = client
         .patch(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success("Agent updated successfully!");
@@ -301,16 +640,15 @@ async fn update_agent(
 async fn delete_agent(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
     print_info(&format!("Deleting agent '{}'...", agent_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}", agent_id);
+    let url = format!("{}/v1/agents/{}", api_base(), agent_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Agent '{}' deleted successfully", agent_id));
@@ -320,19 +658,18 @@ async fn delete_agent(client: &Client, api_key: &str, agent_id: &str) -> Result<
 async fn get_agent_link(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
     print_info(&format!("Getting link for agent '{}'...", agent_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}/link", agent_id);
+    let url = format!("{}/v1/agents/{}/link", api_base(), agent_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let link: LinkResponse = response.json().await?;
+    let link: LinkResponse = read_json(response).await?;
     print_success(&format!("Agent link: {}", link.url.green()));
 
     Ok(())
@@ -345,31 +682,222 @@ async fn duplicate_agent(client: &Client, api_key: &str, agent_id: &str, name: &
         name.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}/duplicate", agent_id);
+    let url = format!("{}/v1/agents/{}/duplicate", api_base(), agent_id);
     let body = json!({ "name": name });
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateAgentResponse = response.json().await?;
+    let result: CreateAgentResponse = read_json(response).await?;
     print_success("Agent duplicated successfully!");
     print_info(&format!("New Agent ID: {}", result.agent_id.yellow()));
 
     Ok(())
 }
 
+/// Top-level agent fields that make up its portable configuration; IDs,
+/// access info and metadata belong to the workspace and are left out
+const EXPORT_FIELDS: &[&str] = &[
+    "name",
+    "tags",
+    "conversation_config",
+    "platform_settings",
+    "workflow",
+];
+
+fn export_document(agent: &Value) -> Value {
+    Value::Object(
+        EXPORT_FIELDS
+            .iter()
+            .filter_map(|field| {
+                let value = agent.get(*field).filter(|v| !v.is_null())?;
+                Some((field.to_string(), value.clone()))
+            })
+            .collect(),
+    )
+}
+
+async fn export_agent(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    output: Option<&str>,
+    assume_yes: bool,
+) -> Result<()> {
+    // Nothing but the document may go to stdout
+    if let Some(path) = output {
+        if !confirm_overwrite(Path::new(path), assume_yes)? {
+            print_info("Cancelled");
+            return Ok(());
+        }
+        print_info(&format!("Exporting agent '{}'...", agent_id.cyan()));
+    }
+
+    let url = format!("{}/v1/agents/{}", api_base(), agent_id);
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch agent")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let agent: Value = read_json(response).await?;
+    let document = export_document(&agent);
+    let as_json = match output {
+        Some(path) => path.ends_with(".json"),
+        None => is_json_mode(),
+    };
+    let text = if as_json {
+        serde_json::to_string_pretty(&document)? + "\n"
+    } else {
+        serde_yaml::to_string(&document)?
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, text).with_context(|| format!("Failed to write {}", path))?;
+            print_success(&format!("Agent exported to {}", path.green()));
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+/// Remove knowledge base documents and tools from an agent's prompt
+fn drop_references(document: &mut Value, documents: &[String], tools: &[String]) {
+    let prompt = &mut document["conversation_config"]["agent"]["prompt"];
+    if let Some(knowledge_base) = prompt["knowledge_base"].as_array_mut() {
+        knowledge_base.retain(|doc| {
+            !doc["id"]
+                .as_str()
+                .is_some_and(|id| documents.iter().any(|d| d == id))
+        });
+    }
+    if let Some(tool_ids) = prompt["tool_ids"].as_array_mut() {
+        tool_ids.retain(|id| !id.as_str().is_some_and(|id| tools.iter().any(|t| t == id)));
+    }
+}
+
+async fn import_agent(
+    client: &Client,
+    api_key: &str,
+    file: &str,
+    agent_id: Option<&str>,
+    name: Option<&str>,
+    drop_missing: bool,
+) -> Result<()> {
+    let text = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+    // YAML is a superset of JSON, so this reads both
+    let parsed: Value =
+        serde_yaml::from_str(&text).with_context(|| format!("Failed to parse {}", file))?;
+    if !parsed["conversation_config"].is_object() {
+        return Err(anyhow::anyhow!(
+            "{} has no conversation_config. Create it with 'agent export'",
+            file
+        ));
+    }
+
+    let mut document = export_document(&parsed);
+    if let Some(name) = name {
+        document["name"] = json!(name);
+    }
+    if agent_id.is_none() && !document["name"].is_string() {
+        return Err(anyhow::anyhow!("{} has no name. Pass --name", file));
+    }
+
+    print_info("Checking referenced resources...");
+    let references = agent_references(&document);
+    for voice in &references.voices {
+        if fetch_optional(client, api_key, &format!("/v1/voices/{}", voice))
+            .await?
+            .is_none()
+        {
+            print_warning(&format!(
+                "Voice {} is not in this workspace. Add it from the voice library",
+                voice
+            ));
+        }
+    }
+    let mut missing = Vec::new();
+    let mut missing_documents = Vec::new();
+    for (id, doc_name) in &references.documents {
+        let path = format!("/v1/convai/knowledge-base/{}", id);
+        if fetch_optional(client, api_key, &path).await?.is_none() {
+            missing.push(format!("knowledge base document '{}' ({})", doc_name, id));
+            missing_documents.push(id.clone());
+        }
+    }
+    let mut missing_tools = Vec::new();
+    for tool in &references.tools {
+        let path = format!("/v1/convai/tools/{}", tool);
+        if fetch_optional(client, api_key, &path).await?.is_none() {
+            missing.push(format!("tool {}", tool));
+            missing_tools.push(tool.clone());
+        }
+    }
+    if !missing.is_empty() {
+        if !drop_missing {
+            return Err(anyhow::anyhow!(
+                "Not found in this workspace: {}. Recreate them, or pass --drop-missing to import without them",
+                missing.join(", ")
+            ));
+        }
+        drop_references(&mut document, &missing_documents, &missing_tools);
+        print_warning(&format!("Left out {}", missing.join(", ")));
+    }
+
+    let request = match agent_id {
+        Some(id) => {
+            print_info(&format!("Updating agent '{}' from {}...", id.cyan(), file));
+            client.patch(format!("{}/v1/agents/{}", api_base(), id))
+        }
+        None => {
+            print_info(&format!("Creating agent from {}...", file));
+            client.post(format!("{}/v1/agents", api_base()))
+        }
+    };
+    let response = request
+        .header("xi-api-key", api_key)
+        .json(&document)
+        .send_with_retry()
+        .await
+        .context("Failed to import agent")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let result: Value = read_json(response).await?;
+    let new_id = agent_id.or(result["agent_id"].as_str()).unwrap_or_default();
+    if is_json_mode() {
+        println!("{}", json!({ "agent_id": new_id }));
+        return Ok(());
+    }
+    match agent_id {
+        Some(_) => print_success("Agent updated successfully!"),
+        None => {
+            print_success("Agent imported successfully!");
+            print_info(&format!("Agent ID: {}", new_id.yellow()));
+        }
+    }
+    Ok(())
+}
+
 async fn list_agent_summaries(client: &Client, api_key: &str, limit: Option<u32>) -> Result<()> {
     print_info("Fetching agent summaries...");
 
-    let url = "https://api.elevenlabs.io/v1/convai/agents/summaries";
+    let url = format!("{}/v1/convai/agents/summaries", api_base());
     let mut request = client.get(url).header("xi-api-key", api_key);
 
     if let Some(lim) = limit {
@@ -377,16 +905,15 @@ async fn list_agent_summaries(client: &Client, api_key: &str, limit: Option<u32>
     }
 
     let response = request
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch agent summaries")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct AgentSummaryItem {
         agent_id: String,
         name: String,
@@ -394,8 +921,12 @@ async fn list_agent_summaries(client: &Client, api_key: &str, limit: Option<u32>
         description: Option<String>,
     }
 
-    let summaries: Vec<AgentSummaryItem> =
-        response.json().await.context("Failed to parse response")?;
+    let summaries: Vec<AgentSummaryItem> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["ID", "Name", "Description"]);
@@ -421,33 +952,118 @@ async fn list_agent_summaries(client: &Client, api_key: &str, limit: Option<u32>
     Ok(())
 }
 
-async fn list_agent_branches(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
-    print_info(&format!(
-        "Fetching branches for agent '{}'...",
-        agent_id.cyan()
-    ));
+/// An agent branch as returned by the branches endpoint
+#[derive(Debug, Deserialize, Serialize)]
+struct BranchInfo {
+    branch_id: String,
+    name: String,
+    #[serde(default)]
+    created_at: Option<String>,
+}
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}/branches", agent_id);
+async fn fetch_agent_branches(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+) -> Result<Vec<BranchInfo>> {
+    let url = format!("{}/v1/agents/{}/branches", api_base(), agent_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
-    struct BranchInfo {
-        branch_id: String,
-        name: String,
-        #[serde(default)]
-        created_at: Option<String>,
+    read_json(response).await
+}
+
+/// Find a branch by ID, or by name (case-insensitive)
+fn find_branch<'a>(branches: &'a [BranchInfo], branch: &str) -> Option<&'a BranchInfo> {
+    branches.iter().find(|b| b.branch_id == branch).or_else(|| {
+        branches
+            .iter()
+            .find(|b| b.name.eq_ignore_ascii_case(branch))
+    })
+}
+
+/// Resolve a branch ID or name to a branch ID
+async fn resolve_branch_id(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    branch: &str,
+) -> Result<String> {
+    let branches = fetch_agent_branches(client, api_key, agent_id).await?;
+    find_branch(&branches, branch)
+        .map(|b| b.branch_id.clone())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Branch '{}' not found for agent '{}'. Use 'agent branch list' to see branches",
+                branch,
+                agent_id
+            )
+        })
+}
+
+async fn branch_command(
+    client: &Client,
+    api_key: &str,
+    command: AgentBranchCommands,
+) -> Result<()> {
+    match command {
+        AgentBranchCommands::List { agent_id } => {
+            list_agent_branches(client, api_key, &agent_id).await
+        }
+        AgentBranchCommands::Create {
+            agent_id,
+            name,
+            from,
+            description,
+        } => {
+            create_agent_branch(
+                client,
+                api_key,
+                &agent_id,
+                &name,
+                from.as_deref(),
+                description.as_deref(),
+            )
+            .await
+        }
+        AgentBranchCommands::Rename {
+            agent_id,
+            branch,
+            name,
+        } => {
+            let branch_id = resolve_branch_id(client, api_key, &agent_id, &branch).await?;
+            rename_agent_branch(client, api_key, &agent_id, &branch_id, &name).await
+        }
+        AgentBranchCommands::Delete { agent_id, branch } => {
+            delete_agent_branch(client, api_key, &agent_id, &branch).await
+        }
+        AgentBranchCommands::Promote {
+            agent_id,
+            branch,
+            to,
+        } => promote_agent_branch(client, api_key, &agent_id, &branch, &to).await,
     }
+}
 
-    let branches: Vec<BranchInfo> = response.json().await?;
+async fn list_agent_branches(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
+    print_info(&format!(
+        "Fetching branches for agent '{}'...",
+        agent_id.cyan()
+    ));
+
+    let branches = fetch_agent_branches(client, api_key, agent_id).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&branches)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Branch ID", "Name", "Created"]);
@@ -465,6 +1081,136 @@ async fn list_agent_branches(client: &Client, api_key: &str, agent_id: &str) ->
     Ok(())
 }
 
+async fn create_agent_branch(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    name: &str,
+    from: Option<&str>,
+    description: Option<&str>,
+) -> Result<()> {
+    print_info(&format!(
+        "Creating branch '{}' for agent '{}'...",
+        name.cyan(),
+        agent_id.cyan()
+    ));
+
+    let mut body = json!({ "name": name });
+    if let Some(from) = from {
+        body["parent_branch_id"] = json!(resolve_branch_id(client, api_key, agent_id, from).await?);
+    }
+    if let Some(description) = description {
+        body["description"] = json!(description);
+    }
+
+    let url = format!("{}/v1/agents/{}/branches", api_base(), agent_id);
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let created: Value = read_json(response).await.unwrap_or(Value::Null);
+    match created["branch_id"].as_str() {
+        Some(branch_id) => print_success(&format!(
+            "Branch '{}' created: {}",
+            name.green(),
+            branch_id.yellow()
+        )),
+        None => print_success(&format!("Branch '{}' created", name.green())),
+    }
+    Ok(())
+}
+
+async fn delete_agent_branch(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    branch: &str,
+) -> Result<()> {
+    let branch_id = resolve_branch_id(client, api_key, agent_id, branch).await?;
+    print_info(&format!("Deleting branch '{}'...", branch_id.cyan()));
+
+    let url = format!(
+        "{}/v1/agents/{}/branches/{}",
+        api_base(),
+        agent_id,
+        branch_id
+    );
+    let response = client
+        .delete(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    print_success(&format!("Branch '{}' deleted", branch.green()));
+    Ok(())
+}
+
+async fn promote_agent_branch(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    branch: &str,
+    target: &str,
+) -> Result<()> {
+    let branches = fetch_agent_branches(client, api_key, agent_id).await?;
+    let not_found = |name: &str| {
+        anyhow::anyhow!(
+            "Branch '{}' not found for agent '{}'. Use 'agent branch list' to see branches",
+            name,
+            agent_id
+        )
+    };
+    let source = find_branch(&branches, branch).ok_or_else(|| not_found(branch))?;
+    let target = find_branch(&branches, target).ok_or_else(|| not_found(target))?;
+    if source.branch_id == target.branch_id {
+        return Err(anyhow::anyhow!(
+            "Cannot promote branch '{}' into itself",
+            source.name
+        ));
+    }
+
+    print_info(&format!(
+        "Promoting branch '{}' into '{}'...",
+        source.name.cyan(),
+        target.name.cyan()
+    ));
+
+    let url = format!(
+        "{}/v1/agents/{}/branches/{}/merge",
+        api_base(),
+        agent_id,
+        source.branch_id
+    );
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .json(&json!({ "target_branch_id": target.branch_id }))
+        .send_with_retry()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    print_success(&format!(
+        "Branch '{}' promoted to '{}'",
+        source.name.green(),
+        target.name.green()
+    ));
+    Ok(())
+}
+
 async fn rename_agent_branch(
     client: &Client,
     api_key: &str,
@@ -479,8 +1225,10 @@ async fn rename_agent_branch(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/agents/{}/branches/{}",
-        agent_id, branch_id
+        "{}/v1/agents/{}/branches/{}",
+        api_base(),
+        agent_id,
+        branch_id
     );
     let body = json!({ "name": name });
 
@@ -488,12 +1236,11 @@ async fn rename_agent_branch(
         .patch(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Branch renamed to '{}'", name.green()));
@@ -503,7 +1250,7 @@ async fn rename_agent_branch(
 async fn list_batch_calls(client: &Client, api_key: &str, limit: Option<u32>) -> Result<()> {
     print_info("Fetching batch calls...");
 
-    let url = "https://api.elevenlabs.io/v1/convai/batch-calling";
+    let url = format!("{}/v1/convai/batch-calling", api_base());
     let mut request = client.get(url).header("xi-api-key", api_key);
 
     if let Some(lim) = limit {
@@ -511,16 +1258,15 @@ async fn list_batch_calls(client: &Client, api_key: &str, limit: Option<u32>) ->
     }
 
     let response = request
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch batch calls")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct BatchCallInfo {
         batch_id: String,
         #[serde(default)]
@@ -533,7 +1279,12 @@ async fn list_batch_calls(client: &Client, api_key: &str, limit: Option<u32>) ->
         created_at: Option<String>,
     }
 
-    let batches: Vec<BatchCallInfo> = response.json().await?;
+    let batches: Vec<BatchCallInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&batches)?);
+        return Ok(());
+    }
 
     if batches.is_empty() {
         print_info("No batch calls found");
@@ -572,22 +1323,18 @@ async fn get_batch_call_status(client: &Client, api_key: &str, batch_id: &str) -
         batch_id.cyan()
     ));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/batch-calling/{}",
-        batch_id
-    );
+    let url = format!("{}/v1/convai/batch-calling/{}", api_base(), batch_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct BatchCallDetail {
         batch_id: String,
         status: String,
@@ -601,7 +1348,12 @@ async fn get_batch_call_status(client: &Client, api_key: &str, batch_id: &str) -
         created_at: Option<String>,
     }
 
-    let batch: BatchCallDetail = response.json().await?;
+    let batch: BatchCallDetail = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&batch)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -620,22 +1372,159 @@ async fn get_batch_call_status(client: &Client, api_key: &str, batch_id: &str) -
     Ok(())
 }
 
+/// A batch call recipient parsed from the recipients CSV
+#[derive(Debug)]
+struct BatchRecipient {
+    phone_number: String,
+    dynamic_variables: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Parse a recipients CSV: `phone_number` is required, every other column
+/// becomes a dynamic variable for that recipient (empty cells are skipped)
+fn parse_batch_recipients<R: std::io::Read>(reader: R) -> Result<Vec<BatchRecipient>> {
+    let mut csv_reader = csv::Reader::from_reader(reader);
+    let headers = csv_reader
+        .headers()
+        .context("Failed to read recipients CSV header")?
+        .clone();
+    let phone_index = headers
+        .iter()
+        .position(|h| h.trim() == "phone_number")
+        .ok_or_else(|| anyhow::anyhow!("Recipients CSV must have a 'phone_number' column"))?;
+
+    let mut recipients = Vec::new();
+    for (row, record) in csv_reader.records().enumerate() {
+        let record = record.with_context(|| format!("Invalid recipients CSV row {}", row + 2))?;
+        let phone_number = record.get(phone_index).unwrap_or("").trim().to_string();
+        if phone_number.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Recipients CSV row {} has no phone_number",
+                row + 2
+            ));
+        }
+
+        let mut dynamic_variables = serde_json::Map::new();
+        for (index, header) in headers.iter().enumerate() {
+            if index == phone_index {
+                continue;
+            }
+            if let Some(value) = record.get(index).filter(|v| !v.is_empty()) {
+                dynamic_variables.insert(header.trim().to_string(), dynamic_var_value(value));
+            }
+        }
+
+        recipients.push(BatchRecipient {
+            phone_number,
+            dynamic_variables,
+        });
+    }
+
+    Ok(recipients)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn create_batch_call(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    phone_number_id: &str,
+    recipients_file: &str,
+    name: Option<&str>,
+    dynamic_vars: &[String],
+    wait: &WaitArgs,
+) -> Result<()> {
+    let shared_variables = parse_dynamic_vars(dynamic_vars)?;
+    let file = std::fs::File::open(recipients_file)
+        .with_context(|| format!("Failed to open recipients file: {}", recipients_file))?;
+    let recipients = parse_batch_recipients(file)?;
+
+    if recipients.is_empty() {
+        return Err(anyhow::anyhow!("Recipients CSV contains no rows"));
+    }
+
+    print_info(&format!(
+        "Submitting batch of {} call(s) for agent '{}'...",
+        recipients.len(),
+        agent_id.cyan()
+    ));
+
+    let recipients: Vec<serde_json::Value> = recipients
+        .into_iter()
+        .map(|recipient| {
+            // Per-recipient columns take precedence over --dynamic-var
+            let mut variables = shared_variables.clone();
+            variables.extend(recipient.dynamic_variables);
+            let mut entry = json!({ "phone_number": recipient.phone_number });
+            if !variables.is_empty() {
+                entry["conversation_initiation_client_data"] =
+                    json!({ "dynamic_variables": variables });
+            }
+            entry
+        })
+        .collect();
+
+    let call_name = name.map(str::to_string).unwrap_or_else(|| {
+        Path::new(recipients_file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "batch".to_string())
+    });
+
+    let body = json!({
+        "call_name": call_name,
+        "agent_id": agent_id,
+        "agent_phone_number_id": phone_number_id,
+        "recipients": recipients,
+    });
+
+    let response = client
+        .post(format!("{}/v1/convai/batch-calling/submit", api_base()))
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to submit batch call")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    #[derive(Deserialize)]
+    struct BatchCallCreated {
+        #[serde(alias = "batch_id")]
+        id: String,
+        #[serde(default)]
+        status: Option<String>,
+    }
+
+    let batch: BatchCallCreated = read_json(response).await?;
+    let job = crate::journal::record(
+        api_key,
+        JobKind::BatchCall,
+        &batch.id,
+        None,
+        name.unwrap_or(agent_id),
+    );
+
+    print_success(&format!("Batch call '{}' submitted", batch.id.green()));
+    if let Some(status) = &batch.status {
+        print_info(&format!("Status: {}", status));
+    }
+    super::jobs::wait_if_requested(api_key, job, wait).await
+}
+
 async fn delete_batch_call(client: &Client, api_key: &str, batch_id: &str) -> Result<()> {
     print_info(&format!("Deleting batch call '{}'...", batch_id.cyan()));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/batch-calling/{}",
-        batch_id
-    );
+    let url = format!("{}/v1/convai/batch-calling/{}", api_base(), batch_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -645,84 +1534,625 @@ async fn delete_batch_call(client: &Client, api_key: &str, batch_id: &str) -> Re
     Ok(())
 }
 
+/// A scripted simulation: user (and optionally agent) turns, mocked tools and criteria
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SimulationScript {
+    #[serde(default)]
+    turns: Vec<ScriptTurn>,
+    /// Tool name to the result returned instead of calling the real tool
+    #[serde(default)]
+    tool_mocks: HashMap<String, ToolMock>,
+    #[serde(default)]
+    evaluation_criteria: Vec<EvaluationCriterion>,
+    #[serde(default)]
+    dynamic_variables: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptTurn {
+    #[serde(default = "default_turn_role")]
+    role: String,
+    message: String,
+}
+
+fn default_turn_role() -> String {
+    "user".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolMock {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    is_error: bool,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EvaluationCriterion {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    prompt: String,
+}
+
+impl SimulationScript {
+    fn from_message(message: String) -> Self {
+        Self {
+            turns: vec![ScriptTurn {
+                role: default_turn_role(),
+                message,
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Check turn roles and that the script gives the simulation something to start from
+    fn validate(&self) -> Result<()> {
+        if self.turns.is_empty() {
+            return Err(anyhow::anyhow!("Simulation script needs at least one turn"));
+        }
+        for (i, turn) in self.turns.iter().enumerate() {
+            if !matches!(turn.role.as_str(), "user" | "agent") {
+                return Err(anyhow::anyhow!(
+                    "Turn {} has role '{}'. Use 'user' or 'agent'",
+                    i + 1,
+                    turn.role
+                ));
+            }
+            if turn.message.trim().is_empty() {
+                return Err(anyhow::anyhow!("Turn {} has an empty message", i + 1));
+            }
+        }
+        let mut ids = std::collections::HashSet::new();
+        for criterion in &self.evaluation_criteria {
+            if !ids.insert(criterion.id.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Duplicate evaluation criterion '{}'",
+                    criterion.id
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Request body for the simulate-conversation endpoint
+    fn request_body(&self, max_turns: u32) -> Value {
+        let history: Vec<Value> = self
+            .turns
+            .iter()
+            .enumerate()
+            .map(|(i, turn)| {
+                json!({
+                    "role": turn.role,
+                    "message": turn.message,
+                    "time_in_call_secs": i,
+                })
+            })
+            .collect();
+
+        let mut specification = json!({ "partial_conversation_history": history });
+        if !self.tool_mocks.is_empty() {
+            let mocks: serde_json::Map<String, Value> = self
+                .tool_mocks
+                .iter()
+                .map(|(tool, mock)| {
+                    let value = match &mock.result {
+                        Value::String(s) => s.clone(),
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    };
+                    (
+                        tool.clone(),
+                        json!({
+                            "default_return_value": value,
+                            "default_is_error": mock.is_error,
+                        }),
+                    )
+                })
+                .collect();
+            specification["tool_mock_config"] = Value::Object(mocks);
+        }
+        if !self.dynamic_variables.is_empty() {
+            specification["dynamic_variables"] = Value::Object(self.dynamic_variables.clone());
+        }
+
+        let mut body = json!({
+            "simulation_specification": specification,
+            "new_turns_limit": max_turns,
+        });
+        if !self.evaluation_criteria.is_empty() {
+            body["extra_evaluation_criteria"] = self
+                .evaluation_criteria
+                .iter()
+                .map(|c| {
+                    json!({
+                        "id": c.id,
+                        "name": c.name.as_deref().unwrap_or(&c.id),
+                        "conversation_goal_prompt": c.prompt,
+                        "use_knowledge_base": false,
+                    })
+                })
+                .collect();
+        }
+        body
+    }
+}
+
+fn load_simulation_script(path: &Path) -> Result<SimulationScript> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid simulation script {}", path.display()))
+}
+
+/// Parse a `--criterion ID=PROMPT` value
+fn parse_evaluation_criterion(value: &str) -> Result<EvaluationCriterion> {
+    let (id, prompt) = value
+        .split_once('=')
+        .map(|(id, prompt)| (id.trim(), prompt.trim()))
+        .filter(|(id, prompt)| !id.is_empty() && !prompt.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Invalid criterion '{}'. Use id=goal prompt", value))?;
+    Ok(EvaluationCriterion {
+        id: id.to_string(),
+        name: None,
+        prompt: prompt.to_string(),
+    })
+}
+
 async fn simulate_conversation(
     client: &Client,
     api_key: &str,
     agent_id: &str,
-    message: &str,
+    script: &SimulationScript,
     max_turns: u32,
 ) -> Result<()> {
+    script.validate()?;
+
     print_info(&format!(
         "Simulating conversation with agent '{}'...",
         agent_id.cyan()
     ));
-    print_info(&format!("Initial message: \"{}\"", message.cyan()));
-    print_info(&format!("Max turns: {}", max_turns));
+    print_info(&format!(
+        "Scripted turns: {}, mocked tools: {}, criteria: {}, max turns: {}",
+        script.turns.len(),
+        script.tool_mocks.len(),
+        script.evaluation_criteria.len(),
+        max_turns
+    ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/agents/{}/simulate",
+        "{}/v1/convai/agents/{}/simulate-conversation",
+        api_base(),
         agent_id
     );
-
-    let body = json!({
-        "messages": [
-            {
-                "role": "user",
-                "content": message
-            }
-        ],
-        "max_turns": max_turns
-    });
-
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
-        .json(&body)
-        .send()
+        .json(&script.request_body(max_turns))
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     #[derive(Deserialize)]
-    struct SimulationMessage {
+    struct SimulatedTurn {
         role: String,
-        content: String,
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        tool_calls: Vec<Value>,
+    }
+
+    #[derive(Deserialize)]
+    struct CriterionResult {
+        #[serde(default)]
+        result: String,
+        #[serde(default)]
+        rationale: String,
+    }
+
+    #[derive(Deserialize, Default)]
+    struct SimulationAnalysis {
+        #[serde(default)]
+        call_successful: Option<String>,
+        #[serde(default)]
+        transcript_summary: Option<String>,
+        #[serde(default)]
+        evaluation_criteria_results: HashMap<String, CriterionResult>,
     }
 
     #[derive(Deserialize)]
     struct SimulationResponse {
-        messages: Vec<SimulationMessage>,
         #[serde(default)]
-        conversation_id: Option<String>,
+        simulated_conversation: Vec<SimulatedTurn>,
+        #[serde(default)]
+        analysis: Option<SimulationAnalysis>,
     }
 
-    let result: SimulationResponse = response.json().await?;
+    let raw: Value = read_json(response).await?;
+    let result: SimulationResponse = serde_json::from_value(raw.clone())?;
+    let analysis = result.analysis.unwrap_or_default();
+    let failed: Vec<&String> = analysis
+        .evaluation_criteria_results
+        .iter()
+        .filter(|(_, r)| r.result == "failure")
+        .map(|(id, _)| id)
+        .collect();
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&raw)?);
+    } else {
+        println!("\n{}", "Conversation Simulation:".bold().underline());
+        println!();
+
+        for turn in &result.simulated_conversation {
+            let message = turn.message.as_deref().unwrap_or("");
+            match turn.role.as_str() {
+                "user" => println!("{} {}", "[User]:".blue().bold(), message),
+                "assistant" | "agent" => println!("{} {}", "[Agent]:".green().bold(), message),
+                role => println!("{}: {}", role, message),
+            }
+            for call in &turn.tool_calls {
+                let name = call["tool_name"].as_str().unwrap_or("tool");
+                println!("  {} {}", "[Tool call]:".yellow(), name);
+            }
+        }
 
-    println!("\n{}", "Conversation Simulation:".bold().underline());
+        if let Some(summary) = &analysis.transcript_summary {
+            println!("\n{} {}", "Summary:".bold(), summary);
+        }
+        if let Some(outcome) = &analysis.call_successful {
+            println!("{} {}", "Outcome:".bold(), outcome);
+        }
 
-    if let Some(cid) = &result.conversation_id {
-        println!("Conversation ID: {}", cid.yellow());
+        if !analysis.evaluation_criteria_results.is_empty() {
+            let mut table = Table::new();
+            table.set_header(vec!["Criterion", "Result", "Rationale"]);
+            let mut ids: Vec<&String> = analysis.evaluation_criteria_results.keys().collect();
+            ids.sort();
+            for id in ids {
+                let criterion = &analysis.evaluation_criteria_results[id];
+                let result = match criterion.result.as_str() {
+                    "success" => criterion.result.green(),
+                    "failure" => criterion.result.red(),
+                    _ => criterion.result.yellow(),
+                };
+                table.add_row(vec![id.cyan(), result, criterion.rationale.normal()]);
+            }
+            println!("\n{}", table);
+        }
     }
-    println!();
 
-    for msg in &result.messages {
-        match msg.role.as_str() {
-            "user" => println!("{} {}", "[User]:".blue().bold(), msg.content),
-            "assistant" | "agent" => println!("{} {}", "[Agent]:".green().bold(), msg.content),
-            _ => println!("{}: {}", msg.role, msg.content),
-        }
+    if !failed.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Simulation failed {} of {} evaluation criteria",
+            failed.len(),
+            analysis.evaluation_criteria_results.len()
+        ));
     }
 
     print_success(&format!(
         "Simulation complete ({} messages)",
-        result.messages.len()
+        result.simulated_conversation.len()
     ));
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One line of the `agent smoke` report
+#[derive(Debug, Serialize)]
+struct SmokeCheck {
+    check: String,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+}
+
+impl SmokeCheck {
+    fn new(check: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Voices, knowledge base documents and tools an agent config refers to
+#[derive(Debug, Default, PartialEq)]
+struct AgentReferences {
+    voices: Vec<String>,
+    /// (document ID, name)
+    documents: Vec<(String, String)>,
+    tools: Vec<String>,
+}
+
+fn agent_references(agent: &Value) -> AgentReferences {
+    let config = &agent["conversation_config"];
+    let mut voices: Vec<String> = std::iter::once(&config["tts"]["voice_id"])
+        .chain(
+            config["tts"]["supported_voices"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|v| &v["voice_id"]),
+        )
+        .filter_map(|v| v.as_str())
+        .map(str::to_string)
+        .collect();
+    let mut seen = std::collections::HashSet::new();
+    voices.retain(|v| seen.insert(v.clone()));
+
+    let prompt = &config["agent"]["prompt"];
+    let documents = prompt["knowledge_base"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|doc| {
+            let id = doc["id"].as_str()?;
+            Some((
+                id.to_string(),
+                doc["name"].as_str().unwrap_or(id).to_string(),
+            ))
+        })
+        .collect();
+    let tools = prompt["tool_ids"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str().map(str::to_string))
+        .collect();
+    AgentReferences {
+        voices,
+        documents,
+        tools,
+    }
+}
+
+/// GET a resource; `None` when it does not exist
+async fn fetch_optional(client: &Client, api_key: &str, path: &str) -> Result<Option<Value>> {
+    let response = client
+        .get(format!("{}{}", api_base(), path))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to fetch {}", path))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(Some(read_json(response).await?))
+}
+
+/// Check that a referenced resource exists
+async fn reference_check(
+    client: &Client,
+    api_key: &str,
+    check: String,
+    path: String,
+    fix: &str,
+) -> SmokeCheck {
+    match fetch_optional(client, api_key, &path).await {
+        Ok(Some(_)) => SmokeCheck::new(check, CheckStatus::Pass, "Found"),
+        Ok(None) => SmokeCheck::new(check, CheckStatus::Fail, "Not found").with_fix(fix),
+        Err(e) => SmokeCheck::new(check, CheckStatus::Fail, format!("{:#}", e)),
+    }
+}
+
+async fn smoke_test_agent(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    message: &str,
+    no_simulate: bool,
+) -> Result<()> {
+    print_info(&format!("Smoke testing agent '{}'...", agent_id.cyan()));
+    let mut checks = Vec::new();
+
+    let agent =
+        match fetch_optional(client, api_key, &format!("/v1/convai/agents/{}", agent_id)).await {
+            Ok(Some(agent)) => {
+                let name = agent["name"].as_str().unwrap_or(agent_id);
+                checks.push(SmokeCheck::new("Agent config", CheckStatus::Pass, name));
+                Some(agent)
+            }
+            Ok(None) => {
+                checks.push(
+                    SmokeCheck::new("Agent config", CheckStatus::Fail, "Agent not found")
+                        .with_fix("Check the ID with 'agent list'"),
+                );
+                None
+            }
+            Err(e) => {
+                checks.push(SmokeCheck::new(
+                    "Agent config",
+                    CheckStatus::Fail,
+                    format!("{:#}", e),
+                ));
+                None
+            }
+        };
+
+    if let Some(agent) = &agent {
+        let prompt = &agent["conversation_config"]["agent"]["prompt"]["prompt"];
+        if prompt.as_str().is_none_or(|p| p.trim().is_empty()) {
+            checks.push(
+                SmokeCheck::new("System prompt", CheckStatus::Warn, "Empty")
+                    .with_fix("Give the agent a system prompt so it knows its job"),
+            );
+        }
+
+        let references = agent_references(agent);
+        if references.voices.is_empty() {
+            checks.push(SmokeCheck::new(
+                "Voice",
+                CheckStatus::Warn,
+                "No voice set; the platform default is used",
+            ));
+        }
+        for voice in &references.voices {
+            checks.push(
+                reference_check(
+                    client,
+                    api_key,
+                    format!("Voice {}", voice),
+                    format!("/v1/voices/{}", voice),
+                    "Add the voice to your account from the voice library, or switch the agent to another voice",
+                )
+                .await,
+            );
+        }
+        for (id, name) in &references.documents {
+            checks.push(
+                reference_check(
+                    client,
+                    api_key,
+                    format!("Knowledge base '{}'", name),
+                    format!("/v1/convai/knowledge-base/{}", id),
+                    "Remove the document from the agent or add it again with 'knowledge add-from-*'",
+                )
+                .await,
+            );
+        }
+        for tool in &references.tools {
+            checks.push(
+                reference_check(
+                    client,
+                    api_key,
+                    format!("Tool {}", tool),
+                    format!("/v1/convai/tools/{}", tool),
+                    "Remove the tool from the agent or recreate it with 'tools create'",
+                )
+                .await,
+            );
+        }
+
+        if !no_simulate {
+            checks.push(simulation_check(client, api_key, agent_id, message).await);
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "agent_id": agent_id,
+                "passed": failed == 0,
+                "checks": checks,
+            }))?
+        );
+    } else {
+        let mut table = Table::new();
+        table.set_header(vec!["Check", "Result", "Details"]);
+        for check in &checks {
+            let result = match check.status {
+                CheckStatus::Pass => format!("{} pass", symbol("✓", "[ok]")).green(),
+                CheckStatus::Warn => format!("{} warn", symbol("⚠", "[warn]")).yellow(),
+                CheckStatus::Fail => format!("{} fail", symbol("✗", "[fail]")).red(),
+            };
+            let detail = match &check.fix {
+                Some(fix) => format!("{}\n{} {}", check.detail, "Fix:".bold(), fix),
+                None => check.detail.clone(),
+            };
+            table.add_row(vec![check.check.clone(), result.to_string(), detail]);
+        }
+        println!("{}", table);
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "Smoke test failed {} of {} checks",
+            failed,
+            checks.len()
+        ));
+    }
+    print_success(&format!("All {} checks passed", checks.len()));
+    Ok(())
+}
+
+/// Run one simulated turn and check the agent answers
+async fn simulation_check(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    message: &str,
+) -> SmokeCheck {
+    let result = async {
+        let response = client
+            .post(format!(
+                "{}/v1/convai/agents/{}/simulate-conversation",
+                api_base(),
+                agent_id
+            ))
+            .header("xi-api-key", api_key)
+            .json(&SimulationScript::from_message(message.to_string()).request_body(1))
+            .send_with_retry()
+            .await
+            .context("Failed to run simulation")?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let result: Value = read_json(response).await?;
+        Ok(result)
+    }
+    .await;
+
+    match result {
+        Ok(result) => {
+            let reply = result["simulated_conversation"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|turn| matches!(turn["role"].as_str(), Some("agent" | "assistant")))
+                .filter_map(|turn| turn["message"].as_str())
+                .find(|text| !text.trim().is_empty());
+            match reply {
+                Some(text) => {
+                    let mut preview: String = text.chars().take(60).collect();
+                    if text.chars().count() > 60 {
+                        preview.push_str("...");
+                    }
+                    SmokeCheck::new("Simulated turn", CheckStatus::Pass, preview)
+                }
+                None => SmokeCheck::new(
+                    "Simulated turn",
+                    CheckStatus::Fail,
+                    "The agent did not reply",
+                )
+                .with_fix("Run 'agent simulate' to see the full conversation"),
+            }
+        }
+        Err(e) => SmokeCheck::new("Simulated turn", CheckStatus::Fail, format!("{:#}", e)),
+    }
+}
+
 async fn update_agent_turn_config(
     client: &Client,
     api_key: &str,
@@ -765,17 +2195,16 @@ async fn update_agent_turn_config(
         }
     });
 
-    let url = format!("https://api.elevenlabs.io/v1/agents/{}", agent_id);
+    let url = format!("{}/v1/agents/{}", api_base(), agent_id);
     let response = client
         .patch(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success("Turn configuration updated successfully!");
@@ -786,20 +2215,19 @@ async fn list_whatsapp_accounts(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching WhatsApp accounts...");
 
     // Get all agents and their WhatsApp accounts
-    let url = "https://api.elevenlabs.io/v1/agents";
+    let url = format!("{}/v1/agents", api_base());
     let response = client
         .get(url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch agents")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct AgentWithWhatsapp {
         agent_id: String,
         name: String,
@@ -807,7 +2235,7 @@ async fn list_whatsapp_accounts(client: &Client, api_key: &str) -> Result<()> {
         whatsapp_accounts: Option<Vec<WhatsAppAccount>>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct WhatsAppAccount {
         #[allow(dead_code)]
         phone_number_id: String,
@@ -817,8 +2245,21 @@ async fn list_whatsapp_accounts(client: &Client, api_key: &str) -> Result<()> {
         business_account_id: Option<String>,
     }
 
-    let agents: Vec<AgentWithWhatsapp> =
-        response.json().await.context("Failed to parse response")?;
+    let agents: Vec<AgentWithWhatsapp> = read_json(response).await?;
+
+    if is_json_mode() {
+        let connected: Vec<&AgentWithWhatsapp> = agents
+            .iter()
+            .filter(|agent| {
+                agent
+                    .whatsapp_accounts
+                    .as_ref()
+                    .is_some_and(|a| !a.is_empty())
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&connected)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec![
@@ -860,24 +2301,25 @@ async fn get_agent_widget(client: &Client, api_key: &str, agent_id: &str) -> Res
         agent_id
     ));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/agents/{}/widget/",
-        agent_id
-    );
+    let url = format!("{}/v1/convai/agents/{}/widget/", api_base(), agent_id);
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch widget configuration")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let widget: serde_json::Value = response.json().await.context("Failed to parse response")?;
+    let widget: serde_json::Value = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&widget)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Widget Configuration:".bold().underline());
     println!(
@@ -951,25 +2393,21 @@ async fn set_agent_widget_avatar(
 
     let form = reqwest::multipart::Form::new().part("avatar_file", part);
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/agents/{}/avatar",
-        agent_id
-    );
+    let url = format!("{}/v1/convai/agents/{}/avatar", api_base(), agent_id);
 
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
         .multipart(form)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to set widget avatar")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: serde_json::Value = response.json().await.context("Failed to parse response")?;
+    let result: serde_json::Value = read_json(response).await?;
 
     println!("\n{}", "Widget Avatar:".bold().underline());
     println!(
@@ -984,3 +2422,170 @@ async fn set_agent_widget_avatar(
     print_success("Widget avatar set successfully");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_references() {
+        let agent = json!({
+            "conversation_config": {
+                "tts": {
+                    "voice_id": "v1",
+                    "supported_voices": [{ "voice_id": "v1" }, { "voice_id": "v2" }]
+                },
+                "agent": { "prompt": {
+                    "knowledge_base": [{ "id": "kb1", "name": "FAQ", "type": "url" }],
+                    "tool_ids": ["tool1"]
+                }}
+            }
+        });
+        assert_eq!(
+            agent_references(&agent),
+            AgentReferences {
+                voices: vec!["v1".to_string(), "v2".to_string()],
+                documents: vec![("kb1".to_string(), "FAQ".to_string())],
+                tools: vec!["tool1".to_string()],
+            }
+        );
+        assert_eq!(agent_references(&json!({})), AgentReferences::default());
+    }
+
+    #[test]
+    fn test_export_document_keeps_portable_fields() {
+        let agent = json!({
+            "agent_id": "agent_1",
+            "name": "Support",
+            "tags": null,
+            "conversation_config": { "agent": { "first_message": "Hi" } },
+            "metadata": { "created_at_unix_secs": 1 },
+            "access_info": { "is_creator": true }
+        });
+        assert_eq!(
+            export_document(&agent),
+            json!({
+                "name": "Support",
+                "conversation_config": { "agent": { "first_message": "Hi" } }
+            })
+        );
+    }
+
+    #[test]
+    fn test_drop_references() {
+        let mut agent = json!({
+            "conversation_config": { "agent": { "prompt": {
+                "knowledge_base": [{ "id": "kb1" }, { "id": "kb2" }],
+                "tool_ids": ["tool1", "tool2"]
+            }}}
+        });
+        drop_references(&mut agent, &["kb1".to_string()], &["tool2".to_string()]);
+        let references = agent_references(&agent);
+        assert_eq!(
+            references.documents,
+            [("kb2".to_string(), "kb2".to_string())]
+        );
+        assert_eq!(references.tools, ["tool1"]);
+    }
+
+    #[test]
+    fn test_find_branch_by_id_or_name() {
+        let branch = |id: &str, name: &str| BranchInfo {
+            branch_id: id.to_string(),
+            name: name.to_string(),
+            created_at: None,
+        };
+        let branches = vec![branch("agtbrch_1", "Main"), branch("agtbrch_2", "staging")];
+
+        assert_eq!(
+            find_branch(&branches, "main").unwrap().branch_id,
+            "agtbrch_1"
+        );
+        assert_eq!(find_branch(&branches, "agtbrch_2").unwrap().name, "staging");
+        assert!(find_branch(&branches, "feature").is_none());
+    }
+
+    #[test]
+    fn test_simulation_script_request_body() {
+        let mut script: SimulationScript = serde_json::from_str(
+            r#"{
+                "turns": [
+                    {"message": "Where is my order?"},
+                    {"role": "agent", "message": "Can I have the order number?"},
+                    {"message": "A-123"}
+                ],
+                "tool_mocks": {"lookup_order": {"result": {"status": "shipped"}}},
+                "evaluation_criteria": [{"id": "resolved", "prompt": "The agent gave the status"}]
+            }"#,
+        )
+        .unwrap();
+        script
+            .evaluation_criteria
+            .push(parse_evaluation_criterion("polite = Agent stays polite").unwrap());
+        script.validate().unwrap();
+
+        let body = script.request_body(3);
+        let spec = &body["simulation_specification"];
+        assert_eq!(spec["partial_conversation_history"][1]["role"], "agent");
+        assert_eq!(spec["partial_conversation_history"][2]["message"], "A-123");
+        assert_eq!(
+            spec["tool_mock_config"]["lookup_order"]["default_return_value"],
+            r#"{"status":"shipped"}"#
+        );
+        assert_eq!(body["new_turns_limit"], 3);
+        assert_eq!(body["extra_evaluation_criteria"][1]["id"], "polite");
+        assert_eq!(
+            body["extra_evaluation_criteria"][1]["conversation_goal_prompt"],
+            "Agent stays polite"
+        );
+        assert!(spec.get("dynamic_variables").is_none());
+
+        assert!(parse_evaluation_criterion("no-prompt").is_err());
+        assert!(SimulationScript::default().validate().is_err());
+        let bad_role: SimulationScript =
+            serde_json::from_str(r#"{"turns": [{"role": "system", "message": "x"}]}"#).unwrap();
+        assert!(bad_role.validate().is_err());
+    }
+
+    #[test]
+    fn test_aggregate_agent_activity() {
+        let conversations = vec![
+            json!({"agent_id": "a1", "start_time_unix_secs": 100, "call_successful": "success"}),
+            json!({"agent_id": "a1", "start_time_unix_secs": 300, "call_successful": "failure"}),
+            json!({"agent_id": "a2", "start_time_unix_secs": 200, "status": "failed"}),
+            json!({"conversation_id": "orphan"}),
+        ];
+        let activity = aggregate_agent_activity(&conversations);
+
+        assert_eq!(
+            activity["a1"],
+            AgentActivity {
+                conversations: 2,
+                failed: 1,
+                last_activity: Some(300),
+            }
+        );
+        assert_eq!(agent_health(&activity["a1"]), "failing");
+        assert_eq!(agent_health(&activity["a2"]), "failing");
+        assert_eq!(agent_health(&AgentActivity::default()), "idle");
+    }
+
+    #[test]
+    fn test_parse_batch_recipients() {
+        let csv = "phone_number,customer_name,balance\n+15550001,Ada,42\n+15550002,,7\n";
+        let recipients = parse_batch_recipients(csv.as_bytes()).unwrap();
+        assert_eq!(recipients.len(), 2);
+        assert_eq!(recipients[0].phone_number, "+15550001");
+        assert_eq!(recipients[0].dynamic_variables["customer_name"], "Ada");
+        assert_eq!(recipients[0].dynamic_variables["balance"], 42);
+        assert!(!recipients[1]
+            .dynamic_variables
+            .contains_key("customer_name"));
+    }
+
+    #[test]
+    fn test_parse_batch_recipients_requires_phone_column() {
+        assert!(parse_batch_recipients("name\nAda\n".as_bytes()).is_err());
+        assert!(parse_batch_recipients("phone_number,name\n,Ada\n".as_bytes()).is_err());
+    }
+}
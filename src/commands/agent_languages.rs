@@ -0,0 +1,328 @@
+//! Agent language presets (`agent languages`)
+//!
+//! Presets live in `conversation_config.language_presets`, keyed by language
+//! code. A preset overrides parts of the config when a conversation runs in
+//! that language; these commands manage `overrides.agent.first_message` and
+//! `overrides.tts.voice_id`. Presets are edited on the fetched config and
+//! written back whole, so other overrides on a preset are kept; removed
+//! languages are written as null.
+
+use crate::cli::AgentLanguageCommands;
+use crate::client::{api_base, api_error, SendWithRetry};
+use crate::commands::agent::fetch_agent;
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+
+/// TTS models that only speak English
+const ENGLISH_ONLY_MODELS: &[&str] = &["eleven_turbo_v2", "eleven_flash_v2"];
+
+/// Preset edits requested on the command line
+#[derive(Debug, Default)]
+struct LanguageChanges {
+    add: Vec<String>,
+    remove: Vec<String>,
+    voices: Vec<(String, String)>,
+    first_messages: Vec<(String, String)>,
+}
+
+pub async fn languages_command(
+    client: &Client,
+    api_key: &str,
+    command: AgentLanguageCommands,
+) -> Result<()> {
+    match command {
+        AgentLanguageCommands::List { agent_id } => {
+            list_languages(client, api_key, &agent_id).await
+        }
+        AgentLanguageCommands::Set {
+            agent_id,
+            add,
+            remove,
+            voice_for,
+            first_message_for,
+        } => {
+            let changes = LanguageChanges {
+                add: add
+                    .iter()
+                    .map(|code| normalize_language(code))
+                    .collect::<Result<_>>()?,
+                remove: remove
+                    .iter()
+                    .map(|code| normalize_language(code))
+                    .collect::<Result<_>>()?,
+                voices: parse_pairs("--voice-for", &voice_for)?,
+                first_messages: parse_pairs("--first-message-for", &first_message_for)?,
+            };
+            set_languages(client, api_key, &agent_id, &changes).await
+        }
+    }
+}
+
+fn presets(agent: &Value) -> Map<String, Value> {
+    agent["conversation_config"]["language_presets"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default()
+}
+
+async fn list_languages(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
+    print_info(&format!(
+        "Fetching language presets for agent '{}'...",
+        agent_id.cyan()
+    ));
+    let agent = fetch_agent(client, api_key, agent_id).await?;
+    let rows: Vec<Value> = presets(&agent)
+        .iter()
+        .map(|(language, preset)| {
+            json!({
+                "language": language,
+                "voice_id": preset["overrides"]["tts"]["voice_id"],
+                "first_message": preset["overrides"]["agent"]["first_message"],
+            })
+        })
+        .collect();
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+    if rows.is_empty() {
+        print_info("No language presets configured");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Language", "Voice", "First Message"]);
+    for row in &rows {
+        table.add_row(vec![
+            row["language"]
+                .as_str()
+                .unwrap_or_default()
+                .cyan()
+                .to_string(),
+            row["voice_id"].as_str().unwrap_or("-").yellow().to_string(),
+            row["first_message"].as_str().unwrap_or("-").to_string(),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+async fn set_languages(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    changes: &LanguageChanges,
+) -> Result<()> {
+    let agent = fetch_agent(client, api_key, agent_id).await?;
+    let updated = apply_changes(presets(&agent), changes)?;
+
+    let model = agent["conversation_config"]["tts"]["model_id"]
+        .as_str()
+        .unwrap_or_default();
+    let non_english = updated
+        .iter()
+        .any(|(language, preset)| !preset.is_null() && !language.starts_with("en"));
+    if non_english && ENGLISH_ONLY_MODELS.contains(&model) {
+        print_warning(&format!(
+            "The agent's TTS model '{}' only speaks English; switch to a multilingual model such as eleven_turbo_v2_5",
+            model
+        ));
+    }
+
+    let body = json!({ "conversation_config": { "language_presets": updated } });
+    let response = client
+        .patch(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to update agent")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let active: Vec<&str> = updated
+        .iter()
+        .filter(|(_, preset)| !preset.is_null())
+        .map(|(language, _)| language.as_str())
+        .collect();
+    print_success(&format!(
+        "Updated language presets on agent '{}': {}",
+        agent_id,
+        if active.is_empty() {
+            "none".to_string()
+        } else {
+            active.join(", ")
+        }
+    ));
+    Ok(())
+}
+
+/// The presets to write back: `existing` with the changes applied and
+/// removed languages set to null
+fn apply_changes(
+    mut presets: Map<String, Value>,
+    changes: &LanguageChanges,
+) -> Result<Map<String, Value>> {
+    let touched = changes
+        .add
+        .iter()
+        .chain(changes.voices.iter().map(|(l, _)| l))
+        .chain(changes.first_messages.iter().map(|(l, _)| l));
+    if touched.clone().next().is_none() && changes.remove.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Nothing to change. Use --add, --remove, --voice-for or --first-message-for"
+        ));
+    }
+
+    for language in &changes.remove {
+        if touched.clone().any(|l| l == language) {
+            return Err(anyhow::anyhow!(
+                "Language '{}' is both removed and changed",
+                language
+            ));
+        }
+        if !presets.contains_key(language) {
+            return Err(anyhow::anyhow!(
+                "The agent has no preset for language '{}'",
+                language
+            ));
+        }
+        presets.insert(language.clone(), Value::Null);
+    }
+
+    for language in touched {
+        let preset = presets
+            .entry(language.clone())
+            .or_insert_with(|| json!({ "overrides": {} }));
+        if !preset.is_object() {
+            *preset = json!({ "overrides": {} });
+        }
+        if !preset["overrides"].is_object() {
+            preset["overrides"] = json!({});
+        }
+    }
+    for (language, voice_id) in &changes.voices {
+        presets[language]["overrides"]["tts"]["voice_id"] = json!(voice_id);
+    }
+    for (language, message) in &changes.first_messages {
+        presets[language]["overrides"]["agent"]["first_message"] = json!(message);
+    }
+    Ok(presets)
+}
+
+/// A language code such as `es`, `deu` or `pt-br`, lowercased
+fn normalize_language(code: &str) -> Result<String> {
+    let code = code.trim().to_ascii_lowercase();
+    let (base, region) = match code.split_once('-') {
+        Some((base, region)) => (base, Some(region)),
+        None => (code.as_str(), None),
+    };
+    let base_ok = (2..=3).contains(&base.len()) && base.chars().all(|c| c.is_ascii_lowercase());
+    let region_ok = region
+        .is_none_or(|r| (2..=4).contains(&r.len()) && r.chars().all(|c| c.is_ascii_alphanumeric()));
+    if base_ok && region_ok {
+        Ok(code)
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid language code '{}' (use codes such as es, de or pt-br)",
+            code
+        ))
+    }
+}
+
+/// Parse `LANG=VALUE` arguments of `flag`
+fn parse_pairs(flag: &str, values: &[String]) -> Result<Vec<(String, String)>> {
+    values
+        .iter()
+        .map(|pair| {
+            let (language, value) = pair.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("Invalid {} '{}'. Use LANG=VALUE, e.g. es=...", flag, pair)
+            })?;
+            if value.trim().is_empty() {
+                return Err(anyhow::anyhow!("{} '{}' has an empty value", flag, pair));
+            }
+            Ok((normalize_language(language)?, value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize_language("ES").unwrap(), "es");
+        assert_eq!(normalize_language("pt-BR").unwrap(), "pt-br");
+        assert_eq!(normalize_language("deu").unwrap(), "deu");
+        assert!(normalize_language("spanish").is_err());
+        assert!(normalize_language("e").is_err());
+        assert!(normalize_language("es-").is_err());
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        let pairs = parse_pairs(
+            "--first-message-for",
+            &["es=Hola, ¿en qué puedo ayudarte?".to_string()],
+        )
+        .unwrap();
+        assert_eq!(pairs[0].0, "es");
+        assert_eq!(pairs[0].1, "Hola, ¿en qué puedo ayudarte?");
+        assert!(parse_pairs("--voice-for", &["es".to_string()]).is_err());
+        assert!(parse_pairs("--voice-for", &["es=".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_apply_changes_keeps_other_overrides() {
+        let existing = json!({
+            "fr": { "overrides": { "agent": { "first_message": "Bonjour", "prompt": { "prompt": "Parle français" } } } },
+            "de": { "overrides": { "tts": { "voice_id": "v_de" } } }
+        });
+        let changes = LanguageChanges {
+            add: vec!["it".to_string()],
+            remove: vec!["de".to_string()],
+            voices: vec![("es".to_string(), "v_es".to_string())],
+            first_messages: vec![
+                ("es".to_string(), "Hola".to_string()),
+                ("fr".to_string(), "Salut".to_string()),
+            ],
+        };
+        let presets = apply_changes(existing.as_object().unwrap().clone(), &changes).unwrap();
+        assert_eq!(presets["de"], Value::Null);
+        assert_eq!(presets["it"], json!({ "overrides": {} }));
+        assert_eq!(presets["es"]["overrides"]["tts"]["voice_id"], "v_es");
+        assert_eq!(presets["es"]["overrides"]["agent"]["first_message"], "Hola");
+        assert_eq!(
+            presets["fr"]["overrides"]["agent"]["first_message"],
+            "Salut"
+        );
+        assert_eq!(
+            presets["fr"]["overrides"]["agent"]["prompt"]["prompt"],
+            "Parle français"
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_rejects_conflicts() {
+        let existing = json!({ "es": { "overrides": {} } });
+        let existing = existing.as_object().unwrap();
+        let both = LanguageChanges {
+            remove: vec!["es".to_string()],
+            voices: vec![("es".to_string(), "v".to_string())],
+            ..Default::default()
+        };
+        assert!(apply_changes(existing.clone(), &both).is_err());
+        let missing = LanguageChanges {
+            remove: vec!["fr".to_string()],
+            ..Default::default()
+        };
+        assert!(apply_changes(existing.clone(), &missing).is_err());
+        assert!(apply_changes(existing.clone(), &LanguageChanges::default()).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! Conversation data retention settings (`agent privacy`, `workspace privacy`)
+//!
+//! The settings live in each agent's `platform_settings.privacy`. There is no
+//! workspace-wide switch, so `workspace privacy set` applies the same change
+//! to every agent and `workspace privacy get` lists them side by side.
+
+use crate::cli::{AgentPrivacyCommands, PrivacySettingsArgs, WorkspacePrivacyCommands};
+use crate::client::{api_base, api_error, SendWithRetry};
+use crate::commands::agent::{fetch_agent, fetch_all_agents};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+
+/// `retention_days` value meaning "keep forever"
+const UNLIMITED_RETENTION: i64 = -1;
+
+pub async fn agent_privacy(
+    client: &Client,
+    api_key: &str,
+    command: AgentPrivacyCommands,
+) -> Result<()> {
+    match command {
+        AgentPrivacyCommands::Get { agent_id } => {
+            let agent = fetch_agent(client, api_key, &agent_id).await?;
+            let privacy = &agent["platform_settings"]["privacy"];
+            if is_json_mode() {
+                println!("{}", serde_json::to_string_pretty(privacy)?);
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.set_header(vec!["Setting", "Value"]);
+            table.add_row(vec!["Retention", &retention_label(privacy)]);
+            table.add_row(vec!["Audio saved", &flag_label(&privacy["record_voice"])]);
+            table.add_row(vec![
+                "Zero retention",
+                &flag_label(&privacy["zero_retention_mode"]),
+            ]);
+            table.add_row(vec![
+                "Delete transcripts and PII",
+                &flag_label(&privacy["delete_transcript_and_pii"]),
+            ]);
+            println!("{}", table);
+            Ok(())
+        }
+        AgentPrivacyCommands::Set { agent_id, settings } => {
+            let privacy = privacy_patch(&settings)?;
+            patch_privacy(client, api_key, &agent_id, &privacy).await?;
+            print_success(&format!(
+                "Privacy settings updated for agent '{}'",
+                agent_id.cyan()
+            ));
+            Ok(())
+        }
+    }
+}
+
+pub async fn workspace_privacy(
+    client: &Client,
+    api_key: &str,
+    command: WorkspacePrivacyCommands,
+    assume_yes: bool,
+) -> Result<()> {
+    match command {
+        WorkspacePrivacyCommands::Get => {
+            print_info("Fetching agent privacy settings...");
+            let mut rows = Vec::new();
+            for (agent_id, name) in list_agents(client, api_key).await? {
+                let agent = fetch_agent(client, api_key, &agent_id).await?;
+                rows.push(json!({
+                    "agent_id": agent_id,
+                    "name": name,
+                    "privacy": agent["platform_settings"]["privacy"],
+                }));
+            }
+            if is_json_mode() {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.set_header(vec![
+                "Agent ID",
+                "Name",
+                "Retention",
+                "Audio",
+                "Zero retention",
+            ]);
+            for row in &rows {
+                let privacy = &row["privacy"];
+                table.add_row(vec![
+                    row["agent_id"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .yellow()
+                        .to_string(),
+                    row["name"].as_str().unwrap_or_default().to_string(),
+                    retention_label(privacy),
+                    flag_label(&privacy["record_voice"]),
+                    flag_label(&privacy["zero_retention_mode"]),
+                ]);
+            }
+            println!("{}", table);
+            Ok(())
+        }
+        WorkspacePrivacyCommands::Set { settings } => {
+            let privacy = privacy_patch(&settings)?;
+            let agents = list_agents(client, api_key).await?;
+            if agents.is_empty() {
+                print_info("No agents in the workspace");
+                return Ok(());
+            }
+
+            print_warning(&format!(
+                "This changes privacy settings on all {} agent(s)",
+                agents.len()
+            ));
+            if !assume_yes && !crate::prompt::confirm("Are you sure?")? {
+                print_info("Cancelled");
+                return Ok(());
+            }
+
+            let mut failed = 0;
+            for (agent_id, name) in &agents {
+                match patch_privacy(client, api_key, agent_id, &privacy).await {
+                    Ok(()) => print_info(&format!("Updated {} ({})", name, agent_id.yellow())),
+                    Err(e) => {
+                        print_warning(&format!("Failed to update {}: {:#}", agent_id, e));
+                        failed += 1;
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(anyhow::anyhow!(
+                    "{} of {} agent(s) could not be updated",
+                    failed,
+                    agents.len()
+                ));
+            }
+            print_success(&format!(
+                "Privacy settings updated on {} agent(s)",
+                agents.len()
+            ));
+            Ok(())
+        }
+    }
+}
+
+/// The `privacy` fields to change, from the flags that were given
+fn privacy_patch(settings: &PrivacySettingsArgs) -> Result<Value> {
+    let mut privacy = Map::new();
+    if let Some(days) = &settings.retention_days {
+        privacy.insert("retention_days".to_string(), json!(parse_retention(days)?));
+    }
+    if let Some(save) = settings.audio_save {
+        privacy.insert("record_voice".to_string(), json!(save));
+    }
+    if let Some(zero) = settings.zero_retention {
+        privacy.insert("zero_retention_mode".to_string(), json!(zero));
+    }
+    if let Some(delete) = settings.delete_transcripts {
+        privacy.insert("delete_transcript_and_pii".to_string(), json!(delete));
+    }
+    if settings.apply_to_existing {
+        if !privacy.contains_key("retention_days") {
+            return Err(anyhow::anyhow!(
+                "--apply-to-existing needs --retention-days"
+            ));
+        }
+        privacy.insert("apply_to_existing_conversations".to_string(), json!(true));
+    }
+    if privacy.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Nothing to change. Pass --retention-days, --audio-save, --zero-retention or --delete-transcripts"
+        ));
+    }
+    Ok(Value::Object(privacy))
+}
+
+fn parse_retention(days: &str) -> Result<i64> {
+    if days.eq_ignore_ascii_case("unlimited") {
+        return Ok(UNLIMITED_RETENTION);
+    }
+    match days.parse::<i64>() {
+        Ok(days) if days >= 0 => Ok(days),
+        _ => Err(anyhow::anyhow!(
+            "Invalid --retention-days '{}'. Use a number of days or 'unlimited'",
+            days
+        )),
+    }
+}
+
+fn retention_label(privacy: &Value) -> String {
+    match privacy["retention_days"].as_i64() {
+        Some(UNLIMITED_RETENTION) => "unlimited".to_string(),
+        Some(days) => format!("{} days", days),
+        None => "-".to_string(),
+    }
+}
+
+fn flag_label(value: &Value) -> String {
+    match value.as_bool() {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+async fn patch_privacy(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    privacy: &Value,
+) -> Result<()> {
+    let response = client
+        .patch(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .json(&json!({ "platform_settings": { "privacy": privacy } }))
+        .send_with_retry()
+        .await
+        .context("Failed to update agent")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(())
+}
+
+/// IDs and names of every agent, across all pages
+async fn list_agents(client: &Client, api_key: &str) -> Result<Vec<(String, String)>> {
+    Ok(fetch_all_agents(client, api_key)
+        .await?
+        .iter()
+        .filter_map(|agent| {
+            let id = agent["agent_id"].as_str()?.to_string();
+            let name = agent["name"].as_str().unwrap_or_default().to_string();
+            Some((id, name))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> PrivacySettingsArgs {
+        PrivacySettingsArgs {
+            retention_days: None,
+            audio_save: None,
+            zero_retention: None,
+            delete_transcripts: None,
+            apply_to_existing: false,
+        }
+    }
+
+    #[test]
+    fn test_privacy_patch_only_includes_given_flags() {
+        let mut args = settings();
+        args.retention_days = Some("30".to_string());
+        args.audio_save = Some(false);
+        assert_eq!(
+            privacy_patch(&args).unwrap(),
+            json!({ "retention_days": 30, "record_voice": false })
+        );
+
+        args.retention_days = Some("unlimited".to_string());
+        args.apply_to_existing = true;
+        let patch = privacy_patch(&args).unwrap();
+        assert_eq!(patch["retention_days"], UNLIMITED_RETENTION);
+        assert_eq!(patch["apply_to_existing_conversations"], true);
+    }
+
+    #[test]
+    fn test_privacy_patch_rejects_bad_input() {
+        assert!(privacy_patch(&settings()).is_err());
+        let mut args = settings();
+        args.retention_days = Some("-5".to_string());
+        assert!(privacy_patch(&args).is_err());
+        let mut args = settings();
+        args.audio_save = Some(true);
+        args.apply_to_existing = true;
+        assert!(privacy_patch(&args).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Agent transfer rules (`agent transfer`)
+//!
+//! Transfers live in two built-in system tools under
+//! `conversation_config.agent.prompt.built_in_tools`: `transfer_to_agent`
+//! hands the conversation to another agent and `transfer_to_number` to a
+//! phone number. Each holds a `transfers` list of target plus condition.
+//! Rules are edited on the fetched config and the whole tool is written back,
+//! keeping any other settings on it; a tool left without rules is removed.
+
+use crate::cli::{AgentTransferCommands, PhoneTransferType};
+use crate::client::{api_base, api_error, SendWithRetry};
+use crate::commands::agent::fetch_agent;
+use crate::output::{is_json_mode, print_info, print_success};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const TO_AGENT: &str = "transfer_to_agent";
+const TO_NUMBER: &str = "transfer_to_number";
+
+/// A transfer target given on the command line
+enum Target {
+    Agent(String),
+    Number(String),
+}
+
+impl Target {
+    fn from_args(to_agent: Option<String>, to_number: Option<String>) -> Result<Self> {
+        match (to_agent, to_number) {
+            (Some(agent_id), _) => Ok(Target::Agent(agent_id)),
+            (None, Some(number)) => {
+                validate_phone_number(&number)?;
+                Ok(Target::Number(number))
+            }
+            (None, None) => Err(anyhow::anyhow!("Pass --to-agent or --to-number")),
+        }
+    }
+
+    fn tool(&self) -> &'static str {
+        match self {
+            Target::Agent(_) => TO_AGENT,
+            Target::Number(_) => TO_NUMBER,
+        }
+    }
+
+    fn id(&self) -> &str {
+        match self {
+            Target::Agent(id) | Target::Number(id) => id,
+        }
+    }
+}
+
+pub async fn transfer_command(
+    client: &Client,
+    api_key: &str,
+    command: AgentTransferCommands,
+) -> Result<()> {
+    match command {
+        AgentTransferCommands::List { agent_id } => {
+            list_transfers(client, api_key, &agent_id).await
+        }
+        AgentTransferCommands::Set {
+            agent_id,
+            to_agent,
+            to_number,
+            condition,
+            message,
+            delay_ms,
+            transfer_type,
+        } => {
+            let target = Target::from_args(to_agent, to_number)?;
+            if condition.trim().is_empty() {
+                return Err(anyhow::anyhow!("--condition cannot be empty"));
+            }
+            let rule = transfer_rule(&target, &condition, message, delay_ms, transfer_type);
+            update_transfers(client, api_key, &agent_id, &target, Some(rule)).await
+        }
+        AgentTransferCommands::Remove {
+            agent_id,
+            to_agent,
+            to_number,
+        } => {
+            let target = Target::from_args(to_agent, to_number)?;
+            update_transfers(client, api_key, &agent_id, &target, None).await
+        }
+    }
+}
+
+async fn list_transfers(client: &Client, api_key: &str, agent_id: &str) -> Result<()> {
+    print_info(&format!(
+        "Fetching transfer rules for agent '{}'...",
+        agent_id.cyan()
+    ));
+    let agent = fetch_agent(client, api_key, agent_id).await?;
+    let rules: Vec<Value> = [TO_AGENT, TO_NUMBER]
+        .iter()
+        .flat_map(|tool| {
+            transfers(&agent, tool).into_iter().map(move |rule| {
+                json!({
+                    "type": if *tool == TO_AGENT { "agent" } else { "phone" },
+                    "target": rule_target(&rule).unwrap_or_default(),
+                    "condition": rule["condition"],
+                    "rule": rule,
+                })
+            })
+        })
+        .collect();
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+    if rules.is_empty() {
+        print_info("No transfer rules configured");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Type", "Target", "Condition"]);
+    for rule in &rules {
+        table.add_row(vec![
+            rule["type"].as_str().unwrap_or_default().to_string(),
+            rule["target"]
+                .as_str()
+                .unwrap_or_default()
+                .yellow()
+                .to_string(),
+            rule["condition"].as_str().unwrap_or_default().to_string(),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+/// Replace the rule for `target` with `rule`, or remove it when `rule` is `None`
+async fn update_transfers(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    target: &Target,
+    rule: Option<Value>,
+) -> Result<()> {
+    let agent = fetch_agent(client, api_key, agent_id).await?;
+    let tool_name = target.tool();
+    let mut rules = transfers(&agent, tool_name);
+    let before = rules.len();
+    rules.retain(|r| rule_target(r) != Some(target.id()));
+    let replaced = rules.len() < before;
+
+    let adding = rule.is_some();
+    if let Some(rule) = rule {
+        rules.push(rule);
+    } else if !replaced {
+        return Err(anyhow::anyhow!(
+            "Agent '{}' has no transfer rule for '{}'",
+            agent_id,
+            target.id()
+        ));
+    }
+
+    let tool = transfer_tool(tool_name, &built_in_tools(&agent)[tool_name], rules);
+    let body = json!({
+        "conversation_config": {
+            "agent": { "prompt": { "built_in_tools": { tool_name: tool } } }
+        }
+    });
+    let response = client
+        .patch(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to update agent")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let action = match (adding, replaced) {
+        (true, true) => "Updated",
+        (true, false) => "Added",
+        (false, _) => "Removed",
+    };
+    print_success(&format!(
+        "{} transfer to '{}' on agent '{}'",
+        action,
+        target.id().cyan(),
+        agent_id
+    ));
+    Ok(())
+}
+
+fn built_in_tools(agent: &Value) -> &Value {
+    &agent["conversation_config"]["agent"]["prompt"]["built_in_tools"]
+}
+
+/// Rules currently configured on one of the transfer tools
+fn transfers(agent: &Value, tool: &str) -> Vec<Value> {
+    built_in_tools(agent)[tool]["params"]["transfers"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Agent ID or phone number a rule transfers to
+fn rule_target(rule: &Value) -> Option<&str> {
+    rule["agent_id"]
+        .as_str()
+        .or_else(|| rule["transfer_destination"]["phone_number"].as_str())
+        .or_else(|| rule["phone_number"].as_str())
+}
+
+fn transfer_rule(
+    target: &Target,
+    condition: &str,
+    message: Option<String>,
+    delay_ms: Option<u32>,
+    transfer_type: Option<PhoneTransferType>,
+) -> Value {
+    match target {
+        Target::Agent(agent_id) => {
+            let mut rule = json!({ "agent_id": agent_id, "condition": condition });
+            if let Some(message) = message {
+                rule["transfer_message"] = json!(message);
+            }
+            if let Some(delay) = delay_ms {
+                rule["delay_ms"] = json!(delay);
+            }
+            rule
+        }
+        Target::Number(number) => {
+            let mut rule = json!({
+                "transfer_destination": { "type": "phone", "phone_number": number },
+                "condition": condition,
+            });
+            if let Some(transfer_type) = transfer_type {
+                rule["transfer_type"] = json!(match transfer_type {
+                    PhoneTransferType::Conference => "conference",
+                    PhoneTransferType::Blind => "blind",
+                });
+            }
+            rule
+        }
+    }
+}
+
+/// The tool to write back: `existing` with its rules replaced, a new tool when
+/// there was none, or `null` (removing it) when no rules are left
+fn transfer_tool(tool_name: &str, existing: &Value, rules: Vec<Value>) -> Value {
+    if rules.is_empty() {
+        return Value::Null;
+    }
+    let mut tool = if existing.is_object() {
+        existing.clone()
+    } else {
+        json!({
+            "name": tool_name,
+            "description": "",
+            "params": { "system_tool_type": tool_name },
+        })
+    };
+    tool["params"]["transfers"] = json!(rules);
+    tool
+}
+
+fn validate_phone_number(number: &str) -> Result<()> {
+    let digits = number.strip_prefix('+').unwrap_or("");
+    if (8..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid phone number '{}'. Use E.164 format, e.g. +14155550123",
+            number
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_tool_keeps_settings_and_replaces_rules() {
+        let agent = json!({
+            "conversation_config": { "agent": { "prompt": { "built_in_tools": {
+                "transfer_to_number": {
+                    "name": "transfer_to_number",
+                    "description": "Escalate",
+                    "params": {
+                        "system_tool_type": "transfer_to_number",
+                        "transfers": [
+                            { "phone_number": "+14155550123", "condition": "old" },
+                            { "transfer_destination": { "type": "phone", "phone_number": "+442071234567" }, "condition": "uk" },
+                        ]
+                    }
+                }
+            }}}}
+        });
+        let target = Target::Number("+14155550123".to_string());
+        let mut rules = transfers(&agent, TO_NUMBER);
+        rules.retain(|r| rule_target(r) != Some(target.id()));
+        rules.push(transfer_rule(
+            &target,
+            "caller asks for a human",
+            None,
+            None,
+            Some(PhoneTransferType::Blind),
+        ));
+
+        let tool = transfer_tool(TO_NUMBER, &built_in_tools(&agent)[TO_NUMBER], rules);
+        assert_eq!(tool["description"], "Escalate");
+        let rules = tool["params"]["transfers"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["condition"], "uk");
+        assert_eq!(rules[1]["transfer_type"], "blind");
+        assert_eq!(rule_target(&rules[1]), Some("+14155550123"));
+    }
+
+    #[test]
+    fn test_transfer_tool_created_and_removed() {
+        let rule = transfer_rule(
+            &Target::Agent("agent_billing".to_string()),
+            "billing question",
+            Some("One moment".to_string()),
+            Some(500),
+            None,
+        );
+        let tool = transfer_tool(TO_AGENT, &Value::Null, vec![rule]);
+        assert_eq!(tool["params"]["system_tool_type"], TO_AGENT);
+        assert_eq!(
+            tool["params"]["transfers"][0]["transfer_message"],
+            "One moment"
+        );
+        assert_eq!(tool["params"]["transfers"][0]["delay_ms"], 500);
+        assert!(transfer_tool(TO_AGENT, &tool, Vec::new()).is_null());
+    }
+
+    #[test]
+    fn test_validate_phone_number() {
+        assert!(validate_phone_number("+14155550123").is_ok());
+        assert!(validate_phone_number("14155550123").is_err());
+        assert!(validate_phone_number("+1 415 555").is_err());
+    }
+}
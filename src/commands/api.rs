@@ -0,0 +1,120 @@
+//! Raw API calls (`api`)
+//!
+//! An escape hatch for endpoints the CLI doesn't wrap yet: the request goes
+//! out with the configured API key, retry policy and any `--header`/`--query`
+//! passthrough, and the response body is printed as-is (pretty-printed when
+//! it is JSON).
+
+use crate::cli::ApiArgs;
+use crate::client::{api_base, api_error, create_http_client, SendWithRetry};
+use crate::output::print_success;
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Method;
+use serde_json::Value;
+
+pub async fn execute(args: ApiArgs, api_key: &str) -> Result<()> {
+    let method: Method = args
+        .method
+        .to_uppercase()
+        .parse()
+        .context("Invalid HTTP method")?;
+    let url = endpoint_url(api_base(), &args.path)?;
+    let body = args.data.as_deref().map(read_body).transpose()?;
+
+    let client = create_http_client();
+    let mut request = client.request(method, url).header("xi-api-key", api_key);
+    if let Some(body) = body {
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+    }
+    let response = request
+        .send_with_retry()
+        .await
+        .context("Failed to send request")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let is_text = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_none_or(|ct| ct.contains("json") || ct.starts_with("text/"));
+    let bytes = response.bytes().await.context("Failed to read response")?;
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, &bytes).with_context(|| format!("Failed to write {}", path))?;
+        print_success(&format!("Response saved to {}", path.cyan()));
+        return Ok(());
+    }
+    if !is_text {
+        return Err(anyhow::anyhow!(
+            "The response is binary ({} bytes); use --output <FILE> to save it",
+            bytes.len()
+        ));
+    }
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(json) => println!("{}", serde_json::to_string_pretty(&json)?),
+        Err(_) => println!("{}", String::from_utf8_lossy(&bytes)),
+    }
+    Ok(())
+}
+
+/// Full URL for an endpoint path, which must stay on the API host
+fn endpoint_url(base: &str, path: &str) -> Result<String> {
+    let path = path.trim();
+    if path.contains("://") {
+        return Err(anyhow::anyhow!(
+            "Give an endpoint path such as /v1/voices, not a full URL"
+        ));
+    }
+    if path.is_empty() || path == "/" {
+        return Err(anyhow::anyhow!("The endpoint path is empty"));
+    }
+    Ok(format!("{}/{}", base, path.trim_start_matches('/')))
+}
+
+/// The `--data` body: inline JSON, `@FILE`, or `@-` for stdin
+fn read_body(data: &str) -> Result<String> {
+    let body = match data.strip_prefix('@') {
+        Some("-") => {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+                .context("Failed to read request body from stdin")?;
+            content
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read request body from {}", path))?,
+        None => data.to_string(),
+    };
+    serde_json::from_str::<Value>(&body).context("The request body is not valid JSON")?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_url() {
+        let base = "https://api.elevenlabs.io";
+        assert_eq!(
+            endpoint_url(base, "/v1/voices").unwrap(),
+            "https://api.elevenlabs.io/v1/voices"
+        );
+        assert_eq!(
+            endpoint_url(base, "v1/user").unwrap(),
+            "https://api.elevenlabs.io/v1/user"
+        );
+        assert!(endpoint_url(base, "https://example.com/v1").is_err());
+        assert!(endpoint_url(base, "/").is_err());
+    }
+
+    #[test]
+    fn test_read_body_checks_json() {
+        assert_eq!(read_body(r#"{"a": 1}"#).unwrap(), r#"{"a": 1}"#);
+        assert!(read_body("{not json").is_err());
+    }
+}
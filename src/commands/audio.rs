@@ -0,0 +1,120 @@
+//! Local audio generators: tones and silence for padding and test fixtures
+
+use crate::cli::{AudioArgs, AudioCommands};
+use crate::output::{print_info, print_success};
+use crate::pcm::{encode_local, local_rate, silence, sine_tone};
+use crate::utils::{
+    confirm_overwrite, default_output_path, generate_output_filename, write_bytes_to_file,
+};
+use anyhow::Result;
+use colored::*;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// Longest clip the generators write
+const MAX_DURATION_SECS: f64 = 600.0;
+
+/// Sample rates the generators write
+const SAMPLE_RATES: RangeInclusive<u32> = 8_000..=192_000;
+
+pub fn execute(args: AudioArgs, assume_yes: bool) -> Result<()> {
+    let (samples, rate, format, output, prefix, description) = match args.command {
+        AudioCommands::Tone {
+            freq,
+            duration,
+            volume,
+            format,
+            output,
+        } => {
+            let rate = clip_rate(&format, duration)?;
+            if !(freq > 0.0 && freq < rate as f64 / 2.0) {
+                return Err(anyhow::anyhow!(
+                    "--freq must be between 0 and {} Hz for {}",
+                    rate / 2,
+                    format
+                ));
+            }
+            if !(0.0..=1.0).contains(&volume) {
+                return Err(anyhow::anyhow!("--volume must be between 0 and 1"));
+            }
+            let samples = sine_tone(freq, duration, rate, volume);
+            let description = format!("{}s {} Hz tone", duration, freq);
+            (samples, rate, format, output, "tone", description)
+        }
+        AudioCommands::Silence {
+            duration,
+            format,
+            output,
+        } => {
+            let rate = clip_rate(&format, duration)?;
+            let samples = silence(duration, rate);
+            let description = format!("{}s of silence", duration);
+            (samples, rate, format, output, "silence", description)
+        }
+    };
+
+    let audio = encode_local(&samples, rate, &format)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported format '{}'", format))?;
+    // wav, pcm, ulaw or alaw
+    let extension = format.split('_').next().unwrap_or("wav");
+
+    let output_path = match output {
+        Some(output) => output,
+        None => default_output_path(&generate_output_filename(prefix, extension), None)?,
+    };
+    let path = Path::new(&output_path);
+    if !confirm_overwrite(path, assume_yes)? {
+        print_info("Cancelled");
+        return Ok(());
+    }
+
+    write_bytes_to_file(&audio, path)?;
+    print_success(&format!(
+        "Wrote {} ({}) -> {}",
+        description,
+        format,
+        output_path.green()
+    ));
+    Ok(())
+}
+
+/// Sample rate of the output format, after checking the duration
+fn clip_rate(format: &str, duration: f64) -> Result<u32> {
+    if !(duration > 0.0 && duration <= MAX_DURATION_SECS) {
+        return Err(anyhow::anyhow!(
+            "--duration must be between 0 and {} seconds",
+            MAX_DURATION_SECS
+        ));
+    }
+    let rate = local_rate(format).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unsupported format '{}'. Use wav_<rate>, pcm_<rate>, ulaw_8000 or alaw_8000",
+            format
+        )
+    })?;
+    if !SAMPLE_RATES.contains(&rate) {
+        return Err(anyhow::anyhow!(
+            "Sample rate {} Hz in '{}' is out of range. Use {} to {} Hz",
+            rate,
+            format,
+            SAMPLE_RATES.start(),
+            SAMPLE_RATES.end()
+        ));
+    }
+    Ok(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_rate() {
+        assert_eq!(clip_rate("wav_44100", 1.0).unwrap(), 44_100);
+        assert_eq!(clip_rate("ulaw_8000", 1.0).unwrap(), 8_000);
+        assert!(clip_rate("wav_0", 1.0).is_err());
+        let error = clip_rate("pcm_4000000000", 1.0).unwrap_err();
+        assert!(error.to_string().contains("out of range"), "{}", error);
+        assert!(clip_rate("wav_44100", MAX_DURATION_SECS + 1.0).is_err());
+    }
+}
@@ -1,12 +1,12 @@
+use crate::article::fetch_article;
 use crate::cli::{AudioNativeArgs, AudioNativeCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 pub async fn execute(args: AudioNativeArgs, api_key: &str) -> Result<()> {
@@ -27,11 +27,30 @@ pub async fn execute(args: AudioNativeArgs, api_key: &str) -> Result<()> {
             voice_id,
             model_id,
             file,
+            url,
             small,
             text_color,
             background_color,
             auto_convert,
         } => {
+            let (content, title, author) = match (file, url) {
+                (Some(file), _) => (Some(read_content_file(&file)?), title, author),
+                (None, Some(url)) => {
+                    let article = fetch_article(&url).await?;
+                    print_info(&format!(
+                        "Extracted article: {} ({} words)",
+                        article.title.as_deref().unwrap_or(&url).cyan(),
+                        article.word_count()
+                    ));
+                    let content = ("article.html".to_string(), article.to_html().into_bytes());
+                    (
+                        Some(content),
+                        title.or(article.title),
+                        author.or(article.byline),
+                    )
+                }
+                (None, None) => (None, title, author),
+            };
             create_audio_native(
                 &client,
                 api_key,
@@ -41,7 +60,7 @@ pub async fn execute(args: AudioNativeArgs, api_key: &str) -> Result<()> {
                 image.as_deref(),
                 voice_id.as_deref(),
                 model_id.as_deref(),
-                file.as_deref(),
+                content,
                 small,
                 text_color.as_deref(),
                 background_color.as_deref(),
@@ -55,7 +74,7 @@ pub async fn execute(args: AudioNativeArgs, api_key: &str) -> Result<()> {
 async fn list_audio_native(client: &Client, api_key: &str, limit: u32, page: u32) -> Result<()> {
     print_info("Fetching audio native projects...");
 
-    let url = "https://api.elevenlabs.io/v1/audio-native";
+    let url = format!("{}/v1/audio-native", api_base());
     let request = client
         .get(url)
         .header("xi-api-key", api_key)
@@ -63,17 +82,20 @@ async fn list_audio_native(client: &Client, api_key: &str, limit: u32, page: u32
         .query(&[("page", page.to_string())]);
 
     let response = request
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch audio native projects")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: AudioNativeListResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: AudioNativeListResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.projects)?);
+        return Ok(());
+    }
 
     let projects = result.projects;
 
@@ -113,19 +135,23 @@ async fn get_audio_native(client: &Client, api_key: &str, project_id: &str) -> R
         project_id.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/audio-native/{}", project_id);
+    let url = format!("{}/v1/audio-native/{}", api_base(), project_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let project: AudioNativeProject = response.json().await.context("Failed to parse response")?;
+    let project: AudioNativeProject = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&project)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -161,6 +187,20 @@ async fn get_audio_native(client: &Client, api_key: &str, project_id: &str) -> R
     Ok(())
 }
 
+/// Read a local content file as (file name, bytes) for upload
+fn read_content_file(file: &str) -> Result<(String, Vec<u8>)> {
+    let path = Path::new(file);
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", file));
+    }
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", file))?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| file.to_string());
+    Ok((file_name, bytes))
+}
+
 async fn create_audio_native(
     client: &Client,
     api_key: &str,
@@ -170,88 +210,59 @@ async fn create_audio_native(
     image: Option<&str>,
     voice_id: Option<&str>,
     model_id: Option<&str>,
-    file: Option<&str>,
+    content: Option<(String, Vec<u8>)>,
     small: bool,
     text_color: Option<&str>,
     background_color: Option<&str>,
     auto_convert: bool,
 ) -> Result<()> {
-    // Check if file exists if provided
-    if let Some(file_path) = file {
-        let path = Path::new(file_path);
-        if !path.exists() {
-            return Err(anyhow::anyhow!("File not found: {}", file_path));
-        }
-    }
-
     print_info(&format!(
         "Creating Audio Native project '{}'...",
         name.cyan()
     ));
 
-    // Build body
-    let mut body = json!({
-        "name": name
-    });
-
-    if let Some(author) = author {
-        body["author"] = json!(author);
-    }
-
-    if let Some(title) = title {
-        body["title"] = json!(title);
-    }
-
-    if let Some(image) = image {
-        body["image"] = json!(image);
-    }
-
-    if let Some(voice_id) = voice_id {
-        body["voice_id"] = json!(voice_id);
-    }
-
-    if let Some(model_id) = model_id {
-        body["model_id"] = json!(model_id);
-    }
-
-    if let Some(file) = file {
-        // For file upload, we need to handle it differently
-        // This is a simplified version - the actual implementation would need multipart upload
-        body["file"] = json!(file);
+    let mut form = reqwest::multipart::Form::new().text("name", name.to_string());
+    let fields = [
+        ("author", author),
+        ("title", title),
+        ("image", image),
+        ("voice_id", voice_id),
+        ("model_id", model_id),
+        ("text_color", text_color),
+        ("background_color", background_color),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            form = form.text(key, value.to_string());
+        }
     }
-
     if small {
-        body["small"] = json!(true);
+        form = form.text("small", "true");
     }
-
-    if let Some(text_color) = text_color {
-        body["text_color"] = json!(text_color);
-    }
-
-    if let Some(background_color) = background_color {
-        body["background_color"] = json!(background_color);
-    }
-
     if auto_convert {
-        body["auto_convert"] = json!(true);
+        form = form.text("auto_convert", "true");
+    }
+    if let Some((file_name, bytes)) = content {
+        form = form.part(
+            "file",
+            reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+        );
     }
 
-    let url = "https://api.elevenlabs.io/v1/audio-native";
+    let url = format!("{}/v1/audio-native", api_base());
     let response = client
         .post(url)
         .header("xi-api-key", api_key)
-        .json(&body)
-        .send()
+        .multipart(form)
+        .send_with_retry()
         .await
         .context("Failed to create audio native project")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: AudioNativeCreateResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: AudioNativeCreateResponse = read_json(response).await?;
 
     print_success(&format!("Audio Native project created: '{}'", name));
     println!("  Project ID: {}", result.project_id.cyan());
@@ -266,12 +277,12 @@ async fn create_audio_native(
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AudioNativeListResponse {
     projects: Vec<AudioNativeProject>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AudioNativeProject {
     project_id: String,
     name: String,
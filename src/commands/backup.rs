@@ -0,0 +1,1036 @@
+//! Account backup
+//!
+//! `export` writes each resource as raw API JSON into a directory, with a
+//! `manifest.json` listing every item and any errors:
+//!
+//! ```text
+//! backup/
+//!   manifest.json
+//!   settings.json
+//!   voices/<voice_id>/voice.json
+//!   voices/<voice_id>/samples/<sample_id>_<file_name>
+//!   agents/<agent_id>.json
+//!   knowledge/<document_id>.json
+//!   dictionaries/<dictionary_id>.json
+//!   dictionaries/<dictionary_id>.pls
+//!   webhooks/<webhook_id>.json
+//! ```
+//!
+//! `import` reads the manifest and re-creates each item in the current
+//! account, rewriting references to voices, knowledge documents and
+//! dictionaries inside agent configs to the new IDs. Created items are
+//! recorded in a job state file, so rerunning an import that failed partway
+//! reuses them instead of creating duplicates.
+
+use crate::cli::{BackupSection, ExportArgs, ImportArgs};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::config::Config;
+use crate::job_state::JobState;
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::utils::{sanitize_path_component, unix_now};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Backup layout version written to the manifest
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const ALL_SECTIONS: &[BackupSection] = &[
+    BackupSection::Settings,
+    BackupSection::Voices,
+    BackupSection::Dictionaries,
+    BackupSection::Knowledge,
+    BackupSection::Agents,
+    BackupSection::Webhooks,
+];
+
+/// Backup contents, read back by `import`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct BackupManifest {
+    pub format_version: u32,
+    pub cli_version: String,
+    pub created_at: u64,
+    pub items: Vec<BackupItem>,
+    #[serde(default)]
+    pub errors: Vec<BackupError>,
+}
+
+/// One exported resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BackupItem {
+    pub section: String,
+    pub id: String,
+    pub name: String,
+    /// Metadata file, relative to the backup directory
+    pub path: String,
+    /// Extra files (samples, PLS), relative to the backup directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BackupError {
+    pub section: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub message: String,
+}
+
+pub(crate) fn section_name(section: BackupSection) -> &'static str {
+    match section {
+        BackupSection::Voices => "voices",
+        BackupSection::Agents => "agents",
+        BackupSection::Knowledge => "knowledge",
+        BackupSection::Dictionaries => "dictionaries",
+        BackupSection::Webhooks => "webhooks",
+        BackupSection::Settings => "settings",
+    }
+}
+
+/// Import order, so agents are created after what they reference
+const IMPORT_ORDER: &[BackupSection] = &[
+    BackupSection::Dictionaries,
+    BackupSection::Voices,
+    BackupSection::Knowledge,
+    BackupSection::Agents,
+    BackupSection::Webhooks,
+    BackupSection::Settings,
+];
+
+/// Writes files under the backup directory and records them in the manifest
+struct BackupWriter {
+    root: PathBuf,
+    manifest: BackupManifest,
+}
+
+impl BackupWriter {
+    fn write(&self, relative: &str, contents: &[u8]) -> Result<()> {
+        let path = self.root.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn write_json(&self, relative: &str, value: &Value) -> Result<()> {
+        self.write(relative, serde_json::to_string_pretty(value)?.as_bytes())
+    }
+
+    fn error(&mut self, section: BackupSection, id: Option<&str>, error: &anyhow::Error) {
+        print_warning(&format!(
+            "{}{}: {:#}",
+            section_name(section),
+            id.map(|id| format!(" {}", id)).unwrap_or_default(),
+            error
+        ));
+        self.manifest.errors.push(BackupError {
+            section: section_name(section).to_string(),
+            id: id.map(str::to_string),
+            message: format!("{:#}", error),
+        });
+    }
+}
+
+pub async fn export(args: ExportArgs, api_key: &str, config: &Config) -> Result<()> {
+    let created_at = unix_now();
+    let root = PathBuf::from(
+        args.out
+            .unwrap_or_else(|| format!("elevenlabs-backup-{}", created_at)),
+    );
+    if root.join("manifest.json").exists() {
+        return Err(anyhow::anyhow!(
+            "'{}' already contains a backup. Choose an empty directory",
+            root.display()
+        ));
+    }
+    std::fs::create_dir_all(&root)
+        .with_context(|| format!("Failed to create {}", root.display()))?;
+
+    let sections: Vec<BackupSection> = if args.only.is_empty() {
+        ALL_SECTIONS.to_vec()
+    } else {
+        ALL_SECTIONS
+            .iter()
+            .copied()
+            .filter(|s| args.only.contains(s))
+            .collect()
+    };
+
+    let client = create_http_client();
+    let mut writer = BackupWriter {
+        root: root.clone(),
+        manifest: BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at,
+            ..Default::default()
+        },
+    };
+
+    for section in sections {
+        print_info(&format!("Exporting {}...", section_name(section)));
+        let result = match section {
+            BackupSection::Settings => export_settings(&mut writer, config),
+            BackupSection::Voices => {
+                export_voices(&client, api_key, &mut writer, !args.no_samples).await
+            }
+            BackupSection::Agents => export_agents(&client, api_key, &mut writer).await,
+            BackupSection::Knowledge => export_knowledge(&client, api_key, &mut writer).await,
+            BackupSection::Dictionaries => export_dictionaries(&client, api_key, &mut writer).await,
+            BackupSection::Webhooks => export_webhooks(&client, api_key, &mut writer).await,
+        };
+        if let Err(e) = result {
+            writer.error(section, None, &e);
+        }
+    }
+
+    writer.write_json("manifest.json", &serde_json::to_value(&writer.manifest)?)?;
+
+    let manifest = &writer.manifest;
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "directory": root.display().to_string(),
+                "items": manifest.items.len(),
+                "errors": manifest.errors,
+            }))?
+        );
+    } else {
+        for section in ALL_SECTIONS {
+            let count = manifest
+                .items
+                .iter()
+                .filter(|i| i.section == section_name(*section))
+                .count();
+            if count > 0 {
+                println!("  {}: {}", section_name(*section), count);
+            }
+        }
+    }
+
+    if manifest.errors.is_empty() {
+        if !is_json_mode() {
+            print_success(&format!(
+                "Exported {} item(s) -> {}",
+                manifest.items.len(),
+                root.display().to_string().green()
+            ));
+        }
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Export finished with {} error(s); see {}",
+            manifest.errors.len(),
+            root.join("manifest.json").display()
+        ))
+    }
+}
+
+/// Every item of a list endpoint, following `next_cursor` while `has_more`
+async fn fetch_list(
+    client: &Client,
+    api_key: &str,
+    endpoint: &str,
+    list_key: &str,
+) -> Result<Vec<Value>> {
+    let url = format!("{}{}", api_base(), endpoint);
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = client.get(&url).header("xi-api-key", api_key);
+        if let Some(c) = &cursor {
+            request = request.query(&[("cursor", c)]);
+        }
+        let response = request
+            .send_with_retry()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let page: Value = read_json(response).await?;
+        items.extend(list_field(&page, list_key));
+
+        cursor = page["next_cursor"].as_str().map(str::to_string);
+        if !page["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            return Ok(items);
+        }
+    }
+}
+
+async fn get_bytes(client: &Client, api_key: &str, url: &str) -> Result<Vec<u8>> {
+    let response = client
+        .get(url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// The list inside a response that is either a bare array or `{ "<key>": [...] }`
+fn list_field(body: &Value, key: &str) -> Vec<Value> {
+    match body {
+        Value::Array(items) => items.clone(),
+        _ => body[key].as_array().cloned().unwrap_or_default(),
+    }
+}
+
+fn str_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|k| value[*k].as_str())
+}
+
+/// CLI defaults worth carrying to another machine (never the API key)
+fn export_settings(writer: &mut BackupWriter, config: &Config) -> Result<()> {
+    let settings = json!({
+        "default_voice": config.default_voice,
+        "default_model": config.default_model,
+        "default_output_format": config.default_output_format,
+    });
+    writer.write_json("settings.json", &settings)?;
+    writer.manifest.items.push(BackupItem {
+        section: "settings".to_string(),
+        id: "settings".to_string(),
+        name: "CLI settings".to_string(),
+        path: "settings.json".to_string(),
+        files: Vec::new(),
+    });
+    Ok(())
+}
+
+async fn export_voices(
+    client: &Client,
+    api_key: &str,
+    writer: &mut BackupWriter,
+    samples: bool,
+) -> Result<()> {
+    let voices = fetch_list(client, api_key, "/v1/voices", "voices").await?;
+
+    // Premade voices exist in every account
+    for voice in voices
+        .iter()
+        .filter(|v| v["category"].as_str() != Some("premade"))
+    {
+        let Some(voice_id) = voice["voice_id"].as_str() else {
+            continue;
+        };
+        let dir = format!("voices/{}", sanitize_path_component(voice_id));
+        let path = format!("{}/voice.json", dir);
+        writer.write_json(&path, voice)?;
+
+        let mut files = Vec::new();
+        if samples {
+            for sample in voice["samples"].as_array().into_iter().flatten() {
+                let Some(sample_id) = sample["sample_id"].as_str() else {
+                    continue;
+                };
+                let file_name = sample["file_name"].as_str().unwrap_or("sample.mp3");
+                let relative = format!(
+                    "{}/samples/{}_{}",
+                    dir,
+                    sanitize_path_component(sample_id),
+                    sanitize_path_component(file_name)
+                );
+                let url = format!(
+                    "{}/v1/voices/{}/samples/{}/audio",
+                    api_base(),
+                    voice_id,
+                    sample_id
+                );
+                match get_bytes(client, api_key, &url).await {
+                    Ok(audio) => {
+                        writer.write(&relative, &audio)?;
+                        files.push(relative);
+                    }
+                    Err(e) => writer.error(BackupSection::Voices, Some(voice_id), &e),
+                }
+            }
+        }
+
+        writer.manifest.items.push(BackupItem {
+            section: "voices".to_string(),
+            id: voice_id.to_string(),
+            name: voice["name"].as_str().unwrap_or_default().to_string(),
+            path,
+            files,
+        });
+    }
+    Ok(())
+}
+
+async fn export_agents(client: &Client, api_key: &str, writer: &mut BackupWriter) -> Result<()> {
+    for agent in fetch_list(client, api_key, "/v1/agents", "agents").await? {
+        let Some(agent_id) = agent["agent_id"].as_str() else {
+            continue;
+        };
+        let url = format!("{}/v1/agents/{}", api_base(), agent_id);
+        let detail: Result<Value> = async {
+            let response = client
+                .get(&url)
+                .header("xi-api-key", api_key)
+                .send_with_retry()
+                .await
+                .with_context(|| format!("Failed to fetch {}", url))?;
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+            read_json(response).await
+        }
+        .await;
+        let detail = match detail {
+            Ok(detail) => detail,
+            Err(e) => {
+                writer.error(BackupSection::Agents, Some(agent_id), &e);
+                continue;
+            }
+        };
+        let path = format!("agents/{}.json", sanitize_path_component(agent_id));
+        writer.write_json(&path, &detail)?;
+        writer.manifest.items.push(BackupItem {
+            section: "agents".to_string(),
+            id: agent_id.to_string(),
+            name: str_field(&detail, &["name"])
+                .or(agent["name"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            path,
+            files: Vec::new(),
+        });
+    }
+    Ok(())
+}
+
+async fn export_knowledge(client: &Client, api_key: &str, writer: &mut BackupWriter) -> Result<()> {
+    let documents = fetch_list(client, api_key, "/v1/convai/knowledge-base", "documents").await?;
+
+    for document in documents {
+        let Some(document_id) = str_field(&document, &["id", "document_id"]) else {
+            continue;
+        };
+        let url = format!("{}/v1/convai/knowledge-base/{}", api_base(), document_id);
+        let detail: Result<Value> = async {
+            let response = client
+                .get(&url)
+                .header("xi-api-key", api_key)
+                .send_with_retry()
+                .await
+                .with_context(|| format!("Failed to fetch {}", url))?;
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+            read_json(response).await
+        }
+        .await;
+        let detail = match detail {
+            Ok(detail) => detail,
+            Err(e) => {
+                writer.error(BackupSection::Knowledge, Some(document_id), &e);
+                continue;
+            }
+        };
+        let path = format!("knowledge/{}.json", sanitize_path_component(document_id));
+        writer.write_json(&path, &detail)?;
+        writer.manifest.items.push(BackupItem {
+            section: "knowledge".to_string(),
+            id: document_id.to_string(),
+            name: str_field(&detail, &["name"])
+                .or(document["name"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            path,
+            files: Vec::new(),
+        });
+    }
+    Ok(())
+}
+
+async fn export_dictionaries(
+    client: &Client,
+    api_key: &str,
+    writer: &mut BackupWriter,
+) -> Result<()> {
+    let dictionaries = fetch_list(
+        client,
+        api_key,
+        "/v1/pronunciation-dictionaries",
+        "pronunciation_dictionaries",
+    )
+    .await?;
+
+    for dictionary in dictionaries {
+        let Some(dictionary_id) = dictionary["id"].as_str() else {
+            continue;
+        };
+        let base = format!("dictionaries/{}", sanitize_path_component(dictionary_id));
+        let path = format!("{}.json", base);
+        writer.write_json(&path, &dictionary)?;
+
+        let mut files = Vec::new();
+        let url = format!(
+            "{}/v1/pronunciation/dictionaries/{}/pls",
+            api_base(),
+            dictionary_id
+        );
+        match get_bytes(client, api_key, &url).await {
+            Ok(pls) => {
+                let relative = format!("{}.pls", base);
+                writer.write(&relative, &pls)?;
+                files.push(relative);
+            }
+            Err(e) => writer.error(BackupSection::Dictionaries, Some(dictionary_id), &e),
+        }
+
+        writer.manifest.items.push(BackupItem {
+            section: "dictionaries".to_string(),
+            id: dictionary_id.to_string(),
+            name: dictionary["name"].as_str().unwrap_or_default().to_string(),
+            path,
+            files,
+        });
+    }
+    Ok(())
+}
+
+async fn export_webhooks(client: &Client, api_key: &str, writer: &mut BackupWriter) -> Result<()> {
+    for webhook in fetch_list(client, api_key, "/v1/webhooks", "webhooks").await? {
+        let Some(webhook_id) = str_field(&webhook, &["id", "webhook_id"]) else {
+            continue;
+        };
+        let path = format!("webhooks/{}.json", sanitize_path_component(webhook_id));
+        writer.write_json(&path, &webhook)?;
+        writer.manifest.items.push(BackupItem {
+            section: "webhooks".to_string(),
+            id: webhook_id.to_string(),
+            name: webhook["name"].as_str().unwrap_or_default().to_string(),
+            path,
+            files: Vec::new(),
+        });
+    }
+    Ok(())
+}
+
+/// What happened to one backup item during import
+#[derive(Debug, Serialize)]
+struct ImportResult {
+    section: String,
+    name: String,
+    old_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_id: Option<String>,
+    status: &'static str,
+}
+
+enum ImportOutcome {
+    Created(String),
+    Applied,
+    Unsupported(String),
+}
+
+pub async fn import(args: ImportArgs, api_key: &str) -> Result<()> {
+    let root = PathBuf::from(&args.dir);
+    let manifest_path = root.join("manifest.json");
+    let contents = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: BackupManifest = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid backup manifest {}", manifest_path.display()))?;
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(anyhow::anyhow!(
+            "Backup format {} was written by a newer CLI ({}); upgrade to import it",
+            manifest.format_version,
+            manifest.cli_version
+        ));
+    }
+
+    let client = create_http_client();
+    let mut id_map: HashMap<String, String> = HashMap::new();
+    let mut results = Vec::new();
+
+    let canonical = std::fs::canonicalize(&root).unwrap_or_else(|_| root.clone());
+    let mut state = JobState::open("import", api_key, &[&canonical.to_string_lossy()]);
+    if state.completed_count() > 0 {
+        print_info(&format!(
+            "Resuming: {} item(s) were created by an earlier run{}",
+            state.completed_count(),
+            state
+                .path()
+                .map(|p| format!(" (state in {})", p.display()))
+                .unwrap_or_default()
+        ));
+    }
+
+    let sections = IMPORT_ORDER
+        .iter()
+        .copied()
+        .filter(|s| args.only.is_empty() || args.only.contains(s));
+    for section in sections {
+        let items: Vec<&BackupItem> = manifest
+            .items
+            .iter()
+            .filter(|i| i.section == section_name(section))
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        print_info(&format!(
+            "Importing {} {}...",
+            items.len(),
+            section_name(section)
+        ));
+
+        let existing = if args.skip_existing && section != BackupSection::Settings {
+            existing_by_name(&client, api_key, section)
+                .await
+                .with_context(|| format!("Failed to list existing {}", section_name(section)))?
+        } else {
+            HashMap::new()
+        };
+
+        for item in items {
+            let mut result = ImportResult {
+                section: section_name(section).to_string(),
+                name: item.name.clone(),
+                old_id: item.id.clone(),
+                new_id: None,
+                status: "",
+            };
+
+            let state_key = format!("{}/{}", section_name(section), item.id);
+            if let Some(done_id) = state.completed(&state_key) {
+                id_map.insert(item.id.clone(), done_id.to_string());
+                result.new_id = Some(done_id.to_string());
+                result.status = "resumed";
+            } else if let Some(existing_id) = existing.get(&item.name) {
+                id_map.insert(item.id.clone(), existing_id.clone());
+                result.new_id = Some(existing_id.clone());
+                result.status = "exists";
+            } else if args.dry_run {
+                result.status = "would create";
+            } else {
+                let outcome = import_item(
+                    &client,
+                    api_key,
+                    &root,
+                    section,
+                    item,
+                    &id_map,
+                    args.skip_existing,
+                )
+                .await;
+                match outcome {
+                    Ok(ImportOutcome::Created(new_id)) => {
+                        if let Err(e) = state.mark_done(&state_key, &new_id) {
+                            print_warning(&format!("{:#}", e));
+                        }
+                        id_map.insert(item.id.clone(), new_id.clone());
+                        result.new_id = Some(new_id);
+                        result.status = "created";
+                    }
+                    Ok(ImportOutcome::Applied) => result.status = "applied",
+                    Ok(ImportOutcome::Unsupported(reason)) => {
+                        print_warning(&format!(
+                            "Skipping {} '{}': {}",
+                            section_name(section),
+                            item.name,
+                            reason
+                        ));
+                        result.status = "skipped";
+                    }
+                    Err(e) => {
+                        print_warning(&format!(
+                            "Failed to import {} '{}': {:#}",
+                            section_name(section),
+                            item.name,
+                            e
+                        ));
+                        result.status = "failed";
+                    }
+                }
+            }
+            results.push(result);
+        }
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else if results.is_empty() {
+        print_info("Nothing to import");
+    } else {
+        let mut table = Table::new();
+        table.set_header(vec!["Type", "Name", "Old ID", "New ID", "Status"]);
+        for r in &results {
+            let status = match r.status {
+                "created" | "applied" | "resumed" => r.status.green(),
+                "failed" => r.status.red(),
+                _ => r.status.yellow(),
+            };
+            table.add_row(vec![
+                r.section.as_str().into(),
+                r.name.cyan(),
+                r.old_id.as_str().into(),
+                r.new_id.as_deref().unwrap_or("-").yellow(),
+                status,
+            ]);
+        }
+        println!("{}", table);
+    }
+
+    let failed = results.iter().filter(|r| r.status == "failed").count();
+    if failed > 0 {
+        if !args.dry_run {
+            print_info("Run the same import again to retry; items already created are skipped");
+        }
+        return Err(anyhow::anyhow!(
+            "Import failed for {} of {} item(s)",
+            failed,
+            results.len()
+        ));
+    }
+    if !args.dry_run {
+        state.finish();
+    }
+    if !args.dry_run && !is_json_mode() {
+        let created = results
+            .iter()
+            .filter(|r| r.status == "created" || r.status == "resumed")
+            .count();
+        print_success(&format!(
+            "Import complete: {} created, {} already existed",
+            created,
+            results.iter().filter(|r| r.status == "exists").count()
+        ));
+    }
+    Ok(())
+}
+
+/// Existing resources in the target account, by name
+async fn existing_by_name(
+    client: &Client,
+    api_key: &str,
+    section: BackupSection,
+) -> Result<HashMap<String, String>> {
+    let (endpoint, list_key, id_keys): (&str, &str, &[&str]) = match section {
+        BackupSection::Voices => ("/v1/voices", "voices", &["voice_id"]),
+        BackupSection::Agents => ("/v1/agents", "agents", &["agent_id"]),
+        BackupSection::Knowledge => (
+            "/v1/convai/knowledge-base",
+            "documents",
+            &["id", "document_id"],
+        ),
+        BackupSection::Dictionaries => (
+            "/v1/pronunciation-dictionaries",
+            "pronunciation_dictionaries",
+            &["id"],
+        ),
+        BackupSection::Webhooks => ("/v1/webhooks", "webhooks", &["id", "webhook_id"]),
+        BackupSection::Settings => return Ok(HashMap::new()),
+    };
+    Ok(fetch_list(client, api_key, endpoint, list_key)
+        .await?
+        .iter()
+        .filter_map(|item| {
+            Some((
+                item["name"].as_str()?.to_string(),
+                str_field(item, id_keys)?.to_string(),
+            ))
+        })
+        .collect())
+}
+
+async fn import_item(
+    client: &Client,
+    api_key: &str,
+    root: &Path,
+    section: BackupSection,
+    item: &BackupItem,
+    id_map: &HashMap<String, String>,
+    keep_existing_settings: bool,
+) -> Result<ImportOutcome> {
+    let path = backup_path(root, &item.path)?;
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let data: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid JSON in {}", path.display()))?;
+
+    let created = match section {
+        BackupSection::Dictionaries => {
+            let Some(pls) = item.files.iter().find(|f| f.ends_with(".pls")) else {
+                return Ok(ImportOutcome::Unsupported(
+                    "backup has no PLS file".to_string(),
+                ));
+            };
+            let mut form = reqwest::multipart::Form::new()
+                .text("name", item.name.clone())
+                .part("file", file_part(root, pls)?);
+            if let Some(description) = data["description"].as_str() {
+                form = form.text("description", description.to_string());
+            }
+            post_multipart(
+                client,
+                api_key,
+                "/v1/pronunciation-dictionaries/add-from-file",
+                form,
+            )
+            .await?
+        }
+        BackupSection::Voices => {
+            if item.files.is_empty() {
+                return Ok(ImportOutcome::Unsupported(
+                    "backup has no samples to clone from".to_string(),
+                ));
+            }
+            let mut form = reqwest::multipart::Form::new().text("name", item.name.clone());
+            if let Some(description) = data["description"].as_str() {
+                form = form.text("description", description.to_string());
+            }
+            if data["labels"].as_object().is_some_and(|l| !l.is_empty()) {
+                form = form.text("labels", data["labels"].to_string());
+            }
+            for sample in &item.files {
+                form = form.part("files", file_part(root, sample)?);
+            }
+            post_multipart(client, api_key, "/v1/voices/add", form).await?
+        }
+        BackupSection::Knowledge => {
+            let body = match data["type"].as_str() {
+                Some("url") => json!({
+                    "name": item.name,
+                    "type": "url",
+                    "url": str_field(&data, &["url"])
+                        .ok_or_else(|| anyhow::anyhow!("URL document has no url"))?,
+                }),
+                _ => json!({
+                    "name": item.name,
+                    "type": "text",
+                    "content": str_field(&data, &["extracted_inner_html", "content"])
+                        .ok_or_else(|| anyhow::anyhow!("document has no content"))?,
+                }),
+            };
+            post_json(client, api_key, "/v1/convai/knowledge-base", &body).await?
+        }
+        BackupSection::Agents => {
+            let mut body = json!({ "name": item.name });
+            for key in ["conversation_config", "platform_settings", "tags"] {
+                if !data[key].is_null() {
+                    body[key] = data[key].clone();
+                }
+            }
+            remap_ids(&mut body, id_map);
+            post_json(client, api_key, "/v1/agents", &body).await?
+        }
+        BackupSection::Webhooks => {
+            let body = json!({
+                "name": item.name,
+                "url": str_field(&data, &["url", "webhook_url"])
+                    .ok_or_else(|| anyhow::anyhow!("webhook has no url"))?,
+                "events": data["events"],
+            });
+            post_json(client, api_key, "/v1/webhooks", &body).await?
+        }
+        BackupSection::Settings => {
+            import_settings(&data, id_map, keep_existing_settings)?;
+            return Ok(ImportOutcome::Applied);
+        }
+    };
+
+    str_field(&created, &["voice_id", "agent_id", "webhook_id", "id"])
+        .map(|id| ImportOutcome::Created(id.to_string()))
+        .ok_or_else(|| anyhow::anyhow!("Response did not include the new ID"))
+}
+
+/// Apply exported CLI defaults to the local config
+fn import_settings(
+    settings: &Value,
+    id_map: &HashMap<String, String>,
+    keep_existing: bool,
+) -> Result<()> {
+    Config::update(|config| {
+        for (key, current) in [
+            ("default_voice", &mut config.default_voice),
+            ("default_model", &mut config.default_model),
+            ("default_output_format", &mut config.default_output_format),
+        ] {
+            let Some(value) = settings[key].as_str() else {
+                continue;
+            };
+            if keep_existing && current.is_some() {
+                continue;
+            }
+            *current = Some(
+                id_map
+                    .get(value)
+                    .cloned()
+                    .unwrap_or_else(|| value.to_string()),
+            );
+        }
+        Ok(())
+    })
+}
+
+/// Replace every string equal to an old ID with its new ID
+fn remap_ids(value: &mut Value, id_map: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new_id) = id_map.get(s.as_str()) {
+                *s = new_id.clone();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| remap_ids(v, id_map)),
+        Value::Object(map) => map.values_mut().for_each(|v| remap_ids(v, id_map)),
+        _ => {}
+    }
+}
+
+/// A file named by the manifest, which must stay inside the backup: a
+/// crafted manifest could otherwise upload any local file
+fn backup_path(root: &Path, relative: &str) -> Result<PathBuf> {
+    let root = std::fs::canonicalize(root)
+        .with_context(|| format!("Failed to read {}", root.display()))?;
+    let path = root.join(relative);
+    let resolved = std::fs::canonicalize(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if !resolved.starts_with(&root) {
+        return Err(anyhow::anyhow!(
+            "Backup file '{}' is outside the backup directory",
+            relative
+        ));
+    }
+    Ok(resolved)
+}
+
+fn file_part(root: &Path, relative: &str) -> Result<reqwest::multipart::Part> {
+    let path = backup_path(root, relative)?;
+    let bytes =
+        std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(reqwest::multipart::Part::bytes(bytes).file_name(file_name))
+}
+
+async fn post_json(client: &Client, api_key: &str, endpoint: &str, body: &Value) -> Result<Value> {
+    let url = format!("{}{}", api_base(), endpoint);
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .json(body)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to post to {}", url))?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    read_json(response).await
+}
+
+async fn post_multipart(
+    client: &Client,
+    api_key: &str,
+    endpoint: &str,
+    form: reqwest::multipart::Form,
+) -> Result<Value> {
+    let url = format!("{}{}", api_base(), endpoint);
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to post to {}", url))?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    read_json(response).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_field_accepts_both_shapes() {
+        let bare = json!([{ "id": "a" }]);
+        let wrapped = json!({ "webhooks": [{ "id": "a" }, { "id": "b" }] });
+        assert_eq!(list_field(&bare, "webhooks").len(), 1);
+        assert_eq!(list_field(&wrapped, "webhooks").len(), 2);
+        assert!(list_field(&json!({}), "webhooks").is_empty());
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            cli_version: "0.0.0".to_string(),
+            created_at: 1,
+            items: vec![BackupItem {
+                section: "voices".to_string(),
+                id: "v1".to_string(),
+                name: "Narrator".to_string(),
+                path: "voices/v1/voice.json".to_string(),
+                files: vec!["voices/v1/samples/s1_take.mp3".to_string()],
+            }],
+            errors: Vec::new(),
+        };
+        let text = serde_json::to_string(&manifest).unwrap();
+        let parsed: BackupManifest = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed.items, manifest.items);
+        assert!(!text.contains("\"errors\":null"));
+    }
+
+    #[test]
+    fn test_backup_path_stays_inside_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("backup");
+        std::fs::create_dir_all(root.join("voices")).unwrap();
+        std::fs::write(root.join("voices/take.mp3"), b"audio").unwrap();
+        std::fs::write(dir.path().join("secret.txt"), b"key").unwrap();
+
+        assert!(backup_path(&root, "voices/take.mp3").is_ok());
+        assert!(backup_path(&root, "voices/../voices/take.mp3").is_ok());
+        for path in [
+            "../secret.txt",
+            "voices/../../secret.txt",
+            dir.path().join("secret.txt").to_str().unwrap(),
+        ] {
+            let error = backup_path(&root, path).unwrap_err();
+            assert!(error.to_string().contains("outside"), "{}: {}", path, error);
+        }
+    }
+
+    #[test]
+    fn test_remap_ids_rewrites_nested_references() {
+        let map = HashMap::from([
+            ("v_old".to_string(), "v_new".to_string()),
+            ("kb_old".to_string(), "kb_new".to_string()),
+        ]);
+        let mut config = json!({
+            "tts": { "voice_id": "v_old" },
+            "agent": { "prompt": {
+                "prompt": "mention v_old by name",
+                "knowledge_base": [{ "id": "kb_old", "type": "url" }]
+            }}
+        });
+        remap_ids(&mut config, &map);
+        assert_eq!(config["tts"]["voice_id"], "v_new");
+        assert_eq!(
+            config["agent"]["prompt"]["knowledge_base"][0]["id"],
+            "kb_new"
+        );
+        // Only whole values are IDs
+        assert_eq!(config["agent"]["prompt"]["prompt"], "mention v_old by name");
+    }
+}
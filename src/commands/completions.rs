@@ -0,0 +1,155 @@
+use crate::cli::{CompletionsArgs, CompletionsCommands};
+use crate::output::{print_info, print_success};
+use anyhow::{Context, Result};
+use clap_complete::{generate, Shell};
+use colored::*;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn execute(args: CompletionsArgs, mut cmd: clap::Command) -> Result<()> {
+    match (args.command, args.shell) {
+        (Some(CompletionsCommands::Install { shell, dir }), _) => {
+            let shell = match shell {
+                Some(s) => s,
+                None => detect_shell()?,
+            };
+            install_completions(shell, dir.as_deref(), &mut cmd)
+        }
+        (None, Some(shell)) => {
+            let name = cmd.get_name().to_string();
+            generate(shell, &mut cmd, name, &mut io::stdout());
+            Ok(())
+        }
+        (None, None) => Err(anyhow::anyhow!(
+            "Specify a shell (e.g. `completions zsh`) or use `completions install`"
+        )),
+    }
+}
+
+/// Detect the user's shell from $SHELL
+fn detect_shell() -> Result<Shell> {
+    let shell_path = env::var("SHELL").unwrap_or_default();
+    Shell::from_env().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not detect shell from $SHELL ('{}'). Use --shell",
+            shell_path
+        )
+    })
+}
+
+fn home_dir() -> Result<PathBuf> {
+    directories::BaseDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))
+}
+
+/// Read an XDG base directory variable, falling back to `$HOME/<default>`
+fn xdg_dir(var: &str, default: &str) -> Result<PathBuf> {
+    match env::var_os(var) {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(home_dir()?.join(default)),
+    }
+}
+
+/// Whether a bash-completion (v2) installation is present to auto-load user completions
+fn has_bash_completion() -> bool {
+    [
+        "/usr/share/bash-completion/bash_completion",
+        "/etc/bash_completion",
+        "/opt/homebrew/etc/profile.d/bash_completion.sh",
+        "/usr/local/etc/profile.d/bash_completion.sh",
+    ]
+    .iter()
+    .any(|p| Path::new(p).exists())
+}
+
+/// Resolve the completion directory and file name for a shell, plus an optional setup hint
+fn completion_target(shell: Shell, name: &str) -> Result<(PathBuf, String, Option<String>)> {
+    match shell {
+        Shell::Bash => {
+            let file = name.to_string();
+            if has_bash_completion() {
+                let dir = xdg_dir("XDG_DATA_HOME", ".local/share")?
+                    .join("bash-completion")
+                    .join("completions");
+                Ok((dir, file, None))
+            } else {
+                let dir = home_dir()?.join(".bash_completion.d");
+                let hint = format!(
+                    "bash-completion not found; add to ~/.bashrc: source {}",
+                    dir.join(&file).display()
+                );
+                Ok((dir, file, Some(hint)))
+            }
+        }
+        Shell::Zsh => {
+            let file = format!("_{}", name);
+            // oh-my-zsh adds $ZSH/completions to fpath automatically
+            let omz = env::var_os("ZSH").map(PathBuf::from).or_else(|| {
+                home_dir()
+                    .ok()
+                    .map(|h| h.join(".oh-my-zsh"))
+                    .filter(|p| p.exists())
+            });
+            match omz {
+                Some(dir) => Ok((dir.join("completions"), file, None)),
+                None => {
+                    let dir = home_dir()?.join(".zsh").join("completions");
+                    let hint = format!(
+                        "Add to ~/.zshrc before compinit: fpath=({} $fpath)",
+                        dir.display()
+                    );
+                    Ok((dir, file, Some(hint)))
+                }
+            }
+        }
+        Shell::Fish => {
+            let dir = xdg_dir("XDG_CONFIG_HOME", ".config")?
+                .join("fish")
+                .join("completions");
+            Ok((dir, format!("{}.fish", name), None))
+        }
+        other => Err(anyhow::anyhow!(
+            "Automatic install is not supported for {}. Use `{} completions {} > <file>` instead",
+            other,
+            name,
+            other
+        )),
+    }
+}
+
+fn install_completions(shell: Shell, dir: Option<&str>, cmd: &mut clap::Command) -> Result<()> {
+    let name = cmd.get_name().to_string();
+    let (detected_dir, file_name, hint) = completion_target(shell, &name)?;
+    let (target_dir, hint) = match dir {
+        Some(d) => (PathBuf::from(d), None),
+        None => (detected_dir, hint),
+    };
+
+    std::fs::create_dir_all(&target_dir).with_context(|| {
+        format!(
+            "Failed to create completions directory: {}",
+            target_dir.display()
+        )
+    })?;
+
+    let mut script = Vec::new();
+    generate(shell, cmd, name, &mut script);
+
+    let path = target_dir.join(file_name);
+    std::fs::write(&path, script)
+        .with_context(|| format!("Failed to write completions to {}", path.display()))?;
+
+    print_success(&format!(
+        "Installed {} completions -> {}",
+        shell,
+        path.display().to_string().green()
+    ));
+    if let Some(hint) = hint {
+        print_info(&hint);
+    }
+    print_info("Restart your shell to enable completions");
+
+    Ok(())
+}
@@ -1,18 +1,68 @@
-use crate::cli::{ConfigArgs, ConfigCommands};
-use crate::config::Config;
-use crate::output::print_success;
+use crate::cli::{ConfigArgs, ConfigCommands, ProfileCommands};
+use crate::config::{Config, KeyStorage, Profile};
+use crate::credentials;
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::paths::{self, DirKind};
 use anyhow::Result;
 use colored::*;
+use comfy_table::Table;
+use serde_json::json;
 
 pub fn execute(args: ConfigArgs, config: &mut Config) -> Result<()> {
     match args.command {
         ConfigCommands::Show => show_config(config),
+        ConfigCommands::Paths => show_paths(),
         ConfigCommands::Set { key, value } => set_config(config, &key, &value),
+        ConfigCommands::SetKey { key, plaintext } => set_key(config, key, plaintext),
         ConfigCommands::Unset { key } => unset_config(config, &key),
+        ConfigCommands::Profile { command } => match command {
+            ProfileCommands::Add {
+                name,
+                key,
+                plaintext,
+                default_voice,
+                default_model,
+                default_output_format,
+            } => add_profile(
+                &name,
+                key.as_deref(),
+                plaintext,
+                Profile {
+                    default_voice,
+                    default_model,
+                    default_output_format,
+                    ..Default::default()
+                },
+            ),
+            ProfileCommands::List => list_profiles(config),
+            ProfileCommands::Use { name } => use_profile(&name),
+            ProfileCommands::Remove { name } => remove_profile(&name),
+        },
     }
 }
 
 fn show_config(config: &Config) -> Result<()> {
+    if is_json_mode() {
+        // The key itself is never printed, only whether it is set
+        let json = serde_json::json!({
+            "config_file": Config::config_path()?,
+            "profile": config.profile,
+            "api_key_set": config.api_key.is_some(),
+            "api_key_in_keyring": config.api_key_in_keyring,
+            "default_voice": config.default_voice,
+            "default_model": config.default_model,
+            "default_output_format": config.default_output_format,
+            "color": config.color,
+            "ascii_symbols": config.ascii_symbols,
+            "output_dir": config.output_dir,
+            "organize": config.organize,
+            "player_cmd": config.player_cmd,
+            "clean_input": config.clean_input,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
     println!("{}", "Current Configuration:".bold().underline());
 
     let config_path = Config::config_path()?;
@@ -21,13 +71,18 @@ fn show_config(config: &Config) -> Result<()> {
         config_path.display().to_string().dimmed()
     );
 
+    if let Some(ref profile) = config.profile {
+        println!("  Profile: {}", profile.cyan());
+    }
+
     println!("\n  Values:");
     println!(
         "    api_key: {}",
-        if config.api_key.is_some() {
-            "[set]".green().to_string()
-        } else {
-            "[not set]".red().to_string()
+        match (&config.api_key, &config.keyring_error) {
+            (Some(_), _) if config.api_key_in_keyring => "[set, in keyring]".green().to_string(),
+            (Some(_), _) => "[set]".green().to_string(),
+            (None, Some(error)) => format!("[keyring unreadable: {}]", error).red().to_string(),
+            (None, None) => "[not set]".red().to_string(),
         }
     );
 
@@ -49,14 +104,80 @@ fn show_config(config: &Config) -> Result<()> {
             .unwrap_or("[not set]")
     );
 
+    println!(
+        "    color: {}",
+        config.color.as_deref().unwrap_or("[not set]")
+    );
+
+    println!(
+        "    ascii_symbols: {}",
+        config
+            .ascii_symbols
+            .map(|b| b.to_string())
+            .unwrap_or_else(|| "[not set]".to_string())
+    );
+
+    println!(
+        "    output_dir: {}",
+        config.output_dir.as_deref().unwrap_or("[not set]")
+    );
+
+    println!(
+        "    organize: {}",
+        config.organize.as_deref().unwrap_or("[not set]")
+    );
+
+    println!(
+        "    player_cmd: {}",
+        config.player_cmd.as_deref().unwrap_or("[not set]")
+    );
+
+    println!(
+        "    clean_input: {}",
+        config.clean_input.as_deref().unwrap_or("[not set]")
+    );
+
+    Ok(())
+}
+
+fn show_paths() -> Result<()> {
+    let entries = [
+        ("config", Config::config_path().ok()),
+        ("cache", paths::dir(DirKind::Cache)),
+        ("data", paths::dir(DirKind::Data)),
+    ];
+
+    if is_json_mode() {
+        let map: serde_json::Map<String, serde_json::Value> = entries
+            .iter()
+            .map(|(name, path)| {
+                (
+                    name.to_string(),
+                    path.as_ref()
+                        .map(|p| p.display().to_string().into())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&map)?);
+        return Ok(());
+    }
+
+    println!("{}", "Storage Locations:".bold().underline());
+    for (name, path) in entries {
+        let path = path
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "[unavailable]".to_string());
+        println!("  {:<7} {}", format!("{}:", name), path.cyan());
+    }
     Ok(())
 }
 
 fn set_config(config: &mut Config, key: &str, value: &str) -> Result<()> {
     match key {
         "api_key" => {
-            config.set(key, value)?;
-            print_success(&format!("Set '{}' to [hidden]", key));
+            let storage = config.set_api_key(value, false)?;
+            report_key_storage("API key", &storage);
         }
         _ => {
             config.set(key, value)?;
@@ -66,8 +187,161 @@ fn set_config(config: &mut Config, key: &str, value: &str) -> Result<()> {
     Ok(())
 }
 
+fn set_key(config: &mut Config, key: Option<String>, plaintext: bool) -> Result<()> {
+    let key = match key {
+        Some(key) => key,
+        None => crate::prompt::password("API key")?,
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow::anyhow!("API key cannot be empty"));
+    }
+    let storage = config.set_api_key(key, plaintext)?;
+    report_key_storage("API key", &storage);
+    Ok(())
+}
+
+fn report_key_storage(what: &str, storage: &KeyStorage) {
+    match storage {
+        KeyStorage::Keyring => print_success(&format!("Stored the {} in the OS keyring", what)),
+        KeyStorage::ConfigFile { keyring_error } => {
+            if let Some(error) = keyring_error {
+                print_warning(&format!("{}; falling back to the config file", error));
+            }
+            print_success(&format!("Stored the {} in the config file", what));
+        }
+    }
+}
+
 fn unset_config(config: &mut Config, key: &str) -> Result<()> {
     config.unset(key)?;
     print_success(&format!("Unset '{}'", key));
     Ok(())
 }
+
+fn add_profile(name: &str, key: Option<&str>, plaintext: bool, values: Profile) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(anyhow::anyhow!("Profile name cannot be empty"));
+    }
+
+    let (existed, storage) = Config::update(|config| {
+        let existed = config.profiles.contains_key(name);
+        let profile = config.profiles.entry(name.to_string()).or_default();
+        let storage = key.map(|key| Config::store_profile_key(profile, name, key, plaintext));
+        if values.default_voice.is_some() {
+            profile.default_voice = values.default_voice;
+        }
+        if values.default_model.is_some() {
+            profile.default_model = values.default_model;
+        }
+        if values.default_output_format.is_some() {
+            profile.default_output_format = values.default_output_format;
+        }
+        Ok((existed, storage))
+    })?;
+
+    if let Some(storage) = storage {
+        report_key_storage(&format!("API key of '{}'", name), &storage);
+    }
+    if existed {
+        print_success(&format!("Updated profile '{}'", name.green()));
+    } else {
+        print_success(&format!("Added profile '{}'", name.green()));
+        print_info(&format!(
+            "Select it with --profile {} or `config profile use {}`",
+            name, name
+        ));
+    }
+    Ok(())
+}
+
+fn list_profiles(config: &Config) -> Result<()> {
+    if is_json_mode() {
+        let profiles: Vec<_> = config
+            .profiles
+            .iter()
+            .map(|(name, profile)| {
+                json!({
+                    "name": name,
+                    "in_use": config.profile.as_deref() == Some(name.as_str()),
+                    "active": config.active_profile.as_deref() == Some(name.as_str()),
+                    "api_key": profile.has_api_key(),
+                    "default_voice": profile.default_voice,
+                    "default_model": profile.default_model,
+                    "default_output_format": profile.default_output_format,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&profiles)?);
+        return Ok(());
+    }
+
+    if config.profiles.is_empty() {
+        print_info("No profiles. Add one with `config profile add <NAME> --key <API_KEY>`");
+        return Ok(());
+    }
+
+    let unset = || "-".to_string();
+    let mut table = Table::new();
+    table.set_header(vec!["", "Profile", "API Key", "Voice", "Model", "Format"]);
+    for (name, profile) in &config.profiles {
+        let marker = if config.profile.as_deref() == Some(name.as_str()) {
+            "*"
+        } else {
+            ""
+        };
+        table.add_row(vec![
+            marker.to_string(),
+            name.yellow().to_string(),
+            if profile.has_api_key() {
+                "[set]".to_string()
+            } else {
+                unset()
+            },
+            profile.default_voice.clone().unwrap_or_else(unset),
+            profile.default_model.clone().unwrap_or_else(unset),
+            profile.default_output_format.clone().unwrap_or_else(unset),
+        ]);
+    }
+    println!("{}", table);
+    if let Some(ref active) = config.active_profile {
+        print_info(&format!("Active profile: {}", active));
+    }
+    Ok(())
+}
+
+fn use_profile(name: &str) -> Result<()> {
+    Config::update(|config| {
+        if !config.profiles.contains_key(name) {
+            return Err(anyhow::anyhow!(
+                "Unknown profile '{}' (see `config profile list`)",
+                name
+            ));
+        }
+        config.active_profile = Some(name.to_string());
+        Ok(())
+    })?;
+    print_success(&format!("Now using profile '{}'", name.green()));
+    Ok(())
+}
+
+fn remove_profile(name: &str) -> Result<()> {
+    let was_active = Config::update(|config| {
+        let Some(profile) = config.profiles.remove(name) else {
+            return Err(anyhow::anyhow!("Unknown profile '{}'", name));
+        };
+        if profile.api_key_in_keyring {
+            credentials::delete(&credentials::profile_account(name))?;
+        }
+        let was_active = config.active_profile.as_deref() == Some(name);
+        if was_active {
+            config.active_profile = None;
+        }
+        Ok(was_active)
+    })?;
+    print_success(&format!("Removed profile '{}'", name.green()));
+    if was_active {
+        print_info("It was the active profile; the top-level values apply again");
+    }
+    Ok(())
+}
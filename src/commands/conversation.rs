@@ -1,34 +1,71 @@
 use crate::cli::{ConversationArgs, ConversationCommands, ConverseArgs};
-use crate::client::create_http_client;
-use crate::output::{print_error, print_info, print_success, print_warning};
-use crate::utils::{confirm_overwrite, write_bytes_to_file};
+use crate::client::{api_base, api_error, create_http_client, read_json, ws_base, SendWithRetry};
+use crate::commands::converse_talk;
+use crate::output::{is_json_mode, print_error, print_info, print_success, print_warning};
+use crate::pcm::{pcm_sample_rate, wav_header};
+use crate::utils::{
+    confirm_overwrite, format_to_extension, parse_dynamic_vars, parse_time_spec,
+    write_bytes_to_file,
+};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
-use dialoguer::Confirm;
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::io::{self, Write};
-use std::path::Path;
-use std::time::Duration;
+use std::fs::File;
+use std::io::{self, IsTerminal, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
 /// WebSocket connection timeout in seconds
 const WS_CONNECT_TIMEOUT_SECS: u64 = 30;
 
+/// Interval between client keepalive pings in seconds
+const WS_KEEPALIVE_INTERVAL_SECS: u64 = 15;
+
+/// Treat the connection as dead after this long without any server traffic
+const WS_KEEPALIVE_TIMEOUT_SECS: u64 = 45;
+
+/// Number of recent messages replayed as context after a reconnect
+const RECONNECT_CONTEXT_MESSAGES: usize = 20;
+
+/// How long a scripted chat waits for the agent's first message
+const GREETING_WAIT_SECS: u64 = 5;
+
+/// Quiet time after an agent response before its turn counts as finished
+const TURN_SETTLE_MS: u64 = 1_000;
+
 /// Conversation command dispatcher
 pub async fn execute(args: ConversationArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     match args.command {
         ConversationCommands::Converse(converse_args) => {
-            start_websocket_conversation(converse_args).await
+            start_websocket_conversation(converse_args, api_key).await
         }
+        ConversationCommands::Talk(talk_args) => converse_talk::execute(talk_args, api_key).await,
         ConversationCommands::List {
             agent_id,
             branch_id,
             limit,
-        } => list_conversations(&api_key, agent_id.as_deref(), branch_id.as_deref(), limit).await,
+            status,
+            call_successful,
+            since,
+            until,
+        } => {
+            let filter = ConversationListFilter {
+                agent_id,
+                branch_id,
+                limit,
+                status,
+                call_successful,
+                since,
+                until,
+            };
+            list_conversations(&api_key, &filter).await
+        }
         ConversationCommands::Get { conversation_id } => {
             get_conversation(&api_key, &conversation_id).await
         }
@@ -46,8 +83,16 @@ pub async fn execute(args: ConversationArgs, api_key: &str, assume_yes: bool) ->
         ConversationCommands::Audio {
             conversation_id,
             output,
+            waveform,
         } => {
-            get_conversation_audio(&api_key, &conversation_id, output.as_deref(), assume_yes).await
+            get_conversation_audio(
+                &api_key,
+                &conversation_id,
+                output.as_deref(),
+                waveform,
+                assume_yes,
+            )
+            .await
         }
         ConversationCommands::Feedback {
             conversation_id,
@@ -66,47 +111,85 @@ pub async fn execute(args: ConversationArgs, api_key: &str, assume_yes: bool) ->
     }
 }
 
-/// List conversations
-async fn list_conversations(
-    api_key: &str,
-    agent_id: Option<&str>,
-    branch_id: Option<&str>,
+/// Filters for `converse list`
+#[derive(Debug, Default)]
+struct ConversationListFilter {
+    agent_id: Option<String>,
+    branch_id: Option<String>,
     limit: Option<u32>,
-) -> Result<()> {
-    let client = create_http_client();
-    print_info("Fetching conversations...");
-
-    let mut url = "https://api.elevenlabs.io/v1/convai/conversations".to_string();
-    let mut params = Vec::new();
+    /// Not supported by the API; applied to the fetched page
+    status: Option<String>,
+    call_successful: Option<bool>,
+    since: Option<String>,
+    until: Option<String>,
+}
 
-    if let Some(aid) = agent_id {
-        params.push(format!("agent_id={}", aid));
-    }
-    if let Some(bid) = branch_id {
-        params.push(format!("branch_id={}", bid));
-    }
-    if let Some(lim) = limit {
-        params.push(format!("page_size={}", lim));
+impl ConversationListFilter {
+    /// Query parameters for the conversations endpoint
+    fn query_params(&self, now_unix: u64) -> Result<Vec<(&'static str, String)>> {
+        let mut params = Vec::new();
+        if let Some(aid) = &self.agent_id {
+            params.push(("agent_id", aid.clone()));
+        }
+        if let Some(bid) = &self.branch_id {
+            params.push(("branch_id", bid.clone()));
+        }
+        if let Some(lim) = self.limit {
+            params.push(("page_size", lim.to_string()));
+        }
+        if let Some(successful) = self.call_successful {
+            let value = if successful { "success" } else { "failure" };
+            params.push(("call_successful", value.to_string()));
+        }
+        let after = self
+            .since
+            .as_deref()
+            .map(|s| parse_time_spec(s, now_unix))
+            .transpose()?;
+        let before = self
+            .until
+            .as_deref()
+            .map(|s| parse_time_spec(s, now_unix))
+            .transpose()?;
+        if let (Some(after), Some(before)) = (after, before) {
+            if after >= before {
+                return Err(anyhow::anyhow!("--since must be earlier than --until"));
+            }
+        }
+        if let Some(after) = after {
+            params.push(("call_start_after_unix", after.to_string()));
+        }
+        if let Some(before) = before {
+            params.push(("call_start_before_unix", before.to_string()));
+        }
+        Ok(params)
     }
+}
 
-    if !params.is_empty() {
-        url.push('?');
-        url.push_str(&params.join("&"));
-    }
+/// List conversations
+async fn list_conversations(api_key: &str, filter: &ConversationListFilter) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let params = filter.query_params(now)?;
+
+    let client = create_http_client();
+    print_info("Fetching conversations...");
 
     let response = client
-        .get(&url)
+        .get(format!("{}/v1/convai/conversations", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .query(&params)
+        .send_with_retry()
         .await
         .context("Failed to fetch conversations")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct ConversationListItem {
         conversation_id: String,
         #[serde(default)]
@@ -116,16 +199,28 @@ async fn list_conversations(
         #[serde(default)]
         status: Option<String>,
         #[serde(default)]
+        call_successful: Option<String>,
+        #[serde(default)]
         created_at: Option<String>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct ConversationsResponse {
         conversations: Vec<ConversationListItem>,
     }
 
-    let result: ConversationsResponse =
-        response.json().await.context("Failed to parse response")?;
+    let mut result: ConversationsResponse = read_json(response).await?;
+
+    if let Some(status) = &filter.status {
+        result
+            .conversations
+            .retain(|c| c.status.as_deref() == Some(status.as_str()));
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.conversations)?);
+        return Ok(());
+    }
 
     if result.conversations.is_empty() {
         print_info("No conversations found");
@@ -138,6 +233,7 @@ async fn list_conversations(
         "Agent",
         "Version",
         "Status",
+        "Outcome",
         "Created",
     ]);
 
@@ -160,6 +256,11 @@ async fn list_conversations(
             agent.cyan(),
             version.into(),
             conv.status.as_deref().unwrap_or("-").into(),
+            match conv.call_successful.as_deref() {
+                Some("success") => "success".green(),
+                Some("failure") => "failure".red(),
+                other => other.unwrap_or("-").into(),
+            },
             conv.created_at.as_deref().unwrap_or("-").into(),
         ]);
     }
@@ -180,22 +281,18 @@ async fn get_conversation(api_key: &str, conversation_id: &str) -> Result<()> {
         conversation_id.cyan()
     ));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}",
-        conversation_id
-    );
+    let url = format!("{}/v1/convai/conversations/{}", api_base(), conversation_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct ConversationDetail {
         conversation_id: String,
         #[serde(default)]
@@ -210,7 +307,7 @@ async fn get_conversation(api_key: &str, conversation_id: &str) -> Result<()> {
         transcript: Option<Vec<TranscriptMessage>>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct TranscriptMessage {
         role: String,
         content: String,
@@ -218,7 +315,12 @@ async fn get_conversation(api_key: &str, conversation_id: &str) -> Result<()> {
         timestamp: Option<String>,
     }
 
-    let conv: ConversationDetail = response.json().await?;
+    let conv: ConversationDetail = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&conv)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -259,14 +361,32 @@ async fn get_conversation(api_key: &str, conversation_id: &str) -> Result<()> {
 
 /// Get signed URL for conversation
 async fn get_signed_url(api_key: &str, agent_id: &str, branch_id: Option<&str>) -> Result<()> {
-    let client = create_http_client();
     print_info(&format!(
         "Getting signed URL for agent '{}'...",
         agent_id.cyan()
     ));
 
+    let signed_url = fetch_signed_url(api_key, agent_id, branch_id).await?;
+    if is_json_mode() {
+        println!("{}", json!({ "signed_url": signed_url }));
+        return Ok(());
+    }
+    print_success("Signed URL generated:");
+    println!("{}", signed_url.green());
+
+    Ok(())
+}
+
+/// Fetch a signed websocket URL for an agent
+async fn fetch_signed_url(
+    api_key: &str,
+    agent_id: &str,
+    branch_id: Option<&str>,
+) -> Result<String> {
+    let client = create_http_client();
     let mut url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversation/get-signed-url?agent_id={}",
+        "{}/v1/convai/conversation/get-signed-url?agent_id={}",
+        api_base(),
         agent_id
     );
 
@@ -277,12 +397,11 @@ async fn get_signed_url(api_key: &str, agent_id: &str, branch_id: Option<&str>)
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     #[derive(Deserialize)]
@@ -290,11 +409,8 @@ async fn get_signed_url(api_key: &str, agent_id: &str, branch_id: Option<&str>)
         signed_url: String,
     }
 
-    let result: SignedUrlResponse = response.json().await?;
-    print_success("Signed URL generated:");
-    println!("{}", result.signed_url.green());
-
-    Ok(())
+    let result: SignedUrlResponse = read_json(response).await?;
+    Ok(result.signed_url)
 }
 
 /// Get conversation token
@@ -303,14 +419,42 @@ async fn get_conversation_token(
     agent_id: &str,
     branch_id: Option<&str>,
 ) -> Result<()> {
-    let client = create_http_client();
     print_info(&format!(
         "Getting conversation token for agent '{}'...",
         agent_id.cyan()
     ));
 
+    let result = fetch_conversation_token(api_key, agent_id, branch_id).await?;
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+    print_success("Conversation token generated:");
+    println!("Token: {}", result.token.yellow());
+    if let Some(expires) = &result.expires_at {
+        println!("Expires: {}", expires);
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize)]
+struct TokenResponse {
+    token: String,
+    #[serde(default)]
+    expires_at: Option<String>,
+}
+
+/// Fetch a short-lived conversation token for an agent
+async fn fetch_conversation_token(
+    api_key: &str,
+    agent_id: &str,
+    branch_id: Option<&str>,
+) -> Result<TokenResponse> {
+    let client = create_http_client();
     let mut url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversation/token?agent_id={}",
+        "{}/v1/convai/conversation/token?agent_id={}",
+        api_base(),
         agent_id
     );
 
@@ -321,185 +465,815 @@ async fn get_conversation_token(
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
-    struct TokenResponse {
-        token: String,
-        #[serde(default)]
-        expires_at: Option<String>,
-    }
-
-    let result: TokenResponse = response.json().await?;
-    print_success("Conversation token generated:");
-    println!("Token: {}", result.token.yellow());
-    if let Some(expires) = &result.expires_at {
-        println!("Expires: {}", expires);
-    }
-
-    Ok(())
+    read_json(response).await
 }
 
 /// WebSocket conversation with an ElevenLabs agent
-async fn start_websocket_conversation(args: ConverseArgs) -> Result<()> {
+async fn start_websocket_conversation(args: ConverseArgs, api_key: &str) -> Result<()> {
     print_info(&format!(
         "Starting conversation with agent '{}'...",
         args.agent_id.cyan()
     ));
-    print_info("Type your message and press Enter to send. Press Ctrl+C to exit.\n");
 
-    // Build WebSocket URL
-    let ws_url = format!(
-        "wss://api.elevenlabs.io/v1/convai/conversation?agent_id={}",
-        args.agent_id
+    let mut messages = args.message.clone();
+    if let Some(path) = &args.messages_file {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read messages file: {}", path))?;
+        messages.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string),
+        );
+    }
+    if args.non_interactive && messages.is_empty() {
+        return Err(anyhow::anyhow!(
+            "--non-interactive needs --message or --messages-file"
+        ));
+    }
+
+    let system_prompt = match &args.system_prompt_file {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read system prompt file: {}", path))?,
+        ),
+        None => None,
+    };
+    let dynamic_variables = parse_dynamic_vars(&args.dynamic_vars)?;
+    let has_overrides =
+        system_prompt.is_some() || args.first_message.is_some() || args.language.is_some();
+    if has_overrides {
+        print_info("Sending conversation overrides (these must be enabled in the agent's security settings)");
+    }
+
+    let endpoint = ConversationEndpoint {
+        agent_id: args.agent_id.clone(),
+        api_key: api_key.to_string(),
+        auth: if args.use_signed_url {
+            ConversationAuth::SignedUrl
+        } else if args.token {
+            ConversationAuth::Token
+        } else {
+            ConversationAuth::Public
+        },
+    };
+
+    let recorder = match &args.record {
+        Some(dir) => {
+            let recorder = SessionRecorder::create(Path::new(dir), &args.agent_id)?;
+            print_info(&format!("Recording session to {}", dir.cyan()));
+            Some(Arc::new(Mutex::new(recorder)))
+        }
+        None => None,
+    };
+
+    // Initialization message, re-sent on every (re)connect
+    let init_message = build_initiation_message(
+        system_prompt.as_deref(),
+        args.first_message.as_deref(),
+        args.language.as_deref(),
+        &dynamic_variables,
     );
 
-    // Connect to WebSocket with timeout
-    let connect_future = connect_async(&ws_url);
-    let (ws_stream, _) =
-        tokio::time::timeout(Duration::from_secs(WS_CONNECT_TIMEOUT_SECS), connect_future)
-            .await
-            .context("Connection timeout")?
-            .context("Failed to connect to ElevenLabs WebSocket")?;
+    if args.non_interactive {
+        return run_scripted_conversation(
+            &endpoint,
+            &init_message,
+            &messages,
+            Duration::from_secs(args.turn_timeout),
+            &recorder,
+        )
+        .await;
+    }
+    if io::stdin().is_terminal() {
+        crate::prompt::ensure_input_allowed("chat messages; use --non-interactive")?;
+    }
+    print_info("Type your message and press Enter to send. Press Ctrl+C to exit.\n");
 
-    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+    // Connect before reading input so connection errors surface immediately
+    let ws_stream = connect_conversation(&endpoint, &init_message).await?;
+
+    // Stdin is read on a plain thread so a dropped session can end the chat without
+    // waiting for another line of input; --message lines go first
+    let (input_tx, input_rx) = mpsc::channel::<String>(32);
+    let input_recorder = recorder.clone();
+    std::thread::spawn(move || {
+        for text in messages {
+            println!("{} {}", "[You]:".blue().bold(), text);
+            record(&input_recorder, |r| r.add_message("user", "text", &text));
+            if input_tx.blocking_send(text).is_err() {
+                return;
+            }
+        }
+        println!(
+            "{} Type your message and press Enter (Ctrl+C to exit)",
+            "?".yellow()
+        );
+        loop {
+            print!("> ");
+            let _ = io::stdout().flush();
+
+            let mut input = String::new();
+            match io::stdin().read_line(&mut input) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
 
-    // Send initialization message
-    let init_message = json!({
-        "type": "conversation_initiation_client_data",
-        "conversation_config_override": {
-            "agent": {
-                "prompt": {}
+            let text = input.trim();
+            if text.is_empty() {
+                continue;
             }
+
+            if input_tx.blocking_send(text.to_string()).is_err() {
+                break;
+            }
+            record(&input_recorder, |r| r.add_message("user", "text", text));
         }
     });
 
-    ws_sender
+    run_conversation_session(
+        ws_stream,
+        &endpoint,
+        &init_message,
+        input_rx,
+        &recorder,
+        args.max_reconnects,
+    )
+    .await
+}
+
+pub(crate) type ConversationStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// How a single websocket connection ended
+enum ConnectionEnd {
+    /// Input was closed (EOF); the conversation is over
+    InputClosed,
+    /// The server closed the conversation deliberately
+    ClosedByServer,
+    /// The connection dropped and may be re-established
+    Lost(String),
+}
+
+/// How the conversation websocket is authenticated
+pub(crate) enum ConversationAuth {
+    /// Public agents: connect with the agent ID only
+    Public,
+    /// Fetch a signed URL with the API key (agents with authentication enabled)
+    SignedUrl,
+    /// Fetch a short-lived conversation token with the API key
+    Token,
+}
+
+/// Where and how to connect for a conversation
+pub(crate) struct ConversationEndpoint {
+    pub(crate) agent_id: String,
+    pub(crate) api_key: String,
+    pub(crate) auth: ConversationAuth,
+}
+
+impl ConversationEndpoint {
+    /// Resolve the websocket URL; signed URLs and tokens are single-use, so this is
+    /// called again on every reconnect
+    async fn url(&self) -> Result<String> {
+        let public_url = format!(
+            "{}/v1/convai/conversation?agent_id={}",
+            ws_base(),
+            self.agent_id
+        );
+        match self.auth {
+            ConversationAuth::Public => Ok(public_url),
+            ConversationAuth::SignedUrl => fetch_signed_url(&self.api_key, &self.agent_id, None)
+                .await
+                .context("Failed to get signed URL"),
+            ConversationAuth::Token => {
+                let token = fetch_conversation_token(&self.api_key, &self.agent_id, None)
+                    .await
+                    .context("Failed to get conversation token")?;
+                Ok(format!("{}&token={}", public_url, token.token))
+            }
+        }
+    }
+}
+
+/// Open the conversation websocket and send the initialization message
+pub(crate) async fn connect_conversation(
+    endpoint: &ConversationEndpoint,
+    init_message: &serde_json::Value,
+) -> Result<ConversationStream> {
+    let ws_url = endpoint.url().await?;
+    let (mut ws_stream, _) = tokio::time::timeout(
+        Duration::from_secs(WS_CONNECT_TIMEOUT_SECS),
+        connect_async(&ws_url),
+    )
+    .await
+    .context("Connection timeout")?
+    .context("Failed to connect to ElevenLabs WebSocket")?;
+
+    ws_stream
         .send(Message::Text(init_message.to_string()))
         .await
         .context("Failed to send initialization message")?;
 
-    // Create channel for input
-    let (input_tx, mut input_rx) = mpsc::channel::<String>(32);
+    Ok(ws_stream)
+}
 
-    // Spawn WebSocket sender task
-    let ws_sender_handle = tokio::spawn(async move {
-        while let Some(text) = input_rx.recv().await {
-            let message = json!({
-                "type": "user_input",
-                "text": text
-            });
+/// Drive the conversation, reconnecting after network failures up to `max_reconnects`
+/// consecutive times. The API cannot resume a conversation, so each reconnect starts a
+/// new one and sends the transcript so far as a contextual update.
+async fn run_conversation_session(
+    ws_stream: ConversationStream,
+    endpoint: &ConversationEndpoint,
+    init_message: &serde_json::Value,
+    mut input_rx: mpsc::Receiver<String>,
+    recorder: &Option<Arc<Mutex<SessionRecorder>>>,
+    max_reconnects: u32,
+) -> Result<()> {
+    let mut history: Vec<(String, String)> = Vec::new();
+    let mut stream = Some(ws_stream);
+    let mut attempts = 0u32;
 
-            if ws_sender
-                .send(Message::Text(message.to_string()))
-                .await
-                .is_err()
-            {
-                break;
+    loop {
+        let end = match stream.take() {
+            Some(mut ws) => {
+                if attempts > 0 {
+                    if let Some(note) = build_reconnect_context(&history) {
+                        let update = json!({ "type": "contextual_update", "text": note });
+                        let _ = ws.send(Message::Text(update.to_string())).await;
+                    }
+                }
+                let mut connected = false;
+                let end = pump_connection(
+                    &mut ws,
+                    &mut input_rx,
+                    recorder,
+                    &mut history,
+                    &mut connected,
+                )
+                .await;
+                if connected {
+                    attempts = 0;
+                }
+                end
+            }
+            None => match connect_conversation(endpoint, init_message).await {
+                Ok(ws) => {
+                    stream = Some(ws);
+                    continue;
+                }
+                Err(e) => ConnectionEnd::Lost(format!("{:#}", e)),
+            },
+        };
+
+        match end {
+            ConnectionEnd::InputClosed => return Ok(()),
+            ConnectionEnd::ClosedByServer => {
+                print_warning("Connection closed by server");
+                return Ok(());
+            }
+            ConnectionEnd::Lost(reason) => {
+                if attempts >= max_reconnects {
+                    return Err(anyhow::anyhow!(
+                        "Connection lost: {} (gave up after {} reconnect attempt(s))",
+                        reason,
+                        attempts
+                    ));
+                }
+                attempts += 1;
+                let delay = reconnect_delay(attempts);
+                print_warning(&format!(
+                    "Connection lost: {}. Reconnecting in {}s ({}/{})...",
+                    reason,
+                    delay.as_secs(),
+                    attempts,
+                    max_reconnects
+                ));
+                tokio::time::sleep(delay).await;
             }
         }
-    });
+    }
+}
 
-    // Spawn WebSocket receiver task
-    let ws_receiver_handle = tokio::spawn(async move {
-        while let Some(msg_result) = ws_receiver.next().await {
-            match msg_result {
-                Ok(Message::Text(text)) => {
-                    if let Ok(response) = serde_json::from_str::<ConversationResponse>(&text) {
-                        match response.event_type.as_str() {
-                            "conversation_initiation_metadata" => {
-                                print_success("Connected to agent!");
-                                if let Some(meta) = response.conversation_initiation_metadata_event
-                                {
-                                    print_info(&format!(
-                                        "Conversation ID: {}",
-                                        meta.conversation_id
-                                    ));
-                                }
-                                println!();
-                            }
-                            "audio" => {
-                                if let Some(audio_event) = response.audio_event {
-                                    print_info(&format!(
-                                        "[Agent]: {} (audio {}ms)",
-                                        audio_event.event_id.cyan(),
-                                        audio_event.audio_duration_ms.unwrap_or(0)
-                                    ));
-                                }
-                            }
-                            "agent_response" => {
-                                if let Some(agent_event) = response.agent_response_event {
-                                    if let Some(text) = agent_event.agent_response {
-                                        println!("{} {}", "[Agent]:".green().bold(), text);
-                                    }
-                                }
-                            }
-                            "user_transcript" => {
-                                if let Some(user_event) = response.user_transcription_event {
-                                    if let Some(text) = user_event.user_transcript {
-                                        println!("{} {}", "[You]:".blue().bold(), text);
-                                    }
-                                }
-                            }
-                            "ping" => {}
-                            "interruption" => {
-                                print_warning("Agent was interrupted");
-                            }
-                            "error" => {
-                                if let Some(error) = response.error_event {
-                                    print_error(&format!("Error: {}", error.error));
-                                }
+/// Exponential backoff between reconnect attempts: 1s, 2s, 4s, ... capped at 30s
+fn reconnect_delay(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt.saturating_sub(1)).min(30))
+}
+
+/// Summarize the conversation so far for a fresh connection after a reconnect
+fn build_reconnect_context(history: &[(String, String)]) -> Option<String> {
+    if history.is_empty() {
+        return None;
+    }
+    let start = history.len().saturating_sub(RECONNECT_CONTEXT_MESSAGES);
+    let lines: Vec<String> = history[start..]
+        .iter()
+        .map(|(role, text)| format!("{}: {}", role, text))
+        .collect();
+    Some(format!(
+        "The previous connection dropped and this is a continuation of the same conversation. \
+         Do not greet the user again. Conversation so far:\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// Relay events and user input over one websocket connection until it ends
+async fn pump_connection(
+    ws: &mut ConversationStream,
+    input_rx: &mut mpsc::Receiver<String>,
+    recorder: &Option<Arc<Mutex<SessionRecorder>>>,
+    history: &mut Vec<(String, String)>,
+    connected: &mut bool,
+) -> ConnectionEnd {
+    let mut keepalive = tokio::time::interval(Duration::from_secs(WS_KEEPALIVE_INTERVAL_SECS));
+    let mut last_activity = Instant::now();
+
+    loop {
+        tokio::select! {
+            msg = ws.next() => {
+                last_activity = Instant::now();
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(reply) = handle_conversation_event(&text, recorder, history, connected) {
+                            if let Err(e) = ws.send(Message::Text(reply.to_string())).await {
+                                return ConnectionEnd::Lost(e.to_string());
                             }
-                            _ => {}
                         }
                     }
+                    Some(Ok(Message::Close(_))) => return ConnectionEnd::ClosedByServer,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return ConnectionEnd::Lost(e.to_string()),
+                    None => return ConnectionEnd::Lost("connection reset".to_string()),
                 }
-                Ok(Message::Close(_)) => {
-                    print_warning("Connection closed by server");
-                    break;
+            }
+            input = input_rx.recv() => {
+                let Some(text) = input else {
+                    let _ = ws.close(None).await;
+                    return ConnectionEnd::InputClosed;
+                };
+                let message = user_message(&text);
+                history.push(("user".to_string(), text));
+                if let Err(e) = ws.send(Message::Text(message.to_string())).await {
+                    return ConnectionEnd::Lost(e.to_string());
                 }
-                Ok(_) => {}
-                Err(e) => {
-                    print_error(&format!("WebSocket error: {}", e));
-                    break;
+            }
+            _ = keepalive.tick() => {
+                if last_activity.elapsed() > Duration::from_secs(WS_KEEPALIVE_TIMEOUT_SECS) {
+                    return ConnectionEnd::Lost("keepalive timeout".to_string());
+                }
+                if let Err(e) = ws.send(Message::Ping(Vec::new())).await {
+                    return ConnectionEnd::Lost(e.to_string());
                 }
             }
         }
-    });
+    }
+}
 
-    // Input loop in main thread
-    println!(
-        "{} Type your message and press Enter (Ctrl+C to exit)",
-        "?".yellow()
-    );
+/// A typed user turn. Earlier releases sent these as `user_input`, which the
+/// agents WebSocket API doesn't define; proxies or mock servers matching on
+/// that name need updating
+fn user_message(text: &str) -> serde_json::Value {
+    json!({ "type": "user_message", "text": text })
+}
 
-    loop {
-        print!("> ");
-        io::stdout().flush()?;
+/// One line of a scripted conversation transcript
+#[derive(Debug, Serialize)]
+struct ScriptedMessage {
+    role: &'static str,
+    text: String,
+}
+
+/// Result of `converse chat --non-interactive`
+#[derive(Debug, Serialize)]
+struct ScriptedTranscript {
+    agent_id: String,
+    conversation_id: Option<String>,
+    messages: Vec<ScriptedMessage>,
+    /// False when the agent did not answer every message in time
+    complete: bool,
+}
+
+/// How waiting for the agent's turn ended
+#[derive(Debug, PartialEq)]
+enum TurnEnd {
+    /// The agent replied and then went quiet
+    Replied,
+    /// No reply before the deadline
+    TimedOut,
+    /// The server closed the conversation
+    Closed,
+}
+
+/// Send each message, wait for the agent to finish its turn, then print the
+/// transcript (JSON with `--json`) and exit
+async fn run_scripted_conversation(
+    endpoint: &ConversationEndpoint,
+    init_message: &serde_json::Value,
+    messages: &[String],
+    turn_timeout: Duration,
+    recorder: &Option<Arc<Mutex<SessionRecorder>>>,
+) -> Result<()> {
+    let mut ws = connect_conversation(endpoint, init_message).await?;
+    let mut transcript = ScriptedTranscript {
+        agent_id: endpoint.agent_id.clone(),
+        conversation_id: None,
+        messages: Vec::new(),
+        complete: true,
+    };
+
+    // Collect the greeting, if the agent has a first message
+    let mut end = collect_agent_turn(
+        &mut ws,
+        &mut transcript,
+        recorder,
+        Duration::from_secs(GREETING_WAIT_SECS),
+    )
+    .await?;
+
+    for text in messages {
+        if end == TurnEnd::Closed {
+            transcript.complete = false;
+            break;
+        }
+        ws.send(Message::Text(user_message(text).to_string()))
+            .await
+            .context("Failed to send message")?;
+        record(recorder, |r| r.add_message("user", "text", text));
+        transcript.messages.push(ScriptedMessage {
+            role: "user",
+            text: text.clone(),
+        });
+        end = collect_agent_turn(&mut ws, &mut transcript, recorder, turn_timeout).await?;
+        if end != TurnEnd::Replied {
+            transcript.complete = false;
+            break;
+        }
+    }
+    let _ = ws.close(None).await;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&transcript)?);
+    } else {
+        for message in &transcript.messages {
+            let label = match message.role {
+                "agent" => "[Agent]:".green().bold(),
+                _ => "[You]:".blue().bold(),
+            };
+            println!("{} {}", label, message.text);
+        }
+    }
 
-        let text = input.trim();
-        if text.is_empty() {
+    match end {
+        _ if transcript.complete => Ok(()),
+        TurnEnd::Closed => Err(anyhow::anyhow!(
+            "The agent ended the conversation before all messages were sent"
+        )),
+        _ => Err(anyhow::anyhow!(
+            "The agent did not answer within {}s",
+            turn_timeout.as_secs()
+        )),
+    }
+}
+
+/// Read events until the agent has replied and stayed quiet for
+/// `TURN_SETTLE_MS`, the deadline passes, or the server closes
+async fn collect_agent_turn(
+    ws: &mut ConversationStream,
+    transcript: &mut ScriptedTranscript,
+    recorder: &Option<Arc<Mutex<SessionRecorder>>>,
+    timeout: Duration,
+) -> Result<TurnEnd> {
+    let mut deadline = tokio::time::Instant::now() + timeout;
+    let mut replied = false;
+
+    loop {
+        let message = match tokio::time::timeout_at(deadline, ws.next()).await {
+            Ok(message) => message,
+            Err(_) if replied => return Ok(TurnEnd::Replied),
+            Err(_) => return Ok(TurnEnd::TimedOut),
+        };
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => return Ok(TurnEnd::Closed),
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(anyhow::anyhow!("Connection lost: {}", e)),
+        };
+        let Ok(event) = serde_json::from_str::<ConversationResponse>(&text) else {
             continue;
+        };
+
+        match event.event_type.as_str() {
+            "conversation_initiation_metadata" => {
+                if let Some(meta) = event.conversation_initiation_metadata_event {
+                    transcript.conversation_id = Some(meta.conversation_id.clone());
+                    record(recorder, |r| r.set_metadata(&meta));
+                }
+            }
+            "audio" => {
+                if let Some(audio) = event.audio_event.and_then(|e| e.audio_base_64) {
+                    record(recorder, |r| r.append_agent_audio(&audio));
+                }
+            }
+            "agent_response" => {
+                if let Some(text) = event.agent_response_event.and_then(|e| e.agent_response) {
+                    record(recorder, |r| r.add_message("agent", "speech", &text));
+                    transcript.messages.push(ScriptedMessage {
+                        role: "agent",
+                        text,
+                    });
+                    replied = true;
+                    deadline = tokio::time::Instant::now() + Duration::from_millis(TURN_SETTLE_MS);
+                }
+            }
+            "ping" => {
+                if let Some(ping) = event.ping_event {
+                    let pong = json!({ "type": "pong", "event_id": ping.event_id });
+                    ws.send(Message::Text(pong.to_string()))
+                        .await
+                        .context("Failed to answer ping")?;
+                }
+            }
+            "error" => {
+                let error = event.error_event.map(|e| e.error).unwrap_or_default();
+                return Err(anyhow::anyhow!("Agent error: {}", error));
+            }
+            _ => {}
         }
+    }
+}
 
-        if input_tx.send(text.to_string()).await.is_err() {
-            break;
+/// Display (and record) a server event; returns a reply to send, if any
+fn handle_conversation_event(
+    text: &str,
+    recorder: &Option<Arc<Mutex<SessionRecorder>>>,
+    history: &mut Vec<(String, String)>,
+    connected: &mut bool,
+) -> Option<serde_json::Value> {
+    let response = serde_json::from_str::<ConversationResponse>(text).ok()?;
+
+    match response.event_type.as_str() {
+        "conversation_initiation_metadata" => {
+            *connected = true;
+            print_success("Connected to agent!");
+            if let Some(meta) = response.conversation_initiation_metadata_event {
+                print_info(&format!("Conversation ID: {}", meta.conversation_id));
+                record(recorder, |r| r.set_metadata(&meta));
+            }
+            println!();
+        }
+        "audio" => {
+            if let Some(audio_event) = response.audio_event {
+                print_info(&format!(
+                    "[Agent]: {} (audio {}ms)",
+                    audio_event.event_id.cyan(),
+                    audio_event.audio_duration_ms.unwrap_or(0)
+                ));
+                if let Some(audio) = &audio_event.audio_base_64 {
+                    record(recorder, |r| r.append_agent_audio(audio));
+                }
+            }
+        }
+        "agent_response" => {
+            if let Some(text) = response.agent_response_event.and_then(|e| e.agent_response) {
+                println!("{} {}", "[Agent]:".green().bold(), text);
+                record(recorder, |r| r.add_message("agent", "speech", &text));
+                history.push(("agent".to_string(), text));
+            }
+        }
+        "user_transcript" => {
+            if let Some(text) = response
+                .user_transcription_event
+                .and_then(|e| e.user_transcript)
+            {
+                println!("{} {}", "[You]:".blue().bold(), text);
+                record(recorder, |r| r.add_message("user", "transcript", &text));
+            }
+        }
+        "ping" => {
+            // The server drops conversations whose pings go unanswered
+            if let Some(ping) = response.ping_event {
+                return Some(json!({ "type": "pong", "event_id": ping.event_id }));
+            }
+        }
+        "interruption" => {
+            print_warning("Agent was interrupted");
         }
+        "error" => {
+            if let Some(error) = response.error_event {
+                print_error(&format!("Error: {}", error.error));
+            }
+        }
+        _ => {}
     }
 
-    // Wait for tasks
-    let _ = tokio::try_join!(ws_sender_handle, ws_receiver_handle);
+    None
+}
+
+/// Build the `conversation_initiation_client_data` message with optional agent overrides
+pub(crate) fn build_initiation_message(
+    system_prompt: Option<&str>,
+    first_message: Option<&str>,
+    language: Option<&str>,
+    dynamic_variables: &serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    let mut agent = json!({ "prompt": {} });
+    if let Some(prompt) = system_prompt {
+        agent["prompt"]["prompt"] = json!(prompt.trim());
+    }
+    if let Some(first_message) = first_message {
+        agent["first_message"] = json!(first_message);
+    }
+    if let Some(language) = language {
+        agent["language"] = json!(language);
+    }
 
-    Ok(())
+    let mut message = json!({
+        "type": "conversation_initiation_client_data",
+        "conversation_config_override": {
+            "agent": agent
+        }
+    });
+    if !dynamic_variables.is_empty() {
+        message["dynamic_variables"] = json!(dynamic_variables);
+    }
+    message
+}
+
+/// Apply a recording step if `--record-agent` is active, reporting failures without ending the chat
+fn record<F>(recorder: &Option<Arc<Mutex<SessionRecorder>>>, step: F)
+where
+    F: FnOnce(&mut SessionRecorder) -> Result<()>,
+{
+    if let Some(recorder) = recorder {
+        if let Ok(mut recorder) = recorder.lock() {
+            if let Err(e) = step(&mut recorder) {
+                print_warning(&format!("Recording failed: {}", e));
+            }
+        }
+    }
+}
+
+/// A single transcript line in a recorded session
+#[derive(Debug, Serialize)]
+struct TranscriptEntry {
+    role: String,
+    source: String,
+    text: String,
+    offset_secs: f64,
+}
+
+/// Transcript JSON written to `<session>/transcript.json`
+#[derive(Debug, Serialize)]
+struct SessionTranscript<'a> {
+    agent_id: &'a str,
+    conversation_id: Option<&'a str>,
+    started_at: u64,
+    agent_audio_format: &'a str,
+    agent_audio_file: Option<&'a str>,
+    messages: &'a [TranscriptEntry],
+}
+
+/// Destination for agent audio chunks
+enum AudioSink {
+    Wav(WavWriter),
+    Raw(File),
+}
+
+/// Streaming 16-bit mono PCM WAV writer; sizes in the header are patched after every chunk
+/// so the file stays playable even if the session is interrupted
+struct WavWriter {
+    file: File,
+    data_len: u32,
+}
+
+impl WavWriter {
+    fn create(path: &Path, sample_rate: u32) -> Result<Self> {
+        let mut file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        file.write_all(&wav_header(sample_rate, 1, 0))?;
+        Ok(Self { file, data_len: 0 })
+    }
+
+    fn append(&mut self, pcm: &[u8]) -> Result<()> {
+        self.file.seek(SeekFrom::End(0))?;
+        self.file.write_all(pcm)?;
+        self.data_len += pcm.len() as u32;
+        self.file.seek(SeekFrom::Start(4))?;
+        self.file.write_all(&(36 + self.data_len).to_le_bytes())?;
+        self.file.seek(SeekFrom::Start(40))?;
+        self.file.write_all(&self.data_len.to_le_bytes())?;
+        Ok(())
+    }
+}
+
+/// Records a websocket conversation into a session directory:
+/// agent audio (`agent_audio.wav`, or raw bytes for non-PCM formats) and `transcript.json`
+struct SessionRecorder {
+    dir: PathBuf,
+    agent_id: String,
+    conversation_id: Option<String>,
+    audio_format: String,
+    audio_file: Option<String>,
+    audio: Option<AudioSink>,
+    messages: Vec<TranscriptEntry>,
+    started: Instant,
+    started_at: u64,
+}
+
+impl SessionRecorder {
+    fn create(dir: &Path, agent_id: &str) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create session directory: {}", dir.display()))?;
+        let recorder = Self {
+            dir: dir.to_path_buf(),
+            agent_id: agent_id.to_string(),
+            conversation_id: None,
+            audio_format: "pcm_16000".to_string(),
+            audio_file: None,
+            audio: None,
+            messages: Vec::new(),
+            started: Instant::now(),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        recorder.write_transcript()?;
+        Ok(recorder)
+    }
+
+    fn set_metadata(&mut self, meta: &ConversationInitiationMetadata) -> Result<()> {
+        self.conversation_id = Some(meta.conversation_id.clone());
+        if let Some(format) = &meta.agent_output_audio_format {
+            self.audio_format = format.clone();
+        }
+        self.write_transcript()
+    }
+
+    fn append_agent_audio(&mut self, audio_base_64: &str) -> Result<()> {
+        let bytes =
+            base64::Engine::decode(&base64::engine::general_purpose::STANDARD, audio_base_64)
+                .context("Failed to decode agent audio")?;
+
+        if self.audio.is_none() {
+            let (file_name, sink) = match pcm_sample_rate(&self.audio_format) {
+                Some(rate) => {
+                    let name = "agent_audio.wav".to_string();
+                    let writer = WavWriter::create(&self.dir.join(&name), rate)?;
+                    (name, AudioSink::Wav(writer))
+                }
+                None => {
+                    let name = format!("agent_audio.{}", format_to_extension(&self.audio_format));
+                    let path = self.dir.join(&name);
+                    let file = File::create(&path)
+                        .with_context(|| format!("Failed to create {}", path.display()))?;
+                    (name, AudioSink::Raw(file))
+                }
+            };
+            self.audio_file = Some(file_name);
+            self.audio = Some(sink);
+            self.write_transcript()?;
+        }
+
+        match self.audio.as_mut() {
+            Some(AudioSink::Wav(writer)) => writer.append(&bytes),
+            Some(AudioSink::Raw(file)) => Ok(file.write_all(&bytes)?),
+            None => Ok(()),
+        }
+    }
+
+    fn add_message(&mut self, role: &str, source: &str, text: &str) -> Result<()> {
+        self.messages.push(TranscriptEntry {
+            role: role.to_string(),
+            source: source.to_string(),
+            text: text.to_string(),
+            offset_secs: (self.started.elapsed().as_millis() as f64) / 1000.0,
+        });
+        self.write_transcript()
+    }
+
+    /// Rewrite transcript.json so it is always complete on disk
+    fn write_transcript(&self) -> Result<()> {
+        let transcript = SessionTranscript {
+            agent_id: &self.agent_id,
+            conversation_id: self.conversation_id.as_deref(),
+            started_at: self.started_at,
+            agent_audio_format: &self.audio_format,
+            agent_audio_file: self.audio_file.as_deref(),
+            messages: &self.messages,
+        };
+        let path = self.dir.join("transcript.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&transcript)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -516,22 +1290,28 @@ struct ConversationResponse {
     user_transcription_event: Option<UserTranscriptEvent>,
     #[serde(default)]
     error_event: Option<ErrorEvent>,
+    #[serde(default)]
+    ping_event: Option<PingEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PingEvent {
+    #[serde(default)]
+    event_id: u64,
 }
 
 #[derive(Debug, Deserialize)]
 struct ConversationInitiationMetadata {
     #[serde(default)]
     conversation_id: String,
-    #[allow(dead_code)]
     #[serde(default)]
-    agent_output: Option<String>,
+    agent_output_audio_format: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct AudioEvent {
     #[serde(default)]
     event_id: String,
-    #[allow(dead_code)]
     #[serde(default)]
     audio_base_64: Option<String>,
     #[serde(default)]
@@ -564,10 +1344,7 @@ async fn delete_conversation(api_key: &str, conversation_id: &str, assume_yes: b
     ));
 
     if !assume_yes {
-        let confirm = Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
         if !confirm {
             print_info("Cancelled");
             return Ok(());
@@ -580,21 +1357,17 @@ async fn delete_conversation(api_key: &str, conversation_id: &str, assume_yes: b
         conversation_id.cyan()
     ));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}",
-        conversation_id
-    );
+    let url = format!("{}/v1/convai/conversations/{}", api_base(), conversation_id);
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to delete conversation")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -609,6 +1382,7 @@ async fn get_conversation_audio(
     api_key: &str,
     conversation_id: &str,
     output: Option<&str>,
+    waveform: bool,
     assume_yes: bool,
 ) -> Result<()> {
     let client = create_http_client();
@@ -618,20 +1392,20 @@ async fn get_conversation_audio(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}/audio",
+        "{}/v1/convai/conversations/{}/audio",
+        api_base(),
         conversation_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to download conversation audio")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     let audio = response
@@ -654,6 +1428,9 @@ async fn get_conversation_audio(
         "Conversation audio downloaded -> {}",
         output_path.green()
     ));
+    if waveform {
+        crate::waveform::print_waveform(path)?;
+    }
     Ok(())
 }
 
@@ -678,7 +1455,8 @@ async fn send_conversation_feedback(
     };
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/conversations/{}/feedback",
+        "{}/v1/conversations/{}/feedback",
+        api_base(),
         conversation_id
     );
 
@@ -686,13 +1464,12 @@ async fn send_conversation_feedback(
         .post(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to send conversation feedback")?;
 
     if !response.status().is_success() {
-        let error = response.text().await.unwrap_or_default();
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     let feedback_type = if thumbs_up { "liked" } else { "disliked" };
@@ -724,7 +1501,7 @@ async fn outbound_call(
         body["greeting_message"] = json!(msg);
     }
 
-    let url = "https://api.elevenlabs.io/v1/conversations/outbound";
+    let url = format!("{}/v1/conversations/outbound", api_base());
 
     print_info("Initiating outbound call...");
 
@@ -732,16 +1509,15 @@ async fn outbound_call(
         .post(url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to initiate outbound call")?;
 
     if !response.status().is_success() {
-        let error = response.text().await.unwrap_or_default();
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: serde_json::Value = response.json().await.context("Failed to parse response")?;
+    let result: serde_json::Value = read_json(response).await?;
 
     println!("\n{}", "Outbound Call:".bold().underline());
     println!(
@@ -757,3 +1533,129 @@ async fn outbound_call(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation_list_query_params() {
+        let now = 1_749_988_800;
+        let filter = ConversationListFilter {
+            agent_id: Some("agent_1".to_string()),
+            call_successful: Some(false),
+            since: Some("2025-06-01".to_string()),
+            until: Some("1d".to_string()),
+            status: Some("failed".to_string()),
+            ..Default::default()
+        };
+        let params = filter.query_params(now).unwrap();
+        assert_eq!(
+            params,
+            vec![
+                ("agent_id", "agent_1".to_string()),
+                ("call_successful", "failure".to_string()),
+                ("call_start_after_unix", "1748736000".to_string()),
+                ("call_start_before_unix", (now - 86_400).to_string()),
+            ]
+        );
+
+        let inverted = ConversationListFilter {
+            since: Some("1d".to_string()),
+            until: Some("7d".to_string()),
+            ..Default::default()
+        };
+        assert!(inverted.query_params(now).is_err());
+    }
+
+    #[test]
+    fn test_build_initiation_message_defaults() {
+        let message = build_initiation_message(None, None, None, &serde_json::Map::new());
+        assert_eq!(message["type"], "conversation_initiation_client_data");
+        assert_eq!(
+            message["conversation_config_override"]["agent"],
+            json!({ "prompt": {} })
+        );
+        assert!(message.get("dynamic_variables").is_none());
+    }
+
+    #[test]
+    fn test_build_initiation_message_overrides() {
+        let vars = parse_dynamic_vars(&["customer=Ada".to_string()]).unwrap();
+        let message =
+            build_initiation_message(Some("You are terse.\n"), Some("Hola"), Some("es"), &vars);
+        let agent = &message["conversation_config_override"]["agent"];
+        assert_eq!(agent["prompt"]["prompt"], "You are terse.");
+        assert_eq!(agent["first_message"], "Hola");
+        assert_eq!(agent["language"], "es");
+        assert_eq!(message["dynamic_variables"]["customer"], "Ada");
+    }
+
+    #[test]
+    fn test_session_recorder_writes_audio_and_transcript() {
+        let dir = tempfile::tempdir().unwrap();
+        let session = dir.path().join("session");
+        let mut recorder = SessionRecorder::create(&session, "agent_123").unwrap();
+
+        let meta = ConversationInitiationMetadata {
+            conversation_id: "conv_1".to_string(),
+            agent_output_audio_format: Some("pcm_16000".to_string()),
+        };
+        recorder.set_metadata(&meta).unwrap();
+        recorder.add_message("user", "text", "Hello").unwrap();
+        recorder.append_agent_audio("AAAAAA==").unwrap();
+        recorder.append_agent_audio("AAAAAA==").unwrap();
+        recorder.add_message("agent", "speech", "Hi there").unwrap();
+
+        let wav = std::fs::read(session.join("agent_audio.wav")).unwrap();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+
+        let transcript: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(session.join("transcript.json")).unwrap())
+                .unwrap();
+        assert_eq!(transcript["conversation_id"], "conv_1");
+        assert_eq!(transcript["agent_audio_file"], "agent_audio.wav");
+        assert_eq!(transcript["messages"][1]["role"], "agent");
+        assert_eq!(transcript["messages"][1]["text"], "Hi there");
+    }
+
+    #[test]
+    fn test_ping_event_gets_pong_reply() {
+        let mut history = Vec::new();
+        let mut connected = false;
+        let reply = handle_conversation_event(
+            r#"{"type":"ping","ping_event":{"event_id":7,"ping_ms":50}}"#,
+            &None,
+            &mut history,
+            &mut connected,
+        );
+        assert_eq!(reply, Some(json!({ "type": "pong", "event_id": 7 })));
+
+        let reply = handle_conversation_event(
+            r#"{"type":"agent_response","agent_response_event":{"agent_response":"Hi"}}"#,
+            &None,
+            &mut history,
+            &mut connected,
+        );
+        assert!(reply.is_none());
+        assert_eq!(history, vec![("agent".to_string(), "Hi".to_string())]);
+        assert!(!connected);
+    }
+
+    #[test]
+    fn test_reconnect_backoff_and_context() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(4));
+        assert_eq!(reconnect_delay(10), Duration::from_secs(30));
+
+        assert!(build_reconnect_context(&[]).is_none());
+        let history = vec![
+            ("user".to_string(), "Book a table".to_string()),
+            ("agent".to_string(), "For how many?".to_string()),
+        ];
+        let note = build_reconnect_context(&history).unwrap();
+        assert!(note.ends_with("user: Book a table\nagent: For how many?"));
+    }
+}
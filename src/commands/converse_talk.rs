@@ -0,0 +1,286 @@
+//! Voice conversations with an agent (`converse talk`)
+//!
+//! Microphone audio is downmixed, resampled to the agent's input format and
+//! streamed as `user_audio_chunk` messages over the conversation websocket;
+//! the agent's `audio_event` PCM is played as it arrives. The agent's speech
+//! recognition and turn taking do the rest, so there is no push-to-talk. When
+//! the agent is interrupted, queued agent audio is dropped so it stops talking
+//! at once. Use headphones, or the agent may hear (and answer) itself.
+
+use crate::cli::ConverseTalkArgs;
+use crate::commands::conversation::{
+    build_initiation_message, connect_conversation, ConversationAuth, ConversationEndpoint,
+};
+use crate::commands::stt_live::start_capture;
+use crate::output::{print_error, print_info, print_success, print_warning};
+use crate::pcm::{downmix, pcm_sample_rate, resample, samples_from_pcm16, samples_to_pcm16};
+use crate::utils::parse_dynamic_vars;
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use colored::*;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+/// Input format the API uses when the agent doesn't say
+const DEFAULT_INPUT_FORMAT: &str = "pcm_16000";
+
+/// A server message, reduced to what a voice session acts on
+#[derive(Debug, PartialEq)]
+enum TalkEvent {
+    Connected {
+        conversation_id: String,
+        input_rate: u32,
+        output_format: String,
+    },
+    Audio(Vec<u8>),
+    AgentText(String),
+    UserText(String),
+    Interrupted,
+    Ping(u64),
+    Error(String),
+    Other,
+}
+
+fn parse_event(text: &str) -> Result<TalkEvent> {
+    let event: Value = serde_json::from_str(text).context("Invalid websocket message")?;
+    let str_at = |pointer: &str| {
+        event
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    Ok(match event["type"].as_str().unwrap_or_default() {
+        "conversation_initiation_metadata" => {
+            let input_format =
+                str_at("/conversation_initiation_metadata_event/user_input_audio_format")
+                    .unwrap_or_else(|| DEFAULT_INPUT_FORMAT.to_string());
+            let input_rate = pcm_sample_rate(&input_format).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The agent expects {} input, but converse talk sends PCM. Set the agent's user input audio format to {}",
+                    input_format,
+                    DEFAULT_INPUT_FORMAT
+                )
+            })?;
+            TalkEvent::Connected {
+                conversation_id: str_at("/conversation_initiation_metadata_event/conversation_id")
+                    .unwrap_or_default(),
+                input_rate,
+                output_format: str_at(
+                    "/conversation_initiation_metadata_event/agent_output_audio_format",
+                )
+                .unwrap_or_else(|| DEFAULT_INPUT_FORMAT.to_string()),
+            }
+        }
+        "audio" => match str_at("/audio_event/audio_base_64") {
+            Some(audio) => TalkEvent::Audio(
+                general_purpose::STANDARD
+                    .decode(audio)
+                    .context("Failed to decode agent audio")?,
+            ),
+            None => TalkEvent::Other,
+        },
+        "agent_response" => str_at("/agent_response_event/agent_response")
+            .map_or(TalkEvent::Other, TalkEvent::AgentText),
+        "user_transcript" => str_at("/user_transcription_event/user_transcript")
+            .map_or(TalkEvent::Other, TalkEvent::UserText),
+        "interruption" => TalkEvent::Interrupted,
+        "ping" => TalkEvent::Ping(event["ping_event"]["event_id"].as_u64().unwrap_or_default()),
+        "error" => TalkEvent::Error(
+            str_at("/error_event/error").unwrap_or_else(|| "unknown error".to_string()),
+        ),
+        _ => TalkEvent::Other,
+    })
+}
+
+/// Microphone samples at the device's rate as a `user_audio_chunk` message
+/// at the agent's input rate
+fn audio_chunk_message(pcm: &[u8], rate: u32, channels: u16, input_rate: u32) -> Value {
+    let mono = downmix(&samples_from_pcm16(pcm), channels);
+    let samples = resample(&mono, rate, input_rate);
+    json!({ "user_audio_chunk": general_purpose::STANDARD.encode(samples_to_pcm16(&samples)) })
+}
+
+/// Plays agent audio as it arrives
+struct Speaker {
+    #[cfg(feature = "audio")]
+    player: crate::audio::audio_io::StreamingPlayer,
+}
+
+impl Speaker {
+    #[cfg(feature = "audio")]
+    fn open(sample_rate: u32) -> Result<Self> {
+        let player = crate::audio::audio_io::StreamingPlayer::pcm(sample_rate)
+            .map_err(|e| anyhow::anyhow!("Failed to open audio output: {}", e))?;
+        Ok(Self { player })
+    }
+
+    #[cfg(not(feature = "audio"))]
+    fn open(_sample_rate: u32) -> Result<Self> {
+        Err(anyhow::anyhow!(
+            "Audio playback needs the audio feature. Rebuild with --features audio"
+        ))
+    }
+
+    fn play(&self, pcm: &[u8]) {
+        #[cfg(feature = "audio")]
+        let _ = self.player.send_chunk(pcm);
+        #[cfg(not(feature = "audio"))]
+        let _ = pcm;
+    }
+
+    fn clear(&self) {
+        #[cfg(feature = "audio")]
+        let _ = self.player.clear();
+    }
+}
+
+pub async fn execute(args: ConverseTalkArgs, api_key: &str) -> Result<()> {
+    let dynamic_variables = parse_dynamic_vars(&args.dynamic_vars)?;
+    let init_message =
+        build_initiation_message(None, None, args.language.as_deref(), &dynamic_variables);
+    let endpoint = ConversationEndpoint {
+        agent_id: args.agent_id.clone(),
+        api_key: api_key.to_string(),
+        auth: if args.use_signed_url {
+            ConversationAuth::SignedUrl
+        } else if args.token {
+            ConversationAuth::Token
+        } else {
+            ConversationAuth::Public
+        },
+    };
+
+    // Open the microphone first: without it there is nothing to talk with
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let capture = start_capture(args.device.clone(), tx)?;
+
+    print_info(&format!(
+        "Connecting to agent '{}'...",
+        args.agent_id.cyan()
+    ));
+    let mut ws = connect_conversation(&endpoint, &init_message).await?;
+
+    let mut input_rate: Option<u32> = None;
+    let mut speaker: Option<Speaker> = None;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            msg = ws.next() => {
+                let text = match msg {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => {
+                        print_warning("Connection closed by server");
+                        break;
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(anyhow::anyhow!("Connection lost: {}", e)),
+                };
+                match parse_event(&text)? {
+                    TalkEvent::Connected { conversation_id, input_rate: rate, output_format } => {
+                        input_rate = Some(rate);
+                        match pcm_sample_rate(&output_format) {
+                            Some(output_rate) => speaker = Some(Speaker::open(output_rate)?),
+                            None => print_warning(&format!(
+                                "The agent sends {} audio, which can't be played here; set its output format to pcm_16000 to hear it",
+                                output_format
+                            )),
+                        }
+                        print_success("Connected to agent!");
+                        print_info(&format!("Conversation ID: {}", conversation_id));
+                        print_info("Start talking. Press Ctrl+C to hang up.\n");
+                    }
+                    TalkEvent::Audio(pcm) => {
+                        if let Some(speaker) = &speaker {
+                            speaker.play(&pcm);
+                        }
+                    }
+                    TalkEvent::AgentText(text) => println!("{} {}", "[Agent]:".green().bold(), text),
+                    TalkEvent::UserText(text) => println!("{} {}", "[You]:".blue().bold(), text),
+                    TalkEvent::Interrupted => {
+                        if let Some(speaker) = &speaker {
+                            speaker.clear();
+                        }
+                    }
+                    TalkEvent::Ping(event_id) => {
+                        let pong = json!({ "type": "pong", "event_id": event_id });
+                        ws.send(Message::Text(pong.to_string()))
+                            .await
+                            .context("Failed to answer ping")?;
+                    }
+                    TalkEvent::Error(error) => print_error(&format!("Error: {}", error)),
+                    TalkEvent::Other => {}
+                }
+            }
+            chunk = rx.recv() => {
+                let Some((pcm, rate, channels)) = chunk else {
+                    // Capture ended on its own, usually because the device failed
+                    capture.await.context("Audio capture stopped")??;
+                    break;
+                };
+                // Audio from before the conversation started is dropped
+                if let Some(input_rate) = input_rate {
+                    let message = audio_chunk_message(&pcm, rate, channels, input_rate);
+                    ws.send(Message::Text(message.to_string()))
+                        .await
+                        .context("Failed to send microphone audio")?;
+                }
+            }
+            _ = &mut ctrl_c => {
+                println!();
+                break;
+            }
+        }
+    }
+
+    let _ = ws.close(None).await;
+    print_success("Conversation ended");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        let metadata = r#"{"type":"conversation_initiation_metadata","conversation_initiation_metadata_event":{"conversation_id":"conv_1","agent_output_audio_format":"pcm_22050","user_input_audio_format":"pcm_16000"}}"#;
+        assert_eq!(
+            parse_event(metadata).unwrap(),
+            TalkEvent::Connected {
+                conversation_id: "conv_1".to_string(),
+                input_rate: 16_000,
+                output_format: "pcm_22050".to_string(),
+            }
+        );
+        let ulaw_input = r#"{"type":"conversation_initiation_metadata","conversation_initiation_metadata_event":{"user_input_audio_format":"ulaw_8000"}}"#;
+        assert!(parse_event(ulaw_input).is_err());
+
+        let audio = r#"{"type":"audio","audio_event":{"event_id":1,"audio_base_64":"AQID"}}"#;
+        assert_eq!(parse_event(audio).unwrap(), TalkEvent::Audio(vec![1, 2, 3]));
+        assert_eq!(
+            parse_event(r#"{"type":"ping","ping_event":{"event_id":7}}"#).unwrap(),
+            TalkEvent::Ping(7)
+        );
+        assert_eq!(
+            parse_event(r#"{"type":"interruption","interruption_event":{}}"#).unwrap(),
+            TalkEvent::Interrupted
+        );
+        assert_eq!(
+            parse_event(r#"{"type":"vad_score"}"#).unwrap(),
+            TalkEvent::Other
+        );
+    }
+
+    #[test]
+    fn test_audio_chunk_message_resamples_to_input_rate() {
+        // 4 stereo frames at 32 kHz become 2 mono samples at 16 kHz
+        let pcm = samples_to_pcm16(&[100, 300, 100, 300, 100, 300, 100, 300]);
+        let message = audio_chunk_message(&pcm, 32_000, 2, 16_000);
+        let audio = general_purpose::STANDARD
+            .decode(message["user_audio_chunk"].as_str().unwrap())
+            .unwrap();
+        assert_eq!(samples_from_pcm16(&audio), [200, 200]);
+    }
+}
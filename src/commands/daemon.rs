@@ -0,0 +1,411 @@
+//! Job queue daemon
+//!
+//! Watches a directory for job files and runs each one as a CLI invocation:
+//!
+//! ```json
+//! { "command": "tts", "args": ["Hello", "-o", "hello.mp3"] }
+//! ```
+//!
+//! A claimed job moves to `running/`, then to `done/` or `failed/` next to a
+//! `<job>.result.json` manifest with the exit code and captured output.
+
+use crate::cli::DaemonArgs;
+use crate::client::{initial_concurrency, AdaptiveConcurrency};
+use crate::errors::is_rate_limited;
+use crate::output::{print_error, print_info, print_success, print_warning};
+use crate::utils::unix_now;
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+/// Commands a job may not run (long-running or interactive)
+pub(crate) const FORBIDDEN_COMMANDS: &[&str] =
+    &["daemon", "schedule", "interactive", "repl", "mcp", "update"];
+
+/// A job description read from the queue directory
+#[derive(Debug, Deserialize, PartialEq)]
+struct Job {
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+/// Result manifest written after a job finishes
+#[derive(Debug, Serialize)]
+struct JobManifest {
+    job: String,
+    command: Option<String>,
+    args: Vec<String>,
+    status: &'static str,
+    exit_code: Option<i32>,
+    started_at: u64,
+    finished_at: u64,
+    duration_secs: f64,
+    stdout: String,
+    stderr: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Queue directory layout
+struct QueueDirs {
+    queue: PathBuf,
+    running: PathBuf,
+    done: PathBuf,
+    failed: PathBuf,
+}
+
+impl QueueDirs {
+    fn create(queue: &Path) -> Result<Self> {
+        let dirs = Self {
+            queue: queue.to_path_buf(),
+            running: queue.join("running"),
+            done: queue.join("done"),
+            failed: queue.join("failed"),
+        };
+        for dir in [&dirs.queue, &dirs.running, &dirs.done, &dirs.failed] {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        Ok(dirs)
+    }
+}
+
+pub async fn execute(args: DaemonArgs, api_key: &str) -> Result<()> {
+    if args.concurrency == 0 {
+        return Err(anyhow::anyhow!("--concurrency must be at least 1"));
+    }
+    if args.rate == Some(0) {
+        return Err(anyhow::anyhow!("--rate must be at least 1"));
+    }
+
+    let dirs = Arc::new(QueueDirs::create(Path::new(&args.queue_dir))?);
+    let exe = std::env::current_exe().context("Failed to locate the CLI executable")?;
+    let start_interval = args.rate.map(|rate| Duration::from_secs(60) / rate);
+    // Auto-tuning stays within the account's reported limit and backs off on 429s
+    let concurrency = if args.no_auto_tune {
+        args.concurrency
+    } else {
+        initial_concurrency(args.concurrency)
+    };
+    let limiter = AdaptiveConcurrency::new(concurrency, concurrency);
+
+    let leftover = list_jobs(&dirs.running)?;
+    if !leftover.is_empty() {
+        print_warning(&format!(
+            "{} job(s) in {} were interrupted; move them back to the queue to retry",
+            leftover.len(),
+            dirs.running.display()
+        ));
+    }
+
+    print_info(&format!(
+        "Watching '{}' (concurrency: {}{})",
+        args.queue_dir.cyan(),
+        if concurrency == args.concurrency {
+            concurrency.to_string()
+        } else {
+            format!("{}, capped by the account limit", concurrency)
+        },
+        args.rate
+            .map(|r| format!(", max {} jobs/min", r))
+            .unwrap_or_default()
+    ));
+
+    let mut tasks = JoinSet::new();
+    let mut last_start: Option<Instant> = None;
+    let (mut succeeded, mut failed) = (0usize, 0usize);
+
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
+
+    'scan: loop {
+        for path in list_jobs(&dirs.queue)? {
+            let permit = tokio::select! {
+                permit = limiter.acquire() => permit,
+                _ = &mut shutdown => break 'scan,
+            };
+
+            if let (Some(interval), Some(last)) = (start_interval, last_start) {
+                let wait = interval.saturating_sub(last.elapsed());
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = &mut shutdown => break 'scan,
+                }
+            }
+
+            // Claim the job; another daemon may have taken it already
+            let Some(name) = path.file_name().map(|n| n.to_os_string()) else {
+                continue;
+            };
+            let running_path = dirs.running.join(&name);
+            if std::fs::rename(&path, &running_path).is_err() {
+                continue;
+            }
+            last_start = Some(Instant::now());
+
+            let dirs = Arc::clone(&dirs);
+            let exe = exe.clone();
+            let api_key = api_key.to_string();
+            let limiter = limiter.clone();
+            let auto_tune = !args.no_auto_tune;
+            tasks.spawn(async move {
+                let manifest = run_job(&exe, &api_key, &running_path).await;
+                let ok = finish_job(&dirs, &running_path, &manifest);
+                let throttled = auto_tune && !ok && job_was_throttled(&manifest);
+                if let Some(limit) = limiter.release(permit, throttled) {
+                    let reason = if throttled {
+                        "rate limited"
+                    } else {
+                        "no throttling"
+                    };
+                    print_info(&format!("Concurrency now {} ({})", limit, reason));
+                }
+                ok
+            });
+        }
+
+        if args.once && tasks.is_empty() {
+            break;
+        }
+
+        tokio::select! {
+            Some(result) = tasks.join_next(), if !tasks.is_empty() => {
+                tally(result, &mut succeeded, &mut failed);
+            }
+            _ = tokio::time::sleep(Duration::from_secs(args.poll)), if !args.once => {}
+            _ = &mut shutdown => break,
+        }
+    }
+
+    if !tasks.is_empty() {
+        print_info(&format!(
+            "Waiting for {} running job(s) to finish...",
+            tasks.len()
+        ));
+    }
+    while let Some(result) = tasks.join_next().await {
+        tally(result, &mut succeeded, &mut failed);
+    }
+
+    print_success(&format!(
+        "Daemon stopped: {} succeeded, {} failed",
+        succeeded, failed
+    ));
+    Ok(())
+}
+
+fn tally(
+    result: std::result::Result<bool, tokio::task::JoinError>,
+    succeeded: &mut usize,
+    failed: &mut usize,
+) {
+    match result {
+        Ok(true) => *succeeded += 1,
+        _ => *failed += 1,
+    }
+}
+
+/// Whether a failed job hit the API rate limit (HTTP 429)
+fn job_was_throttled(manifest: &JobManifest) -> bool {
+    is_rate_limited(&anyhow::anyhow!("{}", manifest.stderr))
+}
+
+/// Queued job files in a directory, oldest name first
+fn list_jobs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut jobs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+    jobs.sort();
+    Ok(jobs)
+}
+
+/// Parse and validate a job description
+fn parse_job(contents: &str) -> Result<Job> {
+    let mut job: Job = serde_json::from_str(contents).context("Invalid job file")?;
+    job.command = job.command.trim().to_string();
+    let command = job.command.as_str();
+    if command.is_empty() {
+        return Err(anyhow::anyhow!("Job has an empty 'command'"));
+    }
+    if FORBIDDEN_COMMANDS.contains(&command) {
+        return Err(anyhow::anyhow!(
+            "Command '{}' cannot be run from a job",
+            command
+        ));
+    }
+    Ok(job)
+}
+
+/// Run one job as a child CLI process and describe the outcome
+async fn run_job(exe: &Path, api_key: &str, path: &Path) -> JobManifest {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let started_at = unix_now();
+    let start = Instant::now();
+
+    let mut manifest = JobManifest {
+        job: name.clone(),
+        command: None,
+        args: Vec::new(),
+        status: "failed",
+        exit_code: None,
+        started_at,
+        finished_at: started_at,
+        duration_secs: 0.0,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+
+    let job = match std::fs::read_to_string(path)
+        .context("Failed to read job file")
+        .and_then(|contents| parse_job(&contents))
+    {
+        Ok(job) => job,
+        Err(e) => {
+            print_error(&format!("{}: {:#}", name, e));
+            manifest.error = Some(format!("{:#}", e));
+            return manifest;
+        }
+    };
+
+    print_info(&format!(
+        "Running {}: {} {}",
+        name.cyan(),
+        job.command,
+        job.args.join(" ")
+    ));
+
+    // Jobs run unattended, so prompts are always auto-confirmed. The flag
+    // goes before the command, where a trailing `--` or variadic argument
+    // can't take it as a value.
+    let output = tokio::process::Command::new(exe)
+        .arg("--yes")
+        .arg(&job.command)
+        .args(&job.args)
+        .env("ELEVENLABS_API_KEY", api_key)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await;
+
+    manifest.command = Some(job.command);
+    manifest.args = job.args;
+    manifest.finished_at = unix_now();
+    manifest.duration_secs = start.elapsed().as_secs_f64();
+
+    match output {
+        Ok(output) => {
+            manifest.exit_code = output.status.code();
+            manifest.stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            manifest.stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            if output.status.success() {
+                manifest.status = "succeeded";
+                print_success(&format!(
+                    "{} finished in {:.1}s",
+                    name.green(),
+                    manifest.duration_secs
+                ));
+            } else {
+                print_error(&format!(
+                    "{} failed with exit code {}",
+                    name,
+                    output
+                        .status
+                        .code()
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "unknown".to_string())
+                ));
+            }
+        }
+        Err(e) => {
+            print_error(&format!("{}: failed to start: {}", name, e));
+            manifest.error = Some(format!("Failed to start job: {}", e));
+        }
+    }
+
+    manifest
+}
+
+/// Move the job file to done/ or failed/ and write its manifest; returns success
+fn finish_job(dirs: &QueueDirs, running_path: &Path, manifest: &JobManifest) -> bool {
+    let succeeded = manifest.status == "succeeded";
+    let target_dir = if succeeded { &dirs.done } else { &dirs.failed };
+
+    let stem = running_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| manifest.job.clone());
+    let manifest_path = target_dir.join(format!("{}.result.json", stem));
+
+    let written = serde_json::to_string_pretty(manifest)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| Ok(std::fs::write(&manifest_path, json)?));
+    if let Err(e) = written {
+        print_warning(&format!(
+            "Failed to write {}: {}",
+            manifest_path.display(),
+            e
+        ));
+    }
+
+    if let Some(name) = running_path.file_name() {
+        if let Err(e) = std::fs::rename(running_path, target_dir.join(name)) {
+            print_warning(&format!("Failed to move {}: {}", running_path.display(), e));
+        }
+    }
+
+    succeeded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_job() {
+        let job = parse_job(r#"{"command": "tts", "args": ["Hello", "-o", "a.mp3"]}"#).unwrap();
+        assert_eq!(job.command, "tts");
+        assert_eq!(job.args, vec!["Hello", "-o", "a.mp3"]);
+
+        assert!(parse_job(r#"{"command": "user"}"#).unwrap().args.is_empty());
+        assert!(parse_job(r#"{"command": " "}"#).is_err());
+        assert!(parse_job(r#"{"command": "daemon", "args": []}"#).is_err());
+        assert!(parse_job(r#"{"command": "schedule", "args": ["run"]}"#).is_err());
+        assert_eq!(
+            parse_job(r#"{"command": " user "}"#).unwrap().command,
+            "user"
+        );
+        assert!(parse_job(r#"{"args": ["x"]}"#).is_err());
+        assert!(parse_job("not json").is_err());
+    }
+
+    #[test]
+    fn test_list_jobs_only_json_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("a.JSON"), "{}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        std::fs::create_dir(dir.path().join("done.json")).unwrap();
+
+        let jobs = list_jobs(dir.path()).unwrap();
+        let names: Vec<_> = jobs
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["a.JSON", "b.json"]);
+    }
+}
@@ -1,7 +1,13 @@
+use crate::casting::Casting;
 use crate::cli::DialogueArgs;
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
-use crate::utils::{confirm_overwrite, format_to_extension, generate_output_filename};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::tts::dialogue_settings;
+use crate::config::{Config, VoiceDefaults};
+use crate::output::{print_info, print_success, print_warning, Progress};
+use crate::utils::{
+    confirm_overwrite, default_output_path, format_to_extension, generate_output_filename,
+};
+use crate::validation::preflight_tts;
 use anyhow::{Context, Result};
 use colored::*;
 use serde::Deserialize;
@@ -30,6 +36,46 @@ pub async fn execute(
         ));
     }
 
+    // With a casting file, inputs name characters: swap in their voices,
+    // settings and pronunciations
+    let config = Config::load().unwrap_or_default();
+    let casting = args.casting.as_deref().map(Casting::load).transpose()?;
+    let mut settings: Vec<VoiceDefaults> = Vec::with_capacity(inputs.len());
+    let inputs: Vec<(String, String)> = inputs
+        .into_iter()
+        .map(|(text, voice)| {
+            let cast = casting.as_ref().and_then(|c| c.character(&voice));
+            if casting.is_some() && cast.is_none() {
+                print_warning(&format!(
+                    "'{}' is not in the casting file; using it as a voice ID",
+                    voice
+                ));
+            }
+            match cast {
+                Some(cast) => {
+                    settings.push(
+                        config
+                            .voice_defaults_for(&[&voice, &cast.voice])
+                            .overridden_by(cast.settings),
+                    );
+                    (cast.apply_dictionary(&text), cast.voice.clone())
+                }
+                None => {
+                    settings.push(config.voice_defaults_for(&[&voice]));
+                    (text, voice)
+                }
+            }
+        })
+        .collect();
+
+    // Pre-flight checks against the combined dialogue text
+    let combined: String = inputs
+        .iter()
+        .map(|(text, _)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    preflight_tts(&combined, &args.model, output_format, None)?;
+
     print_info(&format!(
         "Creating dialogue with {} input(s)...",
         inputs.len().to_string().yellow()
@@ -37,7 +83,7 @@ pub async fn execute(
 
     let client = create_http_client();
 
-    let url = "https://api.elevenlabs.io/v1/text-to-dialogue/stream/with-timestamps";
+    let url = format!("{}/v1/text-to-dialogue/stream/with-timestamps", api_base());
 
     // Build request body
     let dialogue_inputs: Vec<_> = inputs
@@ -45,28 +91,31 @@ pub async fn execute(
         .map(|(text, voice_id)| json!({ "text": text, "voice_id": voice_id }))
         .collect();
 
-    let body = json!({
+    let mut body = json!({
         "inputs": dialogue_inputs,
         "model_id": args.model,
         "output_format": output_format
     });
+    if let Some(settings) = dialogue_settings(&settings) {
+        body["settings"] = settings;
+    }
 
     // Make request
+    let progress = Progress::spinner("Generating dialogue");
     let response = client
         .post(url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to send dialogue request")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("ElevenLabs API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let dialogue_response: DialogueResponse =
-        response.json().await.context("Failed to parse response")?;
+    let dialogue_response: DialogueResponse = read_json(response).await?;
+    progress.finish();
 
     // Decode audio
     let audio_bytes = base64::Engine::decode(
@@ -79,7 +128,10 @@ pub async fn execute(
     let output_path = if let Some(output) = args.output {
         output
     } else {
-        generate_output_filename("dialogue", format_to_extension(output_format))
+        default_output_path(
+            &generate_output_filename("dialogue", format_to_extension(output_format)),
+            None,
+        )?
     };
 
     // Check for overwrite
@@ -1,18 +1,36 @@
-use crate::cli::{DubbingArgs, DubbingCommands};
-use crate::output::{print_error, print_info, print_success, print_warning};
-use crate::utils::{confirm_overwrite, validate_file_size};
-use anyhow::Result;
+use crate::cli::{DubbingArgs, DubbingCommands, JobKind, TimeRange, WaitArgs};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::commands::dubbing_speakers::{apply_speaker_map, parse_speaker_map};
+use crate::output::{
+    is_json_mode, print_error, print_info, print_success, print_warning, Progress,
+};
+use crate::utils::{confirm_overwrite, poll_until, PollStatus};
+use crate::validation::validate_input_file;
+use anyhow::{Context, Result};
 use colored::*;
+use comfy_table::Table;
 use elevenlabs_rs::{
     endpoints::genai::dubbing::{
         DeleteDubbing, DubAVideoOrAnAudioFile, DubbingBody, GetDubbedAudio, GetDubbing,
     },
     ElevenLabsClient,
 };
+use futures::StreamExt;
+use reqwest::{Client, Method};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Inputs rendered back to video after a speaker map is applied
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm"];
 
 pub async fn execute(args: DubbingArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     match args.command {
         DubbingCommands::Create {
@@ -21,20 +39,60 @@ pub async fn execute(args: DubbingArgs, api_key: &str, assume_yes: bool) -> Resu
             target_lang,
             num_speakers,
             watermark,
+            speaker_map,
+            wait,
         } => {
             create_dub(
                 &client,
+                api_key,
                 &file,
                 &source_lang,
                 &target_lang,
                 num_speakers,
                 watermark,
+                speaker_map.as_deref(),
+                &wait,
             )
             .await?
         }
+        DubbingCommands::Estimate {
+            file,
+            target_lang,
+            duration,
+            credits_per_minute,
+        } => estimate_dub(&file, &target_lang, duration, credits_per_minute)?,
         DubbingCommands::Status { dubbing_id } => get_status(&client, &dubbing_id).await?,
-        DubbingCommands::Download { dubbing_id, output } => {
-            download_dub(&client, &dubbing_id, output, assume_yes).await?
+        DubbingCommands::Download {
+            dubbing_id,
+            language,
+            output,
+        } => download_dub(api_key, &dubbing_id, language, output, assume_yes).await?,
+        DubbingCommands::Transcript {
+            dubbing_id,
+            language,
+            format,
+            output,
+        } => export_transcript(api_key, &dubbing_id, language, &format, output, assume_yes).await?,
+        DubbingCommands::BurnCaptions {
+            dubbing_id,
+            lang,
+            output,
+        } => burn_captions(&client, api_key, &dubbing_id, lang, output, assume_yes).await?,
+        DubbingCommands::Redo {
+            dubbing_id,
+            range,
+            lang,
+            retranslate,
+            render,
+            no_render,
+            dry_run,
+        } => {
+            let options = RedoOptions {
+                retranslate,
+                render: (!no_render).then_some(render),
+                dry_run,
+            };
+            redo_segments(api_key, &dubbing_id, &range, &lang, &options).await?
         }
         DubbingCommands::Delete { dubbing_id } => {
             delete_dub(&client, &dubbing_id, assume_yes).await?
@@ -44,22 +102,29 @@ pub async fn execute(args: DubbingArgs, api_key: &str, assume_yes: bool) -> Resu
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn create_dub(
     client: &ElevenLabsClient,
+    api_key: &str,
     file: &str,
     source_lang: &str,
     target_lang: &str,
     num_speakers: Option<u32>,
     watermark: bool,
+    speaker_map: Option<&str>,
+    wait: &WaitArgs,
 ) -> Result<()> {
     let file_path = Path::new(file);
 
-    if !file_path.exists() {
-        return Err(anyhow::anyhow!("File not found: {}", file));
-    }
-
-    // Validate file size
-    validate_file_size(file_path)?;
+    // Pre-flight checks (existence, size, extension)
+    validate_input_file(file_path)?;
+    let speaker_map = speaker_map
+        .map(|path| {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path))?;
+            parse_speaker_map(&text).with_context(|| format!("Invalid speaker map {}", path))
+        })
+        .transpose()?;
 
     let metadata = std::fs::metadata(file_path)?;
     let file_size = metadata.len();
@@ -73,50 +138,412 @@ async fn create_dub(
         (file_size as f64 / 1_048_576.0).round()
     ));
 
-    // Build request body
-    let mut body = DubbingBody::new(target_lang)
-        .with_file(file)
-        .with_source_lang(source_lang);
-
-    if let Some(speakers) = num_speakers {
-        body = body.with_num_speakers(speakers);
-    }
+    // Create dub
+    let start_time = std::time::Instant::now();
+    let progress = Progress::spinner("Uploading for dubbing");
+    let created = match &speaker_map {
+        // Speaker voices can only be changed in Dubbing Studio projects
+        Some(_) => {
+            create_studio_dub(
+                api_key,
+                file_path,
+                source_lang,
+                target_lang,
+                num_speakers,
+                watermark,
+            )
+            .await
+        }
+        None => {
+            // Build request body
+            let mut body = DubbingBody::new(target_lang)
+                .with_file(file)
+                .with_source_lang(source_lang);
 
-    if watermark {
-        body = body.with_watermark(true);
-    }
+            if let Some(speakers) = num_speakers {
+                body = body.with_num_speakers(speakers);
+            }
 
-    let endpoint = DubAVideoOrAnAudioFile::new(body);
+            if watermark {
+                body = body.with_watermark(true);
+            }
 
-    // Create dub
-    let start_time = std::time::Instant::now();
-    let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+            let endpoint = DubAVideoOrAnAudioFile::new(body);
+            client
+                .hit(endpoint)
+                .await
+                .map(|r| (r.dubbing_id, r.expected_duration_sec as f64))
+                .map_err(|e| anyhow::anyhow!(e))
+        }
+    };
+    progress.finish();
+    let (dubbing_id, expected_duration_sec) = created?;
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file.to_string());
+    let job = crate::journal::record(
+        api_key,
+        JobKind::Dubbing,
+        &dubbing_id,
+        None,
+        &format!("{} → {}", file_name, target_lang),
+    );
 
     print_success(&format!(
         "Dubbing project created in {:.2}s",
         start_time.elapsed().as_secs_f64()
     ));
-    println!("  Dubbing ID: {}", response.dubbing_id.cyan());
+    println!("  Dubbing ID: {}", dubbing_id.cyan());
     println!(
         "  Expected duration: {}s",
-        response.expected_duration_sec.to_string().yellow()
+        expected_duration_sec.to_string().yellow()
     );
+
+    if let Some(map) = &speaker_map {
+        let http_client = create_http_client();
+        wait_until_dubbed(&http_client, api_key, &dubbing_id, wait).await?;
+        let video = file_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| VIDEO_EXTENSIONS.contains(&e.as_str()));
+        let progress = Progress::spinner("Assigning speaker voices");
+        let renders = apply_speaker_map(
+            &http_client,
+            api_key,
+            &dubbing_id,
+            map,
+            if video { "mp4" } else { "mp3" },
+        )
+        .await;
+        progress.finish();
+        let renders = renders?;
+        let languages: Vec<&str> = renders
+            .iter()
+            .filter_map(|r| r["language"].as_str())
+            .collect();
+        print_success(&format!(
+            "Assigned voices to {} speaker(s); rendering {}",
+            map.len(),
+            languages.join(", ")
+        ));
+
+        // The first pass is already done; what's left to wait for are the renders
+        if wait.wait {
+            let render_ids: Vec<&str> = renders
+                .iter()
+                .filter_map(|r| r["render_id"].as_str())
+                .collect();
+            wait_for_renders(&http_client, api_key, &dubbing_id, &render_ids, wait).await?;
+            print_success(&format!("Rendered {}", languages.join(", ")));
+            return Ok(());
+        }
+    }
+
+    if wait.wait {
+        return super::jobs::wait_if_requested(api_key, job, wait).await;
+    }
     println!(
         "\nUse 'elevenlabs dub status {}' to check progress",
-        response.dubbing_id
+        dubbing_id
     );
 
     Ok(())
 }
 
+/// Create a Dubbing Studio project, whose speakers can be edited afterwards
+async fn create_studio_dub(
+    api_key: &str,
+    file_path: &Path,
+    source_lang: &str,
+    target_lang: &str,
+    num_speakers: Option<u32>,
+    watermark: bool,
+) -> Result<(String, f64)> {
+    let data = tokio::fs::read(file_path)
+        .await
+        .with_context(|| format!("Failed to read {}", file_path.display()))?;
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "media".to_string());
+    let mut form = reqwest::multipart::Form::new()
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(data).file_name(file_name),
+        )
+        .text("source_lang", source_lang.to_string())
+        .text("target_lang", target_lang.to_string())
+        .text("watermark", watermark.to_string())
+        .text("dubbing_studio", "true");
+    if let Some(speakers) = num_speakers {
+        form = form.text("num_speakers", speakers.to_string());
+    }
+
+    let response = create_http_client()
+        .post(format!("{}/v1/dubbing", api_base()))
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to upload for dubbing")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let created: Value = read_json(response).await?;
+    let dubbing_id = created["dubbing_id"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("The API returned no dubbing ID"))?;
+    Ok((
+        dubbing_id.to_string(),
+        created["expected_duration_sec"]
+            .as_f64()
+            .unwrap_or_default(),
+    ))
+}
+
+/// Poll until the first pass of a dub is done and its speakers are known
+async fn wait_until_dubbed(
+    client: &Client,
+    api_key: &str,
+    dubbing_id: &str,
+    wait: &WaitArgs,
+) -> Result<()> {
+    let url = format!("{}/v1/dubbing/{}", api_base(), dubbing_id);
+    poll_until(
+        &format!("Waiting for '{}' to detect speakers", dubbing_id),
+        Duration::from_secs(wait.interval),
+        wait.timeout.map(Duration::from_secs),
+        || async {
+            let response = client
+                .get(&url)
+                .header("xi-api-key", api_key)
+                .send_with_retry()
+                .await
+                .context("Failed to fetch dubbing status")?;
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+            let dubbing: Value = read_json(response).await?;
+            match dubbing["status"].as_str().unwrap_or_default() {
+                "dubbed" => Ok(PollStatus::Done(())),
+                "failed" => Err(anyhow::anyhow!(
+                    "Dubbing '{}' failed: {}",
+                    dubbing_id,
+                    dubbing["error"].as_str().unwrap_or("unknown error")
+                )),
+                status => Ok(PollStatus::Pending(status.to_string())),
+            }
+        },
+    )
+    .await
+}
+
+/// Poll a Dubbing Studio resource until the given renders are complete
+async fn wait_for_renders(
+    client: &Client,
+    api_key: &str,
+    dubbing_id: &str,
+    render_ids: &[&str],
+    wait: &WaitArgs,
+) -> Result<()> {
+    let url = format!("{}/v1/dubbing/resource/{}", api_base(), dubbing_id);
+    poll_until(
+        &format!("Waiting for '{}' to render", dubbing_id),
+        Duration::from_secs(wait.interval),
+        wait.timeout.map(Duration::from_secs),
+        || async {
+            let response = client
+                .get(&url)
+                .header("xi-api-key", api_key)
+                .send_with_retry()
+                .await
+                .context("Failed to fetch dubbing resource")?;
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+            let resource: Value = read_json(response).await?;
+            let mut pending = 0;
+            for id in render_ids {
+                match resource["renders"][*id]["status"]
+                    .as_str()
+                    .unwrap_or_default()
+                {
+                    "complete" => {}
+                    "failed" => {
+                        return Err(anyhow::anyhow!(
+                            "Render '{}' of dubbing '{}' failed",
+                            id,
+                            dubbing_id
+                        ))
+                    }
+                    _ => pending += 1,
+                }
+            }
+            Ok(if pending == 0 {
+                PollStatus::Done(())
+            } else {
+                PollStatus::Pending(format!("{} of {} rendering", pending, render_ids.len()))
+            })
+        },
+    )
+    .await
+}
+
+/// Cost and time estimate for dubbing one file
+#[derive(Debug, Serialize)]
+struct DubEstimate {
+    file: String,
+    duration_secs: f64,
+    credits_per_minute: u64,
+    languages: Vec<LanguageEstimate>,
+    total_credits: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct LanguageEstimate {
+    language: String,
+    credits: u64,
+}
+
+fn build_dub_estimate(
+    file: &str,
+    duration_secs: f64,
+    languages: &[String],
+    credits_per_minute: u64,
+) -> DubEstimate {
+    let credits = (duration_secs / 60.0 * credits_per_minute as f64).ceil() as u64;
+    let languages: Vec<LanguageEstimate> = languages
+        .iter()
+        .map(|language| LanguageEstimate {
+            language: language.clone(),
+            credits,
+        })
+        .collect();
+    DubEstimate {
+        file: file.to_string(),
+        duration_secs,
+        credits_per_minute,
+        total_credits: credits * languages.len() as u64,
+        languages,
+    }
+}
+
+/// Media duration in seconds, read with ffprobe
+fn probe_duration(path: &Path) -> Result<f64> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+        ])
+        .arg(path)
+        .output()
+        .map_err(|_| {
+            anyhow::anyhow!("ffprobe not found in PATH. Install ffmpeg or pass --duration")
+        })?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|d| d.is_finite() && *d > 0.0)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the duration of {}", path.display()))
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3_600, (secs % 3_600) / 60),
+    }
+}
+
+fn estimate_dub(
+    file: &str,
+    target_langs: &[String],
+    duration: Option<f64>,
+    rate: u64,
+) -> Result<()> {
+    let languages: Vec<String> = target_langs
+        .iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if languages.is_empty() {
+        return Err(anyhow::anyhow!("At least one --target-lang is required"));
+    }
+
+    let duration_secs = match duration {
+        Some(d) if d.is_finite() && d > 0.0 => d,
+        Some(_) => return Err(anyhow::anyhow!("--duration must be greater than 0")),
+        None => {
+            let path = Path::new(file);
+            validate_input_file(path)?;
+            probe_duration(path)?
+        }
+    };
+    let estimate = build_dub_estimate(file, duration_secs, &languages, rate);
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&estimate)?);
+        return Ok(());
+    }
+
+    print_info(&format!(
+        "'{}': {} of media at {} credits/min per language",
+        file.cyan(),
+        format_duration(duration_secs),
+        rate
+    ));
+
+    let mut table = Table::new();
+    table.set_header(vec!["Language", "Credits"]);
+    for language in &estimate.languages {
+        table.add_row(vec![
+            language.language.clone(),
+            language.credits.to_string(),
+        ]);
+    }
+    table.add_row(vec![
+        "Total".bold().to_string(),
+        estimate.total_credits.to_string().bold().to_string(),
+    ]);
+    println!("{}", table);
+    print_info("The final charge is shown in your usage history");
+    Ok(())
+}
+
 async fn get_status(client: &ElevenLabsClient, dubbing_id: &str) -> Result<()> {
     print_info(&format!(
         "Checking status of dubbing '{}'...",
         dubbing_id.cyan()
     ));
 
-    let endpoint = GetDubbing::new(dubbing_id);
-    let status = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let status = retry_sdk(Method::GET, || client.hit(GetDubbing::new(dubbing_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let json = serde_json::json!({
+            "dubbing_id": dubbing_id,
+            "name": status.name,
+            "status": status.status,
+            "target_languages": status.target_languages,
+            "error": status.error,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Dubbing Status:".bold().underline());
     println!("  ID: {}", dubbing_id.cyan());
@@ -141,65 +568,546 @@ async fn get_status(client: &ElevenLabsClient, dubbing_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// The parts of `GET /v1/dubbing/{id}` downloads need
+#[derive(Debug, Deserialize)]
+struct DubbingInfo {
+    status: String,
+    #[serde(default)]
+    target_languages: Vec<String>,
+}
+
+async fn fetch_dubbing(client: &Client, api_key: &str, dubbing_id: &str) -> Result<DubbingInfo> {
+    let response = client
+        .get(format!("{}/v1/dubbing/{}", api_base(), dubbing_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch dubbing status")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    read_json(response)
+        .await
+        .context("Failed to parse dubbing status")
+}
+
+/// `language` if the dub has it, else the first target language
+fn pick_language(
+    dubbing_id: &str,
+    target_languages: &[String],
+    language: Option<String>,
+) -> Result<String> {
+    match language {
+        Some(language) => {
+            if !target_languages.iter().any(|l| l == &language) {
+                return Err(anyhow::anyhow!(
+                    "Dubbing '{}' has no '{}' track. Available: {}",
+                    dubbing_id,
+                    language,
+                    target_languages.join(", ")
+                ));
+            }
+            Ok(language)
+        }
+        None => target_languages
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Dubbing '{}' has no target languages", dubbing_id)),
+    }
+}
+
+/// File extension for the dubbed media's content type
+fn media_extension(content_type: Option<&str>) -> &'static str {
+    match content_type {
+        Some(t) if t.starts_with("audio/") => "mp3",
+        _ => "mp4",
+    }
+}
+
 async fn download_dub(
-    client: &ElevenLabsClient,
+    api_key: &str,
     dubbing_id: &str,
+    language: Option<String>,
     output: Option<String>,
     assume_yes: bool,
 ) -> Result<()> {
-    // First check status
-    let status_endpoint = GetDubbing::new(dubbing_id);
-    let status = client
-        .hit(status_endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!(e))?;
+    let client = create_http_client();
+    let info = fetch_dubbing(&client, api_key, dubbing_id).await?;
 
-    if status.status != "dubbed" {
+    if info.status != "dubbed" {
         print_warning(&format!(
             "Dubbing status: {}. The file may not be ready yet.",
-            status.status
+            info.status
         ));
 
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Do you want to continue?")
-            .default(false)
-            .interact()?;
-
-        if !confirm {
+        if !assume_yes && !crate::prompt::confirm("Do you want to continue?")? {
             return Ok(());
         }
     }
 
-    // Get target language
-    let target_lang = status
-        .target_languages
-        .first()
-        .cloned()
-        .unwrap_or_else(|| "en".to_string());
+    let language = pick_language(dubbing_id, &info.target_languages, language)?;
+
+    let response = client
+        .get(format!(
+            "{}/v1/dubbing/{}/audio/{}",
+            api_base(),
+            dubbing_id,
+            language
+        ))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to download dubbed media")?;
 
-    // Determine output path
-    let output_path = output.unwrap_or_else(|| format!("{}_{}.mp4", dubbing_id, target_lang));
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    // Video dubs come back as MP4 and audio dubs as MP3
+    let output_path = output.unwrap_or_else(|| {
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok());
+        format!(
+            "{}_{}.{}",
+            dubbing_id,
+            language,
+            media_extension(content_type)
+        )
+    });
+    let path = Path::new(&output_path);
+    if !confirm_overwrite(path, assume_yes)? {
+        print_info("Cancelled");
+        return Ok(());
+    }
 
     print_info(&format!(
-        "Downloading dubbed audio to '{}'...",
+        "Downloading '{}' dub to '{}'...",
+        language.yellow(),
         output_path.cyan()
     ));
 
-    let endpoint = GetDubbedAudio::new(dubbing_id, &target_lang);
-    let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    // Stream to a partial file so an interrupted download never looks finished
+    let partial = path.with_extension(format!(
+        "{}.part",
+        path.extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("download")
+    ));
+    let progress = match response.content_length() {
+        Some(len) => Progress::bar(len, "Downloading dubbed media"),
+        None => Progress::spinner("Downloading dubbed media"),
+    };
+    let mut file = tokio::fs::File::create(&partial)
+        .await
+        .with_context(|| format!("Failed to create {}", partial.display()))?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Failed to read download stream")?;
+        progress.inc(chunk.len() as u64);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    progress.finish();
+    drop(file);
+    tokio::fs::rename(&partial, path)
+        .await
+        .with_context(|| format!("Failed to move download to {}", output_path))?;
+
+    print_success(&format!("Downloaded -> {}", output_path.green()));
+
+    Ok(())
+}
+
+async fn export_transcript(
+    api_key: &str,
+    dubbing_id: &str,
+    language: Option<String>,
+    format: &str,
+    output: Option<String>,
+    assume_yes: bool,
+) -> Result<()> {
+    let client = create_http_client();
+    let info = fetch_dubbing(&client, api_key, dubbing_id).await?;
+    let language = pick_language(dubbing_id, &info.target_languages, language)?;
+
+    let transcript = fetch_transcript(api_key, dubbing_id, &language, format).await?;
+
+    match output {
+        Some(output_path) => {
+            if !confirm_overwrite(Path::new(&output_path), assume_yes)? {
+                print_info("Cancelled");
+                return Ok(());
+            }
+            std::fs::write(&output_path, transcript)
+                .with_context(|| format!("Failed to write {}", output_path))?;
+            print_success(&format!(
+                "'{}' transcript -> {}",
+                language.yellow(),
+                output_path.green()
+            ));
+        }
+        None => print!("{}", transcript),
+    }
+    Ok(())
+}
+
+async fn burn_captions(
+    client: &ElevenLabsClient,
+    api_key: &str,
+    dubbing_id: &str,
+    lang: Option<String>,
+    output: Option<String>,
+    assume_yes: bool,
+) -> Result<()> {
+    // Fail before downloading anything if ffmpeg is missing
+    let ffmpeg_found = Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !ffmpeg_found {
+        return Err(anyhow::anyhow!(
+            "ffmpeg not found in PATH. Install ffmpeg to burn captions"
+        ));
+    }
+
+    let status = retry_sdk(Method::GET, || client.hit(GetDubbing::new(dubbing_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
 
-    // Check for overwrite
+    if status.status != "dubbed" {
+        return Err(anyhow::anyhow!(
+            "Dubbing '{}' is not finished yet (status: {})",
+            dubbing_id,
+            status.status
+        ));
+    }
+
+    let target_lang = pick_language(dubbing_id, &status.target_languages, lang)?;
+
+    let output_path =
+        output.unwrap_or_else(|| format!("{}_{}_captioned.mp4", dubbing_id, target_lang));
     let path = Path::new(&output_path);
     if !confirm_overwrite(path, assume_yes)? {
         print_info("Cancelled");
         return Ok(());
     }
 
-    // Write file
-    std::fs::write(&output_path, audio)?;
+    let work_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let video_path = work_dir.path().join("dubbed.mp4");
+    let srt_path = work_dir.path().join("captions.srt");
 
-    print_success(&format!("Downloaded -> {}", output_path.green()));
+    let progress = Progress::spinner("Downloading dubbed video");
+    let video = retry_sdk(Method::GET, || {
+        client.hit(GetDubbedAudio::new(dubbing_id, &target_lang))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
+    std::fs::write(&video_path, video)?;
+
+    progress.set_message("Downloading subtitles");
+    let srt = fetch_transcript(api_key, dubbing_id, &target_lang, "srt").await?;
+    std::fs::write(&srt_path, srt)?;
+    progress.finish();
+
+    print_info(&format!(
+        "Burning '{}' captions into '{}'...",
+        target_lang.yellow(),
+        output_path.cyan()
+    ));
+
+    let progress = Progress::spinner("Running ffmpeg");
+    let result = Command::new("ffmpeg")
+        .arg("-y")
+        .args(["-loglevel", "error"])
+        .arg("-i")
+        .arg(&video_path)
+        .arg("-vf")
+        .arg(format!(
+            "subtitles={}",
+            escape_filter_path(&srt_path.to_string_lossy())
+        ))
+        .args(["-c:a", "copy"])
+        .arg(&output_path)
+        .output()
+        .context("Failed to run ffmpeg")?;
+    progress.finish();
+
+    if !result.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
 
+    print_success(&format!("Captioned video -> {}", output_path.green()));
+    Ok(())
+}
+
+/// Download the dubbed transcript for a language as `srt` or `vtt`
+async fn fetch_transcript(
+    api_key: &str,
+    dubbing_id: &str,
+    lang: &str,
+    format: &str,
+) -> Result<String> {
+    let client = create_http_client();
+    let format_type = if format == "vtt" { "webvtt" } else { "srt" };
+    let url = format!(
+        "{}/v1/dubbing/{}/transcript/{}?format_type={}",
+        api_base(),
+        dubbing_id,
+        lang,
+        format_type
+    );
+
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to download subtitles")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Escape a path for use inside an ffmpeg filter argument
+fn escape_filter_path(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        if matches!(c, '\\' | ':' | '\'' | ',' | '[' | ']' | ';') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A speaker segment of a Dubbing Studio resource
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct Segment {
+    id: String,
+    start: f64,
+    end: f64,
+    text: String,
+}
+
+struct RedoOptions {
+    retranslate: bool,
+    /// Render format, or `None` to skip rendering
+    render: Option<String>,
+    dry_run: bool,
+}
+
+/// Segments of a resource, ordered by start time
+fn resource_segments(resource: &Value) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = resource["speaker_segments"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(id, segment)| Segment {
+            id: segment["id"].as_str().unwrap_or(id).to_string(),
+            start: segment["start_time"].as_f64().unwrap_or(0.0),
+            end: segment["end_time"].as_f64().unwrap_or(0.0),
+            text: segment["text"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect();
+    segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+    segments
+}
+
+/// Segments that overlap any of the ranges
+fn segments_in_ranges(segments: &[Segment], ranges: &[TimeRange]) -> Vec<Segment> {
+    segments
+        .iter()
+        .filter(|s| ranges.iter().any(|r| s.start < r.end && s.end > r.start))
+        .cloned()
+        .collect()
+}
+
+fn format_timestamp(secs: f64) -> String {
+    let tenths = (secs * 10.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{}",
+        tenths / 36_000,
+        tenths / 600 % 60,
+        tenths / 10 % 60,
+        tenths % 10
+    )
+}
+
+/// POST to a Dubbing Studio resource endpoint
+pub(crate) async fn post_resource(
+    client: &reqwest::Client,
+    api_key: &str,
+    dubbing_id: &str,
+    action: &str,
+    body: &Value,
+) -> Result<Value> {
+    let response = client
+        .post(format!(
+            "{}/v1/dubbing/resource/{}/{}",
+            api_base(),
+            dubbing_id,
+            action
+        ))
+        .header("xi-api-key", api_key)
+        .json(body)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to send {} request", action))?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    read_json(response).await
+}
+
+/// Re-dub only the segments inside the given time ranges
+async fn redo_segments(
+    api_key: &str,
+    dubbing_id: &str,
+    ranges: &[TimeRange],
+    langs: &[String],
+    options: &RedoOptions,
+) -> Result<()> {
+    let client = create_http_client();
+    let response = client
+        .get(format!("{}/v1/dubbing/resource/{}", api_base(), dubbing_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch dubbing resource")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await.context(
+            "Partial re-dubs need a Dubbing Studio project (created with dubbing studio enabled)",
+        ));
+    }
+    let resource: Value = read_json(response).await?;
+
+    let available: Vec<String> = resource["target_languages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| l.as_str().map(str::to_string))
+        .collect();
+    let languages = if langs.is_empty() {
+        available.clone()
+    } else {
+        if let Some(missing) = langs.iter().find(|l| !available.contains(l)) {
+            return Err(anyhow::anyhow!(
+                "Dubbing '{}' has no '{}' track. Available: {}",
+                dubbing_id,
+                missing,
+                available.join(", ")
+            ));
+        }
+        langs.to_vec()
+    };
+    if languages.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Dubbing '{}' has no target languages",
+            dubbing_id
+        ));
+    }
+
+    let selected = segments_in_ranges(&resource_segments(&resource), ranges);
+    if selected.is_empty() {
+        return Err(anyhow::anyhow!("No segments overlap the given range(s)"));
+    }
+
+    if !is_json_mode() {
+        let mut table = Table::new();
+        table.set_header(vec!["Segment", "Start", "End", "Text"]);
+        for segment in &selected {
+            let mut text: String = segment.text.chars().take(60).collect();
+            if segment.text.chars().count() > 60 {
+                text.push_str("...");
+            }
+            table.add_row(vec![
+                segment.id.clone(),
+                format_timestamp(segment.start),
+                format_timestamp(segment.end),
+                text,
+            ]);
+        }
+        println!("{}", table);
+    }
+
+    let segment_ids: Vec<&str> = selected.iter().map(|s| s.id.as_str()).collect();
+    let mut renders = Vec::new();
+    if !options.dry_run {
+        let body = json!({ "segments": segment_ids, "languages": languages });
+        let progress = Progress::spinner("Updating segments");
+        if options.retranslate {
+            progress.set_message("Translating segments");
+            post_resource(&client, api_key, dubbing_id, "translate", &body).await?;
+        }
+        progress.set_message("Dubbing segments");
+        post_resource(&client, api_key, dubbing_id, "dub", &body).await?;
+
+        if let Some(render_type) = &options.render {
+            for language in &languages {
+                progress.set_message(&format!("Rendering {}", language));
+                let render = post_resource(
+                    &client,
+                    api_key,
+                    dubbing_id,
+                    &format!("render/{}", language),
+                    &json!({ "render_type": render_type }),
+                )
+                .await?;
+                renders.push(json!({
+                    "language": language,
+                    "render_id": render["render_id"],
+                }));
+            }
+        }
+        progress.finish();
+    }
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "dubbing_id": dubbing_id,
+                "languages": languages,
+                "segments": selected,
+                "retranslated": options.retranslate && !options.dry_run,
+                "dry_run": options.dry_run,
+                "renders": renders,
+            }))?
+        );
+        return Ok(());
+    }
+
+    if options.dry_run {
+        print_info(&format!(
+            "Dry run: {} segment(s) would be redone for {}",
+            selected.len(),
+            languages.join(", ")
+        ));
+    } else if options.render.is_some() {
+        print_success(&format!(
+            "Redid {} segment(s) for {}; rendering started (check with 'dub status {}')",
+            selected.len(),
+            languages.join(", "),
+            dubbing_id
+        ));
+    } else {
+        print_success(&format!(
+            "Redid {} segment(s) for {}; they are included in the next render",
+            selected.len(),
+            languages.join(", ")
+        ));
+    }
     Ok(())
 }
 
@@ -210,10 +1118,7 @@ async fn delete_dub(client: &ElevenLabsClient, dubbing_id: &str, assume_yes: boo
     ));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -221,9 +1126,100 @@ async fn delete_dub(client: &ElevenLabsClient, dubbing_id: &str, assume_yes: boo
         }
     }
 
-    let endpoint = DeleteDubbing::new(dubbing_id);
-    client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    retry_sdk(Method::DELETE, || {
+        client.hit(DeleteDubbing::new(dubbing_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     print_success(&format!("Deleted dubbing project '{}'", dubbing_id));
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_in_ranges() {
+        let resource = json!({
+            "speaker_segments": {
+                "b": { "id": "b", "start_time": 131.0, "end_time": 138.5, "text": "Second" },
+                "a": { "id": "a", "start_time": 120.0, "end_time": 130.5, "text": "First" },
+                "c": { "id": "c", "start_time": 160.0, "end_time": 170.0, "text": "Third" },
+            }
+        });
+        let segments = resource_segments(&resource);
+        assert_eq!(
+            segments.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            ["a", "b", "c"]
+        );
+
+        let range: TimeRange = "00:02:10-00:02:40".parse().unwrap();
+        assert_eq!(
+            range,
+            TimeRange {
+                start: 130.0,
+                end: 160.0
+            }
+        );
+        // Touching the range end does not count as overlap
+        let selected = segments_in_ranges(&segments, &[range]);
+        assert_eq!(
+            selected.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+
+        assert_eq!("2:10.5-2:11".parse::<TimeRange>().unwrap().start, 130.5);
+        assert_eq!("130-160".parse::<TimeRange>().unwrap().end, 160.0);
+        assert!("2:40-2:10".parse::<TimeRange>().is_err());
+        assert!("1:75-2:00".parse::<TimeRange>().is_err());
+        assert!("130".parse::<TimeRange>().is_err());
+        assert_eq!(format_timestamp(130.25), "00:02:10.3");
+    }
+
+    #[test]
+    fn test_build_dub_estimate() {
+        let langs = vec!["es".to_string(), "fr".to_string()];
+        let estimate = build_dub_estimate("talk.mp4", 90.0, &langs, 2_000);
+        assert_eq!(estimate.languages.len(), 2);
+        assert_eq!(estimate.languages[0].credits, 3_000);
+        assert_eq!(estimate.total_credits, 6_000);
+
+        let short = build_dub_estimate("clip.mp3", 10.0, &langs[..1], 1_000);
+        assert_eq!(short.languages[0].credits, 167);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(42.4), "42s");
+        assert_eq!(format_duration(125.0), "2m 05s");
+        assert_eq!(format_duration(3_725.0), "1h 02m");
+    }
+
+    #[test]
+    fn test_pick_language_and_extension() {
+        let languages = vec!["es".to_string(), "de".to_string()];
+        assert_eq!(pick_language("dub_1", &languages, None).unwrap(), "es");
+        assert_eq!(
+            pick_language("dub_1", &languages, Some("de".to_string())).unwrap(),
+            "de"
+        );
+        let error = pick_language("dub_1", &languages, Some("fr".to_string())).unwrap_err();
+        assert!(error.to_string().contains("Available: es, de"));
+        assert!(pick_language("dub_1", &[], None).is_err());
+
+        assert_eq!(media_extension(Some("audio/mpeg")), "mp3");
+        assert_eq!(media_extension(Some("video/mp4")), "mp4");
+        assert_eq!(media_extension(None), "mp4");
+    }
+
+    #[test]
+    fn test_escape_filter_path() {
+        assert_eq!(escape_filter_path("/tmp/a.srt"), "/tmp/a.srt");
+        assert_eq!(
+            escape_filter_path("C:\\subs\\it's.srt"),
+            "C\\:\\\\subs\\\\it\\'s.srt"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! Assigning voices to the speakers of a dub (`dub create --speaker-map`)
+//!
+//! The map is a YAML file from speaker to voice:
+//!
+//! ```yaml
+//! 1: 21m00Tcm4TlvDq8ikWAM          # first speaker to appear
+//! "Speaker 2": original             # keep a clone of the speaker's own voice
+//! spk_3: { es: pNInz6obpgDQGcFmaJgB, fr: original }
+//! ```
+//!
+//! Speakers are named by track ID, speaker name or their number in order of
+//! appearance. Speakers are only known once the dub has been processed, so
+//! the project is created in Dubbing Studio mode, the map is applied through
+//! the resource API afterwards, and the mapped speakers' segments are dubbed
+//! and rendered again.
+
+use crate::client::{api_base, api_error, read_json, SendWithRetry};
+use crate::commands::dubbing::post_resource;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Map value that keeps the speaker's original voice
+const ORIGINAL_VOICE: &str = "original";
+
+/// Voice the API clones from the speaker's whole track
+const TRACK_CLONE: &str = "track-clone";
+
+/// Voice for a speaker: one for every language, or one per language
+#[derive(Debug, PartialEq)]
+pub enum SpeakerVoice {
+    All(String),
+    PerLanguage(BTreeMap<String, String>),
+}
+
+/// Speaker reference and voice, in file order
+pub type SpeakerMap = Vec<(String, SpeakerVoice)>;
+
+pub fn parse_speaker_map(text: &str) -> Result<SpeakerMap> {
+    let map: serde_yaml::Value = serde_yaml::from_str(text).context("Invalid speaker map")?;
+    let serde_yaml::Value::Mapping(map) = map else {
+        return Err(anyhow::anyhow!(
+            "The speaker map must map speakers to voices, e.g. '1: <VOICE_ID>'"
+        ));
+    };
+    let scalar = |value: &serde_yaml::Value| match value {
+        serde_yaml::Value::String(s) => Some(s.trim().to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+
+    let mut speakers = Vec::new();
+    for (speaker, voice) in &map {
+        let speaker = scalar(speaker)
+            .ok_or_else(|| anyhow::anyhow!("Speaker keys must be text or numbers"))?;
+        let voice = match voice {
+            serde_yaml::Value::Mapping(languages) => SpeakerVoice::PerLanguage(
+                languages
+                    .iter()
+                    .map(|(language, voice)| {
+                        scalar(language).zip(scalar(voice)).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Speaker '{}': map each language code to a voice ID",
+                                speaker
+                            )
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            voice => SpeakerVoice::All(scalar(voice).ok_or_else(|| {
+                anyhow::anyhow!("Speaker '{}' needs a voice ID or 'original'", speaker)
+            })?),
+        };
+        speakers.push((speaker, voice));
+    }
+    if speakers.is_empty() {
+        return Err(anyhow::anyhow!("The speaker map is empty"));
+    }
+    Ok(speakers)
+}
+
+/// A detected speaker of a Dubbing Studio resource
+#[derive(Debug, PartialEq)]
+struct Speaker {
+    id: String,
+    name: String,
+    segments: Vec<String>,
+}
+
+/// Speakers of a resource in order of first appearance
+fn resource_speakers(resource: &Value) -> Vec<Speaker> {
+    let start = |segment: &str| {
+        resource["speaker_segments"][segment]["start_time"]
+            .as_f64()
+            .unwrap_or(f64::MAX)
+    };
+    let mut speakers: Vec<Speaker> = resource["speaker_tracks"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(id, track)| Speaker {
+            id: track["id"].as_str().unwrap_or(id).to_string(),
+            name: track["speaker_name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            segments: track["segments"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect(),
+        })
+        .collect();
+    let first = |speaker: &Speaker| {
+        speaker
+            .segments
+            .iter()
+            .map(|s| start(s))
+            .fold(f64::MAX, f64::min)
+    };
+    speakers.sort_by(|a, b| first(a).total_cmp(&first(b)));
+    speakers
+}
+
+/// Find a speaker by track ID, name or number
+fn find_speaker<'a>(speakers: &'a [Speaker], reference: &str) -> Option<&'a Speaker> {
+    speakers
+        .iter()
+        .find(|s| s.id == reference)
+        .or_else(|| {
+            speakers
+                .iter()
+                .find(|s| !s.name.is_empty() && s.name.eq_ignore_ascii_case(reference))
+        })
+        .or_else(|| {
+            let number: usize = reference.parse().ok()?;
+            speakers.get(number.checked_sub(1)?)
+        })
+}
+
+/// One voice change for the resource API
+#[derive(Debug, PartialEq)]
+struct Assignment {
+    speaker_id: String,
+    voice_id: String,
+    languages: Vec<String>,
+}
+
+/// Resolve the map against a resource: voice changes and the segments to
+/// dub again
+fn plan_assignments(
+    map: &SpeakerMap,
+    speakers: &[Speaker],
+    languages: &[String],
+) -> Result<(Vec<Assignment>, Vec<String>)> {
+    let voice_id = |voice: &str| match voice {
+        ORIGINAL_VOICE => TRACK_CLONE.to_string(),
+        voice => voice.to_string(),
+    };
+    let mut assignments = Vec::new();
+    let mut segments = Vec::new();
+    let mut mapped: BTreeMap<&str, &str> = BTreeMap::new();
+    for (reference, voice) in map {
+        let speaker = find_speaker(speakers, reference).ok_or_else(|| {
+            let known: Vec<String> = speakers
+                .iter()
+                .enumerate()
+                .map(|(i, s)| match s.name.as_str() {
+                    "" => format!("{} ({})", i + 1, s.id),
+                    name => format!("{} ({}, {})", i + 1, s.id, name),
+                })
+                .collect();
+            anyhow::anyhow!(
+                "No speaker '{}' in this dub. Speakers: {}",
+                reference,
+                known.join(", ")
+            )
+        })?;
+        if let Some(earlier) = mapped.insert(&speaker.id, reference) {
+            return Err(anyhow::anyhow!(
+                "'{}' and '{}' both refer to speaker {}; map each speaker once",
+                earlier,
+                reference,
+                speaker.id
+            ));
+        }
+        match voice {
+            SpeakerVoice::All(voice) => assignments.push(Assignment {
+                speaker_id: speaker.id.clone(),
+                voice_id: voice_id(voice),
+                languages: languages.to_vec(),
+            }),
+            SpeakerVoice::PerLanguage(per_language) => {
+                // One request per voice, covering all its languages
+                let mut by_voice: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                for (language, voice) in per_language {
+                    if !languages.contains(language) {
+                        return Err(anyhow::anyhow!(
+                            "Speaker '{}': the dub has no '{}' track. Languages: {}",
+                            reference,
+                            language,
+                            languages.join(", ")
+                        ));
+                    }
+                    by_voice
+                        .entry(voice_id(voice))
+                        .or_default()
+                        .push(language.clone());
+                }
+                assignments.extend(
+                    by_voice
+                        .into_iter()
+                        .map(|(voice_id, languages)| Assignment {
+                            speaker_id: speaker.id.clone(),
+                            voice_id,
+                            languages,
+                        }),
+                );
+            }
+        }
+        segments.extend(speaker.segments.iter().cloned());
+    }
+    Ok((assignments, segments))
+}
+
+/// Apply the map to a processed Dubbing Studio project, dub the mapped
+/// speakers again and start a render per language; returns the renders
+pub async fn apply_speaker_map(
+    client: &Client,
+    api_key: &str,
+    dubbing_id: &str,
+    map: &SpeakerMap,
+    render_type: &str,
+) -> Result<Vec<Value>> {
+    let response = client
+        .get(format!("{}/v1/dubbing/resource/{}", api_base(), dubbing_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch dubbing resource")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let resource: Value = read_json(response).await?;
+    let languages: Vec<String> = resource["target_languages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| l.as_str().map(str::to_string))
+        .collect();
+    let (assignments, segments) = plan_assignments(map, &resource_speakers(&resource), &languages)?;
+
+    for assignment in &assignments {
+        let response = client
+            .patch(format!(
+                "{}/v1/dubbing/resource/{}/speaker/{}",
+                api_base(),
+                dubbing_id,
+                assignment.speaker_id
+            ))
+            .header("xi-api-key", api_key)
+            .json(&json!({
+                "voice_id": assignment.voice_id,
+                "languages": assignment.languages,
+            }))
+            .send_with_retry()
+            .await
+            .context("Failed to update speaker")?;
+        if !response.status().is_success() {
+            return Err(api_error(response)
+                .await
+                .context(format!("Speaker {}", assignment.speaker_id)));
+        }
+    }
+
+    let affected: BTreeSet<&String> = assignments.iter().flat_map(|a| &a.languages).collect();
+    let body = json!({ "segments": segments, "languages": affected });
+    post_resource(client, api_key, dubbing_id, "dub", &body).await?;
+
+    let mut renders = Vec::new();
+    for language in affected {
+        let render = post_resource(
+            client,
+            api_key,
+            dubbing_id,
+            &format!("render/{}", language),
+            &json!({ "render_type": render_type }),
+        )
+        .await?;
+        renders.push(json!({ "language": language, "render_id": render["render_id"] }));
+    }
+    Ok(renders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource() -> Value {
+        json!({
+            "target_languages": ["es", "fr"],
+            "speaker_tracks": {
+                "spk_b": { "id": "spk_b", "speaker_name": "Host", "segments": ["s2"] },
+                "spk_a": { "id": "spk_a", "speaker_name": "", "segments": ["s1", "s3"] }
+            },
+            "speaker_segments": {
+                "s1": { "start_time": 0.5 },
+                "s2": { "start_time": 4.0 },
+                "s3": { "start_time": 9.0 }
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_speaker_map() {
+        let map =
+            parse_speaker_map("1: voice_a\nHost: original\nspk_a: { es: voice_b }\n").unwrap();
+        assert_eq!(
+            map,
+            [
+                ("1".to_string(), SpeakerVoice::All("voice_a".to_string())),
+                (
+                    "Host".to_string(),
+                    SpeakerVoice::All("original".to_string())
+                ),
+                (
+                    "spk_a".to_string(),
+                    SpeakerVoice::PerLanguage(BTreeMap::from([(
+                        "es".to_string(),
+                        "voice_b".to_string()
+                    )]))
+                ),
+            ]
+        );
+        assert!(parse_speaker_map("- voice_a").is_err());
+        assert!(parse_speaker_map("{}").is_err());
+        assert!(parse_speaker_map("1: [voice_a]").is_err());
+    }
+
+    #[test]
+    fn test_resource_speakers_in_order_of_appearance() {
+        let speakers = resource_speakers(&resource());
+        let ids: Vec<&str> = speakers.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["spk_a", "spk_b"]);
+        assert_eq!(find_speaker(&speakers, "2").unwrap().id, "spk_b");
+        assert_eq!(find_speaker(&speakers, "host").unwrap().id, "spk_b");
+        assert_eq!(find_speaker(&speakers, "spk_a").unwrap().id, "spk_a");
+        assert!(find_speaker(&speakers, "3").is_none());
+    }
+
+    #[test]
+    fn test_plan_assignments() {
+        let speakers = resource_speakers(&resource());
+        let languages = vec!["es".to_string(), "fr".to_string()];
+        let map = parse_speaker_map("Host: original\n1: { es: voice_b, fr: voice_b }\n").unwrap();
+        let (assignments, segments) = plan_assignments(&map, &speakers, &languages).unwrap();
+        assert_eq!(
+            assignments,
+            [
+                Assignment {
+                    speaker_id: "spk_b".to_string(),
+                    voice_id: TRACK_CLONE.to_string(),
+                    languages: languages.clone(),
+                },
+                Assignment {
+                    speaker_id: "spk_a".to_string(),
+                    voice_id: "voice_b".to_string(),
+                    languages: languages.clone(),
+                },
+            ]
+        );
+        assert_eq!(segments, ["s2", "s1", "s3"]);
+
+        let unknown = parse_speaker_map("Guest: voice_a").unwrap();
+        assert!(plan_assignments(&unknown, &speakers, &languages).is_err());
+        let bad_language = parse_speaker_map("1: { de: voice_a }").unwrap();
+        assert!(plan_assignments(&bad_language, &speakers, &languages).is_err());
+        let twice = parse_speaker_map("2: voice_a\nHost: voice_b\n").unwrap();
+        let error = plan_assignments(&twice, &speakers, &languages).unwrap_err();
+        assert!(error.to_string().contains("'2' and 'Host'"), "{}", error);
+    }
+}
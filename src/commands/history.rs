@@ -1,23 +1,50 @@
-use crate::cli::{HistoryArgs, HistoryCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success, print_warning};
-use crate::utils::confirm_overwrite;
-use anyhow::Result;
+use crate::cli::{HistoryArgs, HistoryColumn, HistoryCommands, DEFAULT_HISTORY_COLUMNS};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, retry_sdk, SendWithRetry,
+};
+use crate::commands::history_sync::{sync_history, SyncOptions};
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::tags::{supports_id3, title_from_text, write_id3_tags, AudioTags};
+use crate::utils::{confirm_overwrite, format_relative_time};
+use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use elevenlabs_rs::{
     endpoints::admin::history::{
-        DeleteHistoryItem, GetGeneratedItems, GetHistoryItem, HistoryQuery,
+        DeleteHistoryItem, GetGeneratedItems, GetHistoryItem, GetHistoryItemResponse, HistoryQuery,
+        Source,
     },
     ElevenLabsClient,
 };
+use futures::StreamExt;
+use reqwest::Method;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Typical speaking rate used to estimate audio length from text
+const CHARS_PER_SECOND: f64 = 15.0;
+
+/// Characters of source text shown in the text column
+const TEXT_COLUMN_CHARS: usize = 40;
 
 pub async fn execute(args: HistoryArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     match args.command {
-        HistoryCommands::List { limit, detailed } => list_history(&client, limit, detailed).await?,
+        HistoryCommands::List {
+            limit,
+            detailed,
+            columns,
+            fetch_durations,
+        } => {
+            let columns = if columns.is_empty() {
+                DEFAULT_HISTORY_COLUMNS.to_vec()
+            } else {
+                columns
+            };
+            let fetch_key = fetch_durations.then_some(api_key);
+            list_history(&client, limit, detailed, &columns, fetch_key).await?
+        }
         HistoryCommands::Get { history_item_id } => {
             get_history_item(&client, &history_item_id).await?
         }
@@ -27,40 +54,118 @@ pub async fn execute(args: HistoryArgs, api_key: &str, assume_yes: bool) -> Resu
         HistoryCommands::Download {
             history_item_id,
             output,
-        } => download_history_audio(&client, api_key, &history_item_id, output, assume_yes).await?,
+            tag,
+            album,
+            waveform,
+        } => {
+            download_history_audio(
+                &client,
+                api_key,
+                &history_item_id,
+                output,
+                tag,
+                album,
+                waveform,
+                assume_yes,
+            )
+            .await?
+        }
+        HistoryCommands::Sync {
+            dir,
+            since,
+            voice,
+            limit,
+            concurrency,
+        } => {
+            let options = SyncOptions {
+                since,
+                voice,
+                limit,
+                concurrency,
+            };
+            sync_history(api_key, Path::new(&dir), &options).await?
+        }
         HistoryCommands::Feedback {
             history_item_id,
             thumbs_up,
             feedback,
         } => submit_feedback(&client, api_key, &history_item_id, thumbs_up, feedback).await?,
+        HistoryCommands::FeedbackBatch { file, dry_run } => {
+            submit_feedback_batch(api_key, &file, dry_run).await?
+        }
     }
 
     Ok(())
 }
 
-async fn list_history(client: &ElevenLabsClient, limit: u32, detailed: bool) -> Result<()> {
+async fn list_history(
+    client: &ElevenLabsClient,
+    limit: u32,
+    detailed: bool,
+    columns: &[HistoryColumn],
+    fetch_durations: Option<&str>,
+) -> Result<()> {
     print_info(&format!("Fetching history (last {} items)...", limit));
 
-    let query = HistoryQuery::default().with_page_size(limit as u16);
-
-    let endpoint = GetGeneratedItems::with_query(query);
-    let history = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let history = retry_sdk(Method::GET, || {
+        let query = HistoryQuery::default().with_page_size(limit as u16);
+        client.hit(GetGeneratedItems::with_query(query))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     let items = history.history;
 
-    if items.is_empty() {
+    if items.is_empty() && !is_json_mode() {
         print_info("No history items found");
         return Ok(());
     }
 
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    // Measured durations replace the estimates when --fetch-durations is set
+    let mut durations: Vec<Option<f64>> = vec![None; items.len()];
+    if let Some(api_key) = fetch_durations {
+        let progress = Progress::bar(items.len() as u64, "Measuring audio");
+        for (item, duration) in items.iter().zip(durations.iter_mut()) {
+            match fetch_duration(api_key, &item.history_item_id).await {
+                Ok(measured) => *duration = measured,
+                Err(e) => print_warning(&format!(
+                    "Could not fetch audio for {}: {:#}",
+                    item.history_item_id, e
+                )),
+            }
+            progress.inc(1);
+        }
+        progress.finish();
+    }
+
+    if is_json_mode() {
+        let items: Vec<_> = items
+            .iter()
+            .zip(&durations)
+            .map(|(item, measured)| item_json(item, *measured))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+
     if detailed {
         println!("\n{}", "Generation History:".bold().underline());
-        for item in &items {
+        for (item, measured) in items.iter().zip(&durations) {
             println!("\n{}", item.history_item_id.cyan());
             println!("  Voice: {}", item.voice_name.yellow());
             println!("  Voice ID: {}", item.voice_id);
             println!("  Model: {}", item.model_id.as_deref().unwrap_or("unknown"));
-            println!("  Date: {}", item.date_unix);
+            println!(
+                "  Date: {} ({})",
+                format_relative_time(now.saturating_sub(item.date_unix)),
+                item.date_unix
+            );
+            println!("  Duration: {}", format_item_duration(item, *measured));
             println!(
                 "  Character count change: {}",
                 item.character_count_change_from
@@ -70,18 +175,26 @@ async fn list_history(client: &ElevenLabsClient, limit: u32, detailed: bool) ->
         }
     } else {
         let mut table = Table::new();
-        table.set_header(vec!["ID", "Voice", "Date", "Chars"]);
-
-        for item in &items {
-            table.add_row(vec![
-                item.history_item_id.chars().take(12).collect::<String>() + "...",
-                item.voice_name.to_string(),
-                item.date_unix.to_string(),
-                item.character_count_change_from.to_string(),
-            ]);
+        table.set_header(
+            columns
+                .iter()
+                .map(|c| column_header(*c))
+                .collect::<Vec<_>>(),
+        );
+
+        for (item, measured) in items.iter().zip(&durations) {
+            table.add_row(
+                columns
+                    .iter()
+                    .map(|c| column_value(*c, item, *measured, now))
+                    .collect::<Vec<_>>(),
+            );
         }
 
         println!("\n{}", table);
+        if columns.contains(&HistoryColumn::Duration) && fetch_durations.is_none() {
+            print_info("Durations marked ~ are estimated from the text; use --fetch-durations to measure them");
+        }
     }
 
     print_success(&format!("Showing {} items", items.len()));
@@ -89,14 +202,164 @@ async fn list_history(client: &ElevenLabsClient, limit: u32, detailed: bool) ->
     Ok(())
 }
 
+fn column_header(column: HistoryColumn) -> &'static str {
+    match column {
+        HistoryColumn::Id => "ID",
+        HistoryColumn::Date => "Date",
+        HistoryColumn::Voice => "Voice",
+        HistoryColumn::Model => "Model",
+        HistoryColumn::Chars => "Chars",
+        HistoryColumn::Duration => "Duration",
+        HistoryColumn::Text => "Text",
+        HistoryColumn::State => "State",
+        HistoryColumn::Source => "Source",
+    }
+}
+
+fn column_value(
+    column: HistoryColumn,
+    item: &GetHistoryItemResponse,
+    measured: Option<f64>,
+    now: u64,
+) -> String {
+    match column {
+        HistoryColumn::Id => item.history_item_id.chars().take(12).collect::<String>() + "...",
+        HistoryColumn::Date => format_relative_time(now.saturating_sub(item.date_unix)),
+        HistoryColumn::Voice => item.voice_name.clone(),
+        HistoryColumn::Model => item.model_id.clone().unwrap_or_default(),
+        HistoryColumn::Chars => billed_characters(item).to_string(),
+        HistoryColumn::Duration => format_item_duration(item, measured),
+        HistoryColumn::Text => {
+            let line = item.text.lines().next().unwrap_or("").trim();
+            if line.chars().count() > TEXT_COLUMN_CHARS {
+                let excerpt: String = line.chars().take(TEXT_COLUMN_CHARS).collect();
+                format!("{}...", excerpt.trim_end())
+            } else {
+                line.to_string()
+            }
+        }
+        HistoryColumn::State => item.state.clone(),
+        HistoryColumn::Source => match item.source {
+            Some(Source::Tts) => "TTS".to_string(),
+            Some(Source::Sts) => "STS".to_string(),
+            None => String::new(),
+        },
+    }
+}
+
+/// Characters the generation used, from the account counter before and after
+fn billed_characters(item: &GetHistoryItemResponse) -> u64 {
+    item.character_count_change_to
+        .saturating_sub(item.character_count_change_from)
+}
+
+/// Measured duration, or an estimate from the text marked with `~`
+/// A history item as printed by `--json`; `duration_secs` is null unless
+/// the audio was measured
+fn item_json(item: &GetHistoryItemResponse, measured: Option<f64>) -> serde_json::Value {
+    serde_json::json!({
+        "history_item_id": item.history_item_id,
+        "voice_id": item.voice_id,
+        "voice_name": item.voice_name,
+        "model_id": item.model_id,
+        "date_unix": item.date_unix,
+        "character_count_change_from": item.character_count_change_from,
+        "character_count_change_to": item.character_count_change_to,
+        "duration_secs": measured,
+        "state": item.state,
+        "source": match item.source {
+            Some(Source::Tts) => Some("TTS"),
+            Some(Source::Sts) => Some("STS"),
+            None => None,
+        },
+        "text": item.text,
+    })
+}
+
+fn format_item_duration(item: &GetHistoryItemResponse, measured: Option<f64>) -> String {
+    match measured {
+        Some(secs) => format_clock(secs),
+        None => format!(
+            "~{}",
+            format_clock(item.text.chars().count() as f64 / CHARS_PER_SECOND)
+        ),
+    }
+}
+
+/// `m:ss` for a length in seconds
+fn format_clock(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Download an item's audio and measure it (None when it is not a readable MP3)
+async fn fetch_duration(api_key: &str, history_item_id: &str) -> Result<Option<f64>> {
+    let response = create_http_client()
+        .get(format!(
+            "{}/v1/history/{}/audio",
+            api_base(),
+            history_item_id
+        ))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to download audio")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(mp3_duration(&response.bytes().await?))
+}
+
+/// Length of a constant-bitrate MP3 in seconds, from its first frame header
+fn mp3_duration(data: &[u8]) -> Option<f64> {
+    // Kbit/s by bitrate index for MPEG-1 and MPEG-2/2.5 Layer III
+    const MPEG1_KBPS: [u32; 15] = [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ];
+    const MPEG2_KBPS: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+    // Skip an ID3v2 tag (its size is stored as a synchsafe integer)
+    let mut start = 0;
+    if data.len() >= 10 && data.starts_with(b"ID3") {
+        let size = data[6..10]
+            .iter()
+            .fold(0usize, |acc, &b| (acc << 7) | (b & 0x7F) as usize);
+        start = 10 + size;
+    }
+
+    let offset = data
+        .get(start..)?
+        .windows(4)
+        .position(|h| h[0] == 0xFF && h[1] & 0xE0 == 0xE0 && h[1] & 0x06 == 0x02)?;
+    let header = &data[start + offset..start + offset + 4];
+    let mpeg1 = header[1] & 0x18 == 0x18;
+    let index = (header[2] >> 4) as usize;
+    let kbps = if mpeg1 { MPEG1_KBPS } else { MPEG2_KBPS }
+        .get(index)
+        .copied()?;
+    if kbps == 0 {
+        return None;
+    }
+    let audio_bytes = data.len() - start - offset;
+    Some(audio_bytes as f64 * 8.0 / (kbps as f64 * 1000.0))
+}
+
 async fn get_history_item(client: &ElevenLabsClient, history_item_id: &str) -> Result<()> {
     print_info(&format!(
         "Fetching history item '{}'...",
         history_item_id.cyan()
     ));
 
-    let endpoint = GetHistoryItem::new(history_item_id);
-    let item = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let item = retry_sdk(Method::GET, || {
+        client.hit(GetHistoryItem::new(history_item_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&item_json(&item, None))?);
+        return Ok(());
+    }
 
     println!("\n{}", "History Item:".bold().underline());
     println!("  ID: {}", item.history_item_id.cyan());
@@ -124,10 +387,7 @@ async fn delete_history_item(
     ));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -135,18 +395,25 @@ async fn delete_history_item(
         }
     }
 
-    let endpoint = DeleteHistoryItem::new(history_item_id);
-    client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    retry_sdk(Method::DELETE, || {
+        client.hit(DeleteHistoryItem::new(history_item_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     print_success(&format!("Deleted history item '{}'", history_item_id));
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn download_history_audio(
-    _client: &ElevenLabsClient,
+    client: &ElevenLabsClient,
     api_key: &str,
     history_item_id: &str,
     output: Option<String>,
+    tag: bool,
+    album: Option<String>,
+    waveform: bool,
     assume_yes: bool,
 ) -> Result<()> {
     print_info(&format!(
@@ -166,27 +433,70 @@ async fn download_history_audio(
 
     // Use HTTP client with timeout
     let http_client = create_http_client();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/history/{}/audio",
-        history_item_id
-    );
+    let url = format!("{}/v1/history/{}/audio", api_base(), history_item_id);
     let response = http_client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let audio = response.bytes().await?;
+    let request_id = response
+        .headers()
+        .get("request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    // Stream the body so large downloads show progress
+    let progress = match response.content_length() {
+        Some(len) => Progress::bar(len, "Downloading audio"),
+        None => Progress::spinner("Downloading audio"),
+    };
+    let mut audio = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Failed to read download stream")?;
+        progress.inc(chunk.len() as u64);
+        audio.extend_from_slice(&chunk);
+    }
+    progress.finish();
 
     // Write audio file
     std::fs::write(path, &audio)?;
 
+    // Embed metadata tags if requested
+    if tag {
+        if supports_id3(path) {
+            let item = retry_sdk(Method::GET, || {
+                client.hit(GetHistoryItem::new(history_item_id))
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+            let mut comment = format!("model: {}", item.model_id.as_deref().unwrap_or("unknown"));
+            if let Some(id) = request_id {
+                comment.push_str(&format!("; request-id: {}", id));
+            }
+
+            let tags = AudioTags {
+                title: Some(title_from_text(&item.text)),
+                artist: Some(item.voice_name.to_string()),
+                album,
+                comment: Some(comment),
+            };
+            write_id3_tags(path, &tags)?;
+        } else {
+            print_warning("Skipping tags: ID3 tags are only supported for MP3 output");
+        }
+    }
+
     print_success(&format!("Downloaded audio -> {}", output_path.green()));
+    if waveform {
+        crate::waveform::print_waveform(path)?;
+    }
     Ok(())
 }
 
@@ -202,36 +512,14 @@ async fn submit_feedback(
         history_item_id.cyan()
     ));
 
-    // Use HTTP client for POST request
-    let http_client = create_http_client();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/history/{}/feedback",
-        history_item_id
-    );
-
-    let request = http_client
-        .post(&url)
-        .header("xi-api-key", api_key)
-        .header("Content-Type", "application/json");
-
-    // Build the request body
-    let body = if let Some(feedback_text) = feedback {
-        serde_json::json!({
-            "thumbs_up": thumbs_up,
-            "feedback": feedback_text
-        })
-    } else {
-        serde_json::json!({
-            "thumbs_up": thumbs_up
-        })
-    };
-
-    let response = request.body(body.to_string()).send().await?;
-
-    if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
-    }
+    post_feedback(
+        &create_http_client(),
+        api_key,
+        history_item_id,
+        thumbs_up,
+        feedback.as_deref(),
+    )
+    .await?;
 
     let feedback_type = if thumbs_up {
         "thumbs up"
@@ -244,3 +532,224 @@ async fn submit_feedback(
     ));
     Ok(())
 }
+
+/// Send feedback for one history item
+async fn post_feedback(
+    http_client: &reqwest::Client,
+    api_key: &str,
+    history_item_id: &str,
+    thumbs_up: bool,
+    feedback: Option<&str>,
+) -> Result<()> {
+    let url = format!("{}/v1/history/{}/feedback", api_base(), history_item_id);
+
+    let mut body = serde_json::json!({ "thumbs_up": thumbs_up });
+    if let Some(feedback_text) = feedback {
+        body["feedback"] = serde_json::json!(feedback_text);
+    }
+
+    let response = http_client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(())
+}
+
+/// One row of a feedback CSV
+#[derive(Debug, PartialEq)]
+struct FeedbackRow {
+    history_item_id: String,
+    thumbs_up: bool,
+    feedback: Option<String>,
+}
+
+fn parse_rating(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "up" | "thumbs_up" | "+1" | "1" | "true" | "yes" | "good" => Some(true),
+        "down" | "thumbs_down" | "-1" | "0" | "false" | "no" | "bad" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a feedback CSV: `history_item_id` and `rating` are required, and
+/// `feedback` (or `comment`) is optional
+fn parse_feedback_rows<R: std::io::Read>(reader: R) -> Result<Vec<FeedbackRow>> {
+    let mut csv_reader = csv::Reader::from_reader(reader);
+    let headers = csv_reader
+        .headers()
+        .context("Failed to read feedback CSV header")?
+        .clone();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.trim()));
+    let id_index = column(&["history_item_id"])
+        .ok_or_else(|| anyhow::anyhow!("Feedback CSV must have a 'history_item_id' column"))?;
+    let rating_index = column(&["rating", "thumbs_up"])
+        .ok_or_else(|| anyhow::anyhow!("Feedback CSV must have a 'rating' column"))?;
+    let feedback_index = column(&["feedback", "comment"]);
+
+    let mut rows = Vec::new();
+    for (row, record) in csv_reader.records().enumerate() {
+        let line = row + 2;
+        let record = record.with_context(|| format!("Invalid feedback CSV row {}", line))?;
+        let history_item_id = record.get(id_index).unwrap_or("").trim().to_string();
+        if history_item_id.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Feedback CSV row {} has no history_item_id",
+                line
+            ));
+        }
+        let rating = record.get(rating_index).unwrap_or("");
+        let thumbs_up = parse_rating(rating).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Feedback CSV row {} has invalid rating '{}' (use up or down)",
+                line,
+                rating
+            )
+        })?;
+        let feedback = feedback_index
+            .and_then(|index| record.get(index))
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string);
+
+        rows.push(FeedbackRow {
+            history_item_id,
+            thumbs_up,
+            feedback,
+        });
+    }
+    Ok(rows)
+}
+
+async fn submit_feedback_batch(api_key: &str, file: &str, dry_run: bool) -> Result<()> {
+    let reader = std::fs::File::open(file)
+        .with_context(|| format!("Failed to open feedback file: {}", file))?;
+    let rows = parse_feedback_rows(reader)?;
+    if rows.is_empty() {
+        return Err(anyhow::anyhow!("Feedback CSV contains no rows"));
+    }
+
+    let (up, down) = rows.iter().fold((0, 0), |(up, down), row| {
+        if row.thumbs_up {
+            (up + 1, down)
+        } else {
+            (up, down + 1)
+        }
+    });
+
+    if dry_run {
+        let mut table = Table::new();
+        table.set_header(vec!["History Item", "Rating", "Feedback"]);
+        for row in &rows {
+            table.add_row(vec![
+                row.history_item_id.clone(),
+                if row.thumbs_up { "up" } else { "down" }.to_string(),
+                row.feedback.clone().unwrap_or_default(),
+            ]);
+        }
+        println!("{}", table);
+        print_info(&format!(
+            "Dry run: {} rating(s) ({} up, {} down) not submitted",
+            rows.len(),
+            up,
+            down
+        ));
+        return Ok(());
+    }
+
+    print_info(&format!(
+        "Submitting {} rating(s) ({} up, {} down)...",
+        rows.len(),
+        up,
+        down
+    ));
+
+    let http_client = create_http_client();
+    let progress = Progress::bar(rows.len() as u64, "Submitting feedback");
+    let mut failures = Vec::new();
+    for row in &rows {
+        if let Err(e) = post_feedback(
+            &http_client,
+            api_key,
+            &row.history_item_id,
+            row.thumbs_up,
+            row.feedback.as_deref(),
+        )
+        .await
+        {
+            failures.push((row.history_item_id.clone(), e));
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+
+    for (history_item_id, error) in &failures {
+        print_warning(&format!("{}: {}", history_item_id, error));
+    }
+
+    let submitted = rows.len() - failures.len();
+    if failures.is_empty() {
+        print_success(&format!("Submitted feedback for {} item(s)", submitted));
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Feedback failed for {} of {} item(s)",
+            failures.len(),
+            rows.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mp3_duration() {
+        // MPEG-1 Layer III, 128 kbit/s, 44.1 kHz frame header after a 5-byte ID3 tag
+        let mut data = b"ID3\x04\x00\x00\x00\x00\x00\x05tagxx".to_vec();
+        data.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+        data.resize(data.len() + 16_000 - 4, 0);
+        assert_eq!(mp3_duration(&data), Some(1.0));
+
+        assert_eq!(mp3_duration(b"not audio"), None);
+        assert_eq!(format_clock(75.4), "1:15");
+    }
+
+    #[test]
+    fn test_parse_feedback_rows() {
+        let csv = "history_item_id,rating,comment\n\
+                   abc,up,Natural pacing\n\
+                   def,down,\n\
+                   ghi,thumbs_down,\"Mispronounced \"\"Nguyen\"\"\"\n";
+        let rows = parse_feedback_rows(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            FeedbackRow {
+                history_item_id: "abc".to_string(),
+                thumbs_up: true,
+                feedback: Some("Natural pacing".to_string()),
+            }
+        );
+        assert!(!rows[1].thumbs_up);
+        assert_eq!(rows[1].feedback, None);
+        assert_eq!(
+            rows[2].feedback.as_deref(),
+            Some("Mispronounced \"Nguyen\"")
+        );
+    }
+
+    #[test]
+    fn test_parse_feedback_rows_errors() {
+        assert!(parse_feedback_rows("id,rating\nabc,up\n".as_bytes()).is_err());
+        assert!(parse_feedback_rows("history_item_id\nabc\n".as_bytes()).is_err());
+        assert!(parse_feedback_rows("history_item_id,rating\nabc,meh\n".as_bytes()).is_err());
+        assert!(parse_feedback_rows("history_item_id,rating\n,up\n".as_bytes()).is_err());
+    }
+}
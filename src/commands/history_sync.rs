@@ -0,0 +1,321 @@
+//! Mirroring generation history into a local directory (`history sync`)
+//!
+//! History is paged newest first, so `--since` ends the walk at the first
+//! older item. Each item's audio is saved as `<history_item_id>.<ext>` and its
+//! metadata, as returned by the API plus the file name, is kept in
+//! `index.json`. An item counts as present when the index points at a file
+//! that exists, so a rerun only fetches what is new; audio saved earlier by
+//! `history download` as `<history_item_id>.mp3` is indexed, not fetched
+//! again. The index is written even when some downloads fail, and those are
+//! retried on the next run.
+
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::utils::{parse_time_spec, write_atomic};
+use anyhow::{Context, Result};
+use colored::*;
+use futures::StreamExt;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Metadata index written next to the audio
+const INDEX_FILE: &str = "index.json";
+
+/// Largest page the history endpoint returns
+const PAGE_SIZE: u32 = 1000;
+
+/// Filters and limits for a sync
+pub struct SyncOptions {
+    pub since: Option<String>,
+    pub voice: Option<String>,
+    pub limit: Option<usize>,
+    pub concurrency: usize,
+}
+
+/// File extension for a history audio response
+fn audio_extension(content_type: &str) -> &'static str {
+    match content_type.split(';').next().unwrap_or_default().trim() {
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/flac" => "flac",
+        "audio/mp4" | "audio/aac" => "m4a",
+        _ => "mp3",
+    }
+}
+
+/// Items from `index` whose file is still in `dir`
+fn present_items(dir: &Path, index: &[Value]) -> Vec<Value> {
+    index
+        .iter()
+        .filter(|item| {
+            item["history_item_id"].is_string()
+                && item["file"]
+                    .as_str()
+                    .is_some_and(|file| dir.join(file).is_file())
+        })
+        .cloned()
+        .collect()
+}
+
+/// Where an item stands against the local directory
+#[derive(Debug, PartialEq)]
+enum Local {
+    Synced,
+    /// Downloaded earlier by `history download` but not indexed; holds the file
+    Unindexed(String),
+    Missing,
+}
+
+fn local_state(dir: &Path, synced: &HashSet<String>, id: &str) -> Local {
+    let file = format!("{}.mp3", id);
+    if synced.contains(id) {
+        Local::Synced
+    } else if dir.join(&file).is_file() {
+        Local::Unindexed(file)
+    } else {
+        Local::Missing
+    }
+}
+
+fn load_index(path: &Path) -> Result<Vec<Value>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a history index", path.display()))
+}
+
+/// Write the index newest first
+fn save_index(path: &Path, items: &mut [Value]) -> Result<()> {
+    items.sort_by_key(|item| std::cmp::Reverse(item["date_unix"].as_u64().unwrap_or_default()));
+    write_atomic(
+        path,
+        (serde_json::to_string_pretty(items)? + "\n").as_bytes(),
+    )
+}
+
+/// Items to download and already downloaded items to index
+#[derive(Default)]
+struct Pending {
+    missing: Vec<Value>,
+    unindexed: Vec<Value>,
+}
+
+/// Page through history for items not yet in the index, newest first
+async fn find_pending(
+    client: &Client,
+    api_key: &str,
+    dir: &Path,
+    synced: &HashSet<String>,
+    options: &SyncOptions,
+    since: Option<u64>,
+) -> Result<Pending> {
+    let mut pending = Pending::default();
+    let mut start_after: Option<String> = None;
+    loop {
+        let mut query = vec![("page_size", PAGE_SIZE.to_string())];
+        if let Some(voice) = &options.voice {
+            query.push(("voice_id", voice.clone()));
+        }
+        if let Some(after) = &start_after {
+            query.push(("start_after_history_item_id", after.clone()));
+        }
+        let response = client
+            .get(format!("{}/v1/history", api_base()))
+            .header("xi-api-key", api_key)
+            .query(&query)
+            .send_with_retry()
+            .await
+            .context("Failed to fetch history")?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let page: Value = read_json(response).await?;
+
+        for item in page["history"].as_array().into_iter().flatten() {
+            let date = item["date_unix"].as_u64().unwrap_or_default();
+            if since.is_some_and(|since| date < since) {
+                return Ok(pending);
+            }
+            let Some(id) = item["history_item_id"].as_str() else {
+                continue;
+            };
+            match local_state(dir, synced, id) {
+                Local::Synced => {}
+                Local::Unindexed(file) => {
+                    let mut entry = item.clone();
+                    entry["file"] = json!(file);
+                    pending.unindexed.push(entry);
+                }
+                Local::Missing => {
+                    pending.missing.push(item.clone());
+                    if options
+                        .limit
+                        .is_some_and(|limit| pending.missing.len() >= limit)
+                    {
+                        return Ok(pending);
+                    }
+                }
+            }
+        }
+
+        start_after = page["last_history_item_id"].as_str().map(str::to_string);
+        if !page["has_more"].as_bool().unwrap_or(false) || start_after.is_none() {
+            return Ok(pending);
+        }
+    }
+}
+
+/// Download one item's audio into `dir`; returns its index entry
+async fn download_item(client: &Client, api_key: &str, dir: &Path, item: Value) -> Result<Value> {
+    let id = item["history_item_id"].as_str().unwrap_or_default();
+    let response = client
+        .get(format!("{}/v1/history/{}/audio", api_base(), id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to download audio")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let extension = audio_extension(
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default(),
+    );
+    let audio = response.bytes().await.context("Failed to read download")?;
+
+    // A partial file never looks like a synced item
+    let file = format!("{}.{}", id, extension);
+    write_atomic(&dir.join(&file), &audio)?;
+
+    let mut entry = item;
+    entry["file"] = json!(file);
+    Ok(entry)
+}
+
+pub async fn sync_history(api_key: &str, dir: &Path, options: &SyncOptions) -> Result<()> {
+    if options.concurrency == 0 {
+        return Err(anyhow::anyhow!("--concurrency must be at least 1"));
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let since = options
+        .since
+        .as_deref()
+        .map(|s| parse_time_spec(s, now))
+        .transpose()?;
+
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let index_path = dir.join(INDEX_FILE);
+    let mut index = present_items(dir, &load_index(&index_path)?);
+    let synced: HashSet<String> = index
+        .iter()
+        .filter_map(|item| item["history_item_id"].as_str().map(str::to_string))
+        .collect();
+
+    print_info(&format!(
+        "Checking history against '{}'...",
+        dir.display().to_string().cyan()
+    ));
+    let client = create_http_client();
+    let pending = find_pending(&client, api_key, dir, &synced, options, since).await?;
+    index.extend(pending.unindexed);
+
+    let progress = Progress::bar(pending.missing.len() as u64, "Downloading history");
+    let mut downloads = futures::stream::iter(pending.missing.into_iter().map(|item| {
+        let id = item["history_item_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let client = &client;
+        async move { (id, download_item(client, api_key, dir, item).await) }
+    }))
+    .buffer_unordered(options.concurrency);
+
+    let mut downloaded = 0;
+    let mut failures = Vec::new();
+    while let Some((id, result)) = downloads.next().await {
+        match result {
+            Ok(entry) => {
+                index.push(entry);
+                downloaded += 1;
+            }
+            Err(e) => failures.push((id, e)),
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+    save_index(&index_path, &mut index)?;
+
+    for (id, error) in &failures {
+        print_warning(&format!("{}: {:#}", id, error));
+    }
+    if is_json_mode() {
+        println!(
+            "{}",
+            json!({
+                "downloaded": downloaded,
+                "failed": failures.len(),
+                "total": index.len(),
+                "index": index_path,
+            })
+        );
+    } else {
+        print_success(&format!(
+            "Downloaded {} new item(s); {} in {}",
+            downloaded,
+            index.len(),
+            index_path.display().to_string().green()
+        ));
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{} download(s) failed; rerun to retry them",
+            failures.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_extension() {
+        assert_eq!(audio_extension("audio/mpeg"), "mp3");
+        assert_eq!(audio_extension("audio/wav; charset=binary"), "wav");
+        assert_eq!(audio_extension(""), "mp3");
+    }
+
+    #[test]
+    fn test_present_items_drops_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("h1.mp3"), b"audio").unwrap();
+        let index = vec![
+            json!({ "history_item_id": "h1", "file": "h1.mp3" }),
+            json!({ "history_item_id": "h2", "file": "h2.mp3" }),
+        ];
+        let present = present_items(dir.path(), &index);
+        assert_eq!(present, [index[0].clone()]);
+
+        let synced = HashSet::from(["h1".to_string()]);
+        assert_eq!(local_state(dir.path(), &synced, "h1"), Local::Synced);
+        assert_eq!(local_state(dir.path(), &synced, "h2"), Local::Missing);
+        assert_eq!(
+            local_state(dir.path(), &HashSet::new(), "h1"),
+            Local::Unindexed("h1.mp3".to_string())
+        );
+    }
+}
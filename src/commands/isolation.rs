@@ -1,29 +1,21 @@
 use crate::cli::AudioIsolationArgs;
-use crate::output::{print_info, print_success, print_warning};
-use crate::utils::{confirm_overwrite, write_bytes_to_file};
+use crate::client::create_sdk_client;
+use crate::output::{print_info, print_success, Progress};
+use crate::transcode::prepare_upload;
+use crate::utils::{confirm_overwrite, default_output_path, write_bytes_to_file};
+use crate::validation::validate_input_file;
 use anyhow::Result;
 use colored::*;
-use elevenlabs_rs::{endpoints::genai::audio_isolation::AudioIsolation, ElevenLabsClient};
+use elevenlabs_rs::endpoints::genai::audio_isolation::AudioIsolation;
 use std::path::Path;
 
 pub async fn execute(args: AudioIsolationArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     let file_path = Path::new(&args.file);
 
-    if !file_path.exists() {
-        return Err(anyhow::anyhow!("File not found: {}", args.file));
-    }
-
-    // Check file extension
-    if let Some(ext) = file_path.extension() {
-        let ext = ext.to_string_lossy().to_lowercase();
-        if ![
-            "mp3", "wav", "m4a", "ogg", "flac", "mp4", "mov", "avi", "webm",
-        ]
-        .contains(&ext.as_str())
-        {
-            print_warning(&format!("File format '{}' may not be supported", ext));
-        }
-    }
+    // Convert formats the API can't read, then the pre-flight checks
+    // (existence, size, extension) on what will be uploaded
+    let upload = prepare_upload(file_path)?;
+    validate_input_file(upload.path())?;
 
     let metadata = std::fs::metadata(file_path)?;
     let file_size = metadata.len();
@@ -35,14 +27,16 @@ pub async fn execute(args: AudioIsolationArgs, api_key: &str, assume_yes: bool)
     ));
 
     // Create client
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     // Create endpoint - takes file path directly
-    let endpoint = AudioIsolation::new(args.file.clone());
+    let endpoint = AudioIsolation::new(upload.path().to_string_lossy().into_owned());
 
     // Process
     let start_time = std::time::Instant::now();
+    let progress = Progress::spinner("Isolating audio");
     let isolated_audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    progress.finish();
     let duration = start_time.elapsed();
 
     // Determine output path
@@ -53,7 +47,7 @@ pub async fn execute(args: AudioIsolationArgs, api_key: &str, assume_yes: bool)
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("audio");
-        format!("{}_isolated.mp3", stem)
+        default_output_path(&format!("{}_isolated.mp3", stem), None)?
     };
 
     // Check for overwrite
@@ -0,0 +1,363 @@
+//! IVR prompt packs (`ivr build`)
+//!
+//! Generates every prompt in a YAML file as a telephony-ready file named after
+//! its id, and writes `manifest.json` next to them listing each prompt's file,
+//! text, voice and duration, so Twilio or Asterisk configs can be generated
+//! from it. Prompts that already have a file are kept unless `--force`.
+
+use crate::cli::{IvrArgs, IvrBuildArgs, IvrCommands};
+use crate::client::{api_base, api_error, create_http_client, SendWithRetry};
+use crate::commands::tts::resolve_speakers;
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::pcm::{encode_local, local_rate, samples_from_pcm16, WAV_HEADER_LEN};
+use crate::utils::{write_atomic, write_bytes_to_file};
+use crate::validation::preflight_tts;
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+const DEFAULT_VOICE: &str = "Brian";
+const DEFAULT_MODEL: &str = "eleven_multilingual_v2";
+
+/// Prompts file: defaults plus the prompts themselves
+#[derive(Debug, Deserialize)]
+struct PromptSpec {
+    voice: Option<String>,
+    model: Option<String>,
+    prompts: PromptList,
+}
+
+/// `prompts` as a list of entries or as an `id: text` map
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PromptList {
+    List(Vec<Prompt>),
+    Map(serde_yaml::Mapping),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Prompt {
+    id: String,
+    text: String,
+    voice: Option<String>,
+}
+
+/// `manifest.json` written next to the prompts
+#[derive(Debug, Serialize)]
+struct Manifest {
+    format: String,
+    /// `pcm_s16le`, `ulaw` or `alaw`
+    encoding: &'static str,
+    /// `wav` or `raw` (headerless)
+    container: &'static str,
+    sample_rate: u32,
+    model: String,
+    prompts: Vec<ManifestPrompt>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestPrompt {
+    id: String,
+    file: String,
+    /// Asterisk sound name (file name without extension)
+    sound: String,
+    text: String,
+    voice: String,
+    voice_id: String,
+    duration_secs: f64,
+}
+
+pub async fn execute(args: IvrArgs, api_key: &str) -> Result<()> {
+    match args.command {
+        IvrCommands::Build(build) => build_prompts(build, api_key).await,
+    }
+}
+
+async fn build_prompts(args: IvrBuildArgs, api_key: &str) -> Result<()> {
+    let contents = std::fs::read_to_string(&args.prompts)
+        .with_context(|| format!("Failed to read {}", args.prompts))?;
+    let spec: PromptSpec = serde_yaml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", args.prompts))?;
+    let default_voice = args
+        .voice
+        .clone()
+        .or(spec.voice.clone())
+        .unwrap_or_else(|| DEFAULT_VOICE.to_string());
+    let model = args
+        .model
+        .clone()
+        .or(spec.model.clone())
+        .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let prompts = prompt_entries(spec)?;
+
+    let rate = local_rate(&args.format)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported format '{}'", args.format))?;
+    let request_format = format!("pcm_{}", rate);
+    for prompt in &prompts {
+        preflight_tts(&prompt.text, &model, &request_format, None)
+            .with_context(|| format!("Prompt '{}'", prompt.id))?;
+    }
+
+    let out_dir = PathBuf::from(&args.out_dir);
+    std::fs::create_dir_all(&out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+    // Voice names are resolved once for the whole pack
+    let mut voices: Vec<String> = vec![default_voice.clone()];
+    for voice in prompts.iter().filter_map(|p| p.voice.clone()) {
+        if !voices.contains(&voice) {
+            voices.push(voice);
+        }
+    }
+    let voice_ids = resolve_speakers(api_key, &voices).await?;
+
+    let extension = file_extension(&args.format);
+    let client = create_http_client();
+    let mut manifest = Manifest {
+        format: args.format.clone(),
+        encoding: encoding(&args.format),
+        container: if extension == "wav" { "wav" } else { "raw" },
+        sample_rate: rate,
+        model: model.clone(),
+        prompts: Vec::new(),
+    };
+    let mut existing = 0;
+    let mut failed = 0;
+
+    let progress = Progress::bar(prompts.len() as u64, "Generating prompts");
+    for prompt in &prompts {
+        let voice = prompt
+            .voice
+            .clone()
+            .unwrap_or_else(|| default_voice.clone());
+        let voice_id = voice_ids
+            .get(&voice)
+            .cloned()
+            .unwrap_or_else(|| voice.clone());
+        let file = format!("{}.{}", prompt.id, extension);
+        let path = out_dir.join(&file);
+
+        let audio_len = if path.exists() && !args.force {
+            existing += 1;
+            std::fs::metadata(&path)?.len() as usize
+        } else {
+            progress.set_message(&format!("Generating {}", prompt.id));
+            match generate_prompt(&client, api_key, &voice_id, &model, prompt, &args.format)
+                .await
+                .and_then(|audio| write_bytes_to_file(&audio, &path).map(|_| audio.len()))
+            {
+                Ok(len) => len,
+                Err(e) => {
+                    print_warning(&format!("Failed to generate '{}': {:#}", prompt.id, e));
+                    failed += 1;
+                    progress.inc(1);
+                    continue;
+                }
+            }
+        };
+
+        manifest.prompts.push(ManifestPrompt {
+            id: prompt.id.clone(),
+            file,
+            sound: prompt.id.clone(),
+            text: prompt.text.clone(),
+            voice,
+            voice_id,
+            duration_secs: audio_duration(audio_len, &args.format, rate),
+        });
+        progress.inc(1);
+    }
+    progress.finish();
+
+    let manifest_path = out_dir.join("manifest.json");
+    write_atomic(
+        &manifest_path,
+        serde_json::to_string_pretty(&manifest)?.as_bytes(),
+    )?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&manifest)?);
+    } else {
+        if existing > 0 {
+            print_info(&format!(
+                "Kept {} existing prompt(s); use --force to regenerate",
+                existing
+            ));
+        }
+        print_success(&format!(
+            "{} prompt(s) ({}) -> {}",
+            manifest.prompts.len(),
+            args.format,
+            manifest_path.display().to_string().green()
+        ));
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} prompt(s) could not be generated",
+            failed,
+            prompts.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Prompts in file order, with ids checked for use as file names
+fn prompt_entries(spec: PromptSpec) -> Result<Vec<Prompt>> {
+    let prompts = match spec.prompts {
+        PromptList::List(prompts) => prompts,
+        PromptList::Map(map) => map
+            .into_iter()
+            .map(|(id, text)| match (id.as_str(), text.as_str()) {
+                (Some(id), Some(text)) => Ok(Prompt {
+                    id: id.to_string(),
+                    text: text.to_string(),
+                    voice: None,
+                }),
+                _ => Err(anyhow::anyhow!(
+                    "Each entry under `prompts` must be `id: text`"
+                )),
+            })
+            .collect::<Result<_>>()?,
+    };
+    if prompts.is_empty() {
+        return Err(anyhow::anyhow!("The prompts file has no prompts"));
+    }
+
+    let mut seen = HashSet::new();
+    for prompt in &prompts {
+        if !is_valid_id(&prompt.id) {
+            return Err(anyhow::anyhow!(
+                "Invalid prompt id '{}'. Use letters, digits, '_' and '-' (it becomes the file name)",
+                prompt.id
+            ));
+        }
+        if !seen.insert(prompt.id.to_lowercase()) {
+            return Err(anyhow::anyhow!("Duplicate prompt id '{}'", prompt.id));
+        }
+        if prompt.text.trim().is_empty() {
+            return Err(anyhow::anyhow!("Prompt '{}' has no text", prompt.id));
+        }
+    }
+    Ok(prompts)
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Request PCM at the target rate and encode it for telephony
+async fn generate_prompt(
+    client: &Client,
+    api_key: &str,
+    voice_id: &str,
+    model: &str,
+    prompt: &Prompt,
+    format: &str,
+) -> Result<Vec<u8>> {
+    let rate = local_rate(format).context("Unsupported format")?;
+    let response = client
+        .post(format!("{}/v1/text-to-speech/{}", api_base(), voice_id))
+        .header("xi-api-key", api_key)
+        .query(&[("output_format", format!("pcm_{}", rate))])
+        .json(&json!({ "text": prompt.text, "model_id": model }))
+        .send_with_retry()
+        .await
+        .context("Failed to send TTS request")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let pcm = response.bytes().await?;
+
+    let target = match format.strip_prefix("pcm_") {
+        Some(rate) => format!("wav_{}", rate),
+        None => format.to_string(),
+    };
+    encode_local(&samples_from_pcm16(&pcm), rate, &target)
+        .with_context(|| format!("Cannot encode {}", format))
+}
+
+/// File extension: WAV for linear PCM, Asterisk's raw G.711 names otherwise
+fn file_extension(format: &str) -> &'static str {
+    if format.starts_with("ulaw_") {
+        "ulaw"
+    } else if format.starts_with("alaw_") {
+        "alaw"
+    } else {
+        "wav"
+    }
+}
+
+fn encoding(format: &str) -> &'static str {
+    match file_extension(format) {
+        "wav" => "pcm_s16le",
+        other => other,
+    }
+}
+
+/// Playing time of a prompt file, from its size
+fn audio_duration(len: usize, format: &str, rate: u32) -> f64 {
+    let samples = match file_extension(format) {
+        "wav" => len.saturating_sub(WAV_HEADER_LEN) / 2,
+        // One byte per G.711 sample
+        _ => len,
+    };
+    (samples as f64 / rate as f64 * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Result<Vec<Prompt>> {
+        prompt_entries(serde_yaml::from_str(yaml)?)
+    }
+
+    #[test]
+    fn test_prompt_map_and_list() {
+        let prompts = parse(
+            "voice: Rachel\nprompts:\n  welcome: Thanks for calling.\n  main-menu: For sales, press 1.\n",
+        )
+        .unwrap();
+        assert_eq!(prompts.len(), 2);
+        assert_eq!(prompts[0].id, "welcome");
+        assert_eq!(prompts[1].text, "For sales, press 1.");
+
+        let prompts =
+            parse("prompts:\n  - id: closed\n    text: We are closed.\n    voice: Adam\n").unwrap();
+        assert_eq!(
+            prompts,
+            vec![Prompt {
+                id: "closed".to_string(),
+                text: "We are closed.".to_string(),
+                voice: Some("Adam".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_prompt_ids_are_checked() {
+        assert!(parse("prompts:\n  ../escape: Hi\n").is_err());
+        assert!(parse("prompts:\n  - {id: a, text: Hi}\n  - {id: A, text: Hello}\n").is_err());
+        assert!(parse("prompts:\n  empty: ' '\n").is_err());
+        assert!(parse("prompts: {}\n").is_err());
+    }
+
+    #[test]
+    fn test_audio_duration() {
+        assert_eq!(audio_duration(8_000, "ulaw_8000", 8_000), 1.0);
+        assert_eq!(
+            audio_duration(WAV_HEADER_LEN + 16_000, "pcm_8000", 8_000),
+            1.0
+        );
+        assert_eq!(file_extension("pcm_16000"), "wav");
+        assert_eq!(encoding("alaw_8000"), "alaw");
+    }
+}
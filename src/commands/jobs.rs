@@ -0,0 +1,349 @@
+//! Remote job commands: list, status and wait on the jobs in the journal.
+//!
+//! Each kind of job reports its state through its own endpoint; this module
+//! reduces them to a state string, a phase and, where the API gives one, a
+//! percentage. See [`crate::journal`] for how jobs get recorded. Commands
+//! that start a job wait on it here when given `--wait`.
+
+use super::projects::fetch_project;
+use crate::cli::{JobKind, JobsArgs, JobsCommands, WaitArgs};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::journal::{self, phase_of, JobPhase, RemoteJob};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::utils::{format_relative_time, poll_until, unix_now, PollStatus};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use futures::StreamExt;
+use reqwest::Client;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Jobs whose states `list` refreshes at the same time
+const REFRESH_CONCURRENCY: usize = 4;
+
+pub async fn execute(args: JobsArgs, api_key: &str) -> Result<()> {
+    let client = create_http_client();
+
+    match args.command {
+        JobsCommands::List {
+            active,
+            kind,
+            no_refresh,
+        } => list_jobs(&client, api_key, active, kind, no_refresh).await,
+        JobsCommands::Status { job_id } => job_status(&client, api_key, &job_id).await,
+        JobsCommands::Wait {
+            job_id,
+            interval,
+            timeout,
+        } => wait_for_job(&client, api_key, &job_id, interval, timeout).await,
+    }
+}
+
+fn kind_name(kind: JobKind) -> &'static str {
+    match kind {
+        JobKind::Dubbing => "dubbing",
+        JobKind::Pvc => "pvc",
+        JobKind::Project => "project",
+        JobKind::BatchCall => "batch-call",
+        JobKind::RagIndex => "rag-index",
+    }
+}
+
+fn status_field(body: &Value) -> String {
+    body["status"].as_str().unwrap_or("unknown").to_string()
+}
+
+/// Ask the API where `job` stands and return an updated copy
+async fn refresh(client: &Client, api_key: &str, job: &RemoteJob) -> Result<RemoteJob> {
+    let parent = || {
+        job.parent
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Job '{}' has no parent recorded", job.id))
+    };
+
+    let (url, what) = match job.kind {
+        JobKind::Project => {
+            let project = fetch_project(client, api_key, &job.id).await?;
+            let state = project.display_state();
+            let phase = if project.is_converting() {
+                JobPhase::Running
+            } else if phase_of(&state) == JobPhase::Failed {
+                JobPhase::Failed
+            } else {
+                JobPhase::Succeeded
+            };
+            return Ok(refreshed(
+                job,
+                state,
+                phase,
+                Some(project.conversion_percent()),
+            ));
+        }
+        JobKind::Dubbing => (format!("{}/v1/dubbing/{}", api_base(), job.id), "dubbing"),
+        JobKind::Pvc => (
+            format!("{}/v1/voices/{}/fine-tune", api_base(), parent()?),
+            "training status",
+        ),
+        JobKind::BatchCall => (
+            format!("{}/v1/convai/batch-calling/{}", api_base(), job.id),
+            "batch call",
+        ),
+        JobKind::RagIndex => (
+            format!(
+                "{}/v1/convai/knowledge-base/{}/rag-index/{}",
+                api_base(),
+                parent()?,
+                job.id
+            ),
+            "RAG index",
+        ),
+    };
+
+    let response = client
+        .get(url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to fetch {}", what))?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let body: Value = read_json(response).await?;
+
+    let state = status_field(&body);
+    let progress = match job.kind {
+        JobKind::BatchCall => match (
+            body["total_calls_finished"].as_f64(),
+            body["total_calls"].as_f64(),
+        ) {
+            (Some(done), Some(total)) if total > 0.0 => Some(done * 100.0 / total),
+            _ => None,
+        },
+        JobKind::RagIndex => body["progress_percentage"].as_f64(),
+        _ => None,
+    };
+    let phase = phase_of(&state);
+    Ok(refreshed(job, state, phase, progress))
+}
+
+/// `job` with what a check just found
+fn refreshed(job: &RemoteJob, state: String, phase: JobPhase, progress: Option<f64>) -> RemoteJob {
+    RemoteJob {
+        state: Some(state),
+        phase,
+        progress,
+        checked_at: Some(unix_now()),
+        ..job.clone()
+    }
+}
+
+/// Jobs of this account, oldest first
+fn account_jobs(api_key: &str) -> Result<Vec<RemoteJob>> {
+    let account = journal::account_id(api_key);
+    Ok(journal::load()?
+        .into_iter()
+        .filter(|j| j.account == account)
+        .collect())
+}
+
+/// The most recent job with this ID
+fn find_job(api_key: &str, job_id: &str) -> Result<RemoteJob> {
+    account_jobs(api_key)?
+        .into_iter()
+        .rev()
+        .find(|j| j.id == job_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Job '{}' is not in the journal; only jobs started with this CLI are tracked (see `jobs list`)",
+                job_id
+            )
+        })
+}
+
+fn phase_label(job: &RemoteJob) -> String {
+    let state = job.state.as_deref().unwrap_or("started");
+    match job.phase {
+        JobPhase::Running => state.yellow().to_string(),
+        JobPhase::Succeeded => state.green().to_string(),
+        JobPhase::Failed => state.red().to_string(),
+    }
+}
+
+fn progress_label(job: &RemoteJob) -> String {
+    job.progress
+        .map(|p| format!("{:.0}%", p))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn ago(unix_secs: u64) -> String {
+    format_relative_time(unix_now().saturating_sub(unix_secs))
+}
+
+async fn list_jobs(
+    client: &Client,
+    api_key: &str,
+    active: bool,
+    kind: Option<JobKind>,
+    no_refresh: bool,
+) -> Result<()> {
+    let mut jobs: Vec<RemoteJob> = account_jobs(api_key)?
+        .into_iter()
+        .filter(|j| kind.is_none_or(|k| j.kind == k))
+        .collect();
+
+    if !no_refresh && jobs.iter().any(|j| !j.phase.is_finished()) {
+        print_info("Refreshing running jobs...");
+        jobs = futures::stream::iter(jobs)
+            .map(|job| async move {
+                if job.phase.is_finished() {
+                    return job;
+                }
+                match refresh(client, api_key, &job).await {
+                    Ok(updated) => updated,
+                    Err(e) => {
+                        print_warning(&format!("Could not refresh job '{}': {}", job.id, e));
+                        job
+                    }
+                }
+            })
+            .buffered(REFRESH_CONCURRENCY)
+            .collect()
+            .await;
+        journal::save_states(&jobs)?;
+    }
+
+    if active {
+        jobs.retain(|j| !j.phase.is_finished());
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&jobs)?);
+        return Ok(());
+    }
+
+    if jobs.is_empty() {
+        print_info("No jobs found");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Type", "Label", "State", "Progress", "Started"]);
+    for job in jobs.iter().rev() {
+        table.add_row(vec![
+            job.id.yellow().to_string(),
+            kind_name(job.kind).to_string(),
+            job.label.clone(),
+            phase_label(job),
+            progress_label(job),
+            ago(job.started_at),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+async fn job_status(client: &Client, api_key: &str, job_id: &str) -> Result<()> {
+    let job = refresh(client, api_key, &find_job(api_key, job_id)?).await?;
+    journal::save_states(std::slice::from_ref(&job))?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+        return Ok(());
+    }
+    print_job(&job);
+    Ok(())
+}
+
+fn print_job(job: &RemoteJob) {
+    let mut table = Table::new();
+    table.set_header(vec!["Property", "Value"]);
+    table.add_row(vec!["ID", &job.id.yellow()]);
+    table.add_row(vec!["Type", kind_name(job.kind)]);
+    if let Some(ref parent) = job.parent {
+        table.add_row(vec!["Parent", parent]);
+    }
+    table.add_row(vec!["Label", &job.label]);
+    table.add_row(vec!["State", &phase_label(job)]);
+    if job.progress.is_some() {
+        table.add_row(vec!["Progress", &progress_label(job)]);
+    }
+    table.add_row(vec!["Started", &ago(job.started_at)]);
+    if let Some(checked) = job.checked_at {
+        table.add_row(vec!["Checked", &ago(checked)]);
+    }
+    println!("{}", table);
+}
+
+async fn wait_for_job(
+    client: &Client,
+    api_key: &str,
+    job_id: &str,
+    interval: u64,
+    timeout: Option<u64>,
+) -> Result<()> {
+    if interval == 0 {
+        return Err(anyhow::anyhow!("--interval must be at least 1 second"));
+    }
+
+    let job = find_job(api_key, job_id)?;
+    finish_wait(client, api_key, job, interval, timeout).await
+}
+
+/// Wait on a job that was just started if `--wait` was given
+pub async fn wait_if_requested(api_key: &str, job: RemoteJob, wait: &WaitArgs) -> Result<()> {
+    if !wait.wait {
+        return Ok(());
+    }
+    let client = create_http_client();
+    finish_wait(&client, api_key, job, wait.interval, wait.timeout).await
+}
+
+/// Poll `job` until it finishes, then report it; fails when the job fails
+async fn finish_wait(
+    client: &Client,
+    api_key: &str,
+    job: RemoteJob,
+    interval: u64,
+    timeout: Option<u64>,
+) -> Result<()> {
+    let message = format!("Waiting for {} '{}'", kind_name(job.kind), job.id);
+    let job = poll_until(
+        &message,
+        Duration::from_secs(interval),
+        timeout.map(Duration::from_secs),
+        || async {
+            let job = refresh(client, api_key, &job).await?;
+            journal::save_states(std::slice::from_ref(&job))?;
+            Ok(if job.phase.is_finished() {
+                PollStatus::Done(job)
+            } else {
+                PollStatus::Pending(format!(
+                    "{} ({})",
+                    job.state.as_deref().unwrap_or("started"),
+                    progress_label(&job)
+                ))
+            })
+        },
+    )
+    .await?;
+
+    if job.phase == JobPhase::Failed {
+        return Err(anyhow::anyhow!(
+            "Job '{}' failed: {}",
+            job.id,
+            job.state.as_deref().unwrap_or("unknown")
+        ));
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+    } else {
+        print_success(&format!(
+            "Job '{}' finished: {}",
+            job.id.green(),
+            job.state.as_deref().unwrap_or("done")
+        ));
+    }
+    Ok(())
+}
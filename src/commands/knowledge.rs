@@ -1,11 +1,13 @@
 use crate::cli::{KnowledgeArgs, KnowledgeCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::knowledge_crawl::{add_crawled_documents, CrawlOptions};
+use crate::commands::knowledge_update::update_document;
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 pub async fn execute(args: KnowledgeArgs, api_key: &str) -> Result<()> {
@@ -19,7 +21,42 @@ pub async fn execute(args: KnowledgeArgs, api_key: &str) -> Result<()> {
             url,
             name,
             description,
-        } => add_document_from_url(&client, api_key, &url, &name, description.as_deref()).await,
+            crawl_depth,
+            sitemap,
+            include,
+            exclude,
+            max_pages,
+            dry_run,
+        } => {
+            if crawl_depth == 0 && !sitemap && include.is_empty() && exclude.is_empty() && !dry_run
+            {
+                return add_document_from_url(
+                    &client,
+                    api_key,
+                    &url,
+                    &name,
+                    description.as_deref(),
+                )
+                .await;
+            }
+            let options = CrawlOptions {
+                depth: crawl_depth,
+                sitemap,
+                include,
+                exclude,
+                max_pages,
+            };
+            add_crawled_documents(
+                &client,
+                api_key,
+                &url,
+                &name,
+                description.as_deref(),
+                &options,
+                dry_run,
+            )
+            .await
+        }
         KnowledgeCommands::AddFromText {
             text,
             name,
@@ -28,6 +65,24 @@ pub async fn execute(args: KnowledgeArgs, api_key: &str) -> Result<()> {
         KnowledgeCommands::AddFromFile { file, name } => {
             add_document_from_file(&client, api_key, &file, name.as_deref()).await
         }
+        KnowledgeCommands::Update {
+            document_id,
+            file,
+            name,
+            no_rebuild,
+            wait,
+        } => {
+            update_document(
+                &client,
+                api_key,
+                &document_id,
+                &file,
+                name.as_deref(),
+                !no_rebuild,
+                &wait,
+            )
+            .await
+        }
         KnowledgeCommands::Get { document_id } => {
             get_document(&client, api_key, &document_id).await
         }
@@ -45,7 +100,7 @@ async fn list_documents(
 ) -> Result<()> {
     print_info("Fetching knowledge base documents...");
 
-    let url = "https://api.elevenlabs.io/v1/convai/knowledge-base";
+    let url = format!("{}/v1/convai/knowledge-base", api_base());
     let mut request = client.get(url).header("xi-api-key", api_key);
 
     if let Some(lim) = limit {
@@ -56,15 +111,21 @@ async fn list_documents(
         request = request.query(&[("offset", off.to_string())]);
     }
 
-    let response = request.send().await.context("Failed to fetch documents")?;
+    let response = request
+        .send_with_retry()
+        .await
+        .context("Failed to fetch documents")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let documents: Vec<KnowledgeDocument> =
-        response.json().await.context("Failed to parse response")?;
+    let documents: Vec<KnowledgeDocument> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&documents)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["ID", "Name", "Type", "Created"]);
@@ -102,19 +163,18 @@ async fn add_document_from_url(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/knowledge-base")
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to add document")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateDocumentResponse = response.json().await?;
+    let result: CreateDocumentResponse = read_json(response).await?;
     print_success("Document added successfully!");
     print_info(&format!("Document ID: {}", result.id.yellow()));
 
@@ -138,19 +198,18 @@ async fn add_document_from_text(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/knowledge-base")
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to add document")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateDocumentResponse = response.json().await?;
+    let result: CreateDocumentResponse = read_json(response).await?;
     print_success("Document added successfully!");
     print_info(&format!("Document ID: {}", result.id.yellow()));
 
@@ -165,27 +224,7 @@ async fn add_document_from_file(
 ) -> Result<()> {
     print_info(&format!("Adding document from file: {}", file.cyan()));
 
-    // Validate file path and check if file exists
-    let path = std::path::Path::new(file);
-    if !path.exists() {
-        return Err(anyhow::anyhow!("File does not exist: {}", file));
-    }
-
-    if !path.is_file() {
-        return Err(anyhow::anyhow!("Path is not a file: {}", file));
-    }
-
-    // Check file size (limit to 10MB for API)
-    let metadata = std::fs::metadata(file)?;
-    let file_size = metadata.len();
-    if file_size > 10_000_000 {
-        return Err(anyhow::anyhow!(
-            "File too large ({} bytes). Maximum is 10MB.",
-            file_size
-        ));
-    }
-
-    let content = std::fs::read_to_string(file).context("Failed to read file")?;
+    let content = read_document_file(file)?;
     let file_name = name.unwrap_or(file);
 
     let body = json!({
@@ -195,44 +234,69 @@ async fn add_document_from_file(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/knowledge-base")
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to add document")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateDocumentResponse = response.json().await?;
+    let result: CreateDocumentResponse = read_json(response).await?;
     print_success("Document added successfully!");
     print_info(&format!("Document ID: {}", result.id.yellow()));
 
     Ok(())
 }
 
+/// Read a text file to upload as a document
+pub(crate) fn read_document_file(file: &str) -> Result<String> {
+    // Validate file path and check if file exists
+    let path = std::path::Path::new(file);
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File does not exist: {}", file));
+    }
+
+    if !path.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", file));
+    }
+
+    // Check file size (limit to 10MB for API)
+    let metadata = std::fs::metadata(file)?;
+    let file_size = metadata.len();
+    if file_size > 10_000_000 {
+        return Err(anyhow::anyhow!(
+            "File too large ({} bytes). Maximum is 10MB.",
+            file_size
+        ));
+    }
+
+    std::fs::read_to_string(file).context("Failed to read file")
+}
+
 async fn get_document(client: &Client, api_key: &str, document_id: &str) -> Result<()> {
     print_info(&format!("Fetching document '{}'...", document_id.cyan()));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}",
-        document_id
-    );
+    let url = format!("{}/v1/convai/knowledge-base/{}", api_base(), document_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let doc: KnowledgeDocument = response.json().await?;
+    let doc: KnowledgeDocument = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&doc)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -248,26 +312,22 @@ async fn get_document(client: &Client, api_key: &str, document_id: &str) -> Resu
 async fn delete_document(client: &Client, api_key: &str, document_id: &str) -> Result<()> {
     print_info(&format!("Deleting document '{}'...", document_id.cyan()));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}",
-        document_id
-    );
+    let url = format!("{}/v1/convai/knowledge-base/{}", api_base(), document_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Document '{}' deleted successfully", document_id));
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct KnowledgeDocument {
     id: String,
     name: String,
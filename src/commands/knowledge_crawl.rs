@@ -0,0 +1,438 @@
+//! Expanding a root URL into many knowledge base documents
+//!
+//! The knowledge base API fetches one URL per document, so pages are found
+//! here: either by following same-site links from the root page up to a
+//! depth, or from the site's sitemap. Each page found becomes its own URL
+//! document. Added pages are recorded in a job state file, so rerunning a
+//! crawl that failed partway only adds the pages still missing.
+
+use crate::article::extract_links;
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::job_state::JobState;
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::utils::wildcard_match;
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::{Client, Url};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashSet, VecDeque};
+
+/// Nested sitemaps followed from a sitemap index
+const MAX_CHILD_SITEMAPS: usize = 20;
+
+/// File extensions that are never pages
+const ASSET_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "ico", "css", "js", "mjs", "json", "xml", "zip",
+    "gz", "mp3", "mp4", "wav", "webm", "woff", "woff2", "ttf",
+];
+
+/// How to find pages under the root URL
+pub struct CrawlOptions {
+    pub depth: u32,
+    pub sitemap: bool,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub max_pages: usize,
+}
+
+#[derive(Serialize)]
+struct AddedPage {
+    url: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Crawl `root` and add one URL document per page found
+pub async fn add_crawled_documents(
+    client: &Client,
+    api_key: &str,
+    root: &str,
+    name: &str,
+    description: Option<&str>,
+    options: &CrawlOptions,
+    dry_run: bool,
+) -> Result<()> {
+    let root = Url::parse(root).map_err(|_| anyhow::anyhow!("Invalid URL: '{}'", root))?;
+    if root.scheme() != "https" && root.scheme() != "http" {
+        return Err(anyhow::anyhow!("URL must use HTTP or HTTPS scheme"));
+    }
+
+    let progress = Progress::spinner("Finding pages");
+    let found = if options.sitemap {
+        sitemap_pages(&root, options).await
+    } else {
+        crawl_pages(&root, options, &progress).await
+    };
+    progress.finish();
+    let (pages, truncated) = found?;
+
+    if pages.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No pages found under {} that match the include/exclude patterns",
+            root
+        ));
+    }
+    if truncated {
+        print_warning(&format!(
+            "Stopped at {} pages; raise --max-pages to add more",
+            options.max_pages
+        ));
+    }
+
+    let mut added: Vec<AddedPage> = pages
+        .iter()
+        .map(|url| AddedPage {
+            url: url.to_string(),
+            name: page_name(name, url),
+            id: None,
+            error: None,
+        })
+        .collect();
+
+    let mut state = JobState::open("crawl", api_key, &[root.as_str(), name]);
+    if !dry_run {
+        if state.completed_count() > 0 {
+            print_info(&format!(
+                "Resuming: {} page(s) were added by an earlier run",
+                state.completed_count()
+            ));
+        }
+        let progress = Progress::spinner("Adding documents");
+        for (i, page) in added.iter_mut().enumerate() {
+            if let Some(id) = state.completed(&page.url) {
+                page.id = Some(id.to_string());
+                continue;
+            }
+            progress.set_message(&format!("Adding {}/{}: {}", i + 1, pages.len(), page.url));
+            match add_url_document(client, api_key, page, description).await {
+                Ok(id) => {
+                    if let Err(e) = state.mark_done(&page.url, &id) {
+                        page.error = Some(format!("{:#}", e));
+                    }
+                    page.id = Some(id);
+                }
+                Err(e) => page.error = Some(format!("{:#}", e)),
+            }
+        }
+        progress.finish();
+    }
+    let failed = added.iter().filter(|p| p.error.is_some()).count();
+    if !dry_run && failed == 0 {
+        state.finish();
+    }
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "root": root.as_str(),
+                "dry_run": dry_run,
+                "pages": added,
+            }))?
+        );
+    } else {
+        let mut table = Table::new();
+        if dry_run {
+            table.set_header(vec!["URL", "Name"]);
+        } else {
+            table.set_header(vec!["URL", "Name", "Document ID"]);
+        }
+        for page in &added {
+            let mut row = vec![page.url.clone(), page.name.clone()];
+            if !dry_run {
+                row.push(match (&page.id, &page.error) {
+                    (Some(id), _) => id.yellow().to_string(),
+                    (None, Some(error)) => error.red().to_string(),
+                    (None, None) => String::new(),
+                });
+            }
+            table.add_row(row);
+        }
+        println!("{}", table);
+
+        if dry_run {
+            print_info(&format!("Dry run: {} page(s) would be added", added.len()));
+        } else if failed == 0 {
+            print_success(&format!("Added {} document(s)", added.len()));
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} page(s) could not be added",
+            failed,
+            added.len()
+        ));
+    }
+    Ok(())
+}
+
+async fn add_url_document(
+    client: &Client,
+    api_key: &str,
+    page: &AddedPage,
+    description: Option<&str>,
+) -> Result<String> {
+    let response = client
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
+        .header("xi-api-key", api_key)
+        .json(&json!({
+            "name": page.name,
+            "type": "url",
+            "url": page.url,
+            "description": description.unwrap_or("")
+        }))
+        .send_with_retry()
+        .await
+        .context("Failed to add document")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let result: serde_json::Value = read_json(response).await?;
+    result["id"]
+        .as_str()
+        .map(str::to_string)
+        .context("Response has no document ID")
+}
+
+/// Breadth-first crawl of same-site links; returns (pages, stopped at max_pages)
+async fn crawl_pages(
+    root: &Url,
+    options: &CrawlOptions,
+    progress: &Progress,
+) -> Result<(Vec<Url>, bool)> {
+    let mut seen: HashSet<Url> = HashSet::from([root.clone()]);
+    let mut queue = VecDeque::from([(root.clone(), 0u32)]);
+    let mut pages = Vec::new();
+
+    while let Some((url, depth)) = queue.pop_front() {
+        // The root is crawled for links even when the patterns exclude it
+        if url != *root || matches_filters(&url, options) {
+            if pages.len() == options.max_pages {
+                return Ok((pages, true));
+            }
+            pages.push(url.clone());
+        }
+        if depth == options.depth {
+            continue;
+        }
+
+        progress.set_message(&format!("Crawling {} ({} found)", url, pages.len()));
+        let html = match fetch_text(&url).await {
+            Ok(html) => html,
+            // A broken root is an error; broken links further down are skipped
+            Err(e) if url == *root => return Err(e),
+            Err(e) => {
+                print_warning(&format!("Skipping {}: {:#}", url, e));
+                continue;
+            }
+        };
+        for link in page_links(&url, &html) {
+            if matches_filters(&link, options) && seen.insert(link.clone()) {
+                queue.push_back((link, depth + 1));
+            }
+        }
+    }
+    Ok((pages, false))
+}
+
+/// Pages listed in the sitemap; returns (pages, stopped at max_pages)
+async fn sitemap_pages(root: &Url, options: &CrawlOptions) -> Result<(Vec<Url>, bool)> {
+    let sitemap = if root.path().ends_with(".xml") {
+        root.clone()
+    } else {
+        root.join("/sitemap.xml")?
+    };
+    let xml = fetch_text(&sitemap)
+        .await
+        .with_context(|| format!("Failed to read sitemap {}", sitemap))?;
+    let (mut locations, children) = parse_sitemap(&xml)?;
+
+    // A sitemap index lists other sitemaps rather than pages
+    for child in children.iter().take(MAX_CHILD_SITEMAPS) {
+        let result = match Url::parse(child) {
+            Ok(url) => fetch_text(&url).await.and_then(|xml| parse_sitemap(&xml)),
+            Err(e) => Err(e.into()),
+        };
+        match result {
+            Ok((pages, _)) => locations.extend(pages),
+            Err(e) => print_warning(&format!("Skipping sitemap {}: {:#}", child, e)),
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut pages = Vec::new();
+    for location in locations {
+        let Ok(url) = Url::parse(&location) else {
+            continue;
+        };
+        if !matches_filters(&url, options) || !seen.insert(url.clone()) {
+            continue;
+        }
+        if pages.len() == options.max_pages {
+            return Ok((pages, true));
+        }
+        pages.push(url);
+    }
+    Ok((pages, false))
+}
+
+/// `<loc>` entries of a sitemap: (pages, nested sitemaps)
+fn parse_sitemap(xml: &str) -> Result<(Vec<String>, Vec<String>)> {
+    let doc = roxmltree::Document::parse(xml).context("Sitemap is not valid XML")?;
+    let locations: Vec<String> = doc
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "loc")
+        .filter_map(|n| n.text())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect();
+    Ok(match doc.root_element().tag_name().name() {
+        "sitemapindex" => (Vec::new(), locations),
+        _ => (locations, Vec::new()),
+    })
+}
+
+/// Same-site page links of a page, resolved against its URL
+fn page_links(base: &Url, html: &str) -> Vec<Url> {
+    extract_links(html)
+        .iter()
+        .filter_map(|href| base.join(href).ok())
+        .filter(|url| url.scheme() == base.scheme() && url.host() == base.host())
+        .filter(|url| url.port_or_known_default() == base.port_or_known_default())
+        .filter(|url| {
+            let ext = url
+                .path()
+                .rsplit_once('.')
+                .map(|(_, ext)| ext.to_lowercase());
+            !ext.is_some_and(|ext| ASSET_EXTENSIONS.contains(&ext.as_str()))
+        })
+        .map(|mut url| {
+            url.set_fragment(None);
+            url
+        })
+        .collect()
+}
+
+async fn fetch_text(url: &Url) -> Result<String> {
+    let response = create_http_client()
+        .get(url.clone())
+        .header(
+            reqwest::header::USER_AGENT,
+            concat!("elevenlabs-cli/", env!("CARGO_PKG_VERSION")),
+        )
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+    }
+    response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {}", url))
+}
+
+/// Whether a page passes the include and exclude patterns
+fn matches_filters(url: &Url, options: &CrawlOptions) -> bool {
+    let target = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let matches = |pattern: &String| {
+        if pattern.contains("://") {
+            wildcard_match(pattern, url.as_str())
+        } else {
+            wildcard_match(pattern, &target)
+        }
+    };
+    (options.include.is_empty() || options.include.iter().any(matches))
+        && !options.exclude.iter().any(matches)
+}
+
+/// Document name for a crawled page: the base name plus the page path
+fn page_name(base: &str, url: &Url) -> String {
+    match url.path().trim_matches('/') {
+        "" => base.to_string(),
+        path => format!("{} - {}", base, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(include: &[&str], exclude: &[&str]) -> CrawlOptions {
+        CrawlOptions {
+            depth: 1,
+            sitemap: false,
+            include: include.iter().map(|s| s.to_string()).collect(),
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            max_pages: 50,
+        }
+    }
+
+    #[test]
+    fn test_include_exclude_filters() {
+        let url = Url::parse("https://example.com/docs/api?v=2").unwrap();
+        assert!(matches_filters(&url, &options(&[], &[])));
+        assert!(matches_filters(&url, &options(&["/docs/*"], &[])));
+        assert!(!matches_filters(&url, &options(&["/docs/*"], &["*v=2"])));
+        assert!(matches_filters(
+            &url,
+            &options(&["https://example.com/*"], &[])
+        ));
+    }
+
+    #[test]
+    fn test_page_links_stay_on_site() {
+        let base = Url::parse("https://example.com/docs/").unwrap();
+        let html = r##"<a href="intro#top">Intro</a> <a href="/logo.png">Logo</a>
+            <a href="https://other.com/x">Other</a> <a href="mailto:a@b.c">Mail</a>
+            <a href="//example.com/faq">FAQ</a>"##;
+        let links: Vec<String> = page_links(&base, html)
+            .iter()
+            .map(|u| u.to_string())
+            .collect();
+        assert_eq!(
+            links,
+            vec!["https://example.com/docs/intro", "https://example.com/faq"]
+        );
+        assert_eq!(
+            page_name(
+                "Docs",
+                &Url::parse("https://example.com/docs/intro/").unwrap()
+            ),
+            "Docs - docs/intro"
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_and_index() {
+        let (pages, children) = parse_sitemap(
+            r#"<?xml version="1.0"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <url><loc> https://example.com/a </loc></url>
+              <url><loc>https://example.com/b</loc><lastmod>2024-01-01</lastmod></url>
+            </urlset>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pages,
+            vec!["https://example.com/a", "https://example.com/b"]
+        );
+        assert!(children.is_empty());
+
+        let (pages, children) = parse_sitemap(
+            r#"<sitemapindex><sitemap><loc>https://example.com/s1.xml</loc></sitemap></sitemapindex>"#,
+        )
+        .unwrap();
+        assert!(pages.is_empty());
+        assert_eq!(children, vec!["https://example.com/s1.xml"]);
+    }
+}
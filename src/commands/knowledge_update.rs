@@ -0,0 +1,311 @@
+//! Replacing a knowledge base document's content (`knowledge update`)
+//!
+//! The API can rename a document but not change what it contains, so an
+//! update uploads the new content as a fresh document, points every agent
+//! that referenced the old document at the new one, and only then deletes
+//! the old document. If any agent can't be relinked the old document is
+//! kept, so no agent is left pointing at a missing document. The new
+//! document's RAG index is rebuilt afterwards. The replacement is always a
+//! text document; URL documents are refused, since their content comes
+//! from the URL rather than a file.
+
+use crate::cli::WaitArgs;
+use crate::client::{api_base, api_error, read_json, SendWithRetry};
+use crate::commands::knowledge::read_document_file;
+use crate::commands::rag::rebuild_index;
+use crate::output::{print_info, print_success, print_warning};
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+pub async fn update_document(
+    client: &Client,
+    api_key: &str,
+    document_id: &str,
+    file: &str,
+    name: Option<&str>,
+    rebuild: bool,
+    wait: &WaitArgs,
+) -> Result<()> {
+    let content = read_document_file(file)?;
+    let response = client
+        .get(format!(
+            "{}/v1/convai/knowledge-base/{}",
+            api_base(),
+            document_id
+        ))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch document")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let current: Value = read_json(response).await?;
+    let name = name
+        .or_else(|| current["name"].as_str())
+        .unwrap_or(file)
+        .to_string();
+    if current["type"] == "url" {
+        return Err(anyhow::anyhow!(
+            "'{}' is a URL document, whose content is scraped from {}; add the file as a new document instead",
+            name,
+            current["url"].as_str().unwrap_or("its URL")
+        ));
+    }
+    let agent_ids = dependent_agent_ids(client, api_key, document_id).await?;
+
+    print_info(&format!(
+        "Uploading new content for '{}' ({} dependent agent(s))...",
+        name.cyan(),
+        agent_ids.len()
+    ));
+    let response = client
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
+        .header("xi-api-key", api_key)
+        .json(&new_document(&name, &content))
+        .send_with_retry()
+        .await
+        .context("Failed to add document")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let created: Value = read_json(response).await?;
+    let new_id = created["id"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("The API returned no document ID"))?
+        .to_string();
+
+    let mut failed = Vec::new();
+    for agent_id in &agent_ids {
+        let new_doc = NewDocument {
+            id: &new_id,
+            name: &name,
+        };
+        match relink_agent(client, api_key, agent_id, document_id, &new_doc).await {
+            Ok(()) => print_info(&format!("Relinked agent {}", agent_id.yellow())),
+            Err(e) => {
+                print_warning(&format!("Could not relink agent {}: {:#}", agent_id, e));
+                failed.push(agent_id.as_str());
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        delete_document(client, api_key, document_id).await?;
+        print_success(&format!(
+            "Document '{}' replaced: {} -> {}",
+            name,
+            document_id,
+            new_id.green()
+        ));
+    } else {
+        print_warning(&format!(
+            "Kept the old document {} because {} agent(s) still reference it: {}",
+            document_id,
+            failed.len(),
+            failed.join(", ")
+        ));
+        print_success(&format!("New document ID: {}", new_id.green()));
+    }
+
+    if rebuild {
+        rebuild_index(client, api_key, &new_id, wait).await?;
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{} agent(s) could not be relinked to the new document",
+            failed.len()
+        ))
+    }
+}
+
+/// Body for the replacement document, a text document with the new content
+fn new_document(name: &str, content: &str) -> Value {
+    json!({ "name": name, "type": "text", "content": content })
+}
+
+/// Agent IDs from one page of the dependent-agents response
+fn page_agent_ids(page: &Value) -> Vec<String> {
+    page["agents"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|agent| agent["id"].as_str().or_else(|| agent["agent_id"].as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Every agent that depends on the document, across all pages
+async fn dependent_agent_ids(
+    client: &Client,
+    api_key: &str,
+    document_id: &str,
+) -> Result<Vec<String>> {
+    let mut agent_ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = client
+            .get(format!(
+                "{}/v1/convai/knowledge-base/{}/dependent-agents",
+                api_base(),
+                document_id
+            ))
+            .header("xi-api-key", api_key);
+        if let Some(c) = &cursor {
+            request = request.query(&[("cursor", c)]);
+        }
+        let response = request
+            .send_with_retry()
+            .await
+            .context("Failed to fetch dependent agents")?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let page: Value = read_json(response).await?;
+        agent_ids.extend(page_agent_ids(&page));
+
+        cursor = page["next_cursor"].as_str().map(str::to_string);
+        if !page["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            return Ok(agent_ids);
+        }
+    }
+}
+
+/// The replacement document as an agent references it
+struct NewDocument<'a> {
+    id: &'a str,
+    name: &'a str,
+}
+
+/// The agent's knowledge base list with `old_id` swapped for the new
+/// document, or `None` when the agent doesn't reference `old_id`
+fn relinked_knowledge_base(
+    agent: &Value,
+    old_id: &str,
+    new_doc: &NewDocument<'_>,
+) -> Option<Vec<Value>> {
+    let mut documents = agent["conversation_config"]["agent"]["prompt"]["knowledge_base"]
+        .as_array()?
+        .clone();
+    let mut found = false;
+    for doc in documents.iter_mut().filter(|d| d["id"] == old_id) {
+        doc["id"] = json!(new_doc.id);
+        doc["name"] = json!(new_doc.name);
+        doc["type"] = json!("text");
+        found = true;
+    }
+    found.then_some(documents)
+}
+
+async fn relink_agent(
+    client: &Client,
+    api_key: &str,
+    agent_id: &str,
+    old_id: &str,
+    new_doc: &NewDocument<'_>,
+) -> Result<()> {
+    let response = client
+        .get(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch agent")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let agent: Value = read_json(response).await?;
+    let Some(documents) = relinked_knowledge_base(&agent, old_id, new_doc) else {
+        // Dependent through something other than its own knowledge base
+        // (e.g. a workflow node), which can't be relinked from here
+        return Err(anyhow::anyhow!(
+            "the agent doesn't list the document in its knowledge base; relink it manually"
+        ));
+    };
+
+    let body = json!({
+        "conversation_config": {
+            "agent": { "prompt": { "knowledge_base": documents } }
+        }
+    });
+    let response = client
+        .patch(format!("{}/v1/agents/{}", api_base(), agent_id))
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to update agent")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(())
+}
+
+async fn delete_document(client: &Client, api_key: &str, document_id: &str) -> Result<()> {
+    let response = client
+        .delete(format!(
+            "{}/v1/convai/knowledge-base/{}",
+            api_base(),
+            document_id
+        ))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to delete the old document")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_agent_ids() {
+        let response = json!({
+            "agents": [
+                { "id": "agent_1", "name": "Support", "type": "available" },
+                { "agent_id": "agent_2" },
+                { "type": "unknown" }
+            ],
+            "has_more": false
+        });
+        assert_eq!(page_agent_ids(&response), ["agent_1", "agent_2"]);
+        assert!(page_agent_ids(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_relinked_knowledge_base_swaps_only_the_old_document() {
+        let agent = json!({
+            "conversation_config": { "agent": { "prompt": { "knowledge_base": [
+                { "id": "kb_old", "name": "FAQ", "type": "file", "usage_mode": "auto" },
+                { "id": "kb_other", "name": "Pricing", "type": "url" }
+            ]}}}
+        });
+        let new_doc = NewDocument {
+            id: "kb_new",
+            name: "FAQ v2",
+        };
+        let documents = relinked_knowledge_base(&agent, "kb_old", &new_doc).unwrap();
+        assert_eq!(
+            documents[0],
+            json!({ "id": "kb_new", "name": "FAQ v2", "type": "text", "usage_mode": "auto" })
+        );
+        assert_eq!(documents[1]["id"], "kb_other");
+        assert!(relinked_knowledge_base(&agent, "kb_missing", &new_doc).is_none());
+    }
+
+    #[test]
+    fn test_new_document_is_text() {
+        assert_eq!(
+            new_document("FAQ", "new"),
+            json!({ "name": "FAQ", "type": "text", "content": "new" })
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Rate limit status (`limits`)
+//!
+//! Combines the plan quotas from the subscription endpoint with the
+//! concurrency limit and 429s the CLI has recorded locally.
+
+use crate::cli::LimitsArgs;
+use crate::client::{
+    api_base, api_error, create_http_client, load_rate_limits, read_json, reset_rate_limits,
+    RateLimitLog, SendWithRetry,
+};
+use crate::output::{is_json_mode, print_info, print_success};
+use crate::utils::{format_relative_time, unix_now};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use serde_json::{json, Value};
+
+/// Published concurrent request limits per tier: (tier, multilingual models, flash/turbo models)
+const TIER_CONCURRENCY: &[(&str, u32, u32)] = &[
+    ("free", 2, 4),
+    ("starter", 3, 6),
+    ("creator", 5, 10),
+    ("pro", 10, 20),
+    ("scale", 15, 30),
+    ("business", 15, 30),
+];
+
+pub async fn execute(args: LimitsArgs, api_key: &str) -> Result<()> {
+    if args.reset {
+        reset_rate_limits().context("Failed to clear the rate limit log")?;
+        print_success("Cleared the recorded throttling history");
+        return Ok(());
+    }
+
+    print_info("Fetching subscription limits...");
+    let client = create_http_client();
+    let response = client
+        .get(format!("{}/v1/user/subscription", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch subscription")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let subscription: Value = read_json(response).await?;
+
+    let tier = subscription
+        .get("tier")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let number = |key: &str| subscription.get(key).and_then(Value::as_u64);
+    let tier_concurrency = TIER_CONCURRENCY
+        .iter()
+        .find(|(name, _, _)| tier.to_lowercase().starts_with(name))
+        .map(|&(_, multilingual, flash)| (multilingual, flash));
+    let observed = load_rate_limits();
+    let now = unix_now();
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "tier": tier,
+                "character_count": number("character_count"),
+                "character_limit": number("character_limit"),
+                "next_character_count_reset_unix": number("next_character_count_reset_unix"),
+                "voice_slots_used": number("voice_slots_used"),
+                "voice_limit": number("voice_limit"),
+                "professional_voice_limit": number("professional_voice_limit"),
+                "concurrency_limit": tier_concurrency.map(|(m, _)| m),
+                "concurrency_limit_flash": tier_concurrency.map(|(_, f)| f),
+                "observed": observed,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Limit", "Value"]);
+    table.add_row(vec!["Tier".to_string(), tier.yellow().to_string()]);
+    if let (Some(used), Some(limit)) = (number("character_count"), number("character_limit")) {
+        let percent = if limit > 0 {
+            format!(" ({:.1}%)", used as f64 / limit as f64 * 100.0)
+        } else {
+            String::new()
+        };
+        table.add_row(vec![
+            "Characters".to_string(),
+            format!("{} / {}{}", used, limit, percent),
+        ]);
+    }
+    if let Some(reset) = number("next_character_count_reset_unix").filter(|&t| t > now) {
+        table.add_row(vec![
+            "Characters reset in".to_string(),
+            format_duration(reset - now),
+        ]);
+    }
+    if let Some(limit) = number("voice_limit") {
+        let used = number("voice_slots_used")
+            .map(|u| format!("{} / ", u))
+            .unwrap_or_default();
+        table.add_row(vec![
+            "Voice slots".to_string(),
+            format!("{}{}", used, limit),
+        ]);
+    }
+    if let Some(limit) = number("professional_voice_limit").filter(|&l| l > 0) {
+        table.add_row(vec!["Professional voices".to_string(), limit.to_string()]);
+    }
+    if let Some((multilingual, flash)) = tier_concurrency {
+        table.add_row(vec![
+            "Concurrent requests (plan)".to_string(),
+            format!("{} ({} for Flash/Turbo models)", multilingual, flash),
+        ]);
+    }
+    for row in observed_rows(&observed, now) {
+        table.add_row(row);
+    }
+    println!("{}", table);
+
+    if observed.throttled > 0 {
+        print_info(
+            "Batch commands back off automatically; use 'limits --reset' to clear the history",
+        );
+    }
+    Ok(())
+}
+
+/// Table rows for what the CLI has seen from the API itself
+fn observed_rows(observed: &RateLimitLog, now: u64) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    if let Some(max) = observed.max_concurrent {
+        let seen = observed
+            .last_seen
+            .map(|t| format!(", {}", format_relative_time(now.saturating_sub(t))))
+            .unwrap_or_default();
+        rows.push(vec![
+            "Concurrent requests (reported)".to_string(),
+            format!("{}{}", max, seen),
+        ]);
+    }
+    if let Some(peak) = observed.peak_concurrent {
+        rows.push(vec!["Peak concurrency seen".to_string(), peak.to_string()]);
+    }
+    let throttled = match observed.last_throttled {
+        Some(last) => format!(
+            "{} (last {}{})",
+            observed.throttled,
+            format_relative_time(now.saturating_sub(last)),
+            observed
+                .last_retry_after
+                .map(|s| format!(", retry after {}s", s))
+                .unwrap_or_default()
+        )
+        .red()
+        .to_string(),
+        None => "none".green().to_string(),
+    };
+    rows.push(vec!["Rate limited (429)".to_string(), throttled]);
+    rows
+}
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=3_599 => format!("{}m", secs / 60),
+        3_600..=86_399 => format!("{}h {}m", secs / 3_600, secs % 3_600 / 60),
+        _ => format!("{}d {}h", secs / 86_400, secs % 86_400 / 3_600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observed_rows() {
+        let log = RateLimitLog {
+            max_concurrent: Some(5),
+            throttled: 2,
+            last_throttled: Some(940),
+            last_retry_after: Some(30),
+            ..Default::default()
+        };
+        let rows = observed_rows(&log, 1000);
+        assert_eq!(rows[0], vec!["Concurrent requests (reported)", "5"]);
+        assert!(rows[1][1].contains("2 (last 1m ago, retry after 30s)"));
+        assert_eq!(format_duration(90_000), "1d 1h");
+    }
+}
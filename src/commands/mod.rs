@@ -1,12 +1,28 @@
 pub mod agent;
+pub mod agent_languages;
+pub mod agent_privacy;
+pub mod agent_transfer;
+pub mod api;
+pub mod audio;
 pub mod audio_native;
+pub mod backup;
+pub mod completions;
 pub mod config;
 pub mod conversation;
+pub mod converse_talk;
+pub mod daemon;
 pub mod dialogue;
 pub mod dubbing;
+pub mod dubbing_speakers;
 pub mod history;
+pub mod history_sync;
 pub mod isolation;
+pub mod ivr;
+pub mod jobs;
 pub mod knowledge;
+pub mod knowledge_crawl;
+pub mod knowledge_update;
+pub mod limits;
 pub mod models;
 pub mod music;
 pub mod phone;
@@ -15,10 +31,18 @@ pub mod pronunciation;
 pub mod rag;
 pub mod realtime_tts;
 pub mod samples;
+pub mod schedule;
+pub mod search;
 pub mod sfx;
 pub mod stt;
+pub mod stt_live;
+pub mod support_bundle;
 pub mod tools;
+pub mod tools_openapi;
 pub mod tts;
+pub mod tts_feed;
+pub mod tts_session;
+pub mod tts_sink;
 pub mod tts_stream;
 pub mod tts_timestamps;
 pub mod update;
@@ -26,7 +50,11 @@ pub mod usage;
 pub mod user;
 pub mod voice;
 pub mod voice_changer;
+pub mod voice_defaults;
 pub mod voice_design;
+pub mod voice_design_iterate;
 pub mod voice_library;
+pub mod voice_score;
+pub mod voice_verify;
 pub mod webhook;
 pub mod workspace;
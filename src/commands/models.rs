@@ -1,15 +1,46 @@
 use crate::cli::{ModelsArgs, ModelsCommands};
-use crate::output::{print_info, print_success};
-use anyhow::Result;
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::output::{is_json_mode, print_info, print_success};
+use crate::utils::get_input_text;
+use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use elevenlabs_rs::{endpoints::admin::models::GetModels, ElevenLabsClient};
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Plans as listed at https://elevenlabs.io/pricing with monthly billing, as
+/// of 2025: (tier, monthly price in USD, monthly credits). Update these when
+/// the pricing page changes; credits themselves come from the models endpoint.
+const PLANS: &[(&str, f64, u64)] = &[
+    ("free", 0.0, 10_000),
+    ("starter", 5.0, 30_000),
+    ("creator", 22.0, 100_000),
+    ("pro", 99.0, 500_000),
+    ("scale", 330.0, 2_000_000),
+    ("business", 1_320.0, 11_000_000),
+];
 
 pub async fn execute(args: ModelsArgs, api_key: &str) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
     match args.command {
         ModelsCommands::List => list_models(&client).await?,
         ModelsCommands::Rates => get_model_rates(&client).await?,
+        ModelsCommands::Cost {
+            model,
+            characters,
+            file,
+            tier,
+        } => {
+            let characters = match characters {
+                Some(n) => n,
+                None => get_input_text(None, file)?.chars().count() as u64,
+            };
+            estimate_cost(api_key, &model, characters, tier.as_deref()).await?
+        }
     }
     Ok(())
 }
@@ -17,8 +48,33 @@ pub async fn execute(args: ModelsArgs, api_key: &str) -> Result<()> {
 async fn list_models(client: &ElevenLabsClient) -> Result<()> {
     print_info("Fetching available models...");
 
-    let endpoint = GetModels;
-    let models = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let models = retry_sdk(Method::GET, || client.hit(GetModels))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let models: Vec<Value> = models
+            .iter()
+            .map(|model| {
+                json!({
+                    "model_id": model.model_id,
+                    "name": model.name,
+                    "description": model.description,
+                    "languages": model
+                        .languages
+                        .iter()
+                        .map(|l| l.language_id.as_str())
+                        .collect::<Vec<_>>(),
+                    "can_do_text_to_speech": model.can_do_text_to_speech,
+                    "can_do_voice_conversion": model.can_do_voice_conversion,
+                    "max_characters_request_subscribed_user": model.max_characters_request_subscribed_user,
+                    "character_cost_multiplier": model.model_rates.character_cost_multiplier,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&models)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Available Models:".bold().underline());
 
@@ -57,8 +113,24 @@ async fn list_models(client: &ElevenLabsClient) -> Result<()> {
 pub async fn get_model_rates(client: &ElevenLabsClient) -> Result<()> {
     print_info("Fetching model pricing/rates...");
 
-    let endpoint = GetModels;
-    let models = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let models = retry_sdk(Method::GET, || client.hit(GetModels))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let rates: Vec<Value> = models
+            .iter()
+            .map(|model| {
+                json!({
+                    "model_id": model.model_id,
+                    "name": model.name,
+                    "character_cost_multiplier": model.model_rates.character_cost_multiplier,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rates)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Model Pricing/Rates:".bold().underline());
 
@@ -79,3 +151,147 @@ pub async fn get_model_rates(client: &ElevenLabsClient) -> Result<()> {
 
     Ok(())
 }
+
+/// Credit and dollar estimate for synthesizing some text
+#[derive(Debug, Serialize)]
+struct CostEstimate {
+    model: String,
+    characters: u64,
+    character_cost_multiplier: f64,
+    credits: u64,
+    plans: Vec<PlanCost>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanCost {
+    tier: String,
+    monthly_price_usd: f64,
+    monthly_credits: u64,
+    usd_per_1k_credits: f64,
+    cost_usd: f64,
+    /// Share of the plan's monthly credits used
+    share_of_month: f64,
+}
+
+fn build_cost_estimate(
+    model: &str,
+    characters: u64,
+    multiplier: f64,
+    tier: Option<&str>,
+) -> CostEstimate {
+    let credits = (characters as f64 * multiplier).ceil() as u64;
+    let plans = PLANS
+        .iter()
+        .filter(|(name, _, _)| tier.is_none_or(|t| t == *name))
+        .map(|&(name, price, monthly_credits)| {
+            let per_credit = price / monthly_credits as f64;
+            PlanCost {
+                tier: name.to_string(),
+                monthly_price_usd: price,
+                monthly_credits,
+                usd_per_1k_credits: per_credit * 1_000.0,
+                cost_usd: per_credit * credits as f64,
+                share_of_month: credits as f64 / monthly_credits as f64,
+            }
+        })
+        .collect();
+    CostEstimate {
+        model: model.to_string(),
+        characters,
+        character_cost_multiplier: multiplier,
+        credits,
+        plans,
+    }
+}
+
+/// Character cost multiplier of a model, from the models endpoint
+async fn model_cost_multiplier(api_key: &str, model: &str) -> Result<f64> {
+    let response = create_http_client()
+        .get(format!("{}/v1/models", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch models")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let models: Vec<Value> = read_json(response).await?;
+    let found = models
+        .iter()
+        .find(|m| m["model_id"].as_str() == Some(model))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown model '{}'. Use 'models list' to see available models",
+                model
+            )
+        })?;
+    Ok(found["model_rates"]["character_cost_multiplier"]
+        .as_f64()
+        .unwrap_or(1.0))
+}
+
+async fn estimate_cost(
+    api_key: &str,
+    model: &str,
+    characters: u64,
+    tier: Option<&str>,
+) -> Result<()> {
+    let multiplier = model_cost_multiplier(api_key, model).await?;
+    let estimate = build_cost_estimate(model, characters, multiplier, tier);
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&estimate)?);
+        return Ok(());
+    }
+
+    print_info(&format!(
+        "{} characters with {} at {:.2}x = {} credits",
+        characters,
+        model.cyan(),
+        multiplier,
+        estimate.credits.to_string().green()
+    ));
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Plan",
+        "Credits/Month",
+        "USD per 1K Credits",
+        "Est. Cost",
+        "Share of Month",
+    ]);
+    for plan in &estimate.plans {
+        table.add_row(vec![
+            plan.tier.clone(),
+            plan.monthly_credits.to_string(),
+            format!("${:.3}", plan.usd_per_1k_credits),
+            format!("${:.2}", plan.cost_usd),
+            format!("{:.1}%", plan.share_of_month * 100.0),
+        ]);
+    }
+    println!("{}", table);
+    print_info(
+        "Costs use each plan's included credits at the monthly list price from \
+         https://elevenlabs.io/pricing (2025); overage and annual billing differ",
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_cost_estimate() {
+        let estimate = build_cost_estimate("eleven_turbo_v2_5", 250_001, 0.5, None);
+        assert_eq!(estimate.credits, 125_001);
+        assert_eq!(estimate.plans.len(), PLANS.len());
+
+        let creator = build_cost_estimate("eleven_turbo_v2_5", 200_000, 0.5, Some("creator"));
+        assert_eq!(creator.plans.len(), 1);
+        let plan = &creator.plans[0];
+        assert!((plan.usd_per_1k_credits - 0.22).abs() < 1e-9);
+        assert!((plan.cost_usd - 22.0).abs() < 1e-9);
+        assert!((plan.share_of_month - 1.0).abs() < 1e-9);
+    }
+}
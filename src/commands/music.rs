@@ -4,14 +4,15 @@
 //! API Reference: https://elevenlabs.io/docs/api-reference/music
 
 use crate::cli::{MusicArgs, MusicCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
-use crate::utils::{confirm_overwrite, write_bytes_to_file};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::config::Config;
+use crate::output::{is_json_mode, print_info, print_success, symbol, Progress};
+use crate::utils::{confirm_overwrite, default_output_path, write_bytes_to_file};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::path::Path;
 
@@ -25,16 +26,34 @@ pub async fn execute(args: MusicArgs, api_key: &str, assume_yes: bool) -> Result
             duration,
             influence,
         } => generate_music(&client, api_key, &prompt, output, duration, influence).await,
-        MusicCommands::List { limit } => list_music(&client, api_key, limit).await,
+        MusicCommands::List {
+            limit,
+            search,
+            tag,
+            favorites,
+        } => {
+            let filter = MusicFilter {
+                search,
+                tags: tag,
+                favorites_only: favorites,
+            };
+            list_music(&client, api_key, limit, &filter).await
+        }
         MusicCommands::Get { music_id } => get_music(&client, api_key, &music_id).await,
         MusicCommands::Download { music_id, output } => {
             download_music(&client, api_key, &music_id, output, assume_yes).await
         }
         MusicCommands::Delete { music_id } => delete_music(&client, api_key, &music_id).await,
+        MusicCommands::Favorite { music_id, remove } => set_favorite(&music_id, remove),
+        MusicCommands::Tag {
+            music_id,
+            add,
+            remove,
+        } => update_tags(&music_id, &add, &remove),
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct MusicInfo {
     music_id: String,
     #[serde(default)]
@@ -47,9 +66,59 @@ struct MusicInfo {
     status: Option<String>,
     #[serde(default)]
     prompt: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Filters applied by `music list`
+#[derive(Debug, Default)]
+struct MusicFilter {
+    search: Option<String>,
+    tags: Vec<String>,
+    favorites_only: bool,
+}
+
+impl MusicFilter {
+    fn is_active(&self) -> bool {
+        self.search.is_some() || !self.tags.is_empty() || self.favorites_only
+    }
+
+    /// Check a track against the filters; `tags` are the API and local tags combined
+    fn matches(&self, music: &MusicInfo, tags: &[String], favorite: bool) -> bool {
+        if self.favorites_only && !favorite {
+            return false;
+        }
+        if let Some(search) = &self.search {
+            let needle = search.to_lowercase();
+            let found = [&music.title, &music.prompt].iter().any(|field| {
+                field
+                    .as_deref()
+                    .unwrap_or("")
+                    .to_lowercase()
+                    .contains(&needle)
+            });
+            if !found {
+                return false;
+            }
+        }
+        self.tags
+            .iter()
+            .all(|wanted| tags.iter().any(|t| t.eq_ignore_ascii_case(wanted)))
+    }
+}
+
+/// API tags plus local tags for a track, without duplicates
+fn combined_tags(music: &MusicInfo, config: &Config) -> Vec<String> {
+    let mut tags = music.tags.clone();
+    for tag in config.music_tags.get(&music.music_id).into_iter().flatten() {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 struct MusicListResponse {
     music: Vec<MusicInfo>,
     #[allow(dead_code)]
@@ -103,21 +172,21 @@ async fn generate_music(
         body["audio_influence"] = json!(i);
     }
 
+    let progress = Progress::spinner("Generating music");
     let response = client
-        .post("https://api.elevenlabs.io/v1/music")
+        .post(format!("{}/v1/music", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to generate music")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: GenerateMusicResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: GenerateMusicResponse = read_json(response).await?;
+    progress.finish();
 
     print_success("Music generation started!");
     print_info(&format!("Music ID: {}", result.music_id.yellow()));
@@ -131,7 +200,10 @@ async fn generate_music(
         let audio_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, audio)
             .context("Failed to decode audio")?;
 
-        let output_path = output.unwrap_or_else(|| format!("music_{}.mp3", result.music_id));
+        let output_path = match output {
+            Some(output) => output,
+            None => default_output_path(&format!("music_{}.mp3", result.music_id), None)?,
+        };
         let path = Path::new(&output_path);
         std::fs::write(path, &audio_bytes)?;
         print_success(&format!("Audio saved -> {}", output_path.green()));
@@ -144,10 +216,17 @@ async fn generate_music(
     Ok(())
 }
 
-async fn list_music(client: &Client, api_key: &str, limit: Option<u32>) -> Result<()> {
+async fn list_music(
+    client: &Client,
+    api_key: &str,
+    limit: Option<u32>,
+    filter: &MusicFilter,
+) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+
     print_info("Fetching generated music...");
 
-    let mut url = "https://api.elevenlabs.io/v1/music".to_string();
+    let mut url = format!("{}/v1/music", api_base());
 
     if let Some(l) = limit {
         url.push_str(&format!("?page_size={}", l));
@@ -156,27 +235,56 @@ async fn list_music(client: &Client, api_key: &str, limit: Option<u32>) -> Resul
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch music")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let music_response: MusicListResponse =
-        response.json().await.context("Failed to parse response")?;
+    let music_response: MusicListResponse = read_json(response).await?;
+
+    let tracks: Vec<(&MusicInfo, Vec<String>, bool)> = music_response
+        .music
+        .iter()
+        .map(|music| {
+            let favorite = config.music_favorites.contains(&music.music_id);
+            (music, combined_tags(music, &config), favorite)
+        })
+        .filter(|(music, tags, favorite)| filter.matches(music, tags, *favorite))
+        .collect();
+
+    if is_json_mode() {
+        let tracks = tracks
+            .iter()
+            .map(|(music, tags, favorite)| {
+                let mut track = serde_json::to_value(music)?;
+                track["tags"] = json!(tags);
+                track["favorite"] = json!(favorite);
+                Ok(track)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&tracks)?);
+        return Ok(());
+    }
 
-    if music_response.music.is_empty() {
-        print_info("No music found");
+    if tracks.is_empty() {
+        if filter.is_active() {
+            print_info("No music matches the given filters");
+        } else {
+            print_info("No music found");
+        }
         return Ok(());
     }
 
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Title", "Duration", "Status", "Created"]);
+    table.set_header(vec![
+        "", "ID", "Title", "Tags", "Duration", "Status", "Created",
+    ]);
 
-    for music in &music_response.music {
+    for (music, tags, favorite) in &tracks {
+        let marker = if *favorite { symbol("★", "*") } else { "" };
         let title = music.title.as_deref().unwrap_or("Untitled");
         let duration = music
             .duration_seconds
@@ -186,8 +294,10 @@ async fn list_music(client: &Client, api_key: &str, limit: Option<u32>) -> Resul
         let created = music.created_at.as_deref().unwrap_or("-");
 
         table.add_row(vec![
+            marker.yellow(),
             music.music_id.yellow(),
             title.cyan(),
+            tags.join(", ").into(),
             duration.into(),
             status.into(),
             created.into(),
@@ -195,10 +305,7 @@ async fn list_music(client: &Client, api_key: &str, limit: Option<u32>) -> Resul
     }
 
     println!("{}", table);
-    print_success(&format!(
-        "Found {} music tracks",
-        music_response.music.len()
-    ));
+    print_success(&format!("Found {} music tracks", tracks.len()));
 
     Ok(())
 }
@@ -206,19 +313,27 @@ async fn list_music(client: &Client, api_key: &str, limit: Option<u32>) -> Resul
 async fn get_music(client: &Client, api_key: &str, music_id: &str) -> Result<()> {
     print_info(&format!("Fetching music '{}'...", music_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/music/{}", music_id);
+    let url = format!("{}/v1/music/{}", api_base(), music_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let music: MusicInfo = response.json().await?;
+    let music: MusicInfo = read_json(response).await?;
+    let config = Config::load().unwrap_or_default();
+    let tags = combined_tags(&music, &config);
+
+    if is_json_mode() {
+        let mut music = serde_json::to_value(&music)?;
+        music["tags"] = json!(tags);
+        println!("{}", serde_json::to_string_pretty(&music)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -239,6 +354,12 @@ async fn get_music(client: &Client, api_key: &str, music_id: &str) -> Result<()>
     if let Some(ref created) = music.created_at {
         table.add_row(vec!["Created", created]);
     }
+    if !tags.is_empty() {
+        table.add_row(vec!["Tags", &tags.join(", ")]);
+    }
+    if config.music_favorites.contains(&music.music_id) {
+        table.add_row(vec!["Favorite", "yes"]);
+    }
 
     println!("{}", table);
     Ok(())
@@ -253,16 +374,15 @@ async fn download_music(
 ) -> Result<()> {
     print_info(&format!("Downloading music '{}'...", music_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/music/{}/audio", music_id);
+    let url = format!("{}/v1/music/{}/audio", api_base(), music_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     let audio = response.bytes().await?;
@@ -285,21 +405,139 @@ async fn download_music(
 async fn delete_music(client: &Client, api_key: &str, music_id: &str) -> Result<()> {
     print_info(&format!("Deleting music '{}'...", music_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/music/{}", music_id);
+    let url = format!("{}/v1/music/{}", api_base(), music_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
         "Music '{}' deleted successfully",
         music_id.green()
     ));
+
+    // Drop local metadata for the deleted track
+    let config = Config::load().unwrap_or_default();
+    let had_favorite = config.music_favorites.iter().any(|id| id == music_id);
+    if had_favorite || config.music_tags.contains_key(music_id) {
+        Config::update(|config| {
+            config.music_favorites.retain(|id| id != music_id);
+            config.music_tags.remove(music_id);
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+fn set_favorite(music_id: &str, remove: bool) -> Result<()> {
+    let changed = Config::update(|config| {
+        let is_favorite = config.music_favorites.iter().any(|id| id == music_id);
+        if remove && is_favorite {
+            config.music_favorites.retain(|id| id != music_id);
+        } else if !remove && !is_favorite {
+            config.music_favorites.push(music_id.to_string());
+        }
+        Ok(remove == is_favorite)
+    })?;
+
+    match (remove, changed) {
+        (true, true) => print_success(&format!("Removed '{}' from favorites", music_id.green())),
+        (true, false) => print_info(&format!("'{}' is not a favorite", music_id)),
+        (false, true) => print_success(&format!("Added '{}' to favorites", music_id.green())),
+        (false, false) => print_info(&format!("'{}' is already a favorite", music_id)),
+    }
+    Ok(())
+}
+
+fn update_tags(music_id: &str, add: &[String], remove: &[String]) -> Result<()> {
+    if add.is_empty() && remove.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Specify at least one --add or --remove tag"
+        ));
+    }
+
+    let summary = Config::update(|config| {
+        let tags = config.music_tags.entry(music_id.to_string()).or_default();
+        tags.retain(|t| !remove.iter().any(|r| r.eq_ignore_ascii_case(t)));
+        for tag in add {
+            let tag = tag.trim();
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+
+        let summary = if tags.is_empty() {
+            "(none)".to_string()
+        } else {
+            tags.join(", ")
+        };
+        if tags.is_empty() {
+            config.music_tags.remove(music_id);
+        }
+        Ok(summary)
+    })?;
+
+    print_success(&format!("Tags for '{}': {}", music_id.green(), summary));
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str, title: &str, prompt: &str) -> MusicInfo {
+        MusicInfo {
+            music_id: id.to_string(),
+            title: Some(title.to_string()),
+            duration_seconds: None,
+            created_at: None,
+            status: None,
+            prompt: Some(prompt.to_string()),
+            tags: vec!["lofi".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_music_filter_matches() {
+        let music = track("m1", "Rainy Night", "mellow lofi beat");
+        let tags = vec!["lofi".to_string(), "chill".to_string()];
+
+        assert!(MusicFilter::default().matches(&music, &tags, false));
+
+        let search = MusicFilter {
+            search: Some("LOFI".to_string()),
+            ..Default::default()
+        };
+        assert!(search.matches(&music, &tags, false));
+
+        let tagged = MusicFilter {
+            tags: vec!["Chill".to_string(), "lofi".to_string()],
+            ..Default::default()
+        };
+        assert!(tagged.matches(&music, &tags, false));
+        assert!(!tagged.matches(&music, &tags[..1], false));
+
+        let favorites = MusicFilter {
+            favorites_only: true,
+            ..Default::default()
+        };
+        assert!(!favorites.matches(&music, &tags, false));
+        assert!(favorites.matches(&music, &tags, true));
+    }
+
+    #[test]
+    fn test_combined_tags_merges_local_tags() {
+        let music = track("m1", "Rainy Night", "mellow");
+        let mut config = Config::default();
+        config.music_tags.insert(
+            "m1".to_string(),
+            vec!["LOFI".to_string(), "chill".to_string()],
+        );
+        assert_eq!(combined_tags(&music, &config), vec!["lofi", "chill"]);
+    }
+}
@@ -4,13 +4,13 @@
 //! API Reference: https://elevenlabs.io/docs/api-reference/phone-numbers
 
 use crate::cli::{PhoneArgs, PhoneCommands, ProviderType};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success, print_warning};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 pub async fn execute(args: PhoneArgs, api_key: &str, assume_yes: bool) -> Result<()> {
@@ -64,7 +64,7 @@ pub async fn execute(args: PhoneArgs, api_key: &str, assume_yes: bool) -> Result
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct PhoneNumberInfo {
     phone_number_id: String,
     phone_number: String,
@@ -80,7 +80,7 @@ struct PhoneNumberInfo {
     created_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct PhoneNumbersListResponse {
     phone_numbers: Vec<PhoneNumberInfo>,
 }
@@ -95,7 +95,7 @@ struct ImportPhoneResponse {
 async fn list_phone_numbers(client: &Client, api_key: &str, agent_id: Option<&str>) -> Result<()> {
     print_info("Fetching phone numbers...");
 
-    let mut url = "https://api.elevenlabs.io/v1/convai/phone-numbers".to_string();
+    let mut url = format!("{}/v1/convai/phone-numbers", api_base());
 
     if let Some(aid) = agent_id {
         url.push_str(&format!("?agent_id={}", aid));
@@ -104,17 +104,23 @@ async fn list_phone_numbers(client: &Client, api_key: &str, agent_id: Option<&st
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch phone numbers")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let phones_response: PhoneNumbersListResponse =
-        response.json().await.context("Failed to parse response")?;
+    let phones_response: PhoneNumbersListResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&phones_response.phone_numbers)?
+        );
+        return Ok(());
+    }
 
     if phones_response.phone_numbers.is_empty() {
         print_info("No phone numbers found");
@@ -161,22 +167,23 @@ async fn list_phone_numbers(client: &Client, api_key: &str, agent_id: Option<&st
 async fn get_phone_number(client: &Client, api_key: &str, phone_id: &str) -> Result<()> {
     print_info(&format!("Fetching phone number '{}'...", phone_id.cyan()));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}",
-        phone_id
-    );
+    let url = format!("{}/v1/convai/phone-numbers/{}", api_base(), phone_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let phone: PhoneNumberInfo = response.json().await?;
+    let phone: PhoneNumberInfo = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&phone)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -251,19 +258,18 @@ async fn import_phone_number(
     }
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/phone-numbers")
+        .post(format!("{}/v1/convai/phone-numbers", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to import phone number")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: ImportPhoneResponse = response.json().await.context("Failed to parse response")?;
+    let result: ImportPhoneResponse = read_json(response).await?;
     print_success("Phone number imported successfully!");
     print_info(&format!(
         "Phone Number ID: {}",
@@ -297,20 +303,16 @@ async fn update_phone_number(
         ));
     }
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}",
-        phone_id
-    );
+    let url = format!("{}/v1/convai/phone-numbers/{}", api_base(), phone_id);
     let response = client
         .patch(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success("Phone number updated successfully!");
@@ -329,10 +331,7 @@ async fn delete_phone_number(
     ));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -340,19 +339,15 @@ async fn delete_phone_number(
         }
     }
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}",
-        phone_id
-    );
+    let url = format!("{}/v1/convai/phone-numbers/{}", api_base(), phone_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -378,7 +373,8 @@ async fn test_call(
     });
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}/test-call",
+        "{}/v1/convai/phone-numbers/{}/test-call",
+        api_base(),
         phone_id
     );
 
@@ -386,13 +382,12 @@ async fn test_call(
         .post(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to initiate test call")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success("Test call initiated successfully!");
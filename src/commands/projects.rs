@@ -3,28 +3,43 @@
 //! This module implements the Projects API for managing audio projects.
 //! API Reference: https://elevenlabs.io/docs/api-reference/projects
 
-use crate::cli::{ProjectsArgs, ProjectsCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
-use crate::utils::{confirm_overwrite, write_bytes_to_file};
+use crate::cli::{JobKind, ProjectsArgs, ProjectsCommands, WaitArgs};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success, Progress};
+use crate::utils::{confirm_overwrite, format_relative_time, unix_now, write_bytes_to_file};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
+use futures::StreamExt;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
+
+/// Projects whose chapters and snapshots `list` fetches at the same time
+const DETAIL_CONCURRENCY: usize = 4;
 
 pub async fn execute(args: ProjectsArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     let client = create_http_client();
 
     match args.command {
         ProjectsCommands::List { limit } => list_projects(&client, api_key, limit).await,
-        ProjectsCommands::Get { project_id } => get_project(&client, api_key, &project_id).await,
+        ProjectsCommands::Get {
+            project_id,
+            watch,
+            interval,
+        } => {
+            if watch {
+                watch_project(&client, api_key, &project_id, interval).await
+            } else {
+                get_project(&client, api_key, &project_id).await
+            }
+        }
         ProjectsCommands::Delete { project_id } => {
             delete_project(&client, api_key, &project_id).await
         }
-        ProjectsCommands::Convert { project_id } => {
-            convert_project(&client, api_key, &project_id).await
+        ProjectsCommands::Convert { project_id, wait } => {
+            convert_project(&client, api_key, &project_id, &wait).await
         }
         ProjectsCommands::Snapshots { project_id } => {
             list_snapshots(&client, api_key, &project_id).await
@@ -35,20 +50,111 @@ pub async fn execute(args: ProjectsArgs, api_key: &str, assume_yes: bool) -> Res
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct ProjectInfo {
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct ProjectInfo {
     project_id: String,
-    name: String,
+    pub(crate) name: String,
     #[serde(default)]
     state: Option<String>,
     #[serde(default)]
     created_at: Option<String>,
     #[serde(default)]
+    create_date_unix: Option<u64>,
+    #[serde(default)]
     voice_id: Option<String>,
     #[serde(default)]
+    default_paragraph_voice_id: Option<String>,
+    #[serde(default)]
+    default_title_voice_id: Option<String>,
+    #[serde(default)]
     model_id: Option<String>,
     #[serde(default)]
+    default_model_id: Option<String>,
+    #[serde(default)]
     duration: Option<f64>,
+    #[serde(default)]
+    last_conversion_date_unix: Option<u64>,
+    /// Only returned when fetching a single project
+    #[serde(default)]
+    chapters: Vec<ChapterInfo>,
+}
+
+impl ProjectInfo {
+    fn default_voice(&self) -> Option<&str> {
+        self.default_paragraph_voice_id
+            .as_deref()
+            .or(self.default_title_voice_id.as_deref())
+            .or(self.voice_id.as_deref())
+    }
+
+    fn model(&self) -> Option<&str> {
+        self.default_model_id
+            .as_deref()
+            .or(self.model_id.as_deref())
+    }
+
+    fn converting_chapters(&self) -> usize {
+        self.chapters.iter().filter(|c| c.is_converting()).count()
+    }
+
+    /// Whether the project or any of its chapters is still being converted
+    pub(crate) fn is_converting(&self) -> bool {
+        matches!(self.state.as_deref(), Some("converting" | "in_queue"))
+            || self.converting_chapters() > 0
+    }
+
+    /// State as shown to users: the API calls a finished project "default"
+    pub(crate) fn display_state(&self) -> String {
+        if self.converting_chapters() > 0 {
+            return "converting".to_string();
+        }
+        match self.state.as_deref() {
+            Some("default") => "ready".to_string(),
+            Some("in_queue") => "queued".to_string(),
+            Some(state) => state.to_string(),
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// Average conversion progress over the chapters, 0-100
+    pub(crate) fn conversion_percent(&self) -> f64 {
+        if self.chapters.is_empty() {
+            return if self.is_converting() { 0.0 } else { 100.0 };
+        }
+        let total: f64 = self.chapters.iter().map(ChapterInfo::percent).sum();
+        total / self.chapters.len() as f64
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ChapterInfo {
+    chapter_id: String,
+    name: String,
+    #[serde(default)]
+    state: Option<String>,
+    /// Fraction converted while the chapter is converting
+    #[serde(default)]
+    conversion_progress: Option<f64>,
+    #[serde(default)]
+    last_conversion_date_unix: Option<u64>,
+}
+
+impl ChapterInfo {
+    fn is_converting(&self) -> bool {
+        self.state.as_deref() == Some("converting")
+    }
+
+    fn percent(&self) -> f64 {
+        if !self.is_converting() {
+            return 100.0;
+        }
+        match self.conversion_progress {
+            // Reported as a fraction, but accept percentages too
+            Some(p) if p > 1.0 => p.min(100.0),
+            Some(p) => p.max(0.0) * 100.0,
+            None => 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,14 +162,17 @@ struct ProjectsListResponse {
     projects: Vec<ProjectInfo>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct SnapshotInfo {
+    #[serde(alias = "project_snapshot_id")]
     snapshot_id: String,
     #[serde(default)]
     status: Option<String>,
     #[serde(default)]
     created_at: Option<String>,
     #[serde(default)]
+    created_at_unix: Option<u64>,
+    #[serde(default)]
     duration: Option<f64>,
 }
 
@@ -72,10 +181,93 @@ struct SnapshotsResponse {
     snapshots: Vec<SnapshotInfo>,
 }
 
+/// One row of `projects list`
+#[derive(Debug, Serialize)]
+struct ProjectSummary {
+    project_id: String,
+    name: String,
+    state: String,
+    chapters: Option<usize>,
+    converting_chapters: Option<usize>,
+    default_voice_id: Option<String>,
+    last_snapshot_unix: Option<u64>,
+}
+
+fn ago(unix_secs: u64) -> String {
+    format_relative_time(unix_now().saturating_sub(unix_secs))
+}
+
+pub(crate) async fn fetch_project(
+    client: &Client,
+    api_key: &str,
+    project_id: &str,
+) -> Result<ProjectInfo> {
+    let url = format!("{}/v1/projects/{}", api_base(), project_id);
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch project")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    read_json(response).await.context("Failed to parse project")
+}
+
+async fn fetch_snapshots(
+    client: &Client,
+    api_key: &str,
+    project_id: &str,
+) -> Result<Vec<SnapshotInfo>> {
+    let url = format!("{}/v1/projects/{}/snapshots", api_base(), project_id);
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch snapshots")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let snapshots: SnapshotsResponse = read_json(response)
+        .await
+        .context("Failed to parse snapshots")?;
+    Ok(snapshots.snapshots)
+}
+
+/// List entry with the chapter counts and last snapshot, which the list
+/// endpoint does not return; lookups that fail leave those columns empty
+async fn summarize_project(client: &Client, api_key: &str, project: ProjectInfo) -> ProjectSummary {
+    let detail = fetch_project(client, api_key, &project.project_id)
+        .await
+        .ok();
+    let last_snapshot_unix = fetch_snapshots(client, api_key, &project.project_id)
+        .await
+        .ok()
+        .and_then(|snapshots| snapshots.iter().filter_map(|s| s.created_at_unix).max());
+    let project = detail.unwrap_or(project);
+    let has_chapters = !project.chapters.is_empty();
+
+    ProjectSummary {
+        state: project.display_state(),
+        chapters: has_chapters.then_some(project.chapters.len()),
+        converting_chapters: has_chapters.then(|| project.converting_chapters()),
+        default_voice_id: project.default_voice().map(str::to_string),
+        last_snapshot_unix,
+        project_id: project.project_id,
+        name: project.name,
+    }
+}
+
 async fn list_projects(client: &Client, api_key: &str, limit: Option<u32>) -> Result<()> {
     print_info("Fetching projects...");
 
-    let mut url = "https://api.elevenlabs.io/v1/projects".to_string();
+    let mut url = format!("{}/v1/projects", api_base());
 
     if let Some(l) = limit {
         url.push_str(&format!("?page_size={}", l));
@@ -84,45 +276,71 @@ async fn list_projects(client: &Client, api_key: &str, limit: Option<u32>) -> Re
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch projects")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let projects_response: ProjectsListResponse =
-        response.json().await.context("Failed to parse response")?;
+    let projects_response: ProjectsListResponse = read_json(response).await?;
+
+    let summaries: Vec<ProjectSummary> = futures::stream::iter(projects_response.projects)
+        .map(|project| summarize_project(client, api_key, project))
+        .buffered(DETAIL_CONCURRENCY)
+        .collect()
+        .await;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
 
-    if projects_response.projects.is_empty() {
+    if summaries.is_empty() {
         print_info("No projects found");
         return Ok(());
     }
 
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Name", "State", "Duration"]);
-
-    for project in &projects_response.projects {
-        let state = project.state.as_deref().unwrap_or("unknown");
-        let duration = project
-            .duration
-            .map(|d| format!("{:.1}s", d))
-            .unwrap_or_default();
+    table.set_header(vec![
+        "ID",
+        "Name",
+        "State",
+        "Chapters",
+        "Default Voice",
+        "Last Snapshot",
+    ]);
+
+    for summary in &summaries {
+        let state = match summary.state.as_str() {
+            "ready" => summary.state.green(),
+            "converting" | "queued" => summary.state.yellow(),
+            _ => summary.state.normal(),
+        };
+        let chapters = match (summary.chapters, summary.converting_chapters) {
+            (Some(total), Some(converting)) if converting > 0 => {
+                format!("{} ({} converting)", total, converting)
+            }
+            (Some(total), _) => total.to_string(),
+            (None, _) => "-".to_string(),
+        };
         table.add_row(vec![
-            project.project_id.yellow(),
-            project.name.cyan(),
-            state.into(),
-            duration.into(),
+            summary.project_id.yellow(),
+            summary.name.cyan(),
+            state,
+            chapters.normal(),
+            summary.default_voice_id.as_deref().unwrap_or("-").normal(),
+            summary
+                .last_snapshot_unix
+                .map(ago)
+                .unwrap_or_else(|| "-".to_string())
+                .normal(),
         ]);
     }
 
     println!("{}", table);
-    print_success(&format!(
-        "Found {} projects",
-        projects_response.projects.len()
-    ));
+    print_success(&format!("Found {} projects", summaries.len()));
 
     Ok(())
 }
@@ -130,58 +348,131 @@ async fn list_projects(client: &Client, api_key: &str, limit: Option<u32>) -> Re
 async fn get_project(client: &Client, api_key: &str, project_id: &str) -> Result<()> {
     print_info(&format!("Fetching project '{}'...", project_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/projects/{}", project_id);
-    let response = client
-        .get(&url)
-        .header("xi-api-key", api_key)
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+    let project = fetch_project(client, api_key, project_id).await?;
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&project)?);
+        return Ok(());
     }
+    print_project(&project);
+    Ok(())
+}
 
-    let project: ProjectInfo = response.json().await?;
-
+fn print_project(project: &ProjectInfo) {
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
     table.add_row(vec!["ID", &project.project_id.yellow()]);
     table.add_row(vec!["Name", &project.name.cyan()]);
+    table.add_row(vec!["State", &project.display_state()]);
 
-    if let Some(ref state) = project.state {
-        table.add_row(vec!["State", state]);
-    }
     if let Some(ref created) = project.created_at {
         table.add_row(vec!["Created", created]);
+    } else if let Some(created) = project.create_date_unix {
+        table.add_row(vec!["Created", &ago(created)]);
     }
-    if let Some(ref voice) = project.voice_id {
-        table.add_row(vec!["Voice ID", voice]);
+    if let Some(voice) = project.default_voice() {
+        table.add_row(vec!["Default Voice", voice]);
     }
-    if let Some(ref model) = project.model_id {
+    if let Some(model) = project.model() {
         table.add_row(vec!["Model ID", model]);
     }
     if let Some(duration) = project.duration {
         table.add_row(vec!["Duration", &format!("{:.1}s", duration)]);
     }
-
+    if let Some(converted) = project.last_conversion_date_unix {
+        table.add_row(vec!["Last Converted", &ago(converted)]);
+    }
     println!("{}", table);
+
+    if project.chapters.is_empty() {
+        return;
+    }
+    let mut chapters = Table::new();
+    chapters.set_header(vec![
+        "Chapter ID",
+        "Name",
+        "State",
+        "Progress",
+        "Last Converted",
+    ]);
+    for chapter in &project.chapters {
+        let (state, progress) = if chapter.is_converting() {
+            ("converting".yellow(), format!("{:.0}%", chapter.percent()))
+        } else {
+            ("ready".green(), "-".to_string())
+        };
+        chapters.add_row(vec![
+            chapter.chapter_id.yellow(),
+            chapter.name.normal(),
+            state,
+            progress.normal(),
+            chapter
+                .last_conversion_date_unix
+                .map(ago)
+                .unwrap_or_else(|| "-".to_string())
+                .normal(),
+        ]);
+    }
+    println!("{}", chapters);
+}
+
+/// Poll a project until its conversion finishes, then show its details
+async fn watch_project(
+    client: &Client,
+    api_key: &str,
+    project_id: &str,
+    interval: u64,
+) -> Result<()> {
+    if interval == 0 {
+        return Err(anyhow::anyhow!("--interval must be at least 1 second"));
+    }
+
+    let mut project = fetch_project(client, api_key, project_id).await?;
+    if project.is_converting() {
+        let progress = Progress::bar(100, &format!("Converting '{}'", project.name));
+        let mut shown = 0;
+        while project.is_converting() {
+            let percent = project.conversion_percent().floor() as u64;
+            if percent > shown {
+                progress.inc(percent - shown);
+                shown = percent;
+            }
+            if !project.chapters.is_empty() {
+                progress.set_message(&format!(
+                    "Converting '{}' ({} of {} chapters done)",
+                    project.name,
+                    project.chapters.len() - project.converting_chapters(),
+                    project.chapters.len()
+                ));
+            }
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            project = fetch_project(client, api_key, project_id).await?;
+        }
+        progress.finish();
+        print_success(&format!("Project '{}' converted", project.name.green()));
+    } else {
+        print_info("Project is not converting");
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&project)?);
+    } else {
+        print_project(&project);
+    }
     Ok(())
 }
 
 async fn delete_project(client: &Client, api_key: &str, project_id: &str) -> Result<()> {
     print_info(&format!("Deleting project '{}'...", project_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/projects/{}", project_id);
+    let url = format!("{}/v1/projects/{}", api_base(), project_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -191,29 +482,32 @@ async fn delete_project(client: &Client, api_key: &str, project_id: &str) -> Res
     Ok(())
 }
 
-async fn convert_project(client: &Client, api_key: &str, project_id: &str) -> Result<()> {
+async fn convert_project(
+    client: &Client,
+    api_key: &str,
+    project_id: &str,
+    wait: &WaitArgs,
+) -> Result<()> {
     print_info(&format!("Converting project '{}'...", project_id.cyan()));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/projects/{}/convert",
-        project_id
-    );
+    let url = format!("{}/v1/projects/{}/convert", api_base(), project_id);
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
+    let job = crate::journal::record(api_key, JobKind::Project, project_id, None, "conversion");
+
     print_success(&format!(
         "Project '{}' conversion started",
         project_id.green()
     ));
-    Ok(())
+    super::jobs::wait_if_requested(api_key, job, wait).await
 }
 
 async fn list_snapshots(client: &Client, api_key: &str, project_id: &str) -> Result<()> {
@@ -222,24 +516,14 @@ async fn list_snapshots(client: &Client, api_key: &str, project_id: &str) -> Res
         project_id.cyan()
     ));
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/projects/{}/snapshots",
-        project_id
-    );
-    let response = client
-        .get(&url)
-        .header("xi-api-key", api_key)
-        .send()
-        .await?;
+    let snapshots = fetch_snapshots(client, api_key, project_id).await?;
 
-    if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&snapshots)?);
+        return Ok(());
     }
 
-    let snapshots_response: SnapshotsResponse = response.json().await?;
-
-    if snapshots_response.snapshots.is_empty() {
+    if snapshots.is_empty() {
         print_info("No snapshots found");
         return Ok(());
     }
@@ -247,9 +531,13 @@ async fn list_snapshots(client: &Client, api_key: &str, project_id: &str) -> Res
     let mut table = Table::new();
     table.set_header(vec!["Snapshot ID", "Status", "Created", "Duration"]);
 
-    for snapshot in &snapshots_response.snapshots {
+    for snapshot in &snapshots {
         let status = snapshot.status.as_deref().unwrap_or("unknown");
-        let created = snapshot.created_at.as_deref().unwrap_or("-");
+        let created = match (&snapshot.created_at, snapshot.created_at_unix) {
+            (Some(created), _) => created.clone(),
+            (None, Some(created)) => ago(created),
+            (None, None) => "-".to_string(),
+        };
         let duration = snapshot
             .duration
             .map(|d| format!("{:.1}s", d))
@@ -264,10 +552,7 @@ async fn list_snapshots(client: &Client, api_key: &str, project_id: &str) -> Res
     }
 
     println!("{}", table);
-    print_success(&format!(
-        "Found {} snapshots",
-        snapshots_response.snapshots.len()
-    ));
+    print_success(&format!("Found {} snapshots", snapshots.len()));
 
     Ok(())
 }
@@ -284,16 +569,15 @@ async fn download_project_audio(
         project_id.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/projects/{}/audio", project_id);
+    let url = format!("{}/v1/projects/{}/audio", api_base(), project_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     let audio = response.bytes().await?;
@@ -312,3 +596,42 @@ async fn download_project_audio(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(state: &str, chapters: serde_json::Value) -> ProjectInfo {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": state,
+            "chapters": chapters,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_state_and_progress() {
+        let ready = project("default", serde_json::json!([]));
+        assert_eq!(ready.display_state(), "ready");
+        assert!(!ready.is_converting());
+        assert_eq!(ready.conversion_percent(), 100.0);
+
+        let converting = project(
+            "default",
+            serde_json::json!([
+                {"chapter_id": "c1", "name": "One", "state": "default"},
+                {"chapter_id": "c2", "name": "Two", "state": "converting", "conversion_progress": 0.5}
+            ]),
+        );
+        assert_eq!(converting.display_state(), "converting");
+        assert_eq!(converting.converting_chapters(), 1);
+        assert_eq!(converting.conversion_percent(), 75.0);
+
+        assert_eq!(
+            project("in_queue", serde_json::json!([])).display_state(),
+            "queued"
+        );
+    }
+}
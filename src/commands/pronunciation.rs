@@ -1,6 +1,8 @@
 use crate::cli::{PronunciationArgs, PronunciationCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success, print_warning};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
@@ -8,13 +10,14 @@ use elevenlabs_rs::{
     endpoints::admin::pronunciation::{CreateDictionary, CreateDictionaryBody, GetDictionaries},
     ElevenLabsClient,
 };
-use reqwest::Client;
+use reqwest::{Client, Method};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
 pub async fn execute(args: PronunciationArgs, api_key: &str) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
     let http_client = create_http_client();
 
     match args.command {
@@ -42,6 +45,16 @@ pub async fn execute(args: PronunciationArgs, api_key: &str) -> Result<()> {
             dictionary_id,
             output,
         } => get_pls_file(&http_client, api_key, &dictionary_id, &output).await?,
+        PronunciationCommands::Diff {
+            dictionary_id,
+            local_file,
+            exit_code,
+        } => {
+            let changed = diff_pls(&http_client, api_key, &dictionary_id, &local_file).await?;
+            if changed && exit_code {
+                std::process::exit(1);
+            }
+        }
     }
 
     Ok(())
@@ -50,8 +63,29 @@ pub async fn execute(args: PronunciationArgs, api_key: &str) -> Result<()> {
 async fn list_dictionaries(client: &ElevenLabsClient) -> Result<()> {
     print_info("Fetching pronunciation dictionaries...");
 
-    let endpoint = GetDictionaries::default();
-    let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let response = retry_sdk(Method::GET, || client.hit(GetDictionaries::default()))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let dictionaries: Vec<_> = response
+            .pronunciation_dictionaries
+            .iter()
+            .map(|dict| {
+                serde_json::json!({
+                    "id": dict.id,
+                    "name": dict.name,
+                    "description": dict.description,
+                    "latest_version_id": dict.latest_version_id,
+                    "rules": dict.latest_version_rules_num,
+                    "created_by": dict.created_by,
+                    "creation_time_unix": dict.creation_time_unix,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&dictionaries)?);
+        return Ok(());
+    }
 
     if response.pronunciation_dictionaries.is_empty() {
         print_info("No pronunciation dictionaries found");
@@ -128,19 +162,19 @@ async fn delete_dictionary(client: &Client, api_key: &str, dictionary_id: &str)
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation-dictionaries/{}",
+        "{}/v1/pronunciation-dictionaries/{}",
+        api_base(),
         dictionary_id
     );
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to delete dictionary")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Deleted dictionary '{}'", dictionary_id.green()));
@@ -154,22 +188,27 @@ async fn list_rules(client: &Client, api_key: &str, dictionary_id: &str) -> Resu
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         dictionary_id
     );
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch rules")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let rules: serde_json::Value = response.json().await.context("Failed to parse rules")?;
+    let rules: serde_json::Value = read_json(response).await.context("Failed to parse rules")?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
 
     if rules.is_array() && rules.as_array().unwrap().is_empty() {
         print_info("No rules found in this dictionary");
@@ -211,7 +250,8 @@ async fn add_rules(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         dictionary_id
     );
     let response = client
@@ -219,13 +259,12 @@ async fn add_rules(
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(rules_content)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to add rules")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -260,7 +299,8 @@ async fn remove_rules(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         dictionary_id
     );
     let response = client
@@ -268,13 +308,12 @@ async fn remove_rules(
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(rules_content)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to remove rules")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -297,19 +336,19 @@ async fn get_pls_file(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/pls",
+        "{}/v1/pronunciation/dictionaries/{}/pls",
+        api_base(),
         dictionary_id
     );
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to download PLS file")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     let content = response
@@ -325,3 +364,270 @@ async fn get_pls_file(
 
     Ok(())
 }
+
+/// A single PLS lexeme rule: phoneme (with alphabet) or alias
+#[derive(Debug, Clone, PartialEq)]
+enum PlsRule {
+    Phoneme { alphabet: String, phoneme: String },
+    Alias(String),
+}
+
+impl std::fmt::Display for PlsRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlsRule::Phoneme { alphabet, phoneme } => write!(f, "/{}/ ({})", phoneme, alphabet),
+            PlsRule::Alias(alias) => write!(f, "alias: {}", alias),
+        }
+    }
+}
+
+/// Parse a PLS document into grapheme -> rule
+fn parse_pls(content: &str) -> Result<BTreeMap<String, PlsRule>> {
+    let doc = roxmltree::Document::parse(content).context("Failed to parse PLS XML")?;
+    let root = doc.root_element();
+    let default_alphabet = root.attribute("alphabet").unwrap_or("ipa").to_string();
+
+    let mut rules = BTreeMap::new();
+    for lexeme in root.children().filter(|n| n.has_tag_name("lexeme")) {
+        let child_text = |name: &str| {
+            lexeme
+                .children()
+                .find(|n| n.has_tag_name(name))
+                .and_then(|n| n.text())
+                .map(|t| t.trim().to_string())
+        };
+
+        let rule = if let Some(alias) = child_text("alias") {
+            PlsRule::Alias(alias)
+        } else if let Some(phoneme) = child_text("phoneme") {
+            let alphabet = lexeme
+                .children()
+                .find(|n| n.has_tag_name("phoneme"))
+                .and_then(|n| n.attribute("alphabet"))
+                .unwrap_or(&default_alphabet)
+                .to_string();
+            PlsRule::Phoneme { alphabet, phoneme }
+        } else {
+            continue;
+        };
+
+        for grapheme in lexeme.children().filter(|n| n.has_tag_name("grapheme")) {
+            if let Some(text) = grapheme.text() {
+                rules.insert(text.trim().to_string(), rule.clone());
+            }
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Change to a single grapheme between remote and local dictionaries
+#[derive(Debug, PartialEq)]
+enum PlsChange {
+    Added(PlsRule),
+    Removed(PlsRule),
+    Changed { remote: PlsRule, local: PlsRule },
+}
+
+/// Compute the rule-level diff from `remote` to `local`
+fn diff_rules(
+    remote: &BTreeMap<String, PlsRule>,
+    local: &BTreeMap<String, PlsRule>,
+) -> BTreeMap<String, PlsChange> {
+    let mut changes = BTreeMap::new();
+
+    for (grapheme, rule) in local {
+        match remote.get(grapheme) {
+            None => {
+                changes.insert(grapheme.clone(), PlsChange::Added(rule.clone()));
+            }
+            Some(remote_rule) if remote_rule != rule => {
+                changes.insert(
+                    grapheme.clone(),
+                    PlsChange::Changed {
+                        remote: remote_rule.clone(),
+                        local: rule.clone(),
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+
+    for (grapheme, rule) in remote {
+        if !local.contains_key(grapheme) {
+            changes.insert(grapheme.clone(), PlsChange::Removed(rule.clone()));
+        }
+    }
+
+    changes
+}
+
+/// Diff remote PLS against a local file; returns true if there are differences
+async fn diff_pls(
+    client: &Client,
+    api_key: &str,
+    dictionary_id: &str,
+    local_file: &str,
+) -> Result<bool> {
+    let local_content = std::fs::read_to_string(local_file)
+        .with_context(|| format!("Failed to read local PLS file: {}", local_file))?;
+    let local = parse_pls(&local_content)?;
+
+    print_info(&format!(
+        "Fetching PLS for dictionary '{}'...",
+        dictionary_id.cyan()
+    ));
+
+    let url = format!(
+        "{}/v1/pronunciation/dictionaries/{}/pls",
+        api_base(),
+        dictionary_id
+    );
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to download PLS file")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let remote_content = response
+        .text()
+        .await
+        .context("Failed to read PLS content")?;
+    let remote = parse_pls(&remote_content)?;
+
+    let changes = diff_rules(&remote, &local);
+
+    if is_json_mode() {
+        let entries: Vec<_> = changes
+            .iter()
+            .map(|(grapheme, change)| match change {
+                PlsChange::Added(rule) => serde_json::json!({
+                    "grapheme": grapheme, "change": "added", "local": rule.to_string()
+                }),
+                PlsChange::Removed(rule) => serde_json::json!({
+                    "grapheme": grapheme, "change": "removed", "remote": rule.to_string()
+                }),
+                PlsChange::Changed { remote, local } => serde_json::json!({
+                    "grapheme": grapheme,
+                    "change": "changed",
+                    "remote": remote.to_string(),
+                    "local": local.to_string()
+                }),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(!changes.is_empty());
+    }
+
+    if changes.is_empty() {
+        print_success(&format!(
+            "No differences ({} rules in both)",
+            remote.len().to_string().green()
+        ));
+        return Ok(false);
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["", "Grapheme", "Remote", "Local"]);
+
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for (grapheme, change) in &changes {
+        match change {
+            PlsChange::Added(rule) => {
+                added += 1;
+                table.add_row(vec![
+                    "+".green(),
+                    grapheme.cyan(),
+                    "-".into(),
+                    rule.to_string().green(),
+                ]);
+            }
+            PlsChange::Removed(rule) => {
+                removed += 1;
+                table.add_row(vec![
+                    "-".red(),
+                    grapheme.cyan(),
+                    rule.to_string().red(),
+                    "-".into(),
+                ]);
+            }
+            PlsChange::Changed { remote, local } => {
+                changed += 1;
+                table.add_row(vec![
+                    "~".yellow(),
+                    grapheme.cyan(),
+                    remote.to_string().red(),
+                    local.to_string().green(),
+                ]);
+            }
+        }
+    }
+
+    println!("{}", table);
+    print_warning(&format!(
+        "{} added, {} removed, {} changed",
+        added, removed, changed
+    ));
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REMOTE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<lexicon version="1.0" xmlns="http://www.w3.org/2005/01/pronunciation-lexicon"
+         alphabet="ipa" xml:lang="en-US">
+  <lexeme><grapheme>tomato</grapheme><phoneme>təˈmeɪtoʊ</phoneme></lexeme>
+  <lexeme><grapheme>UN</grapheme><alias>United Nations</alias></lexeme>
+  <lexeme><grapheme>Nginx</grapheme><alias>engine x</alias></lexeme>
+</lexicon>"#;
+
+    const LOCAL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<lexicon version="1.0" xmlns="http://www.w3.org/2005/01/pronunciation-lexicon"
+         alphabet="ipa" xml:lang="en-US">
+  <lexeme><grapheme>tomato</grapheme><phoneme>təˈmɑːtoʊ</phoneme></lexeme>
+  <lexeme><grapheme>UN</grapheme><alias>United Nations</alias></lexeme>
+  <lexeme><grapheme>SQL</grapheme><alias>sequel</alias></lexeme>
+</lexicon>"#;
+
+    #[test]
+    fn test_parse_pls() {
+        let rules = parse_pls(REMOTE).unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(
+            rules.get("UN"),
+            Some(&PlsRule::Alias("United Nations".to_string()))
+        );
+        assert_eq!(
+            rules.get("tomato"),
+            Some(&PlsRule::Phoneme {
+                alphabet: "ipa".to_string(),
+                phoneme: "təˈmeɪtoʊ".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_rules() {
+        let remote = parse_pls(REMOTE).unwrap();
+        let local = parse_pls(LOCAL).unwrap();
+        let changes = diff_rules(&remote, &local);
+
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(changes.get("SQL"), Some(PlsChange::Added(_))));
+        assert!(matches!(changes.get("Nginx"), Some(PlsChange::Removed(_))));
+        assert!(matches!(
+            changes.get("tomato"),
+            Some(PlsChange::Changed { .. })
+        ));
+        assert!(!changes.contains_key("UN"));
+    }
+}
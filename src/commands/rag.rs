@@ -1,20 +1,22 @@
-use crate::cli::{RagArgs, RagCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::cli::{JobKind, RagArgs, RagCommands, WaitArgs};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 pub async fn execute(args: RagArgs, api_key: &str) -> Result<()> {
     let client = create_http_client();
 
     match args.command {
-        RagCommands::Create { document_id, model } => {
-            create_rag_index(&client, api_key, &document_id, &model).await
-        }
+        RagCommands::Create {
+            document_id,
+            model,
+            wait,
+        } => create_rag_index(&client, api_key, &document_id, &model, &wait).await,
         RagCommands::Status {
             document_id,
             rag_index_id,
@@ -23,14 +25,16 @@ pub async fn execute(args: RagArgs, api_key: &str) -> Result<()> {
             document_id,
             rag_index_id,
         } => delete_rag_index(&client, api_key, &document_id, &rag_index_id).await,
-        RagCommands::Rebuild { document_id } => rebuild_index(&client, api_key, &document_id).await,
+        RagCommands::Rebuild { document_id, wait } => {
+            rebuild_index(&client, api_key, &document_id, &wait).await
+        }
         RagCommands::IndexStatus { document_id } => {
             get_index_status(&client, api_key, &document_id).await
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RagIndexResponse {
     id: String,
     model: String,
@@ -41,7 +45,7 @@ struct RagIndexResponse {
     document_model_index_usage: Option<RagIndexUsage>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RagIndexUsage {
     #[allow(dead_code)]
     used_bytes: u64,
@@ -52,6 +56,7 @@ async fn create_rag_index(
     api_key: &str,
     document_id: &str,
     model: &str,
+    wait: &WaitArgs,
 ) -> Result<()> {
     print_info(&format!(
         "Creating RAG index for document '{}' with model '{}'...",
@@ -60,7 +65,8 @@ async fn create_rag_index(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/rag-index",
+        "{}/v1/convai/knowledge-base/{}/rag-index",
+        api_base(),
         document_id
     );
 
@@ -72,22 +78,28 @@ async fn create_rag_index(
         .post(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to create RAG index")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: RagIndexResponse = response.json().await.context("Failed to parse response")?;
+    let result: RagIndexResponse = read_json(response).await?;
+    let job = crate::journal::record(
+        api_key,
+        JobKind::RagIndex,
+        &result.id,
+        Some(document_id),
+        &result.model,
+    );
     print_success("RAG index creation initiated!");
     print_info(&format!("RAG Index ID: {}", result.id.yellow()));
     print_info(&format!("Model: {}", result.model));
     print_info(&format!("Status: {}", result.status));
 
-    Ok(())
+    super::jobs::wait_if_requested(api_key, job, wait).await
 }
 
 async fn get_rag_status(
@@ -103,22 +115,28 @@ async fn get_rag_status(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/rag-index/{}",
-        document_id, rag_index_id
+        "{}/v1/convai/knowledge-base/{}/rag-index/{}",
+        api_base(),
+        document_id,
+        rag_index_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: RagIndexResponse = response.json().await?;
+    let result: RagIndexResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -150,29 +168,30 @@ async fn delete_rag_index(
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/rag-index/{}",
-        document_id, rag_index_id
+        "{}/v1/convai/knowledge-base/{}/rag-index/{}",
+        api_base(),
+        document_id,
+        rag_index_id
     );
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: RagIndexResponse = response.json().await?;
+    let result: RagIndexResponse = read_json(response).await?;
     print_success("RAG index deleted!");
     print_info(&format!("Deleted Index ID: {}", result.id.yellow()));
 
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RagIndexStatusResponse {
     #[serde(default)]
     id: Option<String>,
@@ -188,36 +207,41 @@ struct RagIndexStatusResponse {
     error_message: Option<String>,
 }
 
-async fn rebuild_index(client: &Client, api_key: &str, document_id: &str) -> Result<()> {
+pub(crate) async fn rebuild_index(
+    client: &Client,
+    api_key: &str,
+    document_id: &str,
+    wait: &WaitArgs,
+) -> Result<()> {
     print_info(&format!(
         "Rebuilding RAG index for document '{}'...",
         document_id.cyan()
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/rebuild-index",
+        "{}/v1/convai/knowledge-base/{}/rebuild-index",
+        api_base(),
         document_id
     );
 
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to rebuild RAG index")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: RagIndexStatusResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: RagIndexStatusResponse = read_json(response).await?;
     print_success("RAG index rebuild initiated!");
 
-    if let Some(id) = &result.id {
+    let job = result.id.as_deref().map(|id| {
         print_info(&format!("RAG Index ID: {}", id.yellow()));
-    }
+        crate::journal::record(api_key, JobKind::RagIndex, id, Some(document_id), "rebuild")
+    });
     if let Some(status) = &result.status {
         print_info(&format!("Status: {}", status));
     }
@@ -228,7 +252,13 @@ async fn rebuild_index(client: &Client, api_key: &str, document_id: &str) -> Res
         print_info(&format!("Error Message: {}", error_msg.red()));
     }
 
-    Ok(())
+    match job {
+        Some(job) => super::jobs::wait_if_requested(api_key, job, wait).await,
+        None if wait.wait => Err(anyhow::anyhow!(
+            "The API returned no RAG index ID to wait on; check `rag index-status`"
+        )),
+        None => Ok(()),
+    }
 }
 
 async fn get_index_status(client: &Client, api_key: &str, document_id: &str) -> Result<()> {
@@ -238,24 +268,28 @@ async fn get_index_status(client: &Client, api_key: &str, document_id: &str) ->
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/index-status",
+        "{}/v1/convai/knowledge-base/{}/index-status",
+        api_base(),
         document_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to get RAG index status")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: RagIndexStatusResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: RagIndexStatusResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -5,6 +5,7 @@
 
 use crate::cli::RealtimeTtsArgs;
 use crate::output::print_info;
+use crate::validation::{preflight_tts, validate_chunk_schedule};
 
 #[cfg(feature = "audio")]
 use crate::audio::audio_io;
@@ -13,9 +14,14 @@ use anyhow::Result;
 use colored::*;
 
 pub async fn execute(args: RealtimeTtsArgs, _api_key: &str, _assume_yes: bool) -> Result<()> {
-    if args.text.is_empty() {
-        return Err(anyhow::anyhow!("Text cannot be empty"));
-    }
+    // Pre-flight checks (model, format, language, text length)
+    preflight_tts(
+        &args.text,
+        &args.model,
+        &args.output_format,
+        args.language.as_deref(),
+    )?;
+    validate_chunk_schedule(&args.chunk_schedule)?;
 
     print_info(&format!(
         "Real-time TTS using voice '{}'...",
@@ -38,7 +44,9 @@ pub async fn execute(args: RealtimeTtsArgs, _api_key: &str, _assume_yes: bool) -
 #[cfg(feature = "ws")]
 async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     use crate::output::print_success;
-    use crate::utils::{confirm_overwrite, generate_output_filename, write_bytes_to_file};
+    use crate::utils::{
+        confirm_overwrite, default_output_path, generate_output_filename, write_bytes_to_file,
+    };
     use anyhow::Context;
     use futures_util::{SinkExt, StreamExt};
     use std::time::Duration;
@@ -46,10 +54,13 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
 
     // Build WebSocket URL with API key
     let base_url = "wss://api.elevenlabs.io/v1/text-to-speech";
-    let ws_url = format!(
+    let mut ws_url = format!(
         "{}/{}/stream-input?model_id={}&xi-api-key={}",
         base_url, args.voice, args.model, api_key
     );
+    if let Some(latency) = args.latency {
+        ws_url.push_str(&format!("&optimize_streaming_latency={}", latency));
+    }
 
     print_info("Connecting to ElevenLabs WebSocket API...");
     print_info(&format!("Voice: {}, Model: {}", args.voice, args.model));
@@ -66,10 +77,15 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
     // Send BOS (Beginning of Stream) message
-    let bos = serde_json::json!({
+    let mut bos = serde_json::json!({
         "text_type": "text",
         "EOS": false
     });
+    if !args.chunk_schedule.is_empty() {
+        bos["generation_config"] = serde_json::json!({
+            "chunk_length_schedule": args.chunk_schedule
+        });
+    }
 
     ws_sender
         .send(Message::Text(bos.to_string()))
@@ -91,9 +107,10 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
     let mut audio_chunks = Vec::new();
     let mut chunk_count = 0;
 
-    // Play audio in real-time if requested
+    // Play audio in real-time if requested; --fx needs the whole clip, so it
+    // is played once complete instead
     #[cfg(feature = "audio")]
-    let player = if args.play {
+    let player = if args.play && !crate::player::has_playback_effects() {
         match audio_io::StreamingPlayer::new() {
             Ok(p) => Some(p),
             Err(e) => {
@@ -159,6 +176,10 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
 
     // Finish streaming playback
     #[cfg(feature = "audio")]
+    let played = player.is_some();
+    #[cfg(not(feature = "audio"))]
+    let played = false;
+    #[cfg(feature = "audio")]
     if let Some(p) = player {
         if let Err(e) = p.finish() {
             print_info(&format!("Warning: error finishing playback: {}", e));
@@ -186,7 +207,10 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
             f if f.starts_with("opus_") => "opus",
             _ => "mp3",
         };
-        generate_output_filename("realtime_tts", extension)
+        default_output_path(
+            &generate_output_filename("realtime_tts", extension),
+            Some(&args.voice),
+        )?
     };
 
     // Check for overwrite
@@ -200,5 +224,12 @@ async fn execute_ws_tts(args: RealtimeTtsArgs, api_key: &str, assume_yes: bool)
     write_bytes_to_file(&audio_chunks, path)?;
     print_success(&format!("Saved -> {}", output_path.green()));
 
+    // No live playback; hand the whole clip to the external player instead
+    if args.play && !played {
+        if let Err(e) = crate::player::play_audio(&audio_chunks) {
+            print_info(&format!("Could not play audio: {}", e));
+        }
+    }
+
     Ok(())
 }
@@ -1,5 +1,6 @@
 use crate::cli::{SamplesArgs, SamplesCommands};
-use crate::output::{print_info, print_success, print_warning};
+use crate::client::{create_sdk_client, retry_sdk};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
 use crate::utils::{confirm_overwrite, write_bytes_to_file};
 use anyhow::Result;
 use colored::*;
@@ -8,10 +9,11 @@ use elevenlabs_rs::{
     endpoints::admin::voice::GetVoice,
     ElevenLabsClient,
 };
+use reqwest::Method;
 use std::path::Path;
 
 pub async fn execute(args: SamplesArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     match args.command {
         SamplesCommands::List { voice_id } => list_samples(&client, &voice_id).await?,
@@ -35,8 +37,27 @@ async fn list_samples(client: &ElevenLabsClient, voice_id: &str) -> Result<()> {
         voice_id.cyan()
     ));
 
-    let endpoint = GetVoice::new(voice_id);
-    let voice = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let voice = retry_sdk(Method::GET, || client.hit(GetVoice::new(voice_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let samples: Vec<_> = voice
+            .samples
+            .iter()
+            .flatten()
+            .map(|sample| {
+                serde_json::json!({
+                    "sample_id": sample.sample_id,
+                    "file_name": sample.file_name,
+                    "mime_type": sample.mime_type,
+                    "size_bytes": sample.size_bytes,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&samples)?);
+        return Ok(());
+    }
 
     if let Some(samples) = voice.samples {
         if samples.is_empty() {
@@ -86,10 +107,7 @@ async fn delete_sample(
     ));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -97,8 +115,11 @@ async fn delete_sample(
         }
     }
 
-    let endpoint = DeleteSample::new(voice_id, sample_id);
-    client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    retry_sdk(Method::DELETE, || {
+        client.hit(DeleteSample::new(voice_id, sample_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     print_success(&format!("Deleted sample '{}'", sample_id));
     Ok(())
@@ -113,8 +134,11 @@ async fn download_sample(
 ) -> Result<()> {
     print_info(&format!("Downloading sample '{}'...", sample_id.cyan()));
 
-    let endpoint = GetAudioFromSample::new(voice_id, sample_id);
-    let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let audio = retry_sdk(Method::GET, || {
+        client.hit(GetAudioFromSample::new(voice_id, sample_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     let output_path = output.unwrap_or_else(|| format!("sample_{}.mp3", sample_id));
 
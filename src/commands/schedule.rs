@@ -0,0 +1,419 @@
+//! Recurring CLI commands (`schedule`)
+//!
+//! Schedules live in `schedules.json` under the data directory, each a cron
+//! expression and the arguments of a CLI invocation. `schedule run` is a
+//! small foreground scheduler: it wakes at every minute boundary, runs the
+//! commands that are due as child processes (like `daemon` jobs) and records
+//! the outcome of each run. Cron times are UTC.
+
+use crate::cli::{ScheduleArgs, ScheduleCommands};
+use crate::commands::daemon::FORBIDDEN_COMMANDS;
+use crate::cron::{format_utc, CronSchedule};
+use crate::output::{is_json_mode, print_error, print_info, print_success, print_warning};
+use crate::utils::{format_relative_time, unix_now, with_file_lock, write_atomic};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// A command run on a cron schedule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Schedule {
+    id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    cron: String,
+    args: Vec<String>,
+    created_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_run: Option<LastRun>,
+}
+
+/// Outcome of the most recent run of a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct LastRun {
+    started_at: u64,
+    exit_code: Option<i32>,
+    status: String,
+}
+
+pub async fn execute(args: ScheduleArgs, api_key: &str) -> Result<()> {
+    let path = crate::paths::data_file("schedules.json")
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the data directory"))?;
+    match args.command {
+        ScheduleCommands::Add { cron, cmd, name } => add_schedule(&path, &cron, &cmd, name)?,
+        ScheduleCommands::List => list_schedules(&path)?,
+        ScheduleCommands::Remove { id } => remove_schedule(&path, &id)?,
+        ScheduleCommands::Run => run_scheduler(&path, api_key).await?,
+    }
+    Ok(())
+}
+
+fn add_schedule(path: &Path, cron: &str, cmd: &str, name: Option<String>) -> Result<()> {
+    CronSchedule::parse(cron)?;
+    let args = parse_command(cmd)?;
+
+    let schedule = with_file_lock(path, || {
+        let mut schedules = load_schedules(path)?;
+        let next_id = schedules
+            .iter()
+            .filter_map(|s| s.id.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let schedule = Schedule {
+            id: next_id.to_string(),
+            name,
+            cron: cron.trim().to_string(),
+            args,
+            created_at: unix_now(),
+            last_run: None,
+        };
+        schedules.push(schedule.clone());
+        save_schedules(path, &schedules)?;
+        Ok(schedule)
+    })?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&schedule)?);
+        return Ok(());
+    }
+    print_success(&format!(
+        "Scheduled '{}' as {} ({})",
+        schedule.args.join(" ").cyan(),
+        schedule.id.yellow(),
+        schedule.cron
+    ));
+    if let Some(next) = CronSchedule::parse(&schedule.cron)?.next_after(unix_now()) {
+        print_info(&format!("Next run: {}", format_utc(next)));
+    }
+    print_info("Commands run while `elevenlabs schedule run` is active");
+    Ok(())
+}
+
+fn list_schedules(path: &Path) -> Result<()> {
+    let schedules = load_schedules(path)?;
+    let now = unix_now();
+
+    if is_json_mode() {
+        let rows: Vec<serde_json::Value> = schedules
+            .iter()
+            .map(|s| {
+                let mut row = serde_json::to_value(s).unwrap_or_default();
+                row["next_run"] = CronSchedule::parse(&s.cron)
+                    .ok()
+                    .and_then(|c| c.next_after(now))
+                    .into();
+                row
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if schedules.is_empty() {
+        print_info("No schedules. Add one with `schedule add --cron <EXPR> --cmd <COMMAND>`");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID", "Name", "Cron", "Command", "Next run", "Last run",
+    ]);
+    for schedule in &schedules {
+        let next = match CronSchedule::parse(&schedule.cron) {
+            Ok(cron) => cron
+                .next_after(now)
+                .map(format_utc)
+                .unwrap_or_else(|| "never".to_string()),
+            Err(_) => "invalid".to_string(),
+        };
+        let last = schedule
+            .last_run
+            .as_ref()
+            .map(|run| {
+                format!(
+                    "{} ({})",
+                    run.status,
+                    format_relative_time(now.saturating_sub(run.started_at))
+                )
+            })
+            .unwrap_or_else(|| "-".to_string());
+        table.add_row(vec![
+            schedule.id.clone(),
+            schedule.name.clone().unwrap_or_default(),
+            schedule.cron.clone(),
+            schedule.args.join(" "),
+            next,
+            last,
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+fn remove_schedule(path: &Path, id: &str) -> Result<()> {
+    with_file_lock(path, || {
+        let mut schedules = load_schedules(path)?;
+        let before = schedules.len();
+        schedules.retain(|s| s.id != id);
+        if schedules.len() == before {
+            return Err(anyhow::anyhow!("No schedule with ID '{}'", id));
+        }
+        save_schedules(path, &schedules)
+    })?;
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "id": id, "removed": true }))?
+        );
+    } else {
+        print_success(&format!("Removed schedule {}", id.yellow()));
+    }
+    Ok(())
+}
+
+async fn run_scheduler(path: &Path, api_key: &str) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the CLI executable")?;
+    let count = load_schedules(path)?.len();
+    print_info(&format!(
+        "Running {} schedule(s) from {} (times in UTC, Ctrl-C to stop)",
+        count,
+        path.display()
+    ));
+
+    let mut tasks = JoinSet::new();
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
+    let mut minute = (unix_now() / 60 + 1) * 60;
+
+    loop {
+        let wait = minute.saturating_sub(unix_now());
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(wait)) => {}
+            Some(_) = tasks.join_next(), if !tasks.is_empty() => continue,
+            _ = &mut shutdown => break,
+        }
+
+        // Reloaded every minute so `schedule add` and `remove` apply without a restart
+        let schedules = match load_schedules(path) {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                print_error(&format!("{:#}", e));
+                Vec::new()
+            }
+        };
+        for schedule in schedules {
+            let due = match CronSchedule::parse(&schedule.cron) {
+                Ok(cron) => cron.matches(minute),
+                Err(e) => {
+                    print_warning(&format!("Skipping schedule {}: {:#}", schedule.id, e));
+                    false
+                }
+            };
+            if due {
+                let exe = exe.clone();
+                let path = path.to_path_buf();
+                let api_key = api_key.to_string();
+                tasks.spawn(async move { run_schedule(&exe, &path, &api_key, schedule).await });
+            }
+        }
+
+        // After a suspend, resume at the current minute rather than replaying missed ones
+        minute = (minute + 60).max(unix_now() / 60 * 60);
+    }
+
+    if !tasks.is_empty() {
+        print_info(&format!(
+            "Waiting for {} running command(s) to finish...",
+            tasks.len()
+        ));
+    }
+    while tasks.join_next().await.is_some() {}
+    print_success("Scheduler stopped");
+    Ok(())
+}
+
+async fn run_schedule(exe: &Path, path: &Path, api_key: &str, schedule: Schedule) {
+    let label = schedule.name.clone().unwrap_or_else(|| schedule.id.clone());
+    print_info(&format!(
+        "Running {}: {}",
+        label.cyan(),
+        schedule.args.join(" ")
+    ));
+    let started_at = unix_now();
+
+    // Nobody is there to answer prompts, so they are auto-confirmed. The flag
+    // goes before the command's own arguments, where a trailing `--` or
+    // variadic argument can't take it as a value.
+    let output = tokio::process::Command::new(exe)
+        .arg("--yes")
+        .args(&schedule.args)
+        .env("ELEVENLABS_API_KEY", api_key)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await;
+
+    let run = match output {
+        Ok(output) if output.status.success() => {
+            print_success(&format!("{} finished", label));
+            LastRun {
+                started_at,
+                exit_code: output.status.code(),
+                status: "succeeded".to_string(),
+            }
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            print_error(&format!(
+                "{} failed ({}): {}",
+                label,
+                output
+                    .status
+                    .code()
+                    .map(|c| format!("exit code {}", c))
+                    .unwrap_or_else(|| "killed".to_string()),
+                stderr.trim().lines().last().unwrap_or_default()
+            ));
+            LastRun {
+                started_at,
+                exit_code: output.status.code(),
+                status: "failed".to_string(),
+            }
+        }
+        Err(e) => {
+            print_error(&format!("{} could not start: {}", label, e));
+            LastRun {
+                started_at,
+                exit_code: None,
+                status: "failed".to_string(),
+            }
+        }
+    };
+
+    let saved = with_file_lock(path, || {
+        let mut schedules = load_schedules(path)?;
+        if let Some(entry) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+            entry.last_run = Some(run);
+            save_schedules(path, &schedules)?;
+        }
+        Ok(())
+    });
+    if let Err(e) = saved {
+        print_warning(&format!("Could not record the run of {}: {:#}", label, e));
+    }
+}
+
+fn load_schedules(path: &Path) -> Result<Vec<Schedule>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn save_schedules(path: &Path, schedules: &[Schedule]) -> Result<()> {
+    write_atomic(path, serde_json::to_string_pretty(schedules)?.as_bytes())
+        .with_context(|| format!("Failed to save {}", path.display()))
+}
+
+/// Split `--cmd` into CLI arguments, dropping a leading program name
+fn parse_command(cmd: &str) -> Result<Vec<String>> {
+    let mut args = split_command_line(cmd)?;
+    if matches!(
+        args.first().map(String::as_str),
+        Some("elevenlabs" | "elevenlabs-cli")
+    ) {
+        args.remove(0);
+    }
+    let Some(command) = args.first() else {
+        return Err(anyhow::anyhow!("--cmd is empty"));
+    };
+    if FORBIDDEN_COMMANDS.contains(&command.as_str()) {
+        return Err(anyhow::anyhow!("Command '{}' cannot be scheduled", command));
+    }
+    Ok(args)
+}
+
+/// Shell-style word splitting with single quotes, double quotes and
+/// backslash escapes (no variables or globs)
+fn split_command_line(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => word.push(c),
+            (Some(_), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), _) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, _) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(anyhow::anyhow!("Unterminated quote in --cmd"));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line("usage alert  --threshold 80").unwrap(),
+            vec!["usage", "alert", "--threshold", "80"]
+        );
+        assert_eq!(
+            split_command_line(r#"tts "Good morning, team" -o 'daily brief.mp3'"#).unwrap(),
+            vec!["tts", "Good morning, team", "-o", "daily brief.mp3"]
+        );
+        assert_eq!(
+            split_command_line(r#"tts It\'s "" x"#).unwrap(),
+            vec!["tts", "It's", "", "x"]
+        );
+        assert!(split_command_line("tts \"unclosed").is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("elevenlabs usage alert").unwrap(),
+            vec!["usage", "alert"]
+        );
+        assert!(parse_command("  ").is_err());
+        assert!(parse_command("daemon queue").is_err());
+        assert!(parse_command("schedule run").is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! Local search over voices, history and agents
+//!
+//! Searches a small index kept in the cache directory instead of calling
+//! several list endpoints. Each API key (so each profile) has an index of its
+//! own, named after a fingerprint of the key. The index is rebuilt from the
+//! API when it is missing, older than a day, or when `--refresh` is given.
+
+use crate::cli::{SearchArgs, SearchKind};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::agent::fetch_all_agents;
+use crate::journal::account_id;
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::utils::{unix_now, write_atomic};
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Rebuild the index when it is older than this
+const INDEX_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Most recent history items kept in the index
+const HISTORY_INDEX_LIMIT: usize = 1000;
+
+/// One searchable item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexEntry {
+    /// voice, history or agent
+    kind: String,
+    id: String,
+    name: String,
+    /// Extra searchable text (description, labels, generated text)
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SearchIndex {
+    updated_at: u64,
+    entries: Vec<IndexEntry>,
+}
+
+fn kind_name(kind: SearchKind) -> &'static str {
+    match kind {
+        SearchKind::Voice => "voice",
+        SearchKind::History => "history",
+        SearchKind::Agent => "agent",
+    }
+}
+
+fn index_path(api_key: &str) -> Option<PathBuf> {
+    crate::paths::cache_file(&format!("search-index-{}.json", account_id(api_key)))
+}
+
+pub async fn execute(args: SearchArgs, api_key: &str) -> Result<()> {
+    let query = args.query.trim();
+    if query.is_empty() {
+        return Err(anyhow::anyhow!("Search query must not be empty"));
+    }
+
+    let path = index_path(api_key);
+    let cached = path.as_ref().and_then(|p| load_index(p));
+    let index = match cached {
+        Some(index)
+            if !args.refresh
+                && unix_now().saturating_sub(index.updated_at) < INDEX_MAX_AGE_SECS =>
+        {
+            index
+        }
+        _ => {
+            let index = build_index(&create_http_client(), api_key).await?;
+            if let Some(path) = &path {
+                if let Err(e) = save_index(path, &index) {
+                    print_warning(&format!("Could not save search index: {}", e));
+                }
+            }
+            index
+        }
+    };
+
+    let kinds: Vec<&str> = args.kinds.iter().map(|k| kind_name(*k)).collect();
+    let results = search(&index.entries, query, &kinds, args.limit);
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        print_info(&format!("No matches for '{}'", query));
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Type", "Name", "ID", "Match"]);
+    for entry in &results {
+        table.add_row(vec![
+            entry.kind.as_str().into(),
+            entry.name.cyan(),
+            entry.id.yellow(),
+            snippet(&entry.text, query).normal(),
+        ]);
+    }
+    println!("{}", table);
+    print_success(&format!("Found {} match(es)", results.len()));
+    Ok(())
+}
+
+fn load_index(path: &std::path::Path) -> Option<SearchIndex> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn save_index(path: &std::path::Path, index: &SearchIndex) -> Result<()> {
+    write_atomic(path, serde_json::to_string(index)?.as_bytes())
+}
+
+/// Entries matching every word of the query, name matches first
+fn search(entries: &[IndexEntry], query: &str, kinds: &[&str], limit: usize) -> Vec<IndexEntry> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut matches: Vec<(bool, &IndexEntry)> = entries
+        .iter()
+        .filter(|e| kinds.is_empty() || kinds.contains(&e.kind.as_str()))
+        .filter_map(|e| {
+            let name = e.name.to_lowercase();
+            let haystack = format!("{} {} {}", name, e.id.to_lowercase(), e.text.to_lowercase());
+            terms
+                .iter()
+                .all(|t| haystack.contains(t.as_str()))
+                .then(|| (terms.iter().all(|t| name.contains(t.as_str())), e))
+        })
+        .collect();
+    // Stable sort keeps index order (voices, agents, then newest history) within each group
+    matches.sort_by_key(|(name_match, _)| !name_match);
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, e)| e.clone())
+        .collect()
+}
+
+/// A short excerpt of `text` around the first query word
+fn snippet(text: &str, query: &str) -> String {
+    const WIDTH: usize = 60;
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= WIDTH {
+        return text.to_string();
+    }
+    let lower: Vec<char> = text.to_lowercase().chars().collect();
+    let first = query
+        .split_whitespace()
+        .next()
+        .map(|t| t.to_lowercase().chars().collect::<Vec<char>>())
+        .unwrap_or_default();
+    let position = (0..=lower.len().saturating_sub(first.len()))
+        .find(|&i| !first.is_empty() && lower[i..].starts_with(&first))
+        .unwrap_or(0);
+    // Lowercasing can change lengths; fall back to the start if it did
+    let position = if lower.len() == chars.len() {
+        position
+    } else {
+        0
+    };
+    let start = position.saturating_sub(WIDTH / 3);
+    let end = (start + WIDTH).min(chars.len());
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        chars[start..end].iter().collect::<String>(),
+        if end < chars.len() { "..." } else { "" }
+    )
+}
+
+async fn build_index(client: &Client, api_key: &str) -> Result<SearchIndex> {
+    print_info("Building local search index...");
+    let mut entries = fetch_voice_entries(client, api_key).await?;
+    entries.extend(fetch_agent_entries(client, api_key).await?);
+    entries.extend(fetch_history_entries(client, api_key).await?);
+    Ok(SearchIndex {
+        updated_at: unix_now(),
+        entries,
+    })
+}
+
+async fn fetch_voice_entries(client: &Client, api_key: &str) -> Result<Vec<IndexEntry>> {
+    #[derive(Deserialize)]
+    struct Voice {
+        voice_id: String,
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        labels: BTreeMap<String, String>,
+    }
+
+    let response = client
+        .get(format!("{}/v1/voices", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch voices")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let body: Value = read_json(response).await?;
+    let voices: Vec<Voice> = serde_json::from_value(body["voices"].clone())?;
+
+    Ok(voices
+        .into_iter()
+        .map(|v| {
+            let mut text: Vec<String> = v.description.into_iter().chain(v.category).collect();
+            text.extend(v.labels.iter().map(|(k, v)| format!("{}={}", k, v)));
+            IndexEntry {
+                kind: "voice".to_string(),
+                id: v.voice_id,
+                name: v.name,
+                text: text.join(" "),
+            }
+        })
+        .collect())
+}
+
+async fn fetch_agent_entries(client: &Client, api_key: &str) -> Result<Vec<IndexEntry>> {
+    let agents = fetch_all_agents(client, api_key).await?;
+
+    Ok(agents
+        .iter()
+        .filter_map(|a| {
+            let tags: Vec<&str> = a["tags"]
+                .as_array()
+                .map(|t| t.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            Some(IndexEntry {
+                kind: "agent".to_string(),
+                id: a["agent_id"].as_str()?.to_string(),
+                name: a["name"].as_str().unwrap_or_default().to_string(),
+                text: tags.join(" "),
+            })
+        })
+        .collect())
+}
+
+async fn fetch_history_entries(client: &Client, api_key: &str) -> Result<Vec<IndexEntry>> {
+    let mut entries = Vec::new();
+    let mut start_after: Option<String> = None;
+
+    while entries.len() < HISTORY_INDEX_LIMIT {
+        let mut query = vec![("page_size", "100".to_string())];
+        if let Some(after) = &start_after {
+            query.push(("start_after_history_item_id", after.clone()));
+        }
+        let response = client
+            .get(format!("{}/v1/history", api_base()))
+            .header("xi-api-key", api_key)
+            .query(&query)
+            .send_with_retry()
+            .await
+            .context("Failed to fetch history")?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let body: Value = read_json(response).await?;
+
+        for item in body["history"].as_array().into_iter().flatten() {
+            let Some(id) = item["history_item_id"].as_str() else {
+                continue;
+            };
+            entries.push(IndexEntry {
+                kind: "history".to_string(),
+                id: id.to_string(),
+                name: item["voice_name"].as_str().unwrap_or("-").to_string(),
+                text: item["text"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+
+        start_after = body["last_history_item_id"].as_str().map(str::to_string);
+        if !body["has_more"].as_bool().unwrap_or(false) || start_after.is_none() {
+            break;
+        }
+    }
+
+    entries.truncate(HISTORY_INDEX_LIMIT);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &str, id: &str, name: &str, text: &str) -> IndexEntry {
+        IndexEntry {
+            kind: kind.to_string(),
+            id: id.to_string(),
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_search_ranks_name_matches_first() {
+        let entries = vec![
+            entry("history", "h1", "Brian", "Ahoy, said the pirate captain"),
+            entry("voice", "v1", "Pirate Pete", "gravelly accent=british"),
+            entry("agent", "a1", "Support bot", "billing"),
+        ];
+
+        let results = search(&entries, "PIRATE", &[], 10);
+        let ids: Vec<&str> = results.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["v1", "h1"]);
+
+        assert_eq!(search(&entries, "pirate", &["history"], 10)[0].id, "h1");
+        assert_eq!(search(&entries, "pirate captain", &[], 10).len(), 1);
+        assert_eq!(search(&entries, "accent=british", &[], 10)[0].id, "v1");
+        assert_eq!(search(&entries, "pirate", &[], 1).len(), 1);
+        assert!(search(&entries, "dragon", &[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_snippet() {
+        assert_eq!(snippet("short text", "text"), "short text");
+        let long = format!("{} pirate {}", "a".repeat(100), "b".repeat(100));
+        let excerpt = snippet(&long, "pirate");
+        assert!(excerpt.starts_with("..."));
+        assert!(excerpt.ends_with("..."));
+        assert!(excerpt.contains("pirate"));
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("index.json");
+        let index = SearchIndex {
+            updated_at: 42,
+            entries: vec![entry("voice", "v1", "Rachel", "")],
+        };
+        save_index(&path, &index).unwrap();
+        let loaded = load_index(&path).unwrap();
+        assert_eq!(loaded.updated_at, 42);
+        assert_eq!(loaded.entries, index.entries);
+        assert!(load_index(&dir.path().join("missing.json")).is_none());
+    }
+}
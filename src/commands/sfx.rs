@@ -1,14 +1,25 @@
 use crate::cli::SoundEffectsArgs;
-use crate::output::{print_info, print_success};
-use crate::utils::{confirm_overwrite, generate_output_filename, write_bytes_to_file};
-use anyhow::Result;
-use colored::*;
-use elevenlabs_rs::{
-    endpoints::genai::sound_effects::{CreateSoundEffect, CreateSoundEffectBody},
-    ElevenLabsClient,
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, retry_sdk, SendWithRetry,
+};
+use crate::output::{print_info, print_success, Progress};
+use crate::pcm::{crossfade_loop, pcm16_to_wav, samples_from_pcm16, samples_to_pcm16};
+use crate::utils::{
+    confirm_overwrite, default_output_path, generate_output_filename, write_bytes_to_file,
 };
+use anyhow::{Context, Result};
+use colored::*;
+use elevenlabs_rs::endpoints::genai::sound_effects::{CreateSoundEffect, CreateSoundEffectBody};
+use reqwest::Method;
+use serde_json::json;
 use std::path::Path;
 
+/// Sound effects model that supports the `loop` option
+const LOOP_MODEL: &str = "eleven_text_to_sound_v2";
+
+/// Sample rate requested for client-side crossfading
+const CROSSFADE_SAMPLE_RATE: u32 = 44_100;
+
 pub async fn execute(args: SoundEffectsArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     if args.text.is_empty() {
         return Err(anyhow::anyhow!("Text description cannot be empty"));
@@ -30,6 +41,23 @@ pub async fn execute(args: SoundEffectsArgs, api_key: &str, assume_yes: bool) ->
         }
     }
 
+    // Crossfaded loops are produced from PCM and written as WAV
+    if let (Some(_), Some(output)) = (args.crossfade, &args.output) {
+        let is_wav = Path::new(output)
+            .extension()
+            .map(|e| e.eq_ignore_ascii_case("wav"))
+            .unwrap_or(false);
+        if !is_wav {
+            return Err(anyhow::anyhow!(
+                "--crossfade writes WAV audio; use a .wav output path (got '{}')",
+                output
+            ));
+        }
+    }
+    if args.crossfade == Some(0) {
+        return Err(anyhow::anyhow!("Crossfade must be greater than 0 ms"));
+    }
+
     print_info("Generating sound effect...");
     print_info(&format!("Prompt: {}", args.text.cyan()));
 
@@ -39,31 +67,51 @@ pub async fn execute(args: SoundEffectsArgs, api_key: &str, assume_yes: bool) ->
         print_info("Duration: auto");
     }
 
-    // Create client
-    let client = ElevenLabsClient::new(api_key);
-
-    // Build request body
-    let mut body = CreateSoundEffectBody::new(&args.text);
-
-    if let Some(duration) = args.duration {
-        body = body.with_duration_seconds(duration);
+    if args.looping {
+        print_info(&format!("Loop: seamless ({})", LOOP_MODEL));
     }
-    if let Some(influence) = args.influence {
-        body = body.with_prompt_influence(influence);
+    if let Some(ms) = args.crossfade {
+        print_info(&format!("Loop: {}ms client-side crossfade", ms));
     }
 
-    let endpoint = CreateSoundEffect::new(body);
-
     // Generate
     let start_time = std::time::Instant::now();
-    let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let progress = Progress::spinner("Generating sound effect");
+    let audio = if args.looping || args.crossfade.is_some() {
+        request_sound_effect(&args, api_key).await?
+    } else {
+        // Create client
+        let client = create_sdk_client(api_key);
+
+        retry_sdk(Method::POST, || {
+            // Build request body
+            let mut body = CreateSoundEffectBody::new(&args.text);
+
+            if let Some(duration) = args.duration {
+                body = body.with_duration_seconds(duration);
+            }
+            if let Some(influence) = args.influence {
+                body = body.with_prompt_influence(influence);
+            }
+
+            client.hit(CreateSoundEffect::new(body))
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?
+    };
+    progress.finish();
     let duration = start_time.elapsed();
 
+    let (audio, extension) = match args.crossfade {
+        Some(ms) => (make_crossfade_loop(&audio, ms)?, "wav"),
+        None => (audio.to_vec(), "mp3"),
+    };
+
     // Determine output path
     let output_path = if let Some(output) = args.output {
         output
     } else {
-        generate_output_filename("sound_effect", "mp3")
+        default_output_path(&generate_output_filename("sound_effect", extension), None)?
     };
 
     // Check for overwrite
@@ -84,3 +132,60 @@ pub async fn execute(args: SoundEffectsArgs, api_key: &str, assume_yes: bool) ->
 
     Ok(())
 }
+
+/// Call the sound generation endpoint directly for options the SDK does not expose
+/// (`loop`, PCM output for crossfading)
+async fn request_sound_effect(args: &SoundEffectsArgs, api_key: &str) -> Result<bytes::Bytes> {
+    let mut body = json!({ "text": args.text });
+    if let Some(duration) = args.duration {
+        body["duration_seconds"] = json!(duration);
+    }
+    if let Some(influence) = args.influence {
+        body["prompt_influence"] = json!(influence);
+    }
+    if args.looping {
+        body["loop"] = json!(true);
+        body["model_id"] = json!(LOOP_MODEL);
+    }
+
+    let output_format = if args.crossfade.is_some() {
+        format!("pcm_{}", CROSSFADE_SAMPLE_RATE)
+    } else {
+        "mp3_44100_128".to_string()
+    };
+
+    let client = create_http_client();
+    let response = client
+        .post(format!("{}/v1/sound-generation", api_base()))
+        .header("xi-api-key", api_key)
+        .query(&[("output_format", output_format)])
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to send sound effect request")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    Ok(response.bytes().await?)
+}
+
+/// Turn raw PCM into a crossfaded loop wrapped in a WAV container
+fn make_crossfade_loop(pcm: &[u8], crossfade_ms: u32) -> Result<Vec<u8>> {
+    let samples = samples_from_pcm16(pcm);
+    let fade_len = (CROSSFADE_SAMPLE_RATE as u64 * crossfade_ms as u64 / 1000) as usize;
+    if samples.len() < fade_len * 2 {
+        return Err(anyhow::anyhow!(
+            "Crossfade of {}ms is too long for a {:.2}s clip (max: half the clip length)",
+            crossfade_ms,
+            samples.len() as f64 / CROSSFADE_SAMPLE_RATE as f64
+        ));
+    }
+
+    let looped = crossfade_loop(&samples, fade_len);
+    Ok(pcm16_to_wav(
+        &samples_to_pcm16(&looped),
+        CROSSFADE_SAMPLE_RATE,
+    ))
+}
@@ -1,21 +1,136 @@
 use crate::cli::SpeechToTextArgs;
-use crate::output::{print_info, print_success};
-use crate::utils::validate_file_size;
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{print_info, print_success, print_warning, Progress};
+use crate::transcode::prepare_upload;
+use crate::validation::validate_input_file;
 
 #[cfg(feature = "audio")]
 use crate::audio::audio_io;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
-use elevenlabs_rs::{
-    endpoints::genai::speech_to_text::{
-        CreateTranscript, CreateTranscriptBody, Granularity, SpeechToTextModel,
-    },
-    ElevenLabsClient,
-};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Maximum number of key terms accepted per request
+const MAX_KEYTERMS: usize = 100;
+
+/// Maximum length of a single key term
+const MAX_KEYTERM_CHARS: usize = 50;
+
+/// Words at or above this confidence are printed without highlighting
+const HIGH_CONFIDENCE: f64 = 0.95;
+
+/// ISO 639-1 codes and the ISO 639-3 codes the API reports them as
+const LANGUAGE_CODES: &[(&str, &str)] = &[
+    ("ar", "ara"),
+    ("bg", "bul"),
+    ("bn", "ben"),
+    ("ca", "cat"),
+    ("cs", "ces"),
+    ("cy", "cym"),
+    ("da", "dan"),
+    ("de", "deu"),
+    ("el", "ell"),
+    ("en", "eng"),
+    ("es", "spa"),
+    ("et", "est"),
+    ("fa", "fas"),
+    ("fi", "fin"),
+    ("fil", "fil"),
+    ("fr", "fra"),
+    ("ga", "gle"),
+    ("he", "heb"),
+    ("hi", "hin"),
+    ("hr", "hrv"),
+    ("hu", "hun"),
+    ("id", "ind"),
+    ("is", "isl"),
+    ("it", "ita"),
+    ("ja", "jpn"),
+    ("ko", "kor"),
+    ("lt", "lit"),
+    ("lv", "lav"),
+    ("ms", "msa"),
+    ("nl", "nld"),
+    ("no", "nor"),
+    ("pl", "pol"),
+    ("pt", "por"),
+    ("ro", "ron"),
+    ("ru", "rus"),
+    ("sk", "slk"),
+    ("sl", "slv"),
+    ("sr", "srp"),
+    ("sv", "swe"),
+    ("ta", "tam"),
+    ("th", "tha"),
+    ("tr", "tur"),
+    ("uk", "ukr"),
+    ("ur", "urd"),
+    ("vi", "vie"),
+    ("zh", "zho"),
+];
+
+/// Transcription response from the speech-to-text endpoint
+#[derive(Debug, Deserialize)]
+struct Transcript {
+    language_code: String,
+    language_probability: f64,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+/// A word, spacing or audio event in a transcript
+#[derive(Debug, Deserialize)]
+struct Word {
+    text: String,
+    start: Option<f64>,
+    end: Option<f64>,
+    #[serde(rename = "type", default)]
+    kind: String,
+    speaker_id: Option<String>,
+    logprob: Option<f64>,
+}
+
+impl Word {
+    fn is_spacing(&self) -> bool {
+        self.kind == "spacing" || self.text.trim().is_empty()
+    }
+
+    /// Confidence in the 0-1 range, derived from the log probability
+    fn confidence(&self) -> Option<f64> {
+        self.logprob.map(|lp| lp.exp().clamp(0.0, 1.0))
+    }
+}
+
+/// A run of consecutive words below the confidence threshold
+#[derive(Debug, Serialize, PartialEq)]
+struct LowConfidenceSegment {
+    start: Option<f64>,
+    end: Option<f64>,
+    text: String,
+    /// Lowest word confidence in the segment
+    confidence: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speaker_id: Option<String>,
+}
+
+/// Report of segments needing human review
+#[derive(Debug, Serialize)]
+struct ConfidenceReport<'a> {
+    file: &'a str,
+    min_confidence: f64,
+    total_words: usize,
+    low_confidence_words: usize,
+    segments: Vec<LowConfidenceSegment>,
+}
 
 /// JSON output structure for transcription results
 #[derive(Debug, Serialize)]
@@ -32,9 +147,28 @@ struct WordInfo {
     start: Option<f64>,
     end: Option<f64>,
     speaker_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f64>,
+}
+
+impl From<&Word> for WordInfo {
+    fn from(w: &Word) -> Self {
+        Self {
+            text: w.text.clone(),
+            start: w.start,
+            end: w.end,
+            speaker_id: w.speaker_id.clone(),
+            confidence: w.confidence(),
+        }
+    }
 }
 
 pub async fn execute(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
+    if !(0.0..=1.0).contains(&args.min_confidence) {
+        return Err(anyhow::anyhow!("--min-confidence must be between 0 and 1"));
+    }
+    validate_languages(&args.languages)?;
+
     // Handle recording from microphone
     #[cfg(feature = "audio")]
     if args.record {
@@ -60,12 +194,12 @@ pub async fn execute(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
 
     let file_path = Path::new(file_path);
 
-    if !file_path.exists() {
-        return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
-    }
+    // Convert formats the API can't read, then the pre-flight checks
+    // (existence, size, extension) on what will be uploaded
+    let upload = prepare_upload(file_path)?;
+    validate_input_file(upload.path())?;
 
-    // Validate file size using utility
-    validate_file_size(file_path)?;
+    let keyterms = load_keyterms(&args)?;
 
     let metadata = fs::metadata(file_path)?;
     let file_size = metadata.len();
@@ -76,47 +210,24 @@ pub async fn execute(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
         (file_size as f64 / 1_048_576.0).round()
     ));
     print_info(&format!("Model: {}", args.model.yellow()));
-
-    // Create client
-    let client = ElevenLabsClient::new(api_key);
-
-    // Parse model
-    let model = match args.model.as_str() {
-        "scribe_v1" => SpeechToTextModel::ScribeV1,
-        "scribe_v1_base" => SpeechToTextModel::ScribeV1Base,
-        _ => SpeechToTextModel::ScribeV1,
-    };
-
-    // Build request body
-    let mut body = CreateTranscriptBody::new(model, file_path.to_str().unwrap_or("audio.wav"))
-        .with_tag_audio_events(args.tag_audio_events);
-
-    if let Some(lang) = &args.language {
-        body = body.with_language_code(lang);
+    if !keyterms.is_empty() {
+        print_info(&format!("Key terms: {}", keyterms.len()));
     }
-
-    if let Some(speakers) = args.num_speakers {
-        body = body.with_num_speakers(speakers);
+    if !args.languages.is_empty() {
+        print_info(&format!("Languages: {}", args.languages.join(", ")));
     }
 
-    // Parse timestamps granularity
-    let timestamps = match args.timestamps.as_str() {
-        "none" => Granularity::None,
-        "character" => Granularity::Character,
-        _ => Granularity::Word,
-    };
-    body = body.with_timestamps_granularity(timestamps);
-
-    if args.diarize {
-        body = body.with_diarize(true);
+    // Streaming splits the audio with ffmpeg, which reads any format
+    if args.stream {
+        return stream_transcription(api_key, &args, upload.path(), &keyterms).await;
     }
 
-    // Create endpoint
-    let endpoint = CreateTranscript::new(body);
-
     // Transcribe
     let start_time = std::time::Instant::now();
-    let result = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let progress = Progress::spinner("Transcribing");
+    let result = transcribe_hinted(api_key, &args, upload.path(), &keyterms).await;
+    progress.finish();
+    let result = result?;
     let duration = start_time.elapsed();
 
     // Format output based on requested format
@@ -132,10 +243,26 @@ pub async fn execute(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
         ));
     } else {
         println!("\n{}", "Transcription:".bold().underline());
-        println!("{}", output);
+        if args.format == "txt" && has_confidence(&result.words) {
+            println!(
+                "{}",
+                highlight_confidence(&result.words, args.min_confidence)
+            );
+        } else {
+            println!("{}", output);
+        }
         print_success(&format!("Completed in {:.2}s", duration.as_secs_f64()));
     }
 
+    if let Some(report_path) = &args.report {
+        write_report(
+            report_path,
+            &file_path.display().to_string(),
+            &result.words,
+            args.min_confidence,
+        )?;
+    }
+
     // Print metadata
     print_info(&format!(
         "Detected language: {} ({:.1}% confidence)",
@@ -146,37 +273,276 @@ pub async fn execute(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
     Ok(())
 }
 
-fn format_output(
-    result: &elevenlabs_rs::endpoints::genai::speech_to_text::CreateTranscriptResponse,
-    format: &str,
-    diarize: bool,
-) -> Result<String> {
-    // Get words from result - convert types as needed
-    let words: Vec<WordInfo> = result
-        .words
+/// Transcribe with the `--languages` hints: a single hint is sent as the
+/// language, and with several the API detects the language itself unless it
+/// lands outside the list, in which case each hint is tried and the
+/// transcript with the highest mean word confidence is kept
+async fn transcribe_hinted(
+    api_key: &str,
+    args: &SpeechToTextArgs,
+    file_path: &Path,
+    keyterms: &[String],
+) -> Result<Transcript> {
+    match args.languages.as_slice() {
+        [] => transcribe(api_key, args, file_path, keyterms, args.language.as_deref()).await,
+        [only] => transcribe(api_key, args, file_path, keyterms, Some(only)).await,
+        hints => {
+            let detected = transcribe(api_key, args, file_path, keyterms, None).await?;
+            if hinted_language(&detected.language_code, hints).is_some() {
+                return Ok(detected);
+            }
+            print_info(&format!(
+                "Detected '{}', which is not in --languages; retrying with {}",
+                detected.language_code,
+                hints.join(", ")
+            ));
+            let mut best: Option<(f64, Transcript)> = None;
+            for hint in hints {
+                let result = transcribe(api_key, args, file_path, keyterms, Some(hint)).await?;
+                let score = mean_confidence(&result.words).unwrap_or(0.0);
+                if best.as_ref().is_none_or(|(top, _)| score > *top) {
+                    best = Some((score, result));
+                }
+            }
+            Ok(best.map(|(_, result)| result).unwrap_or(detected))
+        }
+    }
+}
+
+/// Send the audio file to the speech-to-text endpoint
+async fn transcribe(
+    api_key: &str,
+    args: &SpeechToTextArgs,
+    file_path: &Path,
+    keyterms: &[String],
+    language: Option<&str>,
+) -> Result<Transcript> {
+    let data = tokio::fs::read(file_path)
+        .await
+        .with_context(|| format!("Failed to read {}", file_path.display()))?;
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "audio.wav".to_string());
+
+    let timestamps = match args.timestamps.as_str() {
+        "none" | "character" => args.timestamps.as_str(),
+        _ => "word",
+    };
+    let mut form = reqwest::multipart::Form::new()
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(data).file_name(file_name),
+        )
+        .text("model_id", args.model.clone())
+        .text("tag_audio_events", args.tag_audio_events.to_string())
+        .text("timestamps_granularity", timestamps.to_string());
+    if let Some(lang) = language {
+        form = form.text("language_code", lang.to_string());
+    }
+    if let Some(speakers) = args.num_speakers {
+        form = form.text("num_speakers", speakers.to_string());
+    }
+    if args.diarize {
+        form = form.text("diarize", "true");
+    }
+    for term in keyterms {
+        form = form.text("keyterms", term.clone());
+    }
+
+    let response = create_http_client()
+        .post(format!("{}/v1/speech-to-text", api_base()))
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to send transcription request")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    read_json(response)
+        .await
+        .context("Failed to parse transcription response")
+}
+
+fn validate_languages(languages: &[String]) -> Result<()> {
+    for code in languages {
+        if !(2..=3).contains(&code.len()) || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(anyhow::anyhow!(
+                "Invalid language code '{}' in --languages (use ISO 639 codes such as en or deu)",
+                code
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The ISO 639-3 form of a language code
+fn iso639_3(code: &str) -> String {
+    let code = code.to_ascii_lowercase();
+    LANGUAGE_CODES
         .iter()
-        .map(|w| WordInfo {
-            text: w.text.clone(),
-            start: w.start.map(|f| f as f64),
-            end: w.end.map(|f| f as f64),
-            speaker_id: w.speaker_id.clone(),
-        })
+        .find(|(short, _)| *short == code)
+        .map_or(code, |(_, long)| long.to_string())
+}
+
+/// The hint, as the user spelled it, that names the detected language
+fn hinted_language<'a>(detected: &str, hints: &'a [String]) -> Option<&'a str> {
+    let detected = iso639_3(detected);
+    hints
+        .iter()
+        .find(|hint| iso639_3(hint) == detected)
+        .map(String::as_str)
+}
+
+/// Mean confidence of the spoken words, if the API returned any
+fn mean_confidence(words: &[Word]) -> Option<f64> {
+    let confidences: Vec<f64> = words
+        .iter()
+        .filter(|w| !w.is_spacing())
+        .filter_map(Word::confidence)
         .collect();
+    (!confidences.is_empty()).then(|| confidences.iter().sum::<f64>() / confidences.len() as f64)
+}
+
+/// Key terms from --keywords and --keyword, deduplicated and validated
+fn load_keyterms(args: &SpeechToTextArgs) -> Result<Vec<String>> {
+    let file_terms = match &args.keywords {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keywords file {}", path))?,
+        None => String::new(),
+    };
+    parse_keyterms(&file_terms, &args.keyword)
+}
+
+fn parse_keyterms(file_contents: &str, extra: &[String]) -> Result<Vec<String>> {
+    let mut terms: Vec<String> = Vec::new();
+    let candidates = file_contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .chain(extra.iter().map(|t| t.trim()));
+    for term in candidates {
+        if term.is_empty() || terms.iter().any(|t| t == term) {
+            continue;
+        }
+        if term.chars().count() > MAX_KEYTERM_CHARS {
+            return Err(anyhow::anyhow!(
+                "Key term '{}' is longer than {} characters",
+                term,
+                MAX_KEYTERM_CHARS
+            ));
+        }
+        terms.push(term.to_string());
+    }
+    if terms.len() > MAX_KEYTERMS {
+        return Err(anyhow::anyhow!(
+            "Too many key terms ({}); the API accepts at most {}",
+            terms.len(),
+            MAX_KEYTERMS
+        ));
+    }
+    Ok(terms)
+}
+
+fn has_confidence(words: &[Word]) -> bool {
+    words.iter().any(|w| w.logprob.is_some())
+}
+
+/// Plain text with low-confidence words colored: red below the threshold,
+/// yellow below [`HIGH_CONFIDENCE`]
+fn highlight_confidence(words: &[Word], min_confidence: f64) -> String {
+    words
+        .iter()
+        .map(|w| match w.confidence() {
+            Some(c) if !w.is_spacing() && c < min_confidence => w.text.red().to_string(),
+            Some(c) if !w.is_spacing() && c < HIGH_CONFIDENCE => w.text.yellow().to_string(),
+            _ => w.text.clone(),
+        })
+        .collect()
+}
+
+/// Group consecutive words below the threshold into review segments
+fn low_confidence_segments(words: &[Word], min_confidence: f64) -> Vec<LowConfidenceSegment> {
+    let mut segments = Vec::new();
+    let mut current: Option<LowConfidenceSegment> = None;
+
+    for word in words {
+        if word.is_spacing() {
+            if let Some(segment) = current.as_mut() {
+                segment.text.push_str(&word.text);
+            }
+            continue;
+        }
+        match word.confidence() {
+            Some(c) if c < min_confidence => {
+                let segment = current.get_or_insert_with(|| LowConfidenceSegment {
+                    start: word.start,
+                    end: word.end,
+                    text: String::new(),
+                    confidence: c,
+                    speaker_id: word.speaker_id.clone(),
+                });
+                segment.text.push_str(&word.text);
+                segment.end = word.end.or(segment.end);
+                segment.confidence = segment.confidence.min(c);
+            }
+            _ => {
+                if let Some(mut segment) = current.take() {
+                    segment.text = segment.text.trim().to_string();
+                    segments.push(segment);
+                }
+            }
+        }
+    }
+    if let Some(mut segment) = current {
+        segment.text = segment.text.trim().to_string();
+        segments.push(segment);
+    }
+    segments
+}
+
+fn write_report(path: &str, file: &str, words: &[Word], min_confidence: f64) -> Result<()> {
+    if !has_confidence(words) {
+        print_warning("The transcript has no word confidence data; the report will be empty");
+    }
+    let spoken: Vec<&Word> = words.iter().filter(|w| !w.is_spacing()).collect();
+    let low = spoken
+        .iter()
+        .filter(|w| w.confidence().is_some_and(|c| c < min_confidence))
+        .count();
+    let report = ConfidenceReport {
+        file,
+        min_confidence,
+        total_words: spoken.len(),
+        low_confidence_words: low,
+        segments: low_confidence_segments(words, min_confidence),
+    };
+    fs::write(path, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Failed to write {}", path))?;
+
+    print_info(&format!(
+        "{} of {} words below {:.0}% confidence ({} segments) -> {}",
+        low,
+        report.total_words,
+        min_confidence * 100.0,
+        report.segments.len(),
+        path.green()
+    ));
+    Ok(())
+}
+
+fn format_output(result: &Transcript, format: &str, diarize: bool) -> Result<String> {
+    let words: Vec<WordInfo> = result.words.iter().map(WordInfo::from).collect();
 
     match format {
         "json" => {
-            // Reconstruct full text from words
-            let full_text: String = result
-                .words
-                .iter()
-                .map(|w| w.text.as_str())
-                .collect::<Vec<_>>()
-                .join(" ");
-
             let output = TranscriptionJsonOutput {
-                text: full_text,
+                text: result.text.clone(),
                 language_code: result.language_code.clone(),
-                language_probability: result.language_probability as f64,
+                language_probability: result.language_probability,
                 words,
             };
 
@@ -185,51 +551,35 @@ fn format_output(
         "srt" => format_srt(&result.words, diarize),
         "vtt" => format_vtt(&result.words, diarize),
         _ => {
-            // Plain text - reconstruct from words
-            let full_text: String = result
-                .words
-                .iter()
-                .map(|w| w.text.as_str())
-                .collect::<Vec<_>>()
-                .join(" ");
-            Ok(full_text)
+            // Plain text
+            Ok(result.text.clone())
         }
     }
 }
 
-fn format_srt(
-    words: &[elevenlabs_rs::endpoints::genai::speech_to_text::Word],
-    diarize: bool,
-) -> Result<String> {
+fn format_srt(words: &[Word], diarize: bool) -> Result<String> {
+    Ok(srt_cues(words, diarize, 1).0)
+}
+
+fn format_vtt(words: &[Word], diarize: bool) -> Result<String> {
+    Ok(format!("WEBVTT\n\n{}", vtt_cues(words, diarize)))
+}
+
+/// SRT cues numbered from `first`; also returns the next cue number
+fn srt_cues(words: &[Word], diarize: bool, first: usize) -> (String, usize) {
     let mut srt = String::new();
-    let mut counter = 1;
+    let mut counter = first;
 
     for word in words {
-        let start = match word.start {
-            Some(s) => s,
-            None => continue,
-        };
-        let end = match word.end {
-            Some(e) => e,
-            None => continue,
+        let (Some(start), Some(end)) = (word.start, word.end) else {
+            continue;
         };
 
-        let start_secs = (start / 1000.0) as u64;
-        let start_ms = (start % 1000.0) as u64;
-        let end_secs = (end / 1000.0) as u64;
-        let end_ms = (end % 1000.0) as u64;
-
         srt.push_str(&format!("{}\n", counter));
         srt.push_str(&format!(
-            "{:02}:{:02}:{:02},{:03} --> {:02}:{:02}:{:02},{:03}\n",
-            start_secs / 3600,
-            (start_secs % 3600) / 60,
-            start_secs % 60,
-            start_ms,
-            end_secs / 3600,
-            (end_secs % 3600) / 60,
-            end_secs % 60,
-            end_ms
+            "{} --> {}\n",
+            cue_time(start, ','),
+            cue_time(end, ',')
         ));
 
         if diarize {
@@ -242,40 +592,22 @@ fn format_srt(
         counter += 1;
     }
 
-    Ok(srt)
+    (srt, counter)
 }
 
-fn format_vtt(
-    words: &[elevenlabs_rs::endpoints::genai::speech_to_text::Word],
-    diarize: bool,
-) -> Result<String> {
-    let mut vtt = String::from("WEBVTT\n\n");
+/// WebVTT cues without the file header
+fn vtt_cues(words: &[Word], diarize: bool) -> String {
+    let mut vtt = String::new();
 
     for word in words {
-        let start = match word.start {
-            Some(s) => s,
-            None => continue,
-        };
-        let end = match word.end {
-            Some(e) => e,
-            None => continue,
+        let (Some(start), Some(end)) = (word.start, word.end) else {
+            continue;
         };
 
-        let start_secs = (start / 1000.0) as u64;
-        let start_ms = (start % 1000.0) as u64;
-        let end_secs = (end / 1000.0) as u64;
-        let end_ms = (end % 1000.0) as u64;
-
         vtt.push_str(&format!(
-            "{:02}:{:02}:{:02}.{:03} --> {:02}:{:02}:{:02}.{:03}\n",
-            start_secs / 3600,
-            (start_secs % 3600) / 60,
-            start_secs % 60,
-            start_ms,
-            end_secs / 3600,
-            (end_secs % 3600) / 60,
-            end_secs % 60,
-            end_ms
+            "{} --> {}\n",
+            cue_time(start, '.'),
+            cue_time(end, '.')
         ));
 
         if diarize {
@@ -287,14 +619,286 @@ fn format_vtt(
         vtt.push_str(&format!("{}\n\n", word.text));
     }
 
-    Ok(vtt)
+    vtt
+}
+
+/// Subtitle timestamp (HH:MM:SS,mmm) for a time in seconds
+fn cue_time(secs: f64, separator: char) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+/// A piece of the input file, with its position in the original
+#[derive(Debug, PartialEq)]
+struct AudioChunk {
+    path: PathBuf,
+    start: f64,
+    end: f64,
+}
+
+/// One chunk's transcript in `--stream --format json` output
+#[derive(Debug, Serialize)]
+struct StreamChunkJson {
+    chunk: usize,
+    start: f64,
+    end: f64,
+    text: String,
+    language_code: String,
+    words: Vec<WordInfo>,
+}
+
+/// Transcribe `file_path` chunk by chunk, writing each transcript as it arrives
+async fn stream_transcription(
+    api_key: &str,
+    args: &SpeechToTextArgs,
+    file_path: &Path,
+    keyterms: &[String],
+) -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let progress = Progress::spinner("Splitting audio");
+    let chunks = split_audio(file_path, dir.path(), args.chunk_duration);
+    progress.finish();
+    let chunks = chunks?;
+    print_info(&format!(
+        "Transcribing {} chunk(s) of up to {}s",
+        chunks.len(),
+        args.chunk_duration
+    ));
+
+    // Keyed by language with --split-by-language, otherwise a single entry
+    let mut sinks: BTreeMap<String, TranscriptSink> = BTreeMap::new();
+    if !args.split_by_language {
+        sinks.insert(
+            String::new(),
+            TranscriptSink::open(args.output.as_deref(), &args.format)?,
+        );
+    }
+
+    let start_time = std::time::Instant::now();
+    let mut all_words = Vec::new();
+    let mut language: Option<(String, f64)> = None;
+    let mut language_chunks: BTreeMap<String, usize> = BTreeMap::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let progress = Progress::spinner(&format!(
+            "Transcribing chunk {}/{} ({}-{})",
+            i + 1,
+            chunks.len(),
+            cue_time(chunk.start, '.'),
+            cue_time(chunk.end, '.')
+        ));
+        let result = transcribe_hinted(api_key, args, &chunk.path, keyterms).await;
+        progress.finish();
+        let mut result = result.with_context(|| {
+            format!(
+                "Chunk {}/{} failed; earlier chunks were already written",
+                i + 1,
+                chunks.len()
+            )
+        })?;
+        shift_words(&mut result.words, chunk.start);
+
+        let chunk_language = hinted_language(&result.language_code, &args.languages)
+            .unwrap_or(&result.language_code)
+            .to_string();
+        *language_chunks.entry(chunk_language.clone()).or_default() += 1;
+        let key = if args.split_by_language {
+            chunk_language
+        } else {
+            String::new()
+        };
+        let sink = match sinks.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = language_path(args.output.as_deref().unwrap_or_default(), entry.key());
+                print_info(&format!("Writing '{}' to {}", entry.key(), path.green()));
+                entry.insert(TranscriptSink::open(Some(&path), &args.format)?)
+            }
+        };
+
+        let partial = match args.format.as_str() {
+            "srt" => {
+                let (cues, next) = srt_cues(&result.words, args.diarize, sink.next_cue);
+                sink.next_cue = next;
+                cues
+            }
+            "vtt" => vtt_cues(&result.words, args.diarize),
+            "json" => {
+                let line = StreamChunkJson {
+                    chunk: i + 1,
+                    start: chunk.start,
+                    end: chunk.end,
+                    text: result.text.clone(),
+                    language_code: result.language_code.clone(),
+                    words: result.words.iter().map(WordInfo::from).collect(),
+                };
+                format!("{}\n", serde_json::to_string(&line)?)
+            }
+            _ => format!("{}\n", result.text.trim()),
+        };
+        // Flush per chunk so the transcript can be read while the rest runs
+        sink.writer.write_all(partial.as_bytes())?;
+        sink.writer.flush()?;
+
+        language.get_or_insert((result.language_code, result.language_probability));
+        all_words.extend(result.words);
+    }
+
+    let duration = start_time.elapsed();
+    if args.split_by_language {
+        let files: Vec<String> = sinks
+            .keys()
+            .filter_map(|code| {
+                let output = args.output.as_deref()?;
+                Some(language_path(output, code))
+            })
+            .collect();
+        print_success(&format!(
+            "Transcription complete in {:.2}s -> {}",
+            duration.as_secs_f64(),
+            files.join(", ").green()
+        ));
+    } else {
+        match &args.output {
+            Some(output_file) => print_success(&format!(
+                "Transcription complete in {:.2}s -> {}",
+                duration.as_secs_f64(),
+                output_file.green()
+            )),
+            None => print_success(&format!("Completed in {:.2}s", duration.as_secs_f64())),
+        }
+    }
+
+    if let Some(report_path) = &args.report {
+        write_report(
+            report_path,
+            &file_path.display().to_string(),
+            &all_words,
+            args.min_confidence,
+        )?;
+    }
+
+    if language_chunks.len() > 1 {
+        let counts: Vec<String> = language_chunks
+            .iter()
+            .map(|(code, n)| format!("{} ({} chunk(s))", code.cyan(), n))
+            .collect();
+        print_info(&format!("Detected languages: {}", counts.join(", ")));
+    } else if let Some((code, probability)) = language {
+        print_info(&format!(
+            "Detected language: {} ({:.1}% confidence)",
+            code.cyan(),
+            probability * 100.0
+        ));
+    }
+
+    Ok(())
+}
+
+/// Destination of streamed transcripts, with its SRT cue numbering
+struct TranscriptSink {
+    writer: Box<dyn Write>,
+    next_cue: usize,
+}
+
+impl TranscriptSink {
+    /// Create `path` (stdout if none) and write the format's header
+    fn open(path: Option<&str>, format: &str) -> Result<Self> {
+        let mut writer: Box<dyn Write> = match path {
+            Some(path) => Box::new(
+                fs::File::create(path).with_context(|| format!("Failed to create {}", path))?,
+            ),
+            None => Box::new(std::io::stdout()),
+        };
+        if format == "vtt" {
+            writer.write_all(b"WEBVTT\n\n")?;
+        }
+        Ok(Self {
+            writer,
+            next_cue: 1,
+        })
+    }
+}
+
+/// `out.txt` with the language inserted before the extension: `out.en.txt`
+fn language_path(output: &str, language: &str) -> String {
+    let path = Path::new(output);
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(ext)) => path
+            .with_file_name(format!(
+                "{}.{}.{}",
+                stem.to_string_lossy(),
+                language,
+                ext.to_string_lossy()
+            ))
+            .display()
+            .to_string(),
+        _ => format!("{}.{}", output, language),
+    }
+}
+
+/// Move word timestamps from chunk time to file time
+fn shift_words(words: &mut [Word], offset: f64) {
+    for word in words {
+        word.start = word.start.map(|t| t + offset);
+        word.end = word.end.map(|t| t + offset);
+    }
+}
+
+/// Cut the audio track into FLAC chunks of `seconds` with ffmpeg
+fn split_audio(input: &Path, dir: &Path, seconds: u32) -> Result<Vec<AudioChunk>> {
+    let list = dir.join("chunks.csv");
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-i"])
+        .arg(input)
+        .args(["-vn", "-ac", "1", "-c:a", "flac", "-f", "segment"])
+        .args(["-segment_time", &seconds.to_string()])
+        .args(["-segment_list_type", "csv", "-segment_list"])
+        .arg(&list)
+        .arg(dir.join("chunk%04d.flac"))
+        .output()
+        .map_err(|_| anyhow::anyhow!("ffmpeg not found in PATH. Install ffmpeg to use --stream"))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let chunks = parse_segment_list(&fs::read_to_string(&list)?, dir);
+    if chunks.is_empty() {
+        return Err(anyhow::anyhow!("No audio found in {}", input.display()));
+    }
+    Ok(chunks)
+}
+
+/// Parse ffmpeg's CSV segment list (`file,start,end` per line)
+fn parse_segment_list(csv: &str, dir: &Path) -> Vec<AudioChunk> {
+    csv.lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().rsplitn(3, ',');
+            let end = fields.next()?.parse().ok()?;
+            let start = fields.next()?.parse().ok()?;
+            let name = fields.next()?.trim_matches('"');
+            Some(AudioChunk {
+                path: dir.join(name),
+                start,
+                end,
+            })
+        })
+        .collect()
 }
 
 /// Record from microphone and transcribe
 #[cfg(feature = "audio")]
 async fn record_and_transcribe(args: SpeechToTextArgs, api_key: &str) -> Result<()> {
-    use elevenlabs_rs::endpoints::genai::speech_to_text::CreateTranscriptBody;
-    use tempfile::NamedTempFile;
+    use tempfile::Builder;
 
     print_info(&format!(
         "Recording from microphone for {} seconds...",
@@ -313,43 +917,26 @@ async fn record_and_transcribe(args: SpeechToTextArgs, api_key: &str) -> Result<
     print_info("Recording complete. Saving to temporary file...");
 
     // Save recorded audio to temp file
-    let mut temp_file = NamedTempFile::new()?;
-    use std::io::Write;
+    let mut temp_file = Builder::new().suffix(".wav").tempfile()?;
     temp_file.write_all(&audio_data)?;
-    let temp_path = temp_file.path().to_str().unwrap_or("recorded_audio.wav");
+    let temp_path = temp_file.path().to_path_buf();
 
-    print_info(&format!("Transcribing '{}'...", temp_path));
+    print_info(&format!("Transcribing '{}'...", temp_path.display()));
 
-    // Create client and transcribe
-    let client = ElevenLabsClient::new(api_key);
-
-    let model = SpeechToTextModel::ScribeV1;
-
-    let body = CreateTranscriptBody::new(model, temp_path.to_string())
-        .with_tag_audio_events(args.tag_audio_events);
-
-    let endpoint = CreateTranscript::new(body);
-
-    match client.hit(endpoint).await {
+    let progress = Progress::spinner("Transcribing");
+    let keyterms = load_keyterms(&args)?;
+    let result = transcribe_hinted(api_key, &args, &temp_path, &keyterms).await;
+    progress.finish();
+    match result {
         Ok(response) => {
             // Print results based on format
             match args.format.as_str() {
                 "json" => {
-                    let words: Vec<WordInfo> = response
-                        .words
-                        .iter()
-                        .map(|w| WordInfo {
-                            text: w.text.clone(),
-                            start: w.start.map(|f| f as f64),
-                            end: w.end.map(|f| f as f64),
-                            speaker_id: w.speaker_id.clone(),
-                        })
-                        .collect();
                     let output = TranscriptionJsonOutput {
                         text: response.text.clone(),
                         language_code: response.language_code.clone(),
-                        language_probability: response.language_probability as f64,
-                        words,
+                        language_probability: response.language_probability,
+                        words: response.words.iter().map(WordInfo::from).collect(),
                     };
                     println!("{}", serde_json::to_string_pretty(&output)?);
                 }
@@ -367,3 +954,142 @@ async fn record_and_transcribe(args: SpeechToTextArgs, api_key: &str) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, confidence: Option<f64>) -> Word {
+        Word {
+            text: text.to_string(),
+            start: Some(start),
+            end: Some(start + 0.5),
+            kind: "word".to_string(),
+            speaker_id: None,
+            logprob: confidence.map(f64::ln),
+        }
+    }
+
+    fn space() -> Word {
+        Word {
+            text: " ".to_string(),
+            start: None,
+            end: None,
+            kind: "spacing".to_string(),
+            speaker_id: None,
+            logprob: None,
+        }
+    }
+
+    #[test]
+    fn test_low_confidence_segments() {
+        let words = vec![
+            word("the", 0.0, Some(0.99)),
+            space(),
+            word("quick", 1.0, Some(0.5)),
+            space(),
+            word("brwn", 2.0, Some(0.7)),
+            space(),
+            word("fox", 3.0, Some(0.98)),
+            space(),
+            word("jumps", 4.0, Some(0.2)),
+        ];
+        let segments = low_confidence_segments(&words, 0.85);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "quick brwn");
+        assert_eq!(segments[0].start, Some(1.0));
+        assert_eq!(segments[0].end, Some(2.5));
+        assert!((segments[0].confidence - 0.5).abs() < 1e-9);
+        assert_eq!(segments[1].text, "jumps");
+
+        assert!(low_confidence_segments(&words, 0.1).is_empty());
+    }
+
+    #[test]
+    fn test_parse_keyterms() {
+        let file = "# product names\nElevenLabs\n\n  Scribe  \nElevenLabs\n";
+        let terms = parse_keyterms(file, &["kubectl".to_string(), "Scribe".to_string()]).unwrap();
+        assert_eq!(terms, vec!["ElevenLabs", "Scribe", "kubectl"]);
+
+        assert!(parse_keyterms(&"x".repeat(51), &[]).is_err());
+        let many: String = (0..101).map(|i| format!("term{}\n", i)).collect();
+        assert!(parse_keyterms(&many, &[]).is_err());
+    }
+
+    #[test]
+    fn test_streamed_srt_cues_continue_across_chunks() {
+        let mut first = vec![word("hello", 0.25, None), space()];
+        let mut second = vec![word("again", 1.0, None)];
+        shift_words(&mut first, 0.0);
+        shift_words(&mut second, 60.0);
+
+        let (cues, next) = srt_cues(&first, false, 1);
+        assert_eq!(cues, "1\n00:00:00,250 --> 00:00:00,750\nhello\n\n");
+        let (cues, next) = srt_cues(&second, false, next);
+        assert_eq!(cues, "2\n00:01:01,000 --> 00:01:01,500\nagain\n\n");
+        assert_eq!(next, 3);
+        assert_eq!(cue_time(3725.5, '.'), "01:02:05.500");
+    }
+
+    #[test]
+    fn test_parse_segment_list() {
+        let dir = Path::new("/tmp/chunks");
+        let chunks = parse_segment_list(
+            "chunk0000.flac,0.000000,60.000000\nchunk0001.flac,60.000000,84.512000\n\n",
+            dir,
+        );
+        assert_eq!(
+            chunks,
+            vec![
+                AudioChunk {
+                    path: dir.join("chunk0000.flac"),
+                    start: 0.0,
+                    end: 60.0
+                },
+                AudioChunk {
+                    path: dir.join("chunk0001.flac"),
+                    start: 60.0,
+                    end: 84.512
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language_hints() {
+        let hints = vec!["en".to_string(), "DE".to_string(), "fil".to_string()];
+        assert_eq!(hinted_language("eng", &hints), Some("en"));
+        assert_eq!(hinted_language("deu", &hints), Some("DE"));
+        assert_eq!(hinted_language("fil", &hints), Some("fil"));
+        assert_eq!(hinted_language("fra", &hints), None);
+        assert!(validate_languages(&hints).is_ok());
+        assert!(validate_languages(&["english".to_string()]).is_err());
+        assert!(validate_languages(&["e1".to_string()]).is_err());
+
+        let words = vec![
+            word("hallo", 0.0, Some(0.9)),
+            space(),
+            word("welt", 1.0, Some(0.5)),
+        ];
+        assert!((mean_confidence(&words).unwrap() - 0.7).abs() < 1e-9);
+        assert_eq!(mean_confidence(&[word("hi", 0.0, None)]), None);
+    }
+
+    #[test]
+    fn test_language_path() {
+        assert_eq!(language_path("out.txt", "en"), "out.en.txt");
+        assert_eq!(
+            language_path("subs/talk.final.srt", "de"),
+            "subs/talk.final.de.srt"
+        );
+        assert_eq!(language_path("transcript", "fr"), "transcript.fr");
+    }
+
+    #[test]
+    fn test_words_without_logprob_are_not_flagged() {
+        let words = vec![word("hello", 0.0, None)];
+        assert!(!has_confidence(&words));
+        assert!(low_confidence_segments(&words, 1.0).is_empty());
+        assert_eq!(highlight_confidence(&words, 0.85), "hello");
+    }
+}
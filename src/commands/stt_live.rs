@@ -0,0 +1,375 @@
+//! Live microphone transcription (`stt live`)
+//!
+//! The microphone is captured in 100 ms frames on a blocking thread and split
+//! into utterances on silence: a frame whose RMS level is at or below
+//! `--vad-threshold` counts as silence, and `--silence-duration` of it after
+//! speech closes the utterance. Each utterance is sent to the speech-to-text
+//! endpoint on its own and printed as soon as the API answers, while capture
+//! carries on.
+
+use crate::cli::SttLiveArgs;
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::pcm::{downmix, pcm16_to_wav, samples_from_pcm16, samples_to_pcm16};
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+/// A captured chunk: 16-bit PCM, sample rate and channel count
+pub(crate) type Chunk = (Vec<u8>, u32, u16);
+
+/// Longest utterance; longer speech is cut and sent in pieces
+const MAX_UTTERANCE_SECS: f32 = 30.0;
+
+/// Utterances with less speech than this are dropped (clicks, coughs)
+const MIN_SPEECH_SECS: f32 = 0.3;
+
+/// Quiet frames kept before speech so word onsets aren't clipped
+const PRE_ROLL_FRAMES: usize = 3;
+
+/// A stretch of speech cut out of the capture
+#[derive(Debug)]
+struct Utterance {
+    samples: Vec<i16>,
+    /// Seconds from the start of the session
+    start: f64,
+    end: f64,
+}
+
+/// Splits a stream of mono frames into utterances on silence
+struct Segmenter {
+    sample_rate: u32,
+    threshold: f32,
+    silence_samples: usize,
+    max_samples: usize,
+    min_speech_samples: usize,
+    pre_roll: VecDeque<Vec<i16>>,
+    current: Vec<i16>,
+    speech: usize,
+    silence: usize,
+    /// Samples seen before `current` started
+    start: usize,
+    position: usize,
+}
+
+impl Segmenter {
+    fn new(sample_rate: u32, threshold: f32, silence_secs: f32) -> Self {
+        let samples = |secs: f32| (secs * sample_rate as f32) as usize;
+        Self {
+            sample_rate,
+            threshold,
+            silence_samples: samples(silence_secs),
+            max_samples: samples(MAX_UTTERANCE_SECS),
+            min_speech_samples: samples(MIN_SPEECH_SECS),
+            pre_roll: VecDeque::new(),
+            current: Vec::new(),
+            speech: 0,
+            silence: 0,
+            start: 0,
+            position: 0,
+        }
+    }
+
+    /// Add one frame; returns an utterance when this frame ends one
+    fn push(&mut self, frame: &[i16]) -> Option<Utterance> {
+        let loud = rms(frame) > self.threshold;
+        self.position += frame.len();
+
+        if self.current.is_empty() {
+            if !loud {
+                self.pre_roll.push_back(frame.to_vec());
+                if self.pre_roll.len() > PRE_ROLL_FRAMES {
+                    self.pre_roll.pop_front();
+                }
+                return None;
+            }
+            let pre_roll: usize = self.pre_roll.iter().map(Vec::len).sum();
+            self.start = self.position - frame.len() - pre_roll;
+            self.current = self.pre_roll.drain(..).flatten().collect();
+        }
+
+        self.current.extend_from_slice(frame);
+        if loud {
+            self.speech += frame.len();
+            self.silence = 0;
+        } else {
+            self.silence += frame.len();
+        }
+        if self.silence >= self.silence_samples || self.current.len() >= self.max_samples {
+            self.finish()
+        } else {
+            None
+        }
+    }
+
+    /// End the utterance in progress; `None` if it holds too little speech
+    fn finish(&mut self) -> Option<Utterance> {
+        let samples = std::mem::take(&mut self.current);
+        let speech = std::mem::take(&mut self.speech);
+        self.silence = 0;
+        if speech < self.min_speech_samples {
+            return None;
+        }
+        let secs = |samples: usize| samples as f64 / self.sample_rate as f64;
+        Some(Utterance {
+            start: secs(self.start),
+            end: secs(self.start + samples.len()),
+            samples,
+        })
+    }
+}
+
+/// RMS level of a frame, 0.0-1.0
+fn rms(frame: &[i16]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    ((sum / frame.len() as f64).sqrt() / i16::MAX as f64).min(1.0) as f32
+}
+
+/// `[mm:ss]` offset from the start of the session
+fn clock(secs: f64) -> String {
+    let secs = secs as u64;
+    format!("[{:02}:{:02}]", secs / 60, secs % 60)
+}
+
+pub async fn execute(args: SttLiveArgs, api_key: &str) -> Result<()> {
+    if !(0.0..=1.0).contains(&args.vad_threshold) {
+        return Err(anyhow::anyhow!(
+            "--vad-threshold must be between 0.0 and 1.0"
+        ));
+    }
+    if args.silence_duration <= 0.0 {
+        return Err(anyhow::anyhow!("--silence-duration must be greater than 0"));
+    }
+    let mut transcript = match &args.output {
+        Some(path) => Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open {}", path))?,
+        ),
+        None => None,
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let capture = start_capture(args.device.clone(), tx)?;
+
+    print_info(&format!(
+        "Listening on {}... Press Ctrl+C to stop",
+        args.device
+            .as_deref()
+            .unwrap_or("the default input device")
+            .cyan()
+    ));
+
+    let client = create_http_client();
+    let mut segmenter: Option<Segmenter> = None;
+    let mut count = 0;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        let (pcm, rate, channels) = tokio::select! {
+            frame = rx.recv() => match frame {
+                Some(frame) => frame,
+                None => {
+                    // Capture ended on its own, usually because the device failed
+                    capture.await.context("Audio capture stopped")??;
+                    break;
+                }
+            },
+            _ = &mut ctrl_c => break,
+        };
+        let frame = downmix(&samples_from_pcm16(&pcm), channels);
+        let segmenter = segmenter
+            .get_or_insert_with(|| Segmenter::new(rate, args.vad_threshold, args.silence_duration));
+        if let Some(utterance) = segmenter.push(&frame) {
+            let rate = segmenter.sample_rate;
+            count += emit(&client, api_key, &args, &utterance, rate, &mut transcript).await;
+        }
+    }
+    drop(rx);
+
+    if let Some(mut segmenter) = segmenter {
+        if let Some(utterance) = segmenter.finish() {
+            let rate = segmenter.sample_rate;
+            count += emit(&client, api_key, &args, &utterance, rate, &mut transcript).await;
+        }
+    }
+    if !is_json_mode() {
+        print_success(&format!("Transcribed {} utterance(s)", count));
+    }
+    Ok(())
+}
+
+/// Capture the microphone on a blocking thread, sending chunks until the
+/// receiver is dropped
+#[cfg(feature = "audio")]
+pub(crate) fn start_capture(
+    device: Option<String>,
+    tx: UnboundedSender<Chunk>,
+) -> Result<JoinHandle<Result<()>>> {
+    use crate::audio::audio_io;
+
+    /// Capture frame length in seconds
+    const FRAME_SECS: f32 = 0.1;
+
+    Ok(tokio::task::spawn_blocking(move || {
+        audio_io::stream_from_microphone_with_vad(
+            device.as_deref(),
+            FRAME_SECS,
+            move |pcm, rate, channels| tx.send((pcm.to_vec(), rate, channels)).is_ok(),
+            false,
+            0.0,
+            0.0,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to record audio: {}", e))
+    }))
+}
+
+#[cfg(not(feature = "audio"))]
+pub(crate) fn start_capture(
+    _device: Option<String>,
+    _tx: UnboundedSender<Chunk>,
+) -> Result<JoinHandle<Result<()>>> {
+    Err(anyhow::anyhow!(
+        "Microphone input needs the audio feature. Rebuild with --features audio"
+    ))
+}
+
+/// Transcribe and print one utterance; returns 1 if it produced text.
+/// Failures are reported and the session carries on.
+async fn emit(
+    client: &Client,
+    api_key: &str,
+    args: &SttLiveArgs,
+    utterance: &Utterance,
+    sample_rate: u32,
+    transcript: &mut Option<std::fs::File>,
+) -> usize {
+    let result = match transcribe(client, api_key, args, utterance, sample_rate).await {
+        Ok(result) => result,
+        Err(e) => {
+            print_warning(&format!(
+                "Could not transcribe {}: {:#}",
+                clock(utterance.start),
+                e
+            ));
+            return 0;
+        }
+    };
+    let text = result["text"].as_str().unwrap_or_default().trim();
+    if text.is_empty() {
+        return 0;
+    }
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            json!({
+                "start": utterance.start,
+                "end": utterance.end,
+                "text": text,
+                "language_code": result["language_code"],
+            })
+        );
+    } else {
+        println!("{} {}", clock(utterance.start).dimmed(), text);
+    }
+    if let Some(file) = transcript {
+        if let Err(e) = writeln!(file, "{}", text).and_then(|_| file.flush()) {
+            print_warning(&format!("Could not write transcript: {}", e));
+        }
+    }
+    1
+}
+
+async fn transcribe(
+    client: &Client,
+    api_key: &str,
+    args: &SttLiveArgs,
+    utterance: &Utterance,
+    sample_rate: u32,
+) -> Result<Value> {
+    let wav = pcm16_to_wav(&samples_to_pcm16(&utterance.samples), sample_rate);
+    let mut form = reqwest::multipart::Form::new()
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(wav).file_name("utterance.wav"),
+        )
+        .text("model_id", args.model.clone())
+        .text("tag_audio_events", "false");
+    if let Some(language) = &args.language {
+        form = form.text("language_code", language.clone());
+    }
+
+    let response = client
+        .post(format!("{}/v1/speech-to-text", api_base()))
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to send transcription request")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    read_json(response)
+        .await
+        .context("Failed to parse transcription response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 1_000;
+
+    fn frame(level: i16) -> Vec<i16> {
+        // One 100 ms capture frame
+        vec![level; (RATE / 10) as usize]
+    }
+
+    #[test]
+    fn test_segmenter_cuts_on_silence() {
+        let mut segmenter = Segmenter::new(RATE, 0.02, 0.3);
+        let mut utterances = Vec::new();
+        let frames = [0, 0, 0, 0, 8_000, 8_000, 8_000, 8_000, 0, 0, 0, 0, 0];
+        for level in frames {
+            utterances.extend(segmenter.push(&frame(level)));
+        }
+        assert_eq!(utterances.len(), 1);
+        let utterance = &utterances[0];
+        // Three pre-roll frames, four speech frames, three silent frames
+        assert_eq!(utterance.samples.len(), 1_000);
+        assert!((utterance.start - 0.1).abs() < 1e-9);
+        assert!((utterance.end - 1.1).abs() < 1e-9);
+        assert!(segmenter.finish().is_none());
+    }
+
+    #[test]
+    fn test_segmenter_drops_short_noise_and_flushes_at_end() {
+        let mut segmenter = Segmenter::new(RATE, 0.02, 0.3);
+        for level in [8_000, 0, 0, 0] {
+            assert!(segmenter.push(&frame(level)).is_none());
+        }
+        for level in [8_000, 8_000, 8_000, 8_000] {
+            assert!(segmenter.push(&frame(level)).is_none());
+        }
+        let utterance = segmenter.finish().unwrap();
+        assert!((utterance.start - 0.4).abs() < 1e-9);
+        assert_eq!(utterance.samples.len(), 400);
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(125.7), "[02:05]");
+    }
+}
@@ -0,0 +1,139 @@
+//! Support bundle: version info, redacted config and recent errors in one zip
+
+use crate::cli::SupportBundleArgs;
+use crate::config::Config;
+use crate::errors::{error_log_path, redact_secrets, REDACTED};
+use crate::output::{print_info, print_success};
+use crate::utils::confirm_overwrite;
+use anyhow::{Context, Result};
+use colored::*;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+
+/// Number of error log entries included in the bundle
+const BUNDLE_LOG_LINES: usize = 200;
+
+/// Environment variables reported (by presence only, never by value)
+const REPORTED_ENV_VARS: &[&str] = &[
+    "ELEVENLABS_API_KEY",
+    "ELEVENLABS_SERVE_TOKEN",
+    "NO_COLOR",
+    "TERM",
+    "SHELL",
+];
+
+pub fn execute(
+    args: SupportBundleArgs,
+    config: &Config,
+    cli_api_key: Option<&str>,
+    assume_yes: bool,
+) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let output = args
+        .output
+        .unwrap_or_else(|| format!("elevenlabs-support-{}.zip", timestamp));
+    let path = Path::new(&output);
+    if !confirm_overwrite(path, assume_yes)? {
+        print_info("Cancelled");
+        return Ok(());
+    }
+
+    let env_key = std::env::var("ELEVENLABS_API_KEY").unwrap_or_default();
+    let secrets: Vec<&str> = [
+        config.api_key.as_deref(),
+        cli_api_key,
+        Some(env_key.as_str()),
+    ]
+    .into_iter()
+    .chain(config.profiles.values().map(|p| p.api_key.as_deref()))
+    .flatten()
+    .collect();
+
+    let log = recent_errors(BUNDLE_LOG_LINES);
+    let files = [
+        ("version.txt", version_info()),
+        ("config.toml", redacted_config(config)?),
+        ("environment.txt", environment_info()),
+        (
+            "errors.log",
+            redact_secrets(log.as_deref().unwrap_or("(no errors recorded)\n"), &secrets),
+        ),
+    ];
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in &files {
+        zip.start_file(*name, options)?;
+        zip.write_all(contents.as_bytes())?;
+    }
+    zip.finish()?;
+
+    print_success(&format!("Support bundle written to {}", output.green()));
+    print_info("Secrets are redacted, but please review the contents before sharing");
+    Ok(())
+}
+
+fn version_info() -> String {
+    let features: Vec<&str> = [
+        ("mcp", cfg!(feature = "mcp")),
+        ("serve", cfg!(feature = "serve")),
+        ("audio", cfg!(feature = "audio")),
+        ("ws", cfg!(feature = "ws")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+
+    format!(
+        "elevenlabs-cli {}\nos: {}\narch: {}\nfeatures: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        if features.is_empty() {
+            "(none)".to_string()
+        } else {
+            features.join(", ")
+        }
+    )
+}
+
+/// The saved config with the API keys replaced
+fn redacted_config(config: &Config) -> Result<String> {
+    let mut config = config.clone();
+    let profile_keys = config.profiles.values_mut().map(|p| &mut p.api_key);
+    for key in std::iter::once(&mut config.api_key).chain(profile_keys) {
+        if key.is_some() {
+            *key = Some(REDACTED.to_string());
+        }
+    }
+    Ok(toml::to_string_pretty(&config)?)
+}
+
+fn environment_info() -> String {
+    REPORTED_ENV_VARS
+        .iter()
+        .map(|name| {
+            let state = if std::env::var_os(name).is_some() {
+                "set"
+            } else {
+                "unset"
+            };
+            format!("{}: {}\n", name, state)
+        })
+        .collect()
+}
+
+/// The last `lines` entries of the error log, if there is one
+fn recent_errors(lines: usize) -> Option<String> {
+    let contents = std::fs::read_to_string(error_log_path()?).ok()?;
+    let all: Vec<&str> = contents.lines().collect();
+    let recent = &all[all.len().saturating_sub(lines)..];
+    Some(format!("{}\n", recent.join("\n")))
+}
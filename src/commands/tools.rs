@@ -4,13 +4,14 @@
 //! API Reference: https://elevenlabs.io/docs/api-reference/tools
 
 use crate::cli::{ToolsArgs, ToolsCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::tools_openapi::import_openapi;
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub async fn execute(args: ToolsArgs, api_key: &str) -> Result<()> {
     let client = create_http_client();
@@ -42,10 +43,26 @@ pub async fn execute(args: ToolsArgs, api_key: &str) -> Result<()> {
             .await
         }
         ToolsCommands::Delete { tool_id } => delete_tool(&client, api_key, &tool_id).await,
+        ToolsCommands::ImportOpenapi {
+            spec,
+            filter,
+            base_url,
+            dry_run,
+        } => {
+            import_openapi(
+                &client,
+                api_key,
+                &spec,
+                &filter,
+                base_url.as_deref(),
+                dry_run,
+            )
+            .await
+        }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct ToolInfo {
     id: String,
     name: String,
@@ -57,7 +74,7 @@ struct ToolInfo {
     usage: Option<ToolUsage>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct ToolConfig {
     #[serde(rename = "type")]
     tool_type: Option<String>,
@@ -69,13 +86,13 @@ struct ToolConfig {
     description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct ToolUsage {
     #[serde(default)]
     usage_count: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct ToolsListResponse {
     tools: Vec<ToolInfo>,
     #[allow(dead_code)]
@@ -93,7 +110,7 @@ async fn list_tools(
 ) -> Result<()> {
     print_info("Fetching agent tools...");
 
-    let mut url = "https://api.elevenlabs.io/v1/convai/tools".to_string();
+    let mut url = format!("{}/v1/convai/tools", api_base());
     let mut params = Vec::new();
 
     if let Some(s) = search {
@@ -114,17 +131,20 @@ async fn list_tools(
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch tools")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let tools_response: ToolsListResponse =
-        response.json().await.context("Failed to parse response")?;
+    let tools_response: ToolsListResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&tools_response.tools)?);
+        return Ok(());
+    }
 
     if tools_response.tools.is_empty() {
         print_info("No tools found");
@@ -171,19 +191,23 @@ async fn list_tools(
 async fn get_tool(client: &Client, api_key: &str, tool_id: &str) -> Result<()> {
     print_info(&format!("Fetching tool '{}'...", tool_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/convai/tools/{}", tool_id);
+    let url = format!("{}/v1/convai/tools/{}", api_base(), tool_id);
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let tool: ToolInfo = response.json().await?;
+    let tool: ToolInfo = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&tool)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -211,16 +235,15 @@ async fn get_tool(client: &Client, api_key: &str, tool_id: &str) -> Result<()> {
 async fn delete_tool(client: &Client, api_key: &str, tool_id: &str) -> Result<()> {
     print_info(&format!("Deleting tool '{}'...", tool_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/convai/tools/{}", tool_id);
+    let url = format!("{}/v1/convai/tools/{}", api_base(), tool_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Tool '{}' deleted successfully", tool_id.green()));
@@ -246,21 +269,20 @@ async fn create_tool(
         "tool_config": schema_json
     });
 
-    let url = "https://api.elevenlabs.io/v1/convai/tools";
+    let url = format!("{}/v1/convai/tools", api_base());
     let response = client
         .post(url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to create tool")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: serde_json::Value = response.json().await.context("Failed to parse response")?;
+    let result: serde_json::Value = read_json(response).await?;
 
     println!("\n{}", "Tool Created:".bold().underline());
     println!("  Tool ID: {}", result["id"].as_str().unwrap_or("-").cyan());
@@ -298,21 +320,20 @@ async fn update_tool(
         return Err(anyhow::anyhow!("No updates specified"));
     }
 
-    let url = format!("https://api.elevenlabs.io/v1/convai/tools/{}", tool_id);
+    let url = format!("{}/v1/convai/tools/{}", api_base(), tool_id);
     let response = client
         .patch(&url)
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to update tool")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: serde_json::Value = response.json().await.context("Failed to parse response")?;
+    let result: serde_json::Value = read_json(response).await?;
 
     println!("\n{}", "Tool Updated:".bold().underline());
     println!("  Tool ID: {}", result["id"].as_str().unwrap_or("-").cyan());
@@ -0,0 +1,595 @@
+//! Server tool definitions generated from an OpenAPI spec (`tools import-openapi`)
+//!
+//! Each operation becomes one webhook tool: the operation ID names it, the
+//! summary describes it, and its path, query and JSON body parameters become
+//! the tool's parameter schemas. `$ref`s into the spec are inlined, since the
+//! tool schema has no references. Created tools are recorded in a job state
+//! file, so rerunning an import that failed partway skips them.
+
+use crate::client::{api_base, api_error, read_json, SendWithRetry};
+use crate::job_state::JobState;
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::utils::wildcard_match;
+use anyhow::{Context, Result};
+use colored::*;
+use comfy_table::Table;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// HTTP methods webhook tools can call
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Longest tool name the API accepts
+const MAX_NAME_LEN: usize = 64;
+
+/// Nesting depth at which schemas are cut off (also stops recursive `$ref`s)
+const MAX_SCHEMA_DEPTH: usize = 6;
+
+#[derive(Debug, Serialize)]
+struct ImportedTool {
+    name: String,
+    method: String,
+    url: String,
+    tool_config: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Generate tools from `spec` and create them unless `dry_run`
+pub async fn import_openapi(
+    client: &Client,
+    api_key: &str,
+    spec_path: &str,
+    filters: &[String],
+    base_url: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let spec = load_spec(Path::new(spec_path))?;
+    let mut tools = generate_tools(&spec, filters, base_url)?;
+    if tools.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No operations in {} match the filter(s)",
+            spec_path
+        ));
+    }
+
+    let canonical = std::fs::canonicalize(spec_path).unwrap_or_else(|_| spec_path.into());
+    let mut state = JobState::open("openapi", api_key, &[&canonical.to_string_lossy()]);
+    if !dry_run {
+        for tool in &mut tools {
+            if let Some(id) = state.completed(&tool.name) {
+                print_info(&format!(
+                    "Tool '{}' was created by an earlier run",
+                    tool.name.cyan()
+                ));
+                tool.id = Some(id.to_string());
+                continue;
+            }
+            print_info(&format!("Creating tool '{}'...", tool.name.cyan()));
+            match create_webhook_tool(client, api_key, &tool.tool_config).await {
+                Ok(id) => {
+                    if let Err(e) = state.mark_done(&tool.name, &id) {
+                        tool.error = Some(format!("{:#}", e));
+                    }
+                    tool.id = Some(id);
+                }
+                Err(e) => tool.error = Some(format!("{:#}", e)),
+            }
+        }
+    }
+    let failed = tools.iter().filter(|t| t.error.is_some()).count();
+    if !dry_run && failed == 0 {
+        state.finish();
+    }
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "dry_run": dry_run, "tools": tools }))?
+        );
+    } else {
+        let mut table = Table::new();
+        let mut header = vec!["Name", "Method", "URL"];
+        if !dry_run {
+            header.push("Tool ID");
+        }
+        table.set_header(header);
+        for tool in &tools {
+            let mut row = vec![tool.name.clone(), tool.method.clone(), tool.url.clone()];
+            if !dry_run {
+                row.push(match (&tool.id, &tool.error) {
+                    (Some(id), _) => id.yellow().to_string(),
+                    (None, Some(error)) => error.red().to_string(),
+                    (None, None) => String::new(),
+                });
+            }
+            table.add_row(row);
+        }
+        println!("{}", table);
+
+        if dry_run {
+            print_info(&format!(
+                "Dry run: {} tool(s) would be created; add --json to see the definitions",
+                tools.len()
+            ));
+        } else if failed == 0 {
+            print_success(&format!("Created {} tool(s)", tools.len()));
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} tool(s) could not be created",
+            failed,
+            tools.len()
+        ));
+    }
+    Ok(())
+}
+
+async fn create_webhook_tool(
+    client: &Client,
+    api_key: &str,
+    tool_config: &Value,
+) -> Result<String> {
+    let response = client
+        .post(format!("{}/v1/convai/tools", api_base()))
+        .header("xi-api-key", api_key)
+        .json(&json!({ "tool_config": tool_config }))
+        .send_with_retry()
+        .await
+        .context("Failed to create tool")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let result: Value = read_json(response).await?;
+    result["id"]
+        .as_str()
+        .map(str::to_string)
+        .context("Response has no tool ID")
+}
+
+/// Read a YAML or JSON spec
+fn load_spec(path: &Path) -> Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let spec: Value = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&text).context("Failed to parse OpenAPI spec as JSON")?
+    } else {
+        serde_yaml::from_str(&text).context("Failed to parse OpenAPI spec as YAML")?
+    };
+    if spec.get("swagger").is_some() {
+        return Err(anyhow::anyhow!(
+            "Swagger 2.0 specs are not supported; convert the spec to OpenAPI 3 first"
+        ));
+    }
+    if !spec["openapi"].as_str().is_some_and(|v| v.starts_with('3')) {
+        return Err(anyhow::anyhow!(
+            "{} is not an OpenAPI 3 spec (no 'openapi: 3.x' field)",
+            path.display()
+        ));
+    }
+    Ok(spec)
+}
+
+/// Base URL from the first server entry, with its variables filled in
+fn spec_base_url(spec: &Value) -> Option<String> {
+    let server = spec["servers"].get(0)?;
+    let mut url = server["url"].as_str()?.to_string();
+    if let Some(variables) = server["variables"].as_object() {
+        for (name, variable) in variables {
+            if let Some(default) = variable["default"].as_str() {
+                url = url.replace(&format!("{{{}}}", name), default);
+            }
+        }
+    }
+    url.starts_with("http").then_some(url)
+}
+
+fn generate_tools(
+    spec: &Value,
+    filters: &[String],
+    base_url: Option<&str>,
+) -> Result<Vec<ImportedTool>> {
+    let base_url = match base_url {
+        Some(url) => url.to_string(),
+        None => spec_base_url(spec).ok_or_else(|| {
+            anyhow::anyhow!("The spec has no absolute server URL; pass --base-url")
+        })?,
+    };
+    let base_url = base_url.trim_end_matches('/');
+
+    let mut names = HashSet::new();
+    let mut tools = Vec::new();
+    let paths = spec["paths"].as_object().into_iter().flatten();
+    for (path, item) in paths {
+        if !filters.is_empty() && !filters.iter().any(|f| wildcard_match(f, path)) {
+            continue;
+        }
+        for &method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            match build_tool(spec, path, item, method, operation, base_url) {
+                Ok(mut tool) => {
+                    tool.name = unique_name(&tool.name, &mut names);
+                    tool.tool_config["name"] = json!(tool.name);
+                    tools.push(tool);
+                }
+                Err(e) => print_warning(&format!(
+                    "Skipping {} {}: {}",
+                    method.to_uppercase(),
+                    path,
+                    e
+                )),
+            }
+        }
+    }
+    Ok(tools)
+}
+
+fn build_tool(
+    spec: &Value,
+    path: &str,
+    item: &Value,
+    method: &str,
+    operation: &Value,
+    base_url: &str,
+) -> Result<ImportedTool> {
+    let name = match operation["operationId"].as_str() {
+        Some(id) => tool_name(id),
+        None => tool_name(&format!("{}_{}", method, path)),
+    };
+    let description = ["summary", "description"]
+        .iter()
+        .filter_map(|key| operation[*key].as_str())
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+
+    let mut path_params = Map::new();
+    let mut query_properties = Map::new();
+    let mut query_required = Vec::new();
+    for parameter in operation_parameters(spec, item, operation) {
+        let Some(param_name) = parameter["name"].as_str() else {
+            continue;
+        };
+        let fallback = parameter["description"].as_str().unwrap_or(param_name);
+        let mut schema = convert_schema(spec, &parameter["schema"], fallback, 0);
+        if let Some(description) = parameter["description"].as_str() {
+            schema["description"] = json!(description);
+        }
+        match parameter["in"].as_str() {
+            Some("path") => {
+                path_params.insert(param_name.to_string(), schema);
+            }
+            Some("query") => {
+                if parameter["required"].as_bool() == Some(true) {
+                    query_required.push(json!(param_name));
+                }
+                query_properties.insert(param_name.to_string(), schema);
+            }
+            _ => {}
+        }
+    }
+
+    let mut api_schema = json!({
+        "url": format!("{}{}", base_url, path),
+        "method": method.to_uppercase(),
+    });
+    if !path_params.is_empty() {
+        api_schema["path_params_schema"] = Value::Object(path_params);
+    }
+    if !query_properties.is_empty() {
+        api_schema["query_params_schema"] = json!({
+            "properties": query_properties,
+            "required": query_required,
+        });
+    }
+
+    let body = resolve(spec, &operation["requestBody"]);
+    if let Some(content) = body["content"].as_object() {
+        let schema = content
+            .iter()
+            .find(|(media_type, _)| media_type.contains("json"))
+            .map(|(_, media)| &media["schema"])
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "request body is not JSON ({})",
+                    content.keys().cloned().collect::<Vec<_>>().join(", ")
+                )
+            })?;
+        let fallback = body["description"].as_str().unwrap_or("Request body");
+        let schema = convert_schema(spec, schema, fallback, 0);
+        if schema["type"] != "object" {
+            return Err(anyhow::anyhow!("request body is not a JSON object"));
+        }
+        api_schema["request_body_schema"] = schema;
+    }
+
+    Ok(ImportedTool {
+        method: method.to_uppercase(),
+        url: format!("{}{}", base_url, path),
+        tool_config: json!({
+            "type": "webhook",
+            "name": name,
+            "description": description,
+            "api_schema": api_schema,
+        }),
+        name,
+        id: None,
+        error: None,
+    })
+}
+
+/// Path-level parameters overridden by operation-level ones with the same name and location
+fn operation_parameters<'a>(
+    spec: &'a Value,
+    item: &'a Value,
+    operation: &'a Value,
+) -> Vec<&'a Value> {
+    let mut parameters: Vec<&Value> = Vec::new();
+    let all = item["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(operation["parameters"].as_array().into_iter().flatten());
+    for parameter in all {
+        let parameter = resolve(spec, parameter);
+        parameters.retain(|p| p["name"] != parameter["name"] || p["in"] != parameter["in"]);
+        parameters.push(parameter);
+    }
+    parameters
+}
+
+/// Follow a local `$ref` such as `#/components/schemas/Order`
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    // Bounded so reference cycles cannot loop forever
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+            Some(pointer) => value = spec.pointer(pointer).unwrap_or(&Value::Null),
+            None => break,
+        }
+    }
+    value
+}
+
+/// Convert an OpenAPI schema to the tool parameter schema: every property
+/// needs a description, and only literal, object and array types exist
+fn convert_schema(spec: &Value, schema: &Value, fallback: &str, depth: usize) -> Value {
+    let schema = resolve(spec, schema);
+    let description = ["description", "title"]
+        .iter()
+        .filter_map(|key| schema[*key].as_str())
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or(fallback);
+
+    // Composite schemas: merge allOf parts, take the first oneOf/anyOf option
+    if let Some(parts) = schema["allOf"].as_array() {
+        let mut merged = json!({ "type": "object", "properties": {}, "required": [] });
+        for part in parts {
+            let part = resolve(spec, part);
+            if let Some(properties) = part["properties"].as_object() {
+                for (key, value) in properties {
+                    merged["properties"][key] = value.clone();
+                }
+            }
+            if let Some(required) = part["required"].as_array() {
+                merged["required"]
+                    .as_array_mut()
+                    .expect("required is an array")
+                    .extend(required.iter().cloned());
+            }
+        }
+        merged["description"] = json!(description);
+        return convert_schema(spec, &merged, description, depth + 1);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema[key].get(0) {
+            return convert_schema(spec, first, description, depth + 1);
+        }
+    }
+
+    let schema_type = match &schema["type"] {
+        Value::String(t) => t.as_str(),
+        // OpenAPI 3.1 allows ["string", "null"]
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null")
+            .unwrap_or("string"),
+        _ if schema.get("properties").is_some() => "object",
+        _ if schema.get("items").is_some() => "array",
+        _ => "string",
+    };
+
+    if depth >= MAX_SCHEMA_DEPTH {
+        return json!({ "type": "string", "description": description });
+    }
+    match schema_type {
+        "object" => {
+            let mut properties = Map::new();
+            for (key, value) in schema["properties"].as_object().into_iter().flatten() {
+                properties.insert(key.clone(), convert_schema(spec, value, key, depth + 1));
+            }
+            let required: Vec<&Value> = schema["required"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|r| r.as_str().is_some_and(|r| properties.contains_key(r)))
+                .collect();
+            json!({
+                "type": "object",
+                "description": description,
+                "properties": properties,
+                "required": required,
+            })
+        }
+        "array" => json!({
+            "type": "array",
+            "description": description,
+            "items": convert_schema(spec, &schema["items"], description, depth + 1),
+        }),
+        "integer" | "number" | "boolean" => {
+            json!({ "type": schema_type, "description": description })
+        }
+        _ => {
+            let mut literal = json!({ "type": "string", "description": description });
+            if let Some(values) = schema["enum"].as_array() {
+                literal["enum"] =
+                    json!(values.iter().filter_map(Value::as_str).collect::<Vec<_>>());
+            }
+            literal
+        }
+    }
+}
+
+/// Tool name made of letters, digits, `_` and `-`, at most 64 characters
+fn tool_name(raw: &str) -> String {
+    let mut name = String::new();
+    for c in raw.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_end_matches('_');
+    name.chars().take(MAX_NAME_LEN).collect()
+}
+
+/// Add a numeric suffix when two operations produce the same name
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while !taken.insert(candidate.clone()) {
+        let suffix = format!("_{}", n);
+        let stem: String = name.chars().take(MAX_NAME_LEN - suffix.len()).collect();
+        candidate = format!("{}{}", stem, suffix);
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+servers:
+  - url: https://{region}.shop.example/api
+    variables:
+      region:
+        default: eu
+paths:
+  /v1/orders/{order_id}:
+    parameters:
+      - $ref: '#/components/parameters/OrderId'
+    get:
+      operationId: getOrder
+      summary: Look up an order
+      parameters:
+        - name: expand
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [items, customer]
+    patch:
+      description: Change the delivery address
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Address'
+  /v1/uploads:
+    post:
+      operationId: upload
+      requestBody:
+        content:
+          multipart/form-data:
+            schema:
+              type: object
+  /v2/status:
+    get:
+      operationId: getStatus
+components:
+  parameters:
+    OrderId:
+      name: order_id
+      in: path
+      required: true
+      description: The order number
+      schema:
+        type: string
+  schemas:
+    Address:
+      type: object
+      required: [street, country]
+      properties:
+        street:
+          type: string
+        country:
+          type: string
+          description: ISO country code
+        parent:
+          $ref: '#/components/schemas/Address'
+"#;
+
+    #[test]
+    fn test_generate_tools_from_spec() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let tools = generate_tools(&spec, &["/v1/*".to_string()], None).unwrap();
+        // The multipart upload is skipped, /v2 is filtered out
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["getOrder", "patch_v1_orders_order_id"]);
+
+        let get = &tools[0].tool_config;
+        assert_eq!(get["description"], "Look up an order");
+        assert_eq!(
+            get["api_schema"]["url"],
+            "https://eu.shop.example/api/v1/orders/{order_id}"
+        );
+        assert_eq!(
+            get["api_schema"]["path_params_schema"]["order_id"],
+            json!({ "type": "string", "description": "The order number" })
+        );
+        assert_eq!(
+            get["api_schema"]["query_params_schema"]["properties"]["expand"]["items"]["enum"],
+            json!(["items", "customer"])
+        );
+
+        let body = &tools[1].tool_config["api_schema"]["request_body_schema"];
+        assert_eq!(
+            tools[1].tool_config["description"],
+            "Change the delivery address"
+        );
+        assert_eq!(body["required"], json!(["street", "country"]));
+        assert_eq!(body["properties"]["street"]["description"], "street");
+        assert_eq!(
+            body["properties"]["country"]["description"],
+            "ISO country code"
+        );
+        // The recursive reference is cut off rather than followed forever
+        assert!(body["properties"]["parent"]["type"].is_string());
+    }
+
+    #[test]
+    fn test_tool_names() {
+        assert_eq!(tool_name("get /v1/orders/{id}"), "get_v1_orders_id");
+        assert_eq!(tool_name("list-items"), "list-items");
+        assert_eq!(tool_name(&"a".repeat(80)).len(), MAX_NAME_LEN);
+        let mut taken = HashSet::new();
+        assert_eq!(unique_name("getOrder", &mut taken), "getOrder");
+        assert_eq!(unique_name("getOrder", &mut taken), "getOrder_2");
+    }
+}
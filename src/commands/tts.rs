@@ -1,112 +1,759 @@
-use crate::cli::TextToSpeechArgs;
-use crate::output::{print_info, print_success};
+use crate::article::fetch_article;
+use crate::casting::Casting;
+use crate::cli::{Delivery, TextToSpeechArgs};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::config::{Config, VoiceDefaults};
+use crate::errors::{is_not_found, is_server_error};
+use crate::ledger::{self, LedgerEntry};
+use crate::output::{print_info, print_success, print_warning, Progress};
+use crate::pcm::{encode_local, linear_pcm_rate, local_rate, samples_from_pcm16};
+use crate::tags::{supports_id3, title_from_text, write_id3_tags, AudioTags};
+use crate::text_clean::clean_input_text;
 use crate::utils::{
-    confirm_overwrite, format_to_extension, generate_output_filename, get_input_text,
-    parse_output_format, validate_text_length, write_bytes_to_file,
+    confirm_overwrite, default_output_path, format_to_extension, generate_output_filename,
+    get_input_text, parse_output_format, write_bytes_to_file,
+};
+use crate::validation::{
+    preflight_tts, validate_format_for_model, validate_output_format, validate_voice_settings,
 };
-use crate::validation::validate_voice_settings;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
 use elevenlabs_rs::{
     endpoints::genai::tts::{TextToSpeech, TextToSpeechBody, TextToSpeechQuery},
     ElevenLabsClient, Model, VoiceSettings,
 };
-use std::path::Path;
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Model that routes multi-voice input to the text-to-dialogue endpoint
+const DIALOGUE_MODEL: &str = "eleven_v3";
+
+/// Longest pause a `<break>` tag accepts, in seconds
+const MAX_BREAK_SECS: f32 = 3.0;
+
+/// Models that interpret `[tag]` audio tags such as `[whispers]`
+const AUDIO_TAG_MODELS: &[&str] = &["eleven_v3"];
+
+/// Models that honor `<phoneme>` tags; others read the word normally
+const PHONEME_MODELS: &[&str] = &[
+    "eleven_flash_v2",
+    "eleven_turbo_v2",
+    "eleven_monolingual_v1",
+];
 
 pub async fn execute(
-    args: TextToSpeechArgs,
+    mut args: TextToSpeechArgs,
     api_key: &str,
     output_format: &str,
     assume_yes: bool,
 ) -> Result<()> {
     // Get input text
-    let text = get_input_text(args.text, args.file)?;
+    let text = match &args.url {
+        Some(url) => {
+            let article = fetch_article(url).await?;
+            print_info(&format!(
+                "Extracted article: {} ({} words)",
+                article.title.as_deref().unwrap_or(url).cyan(),
+                article.word_count()
+            ));
+            article.text()
+        }
+        None => get_input_text(args.text.clone(), args.file.clone())?,
+    };
+    let text = clean_input_text(&text, &args.clean_input)?;
 
-    // Validate text length
-    validate_text_length(&text)?;
+    // Check --meta before spending characters on the request
+    parse_metadata(&args.meta)?;
+
+    // Validate voice settings using validation module
+    validate_voice_settings(args.stability, args.similarity_boost, args.style)?;
+
+    let markup = TextMarkup::from_args(&args)?;
+    let also_formats = extra_formats(&args, output_format)?;
+    let casting = args.casting.as_deref().map(Casting::load).transpose()?;
+
+    // Multi-voice narration via inline `@Speaker:` markers
+    if let Some(segments) = parse_speaker_segments(&text) {
+        if !also_formats.is_empty() {
+            return Err(anyhow::anyhow!(
+                "--also-format is not supported with speaker markers"
+            ));
+        }
+        if !args.fallback_voice.is_empty() || !args.fallback_model.is_empty() {
+            return Err(anyhow::anyhow!(
+                "--fallback-voice and --fallback-model are not supported with speaker markers"
+            ));
+        }
+        return execute_multi_voice(
+            args,
+            segments,
+            &markup,
+            casting.as_ref(),
+            api_key,
+            output_format,
+            assume_yes,
+        )
+        .await;
+    }
+    if casting.is_some() {
+        return Err(anyhow::anyhow!(
+            "--casting needs `@Character:` speaker markers in the text"
+        ));
+    }
+
+    let request_text = markup.apply(&text);
+
+    // Pre-flight checks (model, format, language, text length)
+    preflight_tts(
+        &request_text,
+        &args.model,
+        output_format,
+        args.language.as_deref(),
+    )?;
+    for model in &args.fallback_model {
+        preflight_tts(
+            &request_text,
+            model,
+            output_format,
+            args.language.as_deref(),
+        )?;
+    }
 
     // Create client
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
-    // Parse model
-    let model = parse_model(&args.model);
+    print_info(&format!(
+        "Generating speech with voice '{}'...",
+        args.voice.cyan()
+    ));
+    print_info(&format!("Model: {}", args.model.yellow()));
+    print_info(&format!("Characters: {}", text.len().to_string().yellow()));
 
-    // Parse output format
-    let format = parse_output_format(output_format)?;
+    // Generate speech
+    let start_time = std::time::Instant::now();
+    let progress = Progress::spinner("Generating speech");
+    let audio = synthesize_with_fallback(&client, &mut args, &request_text, output_format).await?;
+    progress.finish();
+    let duration = start_time.elapsed();
 
-    // Validate voice settings using validation module
-    validate_voice_settings(args.stability, args.similarity_boost, args.style)?;
+    let Some(output_path) = save_output(
+        audio.clone(),
+        &args,
+        &text,
+        &args.voice,
+        output_format,
+        duration,
+        assume_yes,
+    )?
+    else {
+        return Ok(());
+    };
 
-    // Build voice settings if any provided
-    let voice_settings = if args.stability.is_some()
-        || args.similarity_boost.is_some()
-        || args.style.is_some()
-        || args.speaker_boost
-    {
-        let mut settings = VoiceSettings::default();
-        if let Some(s) = args.stability {
-            settings = settings.with_stability(s);
+    if !also_formats.is_empty() {
+        write_also_formats(
+            &client,
+            &args,
+            &text,
+            &request_text,
+            output_format,
+            &audio,
+            &also_formats,
+            &output_path,
+            assume_yes,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// `--also-format` values, validated, without duplicates or the main format
+fn extra_formats(args: &TextToSpeechArgs, output_format: &str) -> Result<Vec<String>> {
+    let mut formats: Vec<String> = Vec::new();
+    for format in &args.also_format {
+        let format = format.trim();
+        validate_output_format(format)?;
+        validate_format_for_model(format, &args.model)?;
+        if format != output_format && !formats.iter().any(|f| f == format) {
+            formats.push(format.to_string());
+        }
+    }
+    Ok(formats)
+}
+
+/// PCM rates the SDK can request; other rates fall back to MP3 there
+const SDK_PCM_RATES: &[u32] = &[8_000, 16_000, 22_050, 24_000, 44_100];
+
+/// Where the PCM for locally derived formats comes from
+#[derive(Debug, PartialEq)]
+enum PcmSource {
+    /// The main output is already PCM at this rate
+    Primary(u32),
+    /// Request PCM once at this rate
+    Request(u32),
+}
+
+/// Pick one PCM render that every locally derived format can be made from
+fn pcm_source(output_format: &str, extras: &[String]) -> Option<PcmSource> {
+    let needed = extras.iter().filter_map(|f| local_rate(f)).max()?;
+    // Anything above the highest rate the SDK returns is resampled up from it
+    let needed = needed.min(*SDK_PCM_RATES.last()?);
+    match linear_pcm_rate(output_format) {
+        Some(rate) if SDK_PCM_RATES.contains(&rate) && rate >= needed => {
+            Some(PcmSource::Primary(rate))
+        }
+        _ => SDK_PCM_RATES
+            .iter()
+            .find(|&&rate| rate >= needed)
+            .map(|&rate| PcmSource::Request(rate)),
+    }
+}
+
+/// File extension for an extra format; raw PCM and A-law get their own
+fn also_extension(format: &str) -> &str {
+    if format.starts_with("pcm_") {
+        "pcm"
+    } else if format.starts_with("alaw_") {
+        "alaw"
+    } else {
+        format_to_extension(format)
+    }
+}
+
+/// Paths for extra formats next to the main output: `speech.opus`, or
+/// `speech_pcm_16000.pcm` when two outputs would share an extension
+fn also_format_paths(primary: &Path, formats: &[String]) -> Vec<PathBuf> {
+    let stem = primary
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "speech".to_string());
+    let primary_ext = primary
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase());
+    formats
+        .iter()
+        .map(|format| {
+            let ext = also_extension(format);
+            let shared = primary_ext.as_deref() == Some(ext)
+                || formats.iter().filter(|f| also_extension(f) == ext).count() > 1;
+            let name = if shared {
+                format!("{}_{}.{}", stem, format, ext)
+            } else {
+                format!("{}.{}", stem, ext)
+            };
+            primary.with_file_name(name)
+        })
+        .collect()
+}
+
+/// Write `--also-format` outputs: PCM-based formats are derived from one PCM
+/// render (the main output when it already is PCM), encoded formats are
+/// requested again
+#[allow(clippy::too_many_arguments)]
+async fn write_also_formats(
+    client: &ElevenLabsClient,
+    args: &TextToSpeechArgs,
+    text: &str,
+    request_text: &str,
+    output_format: &str,
+    audio: &[u8],
+    formats: &[String],
+    output_path: &Path,
+    assume_yes: bool,
+) -> Result<()> {
+    let settings = voice_settings_for(args, &[&args.voice], VoiceDefaults::default());
+    let source = match pcm_source(output_format, formats) {
+        Some(PcmSource::Primary(rate)) => Some((samples_from_pcm16(audio), rate)),
+        Some(PcmSource::Request(rate)) => {
+            let format = format!("pcm_{}", rate);
+            let progress = Progress::spinner(&format!("Rendering {} for local conversion", format));
+            let pcm =
+                synthesize(client, args, &args.voice, &settings, request_text, &format).await?;
+            progress.finish();
+            Some((samples_from_pcm16(&pcm), rate))
+        }
+        None => None,
+    };
+
+    for (format, path) in formats.iter().zip(also_format_paths(output_path, formats)) {
+        let local = source
+            .as_ref()
+            .and_then(|(samples, rate)| encode_local(samples, *rate, format));
+        let bytes = match local {
+            Some(bytes) => bytes,
+            None => {
+                print_warning(&format!(
+                    "{} cannot be derived locally; requesting it again (uses characters)",
+                    format
+                ));
+                let progress = Progress::spinner(&format!("Generating {}", format));
+                let audio =
+                    synthesize(client, args, &args.voice, &settings, request_text, format).await?;
+                progress.finish();
+                audio.to_vec()
+            }
+        };
+
+        if !confirm_overwrite(&path, assume_yes)? {
+            print_info(&format!("Skipped {}", path.display()));
+            continue;
+        }
+        write_bytes_to_file(&bytes, &path)?;
+        if args.tag && supports_id3(&path) {
+            write_id3_tags(&path, &audio_tags(args, text, &args.voice))?;
+        }
+        print_success(&format!(
+            "Wrote {} -> {}",
+            format,
+            path.display().to_string().green()
+        ));
+    }
+    Ok(())
+}
+
+/// A phoneme override for one word
+#[derive(Debug, Clone, PartialEq)]
+struct Phoneme {
+    word: String,
+    alphabet: &'static str,
+    rendition: String,
+}
+
+/// Convenience markup injected into the request text (`--sentence-pause`, `--phoneme`,
+/// `--delivery`)
+#[derive(Debug, Default)]
+struct TextMarkup {
+    sentence_pause: Option<f32>,
+    phonemes: Vec<Phoneme>,
+    delivery: Option<Delivery>,
+}
+
+impl TextMarkup {
+    fn from_args(args: &TextToSpeechArgs) -> Result<Self> {
+        let sentence_pause = args
+            .sentence_pause
+            .as_deref()
+            .map(parse_pause)
+            .transpose()?;
+        let phonemes = args
+            .phoneme
+            .iter()
+            .map(|p| parse_phoneme(p))
+            .collect::<Result<Vec<_>>>()?;
+
+        if args.delivery.is_some() && !AUDIO_TAG_MODELS.contains(&args.model.as_str()) {
+            return Err(anyhow::anyhow!(
+                "--delivery uses audio tags, which '{}' does not support. Use --model {}",
+                args.model,
+                AUDIO_TAG_MODELS.join(" or --model ")
+            ));
+        }
+        if sentence_pause.is_some() && args.model == DIALOGUE_MODEL {
+            print_warning(&format!(
+                "{} does not support <break> tags; use audio tags like [pause] instead",
+                DIALOGUE_MODEL
+            ));
+        }
+        if !phonemes.is_empty() && !PHONEME_MODELS.contains(&args.model.as_str()) {
+            print_warning(&format!(
+                "Phoneme tags are only honored by {}; '{}' will read the words normally",
+                PHONEME_MODELS.join(", "),
+                args.model
+            ));
+        }
+
+        Ok(Self {
+            sentence_pause,
+            phonemes,
+            delivery: args.delivery,
+        })
+    }
+
+    /// Return the text with phoneme and break tags applied
+    fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for phoneme in &self.phonemes {
+            text = replace_word(&text, &phoneme.word, |original| {
+                format!(
+                    "<phoneme alphabet=\"{}\" ph=\"{}\">{}</phoneme>",
+                    phoneme.alphabet, phoneme.rendition, original
+                )
+            });
         }
-        if let Some(sb) = args.similarity_boost {
-            settings = settings.with_similarity_boost(sb);
+        if let Some(secs) = self.sentence_pause {
+            text = insert_sentence_breaks(&text, secs);
         }
-        if let Some(st) = args.style {
-            settings = settings.with_style(st);
+        if let Some(delivery) = self.delivery {
+            text = apply_delivery(&text, delivery);
         }
-        settings = settings.use_speaker_boost(args.speaker_boost);
-        Some(settings)
+        text
+    }
+}
+
+/// Audio tag for a delivery preset
+fn delivery_tag(delivery: Delivery) -> &'static str {
+    match delivery {
+        Delivery::Excited => "[excited]",
+        Delivery::Whisper => "[whispers]",
+        Delivery::Sad => "[sad]",
+        Delivery::Narration => "[calm]",
+    }
+}
+
+/// Prefix every non-empty line with the delivery tag so the style holds across paragraphs
+fn apply_delivery(text: &str, delivery: Delivery) -> String {
+    let tag = delivery_tag(delivery);
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() || line.trim_start().starts_with(tag) {
+                line.to_string()
+            } else {
+                format!("{} {}", tag, line.trim_start())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a pause such as `400ms`, `1.5s` or `750` (milliseconds) into seconds
+fn parse_pause(spec: &str) -> Result<f32> {
+    let spec = spec.trim();
+    let invalid = || anyhow::anyhow!("Invalid pause '{}'. Use e.g. 400ms or 1.5s", spec);
+    let secs = if let Some(ms) = spec.strip_suffix("ms") {
+        ms.trim().parse::<f32>().map_err(|_| invalid())? / 1000.0
+    } else if let Some(s) = spec.strip_suffix('s') {
+        s.trim().parse::<f32>().map_err(|_| invalid())?
     } else {
-        None
+        spec.parse::<f32>().map_err(|_| invalid())? / 1000.0
     };
 
-    // Build request body
-    let mut body = TextToSpeechBody::new(&text).with_model_id(model);
+    if !(secs > 0.0 && secs <= MAX_BREAK_SECS) {
+        return Err(anyhow::anyhow!(
+            "Pause must be between 0 and {}s, got '{}'",
+            MAX_BREAK_SECS,
+            spec
+        ));
+    }
+    Ok(secs)
+}
 
-    if let Some(settings) = voice_settings {
-        body = body.with_voice_settings(settings);
+/// Parse `word=/ipa/` (IPA) or `word=T AH0 M EY1 T OW2` (CMU Arpabet)
+fn parse_phoneme(spec: &str) -> Result<Phoneme> {
+    let (word, rendition) = spec.split_once('=').ok_or_else(|| {
+        anyhow::anyhow!("Invalid phoneme '{}'. Use word=/ipa/ or word=ARPABET", spec)
+    })?;
+    let word = word.trim();
+    let rendition = rendition.trim();
+    if word.is_empty() || word.contains(char::is_whitespace) {
+        return Err(anyhow::anyhow!(
+            "Invalid phoneme '{}': expected a single word before '='",
+            spec
+        ));
     }
 
-    if let Some(lang) = &args.language {
-        body = body.with_language_code(lang);
+    let (alphabet, rendition) = match rendition
+        .strip_prefix('/')
+        .and_then(|r| r.strip_suffix('/'))
+    {
+        Some(ipa) => ("ipa", ipa.trim()),
+        None => ("cmu-arpabet", rendition),
+    };
+    if rendition.is_empty() || rendition.contains(['"', '<', '>']) {
+        return Err(anyhow::anyhow!("Invalid phoneme rendition in '{}'", spec));
     }
 
-    if let Some(seed) = args.seed {
-        body = body.with_seed(seed as u64);
+    Ok(Phoneme {
+        word: word.to_string(),
+        alphabet,
+        rendition: rendition.to_string(),
+    })
+}
+
+/// Replace whole-word, case-insensitive occurrences of `word`, passing the original spelling
+pub(crate) fn replace_word(text: &str, word: &str, replacement: impl Fn(&str) -> String) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '\'' || c == '-';
+    let lower_text = text.to_lowercase();
+    let lower_word = word.to_lowercase();
+
+    // Lowercasing can change byte lengths for some scripts; fall back to exact matching
+    let (haystack, needle) = if lower_text.len() == text.len() && lower_word.len() == word.len() {
+        (lower_text.as_str(), lower_word.as_str())
+    } else {
+        (text, word)
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(needle) {
+        if start < last {
+            continue;
+        }
+        let end = start + needle.len();
+        let before_ok = text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !is_word_char(c));
+        let after_ok = text[end..].chars().next().is_none_or(|c| !is_word_char(c));
+        if before_ok && after_ok {
+            result.push_str(&text[last..start]);
+            result.push_str(&replacement(&text[start..end]));
+            last = end;
+        }
     }
+    result.push_str(&text[last..]);
+    result
+}
 
-    print_info(&format!(
-        "Generating speech with voice '{}'...",
-        args.voice.cyan()
-    ));
-    print_info(&format!("Model: {}", args.model.yellow()));
-    print_info(&format!("Characters: {}", text.len().to_string().yellow()));
+/// Insert `<break>` tags after sentence-ending punctuation followed by whitespace
+fn insert_sentence_breaks(text: &str, secs: f32) -> String {
+    let tag = format!(" <break time=\"{}s\" />", (secs * 1000.0).round() / 1000.0);
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        result.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            // Keep runs like "?!" or "..." together
+            if matches!(chars.peek(), Some('.' | '!' | '?')) {
+                continue;
+            }
+            let mut whitespace = String::new();
+            while let Some(&next) = chars.peek() {
+                if !next.is_whitespace() {
+                    break;
+                }
+                whitespace.push(next);
+                chars.next();
+            }
+            if !whitespace.is_empty() && chars.peek().is_some() {
+                result.push_str(&tag);
+            }
+            result.push_str(&whitespace);
+        }
+    }
+    result
+}
+
+/// Generate speech for one piece of text with the given voice
+async fn synthesize(
+    client: &ElevenLabsClient,
+    args: &TextToSpeechArgs,
+    voice: &str,
+    settings: &VoiceDefaults,
+    text: &str,
+    output_format: &str,
+) -> Result<bytes::Bytes> {
+    let model = parse_model(&args.model);
+    let format = parse_output_format(output_format)?;
 
-    // Build query with output format
-    let query = TextToSpeechQuery::default().with_output_format(format);
+    // The request is rebuilt for each attempt: the SDK consumes it
+    retry_sdk(Method::POST, || {
+        // Build request body
+        let mut body = TextToSpeechBody::new(text).with_model_id(model.clone());
 
-    // Create endpoint
-    let endpoint = TextToSpeech::new(&args.voice, body).with_query(query);
+        if let Some(settings) = build_voice_settings(settings) {
+            body = body.with_voice_settings(settings);
+        }
 
-    // Generate speech
-    let start_time = std::time::Instant::now();
-    let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
-    let duration = start_time.elapsed();
+        if let Some(lang) = &args.language {
+            body = body.with_language_code(lang);
+        }
 
+        if let Some(seed) = args.seed {
+            body = body.with_seed(seed as u64);
+        }
+
+        // Build query with output format
+        let query = TextToSpeechQuery::default().with_output_format(format.clone());
+
+        // Create endpoint
+        let endpoint = TextToSpeech::new(voice, body).with_query(query);
+
+        client.hit(endpoint)
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Which link of the fallback chain a failed request moves along
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Failover {
+    Voice,
+    Model,
+}
+
+/// A missing voice moves to the next voice; model and server errors (still
+/// failing after retries) to the next model; anything else is final
+fn failover_for(err: &anyhow::Error) -> Option<Failover> {
+    let message = err.to_string().to_lowercase();
+    if message.contains("voice_not_found") || (message.contains("voice") && is_not_found(err)) {
+        Some(Failover::Voice)
+    } else if message.contains("model") || is_server_error(err) {
+        Some(Failover::Model)
+    } else {
+        None
+    }
+}
+
+/// Generate with the first voice and model of the `--fallback-voice` /
+/// `--fallback-model` chain that works. `args` is left with the voice and
+/// model used, and the requested ones are added to its metadata when they
+/// differ, so the ledger records the fallback
+async fn synthesize_with_fallback(
+    client: &ElevenLabsClient,
+    args: &mut TextToSpeechArgs,
+    text: &str,
+    output_format: &str,
+) -> Result<bytes::Bytes> {
+    let voices: Vec<String> = std::iter::once(args.voice.clone())
+        .chain(args.fallback_voice.iter().cloned())
+        .collect();
+    let models: Vec<String> = std::iter::once(args.model.clone())
+        .chain(args.fallback_model.iter().cloned())
+        .collect();
+
+    let (mut voice, mut model) = (0, 0);
+    let audio = loop {
+        args.voice = voices[voice].clone();
+        args.model = models[model].clone();
+        let settings = voice_settings_for(args, &[&args.voice], VoiceDefaults::default());
+        let error =
+            match synthesize(client, args, &args.voice, &settings, text, output_format).await {
+                Ok(audio) => break audio,
+                Err(e) => e,
+            };
+        let next = match failover_for(&error) {
+            Some(Failover::Voice) if voice + 1 < voices.len() => {
+                voice += 1;
+                format!("voice '{}'", voices[voice])
+            }
+            Some(Failover::Model) if model + 1 < models.len() => {
+                model += 1;
+                format!("model '{}'", models[model])
+            }
+            _ => return Err(error),
+        };
+        print_warning(&format!("{}; falling back to {}", error, next));
+    };
+
+    if voice > 0 {
+        args.meta.push(format!("requested_voice={}", voices[0]));
+    }
+    if model > 0 {
+        args.meta.push(format!("requested_model={}", models[0]));
+    }
+    Ok(audio)
+}
+
+/// Settings for a voice: those stored with `voice defaults set` under the
+/// first of `voices` that has any, then `cast` (a casting file's settings for
+/// the character), with the command line flags on top
+fn voice_settings_for(
+    args: &TextToSpeechArgs,
+    voices: &[&str],
+    cast: VoiceDefaults,
+) -> VoiceDefaults {
+    let flags = VoiceDefaults {
+        stability: args.stability,
+        similarity_boost: args.similarity_boost,
+        style: args.style,
+        speaker_boost: args.speaker_boost(),
+    };
+    Config::load()
+        .unwrap_or_default()
+        .voice_defaults_for(voices)
+        .overridden_by(cast)
+        .overridden_by(flags)
+}
+
+/// Build voice settings if any were provided
+fn build_voice_settings(defaults: &VoiceDefaults) -> Option<VoiceSettings> {
+    if defaults.is_empty() {
+        return None;
+    }
+
+    let mut settings = VoiceSettings::default();
+    if let Some(s) = defaults.stability {
+        settings = settings.with_stability(s);
+    }
+    if let Some(sb) = defaults.similarity_boost {
+        settings = settings.with_similarity_boost(sb);
+    }
+    if let Some(st) = defaults.style {
+        settings = settings.with_style(st);
+    }
+    if let Some(boost) = defaults.speaker_boost {
+        settings = settings.use_speaker_boost(boost);
+    }
+    Some(settings)
+}
+
+/// `settings` for a text-to-dialogue request from each voice's settings. The
+/// endpoint takes one stability for the whole dialogue, so it is only sent
+/// when the voices that have one agree
+pub(crate) fn dialogue_settings(voices: &[VoiceDefaults]) -> Option<serde_json::Value> {
+    let mut stabilities: Vec<f32> = voices.iter().filter_map(|v| v.stability).collect();
+    stabilities.sort_by(f32::total_cmp);
+    stabilities.dedup();
+    match stabilities[..] {
+        [] => None,
+        [stability] => Some(json!({ "stability": stability })),
+        _ => {
+            print_warning(
+                "Voices have different stability settings; text-to-dialogue takes one, so none is sent",
+            );
+            None
+        }
+    }
+}
+
+/// Write generated audio, embed tags, and optionally play it
+fn save_output(
+    audio: bytes::Bytes,
+    args: &TextToSpeechArgs,
+    text: &str,
+    artist: &str,
+    output_format: &str,
+    duration: std::time::Duration,
+    assume_yes: bool,
+) -> Result<Option<PathBuf>> {
     // Determine output path
-    let output_path = args
-        .output
-        .unwrap_or_else(|| generate_output_filename("speech", format_to_extension(output_format)));
+    let output_path = match &args.output {
+        Some(output) => output.clone(),
+        None => default_output_path(
+            &generate_output_filename("speech", format_to_extension(output_format)),
+            Some(&args.voice),
+        )?,
+    };
 
     // Check for overwrite
     let path = Path::new(&output_path);
     if !confirm_overwrite(path, assume_yes)? {
         print_info("Cancelled");
-        return Ok(());
+        return Ok(None);
     }
 
     // Write audio file
     write_bytes_to_file(&audio, path)?;
 
+    let mut entry = LedgerEntry::new("tts", artist, &args.model, text.chars().count());
+    entry.output = Some(output_path.clone());
+    entry.project_id = args.project_id.clone();
+    entry.metadata = parse_metadata(&args.meta)?;
+    ledger::record(&entry);
+
+    // Embed metadata tags if requested
+    if args.tag {
+        if supports_id3(path) {
+            write_id3_tags(path, &audio_tags(args, text, artist))?;
+        } else {
+            print_warning("Skipping tags: ID3 tags are only supported for MP3 output");
+        }
+    }
+
     print_success(&format!(
         "Generated speech in {:.2}s -> {}",
         duration.as_secs_f64(),
@@ -116,9 +763,324 @@ pub async fn execute(
     // Play audio if requested
     if args.play {
         print_info("Playing audio...");
-        elevenlabs_rs::utils::play(audio).map_err(|e| anyhow::anyhow!(e))?;
+        crate::player::play_audio(&audio)?;
+    }
+
+    Ok(Some(path.to_path_buf()))
+}
+
+fn audio_tags(args: &TextToSpeechArgs, text: &str, artist: &str) -> AudioTags {
+    let mut comment = format!("model: {}", args.model);
+    if let Some(project_id) = &args.project_id {
+        comment.push_str(&format!("\nproject: {}", project_id));
+    }
+    AudioTags {
+        title: Some(title_from_text(text)),
+        artist: Some(artist.to_string()),
+        album: args.album.clone(),
+        comment: Some(comment),
+    }
+}
+
+/// `--meta KEY=VALUE` arguments as a map
+fn parse_metadata(meta: &[String]) -> Result<BTreeMap<String, String>> {
+    meta.iter().map(|m| ledger::parse_meta(m)).collect()
+}
+
+/// A narration segment assigned to one speaker
+#[derive(Debug, PartialEq)]
+struct SpeakerSegment {
+    speaker: Option<String>,
+    text: String,
+}
+
+/// Parse a `@Speaker:` marker at the start of a line
+fn parse_speaker_marker(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix('@')?;
+    let (speaker, text) = rest.split_once(':')?;
+    let speaker = speaker.trim();
+
+    let valid = !speaker.is_empty()
+        && speaker.len() <= 64
+        && speaker
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '_' || c == '-');
+
+    valid.then_some((speaker, text.trim()))
+}
+
+/// Split text on inline `@Speaker: text` markers.
+///
+/// Returns `None` when the text has no markers. Lines without a marker continue
+/// the current speaker; text before the first marker uses the default voice.
+fn parse_speaker_segments(text: &str) -> Option<Vec<SpeakerSegment>> {
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    let mut found_marker = false;
+
+    for line in text.lines() {
+        if let Some((speaker, rest)) = parse_speaker_marker(line) {
+            found_marker = true;
+            segments.push(SpeakerSegment {
+                speaker: Some(speaker.to_string()),
+                text: rest.to_string(),
+            });
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match segments.last_mut() {
+            Some(segment) => {
+                if !segment.text.is_empty() {
+                    segment.text.push(' ');
+                }
+                segment.text.push_str(line);
+            }
+            None => segments.push(SpeakerSegment {
+                speaker: None,
+                text: line.to_string(),
+            }),
+        }
     }
 
+    if !found_marker {
+        return None;
+    }
+
+    segments.retain(|s| !s.text.is_empty());
+    Some(segments)
+}
+
+#[derive(Deserialize)]
+struct VoiceListResponse {
+    voices: Vec<VoiceListEntry>,
+}
+
+#[derive(Deserialize)]
+struct VoiceListEntry {
+    voice_id: String,
+    name: String,
+}
+
+/// Map speaker names to voice IDs using the account's voice list.
+/// Names that don't match a voice are passed through as IDs.
+pub(crate) async fn resolve_speakers(
+    api_key: &str,
+    speakers: &[String],
+) -> Result<HashMap<String, String>> {
+    let client = create_http_client();
+    let response = client
+        .get(format!("{}/v1/voices", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch voices")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let voices: VoiceListResponse = read_json(response).await?;
+
+    let mut resolved = HashMap::new();
+    for speaker in speakers {
+        let voice_id = voices
+            .voices
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(speaker) || v.voice_id == *speaker)
+            .map(|v| v.voice_id.clone())
+            .unwrap_or_else(|| speaker.clone());
+        resolved.insert(speaker.clone(), voice_id);
+    }
+
+    Ok(resolved)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn execute_multi_voice(
+    args: TextToSpeechArgs,
+    mut segments: Vec<SpeakerSegment>,
+    markup: &TextMarkup,
+    casting: Option<&Casting>,
+    api_key: &str,
+    output_format: &str,
+    assume_yes: bool,
+) -> Result<()> {
+    if segments.is_empty() {
+        return Err(anyhow::anyhow!("No text found after speaker markers"));
+    }
+
+    // Title for tagging uses the plain script, requests use the marked-up text
+    let plain_text: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+    let plain_text = plain_text.join("\n");
+    let character =
+        |speaker: Option<&str>| casting.and_then(|c| c.character(speaker.unwrap_or(&args.voice)));
+    for segment in &mut segments {
+        let text = match character(segment.speaker.as_deref()) {
+            Some(cast) => cast.apply_dictionary(&segment.text),
+            None => segment.text.clone(),
+        };
+        segment.text = markup.apply(&text);
+    }
+
+    let use_dialogue = args.model == DIALOGUE_MODEL;
+
+    // Raw concatenation only works for frame-based formats without a file header
+    if !use_dialogue && output_format.starts_with("wav_") {
+        return Err(anyhow::anyhow!(
+            "Multi-voice stitching does not support '{}'. Use an mp3/pcm format or --model {}",
+            output_format,
+            DIALOGUE_MODEL
+        ));
+    }
+
+    // Pre-flight checks: the dialogue endpoint counts the full script,
+    // sequential TTS counts each segment
+    if use_dialogue {
+        let combined: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        preflight_tts(
+            &combined.join("\n"),
+            &args.model,
+            output_format,
+            args.language.as_deref(),
+        )?;
+    } else {
+        for segment in &segments {
+            preflight_tts(
+                &segment.text,
+                &args.model,
+                output_format,
+                args.language.as_deref(),
+            )?;
+        }
+    }
+
+    let mut speakers: Vec<String> = Vec::new();
+    for segment in &segments {
+        let speaker = segment.speaker.as_deref().unwrap_or(&args.voice);
+        if !speakers.iter().any(|s| s == speaker) {
+            speakers.push(speaker.to_string());
+        }
+    }
+
+    print_info(&format!(
+        "Generating multi-voice narration: {} segment(s), {} speaker(s)",
+        segments.len().to_string().yellow(),
+        speakers.len().to_string().yellow()
+    ));
+    print_info(&format!("Speakers: {}", speakers.join(", ").cyan()));
+    print_info(&format!(
+        "Mode: {}",
+        if use_dialogue {
+            "text-to-dialogue".yellow()
+        } else {
+            "sequential TTS with stitching".yellow()
+        }
+    ));
+
+    // A casting file turns character names into voices
+    let voice_name = |speaker: &str| -> String {
+        character(Some(speaker))
+            .map_or(speaker, |c| c.voice.as_str())
+            .to_string()
+    };
+    if casting.is_some() {
+        for speaker in &speakers {
+            if character(Some(speaker)).is_none() {
+                print_warning(&format!(
+                    "'{}' is not in the casting file; using it as a voice",
+                    speaker
+                ));
+            }
+        }
+    }
+    let voices: Vec<String> = speakers.iter().map(|s| voice_name(s)).collect();
+    let voice_ids = resolve_speakers(api_key, &voices).await?;
+    let voice_for = |segment: &SpeakerSegment| -> String {
+        let voice = voice_name(segment.speaker.as_deref().unwrap_or(&args.voice));
+        voice_ids.get(&voice).cloned().unwrap_or(voice)
+    };
+    let settings_for = |segment: &SpeakerSegment| -> VoiceDefaults {
+        let speaker = segment.speaker.as_deref().unwrap_or(&args.voice);
+        let cast = character(Some(speaker))
+            .map(|c| c.settings)
+            .unwrap_or_default();
+        voice_settings_for(&args, &[speaker, &voice_for(segment)], cast)
+    };
+
+    let start_time = std::time::Instant::now();
+
+    let audio = if use_dialogue {
+        let progress = Progress::spinner("Generating dialogue");
+        let inputs: Vec<_> = segments
+            .iter()
+            .map(|s| json!({ "text": s.text, "voice_id": voice_for(s) }))
+            .collect();
+
+        let mut body = json!({
+            "inputs": inputs,
+            "model_id": args.model,
+        });
+        if let Some(seed) = args.seed {
+            body["seed"] = json!(seed);
+        }
+        let settings: Vec<VoiceDefaults> = segments.iter().map(settings_for).collect();
+        if let Some(settings) = dialogue_settings(&settings) {
+            body["settings"] = settings;
+        }
+
+        let client = create_http_client();
+        let response = client
+            .post(format!("{}/v1/text-to-dialogue", api_base()))
+            .header("xi-api-key", api_key)
+            .query(&[("output_format", output_format)])
+            .json(&body)
+            .send_with_retry()
+            .await
+            .context("Failed to send dialogue request")?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let audio = response.bytes().await?;
+        progress.finish();
+        audio
+    } else {
+        let client = create_sdk_client(api_key);
+        let progress = Progress::bar(segments.len() as u64, "Generating segments");
+        let mut combined = Vec::new();
+        for segment in &segments {
+            let audio = synthesize(
+                &client,
+                &args,
+                &voice_for(segment),
+                &settings_for(segment),
+                &segment.text,
+                output_format,
+            )
+            .await?;
+            combined.extend_from_slice(&audio);
+            progress.inc(1);
+        }
+        progress.finish();
+        bytes::Bytes::from(combined)
+    };
+
+    let duration = start_time.elapsed();
+
+    save_output(
+        audio,
+        &args,
+        &plain_text,
+        &speakers.join(", "),
+        output_format,
+        duration,
+        assume_yes,
+    )?;
     Ok(())
 }
 
@@ -135,3 +1097,181 @@ fn parse_model(model: &str) -> Model {
         _ => Model::ElevenMultilingualV2,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pause() {
+        assert_eq!(parse_pause("400ms").unwrap(), 0.4);
+        assert_eq!(parse_pause("1.5s").unwrap(), 1.5);
+        assert_eq!(parse_pause("750").unwrap(), 0.75);
+        assert!(parse_pause("4s").is_err());
+        assert!(parse_pause("0ms").is_err());
+        assert!(parse_pause("soon").is_err());
+    }
+
+    #[test]
+    fn test_parse_phoneme() {
+        let ipa = parse_phoneme("tomato=/təˈmeɪtoʊ/").unwrap();
+        assert_eq!(ipa.word, "tomato");
+        assert_eq!(ipa.alphabet, "ipa");
+        assert_eq!(ipa.rendition, "təˈmeɪtoʊ");
+
+        let cmu = parse_phoneme("madison=M AE1 D IH0 S AH0 N").unwrap();
+        assert_eq!(cmu.alphabet, "cmu-arpabet");
+
+        assert!(parse_phoneme("tomato").is_err());
+        assert!(parse_phoneme("two words=/x/").is_err());
+        assert!(parse_phoneme("word=//").is_err());
+    }
+
+    #[test]
+    fn test_text_markup_apply() {
+        let markup = TextMarkup {
+            sentence_pause: Some(0.4),
+            phonemes: vec![parse_phoneme("tomato=/təˈmeɪtoʊ/").unwrap()],
+            delivery: None,
+        };
+        let text = "Tomato soup! I like tomatoes. Really?! Yes... tomato.";
+        assert_eq!(
+            markup.apply(text),
+            "<phoneme alphabet=\"ipa\" ph=\"təˈmeɪtoʊ\">Tomato</phoneme> soup! <break time=\"0.4s\" /> \
+             I like tomatoes. <break time=\"0.4s\" /> Really?! <break time=\"0.4s\" /> \
+             Yes... <break time=\"0.4s\" /> \
+             <phoneme alphabet=\"ipa\" ph=\"təˈmeɪtoʊ\">tomato</phoneme>."
+        );
+        assert_eq!(TextMarkup::default().apply(text), text);
+    }
+
+    #[test]
+    fn test_apply_delivery() {
+        assert_eq!(
+            apply_delivery("Once upon a time.\n\n  The end.", Delivery::Whisper),
+            "[whispers] Once upon a time.\n\n[whispers] The end."
+        );
+        assert_eq!(
+            apply_delivery("[excited] Already tagged", Delivery::Excited),
+            "[excited] Already tagged"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_has_no_segments() {
+        assert!(parse_speaker_segments("Hello world\nSecond line").is_none());
+        assert!(parse_speaker_segments("Email me @ home: later").is_none());
+    }
+
+    #[test]
+    fn test_parse_speaker_segments() {
+        let text = "Intro line\n@Rachel: Hello\n@Brian: Hi there\nhow are you?\n\n@Rachel: Fine";
+        let segments = parse_speaker_segments(text).unwrap();
+
+        assert_eq!(segments.len(), 4);
+        assert_eq!(segments[0].speaker, None);
+        assert_eq!(segments[0].text, "Intro line");
+        assert_eq!(segments[1].speaker.as_deref(), Some("Rachel"));
+        assert_eq!(segments[2].speaker.as_deref(), Some("Brian"));
+        assert_eq!(segments[2].text, "Hi there how are you?");
+        assert_eq!(segments[3].text, "Fine");
+    }
+
+    #[test]
+    fn test_pcm_source_for_also_formats() {
+        let formats = |list: &[&str]| list.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            pcm_source("pcm_44100", &formats(&["pcm_16000", "ulaw_8000"])),
+            Some(PcmSource::Primary(44_100))
+        );
+        assert_eq!(
+            pcm_source("mp3_44100_128", &formats(&["wav_22050", "opus_48000_64"])),
+            Some(PcmSource::Request(22_050))
+        );
+        // A lower-rate main output is not upsampled; 48 kHz comes from 44.1 kHz
+        assert_eq!(
+            pcm_source("pcm_16000", &formats(&["wav_48000"])),
+            Some(PcmSource::Request(44_100))
+        );
+        assert_eq!(
+            pcm_source("mp3_44100_128", &formats(&["opus_48000_64"])),
+            None
+        );
+
+        let wav = encode_local(&[0, 0, 0, 0], 16_000, "wav_8000").unwrap();
+        assert_eq!(wav.len(), crate::pcm::WAV_HEADER_LEN + 4);
+        assert_eq!(
+            encode_local(&[0, 0], 8_000, "ulaw_8000").unwrap(),
+            vec![0xFF; 2]
+        );
+        assert!(encode_local(&[0], 8_000, "mp3_44100_128").is_none());
+    }
+
+    #[test]
+    fn test_also_format_paths() {
+        let formats: Vec<String> = ["opus_48000_64", "pcm_16000", "pcm_8000", "wav_16000"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let paths = also_format_paths(Path::new("out/intro.wav"), &formats);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("out/intro.opus"),
+                PathBuf::from("out/intro_pcm_16000.pcm"),
+                PathBuf::from("out/intro_pcm_8000.pcm"),
+                PathBuf::from("out/intro_wav_16000.wav"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dialogue_settings_need_one_stability() {
+        let voice = |stability| VoiceDefaults {
+            stability,
+            ..Default::default()
+        };
+        assert_eq!(dialogue_settings(&[voice(None), voice(None)]), None);
+        assert_eq!(
+            dialogue_settings(&[voice(Some(0.5)), voice(None), voice(Some(0.5))]),
+            Some(json!({ "stability": 0.5 }))
+        );
+        assert_eq!(
+            dialogue_settings(&[voice(Some(0.5)), voice(Some(0.7))]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_build_voice_settings_sends_speaker_boost_when_set() {
+        let settings = |speaker_boost| VoiceDefaults {
+            stability: Some(0.5),
+            speaker_boost,
+            ..Default::default()
+        };
+        let built = build_voice_settings(&settings(None)).unwrap();
+        assert_eq!(built.use_speaker_boost, None);
+        let built = build_voice_settings(&settings(Some(false))).unwrap();
+        assert_eq!(built.use_speaker_boost, Some(false));
+    }
+
+    #[test]
+    fn test_failover_for() {
+        let failover = |message: &str| failover_for(&anyhow::anyhow!("{}", message));
+        assert_eq!(
+            failover(r#"{"detail":{"status":"voice_not_found"}}"#),
+            Some(Failover::Voice)
+        );
+        assert_eq!(
+            failover("404 Not Found: voice Rachel"),
+            Some(Failover::Voice)
+        );
+        assert_eq!(failover("503 Service Unavailable"), Some(Failover::Model));
+        assert_eq!(
+            failover("model_can_not_do_text_to_speech"),
+            Some(Failover::Model)
+        );
+        assert_eq!(failover("401 Unauthorized"), None);
+        assert_eq!(failover("quota_exceeded"), None);
+    }
+}
@@ -0,0 +1,557 @@
+//! Feed narration (`tts feed`)
+//!
+//! Reads the newest items of an RSS or Atom feed, narrates each one (fetching
+//! the full article when the feed only carries a summary) and writes tagged
+//! audio files. Items that already have a file in the output directory are
+//! skipped, so the command can run on a schedule as a personal podcast.
+
+use crate::article::{as_sentence, fetch_article};
+use crate::cli::TtsFeedArgs;
+use crate::client::{api_base, api_error, create_http_client, SendWithRetry};
+use crate::commands::tts::resolve_speakers;
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::tags::{supports_id3, write_id3_tags, AudioTags};
+use crate::text_clean::html_to_text;
+use crate::utils::{format_to_extension, write_bytes_to_file};
+use crate::validation::{
+    model_char_limit, preflight_tts, validate_format_for_model, validate_output_format,
+    validate_tts_model,
+};
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+/// Longest slug used in episode file names
+const SLUG_MAX_CHARS: usize = 60;
+
+#[derive(Debug, Default, PartialEq)]
+struct Feed {
+    title: String,
+    items: Vec<FeedItem>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct FeedItem {
+    title: String,
+    link: Option<String>,
+    author: Option<String>,
+    summary: Option<String>,
+    /// Full HTML body (`content:encoded` or Atom `content`)
+    content: Option<String>,
+    published: Option<String>,
+}
+
+/// Outcome for one feed item
+#[derive(Debug, Serialize)]
+struct EpisodeResult {
+    title: String,
+    file: String,
+    status: &'static str,
+}
+
+pub async fn execute(args: TtsFeedArgs, api_key: &str, output_format: &str) -> Result<()> {
+    // Chunks are joined byte-for-byte, which only works without a file header
+    if output_format.starts_with("wav_") {
+        return Err(anyhow::anyhow!(
+            "Feed narration does not support '{}'. Use an mp3 or pcm format",
+            output_format
+        ));
+    }
+    validate_tts_model(&args.model)?;
+    validate_output_format(output_format)?;
+    validate_format_for_model(output_format, &args.model)?;
+    if args.latest == 0 {
+        return Err(anyhow::anyhow!("--latest must be at least 1"));
+    }
+
+    let client = create_http_client();
+    print_info(&format!("Fetching feed {}...", args.url.cyan()));
+    let feed = fetch_feed(&client, &args.url).await?;
+    let items: Vec<&FeedItem> = feed.items.iter().take(args.latest).collect();
+    if items.is_empty() {
+        print_info("Feed has no items");
+        return Ok(());
+    }
+    print_info(&format!(
+        "{}: narrating {} of {} item(s)",
+        feed.title.cyan(),
+        items.len(),
+        feed.items.len()
+    ));
+
+    let out_dir = PathBuf::from(&args.out_dir);
+    std::fs::create_dir_all(&out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+    let voice_id = resolve_speakers(api_key, std::slice::from_ref(&args.voice))
+        .await?
+        .remove(&args.voice)
+        .unwrap_or_else(|| args.voice.clone());
+
+    let mut results = Vec::new();
+    for item in items {
+        let path = out_dir.join(format!(
+            "{}.{}",
+            episode_name(item),
+            format_to_extension(output_format)
+        ));
+        let mut result = EpisodeResult {
+            title: item.title.clone(),
+            file: path.display().to_string(),
+            status: "created",
+        };
+
+        if path.exists() && !args.force {
+            result.status = "exists";
+        } else if let Err(e) = narrate_item(
+            &client,
+            api_key,
+            &args,
+            &voice_id,
+            &feed,
+            item,
+            &path,
+            output_format,
+        )
+        .await
+        {
+            print_warning(&format!("Failed to narrate '{}': {:#}", item.title, e));
+            result.status = "failed";
+        }
+        results.push(result);
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        for r in results.iter().filter(|r| r.status == "exists") {
+            print_info(&format!("Already narrated: {}", r.file));
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.status == "failed").count();
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "Narration failed for {} of {} item(s)",
+            failed,
+            results.len()
+        ));
+    }
+    if !is_json_mode() {
+        print_success(&format!(
+            "Narrated {} new episode(s) -> {}",
+            results.iter().filter(|r| r.status == "created").count(),
+            out_dir.display().to_string().green()
+        ));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn narrate_item(
+    client: &Client,
+    api_key: &str,
+    args: &TtsFeedArgs,
+    voice_id: &str,
+    feed: &Feed,
+    item: &FeedItem,
+    path: &Path,
+    output_format: &str,
+) -> Result<()> {
+    print_info(&format!("Narrating '{}'...", item.title.cyan()));
+    let text = item_text(item, args.summary_only).await?;
+    let chunks = split_text(&text, model_char_limit(&args.model));
+
+    let progress = Progress::bar(chunks.len() as u64, "Generating audio");
+    let mut audio = Vec::new();
+    for chunk in &chunks {
+        preflight_tts(chunk, &args.model, output_format, None)?;
+        audio.extend(
+            synthesize(client, api_key, voice_id, &args.model, chunk, output_format).await?,
+        );
+        progress.inc(1);
+    }
+    progress.finish();
+    write_bytes_to_file(&audio, path)?;
+
+    if supports_id3(path) {
+        let tags = AudioTags {
+            title: Some(item.title.clone()),
+            artist: Some(item.author.clone().unwrap_or_else(|| feed.title.clone())),
+            album: Some(feed.title.clone()),
+            comment: Some(
+                [
+                    item.link.as_deref(),
+                    Some(&format!("model: {}", args.model)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ),
+        };
+        write_id3_tags(path, &tags)?;
+    }
+    print_success(&format!(
+        "{} ({} characters) -> {}",
+        item.title,
+        text.chars().count(),
+        path.display().to_string().green()
+    ));
+    Ok(())
+}
+
+async fn fetch_feed(client: &Client, url: &str) -> Result<Feed> {
+    let response = client
+        .get(url)
+        .header(
+            reqwest::header::USER_AGENT,
+            concat!("elevenlabs-cli/", env!("CARGO_PKG_VERSION")),
+        )
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch {}: HTTP {}",
+            url,
+            response.status()
+        ));
+    }
+    let body = response.text().await.context("Failed to read feed")?;
+    parse_feed(&body)
+}
+
+fn parse_feed(xml: &str) -> Result<Feed> {
+    let doc = roxmltree::Document::parse(xml).context("Feed is not valid XML")?;
+    let root = doc.root_element();
+
+    match root.tag_name().name() {
+        // RSS 2.0 keeps items in <channel>; RSS 1.0 (RDF) next to it
+        "rss" | "RDF" => {
+            let channel = child(root, "channel");
+            let items = channel
+                .into_iter()
+                .chain(std::iter::once(root))
+                .flat_map(|parent| parent.children().filter(|n| n.has_tag_name("item")))
+                .map(|item| FeedItem {
+                    title: child_text(item, "title").unwrap_or_default(),
+                    link: child_text(item, "link"),
+                    author: child_text(item, "creator").or_else(|| child_text(item, "author")),
+                    summary: child_text(item, "description"),
+                    content: child_text(item, "encoded"),
+                    published: child_text(item, "pubDate").or_else(|| child_text(item, "date")),
+                })
+                .collect();
+            Ok(Feed {
+                title: channel
+                    .and_then(|c| child_text(c, "title"))
+                    .unwrap_or_default(),
+                items,
+            })
+        }
+        "feed" => {
+            let items = root
+                .children()
+                .filter(|n| n.has_tag_name("entry"))
+                .map(|entry| FeedItem {
+                    title: child_text(entry, "title").unwrap_or_default(),
+                    link: entry
+                        .children()
+                        .filter(|n| n.has_tag_name("link"))
+                        .find(|n| n.attribute("rel").is_none_or(|rel| rel == "alternate"))
+                        .and_then(|n| n.attribute("href"))
+                        .map(str::to_string),
+                    author: child(entry, "author").and_then(|a| child_text(a, "name")),
+                    summary: child_text(entry, "summary"),
+                    content: child_text(entry, "content"),
+                    published: child_text(entry, "published")
+                        .or_else(|| child_text(entry, "updated")),
+                })
+                .collect();
+            Ok(Feed {
+                title: child_text(root, "title").unwrap_or_default(),
+                items,
+            })
+        }
+        other => Err(anyhow::anyhow!(
+            "Not an RSS or Atom feed (root element <{}>)",
+            other
+        )),
+    }
+}
+
+/// First child element with the given local name
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// Trimmed text of the first child element with the given local name
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Narration text: the title, then the full article when available
+async fn item_text(item: &FeedItem, summary_only: bool) -> Result<String> {
+    let summary = || item.summary.as_deref().map(html_to_text);
+    let body = if summary_only {
+        summary()
+    } else if let Some(content) = &item.content {
+        Some(html_to_text(content))
+    } else if let Some(link) = &item.link {
+        match fetch_article(link).await {
+            Ok(article) => Some(article.paragraphs.join("\n\n")),
+            Err(e) => {
+                print_warning(&format!("{:#}; reading the feed summary instead", e));
+                summary()
+            }
+        }
+    } else {
+        summary()
+    };
+
+    let body = body
+        .filter(|b| !b.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Item has no text (no content, article link or summary)"))?;
+    Ok(if item.title.is_empty() {
+        body
+    } else {
+        format!("{}\n\n{}", as_sentence(&item.title), body)
+    })
+}
+
+/// Split text into request-sized pieces at paragraph, then sentence, boundaries
+fn split_text(text: &str, limit: usize) -> Vec<String> {
+    let mut pieces: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    let mut push = |part: &str, separator: &str, current: &mut String| {
+        if !current.is_empty()
+            && current.chars().count() + separator.len() + part.chars().count() > limit
+        {
+            pieces.push(std::mem::take(current));
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+        }
+        current.push_str(part);
+    };
+
+    for paragraph in text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+        if paragraph.chars().count() <= limit {
+            push(paragraph, "\n\n", &mut current);
+            continue;
+        }
+        for sentence in split_sentences(paragraph) {
+            if sentence.chars().count() <= limit {
+                push(sentence, " ", &mut current);
+                continue;
+            }
+            // A single sentence over the limit is cut at word boundaries
+            for word in sentence.split_whitespace() {
+                let word: String = word.chars().take(limit).collect();
+                push(&word, " ", &mut current);
+            }
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_some_and(|(_, n)| n.is_whitespace()) {
+            sentences.push(text[start..=i].trim());
+            start = i + 1;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+async fn synthesize(
+    client: &Client,
+    api_key: &str,
+    voice_id: &str,
+    model: &str,
+    text: &str,
+    output_format: &str,
+) -> Result<Vec<u8>> {
+    let url = format!("{}/v1/text-to-speech/{}", api_base(), voice_id);
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .query(&[("output_format", output_format)])
+        .json(&json!({ "text": text, "model_id": model }))
+        .send_with_retry()
+        .await
+        .context("Failed to send TTS request")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// File name for an item: publication date (when known) and a title slug
+fn episode_name(item: &FeedItem) -> String {
+    let mut slug = String::new();
+    for c in item.title.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let mut slug: String = slug.chars().take(SLUG_MAX_CHARS).collect();
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    if slug.is_empty() {
+        slug = "episode".to_string();
+    }
+    match item.published.as_deref().and_then(feed_date) {
+        Some(date) => format!("{}-{}", date, slug),
+        None => slug,
+    }
+}
+
+/// YYYY-MM-DD from an RFC 822 (RSS) or RFC 3339 (Atom) date
+fn feed_date(value: &str) -> Option<String> {
+    let value = value.trim();
+    // RFC 3339: 2025-06-10T12:00:00Z
+    if value.len() >= 10 && value.as_bytes()[4] == b'-' && value.as_bytes()[7] == b'-' {
+        let date = &value[..10];
+        return date
+            .chars()
+            .enumerate()
+            .all(|(i, c)| {
+                if i == 4 || i == 7 {
+                    c == '-'
+                } else {
+                    c.is_ascii_digit()
+                }
+            })
+            .then(|| date.to_string());
+    }
+    // RFC 822: Tue, 10 Jun 2025 12:00:00 +0000
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let parts: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|p| !p.is_empty())
+        .collect();
+    let day_index = parts.iter().position(|p| p.parse::<u32>().is_ok())?;
+    let day: u32 = parts[day_index].parse().ok()?;
+    let month_name = parts.get(day_index + 1)?.to_lowercase();
+    let month = MONTHS.iter().position(|m| month_name.starts_with(m))?;
+    let year: u32 = parts.get(day_index + 2)?.parse().ok()?;
+    (1..=31)
+        .contains(&day)
+        .then(|| format!("{:04}-{:02}-{:02}", year, month + 1, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/"
+     xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Example Blog</title>
+    <item>
+      <title>Second post</title>
+      <link>https://blog.example/2</link>
+      <dc:creator>Ada</dc:creator>
+      <pubDate>Tue, 10 Jun 2025 12:00:00 +0000</pubDate>
+      <description>Short summary</description>
+      <content:encoded><![CDATA[<p>Full <b>body</b></p>]]></content:encoded>
+    </item>
+    <item><title>First post</title><link>https://blog.example/1</link></item>
+  </channel>
+</rss>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title, "Example Blog");
+        assert_eq!(feed.items.len(), 2);
+        let item = &feed.items[0];
+        assert_eq!(item.author.as_deref(), Some("Ada"));
+        assert_eq!(item.content.as_deref(), Some("<p>Full <b>body</b></p>"));
+        assert_eq!(episode_name(item), "2025-06-10-second-post");
+        assert_eq!(episode_name(&feed.items[1]), "first-post");
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom Site</title>
+  <entry>
+    <title>Hello &amp; welcome</title>
+    <link rel="self" href="https://site.example/self"/>
+    <link href="https://site.example/hello"/>
+    <author><name>Grace</name></author>
+    <updated>2025-01-02T03:04:05Z</updated>
+    <summary>Hi</summary>
+  </entry>
+</feed>"#;
+        let feed = parse_feed(xml).unwrap();
+        let item = &feed.items[0];
+        assert_eq!(item.link.as_deref(), Some("https://site.example/hello"));
+        assert_eq!(item.author.as_deref(), Some("Grace"));
+        assert_eq!(episode_name(item), "2025-01-02-hello-welcome");
+        assert!(parse_feed("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_split_text_respects_limit() {
+        let text = "One two three.\n\nFour five six. Seven eight nine.\n\nTen.";
+        let pieces = split_text(text, 20);
+        assert!(
+            pieces.iter().all(|p| p.chars().count() <= 20),
+            "{:?}",
+            pieces
+        );
+        assert_eq!(
+            pieces,
+            vec![
+                "One two three.",
+                "Four five six.",
+                "Seven eight nine.",
+                "Ten."
+            ]
+        );
+        assert_eq!(split_text(text, 1000).len(), 1);
+    }
+
+    #[test]
+    fn test_feed_date() {
+        assert_eq!(
+            feed_date("Mon, 3 Mar 2025 08:00:00 GMT").as_deref(),
+            Some("2025-03-03")
+        );
+        assert_eq!(feed_date("2024-12-31").as_deref(), Some("2024-12-31"));
+        assert_eq!(feed_date("yesterday"), None);
+    }
+}
@@ -0,0 +1,438 @@
+//! Interactive TTS over one websocket (`tts session`)
+//!
+//! Opens the multi-context `multi-stream-input` websocket once and speaks each
+//! input line in a context of its own, so an utterance costs a few messages on
+//! an open connection instead of a new HTTPS request. The server closes idle
+//! connections after `inactivity_timeout`; the session reconnects on the next
+//! line when that happens.
+
+use crate::cli::TtsSessionArgs;
+use crate::client::{ws_api_error, ws_base, ws_handshake_error, ws_request_id};
+use crate::commands::tts::resolve_speakers;
+use crate::output::{print_info, print_success, print_warning};
+use crate::utils::{format_to_extension, write_bytes_to_file};
+use crate::validation::{
+    validate_format_for_model, validate_output_format, validate_tts_model, validate_voice_settings,
+};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use colored::*;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_tungstenite::tungstenite::Error as WsError;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type SessionStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+const CONNECT_TIMEOUT_SECS: u64 = 30;
+
+/// Seconds the server keeps an idle connection open (the API maximum)
+const INACTIVITY_TIMEOUT_SECS: u64 = 180;
+
+/// Longest wait for the next message while an utterance is generating
+const UTTERANCE_TIMEOUT_SECS: u64 = 60;
+
+/// Input line that ends the session
+const QUIT_COMMAND: &str = "/quit";
+
+/// A message from a TTS websocket
+#[derive(Debug, PartialEq)]
+pub enum ServerEvent {
+    Audio { context: String, audio: Vec<u8> },
+    Final { context: String },
+    Other,
+}
+
+/// Parse a server message; API errors become `Err`, tagged with the
+/// connection's `request_id`
+pub fn parse_server_message(text: &str, request_id: Option<&str>) -> Result<ServerEvent> {
+    let message: Value = serde_json::from_str(text).context("Invalid websocket message")?;
+    if let Some(error) = message.get("error").filter(|e| !e.is_null()) {
+        let detail = message
+            .get("message")
+            .and_then(Value::as_str)
+            .map(|m| format!(": {}", m))
+            .unwrap_or_default();
+        return Err(ws_api_error(&format!("{}{}", error, detail), request_id));
+    }
+
+    let field = |camel: &str, snake: &str| message.get(camel).or_else(|| message.get(snake));
+    let context = field("contextId", "context_id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if let Some(audio) = field("audio", "audio_base_64")
+        .and_then(Value::as_str)
+        .filter(|a| !a.is_empty())
+    {
+        let audio = general_purpose::STANDARD
+            .decode(audio)
+            .map_err(|e| anyhow::anyhow!("Failed to decode audio: {}", e))?;
+        return Ok(ServerEvent::Audio { context, audio });
+    }
+    if field("isFinal", "is_final").and_then(Value::as_bool) == Some(true) {
+        return Ok(ServerEvent::Final { context });
+    }
+    Ok(ServerEvent::Other)
+}
+
+/// One spoken line
+struct Utterance {
+    audio: Vec<u8>,
+    first_audio: Option<Duration>,
+    elapsed: Duration,
+}
+
+struct Session {
+    url: String,
+    api_key: String,
+    voice_settings: Option<Value>,
+    stream: Option<SessionStream>,
+    /// Request ID of the current connection's handshake
+    request_id: Option<String>,
+}
+
+impl Session {
+    async fn connect(&mut self) -> Result<()> {
+        let mut request = self
+            .url
+            .as_str()
+            .into_client_request()
+            .context("Invalid websocket URL")?;
+        request.headers_mut().insert(
+            "xi-api-key",
+            self.api_key
+                .parse()
+                .context("API key is not a valid header value")?,
+        );
+        let connected = tokio::time::timeout(
+            Duration::from_secs(CONNECT_TIMEOUT_SECS),
+            connect_async(request),
+        )
+        .await
+        .context("Connection timeout")?;
+        let (stream, response) = match connected {
+            Ok(connected) => connected,
+            Err(WsError::Http(response)) => return Err(ws_handshake_error(&response)),
+            Err(e) => return Err(e).context("Failed to connect to ElevenLabs WebSocket"),
+        };
+        self.request_id = ws_request_id(&response);
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    /// Speak one line, reconnecting once if the connection was dropped
+    async fn speak(
+        &mut self,
+        context: &str,
+        text: &str,
+        on_audio: &mut impl FnMut(&[u8]),
+    ) -> Result<Utterance> {
+        for _ in 0..2 {
+            if self.stream.is_none() {
+                print_info("Reconnecting...");
+                self.connect().await?;
+            }
+            match self.try_speak(context, text, on_audio).await? {
+                Some(utterance) => return Ok(utterance),
+                None => self.stream = None,
+            }
+        }
+        Err(anyhow::anyhow!("Connection lost while speaking"))
+    }
+
+    /// Send the line in a fresh context and collect its audio; `None` when the
+    /// connection turned out to be closed before any audio arrived
+    async fn try_speak(
+        &mut self,
+        context: &str,
+        text: &str,
+        on_audio: &mut impl FnMut(&[u8]),
+    ) -> Result<Option<Utterance>> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(None);
+        };
+        let start = Instant::now();
+
+        let mut init = json!({ "text": " ", "context_id": context });
+        if let Some(settings) = &self.voice_settings {
+            init["voice_settings"] = settings.clone();
+        }
+        let messages = [
+            init,
+            json!({ "text": format!("{} ", text), "context_id": context, "flush": true }),
+            json!({ "context_id": context, "close_context": true }),
+        ];
+        for message in messages {
+            if stream
+                .send(Message::Text(message.to_string()))
+                .await
+                .is_err()
+            {
+                return Ok(None);
+            }
+        }
+
+        let mut audio = Vec::new();
+        let mut first_audio = None;
+        loop {
+            let message =
+                tokio::time::timeout(Duration::from_secs(UTTERANCE_TIMEOUT_SECS), stream.next())
+                    .await
+                    .context("Timed out waiting for audio")?;
+            let text = match message {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    if audio.is_empty() {
+                        return Ok(None);
+                    }
+                    return Err(anyhow::anyhow!("Connection closed mid-utterance"));
+                }
+                Some(Ok(_)) => continue,
+            };
+            match parse_server_message(&text, self.request_id.as_deref())? {
+                ServerEvent::Audio {
+                    context: c,
+                    audio: chunk,
+                } if c.is_empty() || c == context => {
+                    first_audio.get_or_insert_with(|| start.elapsed());
+                    on_audio(&chunk);
+                    audio.extend_from_slice(&chunk);
+                }
+                ServerEvent::Final { context: c } if c.is_empty() || c == context => break,
+                _ => {}
+            }
+        }
+
+        Ok(Some(Utterance {
+            audio,
+            first_audio,
+            elapsed: start.elapsed(),
+        }))
+    }
+
+    /// Drain messages while idle; notices when the server closes the connection
+    async fn idle_message(&mut self) {
+        let Some(stream) = self.stream.as_mut() else {
+            return std::future::pending().await;
+        };
+        match stream.next().await {
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => self.stream = None,
+            _ => {}
+        }
+    }
+
+    async fn close(&mut self) {
+        if let Some(mut stream) = self.stream.take() {
+            let _ = stream
+                .send(Message::Text(json!({ "close_socket": true }).to_string()))
+                .await;
+            let _ = stream.close(None).await;
+        }
+    }
+}
+
+fn prompt(interactive: bool) {
+    if interactive {
+        eprint!("{} ", ">".cyan());
+        let _ = std::io::stderr().flush();
+    }
+}
+
+pub async fn execute(args: TtsSessionArgs, api_key: &str, output_format: &str) -> Result<()> {
+    validate_tts_model(&args.model)?;
+    validate_output_format(output_format)?;
+    validate_format_for_model(output_format, &args.model)?;
+    validate_voice_settings(args.stability, args.similarity_boost, None)?;
+
+    let out_dir = args.out_dir.as_ref().map(PathBuf::from);
+    if let Some(dir) = &out_dir {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    let voice_id = resolve_speakers(api_key, std::slice::from_ref(&args.voice))
+        .await?
+        .remove(&args.voice)
+        .unwrap_or_else(|| args.voice.clone());
+
+    let voice_settings = (args.stability.is_some() || args.similarity_boost.is_some()).then(|| {
+        json!({
+            "stability": args.stability.unwrap_or(0.5),
+            "similarity_boost": args.similarity_boost.unwrap_or(0.75),
+        })
+    });
+    let mut session = Session {
+        url: format!(
+            "{}/v1/text-to-speech/{}/multi-stream-input?model_id={}&output_format={}&inactivity_timeout={}",
+            ws_base(),
+            voice_id,
+            args.model,
+            output_format,
+            INACTIVITY_TIMEOUT_SECS
+        ),
+        api_key: api_key.to_string(),
+        voice_settings,
+        stream: None,
+        request_id: None,
+    };
+
+    print_info(&format!(
+        "Connecting with voice '{}' ({})...",
+        args.voice.cyan(),
+        args.model.yellow()
+    ));
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        crate::prompt::ensure_input_allowed("lines to speak; pipe them on stdin")?;
+    }
+    session.connect().await?;
+
+    if interactive {
+        print_info(&format!(
+            "Type a line and press Enter to speak it; {} or Ctrl-D ends the session",
+            QUIT_COMMAND
+        ));
+    }
+
+    // Chunks only decode on their own as MP3, and --fx needs the whole clip
+    #[cfg(feature = "audio")]
+    let stream_playback = !args.no_play
+        && output_format.starts_with("mp3_")
+        && !crate::player::has_playback_effects();
+    let extension = format_to_extension(output_format);
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut spoken = 0usize;
+    let mut first_audio_total = Duration::ZERO;
+
+    loop {
+        prompt(interactive);
+        let line = loop {
+            tokio::select! {
+                line = lines.next_line() => break line.context("Failed to read input")?,
+                _ = session.idle_message() => {}
+            }
+        };
+        let Some(line) = line else {
+            break;
+        };
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if text == QUIT_COMMAND {
+            break;
+        }
+
+        let number = spoken + 1;
+        #[cfg(feature = "audio")]
+        let player = if stream_playback {
+            crate::audio::audio_io::StreamingPlayer::new().ok()
+        } else {
+            None
+        };
+        #[cfg(feature = "audio")]
+        let mut on_audio = |chunk: &[u8]| {
+            if let Some(player) = &player {
+                let _ = player.send_chunk(chunk);
+            }
+        };
+        #[cfg(not(feature = "audio"))]
+        let mut on_audio = |_: &[u8]| {};
+        let utterance = session
+            .speak(&format!("utterance-{}", number), text, &mut on_audio)
+            .await?;
+        spoken = number;
+
+        #[cfg(feature = "audio")]
+        let streamed = match player {
+            Some(player) => {
+                if let Err(e) = player.finish() {
+                    print_warning(&format!("Playback error: {}", e));
+                }
+                true
+            }
+            None => false,
+        };
+        #[cfg(not(feature = "audio"))]
+        let streamed = false;
+        if utterance.audio.is_empty() {
+            print_warning("No audio received for this line");
+            continue;
+        }
+
+        let mut note = String::new();
+        if let Some(dir) = &out_dir {
+            let path = dir.join(format!("{:03}.{}", number, extension));
+            write_bytes_to_file(&utterance.audio, &path)?;
+            note = format!(" -> {}", path.display().to_string().green());
+        }
+        let first_audio = utterance.first_audio.unwrap_or(utterance.elapsed);
+        first_audio_total += first_audio;
+        print_success(&format!(
+            "#{} first audio {}ms, done in {:.2}s{}",
+            number,
+            first_audio.as_millis(),
+            utterance.elapsed.as_secs_f64(),
+            note
+        ));
+
+        if !args.no_play && !streamed {
+            if let Err(e) = crate::player::play_audio(&utterance.audio) {
+                print_warning(&format!("Could not play audio: {}", e));
+            }
+        }
+    }
+
+    session.close().await;
+    if spoken > 0 {
+        print_info(&format!(
+            "Spoke {} line(s), average first audio {}ms",
+            spoken,
+            (first_audio_total / spoken as u32).as_millis()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_message() {
+        let audio =
+            parse_server_message(r#"{"audio":"AAEC","contextId":"utterance-1"}"#, None).unwrap();
+        assert_eq!(
+            audio,
+            ServerEvent::Audio {
+                context: "utterance-1".to_string(),
+                audio: vec![0, 1, 2]
+            }
+        );
+        assert_eq!(
+            parse_server_message(r#"{"isFinal":true,"context_id":"utterance-1"}"#, None).unwrap(),
+            ServerEvent::Final {
+                context: "utterance-1".to_string()
+            }
+        );
+        assert_eq!(
+            parse_server_message(r#"{"audio":null,"isFinal":null}"#, None).unwrap(),
+            ServerEvent::Other
+        );
+        let error = parse_server_message(
+            r#"{"error":"quota_exceeded","message":"No credits"}"#,
+            Some("req_abc123"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("No credits"));
+        assert!(error.contains("request-id: req_abc123"));
+    }
+}
@@ -0,0 +1,382 @@
+//! Realtime audio sinks for `tts-stream --sink`
+//!
+//! `fifo:PATH` writes the audio bytes to a named pipe (or any writable file)
+//! as they arrive. `udp:HOST:PORT` and `rtp:HOST:PORT` cut the audio into
+//! 20 ms packets and send them at playback speed, so a receiver's jitter
+//! buffer never overflows; these need a raw format whose byte rate is known
+//! (`pcm_*`, `ulaw_8000` or `alaw_8000`). RTP packets carry PCMU (payload
+//! type 0), PCMA (8) or L16 (dynamic type 96, big-endian as RFC 3551 requires).
+
+use crate::output::{print_info, print_success, print_warning};
+use crate::pcm::pcm_sample_rate;
+use anyhow::{Context, Result};
+use colored::*;
+use futures::StreamExt;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::UdpSocket;
+use tokio::time::Instant;
+
+/// Audio per UDP/RTP packet
+const PACKET_MS: u32 = 20;
+
+/// Dynamic RTP payload type used for L16 at any rate
+const RTP_L16_PAYLOAD_TYPE: u8 = 96;
+
+/// Where `--sink` sends audio
+#[derive(Debug, PartialEq)]
+pub enum Sink {
+    Fifo(PathBuf),
+    Udp(SocketAddr),
+    Rtp(SocketAddr),
+}
+
+/// Byte layout of a raw output format
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RawFormat {
+    Ulaw,
+    Alaw,
+    Pcm(u32),
+}
+
+impl RawFormat {
+    fn parse(format: &str) -> Option<Self> {
+        match format {
+            "ulaw_8000" => Some(Self::Ulaw),
+            "alaw_8000" => Some(Self::Alaw),
+            _ => pcm_sample_rate(format).map(Self::Pcm),
+        }
+    }
+
+    fn sample_rate(self) -> u32 {
+        match self {
+            Self::Ulaw | Self::Alaw => 8_000,
+            Self::Pcm(rate) => rate,
+        }
+    }
+
+    fn bytes_per_sample(self) -> usize {
+        match self {
+            Self::Ulaw | Self::Alaw => 1,
+            Self::Pcm(_) => 2,
+        }
+    }
+
+    fn samples_per_packet(self) -> usize {
+        (self.sample_rate() * PACKET_MS / 1_000) as usize
+    }
+
+    fn payload_type(self) -> u8 {
+        match self {
+            Self::Ulaw => 0,
+            Self::Alaw => 8,
+            Self::Pcm(_) => RTP_L16_PAYLOAD_TYPE,
+        }
+    }
+
+    fn rtp_name(self) -> String {
+        match self {
+            Self::Ulaw => "PCMU/8000".to_string(),
+            Self::Alaw => "PCMA/8000".to_string(),
+            Self::Pcm(rate) => format!("L16/{}", rate),
+        }
+    }
+}
+
+/// Parse `fifo:PATH`, `udp:HOST:PORT` or `rtp:HOST:PORT`, checking that the
+/// output format suits the sink
+pub fn parse_sink(spec: &str, output_format: &str) -> Result<Sink> {
+    let (kind, target) = spec.split_once(':').ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid sink '{}'. Use fifo:PATH, udp:HOST:PORT or rtp:HOST:PORT",
+            spec
+        )
+    })?;
+    if kind == "fifo" {
+        if target.is_empty() {
+            return Err(anyhow::anyhow!(
+                "fifo: needs a path, e.g. fifo:/tmp/tts.pcm"
+            ));
+        }
+        return Ok(Sink::Fifo(PathBuf::from(target)));
+    }
+    if kind != "udp" && kind != "rtp" {
+        return Err(anyhow::anyhow!(
+            "Unknown sink type '{}'. Use fifo, udp or rtp",
+            kind
+        ));
+    }
+
+    if RawFormat::parse(output_format).is_none() {
+        return Err(anyhow::anyhow!(
+            "{} sinks need a raw output format; use --output-format ulaw_8000, alaw_8000 or pcm_16000",
+            kind
+        ));
+    }
+    let addr = target
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid address '{}'. Use HOST:PORT, e.g. 127.0.0.1:5004",
+                target
+            )
+        })?;
+    Ok(if kind == "udp" {
+        Sink::Udp(addr)
+    } else {
+        Sink::Rtp(addr)
+    })
+}
+
+/// Send the streamed response body to `sink` as it arrives
+pub async fn stream_to_sink(
+    response: reqwest::Response,
+    sink: &Sink,
+    output_format: &str,
+) -> Result<()> {
+    match sink {
+        Sink::Fifo(path) => write_fifo(response, path).await,
+        Sink::Udp(addr) | Sink::Rtp(addr) => {
+            let format = RawFormat::parse(output_format)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported format {}", output_format))?;
+            let rtp = matches!(sink, Sink::Rtp(_)).then(RtpHeader::new);
+            send_packets(response, *addr, format, rtp).await
+        }
+    }
+}
+
+async fn write_fifo(response: reqwest::Response, path: &PathBuf) -> Result<()> {
+    print_info(&format!(
+        "Waiting for a reader on {}...",
+        path.display().to_string().cyan()
+    ));
+    // Opening a FIFO for writing blocks until something opens it for reading.
+    // Truncating has no effect on a FIFO but keeps a regular file from ending
+    // in an earlier, longer stream's bytes
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to open {} (create a FIFO with 'mkfifo {}')",
+                path.display(),
+                path.display()
+            )
+        })?;
+
+    let mut stream = response.bytes_stream();
+    let mut written = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Failed to read stream chunk")?;
+        let result = match file.write_all(&chunk).await {
+            Ok(()) => file.flush().await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => written += chunk.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+                print_warning("The reader closed the FIFO; stopping");
+                return Ok(());
+            }
+            Err(e) => return Err(e).context("Failed to write to the FIFO"),
+        }
+    }
+    print_success(&format!(
+        "Wrote {} bytes to {}",
+        written,
+        path.display().to_string().green()
+    ));
+    Ok(())
+}
+
+async fn send_packets(
+    response: reqwest::Response,
+    addr: SocketAddr,
+    format: RawFormat,
+    mut rtp: Option<RtpHeader>,
+) -> Result<()> {
+    let bind = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind)
+        .await
+        .context("Failed to open UDP socket")?;
+    socket
+        .connect(addr)
+        .await
+        .with_context(|| format!("Failed to connect to {}", addr))?;
+    print_info(&format!(
+        "Sending {} to {}",
+        match rtp {
+            Some(_) => format!("RTP ({})", format.rtp_name()),
+            None => "raw UDP audio".to_string(),
+        },
+        addr.to_string().cyan()
+    ));
+
+    let packet_len = format.samples_per_packet() * format.bytes_per_sample();
+    let mut pending: Vec<u8> = Vec::with_capacity(packet_len * 2);
+    let mut stream = response.bytes_stream();
+    let mut pacer = Pacer::new();
+    let mut finished = false;
+    while !finished {
+        match stream.next().await {
+            Some(chunk) => {
+                pending.extend_from_slice(&chunk.context("Failed to read stream chunk")?)
+            }
+            None => finished = true,
+        }
+        // Whole packets while streaming; the remainder once the stream ends
+        while pending.len() >= packet_len || (finished && !pending.is_empty()) {
+            let take = packet_len.min(pending.len());
+            let payload: Vec<u8> = pending.drain(..take).collect();
+            let packet = match rtp.as_mut() {
+                Some(header) => header.packet(format, &payload),
+                None => payload,
+            };
+            pacer.wait().await;
+            socket
+                .send(&packet)
+                .await
+                .with_context(|| format!("Failed to send to {}", addr))?;
+        }
+    }
+
+    print_success(&format!(
+        "Sent {} packets to {}",
+        pacer.sent,
+        addr.to_string().green()
+    ));
+    Ok(())
+}
+
+/// Spaces packets `PACKET_MS` apart from the first one
+struct Pacer {
+    start: Option<Instant>,
+    sent: u32,
+}
+
+impl Pacer {
+    fn new() -> Self {
+        Self {
+            start: None,
+            sent: 0,
+        }
+    }
+
+    async fn wait(&mut self) {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        tokio::time::sleep_until(start + Duration::from_millis((PACKET_MS * self.sent) as u64))
+            .await;
+        self.sent += 1;
+    }
+}
+
+/// RTP header state for one stream
+struct RtpHeader {
+    sequence: u16,
+    timestamp: u32,
+    ssrc: u32,
+    first: bool,
+}
+
+impl RtpHeader {
+    fn new() -> Self {
+        // Random-enough starting values without a dependency on rand
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default()
+            ^ std::process::id().rotate_left(16);
+        Self {
+            sequence: seed as u16,
+            timestamp: seed.rotate_left(7),
+            ssrc: seed.wrapping_mul(2_654_435_761),
+            first: true,
+        }
+    }
+
+    /// Build the next packet for `payload`, converting L16 to network order
+    fn packet(&mut self, format: RawFormat, payload: &[u8]) -> Vec<u8> {
+        let marker = if self.first { 0x80 } else { 0 };
+        let mut packet = Vec::with_capacity(12 + payload.len());
+        packet.push(0x80); // version 2, no padding, extension or CSRCs
+        packet.push(marker | format.payload_type());
+        packet.extend_from_slice(&self.sequence.to_be_bytes());
+        packet.extend_from_slice(&self.timestamp.to_be_bytes());
+        packet.extend_from_slice(&self.ssrc.to_be_bytes());
+        match format {
+            RawFormat::Pcm(_) => {
+                for sample in payload.chunks(2) {
+                    packet.extend(sample.iter().rev());
+                }
+            }
+            _ => packet.extend_from_slice(payload),
+        }
+
+        let samples = payload.len() / format.bytes_per_sample();
+        self.sequence = self.sequence.wrapping_add(1);
+        self.timestamp = self.timestamp.wrapping_add(samples as u32);
+        self.first = false;
+        packet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sink() {
+        assert_eq!(
+            parse_sink("fifo:/tmp/tts.pcm", "mp3_44100_128").unwrap(),
+            Sink::Fifo(PathBuf::from("/tmp/tts.pcm"))
+        );
+        assert_eq!(
+            parse_sink("rtp:127.0.0.1:5004", "ulaw_8000").unwrap(),
+            Sink::Rtp("127.0.0.1:5004".parse().unwrap())
+        );
+        assert!(parse_sink("udp:127.0.0.1:5004", "pcm_16000").is_ok());
+        assert!(parse_sink("udp:127.0.0.1:5004", "mp3_44100_128").is_err());
+        assert!(parse_sink("rtp:127.0.0.1", "ulaw_8000").is_err());
+        assert!(parse_sink("tcp:127.0.0.1:5004", "ulaw_8000").is_err());
+        assert!(parse_sink("fifo:", "ulaw_8000").is_err());
+        assert!(parse_sink("/tmp/tts.pcm", "ulaw_8000").is_err());
+    }
+
+    #[test]
+    fn test_rtp_packets() {
+        let mut header = RtpHeader {
+            sequence: u16::MAX,
+            timestamp: 1_000,
+            ssrc: 0x0102_0304,
+            first: true,
+        };
+        let first = header.packet(RawFormat::Ulaw, &[0xff; 160]);
+        assert_eq!(first.len(), 172);
+        assert_eq!(
+            first[..12],
+            [0x80, 0x80, 0xff, 0xff, 0, 0, 0x03, 0xe8, 1, 2, 3, 4]
+        );
+
+        let second = header.packet(RawFormat::Pcm(16_000), &[0x01, 0x02, 0x03, 0x04]);
+        // No marker, L16 payload type, sequence wrapped, timestamp +160 samples
+        assert_eq!(second[..8], [0x80, 96, 0, 0, 0, 0, 0x04, 0x88]);
+        assert_eq!(second[12..], [0x02, 0x01, 0x04, 0x03]);
+    }
+
+    #[test]
+    fn test_packet_sizes() {
+        assert_eq!(RawFormat::Ulaw.samples_per_packet(), 160);
+        assert_eq!(RawFormat::Pcm(16_000).samples_per_packet(), 320);
+        assert_eq!(RawFormat::Pcm(44_100).samples_per_packet(), 882);
+    }
+}
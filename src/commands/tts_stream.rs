@@ -1,19 +1,52 @@
 use crate::cli::TtsStreamArgs;
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
-use crate::utils::{confirm_overwrite, generate_output_filename, write_bytes_to_file};
-
-#[cfg(feature = "audio")]
-use crate::audio::audio_io;
+use crate::client::{
+    api_base, api_error, create_http_client, ws_base, ws_handshake_error, ws_request_id,
+    SendWithRetry,
+};
+use crate::commands::tts_session::{parse_server_message, ServerEvent};
+use crate::commands::tts_sink::{parse_sink, stream_to_sink};
+use crate::output::{print_info, print_success, set_status_to_stderr, Progress};
+use crate::player::play_audio;
+use crate::utils::{
+    confirm_overwrite, default_output_path, generate_output_filename, write_bytes_to_file,
+};
+use crate::validation::{
+    model_credits_per_char, preflight_tts, validate_chunk_schedule, validate_voice_settings,
+};
 
 use anyhow::{Context, Result};
 use colored::*;
-use futures::StreamExt;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::io::{IsTerminal, Write};
 use std::path::Path;
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_tungstenite::tungstenite::Error as WsError;
+
+/// Seconds to wait for the websocket connection
+const WS_CONNECT_TIMEOUT_SECS: u64 = 30;
 
 pub async fn execute(args: TtsStreamArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    if args.text.is_empty() {
-        return Err(anyhow::anyhow!("Text cannot be empty"));
+    // Pre-flight checks (model, format, text length, voice settings)
+    preflight_tts(&args.text, &args.model, &args.output_format, None)?;
+    validate_voice_settings(args.stability, args.similarity_boost, None)?;
+    validate_chunk_schedule(&args.chunk_schedule)?;
+    let sink = args
+        .sink
+        .as_deref()
+        .map(|spec| parse_sink(spec, &args.output_format))
+        .transpose()?;
+    if args.stdout {
+        if std::io::stdout().is_terminal() {
+            return Err(anyhow::anyhow!(
+                "--stdout writes binary audio; pipe it into a player (e.g. '| ffplay -') or use --output"
+            ));
+        }
+        // stdout carries only audio; status lines go to stderr
+        set_status_to_stderr(true);
     }
 
     print_info(&format!(
@@ -27,16 +60,20 @@ pub async fn execute(args: TtsStreamArgs, api_key: &str, assume_yes: bool) -> Re
 
     let client = create_http_client();
 
-    let api_url = "https://api.elevenlabs.io/v1/text-to-speech";
+    let api_url = format!("{}/v1/text-to-speech", api_base());
 
     // Build URL with query parameters
-    let mut url = format!("{}/{}/stream/with-timestamps", api_url, args.voice);
+    // The plain stream endpoint returns audio bytes as they are generated
+    let endpoint = if args.stdout || sink.is_some() {
+        "stream"
+    } else {
+        "stream/with-timestamps"
+    };
+    let mut url = format!("{}/{}/{}", api_url, args.voice, endpoint);
     let mut query_params = Vec::new();
 
     if let Some(latency) = args.latency {
-        if latency <= 4 {
-            query_params.push(format!("optimize_streaming_latency={}", latency));
-        }
+        query_params.push(format!("optimize_streaming_latency={}", latency));
     }
 
     if args.output_format != "mp3_44100_128" {
@@ -81,34 +118,73 @@ pub async fn execute(args: TtsStreamArgs, api_key: &str, assume_yes: bool) -> Re
         body["voice_settings"] = settings;
     }
 
-    // Make streaming request
-    let response = client
-        .post(&url)
-        .header("xi-api-key", api_key)
-        .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .context("Failed to send streaming request")?;
-
-    if !response.status().is_success() {
-        let error_text = response
-            .text()
+    // Make streaming request; the status line doubles as a live usage meter
+    let chars_sent = args.text.chars().count();
+    let credits = (chars_sent as f64 * model_credits_per_char(&model)).ceil() as u64;
+    let progress = Progress::spinner(&meter_message(chars_sent, 0, credits));
+
+    let audio_bytes = if args.chunk_schedule.is_empty() {
+        let response = client
+            .post(&url)
+            .header("xi-api-key", api_key)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send_with_retry()
             .await
-            .context("Failed to read error response")?;
-        return Err(anyhow::anyhow!("ElevenLabs API error: {}", error_text));
-    }
+            .context("Failed to send streaming request")?;
 
-    let mut stream = response.bytes_stream();
-    let mut audio_chunks = Vec::new();
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
 
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk.context("Failed to read stream chunk")?;
-        audio_chunks.extend_from_slice(&chunk);
-    }
+        if args.stdout {
+            progress.finish();
+            return pipe_to_stdout(response).await;
+        }
+        if let Some(sink) = &sink {
+            progress.finish();
+            return stream_to_sink(response, sink, &args.output_format).await;
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut audio_chunks = Vec::new();
 
-    // Combine all audio
-    let audio_bytes: Vec<u8> = audio_chunks;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.context("Failed to read stream chunk")?;
+            audio_chunks.extend_from_slice(&chunk);
+            progress.set_message(&meter_message(chars_sent, audio_chunks.len(), credits));
+        }
+        audio_chunks
+    } else {
+        let mut url = format!(
+            "{}/v1/text-to-speech/{}/stream-input?model_id={}&output_format={}",
+            ws_base(),
+            args.voice,
+            model,
+            args.output_format
+        );
+        if let Some(latency) = args.latency {
+            url.push_str(&format!("&optimize_streaming_latency={}", latency));
+        }
+        let mut received = 0;
+        let mut stdout = std::io::stdout();
+        let audio = stream_over_websocket(&url, api_key, &body, &args.chunk_schedule, |chunk| {
+            received += chunk.len();
+            progress.set_message(&meter_message(chars_sent, received, credits));
+            if args.stdout {
+                stdout.write_all(chunk)?;
+                stdout.flush()?;
+            }
+            Ok(())
+        })
+        .await?;
+        if args.stdout {
+            progress.finish();
+            return Ok(());
+        }
+        audio
+    };
+    progress.finish();
 
     // Determine output path
     let output_path = if let Some(output) = args.output {
@@ -121,7 +197,10 @@ pub async fn execute(args: TtsStreamArgs, api_key: &str, assume_yes: bool) -> Re
             f if f.starts_with("opus_") => "opus",
             _ => "mp3",
         };
-        generate_output_filename("speech_stream", extension)
+        default_output_path(
+            &generate_output_filename("speech_stream", extension),
+            Some(&args.voice),
+        )?
     };
 
     // Check for overwrite
@@ -137,22 +216,123 @@ pub async fn execute(args: TtsStreamArgs, api_key: &str, assume_yes: bool) -> Re
     print_success(&format!("Streamed speech saved -> {}", output_path.green()));
 
     // Play audio if requested
-    #[cfg(feature = "audio")]
     if args.play {
         print_info("Playing audio...");
-        if let Err(e) = audio_io::play_to_speaker(&audio_bytes) {
+        if let Err(e) = play_audio(&audio_bytes) {
             print_info(&format!("Could not play audio: {}", e));
         }
     }
 
-    #[cfg(not(feature = "audio"))]
-    if args.play {
-        print_info("Audio playback not available. Rebuild with --features audio");
-    }
+    Ok(())
+}
 
+/// Write each chunk to stdout as soon as it arrives. A closed pipe (the
+/// player exited) ends the stream without an error.
+async fn pipe_to_stdout(response: reqwest::Response) -> Result<()> {
+    let mut stream = response.bytes_stream();
+    let mut stdout = std::io::stdout();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Failed to read stream chunk")?;
+        match stdout.write_all(&chunk).and_then(|_| stdout.flush()) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e).context("Failed to write audio to stdout"),
+        }
+    }
     Ok(())
 }
 
+/// Generate over the `stream-input` websocket, the only endpoint that takes a
+/// chunk schedule. `on_audio` sees each chunk as it arrives; a closed pipe
+/// ends the stream early without an error.
+async fn stream_over_websocket(
+    url: &str,
+    api_key: &str,
+    body: &Value,
+    chunk_schedule: &[u32],
+    mut on_audio: impl FnMut(&[u8]) -> std::io::Result<()>,
+) -> Result<Vec<u8>> {
+    let mut request = url.into_client_request().context("Invalid websocket URL")?;
+    request.headers_mut().insert(
+        "xi-api-key",
+        api_key
+            .parse()
+            .context("API key is not a valid header value")?,
+    );
+    let connected = tokio::time::timeout(
+        Duration::from_secs(WS_CONNECT_TIMEOUT_SECS),
+        connect_async(request),
+    )
+    .await
+    .context("Connection timeout")?;
+    let (mut stream, response) = match connected {
+        Ok(connected) => connected,
+        Err(WsError::Http(response)) => return Err(ws_handshake_error(&response)),
+        Err(e) => return Err(e).context("Failed to connect to ElevenLabs WebSocket"),
+    };
+    let request_id = ws_request_id(&response);
+
+    // The first message opens the stream and carries the settings, an empty
+    // text closes it once everything has been sent
+    let mut init = json!({
+        "text": " ",
+        "generation_config": { "chunk_length_schedule": chunk_schedule },
+    });
+    if let Some(settings) = body.get("voice_settings") {
+        init["voice_settings"] = settings.clone();
+    }
+    let text = format!("{} ", body["text"].as_str().unwrap_or_default());
+    for message in [init, json!({ "text": text }), json!({ "text": "" })] {
+        stream
+            .send(Message::Text(message.to_string()))
+            .await
+            .context("Failed to send text to the WebSocket")?;
+    }
+
+    let mut audio = Vec::new();
+    while let Some(message) = stream.next().await {
+        let text = match message.context("WebSocket error")? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        match parse_server_message(&text, request_id.as_deref())? {
+            ServerEvent::Audio { audio: chunk, .. } => {
+                match on_audio(&chunk) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => break,
+                    Err(e) => return Err(e).context("Failed to write audio to stdout"),
+                }
+                audio.extend_from_slice(&chunk);
+            }
+            ServerEvent::Final { .. } => break,
+            ServerEvent::Other => {}
+        }
+    }
+    if audio.is_empty() {
+        return Err(anyhow::anyhow!("No audio data received"));
+    }
+    Ok(audio)
+}
+
+/// Status line shown while streaming
+fn meter_message(chars_sent: usize, bytes_received: usize, credits: u64) -> String {
+    format!(
+        "Streaming speech: {} chars sent, {} received, ~{} credits",
+        chars_sent,
+        format_bytes(bytes_received),
+        credits
+    )
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..=1_023 => format!("{} B", bytes),
+        1_024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1_024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
 fn validate_model(model: &str) -> Result<String> {
     let valid_models = [
         "eleven_multilingual_v2",
@@ -172,3 +352,18 @@ fn validate_model(model: &str) -> Result<String> {
 
     Ok(model.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_message() {
+        assert_eq!(
+            meter_message(120, 0, 60),
+            "Streaming speech: 120 chars sent, 0 B received, ~60 credits"
+        );
+        assert_eq!(format_bytes(2_560), "2.5 KB");
+        assert_eq!(format_bytes(3 * 1_048_576), "3.0 MB");
+    }
+}
@@ -1,10 +1,12 @@
 use crate::cli::TtsTimestampsArgs;
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{print_info, print_success, Progress};
+use crate::text_clean::clean_input_text;
 use crate::utils::{
-    confirm_overwrite, format_to_extension, generate_output_filename, get_input_text,
-    write_bytes_to_file,
+    confirm_overwrite, default_output_path, format_to_extension, generate_output_filename,
+    get_input_text, write_bytes_to_file,
 };
+use crate::validation::preflight_tts;
 use anyhow::{Context, Result};
 use colored::*;
 use serde::Deserialize;
@@ -18,15 +20,15 @@ pub async fn execute(
 ) -> Result<()> {
     // Get input text
     let text = get_input_text(args.text, args.file)?;
+    let text = clean_input_text(&text, &args.clean_input)?;
 
-    if text.is_empty() {
-        return Err(anyhow::anyhow!("Text cannot be empty"));
-    }
+    // Pre-flight checks (model, format, text length)
+    preflight_tts(&text, &args.model, output_format, None)?;
 
     // Create HTTP client for direct API calls (SDK doesn't have this endpoint yet)
     let client = create_http_client();
 
-    let api_url = "https://api.elevenlabs.io/v1/text-to-speech";
+    let api_url = format!("{}/v1/text-to-speech", api_base());
 
     // Build URL with query params (using percent encoding for special characters)
     let mut url = format!(
@@ -58,25 +60,22 @@ pub async fn execute(
     print_info(&format!("Characters: {}", text.len().to_string().yellow()));
 
     // Make API request
+    let progress = Progress::spinner("Generating speech");
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(text.clone())
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to send request to ElevenLabs API")?;
 
     if !response.status().is_success() {
-        let error_text = response
-            .text()
-            .await
-            .context("Failed to read error response")?;
-        return Err(anyhow::anyhow!("ElevenLabs API error: {}", error_text));
+        return Err(api_error(response).await);
     }
 
-    let response_data: TtsTimestampsResponse =
-        response.json().await.context("Failed to parse response")?;
+    let response_data: TtsTimestampsResponse = read_json(response).await?;
+    progress.finish();
 
     // Decode audio
     let audio_bytes = base64::Engine::decode(
@@ -89,7 +88,10 @@ pub async fn execute(
     let output_path = if let Some(output) = args.output {
         output
     } else {
-        generate_output_filename("speech_ts", format_to_extension(output_format))
+        default_output_path(
+            &generate_output_filename("speech_ts", format_to_extension(output_format)),
+            Some(&args.voice),
+        )?
     };
 
     // Check for overwrite
@@ -1,5 +1,5 @@
 use crate::cli::UpdateArgs;
-use crate::output::print_success;
+use crate::output::{print_info, print_success};
 use anyhow::{bail, Result};
 use colored::*;
 use std::env;
@@ -131,7 +131,7 @@ async fn fetch_latest_version() -> Result<String> {
 }
 
 fn update_via_homebrew() -> Result<()> {
-    println!("{} Detected Homebrew installation", "ℹ".blue());
+    print_info("Detected Homebrew installation");
     println!("  Running: {}", "brew upgrade elevenlabs-cli".cyan());
 
     let status = Command::new("brew")
@@ -148,7 +148,7 @@ fn update_via_homebrew() -> Result<()> {
 }
 
 fn update_via_cargo() -> Result<()> {
-    println!("{} Detected Cargo installation", "ℹ".blue());
+    print_info("Detected Cargo installation");
     println!(
         "  Running: {}",
         "cargo install elevenlabs-cli --force".cyan()
@@ -168,7 +168,7 @@ fn update_via_cargo() -> Result<()> {
 }
 
 fn update_via_snap() -> Result<()> {
-    println!("{} Detected Snap installation", "ℹ".blue());
+    print_info("Detected Snap installation");
     println!("  Running: {}", "sudo snap refresh elevenlabs-cli".cyan());
 
     let status = Command::new("sudo")
@@ -185,7 +185,7 @@ fn update_via_snap() -> Result<()> {
 }
 
 fn update_via_aur() -> Result<()> {
-    println!("{} Detected AUR installation (Arch Linux)", "ℹ".blue());
+    print_info("Detected AUR installation (Arch Linux)");
     println!("  Please use your AUR helper to update, e.g.:",);
     println!("    yay -S elevenlabs-cli");
     println!("    paru -S elevenlabs-cli");
@@ -195,7 +195,7 @@ fn update_via_aur() -> Result<()> {
 }
 
 async fn update_via_binary(exe_path: &PathBuf, version: &str) -> Result<()> {
-    println!("{} Detected standalone binary installation", "ℹ".blue());
+    print_info("Detected standalone binary installation");
     println!("  Downloading latest version from GitHub...");
 
     // Detect OS and architecture
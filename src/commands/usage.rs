@@ -1,16 +1,32 @@
 use crate::cli::{UsageArgs, UsageCommands};
-use crate::output::{print_info, print_success};
-use anyhow::Result;
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, SendWithRetry,
+};
+use crate::ledger::{self, LedgerEntry};
+use crate::output::{is_json_mode, print_info, print_success, print_warning};
+use crate::utils::{format_relative_time, parse_time_spec};
+use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use elevenlabs_rs::{
     endpoints::admin::usage::{BreakdownType, GetUsage, GetUsageQuery},
     ElevenLabsClient,
 };
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Ledger totals for one project
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct ProjectTotals {
+    project_id: String,
+    generations: usize,
+    characters: usize,
+    last_generated: u64,
+}
+
 pub async fn execute(args: UsageArgs, api_key: &str) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     match args.command {
         UsageCommands::Stats {
@@ -18,8 +34,68 @@ pub async fn execute(args: UsageArgs, api_key: &str) -> Result<()> {
             end,
             breakdown,
         } => get_usage_stats(&client, start, end, breakdown).await?,
+        UsageCommands::Projects { since, project_id } => {
+            project_usage(since.as_deref(), project_id.as_deref())?
+        }
+        UsageCommands::Alert { threshold } => usage_alert(api_key, threshold).await?,
+    }
+
+    Ok(())
+}
+
+/// Check character usage against `threshold` percent of the plan limit,
+/// failing when it is reached so schedules and scripts can act on it
+async fn usage_alert(api_key: &str, threshold: f64) -> Result<()> {
+    if !(threshold > 0.0 && threshold <= 100.0) {
+        return Err(anyhow::anyhow!("--threshold must be between 0 and 100"));
     }
+    let response = create_http_client()
+        .get(format!("{}/v1/user/subscription", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch subscription")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let subscription: serde_json::Value = read_json(response).await?;
+    let used = subscription["character_count"].as_u64().unwrap_or(0);
+    let limit = subscription["character_limit"]
+        .as_u64()
+        .filter(|&l| l > 0)
+        .ok_or_else(|| anyhow::anyhow!("The subscription has no character limit"))?;
+    let percent = used as f64 / limit as f64 * 100.0;
+    let reached = percent >= threshold;
 
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "character_count": used,
+                "character_limit": limit,
+                "percent": percent,
+                "threshold": threshold,
+                "alert": reached,
+            }))?
+        );
+    } else if reached {
+        print_warning(&format!(
+            "Character usage at {:.1}% ({} / {}), threshold {}%",
+            percent, used, limit, threshold
+        ));
+    } else {
+        print_success(&format!(
+            "Character usage at {:.1}% ({} / {}), below {}%",
+            percent, used, limit, threshold
+        ));
+    }
+    if reached {
+        return Err(anyhow::anyhow!(
+            "Character usage {:.1}% reached the {}% threshold",
+            percent,
+            threshold
+        ));
+    }
     Ok(())
 }
 
@@ -63,6 +139,19 @@ async fn get_usage_stats(
     let endpoint = GetUsage::new(query);
     let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
 
+    if is_json_mode() {
+        let total: u64 = response.usage.values().flatten().sum();
+        let stats = serde_json::json!({
+            "start_unix": start_unix,
+            "end_unix": end_unix,
+            "time": response.time,
+            "usage": response.usage,
+            "total_characters": total,
+        });
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
     println!("\n{}", "Usage Statistics:".bold().underline());
 
     if response.time.is_empty() {
@@ -97,3 +186,135 @@ async fn get_usage_stats(
 
     Ok(())
 }
+
+/// Group ledger entries by project, or list one project's entries
+fn project_usage(since: Option<&str>, project_id: Option<&str>) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let since = since.map(|s| parse_time_spec(s, now)).transpose()?;
+    let entries: Vec<LedgerEntry> = ledger::load()
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+        .collect();
+
+    if let Some(project_id) = project_id {
+        let entries: Vec<&LedgerEntry> = entries
+            .iter()
+            .filter(|e| e.project_id.as_deref() == Some(project_id))
+            .collect();
+        if is_json_mode() {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
+        if entries.is_empty() {
+            print_info(&format!("No generations recorded for '{}'", project_id));
+            return Ok(());
+        }
+        let mut table = Table::new();
+        table.set_header(vec![
+            "When",
+            "Voice",
+            "Model",
+            "Characters",
+            "Output",
+            "Metadata",
+        ]);
+        for entry in &entries {
+            let metadata: Vec<String> = entry
+                .metadata
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            table.add_row(vec![
+                format_relative_time(now.saturating_sub(entry.timestamp)),
+                entry.voice.clone(),
+                entry.model.clone(),
+                entry.characters.to_string(),
+                entry.output.clone().unwrap_or_default(),
+                metadata.join(", "),
+            ]);
+        }
+        println!("{}", table);
+        return Ok(());
+    }
+
+    let totals = project_totals(&entries);
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&totals)?);
+        return Ok(());
+    }
+    if totals.is_empty() {
+        print_info("No generations with --project-id recorded yet");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Project", "Generations", "Characters", "Last"]);
+    for total in &totals {
+        table.add_row(vec![
+            total.project_id.clone(),
+            total.generations.to_string(),
+            total.characters.to_string(),
+            format_relative_time(now.saturating_sub(total.last_generated)),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+/// Per-project totals, ignoring entries without a project
+fn project_totals(entries: &[LedgerEntry]) -> Vec<ProjectTotals> {
+    let mut totals: BTreeMap<&str, ProjectTotals> = BTreeMap::new();
+    for entry in entries {
+        let Some(project_id) = entry.project_id.as_deref() else {
+            continue;
+        };
+        let total = totals.entry(project_id).or_insert_with(|| ProjectTotals {
+            project_id: project_id.to_string(),
+            ..Default::default()
+        });
+        total.generations += 1;
+        total.characters += entry.characters;
+        total.last_generated = total.last_generated.max(entry.timestamp);
+    }
+    totals.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_totals() {
+        let entry = |project: Option<&str>, characters, timestamp| {
+            let mut entry = LedgerEntry::new("tts", "Brian", "eleven_multilingual_v2", characters);
+            entry.project_id = project.map(str::to_string);
+            entry.timestamp = timestamp;
+            entry
+        };
+        let totals = project_totals(&[
+            entry(Some("launch"), 100, 10),
+            entry(None, 500, 20),
+            entry(Some("audiobook"), 40, 30),
+            entry(Some("launch"), 50, 40),
+        ]);
+        assert_eq!(
+            totals,
+            vec![
+                ProjectTotals {
+                    project_id: "audiobook".to_string(),
+                    generations: 1,
+                    characters: 40,
+                    last_generated: 30,
+                },
+                ProjectTotals {
+                    project_id: "launch".to_string(),
+                    generations: 2,
+                    characters: 150,
+                    last_generated: 40,
+                },
+            ]
+        );
+    }
+}
@@ -1,15 +1,18 @@
 use crate::cli::{UserArgs, UserCommands};
+use crate::client::{create_sdk_client, retry_sdk};
 use crate::errors::print_subscription_info;
-use crate::output::{print_info, print_success};
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::Result;
 use colored::*;
 use elevenlabs_rs::{
     endpoints::admin::user::{GetUserInfo, GetUserSubscriptionInfo},
     ElevenLabsClient,
 };
+use reqwest::Method;
+use serde_json::json;
 
 pub async fn execute(args: UserArgs, api_key: &str) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     match args.command {
         UserCommands::Info => get_user_info(&client).await?,
@@ -23,8 +26,21 @@ pub async fn execute(args: UserArgs, api_key: &str) -> Result<()> {
 async fn get_user_info(client: &ElevenLabsClient) -> Result<()> {
     print_info("Fetching user information...");
 
-    let endpoint = GetUserInfo;
-    let user = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let user = retry_sdk(Method::GET, || client.hit(GetUserInfo))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let info = json!({
+            "user_id": user.user_id,
+            "tier": user.subscription.tier,
+            "character_count": user.subscription.character_count,
+            "character_limit": user.subscription.character_limit,
+            "next_character_count_reset_unix": user.subscription.next_character_count_reset_unix,
+        });
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
 
     println!("\n{}", "User Information:".bold().underline());
     println!("  User ID: {}", user.user_id.cyan());
@@ -62,8 +78,22 @@ async fn get_user_info(client: &ElevenLabsClient) -> Result<()> {
 async fn get_subscription(client: &ElevenLabsClient) -> Result<()> {
     print_info("Fetching subscription details...");
 
-    let endpoint = GetUserSubscriptionInfo;
-    let subscription = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let subscription = retry_sdk(Method::GET, || client.hit(GetUserSubscriptionInfo))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let details = json!({
+            "tier": subscription.tier,
+            "character_count": subscription.character_count,
+            "character_limit": subscription.character_limit,
+            "voice_limit": subscription.voice_limit,
+            "professional_voice_limit": subscription.professional_voice_limit,
+            "next_character_count_reset_unix": subscription.next_character_count_reset_unix,
+        });
+        println!("{}", serde_json::to_string_pretty(&details)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Subscription Details:".bold().underline());
     println!("  Tier: {}", subscription.tier.yellow());
@@ -111,11 +141,18 @@ async fn get_permissions(client: &ElevenLabsClient) -> Result<()> {
     print_info("Checking API permissions and feature availability...");
 
     // Get user info to determine subscription tier
-    let endpoint = GetUserInfo;
-    let user = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let user = retry_sdk(Method::GET, || client.hit(GetUserInfo))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
 
     let tier = &user.subscription.tier;
 
+    if is_json_mode() {
+        let perms = json!({ "user_id": user.user_id, "tier": tier });
+        println!("{}", serde_json::to_string_pretty(&perms)?);
+        return Ok(());
+    }
+
     println!();
     println!("{}", "API Key Permissions:".bold().underline());
     println!("  User ID: {}", user.user_id.cyan());
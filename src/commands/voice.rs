@@ -1,6 +1,11 @@
-use crate::cli::{FineTuneCommands, VoiceArgs, VoiceCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success, print_warning};
+use crate::cli::{
+    FineTuneCommands, JobKind, VoiceArgs, VoiceCommands, VoiceLabelCommands, WaitArgs,
+};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::output::{is_json_mode, print_info, print_success, print_warning, Progress};
+use crate::transcode::{prepare_upload, CONVERTIBLE_EXTENSIONS};
 use crate::validation::validate_voice_settings;
 use anyhow::{Context, Result};
 use colored::*;
@@ -8,17 +13,19 @@ use comfy_table::Table;
 use elevenlabs_rs::{
     endpoints::admin::voice::{
         AddVoice, DeleteVoice, EditVoiceSettings, EditVoiceSettingsBody, GetVoice,
-        GetVoiceSettings, GetVoices, VoiceBody,
+        GetVoiceResponse, GetVoiceSettings, GetVoices, VoiceBody,
     },
     ElevenLabsClient,
 };
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
-use reqwest::Client;
+use reqwest::{Client, Method};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
 use walkdir::WalkDir;
 
 pub async fn execute(args: VoiceArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
     let http_client = create_http_client();
 
     match args.command {
@@ -65,6 +72,7 @@ pub async fn execute(args: VoiceArgs, api_key: &str, assume_yes: bool) -> Result
                 voice_id,
                 name,
                 description,
+                wait,
             } => {
                 start_fine_tune(
                     &http_client,
@@ -72,6 +80,7 @@ pub async fn execute(args: VoiceArgs, api_key: &str, assume_yes: bool) -> Result
                     &voice_id,
                     &name,
                     description.as_deref(),
+                    &wait,
                 )
                 .await?
             }
@@ -100,6 +109,26 @@ pub async fn execute(args: VoiceArgs, api_key: &str, assume_yes: bool) -> Result
         VoiceCommands::Similar { voice_id, text } => {
             find_similar_voices(&http_client, api_key, voice_id.as_deref(), text.as_deref()).await?
         }
+        VoiceCommands::Label { command } => {
+            label_command(&http_client, api_key, command, assume_yes).await?
+        }
+        VoiceCommands::Verify {
+            voice_id,
+            recording,
+            duration,
+        } => {
+            super::voice_verify::verify_voice(
+                &http_client,
+                api_key,
+                &voice_id,
+                recording.as_deref(),
+                duration,
+                assume_yes,
+            )
+            .await?
+        }
+        // Handled in main before the API key is required
+        VoiceCommands::Score { .. } | VoiceCommands::Defaults { .. } => unreachable!(),
     }
 
     Ok(())
@@ -108,8 +137,15 @@ pub async fn execute(args: VoiceArgs, api_key: &str, assume_yes: bool) -> Result
 async fn list_voices(client: &ElevenLabsClient, detailed: bool) -> Result<()> {
     print_info("Fetching voices...");
 
-    let endpoint = GetVoices::default();
-    let voices = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let voices = retry_sdk(Method::GET, || client.hit(GetVoices::default()))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        let voices: Vec<_> = voices.voices.iter().map(voice_json).collect();
+        println!("{}", serde_json::to_string_pretty(&voices)?);
+        return Ok(());
+    }
 
     if detailed {
         println!("\n{}", "Available Voices:".bold().underline());
@@ -151,8 +187,14 @@ async fn list_voices(client: &ElevenLabsClient, detailed: bool) -> Result<()> {
 async fn get_voice(client: &ElevenLabsClient, voice_id: &str) -> Result<()> {
     print_info(&format!("Fetching voice '{}'...", voice_id.cyan()));
 
-    let endpoint = GetVoice::new(voice_id);
-    let voice = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let voice = retry_sdk(Method::GET, || client.hit(GetVoice::new(voice_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&voice_json(&voice))?);
+        return Ok(());
+    }
 
     println!(
         "\n{}",
@@ -182,14 +224,24 @@ async fn get_voice(client: &ElevenLabsClient, voice_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// A voice as printed by `--json`
+fn voice_json(voice: &GetVoiceResponse) -> serde_json::Value {
+    serde_json::json!({
+        "voice_id": voice.voice_id,
+        "name": voice.name,
+        "category": voice.category,
+        "description": voice.description,
+        "labels": voice.labels,
+        "preview_url": voice.preview_url,
+        "created_at_unix": voice.created_at_unix,
+    })
+}
+
 async fn delete_voice(client: &ElevenLabsClient, voice_id: &str, assume_yes: bool) -> Result<()> {
     print_warning(&format!("You are about to delete voice '{}'", voice_id));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -197,8 +249,9 @@ async fn delete_voice(client: &ElevenLabsClient, voice_id: &str, assume_yes: boo
         }
     }
 
-    let endpoint = DeleteVoice::new(voice_id);
-    client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    retry_sdk(Method::DELETE, || client.hit(DeleteVoice::new(voice_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
 
     print_success(&format!("Deleted voice '{}'", voice_id));
     Ok(())
@@ -223,7 +276,9 @@ async fn clone_voice(
                 let path = entry.path();
                 if let Some(ext) = path.extension() {
                     let ext = ext.to_string_lossy().to_lowercase();
-                    if ["mp3", "wav", "m4a", "ogg", "flac"].contains(&ext.as_str()) {
+                    if ["mp3", "wav", "m4a", "ogg", "flac"].contains(&ext.as_str())
+                        || CONVERTIBLE_EXTENSIONS.contains(&ext.as_str())
+                    {
                         sample_files.push(path.to_string_lossy().to_string());
                     }
                 }
@@ -237,6 +292,16 @@ async fn clone_voice(
 
     print_info(&format!("Using {} sample files", sample_files.len()));
 
+    // Converted copies are deleted when `uploads` is dropped, after the upload
+    let uploads = sample_files
+        .iter()
+        .map(|file| prepare_upload(Path::new(file)))
+        .collect::<Result<Vec<_>>>()?;
+    let sample_files = uploads
+        .iter()
+        .map(|upload| upload.path().to_string_lossy().to_string())
+        .collect();
+
     // Parse labels
     let mut labels_vec = Vec::new();
     for label in labels {
@@ -270,6 +335,12 @@ async fn clone_voice(
         "  Requires verification: {}",
         response.requires_verification
     );
+    if response.requires_verification {
+        print_info(&format!(
+            "Verify the voice with: elevenlabs-cli voice verify {}",
+            response.voice_id
+        ));
+    }
 
     Ok(())
 }
@@ -280,8 +351,14 @@ async fn get_settings(client: &ElevenLabsClient, voice_id: &str) -> Result<()> {
         voice_id.cyan()
     ));
 
-    let endpoint = GetVoiceSettings::new(voice_id);
-    let settings = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let settings = retry_sdk(Method::GET, || client.hit(GetVoiceSettings::new(voice_id)))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&settings)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Voice Settings:".bold().underline());
     println!("  Stability: {:?}", settings.stability);
@@ -384,13 +461,14 @@ async fn start_fine_tune(
     voice_id: &str,
     name: &str,
     description: Option<&str>,
+    wait: &WaitArgs,
 ) -> Result<()> {
     print_info(&format!(
         "Starting fine-tuning for voice '{}'...",
         voice_id.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/voices/{}/fine-tune", voice_id);
+    let url = format!("{}/v1/voices/{}/fine-tune", api_base(), voice_id);
 
     let mut body = serde_json::json!({
         "name": name,
@@ -405,23 +483,29 @@ async fn start_fine_tune(
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to start fine-tuning")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: FineTuneResponse = response.json().await.context("Failed to parse response")?;
+    let result: FineTuneResponse = read_json(response).await?;
+    let job = crate::journal::record(
+        api_key,
+        JobKind::Pvc,
+        &result.fine_tuning_id,
+        Some(voice_id),
+        name,
+    );
 
     print_success("Fine-tuning started");
     println!("  Fine-tuning ID: {}", result.fine_tuning_id.cyan());
     println!("  Voice ID: {}", result.voice_id.cyan());
     println!("  Status: {}", result.status.yellow());
 
-    Ok(())
+    super::jobs::wait_if_requested(api_key, job, wait).await
 }
 
 async fn get_fine_tune_status(client: &Client, api_key: &str, voice_id: &str) -> Result<()> {
@@ -430,22 +514,25 @@ async fn get_fine_tune_status(client: &Client, api_key: &str, voice_id: &str) ->
         voice_id.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/voices/{}/fine-tune", voice_id);
+    let url = format!("{}/v1/voices/{}/fine-tune", api_base(), voice_id);
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to get fine-tuning status")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: FineTuneStatusResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: FineTuneStatusResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
 
     println!("\n{}", "Fine-tuning Status:".bold().underline());
     println!("  Fine-tuning ID: {}", result.fine_tuning_id.cyan());
@@ -491,10 +578,7 @@ async fn cancel_fine_tune(
     ));
 
     if !assume_yes {
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt("Are you sure?")
-            .default(false)
-            .interact()?;
+        let confirm = crate::prompt::confirm("Are you sure?")?;
 
         if !confirm {
             print_info("Cancelled");
@@ -507,18 +591,17 @@ async fn cancel_fine_tune(
         voice_id.cyan()
     ));
 
-    let url = format!("https://api.elevenlabs.io/v1/voices/{}/fine-tune", voice_id);
+    let url = format!("{}/v1/voices/{}/fine-tune", api_base(), voice_id);
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to cancel fine-tuning")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Fine-tuning cancelled for voice '{}'", voice_id));
@@ -542,7 +625,7 @@ async fn edit_voice(
 
     print_info(&format!("Editing voice '{}'...", voice_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/voices/{}", voice_id);
+    let url = format!("{}/v1/voices/{}", api_base(), voice_id);
 
     let mut body = serde_json::json!({});
 
@@ -559,13 +642,12 @@ async fn edit_voice(
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to edit voice")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     #[derive(Deserialize)]
@@ -578,7 +660,7 @@ async fn edit_voice(
         category: Option<String>,
     }
 
-    let result: EditVoiceResponse = response.json().await.context("Failed to parse response")?;
+    let result: EditVoiceResponse = read_json(response).await?;
 
     print_success("Voice updated successfully");
     println!("  Voice ID: {}", result.voice_id.cyan());
@@ -597,19 +679,18 @@ async fn edit_voice(
 async fn share_voice(client: &Client, api_key: &str, voice_id: &str) -> Result<()> {
     print_info(&format!("Sharing voice '{}' publicly...", voice_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/voices/{}/share", voice_id);
+    let url = format!("{}/v1/voices/{}/share", api_base(), voice_id);
 
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to share voice")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     #[derive(Deserialize)]
@@ -620,7 +701,7 @@ async fn share_voice(client: &Client, api_key: &str, voice_id: &str) -> Result<(
         voice_id: String,
     }
 
-    let result: ShareVoiceResponse = response.json().await.context("Failed to parse response")?;
+    let result: ShareVoiceResponse = read_json(response).await?;
 
     print_success("Voice shared successfully");
     println!("  Voice ID: {}", result.voice_id.cyan());
@@ -654,16 +735,10 @@ async fn find_similar_voices(
     print_info("Finding similar voices...");
 
     let url = if let Some(vid) = voice_id {
-        format!(
-            "https://api.elevenlabs.io/v1/voices/similar?voice_id={}",
-            vid
-        )
+        format!("{}/v1/voices/similar?voice_id={}", api_base(), vid)
     } else if let Some(t) = text {
         let encoded = utf8_percent_encode(t, NON_ALPHANUMERIC).to_string();
-        format!(
-            "https://api.elevenlabs.io/v1/voices/similar?text={}",
-            encoded
-        )
+        format!("{}/v1/voices/similar?text={}", api_base(), encoded)
     } else {
         unreachable!()
     };
@@ -671,21 +746,20 @@ async fn find_similar_voices(
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to find similar voices")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct SimilarVoicesResponse {
         voices: Vec<SimilarVoice>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct SimilarVoice {
         voice_id: String,
         name: String,
@@ -695,8 +769,12 @@ async fn find_similar_voices(
         description: Option<String>,
     }
 
-    let result: SimilarVoicesResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: SimilarVoicesResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.voices)?);
+        return Ok(());
+    }
 
     if result.voices.is_empty() {
         print_info("No similar voices found");
@@ -722,3 +800,349 @@ async fn find_similar_voices(
 
     Ok(())
 }
+
+/// A voice with the fields label editing needs
+#[derive(Debug, Deserialize)]
+struct LabeledVoice {
+    voice_id: String,
+    name: String,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+/// Parse a `key=value` label
+pub(crate) fn parse_label(label: &str) -> Result<(String, String)> {
+    let (key, value) = label
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Invalid label '{}'. Use key=value", label))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Invalid label '{}': key must not be empty",
+            label
+        ));
+    }
+    Ok((key.to_string(), value.trim().to_string()))
+}
+
+/// Labels after setting and removing keys
+fn apply_label_changes(
+    labels: &BTreeMap<String, String>,
+    set: &[(String, String)],
+    remove: &[String],
+) -> BTreeMap<String, String> {
+    let mut updated = labels.clone();
+    for (key, value) in set {
+        updated.insert(key.clone(), value.clone());
+    }
+    for key in remove {
+        updated.remove(key);
+    }
+    updated
+}
+
+/// Whether a voice matches every `key=value` filter; `category` matches the
+/// voice category and any other key matches a label (case-insensitive values)
+fn voice_matches(voice: &LabeledVoice, filters: &[(String, String)]) -> bool {
+    filters.iter().all(|(key, value)| {
+        let actual = match key.as_str() {
+            "category" => voice.category.as_deref(),
+            _ => voice.labels.get(key).map(String::as_str),
+        };
+        actual.is_some_and(|actual| actual.eq_ignore_ascii_case(value))
+    })
+}
+
+fn format_labels(labels: &BTreeMap<String, String>) -> String {
+    if labels.is_empty() {
+        return "-".to_string();
+    }
+    labels
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+async fn fetch_labeled_voice(
+    client: &Client,
+    api_key: &str,
+    voice_id: &str,
+) -> Result<LabeledVoice> {
+    let url = format!("{}/v1/voices/{}", api_base(), voice_id);
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch voice")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    read_json(response).await.context("Failed to parse voice")
+}
+
+async fn fetch_labeled_voices(client: &Client, api_key: &str) -> Result<Vec<LabeledVoice>> {
+    #[derive(Deserialize)]
+    struct VoicesResponse {
+        voices: Vec<LabeledVoice>,
+    }
+
+    let url = format!("{}/v1/voices", api_base());
+    let response = client
+        .get(&url)
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch voices")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let result: VoicesResponse = read_json(response)
+        .await
+        .context("Failed to parse voices")?;
+    Ok(result.voices)
+}
+
+/// Replace a voice's labels (the edit endpoint requires the name as well)
+async fn write_voice_labels(
+    client: &Client,
+    api_key: &str,
+    voice: &LabeledVoice,
+    labels: &BTreeMap<String, String>,
+) -> Result<()> {
+    let url = format!("{}/v1/voices/{}/edit", api_base(), voice.voice_id);
+    let form = reqwest::multipart::Form::new()
+        .text("name", voice.name.clone())
+        .text("labels", serde_json::to_string(labels)?);
+
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to update voice labels")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    Ok(())
+}
+
+async fn label_command(
+    client: &Client,
+    api_key: &str,
+    command: VoiceLabelCommands,
+    assume_yes: bool,
+) -> Result<()> {
+    match command {
+        VoiceLabelCommands::Set { voice_id, labels } => {
+            let set = labels
+                .iter()
+                .map(|l| parse_label(l))
+                .collect::<Result<Vec<_>>>()?;
+            update_voice_labels(client, api_key, &voice_id, &set, &[]).await
+        }
+        VoiceLabelCommands::Rm { voice_id, keys } => {
+            update_voice_labels(client, api_key, &voice_id, &[], &keys).await
+        }
+        VoiceLabelCommands::Bulk {
+            filters,
+            set,
+            remove,
+            dry_run,
+        } => {
+            let filters = filters
+                .iter()
+                .map(|f| parse_label(f))
+                .collect::<Result<Vec<_>>>()?;
+            let set = set
+                .iter()
+                .map(|l| parse_label(l))
+                .collect::<Result<Vec<_>>>()?;
+            bulk_update_labels(
+                client, api_key, &filters, &set, &remove, dry_run, assume_yes,
+            )
+            .await
+        }
+    }
+}
+
+async fn update_voice_labels(
+    client: &Client,
+    api_key: &str,
+    voice_id: &str,
+    set: &[(String, String)],
+    remove: &[String],
+) -> Result<()> {
+    let voice = fetch_labeled_voice(client, api_key, voice_id).await?;
+    for key in remove {
+        if !voice.labels.contains_key(key) {
+            print_warning(&format!("Voice '{}' has no label '{}'", voice.name, key));
+        }
+    }
+
+    let labels = apply_label_changes(&voice.labels, set, remove);
+    if labels == voice.labels {
+        print_info("Labels unchanged");
+        return Ok(());
+    }
+
+    print_info(&format!("Updating labels for '{}'...", voice.name.cyan()));
+    write_voice_labels(client, api_key, &voice, &labels).await?;
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "voice_id": voice.voice_id,
+                "labels": labels,
+            }))?
+        );
+    } else {
+        print_success(&format!("Labels: {}", format_labels(&labels)));
+    }
+    Ok(())
+}
+
+async fn bulk_update_labels(
+    client: &Client,
+    api_key: &str,
+    filters: &[(String, String)],
+    set: &[(String, String)],
+    remove: &[String],
+    dry_run: bool,
+    assume_yes: bool,
+) -> Result<()> {
+    if set.is_empty() && remove.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Nothing to change. Use --set KEY=VALUE and/or --rm KEY"
+        ));
+    }
+
+    print_info("Fetching voices...");
+    let voices = fetch_labeled_voices(client, api_key).await?;
+
+    // Premade voices are read-only
+    let changes: Vec<(&LabeledVoice, BTreeMap<String, String>)> = voices
+        .iter()
+        .filter(|v| v.category.as_deref() != Some("premade") && voice_matches(v, filters))
+        .map(|v| (v, apply_label_changes(&v.labels, set, remove)))
+        .filter(|(v, labels)| *labels != v.labels)
+        .collect();
+
+    if changes.is_empty() {
+        print_info("No matching voices need changes");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Name", "Voice ID", "Labels Before", "Labels After"]);
+    for (voice, labels) in &changes {
+        table.add_row(vec![
+            voice.name.clone(),
+            voice.voice_id.clone(),
+            format_labels(&voice.labels),
+            format_labels(labels),
+        ]);
+    }
+    println!("{}", table);
+
+    if dry_run {
+        print_info(&format!(
+            "Dry run: {} voice(s) would be updated",
+            changes.len()
+        ));
+        return Ok(());
+    }
+
+    if !assume_yes {
+        let confirm =
+            crate::prompt::confirm(format!("Update labels on {} voice(s)?", changes.len()))?;
+        if !confirm {
+            print_info("Cancelled");
+            return Ok(());
+        }
+    }
+
+    let progress = Progress::bar(changes.len() as u64, "Updating labels");
+    let mut failures = Vec::new();
+    for (voice, labels) in &changes {
+        if let Err(e) = write_voice_labels(client, api_key, voice, labels).await {
+            failures.push((voice.name.clone(), e));
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+
+    for (name, error) in &failures {
+        print_warning(&format!("{}: {}", name, error));
+    }
+
+    if failures.is_empty() {
+        print_success(&format!("Updated labels on {} voice(s)", changes.len()));
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Label update failed for {} of {} voice(s)",
+            failures.len(),
+            changes.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(category: &str, labels: &[(&str, &str)]) -> LabeledVoice {
+        LabeledVoice {
+            voice_id: "v1".to_string(),
+            name: "Narrator".to_string(),
+            category: Some(category.to_string()),
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(
+            parse_label("project = game1").unwrap(),
+            ("project".to_string(), "game1".to_string())
+        );
+        assert_eq!(parse_label("note=").unwrap().1, "");
+        assert!(parse_label("project").is_err());
+        assert!(parse_label("=game1").is_err());
+    }
+
+    #[test]
+    fn test_apply_label_changes() {
+        let original = voice("cloned", &[("accent", "british"), ("project", "old")]).labels;
+        let updated = apply_label_changes(
+            &original,
+            &[("project".to_string(), "game1".to_string())],
+            &["accent".to_string(), "missing".to_string()],
+        );
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated["project"], "game1");
+    }
+
+    #[test]
+    fn test_voice_matches() {
+        let v = voice("cloned", &[("project", "Game1")]);
+        let filter = |k: &str, val: &str| vec![(k.to_string(), val.to_string())];
+        assert!(voice_matches(&v, &filter("category", "cloned")));
+        assert!(voice_matches(&v, &filter("project", "game1")));
+        assert!(!voice_matches(&v, &filter("category", "generated")));
+        assert!(!voice_matches(&v, &filter("accent", "british")));
+        assert!(voice_matches(&v, &[]));
+    }
+}
@@ -1,22 +1,25 @@
 use crate::cli::VoiceChangerArgs;
-use crate::output::{print_info, print_success};
+use crate::output::{print_info, print_success, Progress};
 use crate::utils::{
-    confirm_overwrite, format_to_extension, parse_output_format, write_bytes_to_file,
+    confirm_overwrite, default_output_path, format_to_extension, parse_output_format,
+    write_bytes_to_file,
 };
-use crate::validation::validate_voice_settings;
+use crate::validation::{validate_input_file, validate_sts_model, validate_voice_settings};
 
 #[cfg(feature = "audio")]
 use crate::audio::audio_io;
 
-use anyhow::Result;
+use crate::client::{api_base, api_error, create_http_client, SendWithRetry};
+use anyhow::{Context, Result};
 use colored::*;
-use elevenlabs_rs::{
-    endpoints::genai::voice_changer::{
-        VoiceChanger as VoiceChangerEndpoint, VoiceChangerBody, VoiceChangerQuery,
-    },
-    ElevenLabsClient, Model, VoiceSettings,
-};
+use futures_util::stream;
+use reqwest::multipart::{Form, Part};
 use std::path::Path;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+
+/// Size of each chunk read from disk while uploading
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
 
 pub async fn execute(
     args: VoiceChangerArgs,
@@ -49,9 +52,10 @@ pub async fn execute(
 
     let file_path = Path::new(file);
 
-    if !file_path.exists() {
-        return Err(anyhow::anyhow!("File not found: {}", file));
-    }
+    // Pre-flight checks (file, model family, voice settings)
+    validate_input_file(file_path)?;
+    validate_sts_model(&args.model)?;
+    validate_voice_settings(args.stability, args.similarity_boost, args.style)?;
 
     let metadata = std::fs::metadata(file_path)?;
     let file_size = metadata.len();
@@ -64,55 +68,26 @@ pub async fn execute(
     print_info(&format!("Target voice: {}", args.voice.yellow()));
     print_info(&format!("Model: {}", args.model.yellow()));
 
-    // Create client
-    let client = ElevenLabsClient::new(api_key);
-
-    // Parse model
-    let model = match args.model.as_str() {
-        "eleven_english_sts_v2" => Model::ElevenEnglishV2,
-        "eleven_multilingual_sts_v2" => Model::ElevenMultilingualV2STS,
-        _ => Model::ElevenMultilingualV2STS,
-    };
-
-    // Parse output format
-    let format = parse_output_format(output_format)?;
-
-    // Validate voice settings using validation module
-    validate_voice_settings(args.stability, args.similarity_boost, args.style)?;
-
-    // Build voice settings if any provided
-    let voice_settings =
-        if args.stability.is_some() || args.similarity_boost.is_some() || args.style.is_some() {
-            let mut settings = VoiceSettings::default();
-            if let Some(s) = args.stability {
-                settings = settings.with_stability(s);
-            }
-            if let Some(sb) = args.similarity_boost {
-                settings = settings.with_similarity_boost(sb);
-            }
-            if let Some(st) = args.style {
-                settings = settings.with_style(st);
-            }
-            Some(settings)
-        } else {
-            None
-        };
-
-    // Build request body
-    let mut body = VoiceChangerBody::new(file.clone()).with_model_id(model);
-
-    if let Some(settings) = voice_settings {
-        body = body.with_voice_settings(settings);
+    if args.remove_background_noise {
+        print_info("Background noise will be removed");
     }
 
-    // Build query with output format
-    let query = VoiceChangerQuery::default().with_output_format(format);
+    // Validate the output format before uploading anything
+    parse_output_format(output_format)?;
 
-    let endpoint = VoiceChangerEndpoint::new(&args.voice, body).with_query(query);
+    let voice_settings = voice_settings_json(args.stability, args.similarity_boost, args.style);
 
     // Transform
     let start_time = std::time::Instant::now();
-    let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let audio = convert_file(
+        api_key,
+        &args,
+        file_path,
+        file_size,
+        output_format,
+        voice_settings,
+    )
+    .await?;
     let duration = start_time.elapsed();
 
     // Determine output path
@@ -124,7 +99,7 @@ pub async fn execute(
             .and_then(|s| s.to_str())
             .unwrap_or("audio");
         let ext = format_to_extension(output_format);
-        format!("{}_transformed.{}", stem, ext)
+        default_output_path(&format!("{}_transformed.{}", stem, ext), Some(&args.voice))?
     };
 
     // Check for overwrite
@@ -144,10 +119,9 @@ pub async fn execute(
     ));
 
     // Play if requested
-    #[cfg(feature = "audio")]
     if args.play {
         print_info("Playing audio...");
-        if let Err(e) = audio_io::play_to_speaker(&audio) {
+        if let Err(e) = crate::player::play_audio(&audio) {
             print_info(&format!("Could not play audio: {}", e));
         }
     }
@@ -155,10 +129,107 @@ pub async fn execute(
     Ok(())
 }
 
+/// Voice settings form value, if any setting was given
+fn voice_settings_json(
+    stability: Option<f32>,
+    similarity_boost: Option<f32>,
+    style: Option<f32>,
+) -> Option<String> {
+    let mut settings = serde_json::Map::new();
+    for (name, value) in [
+        ("stability", stability),
+        ("similarity_boost", similarity_boost),
+        ("style", style),
+    ] {
+        if let Some(v) = value {
+            settings.insert(name.to_string(), serde_json::json!(v));
+        }
+    }
+    (!settings.is_empty()).then(|| serde_json::Value::Object(settings).to_string())
+}
+
+/// Upload the file in chunks (never holding it all in memory) and return the
+/// converted audio
+async fn convert_file(
+    api_key: &str,
+    args: &VoiceChangerArgs,
+    file_path: &Path,
+    file_size: u64,
+    output_format: &str,
+    voice_settings: Option<String>,
+) -> Result<Vec<u8>> {
+    let file = tokio::fs::File::open(file_path)
+        .await
+        .with_context(|| format!("Failed to open {}", file_path.display()))?;
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "audio".to_string());
+
+    let progress = Arc::new(Progress::bar(file_size, "Uploading audio"));
+    let upload_progress = Arc::clone(&progress);
+    let chunks = stream::unfold(Some(file), move |file| {
+        let progress = Arc::clone(&upload_progress);
+        async move {
+            let mut file = file?;
+            let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+            match file.read(&mut buf).await {
+                Ok(0) => {
+                    progress.set_message("Transforming voice");
+                    None
+                }
+                Ok(n) => {
+                    buf.truncate(n);
+                    progress.inc(n as u64);
+                    Some((Ok::<_, std::io::Error>(buf), Some(file)))
+                }
+                // Yield the error, then end the stream
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    });
+
+    let audio_part = Part::stream_with_length(reqwest::Body::wrap_stream(chunks), file_size)
+        .file_name(file_name);
+    let mut form = Form::new()
+        .part("audio", audio_part)
+        .text("model_id", args.model.clone())
+        .text(
+            "remove_background_noise",
+            args.remove_background_noise.to_string(),
+        );
+    if let Some(settings) = voice_settings {
+        form = form.text("voice_settings", settings);
+    }
+
+    let url = format!("{}/v1/speech-to-speech/{}", api_base(), args.voice);
+    let response = create_http_client()
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .query(&[("output_format", output_format)])
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to send voice changer request")?;
+    drop(progress);
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let audio = response
+        .bytes()
+        .await
+        .context("Failed to read converted audio")?;
+    Ok(audio.to_vec())
+}
+
 /// Record from microphone and transform voice
 #[cfg(feature = "audio")]
 async fn record_and_transform(args: VoiceChangerArgs, api_key: &str) -> Result<()> {
-    use elevenlabs_rs::ElevenLabsClient;
+    use crate::client::create_sdk_client;
+    use elevenlabs_rs::endpoints::genai::voice_changer::VoiceChangerBody;
+    use elevenlabs_rs::Model;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -185,7 +256,7 @@ async fn record_and_transform(args: VoiceChangerArgs, api_key: &str) -> Result<(
     print_info(&format!("Transforming voice with '{}'...", args.voice));
 
     // Create client and transform
-    let client = ElevenLabsClient::new(api_key);
+    let client = create_sdk_client(api_key);
 
     let model = match args.model.as_str() {
         "eleven_english_sts_v2" => Model::ElevenEnglishV2,
@@ -197,17 +268,34 @@ async fn record_and_transform(args: VoiceChangerArgs, api_key: &str) -> Result<(
     let endpoint =
         elevenlabs_rs::endpoints::genai::voice_changer::VoiceChanger::new(&args.voice, body);
 
+    let progress = Progress::spinner("Transforming voice");
     let audio = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    progress.finish();
 
     print_success(&format!("Transformed {} bytes of audio", audio.len()));
 
     // Play if requested
     if args.play {
         print_info("Playing transformed audio...");
-        if let Err(e) = audio_io::play_to_speaker(&audio) {
+        if let Err(e) = crate::player::play_audio(&audio) {
             print_info(&format!("Could not play audio: {}", e));
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_settings_json() {
+        assert_eq!(voice_settings_json(None, None, None), None);
+        let json = voice_settings_json(Some(0.5), None, Some(0.25)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["stability"], 0.5);
+        assert_eq!(value["style"], 0.25);
+        assert!(value.get("similarity_boost").is_none());
+    }
+}
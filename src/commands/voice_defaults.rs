@@ -0,0 +1,142 @@
+//! Per-voice default settings (`voice defaults`)
+//!
+//! Settings are stored in the config file under the voice name or ID as
+//! given, and `tts` and `dialogue` apply them whenever that voice is used.
+//! Flags given on the command line still win.
+
+use crate::cli::VoiceDefaultsCommands;
+use crate::config::{Config, VoiceDefaults};
+use crate::output::{is_json_mode, print_info, print_success};
+use crate::validation::validate_voice_settings;
+use anyhow::Result;
+use colored::*;
+use comfy_table::Table;
+
+pub fn execute(command: &VoiceDefaultsCommands) -> Result<()> {
+    match command {
+        VoiceDefaultsCommands::Set {
+            voice,
+            stability,
+            similarity_boost,
+            style,
+            speaker_boost,
+        } => set_defaults(
+            voice,
+            VoiceDefaults {
+                stability: *stability,
+                similarity_boost: *similarity_boost,
+                style: *style,
+                speaker_boost: *speaker_boost,
+            },
+        ),
+        VoiceDefaultsCommands::List => list_defaults(),
+        VoiceDefaultsCommands::Rm { voice } => remove_defaults(voice),
+    }
+}
+
+fn set_defaults(voice: &str, settings: VoiceDefaults) -> Result<()> {
+    if settings.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Specify at least one of --stability, --similarity-boost, --style or --speaker-boost"
+        ));
+    }
+    validate_voice_settings(
+        settings.stability,
+        settings.similarity_boost,
+        settings.style,
+    )?;
+
+    let stored = Config::update(|config| {
+        // Keep the spelling the voice was first stored under
+        let key = config
+            .voice_defaults_key(voice)
+            .unwrap_or(voice)
+            .to_string();
+        let stored = config
+            .voice_defaults
+            .get(&key)
+            .copied()
+            .unwrap_or_default()
+            .overridden_by(settings);
+        config.voice_defaults.insert(key, stored);
+        Ok(stored)
+    })?;
+
+    print_success(&format!(
+        "Defaults for '{}': {}",
+        voice.green(),
+        describe(&stored)
+    ));
+    Ok(())
+}
+
+fn list_defaults() -> Result<()> {
+    let config = Config::load()?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&config.voice_defaults)?);
+        return Ok(());
+    }
+
+    if config.voice_defaults.is_empty() {
+        print_info("No voice defaults stored");
+        return Ok(());
+    }
+
+    let show = |value: Option<f32>| value.map_or("-".to_string(), |v| v.to_string());
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Voice",
+        "Stability",
+        "Similarity Boost",
+        "Style",
+        "Speaker Boost",
+    ]);
+    for (voice, settings) in &config.voice_defaults {
+        table.add_row(vec![
+            voice.yellow().to_string(),
+            show(settings.stability),
+            show(settings.similarity_boost),
+            show(settings.style),
+            settings
+                .speaker_boost
+                .map_or("-".to_string(), |b| b.to_string()),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+fn remove_defaults(voice: &str) -> Result<()> {
+    let removed = Config::update(|config| {
+        let key = config.voice_defaults_key(voice).map(str::to_string);
+        Ok(key
+            .and_then(|key| config.voice_defaults.remove(&key))
+            .is_some())
+    })?;
+
+    if removed {
+        print_success(&format!("Removed defaults for '{}'", voice.green()));
+    } else {
+        print_info(&format!("No defaults stored for '{}'", voice));
+    }
+    Ok(())
+}
+
+/// `stability=0.6, style=0.3`
+fn describe(settings: &VoiceDefaults) -> String {
+    let mut parts = Vec::new();
+    if let Some(v) = settings.stability {
+        parts.push(format!("stability={}", v));
+    }
+    if let Some(v) = settings.similarity_boost {
+        parts.push(format!("similarity_boost={}", v));
+    }
+    if let Some(v) = settings.style {
+        parts.push(format!("style={}", v));
+    }
+    if let Some(v) = settings.speaker_boost {
+        parts.push(format!("speaker_boost={}", v));
+    }
+    parts.join(", ")
+}
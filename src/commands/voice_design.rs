@@ -1,47 +1,55 @@
 use crate::cli::VoiceDesignArgs;
-use crate::output::{print_info, print_success};
+use crate::client::create_sdk_client;
+use crate::output::{print_info, print_success, Progress};
 use crate::utils::{confirm_overwrite, write_bytes_to_file};
 use anyhow::Result;
 use base64::{engine::general_purpose, Engine as _};
 use colored::*;
-use elevenlabs_rs::{
-    endpoints::genai::text_to_voice::{TextToVoice, TextToVoiceBody},
-    ElevenLabsClient,
-};
+use elevenlabs_rs::endpoints::genai::text_to_voice::{TextToVoice, TextToVoiceBody};
 use std::path::Path;
 
-pub async fn execute(args: VoiceDesignArgs, api_key: &str, assume_yes: bool) -> Result<()> {
-    let client = ElevenLabsClient::new(api_key);
-
-    // Validate text length
-    if args.text.len() < 100 {
+/// Preview text must be 100-1000 characters
+pub(crate) fn validate_preview_text(text: &str) -> Result<()> {
+    if text.len() < 100 {
         return Err(anyhow::anyhow!(
             "Text must be at least 100 characters long for voice design (current: {})",
-            args.text.len()
+            text.len()
         ));
     }
 
-    if args.text.len() > 1000 {
+    if text.len() > 1000 {
         return Err(anyhow::anyhow!(
             "Text must be at most 1000 characters long for voice design (current: {})",
-            args.text.len()
+            text.len()
         ));
     }
+    Ok(())
+}
+
+pub async fn execute(args: VoiceDesignArgs, api_key: &str, assume_yes: bool) -> Result<()> {
+    let client = create_sdk_client(api_key);
+    // clap requires both unless a subcommand is given
+    let description = args.description.unwrap_or_default();
+    let text = args.text.unwrap_or_default();
+
+    validate_preview_text(&text)?;
 
     print_info(&format!(
         "Generating voice from description: {}",
-        args.description.cyan()
+        description.cyan()
     ));
-    print_info(&format!("Text length: {} characters", args.text.len()));
+    print_info(&format!("Text length: {} characters", text.len()));
 
     // Build body
-    let body = TextToVoiceBody::new(&args.description).with_text(&args.text);
+    let body = TextToVoiceBody::new(&description).with_text(&text);
 
     let endpoint = TextToVoice::new(body);
 
     // Generate voice previews
     let start_time = std::time::Instant::now();
+    let progress = Progress::spinner("Designing voice");
     let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    progress.finish();
     let duration = start_time.elapsed();
 
     let previews = response.previews;
@@ -0,0 +1,443 @@
+//! Iterative voice design (`voice-design iterate`)
+//!
+//! Each call runs one step against a session kept in the data directory:
+//! design previews from a prompt, remix a preview with a refinement prompt,
+//! rate previews, or save the best one as a voice. Without a step the
+//! session history is shown. The preview text is kept for the whole session
+//! so rounds stay comparable.
+
+use crate::cli::{PreviewRating, VoiceDesignIterateArgs};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::voice_design::validate_preview_text;
+use crate::output::{is_json_mode, print_info, print_success, Progress};
+use crate::utils::{sanitize_path_component, unix_now, write_atomic, write_bytes_to_file};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use colored::*;
+use comfy_table::Table;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DesignSession {
+    name: String,
+    /// Preview text used for every round
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    rounds: Vec<DesignRound>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    saved: Option<SavedVoice>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DesignRound {
+    prompt: String,
+    /// Preview this round remixed, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remix_of: Option<String>,
+    created_at: u64,
+    previews: Vec<Preview>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Preview {
+    generated_voice_id: String,
+    file: String,
+    #[serde(default)]
+    duration_secs: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rating: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedVoice {
+    voice_id: String,
+    name: String,
+    generated_voice_id: String,
+}
+
+fn session_path(name: &str) -> Result<PathBuf> {
+    crate::paths::data_file(&format!(
+        "voice-design/{}.json",
+        sanitize_path_component(name)
+    ))
+    .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))
+}
+
+fn load_session(path: &Path, name: &str) -> Result<DesignSession> {
+    if !path.exists() {
+        return Ok(DesignSession {
+            name: name.to_string(),
+            ..Default::default()
+        });
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Invalid session {}", path.display()))
+}
+
+impl DesignSession {
+    /// Find a preview by `<round>.<n>` or generated voice ID
+    fn find(&self, reference: &str) -> Option<(usize, usize)> {
+        if let Some((round, n)) = reference.split_once('.') {
+            if let (Ok(round), Ok(n)) = (round.parse::<usize>(), n.parse::<usize>()) {
+                return (round >= 1
+                    && n >= 1
+                    && self.rounds.get(round - 1)?.previews.get(n - 1).is_some())
+                .then(|| (round - 1, n - 1));
+            }
+        }
+        self.rounds.iter().enumerate().find_map(|(r, round)| {
+            round
+                .previews
+                .iter()
+                .position(|p| p.generated_voice_id == reference)
+                .map(|n| (r, n))
+        })
+    }
+
+    fn preview(&self, reference: &str) -> Result<(usize, usize)> {
+        self.find(reference).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No preview '{}' in session '{}' (use <round>.<n> or a generated voice ID)",
+                reference,
+                self.name
+            )
+        })
+    }
+
+    fn rate(&mut self, rating: &PreviewRating) -> Result<()> {
+        let (r, n) = self.preview(&rating.preview)?;
+        self.rounds[r].previews[n].rating = Some(rating.stars);
+        Ok(())
+    }
+
+    /// Highest rated preview, latest first on ties
+    fn best(&self) -> Option<(usize, usize)> {
+        let mut best: Option<((usize, usize), u8)> = None;
+        for (r, round) in self.rounds.iter().enumerate() {
+            for (n, preview) in round.previews.iter().enumerate() {
+                if let Some(rating) = preview.rating {
+                    if best.is_none_or(|(_, top)| rating >= top) {
+                        best = Some(((r, n), rating));
+                    }
+                }
+            }
+        }
+        best.map(|(position, _)| position)
+    }
+}
+
+pub async fn execute(args: VoiceDesignIterateArgs, api_key: &str) -> Result<()> {
+    let path = session_path(&args.session)?;
+
+    if args.reset {
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+        }
+        print_success(&format!("Session '{}' reset", args.session));
+        return Ok(());
+    }
+
+    let mut session = load_session(&path, &args.session)?;
+    if let Some(saved) = &session.saved {
+        if args.prompt.is_some() || args.save.is_some() {
+            return Err(anyhow::anyhow!(
+                "Session '{}' was saved as voice '{}' ({}); use --reset or another --session",
+                session.name,
+                saved.name,
+                saved.voice_id
+            ));
+        }
+    }
+
+    if let Some(text) = &args.text {
+        validate_preview_text(text)?;
+        session.text = Some(text.clone());
+    }
+    for rating in &args.rate {
+        session.rate(rating)?;
+    }
+
+    let client = create_http_client();
+    if let Some(prompt) = &args.prompt {
+        let out_dir =
+            PathBuf::from(args.out_dir.clone().unwrap_or_else(|| {
+                format!("voice-design-{}", sanitize_path_component(&session.name))
+            }));
+        // Saved voices in the account can be remixed too, so unknown IDs pass through
+        let remix_of = args.remix.as_ref().map(|reference| {
+            session
+                .find(reference)
+                .map(|(r, n)| session.rounds[r].previews[n].generated_voice_id.clone())
+                .unwrap_or_else(|| reference.clone())
+        });
+        let round =
+            design_round(&client, api_key, &mut session, prompt, remix_of, &out_dir).await?;
+        session.rounds.push(round);
+    }
+
+    if let Some(reference) = &args.save {
+        let (r, n) = session.preview(reference)?;
+        // clap requires --name with --save
+        let name = args.name.clone().unwrap_or_default();
+        let round = &session.rounds[r];
+        let generated_voice_id = round.previews[n].generated_voice_id.clone();
+        let voice_id =
+            save_voice(&client, api_key, &name, &round.prompt, &generated_voice_id).await?;
+        session.saved = Some(SavedVoice {
+            voice_id,
+            name,
+            generated_voice_id,
+        });
+    }
+
+    if !session.rounds.is_empty() || session.text.is_some() {
+        write_atomic(&path, serde_json::to_string_pretty(&session)?.as_bytes())?;
+    }
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&session)?);
+        return Ok(());
+    }
+    print_history(&session);
+    if let Some(saved) = &session.saved {
+        print_success(&format!(
+            "Saved as voice '{}' ({})",
+            saved.name,
+            saved.voice_id.green()
+        ));
+    } else if session.rounds.is_empty() {
+        print_info("Start with: voice-design iterate --prompt \"<voice description>\"");
+    } else {
+        print_info(
+            "Next: --rate <round>.<n>=<1-5>, --remix <round>.<n> --prompt \"<change>\", or --save <round>.<n> --name <NAME>",
+        );
+    }
+    Ok(())
+}
+
+/// Design (or remix) one round of previews and write their audio
+async fn design_round(
+    client: &reqwest::Client,
+    api_key: &str,
+    session: &mut DesignSession,
+    prompt: &str,
+    remix_of: Option<String>,
+    out_dir: &Path,
+) -> Result<DesignRound> {
+    let url = match &remix_of {
+        Some(voice_id) => format!("{}/v1/text-to-voice/{}/remix", api_base(), voice_id),
+        None => format!("{}/v1/text-to-voice/design", api_base()),
+    };
+    let mut body = json!({ "voice_description": prompt });
+    match &session.text {
+        Some(text) => body["text"] = json!(text),
+        None => body["auto_generate_text"] = json!(true),
+    }
+
+    let action = if remix_of.is_some() {
+        "Remixing voice"
+    } else {
+        "Designing voice"
+    };
+    print_info(&format!(
+        "Round {}: {}",
+        session.rounds.len() + 1,
+        prompt.cyan()
+    ));
+    let progress = Progress::spinner(action);
+    let response = client
+        .post(&url)
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to send voice design request")?;
+    progress.finish();
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let result: Value = read_json(response).await?;
+
+    // Keep the generated text so later rounds are compared on the same words
+    if session.text.is_none() {
+        session.text = result["text"].as_str().map(str::to_string);
+    }
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+    let number = session.rounds.len() + 1;
+    let mut previews = Vec::new();
+    for (i, preview) in result["previews"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let generated_voice_id = preview["generated_voice_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Preview without generated_voice_id"))?
+            .to_string();
+        let audio = general_purpose::STANDARD
+            .decode(preview["audio_base_64"].as_str().unwrap_or_default())
+            .map_err(|e| anyhow::anyhow!("Failed to decode audio: {}", e))?;
+        let file = out_dir.join(format!("{}.{}_{}.mp3", number, i + 1, generated_voice_id));
+        write_bytes_to_file(&audio, &file)?;
+        previews.push(Preview {
+            generated_voice_id,
+            file: file.display().to_string(),
+            duration_secs: preview["duration_secs"].as_f64().unwrap_or(0.0),
+            rating: None,
+        });
+    }
+    if previews.is_empty() {
+        return Err(anyhow::anyhow!("The API returned no previews"));
+    }
+    print_success(&format!("Generated {} preview(s)", previews.len()));
+
+    Ok(DesignRound {
+        prompt: prompt.to_string(),
+        remix_of,
+        created_at: unix_now(),
+        previews,
+    })
+}
+
+/// Create an account voice from a generated preview
+async fn save_voice(
+    client: &reqwest::Client,
+    api_key: &str,
+    name: &str,
+    description: &str,
+    generated_voice_id: &str,
+) -> Result<String> {
+    let response = client
+        .post(format!("{}/v1/text-to-voice", api_base()))
+        .header("xi-api-key", api_key)
+        .json(&json!({
+            "voice_name": name,
+            "voice_description": description,
+            "generated_voice_id": generated_voice_id,
+        }))
+        .send_with_retry()
+        .await
+        .context("Failed to save voice")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let voice: Value = read_json(response).await?;
+    voice["voice_id"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Response did not include a voice_id"))
+}
+
+fn print_history(session: &DesignSession) {
+    if session.rounds.is_empty() {
+        print_info(&format!("Session '{}' has no rounds yet", session.name));
+        return;
+    }
+
+    let best = session.best();
+    let mut table = Table::new();
+    table.set_header(vec!["Preview", "Prompt", "Rating", "Duration", "File"]);
+    for (r, round) in session.rounds.iter().enumerate() {
+        let prompt = match &round.remix_of {
+            Some(of) => format!("{} (remix of {})", round.prompt, label_of(session, of)),
+            None => round.prompt.clone(),
+        };
+        for (n, preview) in round.previews.iter().enumerate() {
+            let label = format!("{}.{}", r + 1, n + 1);
+            let rating = match preview.rating {
+                Some(stars) if best == Some((r, n)) => {
+                    format!("{} best", stars_text(stars)).green().to_string()
+                }
+                Some(stars) => stars_text(stars),
+                None => "-".to_string(),
+            };
+            table.add_row(vec![
+                label,
+                if n == 0 {
+                    prompt.clone()
+                } else {
+                    String::new()
+                },
+                rating,
+                format!("{:.1}s", preview.duration_secs),
+                preview.file.clone(),
+            ]);
+        }
+    }
+    println!("{}", table);
+}
+
+/// `<round>.<n>` label for a generated voice ID, or the ID itself
+fn label_of(session: &DesignSession, generated_voice_id: &str) -> String {
+    session
+        .find(generated_voice_id)
+        .map(|(r, n)| format!("{}.{}", r + 1, n + 1))
+        .unwrap_or_else(|| generated_voice_id.to_string())
+}
+
+fn stars_text(stars: u8) -> String {
+    format!("{}/5", stars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> DesignSession {
+        let preview = |id: &str, rating| Preview {
+            generated_voice_id: id.to_string(),
+            file: format!("{}.mp3", id),
+            duration_secs: 3.0,
+            rating,
+        };
+        DesignSession {
+            name: "test".to_string(),
+            text: None,
+            rounds: vec![
+                DesignRound {
+                    prompt: "old sailor".to_string(),
+                    remix_of: None,
+                    created_at: 0,
+                    previews: vec![preview("a", Some(3)), preview("b", None)],
+                },
+                DesignRound {
+                    prompt: "more gravelly".to_string(),
+                    remix_of: Some("a".to_string()),
+                    created_at: 0,
+                    previews: vec![preview("c", Some(3))],
+                },
+            ],
+            saved: None,
+        }
+    }
+
+    #[test]
+    fn test_find_preview_by_label_or_id() {
+        let session = session();
+        assert_eq!(session.find("1.2"), Some((0, 1)));
+        assert_eq!(session.find("c"), Some((1, 0)));
+        assert_eq!(session.find("3.1"), None);
+        assert_eq!(session.find("0.1"), None);
+        assert_eq!(label_of(&session, "a"), "1.1");
+    }
+
+    #[test]
+    fn test_rating_and_best() {
+        let mut session = session();
+        // Ties go to the later round
+        assert_eq!(session.best(), Some((1, 0)));
+        session.rate(&"1.2=5".parse().unwrap()).unwrap();
+        assert_eq!(session.best(), Some((0, 1)));
+        assert!(session.rate(&"9.9=1".parse().unwrap()).is_err());
+        assert!("1.1=6".parse::<PreviewRating>().is_err());
+        assert!("1.1".parse::<PreviewRating>().is_err());
+    }
+}
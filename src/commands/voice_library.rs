@@ -1,6 +1,9 @@
 use crate::cli::{VoiceLibraryArgs, VoiceLibraryCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{
+    api_base, api_error, create_http_client, create_sdk_client, read_json, retry_sdk, SendWithRetry,
+};
+use crate::commands::voice::parse_label;
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
@@ -11,7 +14,9 @@ use elevenlabs_rs::{
     },
     ElevenLabsClient,
 };
-use serde::Deserialize;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 pub async fn execute(args: VoiceLibraryArgs, api_key: &str) -> Result<()> {
     match args.command {
@@ -27,7 +32,7 @@ pub async fn execute(args: VoiceLibraryArgs, api_key: &str) -> Result<()> {
             search,
             featured,
         } => {
-            let client = ElevenLabsClient::new(api_key);
+            let client = create_sdk_client(api_key);
             list_shared_voices(
                 &client,
                 page_size,
@@ -49,9 +54,26 @@ pub async fn execute(args: VoiceLibraryArgs, api_key: &str) -> Result<()> {
             voice_id,
             name,
         } => {
-            let client = ElevenLabsClient::new(api_key);
+            let client = create_sdk_client(api_key);
             add_shared_voice(&client, &public_user_id, &voice_id, &name).await?
         }
+        VoiceLibraryCommands::Publish {
+            voice_id,
+            category,
+            labels,
+            notice_period,
+            name,
+            description,
+        } => {
+            let listing = Listing {
+                category,
+                labels,
+                notice_period,
+                name,
+                description,
+            };
+            publish_voice(api_key, &voice_id, &listing).await?
+        }
         VoiceLibraryCommands::Collections { page_size } => {
             list_collections(api_key, page_size).await?
         }
@@ -67,7 +89,7 @@ async fn list_saved_voices(api_key: &str, page_size: Option<u32>) -> Result<()>
     let client = create_http_client();
     print_info("Fetching saved voices...");
 
-    let mut url = "https://api.elevenlabs.io/v2/voices?voice_type=saved".to_string();
+    let mut url = format!("{}/v2/voices?voice_type=saved", api_base());
     if let Some(ps) = page_size {
         url.push_str(&format!("&page_size={}", ps));
     }
@@ -75,21 +97,20 @@ async fn list_saved_voices(api_key: &str, page_size: Option<u32>) -> Result<()>
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch saved voices")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct SavedVoicesResponse {
         voices: Vec<SavedVoice>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct SavedVoice {
         voice_id: String,
         name: String,
@@ -99,7 +120,12 @@ async fn list_saved_voices(api_key: &str, page_size: Option<u32>) -> Result<()>
         collection_ids: Option<Vec<String>>,
     }
 
-    let result: SavedVoicesResponse = response.json().await.context("Failed to parse response")?;
+    let result: SavedVoicesResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.voices)?);
+        return Ok(());
+    }
 
     if result.voices.is_empty() {
         print_info("No saved voices found");
@@ -133,7 +159,7 @@ async fn list_collections(api_key: &str, page_size: Option<u32>) -> Result<()> {
     let client = create_http_client();
     print_info("Fetching voice collections...");
 
-    let mut url = "https://api.elevenlabs.io/v1/voices/collections".to_string();
+    let mut url = format!("{}/v1/voices/collections", api_base());
     if let Some(ps) = page_size {
         url.push_str(&format!("?page_size={}", ps));
     }
@@ -141,21 +167,20 @@ async fn list_collections(api_key: &str, page_size: Option<u32>) -> Result<()> {
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch collections")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct CollectionsResponse {
         collections: Vec<CollectionInfo>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct CollectionInfo {
         collection_id: String,
         name: String,
@@ -165,7 +190,12 @@ async fn list_collections(api_key: &str, page_size: Option<u32>) -> Result<()> {
         created_at: Option<String>,
     }
 
-    let result: CollectionsResponse = response.json().await.context("Failed to parse response")?;
+    let result: CollectionsResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.collections)?);
+        return Ok(());
+    }
 
     if result.collections.is_empty() {
         print_info("No collections found");
@@ -197,28 +227,28 @@ async fn list_collection_voices(api_key: &str, collection_id: &str) -> Result<()
     ));
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/voices/collections/{}/voices",
+        "{}/v1/voices/collections/{}/voices",
+        api_base(),
         collection_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch collection voices")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct CollectionVoicesResponse {
         voices: Vec<CollectionVoice>,
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct CollectionVoice {
         voice_id: String,
         name: String,
@@ -226,8 +256,12 @@ async fn list_collection_voices(api_key: &str, collection_id: &str) -> Result<()
         category: Option<String>,
     }
 
-    let result: CollectionVoicesResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: CollectionVoicesResponse = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result.voices)?);
+        return Ok(());
+    }
 
     if result.voices.is_empty() {
         print_info("No voices in this collection");
@@ -332,6 +366,32 @@ async fn list_shared_voices(
     let endpoint = GetSharedVoices::with_query(query);
     let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
 
+    if is_json_mode() {
+        let voices: Vec<_> = response
+            .voices
+            .iter()
+            .map(|voice| {
+                json!({
+                    "voice_id": voice.voice_id,
+                    "name": voice.name,
+                    "public_owner_id": voice.public_owner_id,
+                    "category": voice.category,
+                    "gender": voice.gender,
+                    "age": voice.age,
+                    "accent": voice.accent,
+                    "language": voice.language,
+                    "use_case": voice.use_case,
+                    "description": voice.description,
+                    "preview_url": voice.preview_url,
+                    "cloned_by_count": voice.cloned_by_count,
+                    "featured": voice.featured,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&voices)?);
+        return Ok(());
+    }
+
     if response.voices.is_empty() {
         print_info("No shared voices found with the given criteria");
         return Ok(());
@@ -361,6 +421,180 @@ async fn list_shared_voices(
     Ok(())
 }
 
+/// Label keys the library lists voices by
+const LIBRARY_LABEL_KEYS: &[&str] = &[
+    "accent",
+    "gender",
+    "age",
+    "language",
+    "use_case",
+    "descriptive",
+];
+
+/// Longest notice period the library allows, in days
+const MAX_NOTICE_PERIOD_DAYS: u32 = 730;
+
+/// Listing settings given to `library publish`
+struct Listing {
+    category: Option<String>,
+    labels: Vec<String>,
+    notice_period: Option<u32>,
+    name: Option<String>,
+    description: Option<String>,
+}
+
+/// Sharing settings for `voice`: the given ones over the current listing,
+/// falling back to the voice's own details
+fn sharing_settings(voice: &Value, listing: &Listing) -> Result<Value> {
+    let sharing = &voice["sharing"];
+    let category = match (&listing.category, voice["category"].as_str()) {
+        (Some(category), _) => category.clone(),
+        (None, Some("professional")) => "professional".to_string(),
+        (None, Some("generated")) => "generated".to_string(),
+        (None, Some("cloned")) => {
+            return Err(anyhow::anyhow!(
+                "Instant voice clones can't be shared in the library; only professional clones and designed voices can"
+            ))
+        }
+        (None, Some("premade")) => {
+            return Err(anyhow::anyhow!("Premade voices are already public"))
+        }
+        (None, other) => {
+            return Err(anyhow::anyhow!(
+                "Can't tell how to list a '{}' voice. Pass --category",
+                other.unwrap_or("unknown")
+            ))
+        }
+    };
+    if listing
+        .notice_period
+        .is_some_and(|days| days > MAX_NOTICE_PERIOD_DAYS)
+    {
+        return Err(anyhow::anyhow!(
+            "--notice-period must be at most {} days",
+            MAX_NOTICE_PERIOD_DAYS
+        ));
+    }
+
+    let mut labels: serde_json::Map<String, Value> = sharing["labels"]
+        .as_object()
+        .or(voice["labels"].as_object())
+        .map(|labels| {
+            labels
+                .iter()
+                .filter(|(key, _)| LIBRARY_LABEL_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    for label in &listing.labels {
+        let (key, value) = parse_label(label)?;
+        if !LIBRARY_LABEL_KEYS.contains(&key.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Unknown library label '{}'. Use one of: {}",
+                key,
+                LIBRARY_LABEL_KEYS.join(", ")
+            ));
+        }
+        labels.insert(key, json!(value));
+    }
+
+    let current = |field: &str| {
+        listing_value(&sharing[field])
+            .or_else(|| listing_value(&voice[field]))
+            .cloned()
+    };
+    let mut settings = json!({
+        "category": category,
+        "name": listing.name.as_deref().map(|n| json!(n)).or_else(|| current("name")),
+        "labels": labels,
+        "enabled_in_library": true,
+    });
+    if let Some(description) = listing
+        .description
+        .as_deref()
+        .map(|d| json!(d))
+        .or_else(|| current("description"))
+    {
+        settings["description"] = description;
+    }
+    if let Some(days) = listing
+        .notice_period
+        .map(|days| json!(days))
+        .or_else(|| listing_value(&sharing["notice_period"]).cloned())
+    {
+        settings["notice_period"] = days;
+    }
+    Ok(settings)
+}
+
+/// A set, non-empty listing field
+fn listing_value(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Null => None,
+        Value::String(s) if s.is_empty() => None,
+        value => Some(value),
+    }
+}
+
+async fn publish_voice(api_key: &str, voice_id: &str, listing: &Listing) -> Result<()> {
+    let client = create_http_client();
+    print_info(&format!("Fetching voice '{}'...", voice_id.cyan()));
+
+    let response = client
+        .get(format!("{}/v1/voices/{}", api_base(), voice_id))
+        .header("xi-api-key", api_key)
+        .query(&[("with_settings", "false")])
+        .send_with_retry()
+        .await
+        .context("Failed to fetch voice")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let voice: Value = read_json(response).await?;
+    let settings = sharing_settings(&voice, listing)?;
+    let reshare = voice["sharing"]["status"].as_str() == Some("enabled");
+
+    print_info(&format!(
+        "{} '{}' as {}...",
+        if reshare {
+            "Updating the library listing of"
+        } else {
+            "Sharing"
+        },
+        settings["name"].as_str().unwrap_or(voice_id).cyan(),
+        settings["category"].as_str().unwrap_or_default()
+    ));
+    let response = client
+        .post(format!("{}/v1/voices/{}/share", api_base(), voice_id))
+        .header("xi-api-key", api_key)
+        .json(&settings)
+        .send_with_retry()
+        .await
+        .context("Failed to share voice")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let result: Value = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+    print_success(if reshare {
+        "Library listing updated"
+    } else {
+        "Voice submitted to the library"
+    });
+    if let Some(status) = result["review_status"].as_str() {
+        println!("  Review status: {}", status.yellow());
+    }
+    if let Some(message) = result["review_message"].as_str().filter(|m| !m.is_empty()) {
+        println!("  Review message: {}", message);
+    }
+    Ok(())
+}
+
 async fn add_shared_voice(
     client: &ElevenLabsClient,
     public_user_id: &str,
@@ -369,11 +603,81 @@ async fn add_shared_voice(
 ) -> Result<()> {
     print_info(&format!("Adding shared voice '{}'...", name.cyan()));
 
-    let endpoint = AddSharedVoice::new(public_user_id, voice_id, name);
-    let response = client.hit(endpoint).await.map_err(|e| anyhow::anyhow!(e))?;
+    let response = retry_sdk(Method::POST, || {
+        client.hit(AddSharedVoice::new(public_user_id, voice_id, name))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
 
     print_success(&format!("Added voice '{}' to your library", name));
     println!("  Voice ID: {}", response.voice_id.cyan());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(labels: &[&str]) -> Listing {
+        Listing {
+            category: None,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            notice_period: None,
+            name: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_sharing_settings_for_new_listing() {
+        let voice = json!({
+            "name": "Narrator",
+            "category": "professional",
+            "description": "",
+            "labels": { "accent": "american", "project": "audiobooks" }
+        });
+        let settings =
+            sharing_settings(&voice, &listing(&["accent=british", "gender=male"])).unwrap();
+        assert_eq!(
+            settings,
+            json!({
+                "category": "professional",
+                "name": "Narrator",
+                "labels": { "accent": "british", "gender": "male" },
+                "enabled_in_library": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_sharing_settings_keep_current_listing() {
+        let voice = json!({
+            "name": "Narrator",
+            "category": "generated",
+            "sharing": {
+                "status": "enabled",
+                "name": "Deep Narrator",
+                "description": "Warm and slow",
+                "notice_period": 90,
+                "labels": { "age": "old" }
+            }
+        });
+        let settings = sharing_settings(&voice, &listing(&[])).unwrap();
+        assert_eq!(settings["name"], "Deep Narrator");
+        assert_eq!(settings["description"], "Warm and slow");
+        assert_eq!(settings["notice_period"], 90);
+        assert_eq!(settings["labels"], json!({ "age": "old" }));
+    }
+
+    #[test]
+    fn test_sharing_settings_errors() {
+        let clone = json!({ "name": "Me", "category": "cloned" });
+        assert!(sharing_settings(&clone, &listing(&[])).is_err());
+        let pro = json!({ "name": "Me", "category": "professional" });
+        assert!(sharing_settings(&pro, &listing(&["mood=happy"])).is_err());
+        let mut long_notice = listing(&[]);
+        long_notice.notice_period = Some(1000);
+        assert!(sharing_settings(&pro, &long_notice).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+//! Local similarity scoring of a generated clip against a reference recording
+//! (`voice score`)
+//!
+//! Both files are decoded to 16 kHz mono and reduced to a voice profile: the
+//! mean MFCCs (spectral envelope, which carries timbre) over frames with
+//! speech, and pitch statistics from autocorrelation. The MFCC distance and
+//! pitch offset are rough signals for comparing clone settings, not a speaker
+//! verification score.
+
+use crate::output::is_json_mode;
+use crate::pcm::{decode_file, resample};
+use anyhow::Result;
+use colored::*;
+use comfy_table::Table;
+use serde::Serialize;
+use serde_json::json;
+use std::f64::consts::PI;
+use std::path::Path;
+
+/// Rate MFCCs are computed at
+const ANALYSIS_RATE: u32 = 16_000;
+
+/// 25 ms frames every 10 ms
+const FRAME_LEN: usize = 400;
+const HOP_LEN: usize = 160;
+const FFT_LEN: usize = 512;
+
+const MEL_FILTERS: usize = 26;
+
+/// Coefficients kept, including c0 (which follows loudness and is not compared)
+const MFCC_COEFFS: usize = 13;
+
+/// Frames more than this far below the loudest frame count as silence
+const SPEECH_RANGE_DB: f64 = 30.0;
+
+/// Rate pitch is tracked at, and the range searched
+const PITCH_RATE: u32 = 8_000;
+const PITCH_FRAME_LEN: usize = 320;
+const PITCH_HOP_LEN: usize = 80;
+const MIN_PITCH_HZ: f64 = 60.0;
+const MAX_PITCH_HZ: f64 = 400.0;
+
+/// Normalized autocorrelation a frame needs to count as voiced
+const VOICING_THRESHOLD: f64 = 0.6;
+
+/// MFCC distance at which the similarity score drops to about 37
+const SCORE_SCALE: f64 = 10.0;
+
+#[derive(Debug, Serialize)]
+struct PitchStats {
+    median_hz: f64,
+    mean_hz: f64,
+    std_hz: f64,
+    p10_hz: f64,
+    p90_hz: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct VoiceProfile {
+    duration_secs: f64,
+    speech_secs: f64,
+    #[serde(skip)]
+    mfcc_mean: Vec<f64>,
+    pitch: Option<PitchStats>,
+}
+
+pub fn execute(reference: &str, candidate: &str) -> Result<()> {
+    let reference_profile = analyze(Path::new(reference))?;
+    let candidate_profile = analyze(Path::new(candidate))?;
+
+    let distance = mfcc_distance(&reference_profile.mfcc_mean, &candidate_profile.mfcc_mean);
+    let similarity = similarity(distance);
+    let pitch_offset = match (&reference_profile.pitch, &candidate_profile.pitch) {
+        (Some(r), Some(c)) => Some(semitones(r.median_hz, c.median_hz)),
+        _ => None,
+    };
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "reference": reference_profile,
+                "candidate": candidate_profile,
+                "mfcc_distance": distance,
+                "pitch_offset_semitones": pitch_offset,
+                "similarity": similarity,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["", "Reference", "Candidate"]);
+    table.add_row(vec![
+        "Duration".to_string(),
+        format!("{:.1}s", reference_profile.duration_secs),
+        format!("{:.1}s", candidate_profile.duration_secs),
+    ]);
+    table.add_row(vec![
+        "Speech".to_string(),
+        format!("{:.1}s", reference_profile.speech_secs),
+        format!("{:.1}s", candidate_profile.speech_secs),
+    ]);
+    let pitch_row = |label: &str, cell: fn(&PitchStats) -> String| {
+        let value = |profile: &VoiceProfile| {
+            profile
+                .pitch
+                .as_ref()
+                .map(cell)
+                .unwrap_or_else(|| "-".to_string())
+        };
+        vec![
+            label.to_string(),
+            value(&reference_profile),
+            value(&candidate_profile),
+        ]
+    };
+    table.add_row(pitch_row("Median pitch", |p| {
+        format!("{:.0} Hz", p.median_hz)
+    }));
+    table.add_row(pitch_row("Pitch range (p10-p90)", |p| {
+        format!("{:.0}-{:.0} Hz", p.p10_hz, p.p90_hz)
+    }));
+    table.add_row(pitch_row("Pitch variation", |p| {
+        format!("±{:.0} Hz", p.std_hz)
+    }));
+    println!("{}", table);
+
+    println!("MFCC distance: {:.2} (lower is closer)", distance);
+    if let Some(offset) = pitch_offset {
+        println!("Pitch offset: {:+.1} semitones", offset);
+    }
+    let score = format!("{:.0}/100", similarity);
+    let score = match similarity {
+        s if s >= 70.0 => score.green(),
+        s if s >= 40.0 => score.yellow(),
+        _ => score.red(),
+    };
+    println!("Similarity (rough): {}", score.bold());
+    Ok(())
+}
+
+fn analyze(path: &Path) -> Result<VoiceProfile> {
+    let samples = decode_file(path, ANALYSIS_RATE, "voice score")?;
+    let filters = mel_filterbank();
+    let mut mfcc_mean = vec![0.0; MFCC_COEFFS];
+    let mut frames = 0;
+    for frame in speech_frames(&samples) {
+        for (sum, c) in mfcc_mean.iter_mut().zip(mfcc(frame, &filters)) {
+            *sum += c;
+        }
+        frames += 1;
+    }
+    if frames == 0 {
+        return Err(anyhow::anyhow!("No speech found in {}", path.display()));
+    }
+    for sum in &mut mfcc_mean {
+        *sum /= frames as f64;
+    }
+
+    Ok(VoiceProfile {
+        duration_secs: samples.len() as f64 / ANALYSIS_RATE as f64,
+        speech_secs: frames as f64 * HOP_LEN as f64 / ANALYSIS_RATE as f64,
+        mfcc_mean,
+        pitch: pitch_stats(&resample(&samples, ANALYSIS_RATE, PITCH_RATE)),
+    })
+}
+
+/// A sample as a float in -1..1
+fn to_unit(sample: i16) -> f64 {
+    sample as f64 / 32_768.0
+}
+
+fn energy_db(frame: &[i16]) -> f64 {
+    let power = frame.iter().map(|&s| to_unit(s).powi(2)).sum::<f64>() / frame.len().max(1) as f64;
+    10.0 * power.max(1e-10).log10()
+}
+
+/// Analysis frames loud enough to hold speech, as windows into `samples`
+fn speech_frames(samples: &[i16]) -> impl Iterator<Item = &[i16]> {
+    let frames = || samples.windows(FRAME_LEN).step_by(HOP_LEN);
+    let loudest = frames().map(energy_db).fold(f64::NEG_INFINITY, f64::max);
+    // Digital silence throughout
+    let threshold = if loudest <= -99.0 {
+        f64::INFINITY
+    } else {
+        loudest - SPEECH_RANGE_DB
+    };
+    frames().filter(move |frame| energy_db(frame) >= threshold)
+}
+
+fn hz_to_mel(hz: f64) -> f64 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    700.0 * (10f64.powf(mel / 2595.0) - 1.0)
+}
+
+/// Triangular filters spaced evenly on the mel scale up to Nyquist, as
+/// weights over the FFT bins
+fn mel_filterbank() -> Vec<Vec<f64>> {
+    let bins = FFT_LEN / 2 + 1;
+    let max_mel = hz_to_mel(ANALYSIS_RATE as f64 / 2.0);
+    let edges: Vec<f64> = (0..MEL_FILTERS + 2)
+        .map(|i| {
+            let hz = mel_to_hz(max_mel * i as f64 / (MEL_FILTERS + 1) as f64);
+            hz * FFT_LEN as f64 / ANALYSIS_RATE as f64
+        })
+        .collect();
+    (0..MEL_FILTERS)
+        .map(|m| {
+            let (left, center, right) = (edges[m], edges[m + 1], edges[m + 2]);
+            (0..bins)
+                .map(|bin| {
+                    let bin = bin as f64;
+                    if bin > left && bin <= center {
+                        (bin - left) / (center - left)
+                    } else if bin > center && bin < right {
+                        (right - bin) / (right - center)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn mfcc(frame: &[i16], filters: &[Vec<f64>]) -> Vec<f64> {
+    // Hamming window, zero-padded to the FFT size
+    let mut re = vec![0.0; FFT_LEN];
+    let mut im = vec![0.0; FFT_LEN];
+    let n = frame.len();
+    for (i, &s) in frame.iter().enumerate() {
+        re[i] = to_unit(s) * (0.54 - 0.46 * (2.0 * PI * i as f64 / (n - 1) as f64).cos());
+    }
+    fft(&mut re, &mut im);
+    let power: Vec<f64> = (0..=FFT_LEN / 2)
+        .map(|k| re[k] * re[k] + im[k] * im[k])
+        .collect();
+
+    let log_energies: Vec<f64> = filters
+        .iter()
+        .map(|filter| {
+            let energy: f64 = filter.iter().zip(&power).map(|(w, p)| w * p).sum();
+            energy.max(1e-10).ln()
+        })
+        .collect();
+    // DCT-II
+    (0..MFCC_COEFFS)
+        .map(|k| {
+            log_energies
+                .iter()
+                .enumerate()
+                .map(|(m, e)| e * (PI * k as f64 * (m as f64 + 0.5) / MEL_FILTERS as f64).cos())
+                .sum()
+        })
+        .collect()
+}
+
+/// In-place radix-2 FFT; the length must be a power of two
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_re, w_im) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Pitch of one frame by normalized autocorrelation, if it is voiced
+fn frame_pitch(frame: &[f64]) -> Option<f64> {
+    let min_lag = (PITCH_RATE as f64 / MAX_PITCH_HZ) as usize;
+    let max_lag = (PITCH_RATE as f64 / MIN_PITCH_HZ) as usize;
+    let window = frame.len().checked_sub(max_lag)?;
+    let correlation = |lag: usize| {
+        let (mut cross, mut a, mut b) = (0.0, 0.0, 0.0);
+        for i in 0..window {
+            cross += frame[i] * frame[i + lag];
+            a += frame[i] * frame[i];
+            b += frame[i + lag] * frame[i + lag];
+        }
+        if a <= 0.0 || b <= 0.0 {
+            0.0
+        } else {
+            cross / (a * b).sqrt()
+        }
+    };
+    let scores: Vec<f64> = (min_lag..=max_lag).map(correlation).collect();
+    let best = scores.iter().copied().fold(0.0, f64::max);
+    if best < VOICING_THRESHOLD {
+        return None;
+    }
+    // The first peak close to the best avoids halving the pitch
+    let is_peak = |i: usize| {
+        scores[i] >= best * 0.9
+            && (i == 0 || scores[i] >= scores[i - 1])
+            && scores.get(i + 1).is_none_or(|&next| scores[i] >= next)
+    };
+    let lag = min_lag + (0..scores.len()).find(|&i| is_peak(i))?;
+    Some(PITCH_RATE as f64 / lag as f64)
+}
+
+fn pitch_stats(samples: &[i16]) -> Option<PitchStats> {
+    let frame_len = PITCH_FRAME_LEN + (PITCH_RATE as f64 / MIN_PITCH_HZ) as usize;
+    let mut pitches: Vec<f64> = samples
+        .windows(frame_len)
+        .step_by(PITCH_HOP_LEN)
+        .filter_map(|w| {
+            let frame: Vec<f64> = w.iter().map(|&s| s as f64).collect();
+            frame_pitch(&frame)
+        })
+        .collect();
+    if pitches.is_empty() {
+        return None;
+    }
+    pitches.sort_by(|a, b| a.total_cmp(b));
+    let percentile = |p: f64| pitches[((pitches.len() - 1) as f64 * p).round() as usize];
+    let mean = pitches.iter().sum::<f64>() / pitches.len() as f64;
+    let variance = pitches.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / pitches.len() as f64;
+    Some(PitchStats {
+        median_hz: percentile(0.5),
+        mean_hz: mean,
+        std_hz: variance.sqrt(),
+        p10_hz: percentile(0.1),
+        p90_hz: percentile(0.9),
+    })
+}
+
+/// Euclidean distance between mean MFCCs, leaving out c0
+fn mfcc_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .skip(1)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// 0-100, where 100 means identical spectral envelopes
+fn similarity(distance: f64) -> f64 {
+    100.0 * (-distance / SCORE_SCALE).exp()
+}
+
+fn semitones(reference_hz: f64, candidate_hz: f64) -> f64 {
+    12.0 * (candidate_hz / reference_hz).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcm::sine_tone;
+
+    #[test]
+    fn test_fft_finds_tone_bin() {
+        let mut re: Vec<f64> = (0..64)
+            .map(|i| (2.0 * PI * 8.0 * i as f64 / 64.0).sin())
+            .collect();
+        let mut im = vec![0.0; 64];
+        fft(&mut re, &mut im);
+        let magnitudes: Vec<f64> = (0..32).map(|k| re[k].hypot(im[k])).collect();
+        let peak = (0..32)
+            .max_by(|&a, &b| magnitudes[a].total_cmp(&magnitudes[b]))
+            .unwrap();
+        assert_eq!(peak, 8);
+        assert!((magnitudes[8] - 32.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pitch_of_tone() {
+        let tone = sine_tone(200.0, 1.0, PITCH_RATE, 0.5);
+        let stats = pitch_stats(&tone).unwrap();
+        assert!((stats.median_hz - 200.0).abs() < 5.0, "{:?}", stats);
+        assert!(pitch_stats(&vec![0; 8_000]).is_none());
+        assert!((semitones(200.0, 400.0) - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mfcc_distance_separates_spectra() {
+        let profile = |freq: f64| {
+            let tone = sine_tone(freq, 0.5, ANALYSIS_RATE, 0.5);
+            let filters = mel_filterbank();
+            let frames: Vec<&[i16]> = speech_frames(&tone).collect();
+            let n = frames.len() as f64;
+            frames.iter().fold(vec![0.0; MFCC_COEFFS], |mut sum, f| {
+                for (s, c) in sum.iter_mut().zip(mfcc(f, &filters)) {
+                    *s += c / n;
+                }
+                sum
+            })
+        };
+        let (low, low_again, high) = (profile(220.0), profile(220.0), profile(2_000.0));
+        assert_eq!(mfcc_distance(&low, &low_again), 0.0);
+        assert_eq!(similarity(0.0), 100.0);
+        assert!(mfcc_distance(&low, &high) > 5.0);
+        assert_eq!(speech_frames(&[0; 4_000]).count(), 0);
+    }
+}
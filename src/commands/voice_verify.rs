@@ -0,0 +1,218 @@
+//! Captcha verification for cloned voices (`voice verify`)
+//!
+//! Clones that come back with `requires_verification` must be confirmed by
+//! the speaker: the API hands out a short captcha text, and a recording of
+//! it being read aloud is sent back. The captcha arrives either as JSON text
+//! or as an image of the text, which is saved to the working directory so it
+//! can be opened.
+
+use crate::client::{api_base, api_error, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success};
+use anyhow::{Context, Result};
+use colored::*;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use serde_json::{json, Value};
+
+/// Longest microphone recording accepted for a captcha
+const MAX_RECORD_SECONDS: f32 = 60.0;
+
+/// The captcha to read aloud
+#[derive(Debug, PartialEq)]
+enum Captcha {
+    Text(String),
+    Image { bytes: Vec<u8>, extension: String },
+}
+
+pub async fn verify_voice(
+    client: &Client,
+    api_key: &str,
+    voice_id: &str,
+    recording: Option<&str>,
+    duration: f32,
+    assume_yes: bool,
+) -> Result<()> {
+    if recording.is_none() && !(duration > 0.0 && duration <= MAX_RECORD_SECONDS) {
+        return Err(anyhow::anyhow!(
+            "--duration must be between 0 and {} seconds",
+            MAX_RECORD_SECONDS
+        ));
+    }
+
+    print_info(&format!(
+        "Fetching verification captcha for voice '{}'...",
+        voice_id.cyan()
+    ));
+    match fetch_captcha(client, api_key, voice_id).await? {
+        Captcha::Text(text) => {
+            println!("\n{}", "Read this aloud:".bold());
+            println!("  {}\n", text.yellow());
+        }
+        Captcha::Image { bytes, extension } => {
+            let path = format!("captcha_{}.{}", voice_id, extension);
+            std::fs::write(&path, &bytes)
+                .with_context(|| format!("Failed to save captcha to {}", path))?;
+            print_info(&format!(
+                "Captcha saved to {}; open it and read the text aloud",
+                path.cyan()
+            ));
+        }
+    }
+
+    let (audio, file_name) = match recording {
+        Some(path) => {
+            let audio = std::fs::read(path).with_context(|| format!("Failed to read {}", path))?;
+            let file_name = std::path::Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "recording.wav".to_string());
+            (audio, file_name)
+        }
+        None => {
+            if !assume_yes && !crate::prompt::confirm("Start recording?")? {
+                print_info("Cancelled");
+                return Ok(());
+            }
+            print_info(&format!("Recording for {} seconds...", duration));
+            let audio = tokio::task::spawn_blocking(move || record_wav(duration)).await??;
+            (audio, "recording.wav".to_string())
+        }
+    };
+    if audio.is_empty() {
+        return Err(anyhow::anyhow!("The recording is empty"));
+    }
+
+    print_info("Submitting verification...");
+    let form = Form::new().part("recording", Part::bytes(audio).file_name(file_name));
+    let response = client
+        .post(captcha_url(voice_id))
+        .header("xi-api-key", api_key)
+        .multipart(form)
+        .send_with_retry()
+        .await
+        .context("Failed to submit verification")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    if is_json_mode() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "voice_id": voice_id, "submitted": true }))?
+        );
+    } else {
+        print_success(&format!(
+            "Verification submitted for voice '{}'",
+            voice_id.cyan()
+        ));
+    }
+    Ok(())
+}
+
+fn captcha_url(voice_id: &str) -> String {
+    format!("{}/v1/voices/pvc/{}/captcha", api_base(), voice_id)
+}
+
+async fn fetch_captcha(client: &Client, api_key: &str, voice_id: &str) -> Result<Captcha> {
+    let response = client
+        .get(captcha_url(voice_id))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch captcha")?;
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let bytes = response.bytes().await.context("Failed to read captcha")?;
+    parse_captcha(&content_type, &bytes)
+}
+
+fn parse_captcha(content_type: &str, bytes: &[u8]) -> Result<Captcha> {
+    if let Some(subtype) = content_type.strip_prefix("image/") {
+        let extension = subtype
+            .split([';', '+'])
+            .next()
+            .unwrap_or("png")
+            .replace("jpeg", "jpg");
+        return Ok(Captcha::Image {
+            bytes: bytes.to_vec(),
+            extension,
+        });
+    }
+
+    let text = match serde_json::from_slice::<Value>(bytes) {
+        Ok(Value::String(text)) => text,
+        Ok(body) => ["text", "captcha"]
+            .iter()
+            .find_map(|key| body[key].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("Captcha response has no text"))?,
+        Err(_) => String::from_utf8_lossy(bytes).to_string(),
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(anyhow::anyhow!("Captcha response is empty"));
+    }
+    Ok(Captcha::Text(text.to_string()))
+}
+
+/// Record from the default microphone as WAV
+#[cfg(feature = "audio")]
+fn record_wav(duration: f32) -> Result<Vec<u8>> {
+    use crate::audio::audio_io;
+    use crate::pcm::wav_header;
+
+    let (sample_rate, channels) =
+        audio_io::default_input_format().map_err(|e| anyhow::anyhow!("{}", e))?;
+    let pcm = audio_io::record_from_microphone(duration)
+        .map_err(|e| anyhow::anyhow!("Failed to record audio: {}", e))?;
+    let mut wav = wav_header(sample_rate, channels, pcm.len() as u32).to_vec();
+    wav.extend_from_slice(&pcm);
+    Ok(wav)
+}
+
+#[cfg(not(feature = "audio"))]
+fn record_wav(_duration: f32) -> Result<Vec<u8>> {
+    Err(anyhow::anyhow!(
+        "Recording needs the audio feature. Rebuild with --features audio or pass --recording <FILE>"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_captcha_text() {
+        assert_eq!(
+            parse_captcha("application/json", br#"{"text": " The quick fox "}"#).unwrap(),
+            Captcha::Text("The quick fox".to_string())
+        );
+        assert_eq!(
+            parse_captcha("application/json", br#""Blue skies""#).unwrap(),
+            Captcha::Text("Blue skies".to_string())
+        );
+        assert_eq!(
+            parse_captcha("text/plain", b"Read me").unwrap(),
+            Captcha::Text("Read me".to_string())
+        );
+        assert!(parse_captcha("application/json", br#"{"other": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_captcha_image() {
+        assert_eq!(
+            parse_captcha("image/jpeg", &[1, 2]).unwrap(),
+            Captcha::Image {
+                bytes: vec![1, 2],
+                extension: "jpg".to_string()
+            }
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use crate::cli::{WebhookArgs, WebhookCommands};
-use crate::client::create_http_client;
-use crate::output::{print_info, print_success};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::output::{is_json_mode, print_info, print_success};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 pub async fn execute(args: WebhookArgs, api_key: &str) -> Result<()> {
@@ -22,7 +22,7 @@ pub async fn execute(args: WebhookArgs, api_key: &str) -> Result<()> {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WebhookInfo {
     id: String,
     name: String,
@@ -39,20 +39,24 @@ struct CreateWebhookResponse {
 async fn list_webhooks(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching webhooks...");
 
-    let url = "https://api.elevenlabs.io/v1/webhooks";
+    let url = format!("{}/v1/webhooks", api_base());
     let response = client
         .get(url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch webhooks")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let webhooks: Vec<WebhookInfo> = response.json().await.context("Failed to parse response")?;
+    let webhooks: Vec<WebhookInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&webhooks)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["ID", "Name", "URL", "Events"]);
@@ -132,20 +136,18 @@ async fn create_webhook(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/webhooks")
+        .post(format!("{}/v1/webhooks", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to create webhook")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
-    let result: CreateWebhookResponse =
-        response.json().await.context("Failed to parse response")?;
+    let result: CreateWebhookResponse = read_json(response).await?;
     print_success("Webhook created successfully!");
     print_info(&format!("Webhook ID: {}", result.id.yellow()));
 
@@ -155,16 +157,15 @@ async fn create_webhook(
 async fn delete_webhook(client: &Client, api_key: &str, webhook_id: &str) -> Result<()> {
     print_info(&format!("Deleting webhook '{}'...", webhook_id.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/webhooks/{}", webhook_id);
+    let url = format!("{}/v1/webhooks/{}", api_base(), webhook_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Webhook '{}' deleted successfully", webhook_id));
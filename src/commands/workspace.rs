@@ -1,14 +1,19 @@
-use crate::cli::{WorkspaceArgs, WorkspaceCommands};
-use crate::client::create_http_client;
-use crate::output::{print_error, print_info, print_success, print_warning};
+use crate::cli::{SecretCommands, WorkspaceArgs, WorkspaceCommands};
+use crate::client::{api_base, api_error, create_http_client, read_json, SendWithRetry};
+use crate::commands::agent_privacy::workspace_privacy;
+use crate::output::{is_json_mode, print_error, print_info, print_success, print_warning};
+use crate::utils::{confirm_overwrite, csv_escape, parse_duration_spec};
 use anyhow::{Context, Result};
 use colored::*;
 use comfy_table::Table;
 use reqwest::Client;
-use serde::Deserialize;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub async fn execute(args: WorkspaceArgs, api_key: &str) -> Result<()> {
+pub async fn execute(args: WorkspaceArgs, api_key: &str, assume_yes: bool) -> Result<()> {
     let client = create_http_client();
 
     match args.command {
@@ -30,6 +35,13 @@ pub async fn execute(args: WorkspaceArgs, api_key: &str) -> Result<()> {
             secret_type,
         } => add_secret(&client, api_key, &name, &value, &secret_type).await,
         WorkspaceCommands::DeleteSecret { name } => delete_secret(&client, api_key, &name).await,
+        WorkspaceCommands::Secret { command } => match command {
+            SecretCommands::Get { name } => get_secret(&client, api_key, &name).await,
+            SecretCommands::Update { name, value_file } => {
+                update_secret(&client, api_key, &name, value_file.as_deref()).await
+            }
+            SecretCommands::WhereUsed { name } => secret_where_used(&client, api_key, &name).await,
+        },
         WorkspaceCommands::Share {
             resource_type,
             resource_id,
@@ -48,10 +60,16 @@ pub async fn execute(args: WorkspaceArgs, api_key: &str) -> Result<()> {
             resource_type,
             resource_id,
         } => unshare_resource(&client, api_key, &resource_type, &resource_id).await,
+        WorkspaceCommands::Audit { since, output } => {
+            audit_workspace(&client, api_key, &since, output.as_deref(), assume_yes).await
+        }
+        WorkspaceCommands::Privacy { command } => {
+            workspace_privacy(&client, api_key, command, assume_yes).await
+        }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WorkspaceInfo {
     id: String,
     name: String,
@@ -63,7 +81,7 @@ struct WorkspaceInfo {
     created_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct InviteInfo {
     email: String,
     role: String,
@@ -78,19 +96,24 @@ async fn get_workspace_info(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching workspace information...");
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/workspace")
+        .get(format!("{}/v1/workspace", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch workspace info")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        print_error(&format!("API error: {}", error));
-        return Err(anyhow::anyhow!("API error: {}", error));
+        let error = api_error(response).await;
+        print_error(&error.to_string());
+        return Err(error);
     }
 
-    let workspace: WorkspaceInfo = response.json().await.context("Failed to parse response")?;
+    let workspace: WorkspaceInfo = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&workspace)?);
+        return Ok(());
+    }
 
     let mut table = Table::new();
     table.set_header(vec!["Property", "Value"]);
@@ -115,19 +138,24 @@ async fn list_invites(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching workspace invites...");
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/workspace/invites")
+        .get(format!("{}/v1/workspace/invites", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch invites")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        print_error(&format!("API error: {}", error));
-        return Err(anyhow::anyhow!("API error: {}", error));
+        let error = api_error(response).await;
+        print_error(&error.to_string());
+        return Err(error);
     }
 
-    let invites: Vec<InviteInfo> = response.json().await.context("Failed to parse response")?;
+    let invites: Vec<InviteInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&invites)?);
+        return Ok(());
+    }
 
     if invites.is_empty() {
         print_success("No pending invites");
@@ -173,16 +201,15 @@ async fn invite_member(client: &Client, api_key: &str, email: &str, role: &str)
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/workspace/invites")
+        .post(format!("{}/v1/workspace/invites", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to send invite")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Invitation sent to '{}'", email.green()));
@@ -192,17 +219,16 @@ async fn invite_member(client: &Client, api_key: &str, email: &str, role: &str)
 async fn revoke_invite(client: &Client, api_key: &str, email: &str) -> Result<()> {
     print_info(&format!("Revoking invitation for '{}'...", email.cyan()));
 
-    let url = format!("https://api.elevenlabs.io/v1/workspace/invites/{}", email);
+    let url = format!("{}/v1/workspace/invites/{}", api_base(), email);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to revoke invite")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Invitation revoked for '{}'", email.green()));
@@ -213,19 +239,19 @@ async fn list_members(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching workspace members...");
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/workspace/members")
+        .get(format!("{}/v1/workspace/members", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch members")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        print_error(&format!("API error: {}", error));
-        return Err(anyhow::anyhow!("API error: {}", error));
+        let error = api_error(response).await;
+        print_error(&error.to_string());
+        return Err(error);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct MemberInfo {
         user_id: String,
         email: String,
@@ -234,7 +260,12 @@ async fn list_members(client: &Client, api_key: &str) -> Result<()> {
         joined_at: Option<String>,
     }
 
-    let members: Vec<MemberInfo> = response.json().await.context("Failed to parse response")?;
+    let members: Vec<MemberInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&members)?);
+        return Ok(());
+    }
 
     if members.is_empty() {
         print_info("No members found");
@@ -264,27 +295,23 @@ async fn remove_member(client: &Client, api_key: &str, user_id: &str) -> Result<
         user_id
     ));
 
-    let confirm = dialoguer::Confirm::new()
-        .with_prompt("Are you sure?")
-        .default(false)
-        .interact()?;
+    let confirm = crate::prompt::confirm("Are you sure?")?;
 
     if !confirm {
         print_info("Cancelled");
         return Ok(());
     }
 
-    let url = format!("https://api.elevenlabs.io/v1/workspace/members/{}", user_id);
+    let url = format!("{}/v1/workspace/members/{}", api_base(), user_id);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to remove member")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -298,19 +325,19 @@ async fn list_api_keys(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching workspace API keys...");
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/workspace/api-keys")
+        .get(format!("{}/v1/workspace/api-keys", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch API keys")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        print_error(&format!("API error: {}", error));
-        return Err(anyhow::anyhow!("API error: {}", error));
+        let error = api_error(response).await;
+        print_error(&error.to_string());
+        return Err(error);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct ApiKeyInfo {
         key_id: String,
         #[serde(default)]
@@ -321,7 +348,12 @@ async fn list_api_keys(client: &Client, api_key: &str) -> Result<()> {
         last_used_at: Option<String>,
     }
 
-    let keys: Vec<ApiKeyInfo> = response.json().await.context("Failed to parse response")?;
+    let keys: Vec<ApiKeyInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&keys)?);
+        return Ok(());
+    }
 
     if keys.is_empty() {
         print_info("No API keys found");
@@ -349,19 +381,19 @@ async fn list_secrets(client: &Client, api_key: &str) -> Result<()> {
     print_info("Fetching workspace secrets...");
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/convai/workspaces/secrets")
+        .get(format!("{}/v1/convai/workspaces/secrets", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to fetch secrets")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        print_error(&format!("API error: {}", error));
-        return Err(anyhow::anyhow!("API error: {}", error));
+        let error = api_error(response).await;
+        print_error(&error.to_string());
+        return Err(error);
     }
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     struct SecretInfo {
         name: String,
         secret_type: String,
@@ -369,7 +401,12 @@ async fn list_secrets(client: &Client, api_key: &str) -> Result<()> {
         created_at: Option<String>,
     }
 
-    let secrets: Vec<SecretInfo> = response.json().await.context("Failed to parse response")?;
+    let secrets: Vec<SecretInfo> = read_json(response).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&secrets)?);
+        return Ok(());
+    }
 
     if secrets.is_empty() {
         print_info("No secrets found");
@@ -422,16 +459,15 @@ async fn add_secret(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/workspaces/secrets")
+        .post(format!("{}/v1/convai/workspaces/secrets", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to add secret")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Secret '{}' added successfully", name.green()));
@@ -439,38 +475,223 @@ async fn add_secret(
 }
 
 async fn delete_secret(client: &Client, api_key: &str, name: &str) -> Result<()> {
+    // Warn about dangling references before deleting
+    if let Ok(secret) = find_secret(client, api_key, name).await {
+        let references = secret_references(&secret);
+        if !references.is_empty() {
+            print_warning(&format!(
+                "Secret '{}' is still referenced by {} resource(s):",
+                name,
+                references.len()
+            ));
+            for (kind, id, ref_name) in &references {
+                println!("  {} {} {}", kind.cyan(), id.yellow(), ref_name);
+            }
+        }
+    }
+
     print_warning(&format!("You are about to delete secret '{}'", name));
 
-    let confirm = dialoguer::Confirm::new()
-        .with_prompt("Are you sure?")
-        .default(false)
-        .interact()?;
+    let confirm = crate::prompt::confirm("Are you sure?")?;
 
     if !confirm {
         print_info("Cancelled");
         return Ok(());
     }
 
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/workspaces/secrets/{}",
-        name
-    );
+    let url = format!("{}/v1/convai/workspaces/secrets/{}", api_base(), name);
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to delete secret")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!("Secret '{}' deleted successfully", name.green()));
     Ok(())
 }
 
+/// Look up a secret's metadata by name
+async fn find_secret(client: &Client, api_key: &str, name: &str) -> Result<Value> {
+    let response = client
+        .get(format!("{}/v1/convai/workspaces/secrets", api_base()))
+        .header("xi-api-key", api_key)
+        .send_with_retry()
+        .await
+        .context("Failed to fetch secrets")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let value: Value = read_json(response).await?;
+
+    audit_items(&value, "secrets")
+        .into_iter()
+        .find(|s| s.get("name").and_then(|n| n.as_str()) == Some(name))
+        .ok_or_else(|| anyhow::anyhow!("Secret '{}' not found", name))
+}
+
+/// Collect `(kind, id, name)` for every resource listed in a secret's `used_by` field
+fn secret_references(secret: &Value) -> Vec<(String, String, String)> {
+    let mut references = Vec::new();
+
+    if let Some(used_by) = secret.get("used_by").and_then(|u| u.as_object()) {
+        for (kind, items) in used_by {
+            for item in items.as_array().into_iter().flatten() {
+                let (id, name) = match item {
+                    Value::String(id) => (id.clone(), String::new()),
+                    _ => (
+                        audit_field(item, &["agent_id", "tool_id", "phone_number_id", "id"]),
+                        audit_field(item, &["name", "phone_number"]),
+                    ),
+                };
+                references.push((kind.trim_end_matches('s').to_string(), id, name));
+            }
+        }
+    }
+
+    references
+}
+
+async fn get_secret(client: &Client, api_key: &str, name: &str) -> Result<()> {
+    print_info(&format!("Fetching secret '{}'...", name.cyan()));
+
+    let secret = find_secret(client, api_key, name).await?;
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&secret)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Property", "Value"]);
+    table.add_row(vec!["Name", &name.cyan()]);
+
+    let secret_id = audit_field(&secret, &["secret_id"]);
+    if !secret_id.is_empty() {
+        table.add_row(vec!["ID".to_string(), secret_id.yellow().to_string()]);
+    }
+    let secret_type = audit_field(&secret, &["secret_type", "type"]);
+    if !secret_type.is_empty() {
+        table.add_row(vec!["Type".to_string(), secret_type]);
+    }
+    let created = audit_field(&secret, &["created_at"]);
+    if !created.is_empty() {
+        table.add_row(vec!["Created".to_string(), created]);
+    }
+    table.add_row(vec![
+        "References".to_string(),
+        secret_references(&secret).len().to_string(),
+    ]);
+
+    println!("{}", table);
+    Ok(())
+}
+
+async fn update_secret(
+    client: &Client,
+    api_key: &str,
+    name: &str,
+    value_file: Option<&str>,
+) -> Result<()> {
+    let value = match value_file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read value file: {}", path))?,
+        None => {
+            use std::io::{IsTerminal, Read};
+            if std::io::stdin().is_terminal() {
+                crate::prompt::ensure_input_allowed("secret value; use --value-file")?;
+                print_info("Enter the new secret value, then press Ctrl-D:");
+            }
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .context("Failed to read secret value from stdin")?;
+            buf
+        }
+    };
+    let value = value.trim_end_matches(['\n', '\r']);
+
+    if value.is_empty() {
+        return Err(anyhow::anyhow!("Secret value cannot be empty"));
+    }
+
+    let secret = find_secret(client, api_key, name).await?;
+    let secret_id = audit_field(&secret, &["secret_id"]);
+    let id = if secret_id.is_empty() {
+        name.to_string()
+    } else {
+        secret_id
+    };
+
+    print_info(&format!("Updating secret '{}'...", name.cyan()));
+
+    let body = json!({
+        "name": name,
+        "value": value
+    });
+
+    let url = format!("{}/v1/convai/workspaces/secrets/{}", api_base(), id);
+    let response = client
+        .patch(&url)
+        .header("xi-api-key", api_key)
+        .json(&body)
+        .send_with_retry()
+        .await
+        .context("Failed to update secret")?;
+
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    print_success(&format!("Secret '{}' updated successfully", name.green()));
+    Ok(())
+}
+
+async fn secret_where_used(client: &Client, api_key: &str, name: &str) -> Result<()> {
+    print_info(&format!(
+        "Finding references to secret '{}'...",
+        name.cyan()
+    ));
+
+    let secret = find_secret(client, api_key, name).await?;
+    let references = secret_references(&secret);
+
+    if is_json_mode() {
+        let references: Vec<Value> = references
+            .iter()
+            .map(|(kind, id, name)| json!({ "type": kind, "id": id, "name": name }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&references)?);
+        return Ok(());
+    }
+
+    if references.is_empty() {
+        print_success(&format!("Secret '{}' is not referenced", name));
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Type", "ID", "Name"]);
+
+    for (kind, id, ref_name) in &references {
+        table.add_row(vec![kind.cyan(), id.yellow(), ref_name.as_str().into()]);
+    }
+
+    println!("{}", table);
+    print_warning(&format!(
+        "Secret '{}' is referenced by {} resource(s)",
+        name,
+        references.len()
+    ));
+    Ok(())
+}
+
 async fn share_resource(
     client: &Client,
     api_key: &str,
@@ -519,16 +740,15 @@ async fn share_resource(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/workspaces/shares")
+        .post(format!("{}/v1/convai/workspaces/shares", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to share resource")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -550,10 +770,7 @@ async fn unshare_resource(
         resource_type, resource_id
     ));
 
-    let confirm = dialoguer::Confirm::new()
-        .with_prompt("Are you sure?")
-        .default(false)
-        .interact()?;
+    let confirm = crate::prompt::confirm("Are you sure?")?;
 
     if !confirm {
         print_info("Cancelled");
@@ -561,19 +778,20 @@ async fn unshare_resource(
     }
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/workspaces/shares/{}/{}",
-        resource_type, resource_id
+        "{}/v1/convai/workspaces/shares/{}/{}",
+        api_base(),
+        resource_type,
+        resource_id
     );
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await
         .context("Failed to unshare resource")?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error));
+        return Err(api_error(response).await);
     }
 
     print_success(&format!(
@@ -583,3 +801,310 @@ async fn unshare_resource(
     ));
     Ok(())
 }
+
+/// A single row of the workspace audit report
+#[derive(Debug, Serialize)]
+struct AuditRow {
+    section: String,
+    id: String,
+    name: String,
+    detail: String,
+    created: String,
+    last_used: String,
+}
+
+/// Fetch a JSON document for the audit, returning `None` (with a warning) on failure
+async fn fetch_audit_section(
+    client: &Client,
+    api_key: &str,
+    section: &str,
+    url: &str,
+    query: &[(&str, String)],
+) -> Option<Value> {
+    let result = async {
+        let response = client
+            .get(url)
+            .header("xi-api-key", api_key)
+            .query(query)
+            .send_with_retry()
+            .await
+            .with_context(|| format!("Failed to fetch {}", section))?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        read_json::<Value>(response).await
+    }
+    .await;
+
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            print_warning(&format!("Skipping {} in audit: {}", section, e));
+            None
+        }
+    }
+}
+
+/// Extract the list of items from a response that is either an array or wraps one under `key`
+fn audit_items(value: &Value, key: &str) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items.clone(),
+        Value::Object(map) => map
+            .get(key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Read the first present field from a JSON object as a display string
+fn audit_field(item: &Value, keys: &[&str]) -> String {
+    keys.iter()
+        .find_map(|k| match item.get(*k) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Null) | None => None,
+            Some(other) => Some(other.to_string()),
+        })
+        .unwrap_or_default()
+}
+
+async fn audit_workspace(
+    client: &Client,
+    api_key: &str,
+    since: &str,
+    output: Option<&str>,
+    assume_yes: bool,
+) -> Result<()> {
+    let window = parse_duration_spec(since)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let start = now.saturating_sub(window);
+
+    print_info(&format!(
+        "Building workspace audit (usage since {})...",
+        since.cyan()
+    ));
+
+    let mut rows: Vec<AuditRow> = Vec::new();
+
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "members",
+        &format!("{}/v1/workspace/members", api_base()),
+        &[],
+    )
+    .await
+    {
+        for item in audit_items(&value, "members") {
+            rows.push(AuditRow {
+                section: "member".to_string(),
+                id: audit_field(&item, &["user_id"]),
+                name: audit_field(&item, &["email"]),
+                detail: audit_field(&item, &["role"]),
+                created: audit_field(&item, &["joined_at"]),
+                last_used: String::new(),
+            });
+        }
+    }
+
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "API keys",
+        &format!("{}/v1/workspace/api-keys", api_base()),
+        &[],
+    )
+    .await
+    {
+        for item in audit_items(&value, "api_keys") {
+            rows.push(AuditRow {
+                section: "api_key".to_string(),
+                id: audit_field(&item, &["key_id"]),
+                name: audit_field(&item, &["name"]),
+                detail: String::new(),
+                created: audit_field(&item, &["created_at"]),
+                last_used: audit_field(&item, &["last_used_at"]),
+            });
+        }
+    }
+
+    // Secret values are never returned by the API; only metadata is exported
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "secrets",
+        &format!("{}/v1/convai/workspaces/secrets", api_base()),
+        &[],
+    )
+    .await
+    {
+        for item in audit_items(&value, "secrets") {
+            rows.push(AuditRow {
+                section: "secret".to_string(),
+                id: audit_field(&item, &["secret_id", "name"]),
+                name: audit_field(&item, &["name"]),
+                detail: audit_field(&item, &["secret_type", "type"]),
+                created: audit_field(&item, &["created_at"]),
+                last_used: String::new(),
+            });
+        }
+    }
+
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "agents",
+        &format!("{}/v1/agents", api_base()),
+        &[],
+    )
+    .await
+    {
+        for item in audit_items(&value, "agents") {
+            rows.push(AuditRow {
+                section: "agent".to_string(),
+                id: audit_field(&item, &["agent_id"]),
+                name: audit_field(&item, &["name"]),
+                detail: String::new(),
+                created: audit_field(&item, &["created_at", "created_at_unix_secs"]),
+                last_used: String::new(),
+            });
+        }
+    }
+
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "webhooks",
+        &format!("{}/v1/webhooks", api_base()),
+        &[],
+    )
+    .await
+    {
+        for item in audit_items(&value, "webhooks") {
+            let events = item
+                .get("events")
+                .and_then(|e| e.as_array())
+                .map(|e| {
+                    e.iter()
+                        .filter_map(|v| v.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            rows.push(AuditRow {
+                section: "webhook".to_string(),
+                id: audit_field(&item, &["id", "webhook_id"]),
+                name: audit_field(&item, &["name"]),
+                detail: format!("{} {}", audit_field(&item, &["url"]), events)
+                    .trim()
+                    .to_string(),
+                created: audit_field(&item, &["created_at"]),
+                last_used: String::new(),
+            });
+        }
+    }
+
+    if let Some(value) = fetch_audit_section(
+        client,
+        api_key,
+        "usage",
+        &format!("{}/v1/usage/character-stats", api_base()),
+        &[
+            ("start_unix", start.as_millis().to_string()),
+            ("end_unix", now.as_millis().to_string()),
+            ("include_workspace_metrics", "true".to_string()),
+        ],
+    )
+    .await
+    {
+        let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+        if let Some(usage) = value.get("usage").and_then(|u| u.as_object()) {
+            for (usage_type, values) in usage {
+                let total: u64 = values
+                    .as_array()
+                    .map(|v| v.iter().filter_map(|n| n.as_f64()).sum::<f64>() as u64)
+                    .unwrap_or(0);
+                totals.insert(usage_type.clone(), total);
+            }
+        }
+        for (usage_type, total) in totals {
+            rows.push(AuditRow {
+                section: "usage".to_string(),
+                id: usage_type,
+                name: format!("last {}", since),
+                detail: format!("{} characters", total),
+                created: String::new(),
+                last_used: String::new(),
+            });
+        }
+    }
+
+    match output {
+        Some(path_str) => {
+            let path = Path::new(path_str);
+            if !confirm_overwrite(path, assume_yes)? {
+                print_info("Cancelled");
+                return Ok(());
+            }
+
+            let is_json = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case("json"))
+                .unwrap_or(false);
+
+            let contents = if is_json {
+                serde_json::to_string_pretty(&rows)?
+            } else {
+                let mut csv = String::from("section,id,name,detail,created,last_used\n");
+                for row in &rows {
+                    let fields = [
+                        &row.section,
+                        &row.id,
+                        &row.name,
+                        &row.detail,
+                        &row.created,
+                        &row.last_used,
+                    ];
+                    let line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+                    csv.push_str(&line.join(","));
+                    csv.push('\n');
+                }
+                csv
+            };
+
+            std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write audit report to {}", path_str))?;
+            print_success(&format!(
+                "Wrote {} audit row(s) -> {}",
+                rows.len(),
+                path_str.green()
+            ));
+        }
+        None if is_json_mode() => {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        }
+        None => {
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for row in &rows {
+                *counts.entry(row.section.as_str()).or_default() += 1;
+            }
+
+            let mut table = Table::new();
+            table.set_header(vec!["Section", "Entries"]);
+            for (section, count) in &counts {
+                table.add_row(vec![section.cyan(), count.to_string().yellow()]);
+            }
+
+            println!("{}", table);
+            print_info("Use -o audit.csv or -o audit.json to export the full report");
+        }
+    }
+
+    Ok(())
+}
@@ -1,16 +1,139 @@
+use crate::credentials;
+use crate::utils::{with_file_lock, write_atomic};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct Config {
     pub api_key: Option<String>,
+    /// The API key is in the OS keyring rather than in `api_key`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub api_key_in_keyring: bool,
     pub default_voice: Option<String>,
     pub default_model: Option<String>,
     pub default_output_format: Option<String>,
+    /// Colored output: auto, always, never
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Use ASCII instead of unicode status symbols
+    #[serde(default)]
+    pub ascii_symbols: Option<bool>,
+    /// Directory for generated files when no output path is given
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// Comma-separated subdirectory layout (by-date, by-voice, by-project)
+    #[serde(default)]
+    pub organize: Option<String>,
+    /// External player used by --play when built-in playback is unavailable (e.g. "mpv -")
+    #[serde(default)]
+    pub player_cmd: Option<String>,
+    /// Comma-separated input cleaning rules for tts (markdown, html, urls, emojis)
+    #[serde(default)]
+    pub clean_input: Option<String>,
+    /// Profile used when neither --profile nor ELEVENLABS_PROFILE is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// Profile applied to this run (not stored)
+    #[serde(skip)]
+    pub profile: Option<String>,
+    /// Why a key stored in the keyring could not be read (not stored)
+    #[serde(skip)]
+    pub keyring_error: Option<String>,
+    /// Music track IDs marked as local favorites
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub music_favorites: Vec<String>,
     #[serde(default)]
     pub mcp: McpConfig,
+    /// Local tags per music track ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub music_tags: BTreeMap<String, Vec<String>>,
+    /// Settings applied when generating with a voice, by voice name or ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub voice_defaults: BTreeMap<String, VoiceDefaults>,
+    /// Named API keys and defaults (`[profiles.work]`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of credentials and defaults; values it sets replace the
+/// top-level ones while it is in use
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub api_key_in_keyring: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_voice: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_output_format: Option<String>,
+}
+
+/// Voice settings stored with `voice defaults set`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+pub struct VoiceDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity_boost: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_boost: Option<bool>,
+}
+
+impl VoiceDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// These settings with any set in `overrides` replacing them
+    pub fn overridden_by(self, overrides: VoiceDefaults) -> Self {
+        Self {
+            stability: overrides.stability.or(self.stability),
+            similarity_boost: overrides.similarity_boost.or(self.similarity_boost),
+            style: overrides.style.or(self.style),
+            speaker_boost: overrides.speaker_boost.or(self.speaker_boost),
+        }
+    }
+}
+
+impl Profile {
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.is_some() || self.api_key_in_keyring
+    }
+}
+
+/// Where `set_api_key` put the key
+#[derive(Debug, PartialEq)]
+pub enum KeyStorage {
+    Keyring,
+    /// In the config file, with the reason if the keyring was tried and failed
+    ConfigFile {
+        keyring_error: Option<String>,
+    },
+}
+
+impl KeyStorage {
+    /// Try the keyring unless `plaintext` is set or the build lacks it
+    fn store(account: &str, secret: &str, plaintext: bool) -> Self {
+        if plaintext || !credentials::AVAILABLE {
+            return Self::ConfigFile {
+                keyring_error: None,
+            };
+        }
+        match credentials::store(account, secret) {
+            Ok(()) => Self::Keyring,
+            Err(e) => Self::ConfigFile {
+                keyring_error: Some(e.to_string()),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
@@ -33,11 +156,33 @@ pub struct McpConfig {
 }
 
 impl Config {
+    /// The config file, with an API key kept in the keyring filled in
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+        let mut config = Self::load_from(&Self::config_path()?)?;
+        if config.api_key_in_keyring {
+            config.api_key = config.read_keyring(credentials::DEFAULT_ACCOUNT);
+        }
+        Ok(config)
+    }
+
+    /// A keyring entry; read failures are kept for the missing key error
+    fn read_keyring(&mut self, account: &str) -> Option<String> {
+        match credentials::load(account) {
+            Ok(Some(secret)) => Some(secret),
+            Ok(None) => {
+                self.keyring_error = Some("the keyring entry is missing".to_string());
+                None
+            }
+            Err(e) => {
+                self.keyring_error = Some(e.to_string());
+                None
+            }
+        }
+    }
 
+    fn load_from(config_path: &Path) -> Result<Self> {
         if config_path.exists() {
-            let contents = fs::read_to_string(&config_path)?;
+            let contents = fs::read_to_string(config_path)?;
             let config: Config = toml::from_str(&contents)?;
             Ok(config)
         } else {
@@ -45,49 +190,183 @@ impl Config {
         }
     }
 
-    pub fn save(&self) -> Result<()> {
+    /// Re-read the config under the lock, apply `change` and write it back,
+    /// so concurrent CLI processes don't drop each other's changes
+    pub fn update<T>(change: impl FnOnce(&mut Config) -> Result<T>) -> Result<T> {
         let config_path = Self::config_path()?;
+        with_file_lock(&config_path, || {
+            let mut config = Self::load_from(&config_path)?;
+            let result = change(&mut config)?;
+            config.write_to(&config_path)?;
+            Ok(result)
+        })
+    }
 
-        // Ensure config directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
+    fn write_to(&self, config_path: &Path) -> Result<()> {
         let contents = toml::to_string_pretty(self)?;
-        fs::write(&config_path, contents)?;
-        Ok(())
+        write_atomic(config_path, contents.as_bytes())
     }
 
     pub fn config_path() -> Result<PathBuf> {
-        let proj_dirs = directories::ProjectDirs::from("com", "elevenlabs", "cli")
-            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
-        Ok(proj_dirs.config_dir().join("config.toml"))
+        crate::paths::config_file()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))
     }
 
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        if key == "api_key" {
+            return self.set_api_key(value, false).map(|_| ());
+        }
+        self.assign(key, value)?;
+        Self::update(|config| config.assign(key, value))
+    }
+
+    /// Store the API key in the OS keyring, or in the config file when
+    /// `plaintext` is set or the keyring is unavailable
+    pub fn set_api_key(&mut self, key: &str, plaintext: bool) -> Result<KeyStorage> {
+        let storage = KeyStorage::store(credentials::DEFAULT_ACCOUNT, key, plaintext);
+        let in_keyring = storage == KeyStorage::Keyring;
+        Self::update(|config| {
+            if config.api_key_in_keyring && !in_keyring {
+                // Don't leave a stale copy behind
+                let _ = credentials::delete(credentials::DEFAULT_ACCOUNT);
+            }
+            config.api_key = (!in_keyring).then(|| key.to_string());
+            config.api_key_in_keyring = in_keyring;
+            Ok(())
+        })?;
+        self.api_key = Some(key.to_string());
+        self.api_key_in_keyring = in_keyring;
+        Ok(storage)
+    }
+
+    /// Store a profile's API key like [`Config::set_api_key`]; the caller
+    /// writes the profile
+    pub fn store_profile_key(
+        profile: &mut Profile,
+        name: &str,
+        key: &str,
+        plaintext: bool,
+    ) -> KeyStorage {
+        let account = credentials::profile_account(name);
+        let storage = KeyStorage::store(&account, key, plaintext);
+        let in_keyring = storage == KeyStorage::Keyring;
+        if profile.api_key_in_keyring && !in_keyring {
+            let _ = credentials::delete(&account);
+        }
+        profile.api_key = (!in_keyring).then(|| key.to_string());
+        profile.api_key_in_keyring = in_keyring;
+        storage
+    }
+
+    fn assign(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
             "api_key" => self.api_key = Some(value.to_string()),
             "default_voice" => self.default_voice = Some(value.to_string()),
             "default_model" => self.default_model = Some(value.to_string()),
             "default_output_format" => self.default_output_format = Some(value.to_string()),
+            "color" => {
+                value.parse::<crate::cli::ColorChoice>()?;
+                self.color = Some(value.to_lowercase());
+            }
+            "ascii_symbols" => {
+                let enabled = value.parse::<bool>().map_err(|_| {
+                    anyhow::anyhow!(
+                        "Invalid value '{}' for ascii_symbols (use true/false)",
+                        value
+                    )
+                })?;
+                self.ascii_symbols = Some(enabled);
+            }
+            "output_dir" => self.output_dir = Some(value.to_string()),
+            "organize" => {
+                crate::cli::parse_organize(value)?;
+                self.organize = Some(value.to_lowercase());
+            }
+            "player_cmd" => self.player_cmd = Some(value.to_string()),
+            "clean_input" => {
+                crate::cli::parse_clean_rules(value)?;
+                self.clean_input = Some(value.to_lowercase());
+            }
             _ => return Err(anyhow::anyhow!("Unknown config key: {}", key)),
         }
-        self.save()?;
         Ok(())
     }
 
     pub fn unset(&mut self, key: &str) -> Result<()> {
+        self.clear(key)?;
+        Self::update(|config| {
+            if key == "api_key" && config.api_key_in_keyring {
+                credentials::delete(credentials::DEFAULT_ACCOUNT)?;
+            }
+            config.clear(key)
+        })
+    }
+
+    fn clear(&mut self, key: &str) -> Result<()> {
         match key {
-            "api_key" => self.api_key = None,
+            "api_key" => {
+                self.api_key = None;
+                self.api_key_in_keyring = false;
+            }
             "default_voice" => self.default_voice = None,
             "default_model" => self.default_model = None,
             "default_output_format" => self.default_output_format = None,
+            "color" => self.color = None,
+            "ascii_symbols" => self.ascii_symbols = None,
+            "output_dir" => self.output_dir = None,
+            "organize" => self.organize = None,
+            "player_cmd" => self.player_cmd = None,
+            "clean_input" => self.clean_input = None,
             _ => return Err(anyhow::anyhow!("Unknown config key: {}", key)),
         }
-        self.save()?;
         Ok(())
     }
 
+    /// Apply the profile named by `--profile`/`ELEVENLABS_PROFILE`, or else
+    /// the active one. Only this run sees the result: `set` and `unset`
+    /// re-read the file
+    pub fn apply_profile(&mut self, requested: Option<&str>) -> Result<()> {
+        let Some(name) = requested.or(self.active_profile.as_deref()) else {
+            return Ok(());
+        };
+        let profile = self.profiles.get(name).cloned().ok_or_else(|| {
+            anyhow::anyhow!("Unknown profile '{}' (see `config profile list`)", name)
+        })?;
+        self.profile = Some(name.to_string());
+        if profile.api_key.is_some() {
+            self.api_key = profile.api_key;
+        } else if profile.api_key_in_keyring {
+            self.api_key = self.read_keyring(&credentials::profile_account(name));
+        }
+        if profile.default_voice.is_some() {
+            self.default_voice = profile.default_voice;
+        }
+        if profile.default_model.is_some() {
+            self.default_model = profile.default_model;
+        }
+        if profile.default_output_format.is_some() {
+            self.default_output_format = profile.default_output_format;
+        }
+        Ok(())
+    }
+
+    /// Key of the stored defaults for `voice` (names match case-insensitively)
+    pub fn voice_defaults_key(&self, voice: &str) -> Option<&str> {
+        self.voice_defaults
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(voice))
+            .map(String::as_str)
+    }
+
+    /// Stored defaults for the first of `voices` that has any
+    pub fn voice_defaults_for(&self, voices: &[&str]) -> VoiceDefaults {
+        voices
+            .iter()
+            .find_map(|voice| self.voice_defaults_key(voice))
+            .map(|key| self.voice_defaults[key])
+            .unwrap_or_default()
+    }
+
     /// Load config from a TOML string (useful for testing)
     #[cfg(test)]
     pub fn from_str(s: &str) -> Result<Self> {
@@ -106,6 +385,128 @@ mod tests {
         assert!(config.default_voice.is_none());
         assert!(config.default_model.is_none());
         assert!(config.default_output_format.is_none());
+        assert!(config.color.is_none());
+        assert!(config.ascii_symbols.is_none());
+    }
+
+    #[test]
+    fn test_config_display_settings_from_toml() {
+        let toml = r#"
+            color = "never"
+            ascii_symbols = true
+        "#;
+
+        let config = Config::from_str(toml).unwrap();
+        assert_eq!(config.color, Some("never".to_string()));
+        assert_eq!(config.ascii_symbols, Some(true));
+    }
+
+    #[test]
+    fn test_config_music_library_roundtrip() {
+        let mut config = Config::default();
+        config.music_favorites.push("m1".to_string());
+        config
+            .music_tags
+            .insert("m1".to_string(), vec!["chill".to_string()]);
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let parsed = Config::from_str(&toml).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_voice_defaults_lookup_and_override() {
+        let toml = r#"
+            [voice_defaults.Rachel]
+            stability = 0.6
+            style = 0.3
+        "#;
+
+        let config = Config::from_str(toml).unwrap();
+        let rachel = config.voice_defaults_for(&["rachel"]);
+        assert_eq!(rachel.stability, Some(0.6));
+        assert!(config.voice_defaults_for(&["Brian"]).is_empty());
+        assert_eq!(
+            config.voice_defaults_for(&["Brian", "RACHEL"]).style,
+            Some(0.3)
+        );
+
+        let merged = rachel.overridden_by(VoiceDefaults {
+            stability: Some(0.2),
+            speaker_boost: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(merged.stability, Some(0.2));
+        assert_eq!(merged.style, Some(0.3));
+        assert_eq!(merged.speaker_boost, Some(true));
+    }
+
+    #[test]
+    fn test_profiles_override_top_level_values() {
+        let toml = r#"
+            api_key = "personal-key"
+            default_voice = "Brian"
+            active_profile = "work"
+
+            [profiles.work]
+            api_key = "work-key"
+
+            [profiles.demo]
+            default_voice = "Rachel"
+        "#;
+
+        let mut config = Config::from_str(toml).unwrap();
+        config.apply_profile(None).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("work"));
+        assert_eq!(config.api_key.as_deref(), Some("work-key"));
+        assert_eq!(config.default_voice.as_deref(), Some("Brian"));
+
+        let mut config = Config::from_str(toml).unwrap();
+        config.apply_profile(Some("demo")).unwrap();
+        assert_eq!(config.api_key.as_deref(), Some("personal-key"));
+        assert_eq!(config.default_voice.as_deref(), Some("Rachel"));
+
+        assert!(Config::from_str(toml)
+            .unwrap()
+            .apply_profile(Some("missing"))
+            .is_err());
+
+        // The run-time profile is never written back
+        let written = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(Config::from_str(&written).unwrap().profile, None);
+    }
+
+    #[test]
+    fn test_keyring_flags_roundtrip() {
+        let toml = r#"
+            api_key_in_keyring = true
+
+            [profiles.work]
+            api_key_in_keyring = true
+
+            [profiles.demo]
+            default_voice = "Rachel"
+        "#;
+
+        let config = Config::from_str(toml).unwrap();
+        assert!(config.api_key.is_none());
+        assert!(config.profiles["work"].has_api_key());
+        assert!(!config.profiles["demo"].has_api_key());
+
+        let written = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(written.matches("api_key_in_keyring = true").count(), 2);
+        assert!(!written.contains("api_key_in_keyring = false"));
+
+        let mut profile = Profile::default();
+        let storage = Config::store_profile_key(&mut profile, "demo", "sk_demo", true);
+        assert_eq!(
+            storage,
+            KeyStorage::ConfigFile {
+                keyring_error: None
+            }
+        );
+        assert_eq!(profile.api_key.as_deref(), Some("sk_demo"));
+        assert!(!profile.api_key_in_keyring);
     }
 
     #[test]
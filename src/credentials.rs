@@ -0,0 +1,80 @@
+//! API keys in the OS keyring (`--features keyring`)
+//!
+//! With the feature, `config set-key`, `config set api_key` and `config
+//! profile add --key` put the key in macOS Keychain, Windows Credential
+//! Manager or the Secret Service, and the config file only records that it
+//! lives there. The config file is the fallback: builds without the feature,
+//! `--plaintext`, and systems where the keyring can't be reached keep the key
+//! in plain text as before.
+
+use anyhow::Result;
+
+/// Keyring service the entries are stored under
+#[cfg(feature = "keyring")]
+const SERVICE: &str = "elevenlabs-cli";
+
+/// Keyring account of the top-level API key
+pub const DEFAULT_ACCOUNT: &str = "api_key";
+
+/// Whether this build can use the keyring
+pub const AVAILABLE: bool = cfg!(feature = "keyring");
+
+/// Keyring account of a profile's API key
+pub fn profile_account(profile: &str) -> String {
+    format!("profile:{}", profile)
+}
+
+#[cfg(feature = "keyring")]
+fn entry(account: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, account)
+        .map_err(|e| anyhow::anyhow!("Could not open the OS keyring: {}", e))
+}
+
+#[cfg(feature = "keyring")]
+pub fn store(account: &str, secret: &str) -> Result<()> {
+    entry(account)?
+        .set_password(secret)
+        .map_err(|e| anyhow::anyhow!("Could not write to the OS keyring: {}", e))
+}
+
+#[cfg(feature = "keyring")]
+pub fn load(account: &str) -> Result<Option<String>> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow::anyhow!("Could not read the OS keyring: {}", e)),
+    }
+}
+
+/// Remove the entry; `false` if there was none
+#[cfg(feature = "keyring")]
+pub fn delete(account: &str) -> Result<bool> {
+    match entry(account)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(anyhow::anyhow!(
+            "Could not delete from the OS keyring: {}",
+            e
+        )),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn unavailable() -> anyhow::Error {
+    anyhow::anyhow!("This build has no keyring support (rebuild with --features keyring)")
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn store(_account: &str, _secret: &str) -> Result<()> {
+    Err(unavailable())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn load(_account: &str) -> Result<Option<String>> {
+    Err(unavailable())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn delete(_account: &str) -> Result<bool> {
+    Ok(false)
+}
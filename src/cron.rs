@@ -0,0 +1,206 @@
+//! Five-field cron expressions for `schedule`
+//!
+//! `minute hour day-of-month month day-of-week`, each a `*`, a number, a
+//! range (`1-5`), a step (`*/15`, `10-50/10`) or a comma list of those.
+//! Day-of-week is 0-7 with both 0 and 7 meaning Sunday, and as in cron a job
+//! runs when either day field matches if both are restricted. `@hourly`,
+//! `@daily`, `@weekly`, `@monthly` and `@yearly` are accepted too. Times are
+//! UTC: the CLI has no time zone database.
+
+use crate::utils::civil_date;
+use anyhow::Result;
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+/// Furthest a next run is searched for (covers Feb 29 on a leap year)
+const MAX_SEARCH_MINUTES: u64 = 5 * 366 * 24 * 60;
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expanded = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(anyhow::anyhow!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                expr
+            ));
+        };
+        let field = |text: &str, name: &str, min: u32, max: u32| {
+            parse_field(text, min, max).map_err(|e| {
+                anyhow::anyhow!("Invalid {} field '{}' in '{}': {}", name, text, expr, e)
+            })
+        };
+
+        let mut weekdays = field(weekday, "weekday", 0, 7)?;
+        // 7 is Sunday too
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: field(minute, "minute", 0, 59)?,
+            hours: field(hour, "hour", 0, 23)?,
+            days: field(day, "day", 1, 31)?,
+            months: field(month, "month", 1, 12)?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    /// Whether the schedule fires in the minute containing `unix_secs`
+    pub fn matches(&self, unix_secs: u64) -> bool {
+        let (_, month, day) = civil_date(unix_secs);
+        let minute = (unix_secs / 60) % 60;
+        let hour = (unix_secs / 3_600) % 24;
+        // 1970-01-01 was a Thursday
+        let weekday = (unix_secs / 86_400 + 4) % 7;
+
+        let day_match = self.days & (1 << day) != 0;
+        let weekday_match = self.weekdays & (1 << weekday) != 0;
+        let day_ok = match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day_match || weekday_match,
+            _ => day_match && weekday_match,
+        };
+        self.minutes & (1 << minute) != 0
+            && self.hours & (1 << hour) != 0
+            && self.months & (1 << month) != 0
+            && day_ok
+    }
+
+    /// Start of the first matching minute after `unix_secs`
+    pub fn next_after(&self, unix_secs: u64) -> Option<u64> {
+        let start = (unix_secs / 60 + 1) * 60;
+        (0..MAX_SEARCH_MINUTES)
+            .map(|i| start + i * 60)
+            .find(|&t| self.matches(t))
+    }
+}
+
+/// Bit mask of the values a field allows
+fn parse_field(text: &str, min: u32, max: u32) -> Result<u64> {
+    let mut mask = 0u64;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("bad step '{}'", step))?;
+                if step == 0 {
+                    return Err(anyhow::anyhow!("step must be at least 1"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let number = |value: &str| -> Result<u32> {
+            let n: u32 = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("'{}' is not a number", value))?;
+            if n < min || n > max {
+                return Err(anyhow::anyhow!("{} is outside {}-{}", n, min, max));
+            }
+            Ok(n)
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (number(a)?, number(b)?),
+                // `5/15` means from 5 to the end in steps of 15
+                None if step > 1 => (number(range)?, max),
+                None => {
+                    let n = number(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if start > end {
+            return Err(anyhow::anyhow!("range {}-{} is backwards", start, end));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// `YYYY-MM-DD HH:MM UTC`
+pub fn format_utc(unix_secs: u64) -> String {
+    let (year, month, day) = civil_date(unix_secs);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        (unix_secs / 3_600) % 24,
+        (unix_secs / 60) % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2025-06-02 09:00 UTC, a Monday
+    const MONDAY_9AM: u64 = 1_748_854_800;
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(
+            parse_field("*/15", 0, 59).unwrap(),
+            1 | 1 << 15 | 1 << 30 | 1 << 45
+        );
+        assert_eq!(parse_field("1-3,5", 0, 59).unwrap(), 0b10_1110);
+        assert_eq!(parse_field("50/5", 0, 59).unwrap(), 1 << 50 | 1 << 55);
+        assert!(parse_field("60", 0, 59).is_err());
+        assert!(parse_field("5-1", 0, 59).is_err());
+        assert!(parse_field("*/0", 0, 59).is_err());
+        assert!(CronSchedule::parse("0 9 * *").is_err());
+        assert_eq!(
+            CronSchedule::parse("0 0 * * 7").unwrap(),
+            CronSchedule::parse("@weekly").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_matches_and_next_run() {
+        assert_eq!(format_utc(MONDAY_9AM), "2025-06-02 09:00 UTC");
+        let daily = CronSchedule::parse("0 9 * * *").unwrap();
+        assert!(daily.matches(MONDAY_9AM + 59));
+        assert!(!daily.matches(MONDAY_9AM + 60));
+        assert_eq!(daily.next_after(MONDAY_9AM), Some(MONDAY_9AM + 86_400));
+
+        let weekdays = CronSchedule::parse("30 8 * * 1-5").unwrap();
+        // Friday 08:30 to Monday 08:30 skips the weekend
+        let friday = MONDAY_9AM + 4 * 86_400 - 1_800;
+        assert!(weekdays.matches(friday));
+        assert_eq!(weekdays.next_after(friday), Some(friday + 3 * 86_400));
+
+        // Day of month OR weekday when both are given
+        let either = CronSchedule::parse("0 9 15 * 1").unwrap();
+        assert!(either.matches(MONDAY_9AM));
+        assert!(either.matches(MONDAY_9AM + 13 * 86_400)); // Sunday the 15th
+
+        let leap = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(MONDAY_9AM).map(format_utc).as_deref(),
+            Some("2028-02-29 00:00 UTC")
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Playback effects for `--fx` (e.g. `pitch=-2,reverb=0.2,tempo=1.1`)
+//!
+//! Effects are written SoX-style as a comma-separated chain and rendered with
+//! ffmpeg filters just before playback, so a clip can be auditioned as it
+//! would sit in a mix; saved files are never changed.
+
+use anyhow::Result;
+use std::io::Write;
+use std::process::Command;
+
+/// Rate the chain works at; pitch shifting resamples relative to it
+const WORK_RATE: u32 = 44_100;
+
+/// One effect in a `--fx` chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+    /// Shift pitch by semitones, keeping the tempo
+    Pitch(f64),
+    /// Change speed by a factor, keeping the pitch
+    Tempo(f64),
+    /// Change speed and pitch together, like a tape
+    Speed(f64),
+    /// Add room reverb; 0 is dry, 1 very wet
+    Reverb(f64),
+    /// Change volume in dB
+    Gain(f64),
+    /// Cut frequencies below this many Hz
+    Highpass(f64),
+    /// Cut frequencies above this many Hz
+    Lowpass(f64),
+}
+
+/// Effect names, their accepted range and what the value means
+const EFFECTS: &[(&str, f64, f64, &str)] = &[
+    ("pitch", -12.0, 12.0, "semitones"),
+    ("tempo", 0.5, 2.0, "a speed factor"),
+    ("speed", 0.5, 2.0, "a speed factor"),
+    ("reverb", 0.0, 1.0, "the wet amount"),
+    ("gain", -30.0, 30.0, "dB"),
+    ("highpass", 20.0, 20_000.0, "Hz"),
+    ("lowpass", 20.0, 20_000.0, "Hz"),
+];
+
+/// Parse a chain such as `pitch=-2,reverb=0.2,tempo=1.1`
+pub fn parse_effects(chain: &str) -> Result<Vec<Effect>> {
+    chain
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(parse_effect)
+        .collect()
+}
+
+fn parse_effect(spec: &str) -> Result<Effect> {
+    let names = || {
+        EFFECTS
+            .iter()
+            .map(|(name, ..)| *name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (name, value) = spec.split_once('=').ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid effect '{}'. Use NAME=VALUE with one of: {}",
+            spec,
+            names()
+        )
+    })?;
+    let name = name.trim().to_ascii_lowercase();
+    let &(_, min, max, unit) = EFFECTS
+        .iter()
+        .find(|(n, ..)| *n == name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown effect '{}'. Use one of: {}", name, names()))?;
+    let value: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Effect '{}' needs a number, got '{}'", name, value))?;
+    if !(min..=max).contains(&value) {
+        return Err(anyhow::anyhow!(
+            "{} must be between {} and {} ({})",
+            name,
+            min,
+            max,
+            unit
+        ));
+    }
+    Ok(match name.as_str() {
+        "pitch" => Effect::Pitch(value),
+        "tempo" => Effect::Tempo(value),
+        "speed" => Effect::Speed(value),
+        "reverb" => Effect::Reverb(value),
+        "gain" => Effect::Gain(value),
+        "highpass" => Effect::Highpass(value),
+        _ => Effect::Lowpass(value),
+    })
+}
+
+/// The ffmpeg `-af` filter graph for a chain, applied in order
+fn ffmpeg_filter(effects: &[Effect]) -> String {
+    let mut filters = vec![format!("aresample={}", WORK_RATE)];
+    for effect in effects {
+        filters.push(match *effect {
+            Effect::Pitch(semitones) => {
+                let factor = 2f64.powf(semitones / 12.0);
+                format!(
+                    "asetrate={:.0},aresample={},atempo={:.6}",
+                    WORK_RATE as f64 * factor,
+                    WORK_RATE,
+                    1.0 / factor
+                )
+            }
+            Effect::Tempo(factor) => format!("atempo={}", factor),
+            Effect::Speed(factor) => format!(
+                "asetrate={:.0},aresample={}",
+                WORK_RATE as f64 * factor,
+                WORK_RATE
+            ),
+            Effect::Reverb(wet) => format!(
+                "aecho=0.8:0.9:40|70|110:{:.3}|{:.3}|{:.3}",
+                0.5 * wet,
+                0.35 * wet,
+                0.2 * wet
+            ),
+            Effect::Gain(db) => format!("volume={}dB", db),
+            Effect::Highpass(hz) => format!("highpass=f={}", hz),
+            Effect::Lowpass(hz) => format!("lowpass=f={}", hz),
+        });
+    }
+    filters.join(",")
+}
+
+/// Render `data` (any format ffmpeg reads) through the chain as WAV
+pub fn apply_effects(data: &[u8], effects: &[Effect]) -> Result<Vec<u8>> {
+    let mut input = tempfile::Builder::new()
+        .prefix("elevenlabs-fx-")
+        .tempfile()?;
+    input.write_all(data)?;
+    input.flush()?;
+
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(input.path())
+        .args(["-af", &ffmpeg_filter(effects), "-f", "wav", "-"])
+        .output()
+        .map_err(|_| anyhow::anyhow!("ffmpeg not found in PATH. Install ffmpeg to use --fx"))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg could not apply --fx: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_effects() {
+        assert_eq!(
+            parse_effects("pitch=-2, reverb=0.2,TEMPO=1.1").unwrap(),
+            [Effect::Pitch(-2.0), Effect::Reverb(0.2), Effect::Tempo(1.1)]
+        );
+        assert!(parse_effects("").unwrap().is_empty());
+        assert!(parse_effects("pitch").is_err());
+        assert!(parse_effects("flanger=1").is_err());
+        assert!(parse_effects("tempo=fast").is_err());
+        assert!(parse_effects("tempo=3").is_err());
+    }
+
+    #[test]
+    fn test_ffmpeg_filter() {
+        assert_eq!(
+            ffmpeg_filter(&[Effect::Pitch(12.0), Effect::Gain(-3.0)]),
+            "aresample=44100,asetrate=88200,aresample=44100,atempo=0.500000,volume=-3dB"
+        );
+        assert_eq!(
+            ffmpeg_filter(&[Effect::Reverb(1.0), Effect::Highpass(80.0)]),
+            "aresample=44100,aecho=0.8:0.9:40|70|110:0.500|0.350|0.200,highpass=f=80"
+        );
+    }
+}
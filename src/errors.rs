@@ -7,9 +7,13 @@
 
 #![allow(dead_code)]
 
+use crate::output::symbol;
+use crate::utils::{with_file_lock, write_atomic};
 use colored::*;
 use std::fmt;
-use std::time::Duration;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Maximum number of retry attempts for transient errors
 const MAX_RETRIES: u32 = 3;
@@ -20,6 +24,18 @@ const INITIAL_BACKOFF_MS: u64 = 1000;
 /// Maximum backoff delay (in milliseconds)
 const MAX_BACKOFF_MS: u64 = 30000;
 
+/// Error log size that triggers trimming to the most recent entries
+const MAX_ERROR_LOG_BYTES: u64 = 512 * 1024;
+
+/// Entries kept when the error log is trimmed
+const ERROR_LOG_KEEP_LINES: usize = 500;
+
+/// Flags whose values are secrets and never written to the error log
+const SECRET_FLAGS: &[&str] = &["--api-key", "-a", "--token"];
+
+/// Replacement text for redacted secrets
+pub const REDACTED: &str = "<redacted>";
+
 /// API Error types for better error messages
 #[derive(Debug, Clone)]
 pub enum ApiError {
@@ -233,6 +249,102 @@ pub fn print_api_error(err: &anyhow::Error) {
     }
 }
 
+/// Location of the local error log collected by `support-bundle`
+pub fn error_log_path() -> Option<PathBuf> {
+    crate::paths::data_file("errors.log")
+}
+
+/// Replace known secrets and anything shaped like an API key (`sk_...`)
+pub fn redact_secrets(text: &str, secrets: &[&str]) -> String {
+    let mut redacted = text.to_string();
+    for secret in secrets.iter().filter(|s| s.len() >= 8) {
+        redacted = redacted.replace(secret, REDACTED);
+    }
+
+    let mut out = String::with_capacity(redacted.len());
+    let mut rest = redacted.as_str();
+    while let Some(pos) = rest.find("sk_") {
+        out.push_str(&rest[..pos]);
+        let token_len = rest[pos..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - pos);
+        if token_len >= 16 {
+            out.push_str(REDACTED);
+        } else {
+            out.push_str(&rest[pos..pos + token_len]);
+        }
+        rest = &rest[pos + token_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Command-line arguments with secret flag values removed
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+    for arg in args {
+        if hide_next {
+            redacted.push(REDACTED.to_string());
+            hide_next = false;
+        } else if let Some((flag, _)) = arg.split_once('=') {
+            if SECRET_FLAGS.contains(&flag) {
+                redacted.push(format!("{}={}", flag, REDACTED));
+            } else {
+                redacted.push(arg.clone());
+            }
+        } else {
+            hide_next = SECRET_FLAGS.contains(&arg.as_str());
+            redacted.push(arg.clone());
+        }
+    }
+    redacted
+}
+
+/// Append a failed invocation to the local error log (best effort, never fails)
+pub fn log_error(err: &anyhow::Error) {
+    let Some(path) = error_log_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if std::fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let env_key = std::env::var("ELEVENLABS_API_KEY").unwrap_or_default();
+    let message = format!("{:#}", err).replace('\n', " ");
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let line = format!(
+        "{}\t{}\t{}\n",
+        timestamp,
+        redact_args(&args).join(" "),
+        redact_secrets(&message, &[&env_key])
+    );
+
+    // Trim and append under the lock so concurrent runs don't lose entries
+    let _ = with_file_lock(&path, || {
+        if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_ERROR_LOG_BYTES) {
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                let lines: Vec<&str> = contents.lines().collect();
+                let keep = &lines[lines.len().saturating_sub(ERROR_LOG_KEEP_LINES)..];
+                write_atomic(&path, format!("{}\n", keep.join("\n")).as_bytes())?;
+            }
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    });
+}
+
 /// Print a retryable error with backoff information
 pub fn print_retry_error(err: &anyhow::Error, attempt: u32, max_retries: u32) {
     let retryable = is_retryable(err);
@@ -404,12 +516,10 @@ pub fn print_subscription_info(tier: &str) {
     };
 
     for (feature, status, note) in features {
-        let status_colored = if status == "✓" {
-            status.green()
-        } else if status == "✗" {
-            status.red()
-        } else {
-            status.yellow()
+        let status_colored = match status {
+            "✓" => symbol("✓", "[ok]").green(),
+            "✗" => symbol("✗", "[no]").red(),
+            _ => status.yellow(),
         };
 
         if let Some(n) = note {
@@ -423,3 +533,33 @@ pub fn print_subscription_info(tier: &str) {
     println!("{}", "View full feature comparison:".dimmed());
     println!("  https://elevenlabs.io/pricing");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        assert_eq!(
+            redact_secrets("key sk_0123456789abcdef0123 failed", &[]),
+            "key <redacted> failed"
+        );
+        assert_eq!(redact_secrets("sk_short stays", &[]), "sk_short stays");
+        assert_eq!(
+            redact_secrets("used mysecretkey", &["mysecretkey"]),
+            "used <redacted>"
+        );
+    }
+
+    #[test]
+    fn test_redact_args() {
+        let args: Vec<String> = ["tts", "--api-key", "abc", "hi", "--token=xyz"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            redact_args(&args),
+            vec!["tts", "--api-key", "<redacted>", "hi", "--token=<redacted>"]
+        );
+    }
+}
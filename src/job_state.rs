@@ -0,0 +1,118 @@
+//! Progress files for batch operations that create resources
+//!
+//! The API takes no idempotency keys, so a batch that fails halfway would
+//! create duplicates when run again. Each batch instead records the items it
+//! has finished, with the ID created for each, in a state file under the
+//! data directory's `jobs/`. A rerun of the same batch against the same
+//! account reuses those IDs instead of creating the items again. The file is
+//! removed once a run finishes without failures.
+
+use crate::utils::write_atomic;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Completed items of one batch
+pub struct JobState {
+    path: Option<PathBuf>,
+    completed: BTreeMap<String, String>,
+}
+
+impl JobState {
+    /// State of the batch identified by `kind` and `parts` (the inputs that
+    /// make two runs the same batch). The API key is part of the identity,
+    /// so the same batch against another account starts fresh.
+    pub fn open(kind: &str, api_key: &str, parts: &[&str]) -> Self {
+        let mut identity = vec![api_key];
+        identity.extend_from_slice(parts);
+        let name = format!("jobs/{}-{:016x}.json", kind, fingerprint(&identity));
+        let path = crate::paths::data_file(&name);
+        let completed = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, completed }
+    }
+
+    /// ID created for `item` by an earlier run
+    pub fn completed(&self, item: &str) -> Option<&str> {
+        self.completed.get(item).map(String::as_str)
+    }
+
+    /// Number of items finished by earlier runs
+    pub fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Record `item` as done, saving right away so a crash loses nothing
+    pub fn mark_done(&mut self, item: &str, id: &str) -> Result<()> {
+        self.completed.insert(item.to_string(), id.to_string());
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        write_atomic(
+            path,
+            serde_json::to_string_pretty(&self.completed)?.as_bytes(),
+        )
+        .with_context(|| format!("Failed to save job state {}", path.display()))
+    }
+
+    /// Forget the batch after a run with no failures
+    pub fn finish(self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+}
+
+/// Stable FNV-1a hash of the batch identity (the file name must not change
+/// between builds, so `DefaultHasher` is not used)
+pub(crate) fn fingerprint(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_is_stable_and_separates_parts() {
+        assert_eq!(fingerprint(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint(&["key", "dir"]), fingerprint(&["key", "dir"]));
+        assert_ne!(fingerprint(&["key", "dir"]), fingerprint(&["keyd", "ir"]));
+        assert_ne!(fingerprint(&["a", "dir"]), fingerprint(&["b", "dir"]));
+    }
+
+    #[test]
+    fn test_completed_items_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs/import.json");
+        let mut state = JobState {
+            path: Some(path.clone()),
+            completed: BTreeMap::new(),
+        };
+        state.mark_done("voices/old_1", "new_1").unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let reopened = JobState {
+            path: Some(path.clone()),
+            completed: serde_json::from_str(&contents).unwrap(),
+        };
+        assert_eq!(reopened.completed("voices/old_1"), Some("new_1"));
+        assert_eq!(reopened.completed_count(), 1);
+        reopened.finish();
+        assert!(!path.exists());
+    }
+}
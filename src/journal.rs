@@ -0,0 +1,222 @@
+//! Local journal of asynchronous remote jobs
+//!
+//! Dubbing, voice training, project conversions, batch calls and RAG index
+//! builds keep running on ElevenLabs after the command that started them
+//! exits. Each start is recorded in `remote-jobs.json` in the data directory
+//! so `jobs list|status|wait` can follow them in one place, whatever their
+//! kind. Entries carry a fingerprint of the API key (never the key) so each
+//! account only sees its own jobs.
+
+use crate::cli::JobKind;
+use crate::job_state::fingerprint;
+use crate::utils::{unix_now, with_file_lock, write_atomic};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Finished jobs beyond this many are dropped, oldest first
+const MAX_FINISHED_JOBS: usize = 200;
+
+/// Where a job stands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPhase {
+    #[default]
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobPhase {
+    pub fn is_finished(self) -> bool {
+        self != JobPhase::Running
+    }
+}
+
+/// One remote job started by the CLI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoteJob {
+    pub id: String,
+    pub kind: JobKind,
+    /// Resource the job's status is read through: the voice of a training
+    /// run, the document of a RAG index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// What the job is for, e.g. the dubbed file and language
+    pub label: String,
+    pub account: String,
+    /// Unix seconds
+    pub started_at: u64,
+    /// State last reported by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub phase: JobPhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    /// When the state was last read (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<u64>,
+}
+
+/// Account fingerprint stored with each job
+pub fn account_id(api_key: &str) -> String {
+    format!("{:016x}", fingerprint(&[api_key]))
+}
+
+fn journal_path() -> Option<PathBuf> {
+    crate::paths::data_file("remote-jobs.json")
+}
+
+/// Record a job that was just started (best effort: bookkeeping never fails
+/// the command). Starting a job again, such as reconverting a project,
+/// replaces its entry. Returns the entry so the caller can wait on it.
+pub fn record(
+    api_key: &str,
+    kind: JobKind,
+    id: &str,
+    parent: Option<&str>,
+    label: &str,
+) -> RemoteJob {
+    let job = RemoteJob {
+        id: id.to_string(),
+        kind,
+        parent: parent.map(str::to_string),
+        label: label.to_string(),
+        account: account_id(api_key),
+        started_at: unix_now(),
+        state: None,
+        phase: JobPhase::Running,
+        progress: None,
+        checked_at: None,
+    };
+    let _ = update(|jobs| {
+        jobs.retain(|j| !(j.id == job.id && j.kind == job.kind));
+        jobs.push(job.clone());
+        prune(jobs);
+    });
+    job
+}
+
+/// Every job in the journal, oldest first
+pub fn load() -> Result<Vec<RemoteJob>> {
+    let Some(path) = journal_path() else {
+        return Ok(Vec::new());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Change the journal under its lock, re-reading it first so concurrent
+/// runs don't lose each other's jobs
+pub fn update(apply: impl FnOnce(&mut Vec<RemoteJob>)) -> Result<()> {
+    let path =
+        journal_path().ok_or_else(|| anyhow::anyhow!("Could not determine the data directory"))?;
+    with_file_lock(&path, || {
+        let mut jobs = load()?;
+        apply(&mut jobs);
+        write_atomic(&path, serde_json::to_string_pretty(&jobs)?.as_bytes())
+    })
+}
+
+/// Store refreshed copies of `refreshed` jobs
+pub fn save_states(refreshed: &[RemoteJob]) -> Result<()> {
+    update(|jobs| {
+        for job in jobs.iter_mut() {
+            if let Some(new) = refreshed
+                .iter()
+                .find(|r| r.id == job.id && r.kind == job.kind)
+            {
+                *job = new.clone();
+            }
+        }
+    })
+}
+
+/// Drop the oldest finished jobs past the limit; running jobs are kept
+fn prune(jobs: &mut Vec<RemoteJob>) {
+    let finished = jobs.iter().filter(|j| j.phase.is_finished()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+    jobs.retain(|j| {
+        if excess > 0 && j.phase.is_finished() {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// The phase a remote status string means. Names differ between job types
+/// ("dubbed", "fine_tuned", "completed"), so this matches on the words used
+pub fn phase_of(state: &str) -> JobPhase {
+    let state = state.to_lowercase();
+    if ["fail", "error", "cancel", "exceeded"]
+        .iter()
+        .any(|word| state.contains(word))
+    {
+        JobPhase::Failed
+    } else if matches!(
+        state.as_str(),
+        "dubbed"
+            | "fine_tuned"
+            | "completed"
+            | "complete"
+            | "succeeded"
+            | "success"
+            | "done"
+            | "ready"
+    ) {
+        JobPhase::Succeeded
+    } else {
+        JobPhase::Running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, phase: JobPhase) -> RemoteJob {
+        RemoteJob {
+            id: id.to_string(),
+            kind: JobKind::Dubbing,
+            parent: None,
+            label: String::new(),
+            account: account_id("key"),
+            started_at: 0,
+            state: None,
+            phase,
+            progress: None,
+            checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_phase_of() {
+        assert_eq!(phase_of("dubbed"), JobPhase::Succeeded);
+        assert_eq!(phase_of("fine_tuned"), JobPhase::Succeeded);
+        assert_eq!(phase_of("dubbing"), JobPhase::Running);
+        assert_eq!(phase_of("in_progress"), JobPhase::Running);
+        assert_eq!(phase_of("Failed"), JobPhase::Failed);
+        assert_eq!(phase_of("rag_limit_exceeded"), JobPhase::Failed);
+        assert_eq!(phase_of("cancelled"), JobPhase::Failed);
+    }
+
+    #[test]
+    fn test_prune_keeps_running_jobs() {
+        let mut jobs: Vec<RemoteJob> = (0..MAX_FINISHED_JOBS + 2)
+            .map(|i| job(&i.to_string(), JobPhase::Succeeded))
+            .collect();
+        jobs.insert(0, job("running", JobPhase::Running));
+        prune(&mut jobs);
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS + 1);
+        assert_eq!(jobs[0].id, "running");
+        assert_eq!(jobs[1].id, "2");
+        assert_ne!(account_id("key"), account_id("other"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Local ledger of generated files
+//!
+//! Each `tts` generation appends one JSON line to `generations.jsonl` in the
+//! data directory, carrying the `--project-id` and `--meta` values given on
+//! the command line. The API has no per-request field for these, so
+//! `usage projects` reads them back from here to group usage by project.
+
+use crate::utils::with_file_lock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One generated file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LedgerEntry {
+    /// Unix seconds
+    pub timestamp: u64,
+    pub command: String,
+    pub voice: String,
+    pub model: String,
+    pub characters: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl LedgerEntry {
+    /// An entry stamped with the current time
+    pub fn new(command: &str, voice: &str, model: &str, characters: usize) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            command: command.to_string(),
+            voice: voice.to_string(),
+            model: model.to_string(),
+            characters,
+            output: None,
+            project_id: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+/// Location of the ledger
+pub fn ledger_path() -> Option<PathBuf> {
+    crate::paths::data_file("generations.jsonl")
+}
+
+/// Append an entry (best effort: bookkeeping never fails the command)
+pub fn record(entry: &LedgerEntry) {
+    let Some(path) = ledger_path() else {
+        return;
+    };
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    let _ = with_file_lock(&path, || {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    });
+}
+
+/// Every readable entry, oldest first (empty when there is no ledger)
+pub fn load() -> Vec<LedgerEntry> {
+    ledger_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+/// Parse ledger lines, skipping any that are damaged
+fn parse(contents: &str) -> Vec<LedgerEntry> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Parse a `KEY=VALUE` metadata argument
+pub fn parse_meta(value: &str) -> anyhow::Result<(String, String)> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(anyhow::anyhow!("Invalid --meta '{}'. Use KEY=VALUE", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_damaged_lines() {
+        let mut entry = LedgerEntry::new("tts", "Brian", "eleven_multilingual_v2", 42);
+        entry.project_id = Some("launch".to_string());
+        entry
+            .metadata
+            .insert("chapter".to_string(), "3".to_string());
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("output"));
+
+        let entries = parse(&format!("{}\n{{broken\n\n{}\n", line, line));
+        assert_eq!(entries, vec![entry.clone(), entry]);
+    }
+
+    #[test]
+    fn test_parse_meta() {
+        assert_eq!(
+            parse_meta("chapter = 3").unwrap(),
+            ("chapter".to_string(), "3".to_string())
+        );
+        assert_eq!(parse_meta("note=a=b").unwrap().1, "a=b");
+        assert!(parse_meta("=x").is_err());
+        assert!(parse_meta("chapter").is_err());
+    }
+}
@@ -1,29 +1,45 @@
 use anyhow::Result;
-use clap::{CommandFactory, Parser};
-use clap_complete::generate;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use colored::*;
-use std::io;
 
+mod article;
 #[cfg(feature = "audio")]
 mod audio;
+mod casting;
 mod cli;
 mod client;
 mod commands;
 mod config;
+mod credentials;
+mod cron;
+mod effects;
 mod errors;
+mod job_state;
+mod journal;
+mod ledger;
 mod output;
+mod paths;
+mod pcm;
+mod player;
+mod prompt;
+mod tags;
+mod text_clean;
+mod transcode;
 mod utils;
 mod validation;
+mod waveform;
 
 #[cfg(feature = "mcp")]
 mod mcp;
 #[cfg(feature = "mcp")]
 mod mcp_handlers;
+#[cfg(feature = "serve")]
+mod serve;
 
 // Import CLI types from the new modular structure
 use cli::{
-    Commands, ModelsArgs, ModelsCommands, TextToSpeechArgs, UserArgs, UserCommands, VoiceArgs,
-    VoiceCommands,
+    ColorChoice, Commands, ModelsArgs, ModelsCommands, Organize, SttCommands, TextToSpeechArgs,
+    TtsCommands, UserArgs, UserCommands, VoiceArgs, VoiceCommands, VoiceDesignCommands,
 };
 use config::Config;
 use output::print_error;
@@ -43,6 +59,10 @@ struct Cli {
     #[arg(short, long, global = true, env = "ELEVENLABS_API_KEY")]
     api_key: Option<String>,
 
+    /// Config profile to use (see `config profile list`)
+    #[arg(long, global = true, env = "ELEVENLABS_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
+
     /// Output format for audio (mp3_44100_128, mp3_44100_192, pcm_16000, etc.)
     #[arg(short, long, global = true, default_value = "mp3_44100_128")]
     format: String,
@@ -55,18 +75,138 @@ struct Cli {
     #[arg(short = 'y', long, global = true)]
     yes: bool,
 
+    /// Never prompt: fail with exit code 3 where input would be needed
+    #[arg(long, global = true)]
+    no_input: bool,
+
     /// Output as JSON (for scripting and MCP integration)
     #[arg(short = 'j', long, global = true)]
     json: bool,
+
+    /// Suppress informational messages and progress indicators
+    #[arg(short = 'q', long, global = true)]
+    quiet: bool,
+
+    /// When to use colored output (honors NO_COLOR in auto mode)
+    #[arg(long, global = true, value_enum, value_name = "WHEN")]
+    color: Option<ColorChoice>,
+
+    /// Use ASCII instead of unicode status symbols
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Directory for generated files when no output path is given
+    #[arg(long, global = true, value_name = "DIR")]
+    output_dir: Option<String>,
+
+    /// Group generated files into subdirectories (comma-separated, outermost first)
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "LAYOUT"
+    )]
+    organize: Vec<Organize>,
+
+    /// Project name used by --organize by-project
+    #[arg(long, global = true, value_name = "NAME")]
+    project: Option<String>,
+
+    /// Retries for rate-limited (429) and failed (5xx) requests; 0 disables them
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = client::DEFAULT_MAX_RETRIES
+    )]
+    max_retries: u32,
+
+    /// Seconds before the first retry, doubled (with jitter) for each further one
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        default_value_t = client::DEFAULT_RETRY_DELAY_SECS
+    )]
+    retry_delay: f64,
+
+    /// Extra HTTP header for API requests, as NAME:VALUE (repeatable). Not
+    /// applied to commands that go through the SDK; those warn instead
+    #[arg(long = "header", global = true, value_name = "NAME:VALUE", value_parser = client::parse_header)]
+    headers: Vec<(String, String)>,
+
+    /// Extra query parameter for API requests, as KEY=VALUE (repeatable). Not
+    /// applied to commands that go through the SDK; those warn instead
+    #[arg(long = "query", global = true, value_name = "KEY=VALUE", value_parser = client::parse_query)]
+    query_params: Vec<(String, String)>,
+
+    /// Effects applied when playing audio, never to saved files (pitch, tempo,
+    /// speed, reverb, gain, highpass, lowpass; needs ffmpeg). Streamed audio
+    /// plays once it is complete rather than as it arrives
+    #[arg(long, global = true, value_name = "pitch=-2,reverb=0.2,tempo=1.1")]
+    fx: Option<String>,
+
+    /// Warn about API response fields the CLI doesn't know yet
+    #[arg(long, global = true, env = "ELEVENLABS_WARN_UNKNOWN_FIELDS")]
+    warn_unknown_fields: bool,
+}
+
+/// The full command line. `dub create -f`, `converse feedback -f` and
+/// `agent widget-avatar -a` are older than the global `-f`/`-a`, so those
+/// subcommands get long-only copies of the global flags, which stops the
+/// globals from being propagated there with their short flags.
+fn cli_command() -> clap::Command {
+    let long_only = |cmd: clap::Command, id: &str| {
+        let global = Cli::command()
+            .get_arguments()
+            .find(|arg| arg.get_id() == id)
+            .cloned()
+            .expect("global argument");
+        cmd.arg(global.short(None))
+    };
+    Cli::command()
+        .mut_subcommand("dub", |dub| {
+            dub.mut_subcommand("create", |create| long_only(create, "format"))
+        })
+        .mut_subcommand("converse", |converse| {
+            converse.mut_subcommand("feedback", |feedback| long_only(feedback, "format"))
+        })
+        .mut_subcommand("agent", |agent| {
+            agent.mut_subcommand("widget-avatar", |avatar| long_only(avatar, "api_key"))
+        })
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let result = run().await;
+    if let Err(e) = &result {
+        errors::log_error(e);
+        if errors::is_rate_limited(e) {
+            client::record_throttle();
+        }
+        if prompt::is_input_required(e) {
+            eprintln!("Error: {:?}", e);
+            std::process::exit(prompt::EXIT_INPUT_REQUIRED);
+        }
+    }
+    result
+}
+
+async fn run() -> Result<()> {
+    let cli = Cli::from_arg_matches(&cli_command().get_matches())?;
+    prompt::set_no_input(cli.no_input);
+    client::set_retry_policy(client::RetryPolicy::new(cli.max_retries, cli.retry_delay)?);
+    client::set_request_extras(client::RequestExtras::new(&cli.headers, &cli.query_params)?);
+    client::set_warn_unknown_fields(cli.warn_unknown_fields);
+    output::set_json_mode(cli.json);
+    output::set_quiet_mode(cli.quiet);
+    paths::migrate_legacy_layout();
 
     // Handle MCP mode (feature-gated)
     #[cfg(feature = "mcp")]
     if let Some(Commands::Mcp {
+        command,
         enable_tools,
         disable_tools,
         disable_admin,
@@ -74,7 +214,16 @@ async fn main() -> Result<()> {
         read_only,
     }) = &cli.command
     {
+        if let Some(cli::McpCommands::Audit { command }) = command {
+            return mcp::audit::execute(command);
+        }
+        // The server reads its own settings from the config; the key comes
+        // from --api-key or the selected profile
+        let mut config = Config::load().unwrap_or_default();
+        config.apply_profile(cli.profile.as_deref())?;
+        let api_key = cli.api_key.clone().or(config.api_key);
         return mcp::run_server(
+            api_key.as_deref(),
             enable_tools.as_deref(),
             disable_tools.as_deref(),
             *disable_admin,
@@ -88,26 +237,102 @@ async fn main() -> Result<()> {
     let command = match cli.command {
         Some(cmd) => cmd,
         None => {
-            Cli::command().print_help()?;
+            cli_command().print_help()?;
             std::process::exit(0);
         }
     };
 
-    // Handle completions command first (doesn't need API key)
-    if let Commands::Completions { shell } = &command {
-        let mut cmd = Cli::command();
-        let name = cmd.get_name().to_string();
-        generate(*shell, &mut cmd, name, &mut io::stdout());
-        return Ok(());
+    // Handle completions command first (doesn't need API key or config)
+    if let Commands::Completions(args) = command {
+        return commands::completions::execute(args, cli_command());
     }
 
+    // Load or create config
+    let mut config = Config::load()?;
+    config.apply_profile(cli.profile.as_deref())?;
+
+    // Apply color and symbol settings (CLI flags take precedence over config)
+    let color = match cli.color {
+        Some(choice) => choice,
+        None => match config.color.as_deref() {
+            Some(value) => value.parse()?,
+            None => ColorChoice::Auto,
+        },
+    };
+    // JSON output never carries ANSI codes, whatever the color setting
+    output::set_color_mode(match color {
+        _ if cli.json => Some(false),
+        ColorChoice::Always => Some(true),
+        ColorChoice::Never => Some(false),
+        ColorChoice::Auto => None,
+    });
+    output::set_ascii_mode(cli.ascii || config.ascii_symbols.unwrap_or(false));
+
+    // Where generated files land when no output path is given
+    let organize = if cli.organize.is_empty() {
+        match config.organize.as_deref() {
+            Some(value) => cli::parse_organize(value)?,
+            None => Vec::new(),
+        }
+    } else {
+        cli.organize.clone()
+    };
+    utils::set_output_layout(utils::OutputLayout {
+        dir: cli
+            .output_dir
+            .as_deref()
+            .or(config.output_dir.as_deref())
+            .map(utils::expand_home),
+        organize,
+        project: cli.project.clone(),
+    });
+    player::set_player_command(config.player_cmd.clone());
+    if let Some(chain) = &cli.fx {
+        player::set_playback_effects(effects::parse_effects(chain)?);
+    }
+    text_clean::set_default_rules(match config.clean_input.as_deref() {
+        Some(value) => cli::parse_clean_rules(value)?,
+        None => Vec::new(),
+    });
+
     // Handle update command (doesn't need API key)
     if let Commands::Update(args) = command {
         return commands::update::execute(args).await;
     }
 
-    // Load or create config
-    let mut config = Config::load()?;
+    // Handle support bundle (doesn't need API key)
+    if let Commands::SupportBundle(args) = command {
+        return commands::support_bundle::execute(args, &config, cli.api_key.as_deref(), cli.yes);
+    }
+
+    // Handle local audio generators (doesn't need API key)
+    if let Commands::Audio(args) = command {
+        return commands::audio::execute(args, cli.yes);
+    }
+
+    // Handle local voice scoring (doesn't need API key)
+    if let Commands::Voice(cli::VoiceArgs {
+        command: cli::VoiceCommands::Score {
+            reference,
+            candidate,
+        },
+    }) = &command
+    {
+        return commands::voice_score::execute(reference, candidate);
+    }
+
+    // Handle stored voice settings (doesn't need API key)
+    if let Commands::Voice(cli::VoiceArgs {
+        command: cli::VoiceCommands::Defaults { command },
+    }) = &command
+    {
+        return commands::voice_defaults::execute(command);
+    }
+
+    // Handle config commands (doesn't need API key)
+    if let Commands::Config(args) = command {
+        return commands::config::execute(args, &mut config);
+    }
 
     // Override config with CLI args if provided
     if let Some(api_key) = cli.api_key {
@@ -118,12 +343,17 @@ async fn main() -> Result<()> {
     let api_key = match config.api_key.as_ref() {
         Some(key) if !key.is_empty() => key.clone(),
         _ => {
+            if let Some(ref reason) = config.keyring_error {
+                eprintln!(
+                    "{}",
+                    format!("The API key is stored in the OS keyring, but: {}", reason).yellow()
+                );
+            }
             errors::print_api_error(&anyhow::anyhow!("API key is required"));
             std::process::exit(1);
         }
     };
     let assume_yes = cli.yes;
-    let json_output = cli.json;
 
     // Apply config defaults
     let output_format = config
@@ -132,17 +362,23 @@ async fn main() -> Result<()> {
         .unwrap_or(&cli.format);
 
     if cli.verbose {
-        println!("{} Using ElevenLabs API", "ℹ".blue());
+        output::print_info("Using ElevenLabs API");
     }
 
-    // Initialize JSON output mode in the output module
-    output::set_json_mode(json_output);
-
     match command {
-        Commands::TextToSpeech(args) => {
-            commands::tts::execute(args, &api_key, output_format, assume_yes).await?
-        }
-        Commands::SpeechToText(args) => commands::stt::execute(args, &api_key).await?,
+        Commands::TextToSpeech(args) => match args.command {
+            Some(TtsCommands::Feed(feed)) => {
+                commands::tts_feed::execute(feed, &api_key, output_format).await?
+            }
+            Some(TtsCommands::Session(session)) => {
+                commands::tts_session::execute(session, &api_key, output_format).await?
+            }
+            None => commands::tts::execute(args, &api_key, output_format, assume_yes).await?,
+        },
+        Commands::SpeechToText(args) => match args.command {
+            Some(SttCommands::Live(live)) => commands::stt_live::execute(live, &api_key).await?,
+            None => commands::stt::execute(args, &api_key).await?,
+        },
         Commands::Voice(args) => commands::voice::execute(args, &api_key, assume_yes).await?,
         Commands::AudioIsolation(args) => {
             commands::isolation::execute(args, &api_key, assume_yes).await?
@@ -155,16 +391,20 @@ async fn main() -> Result<()> {
         Commands::History(args) => commands::history::execute(args, &api_key, assume_yes).await?,
         Commands::User(args) => commands::user::execute(args, &api_key).await?,
         Commands::Models(args) => commands::models::execute(args, &api_key).await?,
-        Commands::Config(args) => commands::config::execute(args, &mut config)?,
         Commands::VoiceLibrary(args) => commands::voice_library::execute(args, &api_key).await?,
         Commands::Pronunciation(args) => commands::pronunciation::execute(args, &api_key).await?,
         Commands::Usage(args) => commands::usage::execute(args, &api_key).await?,
-        Commands::VoiceDesign(args) => {
-            commands::voice_design::execute(args, &api_key, assume_yes).await?
-        }
+        Commands::VoiceDesign(args) => match args.command {
+            Some(VoiceDesignCommands::Iterate(iterate)) => {
+                commands::voice_design_iterate::execute(iterate, &api_key).await?
+            }
+            None => commands::voice_design::execute(args, &api_key, assume_yes).await?,
+        },
         Commands::AudioNative(args) => commands::audio_native::execute(args, &api_key).await?,
         Commands::Samples(args) => commands::samples::execute(args, &api_key, assume_yes).await?,
-        Commands::Workspace(args) => commands::workspace::execute(args, &api_key).await?,
+        Commands::Workspace(args) => {
+            commands::workspace::execute(args, &api_key, assume_yes).await?
+        }
         Commands::TtsWithTimestamps(args) => {
             commands::tts_timestamps::execute(args, &api_key, output_format, assume_yes).await?
         }
@@ -174,7 +414,7 @@ async fn main() -> Result<()> {
         Commands::RealtimeTts(args) => {
             commands::realtime_tts::execute(args, &api_key, assume_yes).await?
         }
-        Commands::Agent(args) => commands::agent::execute(args, &api_key).await?,
+        Commands::Agent(args) => commands::agent::execute(args, &api_key, assume_yes).await?,
         Commands::Conversation(args) => {
             commands::conversation::execute(args, &api_key, assume_yes).await?
         }
@@ -188,9 +428,23 @@ async fn main() -> Result<()> {
         Commands::Projects(args) => commands::projects::execute(args, &api_key, assume_yes).await?,
         Commands::Music(args) => commands::music::execute(args, &api_key, assume_yes).await?,
         Commands::Phone(args) => commands::phone::execute(args, &api_key, assume_yes).await?,
-        Commands::Completions { .. } => unreachable!(),
+        Commands::Ivr(args) => commands::ivr::execute(args, &api_key).await?,
+        Commands::Search(args) => commands::search::execute(args, &api_key).await?,
+        Commands::Export(args) => commands::backup::export(args, &api_key, &config).await?,
+        Commands::Import(args) => commands::backup::import(args, &api_key).await?,
+        Commands::Limits(args) => commands::limits::execute(args, &api_key).await?,
+        Commands::Daemon(args) => commands::daemon::execute(args, &api_key).await?,
+        Commands::Schedule(args) => commands::schedule::execute(args, &api_key).await?,
+        Commands::Jobs(args) => commands::jobs::execute(args, &api_key).await?,
+        Commands::Api(args) => commands::api::execute(args, &api_key).await?,
+        Commands::Completions(_) => unreachable!(),
         Commands::Update { .. } => unreachable!(),
+        Commands::SupportBundle(_) => unreachable!(),
+        Commands::Audio(_) => unreachable!(),
+        Commands::Config(_) => unreachable!(),
         Commands::Interactive => run_interactive_mode(&api_key, output_format, assume_yes).await?,
+        #[cfg(feature = "serve")]
+        Commands::Serve(args) => serve::run_server(args, api_key).await?,
         #[cfg(feature = "mcp")]
         Commands::Mcp { .. } => unreachable!(),
     }
@@ -203,9 +457,7 @@ async fn run_interactive_mode(api_key: &str, default_format: &str, assume_yes: b
     println!("Type 'help' for available commands, 'exit' to quit.\n");
 
     loop {
-        let input = dialoguer::Input::<String>::new()
-            .with_prompt("elevenlabs")
-            .interact_text()?;
+        let input = prompt::input("elevenlabs")?;
 
         let trimmed = input.trim();
         if trimmed.is_empty() {
@@ -260,11 +512,18 @@ async fn run_interactive_mode(api_key: &str, default_format: &str, assume_yes: b
                 }
                 let text = parts[1..].join(" ");
                 let args = TextToSpeechArgs {
+                    command: None,
                     text: Some(text),
                     file: None,
+                    url: None,
+                    clean_input: Vec::new(),
                     voice: "Brian".to_string(),
                     model: "eleven_multilingual_v2".to_string(),
+                    fallback_voice: Vec::new(),
+                    fallback_model: Vec::new(),
+                    casting: None,
                     output: None,
+                    also_format: Vec::new(),
                     play: false,
                     output_device: None,
                     list_output_devices: false,
@@ -272,8 +531,16 @@ async fn run_interactive_mode(api_key: &str, default_format: &str, assume_yes: b
                     similarity_boost: None,
                     style: None,
                     speaker_boost: false,
+                    no_speaker_boost: false,
                     language: None,
                     seed: None,
+                    tag: false,
+                    album: None,
+                    sentence_pause: None,
+                    phoneme: Vec::new(),
+                    delivery: None,
+                    project_id: None,
+                    meta: Vec::new(),
                 };
                 if let Err(e) =
                     commands::tts::execute(args, api_key, default_format, assume_yes).await
@@ -282,7 +549,7 @@ async fn run_interactive_mode(api_key: &str, default_format: &str, assume_yes: b
                 }
             }
             _ => {
-                println!("{} Unknown command: {}", "✗".red(), cmd);
+                print_error(&format!("Unknown command: {}", cmd));
             }
         }
     }
@@ -0,0 +1,268 @@
+//! Audit log of MCP tool calls
+//!
+//! Every tool call is appended as one JSON line to `mcp-audit.jsonl` in the
+//! data directory: the tool, a summary of its arguments, characters sent for
+//! generation, how long it took and whether it succeeded. Long values are
+//! shortened and secrets are never written. `mcp audit tail` reads it back.
+
+use crate::cli::McpAuditCommands;
+use crate::output::{is_json_mode, print_info};
+use crate::utils::{format_relative_time, with_file_lock};
+use anyhow::Result;
+use colored::*;
+use comfy_table::Table;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Longest argument value kept in the log
+const MAX_ARG_LEN: usize = 80;
+
+/// Arguments whose values are never logged
+const REDACTED_ARGS: &[&str] = &["value", "api_key", "token", "audio_base64"];
+
+/// One tool call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    /// Unix seconds
+    pub timestamp: u64,
+    pub tool: String,
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+    /// Text characters sent for generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub characters: Option<usize>,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// An entry for a call that has just finished
+    pub fn new(
+        tool: &str,
+        arguments: &Map<String, Value>,
+        elapsed: Duration,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            tool: tool.to_string(),
+            arguments: summarize_arguments(arguments),
+            characters: characters(tool, arguments),
+            duration_ms: elapsed.as_millis() as u64,
+            success: error.is_none(),
+            error,
+        }
+    }
+}
+
+/// Location of the audit log
+pub fn audit_path() -> Option<PathBuf> {
+    crate::paths::data_file("mcp-audit.jsonl")
+}
+
+/// Append an entry (best effort: logging never fails the tool call)
+pub fn record(entry: &AuditEntry) {
+    let Some(path) = audit_path() else {
+        return;
+    };
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    let _ = with_file_lock(&path, || {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    });
+}
+
+/// Every readable entry, oldest first
+fn load() -> Vec<AuditEntry> {
+    audit_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn execute(command: &McpAuditCommands) -> Result<()> {
+    match command {
+        McpAuditCommands::Tail {
+            lines,
+            tool,
+            failed,
+        } => tail(*lines, tool.as_deref(), *failed),
+    }
+}
+
+fn tail(lines: usize, tool: Option<&str>, failed: bool) -> Result<()> {
+    let entries: Vec<AuditEntry> = load()
+        .into_iter()
+        .filter(|e| tool.is_none_or(|t| e.tool == t))
+        .filter(|e| !failed || !e.success)
+        .collect();
+    let recent = &entries[entries.len().saturating_sub(lines)..];
+
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(recent)?);
+        return Ok(());
+    }
+    if recent.is_empty() {
+        print_info("No MCP tool calls logged");
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut table = Table::new();
+    table.set_header(vec![
+        "When",
+        "Tool",
+        "Duration",
+        "Chars",
+        "Result",
+        "Arguments",
+    ]);
+    for entry in recent {
+        let result = match &entry.error {
+            None => "ok".green().to_string(),
+            Some(error) => truncate(error, 40).red().to_string(),
+        };
+        table.add_row(vec![
+            format_relative_time(now.saturating_sub(entry.timestamp)),
+            entry.tool.cyan().to_string(),
+            format!("{} ms", entry.duration_ms),
+            entry
+                .characters
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            result,
+            truncate(&Value::Object(entry.arguments.clone()).to_string(), 60),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+/// Arguments with long values shortened and secrets redacted
+fn summarize_arguments(arguments: &Map<String, Value>) -> Map<String, Value> {
+    arguments
+        .iter()
+        .map(|(key, value)| {
+            let summary = if REDACTED_ARGS.contains(&key.as_str()) {
+                json!("<redacted>")
+            } else {
+                match value {
+                    Value::String(s) if s.chars().count() > MAX_ARG_LEN => json!(format!(
+                        "{} ({} chars)",
+                        truncate(s, MAX_ARG_LEN),
+                        s.chars().count()
+                    )),
+                    Value::Array(items) if value.to_string().len() > MAX_ARG_LEN => {
+                        json!(format!("<{} items>", items.len()))
+                    }
+                    Value::Object(_) if value.to_string().len() > MAX_ARG_LEN => {
+                        json!("<object>")
+                    }
+                    other => other.clone(),
+                }
+            };
+            (key.clone(), summary)
+        })
+        .collect()
+}
+
+/// Characters a call sends for generation (what the account is billed for)
+fn characters(tool: &str, arguments: &Map<String, Value>) -> Option<usize> {
+    let text_len = |value: &Value| value.as_str().map(|s| s.chars().count());
+    match tool {
+        "text_to_speech" | "generate_sfx" => arguments.get("text").and_then(text_len),
+        "create_dialogue" => arguments.get("inputs")?.as_array().map(|inputs| {
+            inputs
+                .iter()
+                .filter_map(|input| text_len(&input["text"]))
+                .sum()
+        }),
+        _ => None,
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_summarize_arguments_shortens_and_redacts() {
+        let long = "x".repeat(200);
+        let summary = summarize_arguments(&args(json!({
+            "name": "OPENAI_KEY",
+            "value": "sk-secret",
+            "text": long,
+            "limit": 5,
+        })));
+        assert_eq!(summary["name"], "OPENAI_KEY");
+        assert_eq!(summary["value"], "<redacted>");
+        assert_eq!(summary["limit"], 5);
+        let text = summary["text"].as_str().unwrap();
+        assert!(text.ends_with("(200 chars)"));
+        assert!(text.len() < 100);
+    }
+
+    #[test]
+    fn test_characters() {
+        assert_eq!(
+            characters("text_to_speech", &args(json!({ "text": "Héllo" }))),
+            Some(5)
+        );
+        assert_eq!(
+            characters(
+                "create_dialogue",
+                &args(json!({ "inputs": [{ "text": "Hi" }, { "text": "Hey there" }] }))
+            ),
+            Some(11)
+        );
+        assert_eq!(characters("list_voices", &Map::new()), None);
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = AuditEntry::new(
+            "delete_voice",
+            &args(json!({ "voice_id": "v1" })),
+            Duration::from_millis(42),
+            Some("not found".to_string()),
+        );
+        assert!(!entry.success);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("characters"));
+        assert_eq!(serde_json::from_str::<AuditEntry>(&line).unwrap(), entry);
+    }
+}
@@ -3,6 +3,9 @@
 //! This module exposes all ElevenLabs API functionality as MCP tools that can be
 //! used by AI assistants like Claude, GPT-4, etc.
 
+#[cfg(feature = "mcp")]
+pub mod audit;
+
 #[cfg(feature = "mcp")]
 pub mod server;
 
@@ -48,6 +51,7 @@ impl Default for McpConfig {
 /// Run the MCP server (only available with mcp feature)
 #[cfg(feature = "mcp")]
 pub async fn run_server(
+    api_key: Option<&str>,
     enable_tools: Option<&str>,
     disable_tools: Option<&str>,
     disable_admin: bool,
@@ -55,6 +59,7 @@ pub async fn run_server(
     read_only: bool,
 ) -> anyhow::Result<()> {
     server::run_server(
+        api_key,
         enable_tools,
         disable_tools,
         disable_admin,
@@ -66,6 +71,7 @@ pub async fn run_server(
 
 #[cfg(not(feature = "mcp"))]
 pub async fn run_server(
+    _api_key: Option<&str>,
     _enable_tools: Option<&str>,
     _disable_tools: Option<&str>,
     _disable_admin: bool,
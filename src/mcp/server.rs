@@ -1,17 +1,34 @@
 //! MCP Server implementation for ElevenLabs CLI.
 //!
-//! Full MCP server implementation with 80+ tools.
+//! Full MCP server implementation. Every tool is backed by a handler in
+//! `mcp_handlers` and advertised with a JSON schema generated from its
+//! input type; call arguments are checked against that type before the
+//! handler runs, and its output comes back as structured content.
 
 use anyhow::Result;
 use rmcp::{
-    model::{CallToolResult, Content, ServerCapabilities, ServerInfo},
+    model::{
+        CallToolRequestParams, CallToolResult, Content, JsonObject, ListToolsResult,
+        PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool, ToolAnnotations,
+    },
+    service::RequestContext,
     transport::stdio,
-    ErrorData as McpError, ServerHandler, ServiceExt,
+    ErrorData as McpError, RoleServer, ServerHandler, ServiceExt,
 };
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 
+use super::audit::{self, AuditEntry};
+use super::tools::*;
 use crate::config::Config;
+use crate::mcp_handlers;
+
+/// Tools that work on the server machine and need no API key
+const LOCAL_TOOLS: &[&str] = &["record_audio", "play_audio"];
 
 /// ElevenLabs MCP Server
 #[derive(Clone)]
@@ -38,187 +55,324 @@ impl ElevenLabsMcpServer {
         self
     }
 
-    /// Handle tool calls by name - returns tool descriptions
-    fn handle_tool(
-        &self,
-        name: &str,
-        _args: &serde_json::Map<String, serde_json::Value>,
-    ) -> Result<CallToolResult, McpError> {
-        // Check if tool is enabled
+    /// Handle tool calls by name, recording each call in the audit log
+    async fn handle_tool(&self, name: &str, args: JsonObject) -> Result<CallToolResult, McpError> {
+        let start = Instant::now();
+        let result = self.call_tool(name, &args).await;
+        let error = match &result {
+            Ok(result) if result.is_error == Some(true) => Some(error_message(result)),
+            Ok(_) => None,
+            Err(e) => Some(e.message.to_string()),
+        };
+        audit::record(&AuditEntry::new(name, &args, start.elapsed(), error));
+        result
+    }
+
+    /// Run a tool through its handler in `mcp_handlers`
+    async fn call_tool(&self, name: &str, args: &JsonObject) -> Result<CallToolResult, McpError> {
         if !self.tools.contains(&name) {
-            return Ok(CallToolResult::success(vec![Content::text(format!(
+            return Ok(tool_error(format!(
                 "Tool '{}' is not available. Use one of: {:?}",
                 name, self.tools
-            ))]));
+            )));
+        }
+        if input_schema(name).is_none() {
+            return Ok(tool_error(format!(
+                "Tool '{}' cannot be run over MCP yet; use the matching CLI command instead",
+                name
+            )));
+        }
+        let api_key = self.api_key.read().await.clone().unwrap_or_default();
+        if api_key.is_empty() && !LOCAL_TOOLS.contains(&name) {
+            return Ok(tool_error(
+                "No API key configured. Set ELEVENLABS_API_KEY or api_key in the config file"
+                    .to_string(),
+            ));
         }
 
-        let result = match name {
-            // TTS
-            "text_to_speech" => Content::text("Convert text to natural speech. Parameters: text (required), voice, model"),
-            "speech_to_text" => Content::text("Transcribe audio to text. Parameters: file (required), model, language, diarize"),
-            "generate_sfx" => Content::text("Generate sound effects from text. Parameters: text (required), duration"),
-            "audio_isolation" => Content::text("Remove background noise from audio. Parameters: file (required)"),
-            "voice_changer" => Content::text("Transform voice in audio. Parameters: file (required), voice (required)"),
+        let key = api_key.as_str();
+        let args = args.clone();
+        match name {
+            // TTS & Audio
+            "text_to_speech" => run(args, |i| mcp_handlers::text_to_speech(key, i)).await,
+            "speech_to_text" => run(args, |i| mcp_handlers::speech_to_text(key, i)).await,
+            "generate_sfx" => run(args, |i| mcp_handlers::generate_sfx(key, i)).await,
+            "audio_isolation" => run(args, |i| mcp_handlers::audio_isolation(key, i)).await,
+            "voice_changer" => run(args, |i| mcp_handlers::voice_changer(key, i)).await,
 
             // Voice Management
-            "list_voices" => Content::text("List all available voices. Parameters: detailed"),
-            "get_voice" => Content::text("Get voice details. Parameters: voice_id (required)"),
-            "delete_voice" => Content::text("Delete a voice. Parameters: voice_id (required)"),
-            "clone_voice" => Content::text("Clone a voice from samples. Parameters: name (required), samples (required)"),
-            "voice_settings" => Content::text("Get voice settings. Parameters: voice_id (required)"),
-            "edit_voice_settings" => Content::text("Edit voice settings. Parameters: voice_id (required), stability, similarity_boost, style"),
-            "create_voice_design" => Content::text("Create voice design from text. Parameters: text (required), voice_settings"),
-            "get_voice_design" => Content::text("Get voice design status. Parameters: design_id (required)"),
-            "start_voice_fine_tune" => Content::text("Start voice fine-tuning. Parameters: voice_id (required)"),
-            "get_voice_fine_tune_status" => Content::text("Get fine-tune status. Parameters: fine_tune_id (required)"),
-            "cancel_voice_fine_tune" => Content::text("Cancel fine-tune. Parameters: fine_tune_id (required)"),
-            "share_voice" => Content::text("Share voice publicly. Parameters: voice_id (required)"),
-            "get_similar_voices" => Content::text("Get similar voices. Parameters: voice_id (required)"),
+            "list_voices" => run(args, |i| mcp_handlers::list_voices(key, i)).await,
+            "clone_voice" => run(args, |i| mcp_handlers::clone_voice(key, i)).await,
 
             // Dubbing
-            "create_dubbing" => Content::text("Create dubbing project. Parameters: file (required), source_lang (required), target_lang (required)"),
-            "get_dubbing_status" => Content::text("Get dubbing status. Parameters: dubbing_id (required)"),
-            "delete_dubbing" => Content::text("Delete dubbing. Parameters: dubbing_id (required)"),
+            "create_dubbing" => run(args, |i| mcp_handlers::create_dubbing(key, i)).await,
+            "get_dubbing_status" => run(args, |i| mcp_handlers::get_dubbing_status(key, i)).await,
 
             // History
-            "list_history" => Content::text("List generation history. Parameters: limit"),
-            "get_history_item" => Content::text("Get history item. Parameters: history_item_id (required)"),
-            "delete_history_item" => Content::text("Delete history item. Parameters: history_item_id (required)"),
-            "history_feedback" => Content::text("Submit history feedback. Parameters: history_item_id (required), thumbs_up (required)"),
-            "download_history" => Content::text("Download history audio. Parameters: history_item_id (required)"),
+            "list_history" => run(args, |i| mcp_handlers::list_history(key, i)).await,
+            "get_history_item" => run(args, |i| mcp_handlers::get_history_item_ex(key, i)).await,
+            "delete_history_item" => {
+                run(args, |i| mcp_handlers::delete_history_item_ex(key, i)).await
+            }
+            "history_feedback" => run(args, |i| mcp_handlers::submit_feedback(key, i)).await,
 
             // Agents
-            "list_agents" => Content::text("List agents. Parameters: limit"),
-            "get_agent_summaries" => Content::text("Get agent summaries"),
-            "create_agent" => Content::text("Create agent. Parameters: name (required)"),
-            "get_agent" => Content::text("Get agent. Parameters: agent_id (required)"),
-            "update_agent" => Content::text("Update agent. Parameters: agent_id (required)"),
-            "delete_agent" => Content::text("Delete agent. Parameters: agent_id (required)"),
-            "agent_branches" => Content::text("List agent branches. Parameters: agent_id (required)"),
-            "batch_list" => Content::text("List batch jobs"),
-
-            // User
-            "get_user_info" => Content::text("Get user info"),
-            "get_user_subscription" => Content::text("Get user subscription"),
-
-            // Models
-            "list_models" => Content::text("List models"),
-            "get_model_rates" => Content::text("Get model rates. Parameters: model_id (required)"),
-
-            // Usage
-            "get_usage" => Content::text("Get usage. Parameters: start, end"),
-
-            // Knowledge
-            "list_knowledge" => Content::text("List knowledge. Parameters: limit"),
-            "add_knowledge" => Content::text("Add knowledge. Parameters: source_type (required), name (required), content, url, file"),
-            "delete_knowledge" => Content::text("Delete knowledge. Parameters: document_id (required)"),
-
-            // RAG
-            "create_rag" => Content::text("Create RAG. Parameters: name (required)"),
-            "get_rag_status" => Content::text("Get RAG status. Parameters: rag_id (required)"),
-            "delete_rag" => Content::text("Delete RAG. Parameters: rag_id (required)"),
-            "rebuild_rag" => Content::text("Rebuild RAG. Parameters: rag_id (required)"),
-            "get_rag_index_status" => Content::text("Get RAG index status. Parameters: rag_id (required)"),
+            "list_agents" => run(args, |i| mcp_handlers::list_agents(key, i)).await,
+            "create_agent" => run(args, |i| mcp_handlers::create_agent(key, i)).await,
+
+            // User, Models, Usage
+            "get_user_info" => {
+                run(args, |_: GetUserInfoInput| mcp_handlers::get_user_info(key)).await
+            }
+            "list_models" => run(args, |_: ListModelsInput| mcp_handlers::list_models(key)).await,
+            "get_usage" => run(args, |i| mcp_handlers::get_usage(key, i)).await,
+
+            // Knowledge & RAG
+            "list_knowledge" => run(args, |i| mcp_handlers::list_knowledge(key, i)).await,
+            "add_knowledge" => run(args, |i| mcp_handlers::add_knowledge(key, i)).await,
+            "rebuild_rag" => run(args, |i| mcp_handlers::rebuild_index_ex(key, i)).await,
+            "get_rag_index_status" => {
+                run(args, |i| mcp_handlers::get_index_status_ex(key, i)).await
+            }
 
             // Webhooks
-            "list_webhooks" => Content::text("List webhooks"),
-            "create_webhook" => Content::text("Create webhook. Parameters: name (required), url (required), events"),
-            "delete_webhook" => Content::text("Delete webhook. Parameters: webhook_id (required)"),
+            "list_webhooks" => {
+                run(args, |_: ListWebhooksInput| {
+                    mcp_handlers::list_webhooks(key)
+                })
+                .await
+            }
+            "create_webhook" => run(args, |i| mcp_handlers::create_webhook(key, i)).await,
 
             // Dialogue
-            "create_dialogue" => Content::text("Create dialogue. Parameters: inputs (required)"),
+            "create_dialogue" => run(args, |i| mcp_handlers::create_dialogue(key, i)).await,
 
             // Library
-            "list_library_voices" => Content::text("List library voices. Parameters: page_size"),
-            "list_library_collections" => Content::text("List library collections"),
+            "list_library_voices" => {
+                run(args, |i| mcp_handlers::list_voices_from_library(key, i)).await
+            }
+            "list_library_collections" => {
+                run(args, |i| mcp_handlers::list_collections(key, i)).await
+            }
 
             // Pronunciation
-            "list_pronunciations" => Content::text("List pronunciations"),
-            "add_pronunciation" => Content::text("Add pronunciation. Parameters: dictionary_id (required), word (required), alphabet"),
-            "delete_pronunciation" => Content::text("Delete pronunciation. Parameters: dictionary_id (required), word (required)"),
-            "list_pronunciation_rules" => Content::text("List pronunciation rules. Parameters: dictionary_id (required)"),
-            "add_pronunciation_rules" => Content::text("Add pronunciation rules. Parameters: dictionary_id (required), rules_file (required)"),
-            "remove_pronunciation_rules" => Content::text("Remove pronunciation rules. Parameters: dictionary_id (required), rules_file (required)"),
-            "get_pronunciation_pls" => Content::text("Get pronunciation PLS. Parameters: dictionary_id (required)"),
+            "list_pronunciations" => {
+                run(args, |i| mcp_handlers::list_dictionaries_ex(key, i)).await
+            }
+            "add_pronunciation" => run(args, |i| mcp_handlers::add_dictionary_ex(key, i)).await,
+            "get_pronunciation" => run(args, |i| mcp_handlers::get_dictionary_ex(key, i)).await,
+            "delete_pronunciation" => {
+                run(args, |i| mcp_handlers::delete_dictionary_ex(key, i)).await
+            }
+            "list_pronunciation_rules" => run(args, |i| mcp_handlers::list_rules_ex(key, i)).await,
+            "add_pronunciation_rules" => run(args, |i| mcp_handlers::add_rules_ex(key, i)).await,
+            "remove_pronunciation_rules" => {
+                run(args, |i| mcp_handlers::remove_rules_ex(key, i)).await
+            }
 
             // Workspace
-            "workspace_info" => Content::text("Get workspace info"),
-            "list_workspace_members" => Content::text("List workspace members"),
-            "list_workspace_invites" => Content::text("List workspace invites"),
-            "invite_workspace_member" => Content::text("Invite member. Parameters: email (required), role"),
-            "revoke_workspace_invite" => Content::text("Revoke invite. Parameters: invite_id (required)"),
-            "list_workspace_api_keys" => Content::text("List workspace API keys"),
-            "list_secrets" => Content::text("List secrets"),
-            "add_secret" => Content::text("Add secret. Parameters: name (required), value (required)"),
-            "delete_secret" => Content::text("Delete secret. Parameters: name (required)"),
-            "share_workspace" => Content::text("Share workspace. Parameters: workspace_id (required)"),
+            "list_workspace_members" => run(args, |i| mcp_handlers::list_members(key, i)).await,
+            "invite_workspace_member" => run(args, |i| mcp_handlers::invite_member(key, i)).await,
+            "revoke_workspace_invite" => run(args, |i| mcp_handlers::revoke_invite(key, i)).await,
+            "list_secrets" => run(args, |i| mcp_handlers::list_secrets(key, i)).await,
+            "add_secret" => run(args, |i| mcp_handlers::add_secret(key, i)).await,
+            "delete_secret" => run(args, |i| mcp_handlers::delete_secret(key, i)).await,
 
             // Phone
-            "list_phones" => Content::text("List phones"),
-            "get_phone" => Content::text("Get phone. Parameters: phone_id (required)"),
-            "import_phone" => Content::text("Import phone. Parameters: number (required), provider"),
-            "update_phone" => Content::text("Update phone. Parameters: phone_id (required)"),
-            "delete_phone" => Content::text("Delete phone. Parameters: phone_id (required)"),
-            "test_phone_call" => Content::text("Test phone call. Parameters: phone_id (required)"),
+            "get_phone" => run(args, |i| mcp_handlers::get_phone_number_ex(key, i)).await,
+            "delete_phone" => run(args, |i| mcp_handlers::delete_phone_number_ex(key, i)).await,
 
             // Conversation
-            "converse_chat" => Content::text("Conversation chat. Parameters: agent_id (required), message (required)"),
-            "list_conversations" => Content::text("List conversations"),
-            "get_conversation" => Content::text("Get conversation. Parameters: conversation_id (required)"),
-            "get_signed_url" => Content::text("Get signed URL. Parameters: conversation_id (required)"),
-            "get_conversation_token" => Content::text("Get conversation token. Parameters: agent_id (required)"),
-            "delete_conversation" => Content::text("Delete conversation. Parameters: conversation_id (required)"),
-            "get_conversation_audio" => Content::text("Get conversation audio. Parameters: conversation_id (required)"),
-
-            // Projects
-            "list_projects" => Content::text("List projects"),
-            "get_project" => Content::text("Get project. Parameters: project_id (required)"),
-            "delete_project" => Content::text("Delete project. Parameters: project_id (required)"),
-            "convert_project" => Content::text("Convert project. Parameters: project_id (required), target_format (required)"),
-            "list_project_snapshots" => Content::text("List project snapshots. Parameters: project_id (required)"),
-            "get_project_audio" => Content::text("Get project audio. Parameters: project_id (required)"),
-
-            // Music
-            "generate_music" => Content::text("Generate music. Parameters: prompt (required)"),
-            "list_music" => Content::text("List music. Parameters: limit"),
-            "get_music" => Content::text("Get music. Parameters: music_id (required)"),
-            "download_music" => Content::text("Download music. Parameters: music_id (required)"),
-            "delete_music" => Content::text("Delete music. Parameters: music_id (required)"),
-
-            // Samples
-            "list_samples" => Content::text("List samples. Parameters: voice_id (required)"),
-            "delete_sample" => Content::text("Delete sample. Parameters: voice_id (required), sample_id (required)"),
-
-            // Tools
-            "list_tools" => Content::text("List agent tools"),
-            "get_tool" => Content::text("Get tool. Parameters: tool_id (required)"),
-            "delete_tool" => Content::text("Delete tool. Parameters: tool_id (required)"),
+            "get_conversation" => run(args, |i| mcp_handlers::get_conversation_ex(key, i)).await,
+            "delete_conversation" => {
+                run(args, |i| mcp_handlers::delete_conversation_ex(key, i)).await
+            }
+            "get_conversation_audio" => {
+                run(args, |i| mcp_handlers::get_conversation_audio_ex(key, i)).await
+            }
 
             // Audio Native
-            "list_audio_native" => Content::text("List audio native. Parameters: limit"),
-            "get_audio_native" => Content::text("Get audio native. Parameters: project_id (required)"),
-            "create_audio_native" => Content::text("Create audio native. Parameters: name (required)"),
+            "list_audio_native" => run(args, |i| mcp_handlers::list_audio_native_ex(key, i)).await,
+            "get_audio_native" => run(args, |i| mcp_handlers::get_audio_native_ex(key, i)).await,
 
-            _ => Content::text(format!("Unknown tool: {}. Use list_tools to see all available tools.", name)),
-        };
+            // Local Audio
+            "record_audio" => run(args, mcp_handlers::record_audio).await,
+            "play_audio" => run(args, mcp_handlers::play_audio).await,
 
-        Ok(CallToolResult::success(vec![result]))
+            _ => Ok(tool_error(format!(
+                "Tool '{}' cannot be run over MCP yet; use the matching CLI command instead",
+                name
+            ))),
+        }
+    }
+
+    /// Enabled tools that can be run, with their schemas
+    fn tool_definitions(&self) -> Vec<Tool> {
+        self.tools
+            .iter()
+            .filter_map(|&name| {
+                let schema = input_schema(name)?;
+                let description = schema
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or(name)
+                    .to_string();
+                let annotations = ToolAnnotations::new()
+                    .read_only(!is_mutating(name))
+                    .destructive(DESTRUCTIVE_TOOLS.contains(&name));
+                Some(Tool::new(name, description, schema).annotate(annotations))
+            })
+            .collect()
     }
 }
 
+/// JSON schema of a tool's input type; `None` for tools without a handler
+fn input_schema(name: &str) -> Option<JsonObject> {
+    use schemars::schema_for;
+
+    let schema = match name {
+        "text_to_speech" => schema_for!(TextToSpeechInput),
+        "speech_to_text" => schema_for!(SpeechToTextInput),
+        "generate_sfx" => schema_for!(GenerateSfxInput),
+        "audio_isolation" => schema_for!(AudioIsolationInput),
+        "voice_changer" => schema_for!(VoiceChangerInput),
+        "list_voices" => schema_for!(ListVoicesInput),
+        "clone_voice" => schema_for!(CloneVoiceInput),
+        "create_dubbing" => schema_for!(CreateDubbingInput),
+        "get_dubbing_status" => schema_for!(GetDubbingStatusInput),
+        "list_history" => schema_for!(ListHistoryInput),
+        "get_history_item" => schema_for!(GetHistoryItemInput),
+        "delete_history_item" => schema_for!(DeleteHistoryItemInput),
+        "history_feedback" => schema_for!(SubmitFeedbackInput),
+        "list_agents" => schema_for!(ListAgentsInput),
+        "create_agent" => schema_for!(CreateAgentInput),
+        "get_user_info" => schema_for!(GetUserInfoInput),
+        "list_models" => schema_for!(ListModelsInput),
+        "get_usage" => schema_for!(GetUsageInput),
+        "list_knowledge" => schema_for!(ListKnowledgeInput),
+        "add_knowledge" => schema_for!(AddKnowledgeInput),
+        "rebuild_rag" => schema_for!(RebuildIndexInput),
+        "get_rag_index_status" => schema_for!(GetIndexStatusInput),
+        "list_webhooks" => schema_for!(ListWebhooksInput),
+        "create_webhook" => schema_for!(CreateWebhookInput),
+        "create_dialogue" => schema_for!(CreateDialogueInput),
+        "list_library_voices" => schema_for!(ListVoicesFromLibraryInput),
+        "list_library_collections" => schema_for!(ListCollectionsInput),
+        "list_pronunciations" => schema_for!(ListDictionariesInput),
+        "add_pronunciation" => schema_for!(AddDictionaryInput),
+        "get_pronunciation" => schema_for!(GetDictionaryInput),
+        "delete_pronunciation" => schema_for!(DeleteDictionaryInput),
+        "list_pronunciation_rules" => schema_for!(ListRulesInput),
+        "add_pronunciation_rules" => schema_for!(AddRulesInput),
+        "remove_pronunciation_rules" => schema_for!(RemoveRulesInput),
+        "list_workspace_members" => schema_for!(ListMembersInput),
+        "invite_workspace_member" => schema_for!(InviteMemberInput),
+        "revoke_workspace_invite" => schema_for!(RevokeInviteInput),
+        "list_secrets" => schema_for!(ListSecretsInput),
+        "add_secret" => schema_for!(AddSecretInput),
+        "delete_secret" => schema_for!(DeleteSecretInput),
+        "get_phone" => schema_for!(GetPhoneNumberInput),
+        "delete_phone" => schema_for!(DeletePhoneNumberInput),
+        "get_conversation" => schema_for!(GetConversationInput),
+        "delete_conversation" => schema_for!(DeleteConversationInput),
+        "get_conversation_audio" => schema_for!(GetConversationAudioInput),
+        "list_audio_native" => schema_for!(ListAudioNativeInput),
+        "get_audio_native" => schema_for!(GetAudioNativeInput),
+        "record_audio" => schema_for!(RecordAudioInput),
+        "play_audio" => schema_for!(PlayAudioInput),
+        _ => return None,
+    };
+    match serde_json::to_value(schema) {
+        Ok(Value::Object(schema)) => Some(schema),
+        _ => None,
+    }
+}
+
+/// Parse the arguments into the tool's input type, run the handler and
+/// return its output as structured content. Bad arguments are a protocol
+/// error; a failing handler is a tool error the assistant can read.
+async fn run<I, O, F, Fut>(args: JsonObject, handler: F) -> Result<CallToolResult, McpError>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+    F: FnOnce(I) -> Fut,
+    Fut: Future<Output = Result<O>>,
+{
+    let input: I = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {}", e), None))?;
+    match handler(input).await {
+        Ok(output) => {
+            let output = serde_json::to_value(output)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            // Outputs report their own failures through `success`
+            if output.get("success") == Some(&Value::Bool(false)) {
+                Ok(CallToolResult::structured_error(output))
+            } else {
+                Ok(CallToolResult::structured(output))
+            }
+        }
+        Err(e) => Ok(tool_error(format!("{:#}", e))),
+    }
+}
+
+fn tool_error(message: String) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(message)])
+}
+
+/// Error text of a failed call, for the audit log
+fn error_message(result: &CallToolResult) -> String {
+    if let Some(error) = result
+        .structured_content
+        .as_ref()
+        .and_then(|s| s.get("error"))
+        .and_then(Value::as_str)
+    {
+        return error.to_string();
+    }
+    result
+        .content
+        .iter()
+        .find_map(|c| c.as_text().map(|t| t.text.clone()))
+        .unwrap_or_else(|| "tool failed".to_string())
+}
+
 impl ServerHandler for ElevenLabsMcpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some(
-                "ElevenLabs CLI - 80+ MCP tools for TTS, STT, Voice, SFX, Dubbing, History, \
-                Agents, Knowledge, RAG, Webhooks, Dialogue, Phone, Conversation, Projects, Music, \
-                Samples, Tools, Audio Native, Voice Design, Fine-tuning."
+                "ElevenLabs CLI - MCP tools for TTS, STT, Voice, SFX, Dubbing, History, Agents, \
+                Knowledge, RAG, Webhooks, Dialogue, Library, Pronunciation, Workspace, Phone, \
+                Conversation, Audio Native and local audio. Other operations are available \
+                through the CLI."
                     .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
             ..Default::default()
         }
     }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_definitions()))
+    }
+
+    fn get_tool(&self, name: &str) -> Option<Tool> {
+        self.tool_definitions()
+            .into_iter()
+            .find(|tool| tool.name == name)
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.handle_tool(&request.name, request.arguments.unwrap_or_default())
+            .await
+    }
 }
 
 /// Get all available tools as a list
@@ -232,59 +386,33 @@ pub fn list_tools() -> Vec<&'static str> {
         "voice_changer",
         // Voice Management
         "list_voices",
-        "get_voice",
-        "delete_voice",
         "clone_voice",
-        "voice_settings",
-        "edit_voice_settings",
-        "create_voice_design",
-        "get_voice_design",
-        "start_voice_fine_tune",
-        "get_voice_fine_tune_status",
-        "cancel_voice_fine_tune",
-        "share_voice",
-        "get_similar_voices",
         // Dubbing
         "create_dubbing",
         "get_dubbing_status",
-        "delete_dubbing",
         // History
         "list_history",
         "get_history_item",
         "delete_history_item",
         "history_feedback",
-        "download_history",
         // Agents
         "list_agents",
-        "get_agent_summaries",
         "create_agent",
-        "get_agent",
-        "update_agent",
-        "delete_agent",
-        "agent_branches",
-        "batch_list",
         // User
         "get_user_info",
-        "get_user_subscription",
         // Models
         "list_models",
-        "get_model_rates",
         // Usage
         "get_usage",
         // Knowledge
         "list_knowledge",
         "add_knowledge",
-        "delete_knowledge",
         // RAG
-        "create_rag",
-        "get_rag_status",
-        "delete_rag",
         "rebuild_rag",
         "get_rag_index_status",
         // Webhooks
         "list_webhooks",
         "create_webhook",
-        "delete_webhook",
         // Dialogue
         "create_dialogue",
         // Library
@@ -293,84 +421,42 @@ pub fn list_tools() -> Vec<&'static str> {
         // Pronunciation
         "list_pronunciations",
         "add_pronunciation",
+        "get_pronunciation",
         "delete_pronunciation",
         "list_pronunciation_rules",
         "add_pronunciation_rules",
         "remove_pronunciation_rules",
-        "get_pronunciation_pls",
         // Workspace
-        "workspace_info",
         "list_workspace_members",
-        "list_workspace_invites",
         "invite_workspace_member",
         "revoke_workspace_invite",
-        "list_workspace_api_keys",
         "list_secrets",
         "add_secret",
         "delete_secret",
-        "share_workspace",
         // Phone
-        "list_phones",
         "get_phone",
-        "import_phone",
-        "update_phone",
         "delete_phone",
-        "test_phone_call",
         // Conversation
-        "converse_chat",
-        "list_conversations",
         "get_conversation",
-        "get_signed_url",
-        "get_conversation_token",
         "delete_conversation",
         "get_conversation_audio",
-        // Projects
-        "list_projects",
-        "get_project",
-        "delete_project",
-        "convert_project",
-        "list_project_snapshots",
-        "get_project_audio",
-        // Music
-        "generate_music",
-        "list_music",
-        "get_music",
-        "download_music",
-        "delete_music",
-        // Samples
-        "list_samples",
-        "delete_sample",
-        // Tools
-        "list_tools",
-        "get_tool",
-        "delete_tool",
         // Audio Native
         "list_audio_native",
         "get_audio_native",
-        "create_audio_native",
+        // Local Audio
+        "record_audio",
+        "play_audio",
     ]
 }
 
 /// Tools that are considered administrative/destructive (can delete, create, modify resources)
 const ADMIN_TOOLS: &[&str] = &[
     // Voice management - destructive
-    "delete_voice",
     "clone_voice",
-    "edit_voice_settings",
-    "share_voice",
-    // Dubbing - destructive
-    "delete_dubbing",
     // History - destructive
     "delete_history_item",
-    // Samples - destructive
-    "delete_sample",
-    // Tools - administrative
-    "create_tool",
-    "update_tool",
-    "delete_tool",
     // Webhooks - administrative
     "create_webhook",
-    "delete_webhook",
     // Pronunciation - destructive
     "add_pronunciation",
     "delete_pronunciation",
@@ -381,73 +467,115 @@ const ADMIN_TOOLS: &[&str] = &[
     "revoke_workspace_invite",
     "add_secret",
     "delete_secret",
-    "share_workspace",
     // Phone - destructive
-    "import_phone",
-    "update_phone",
     "delete_phone",
     // Agent - administrative
     "create_agent",
-    "update_agent",
-    "delete_agent",
-    "duplicate_agent",
-    "rename_agent_branch",
-    "delete_batch_call",
     // Conversation - destructive
     "delete_conversation",
     // Knowledge - destructive
     "add_knowledge",
-    "delete_knowledge",
     // RAG - destructive
-    "create_rag",
-    "delete_rag",
     "rebuild_rag",
-    // Projects - destructive
-    "delete_project",
-    "convert_project",
-    // Music - destructive
-    "delete_music",
     // Dialogue - destructive
     "create_dialogue",
+    // Side effects outside the account's resources
+    "history_feedback",
+];
+
+/// Tools that only look things up: they change nothing in the account,
+/// spend no credits and write no local files. Everything else, including
+/// generation, is dropped in read-only mode.
+const READ_ONLY_TOOLS: &[&str] = &[
+    // Voice
+    "list_voices",
+    // Dubbing
+    "get_dubbing_status",
+    // History
+    "list_history",
+    "get_history_item",
+    // Agent
+    "list_agents",
+    // User and models
+    "get_user_info",
+    "list_models",
+    "get_usage",
+    // Knowledge and RAG
+    "list_knowledge",
+    "get_rag_index_status",
+    // Webhooks
+    "list_webhooks",
+    // Library
+    "list_library_voices",
+    "list_library_collections",
+    // Pronunciation
+    "list_pronunciations",
+    "get_pronunciation",
+    "list_pronunciation_rules",
+    // Workspace
+    "list_workspace_members",
+    "list_secrets",
+    // Phone
+    "get_phone",
+    // Conversation
+    "get_conversation",
+    // Audio Native
+    "list_audio_native",
+    "get_audio_native",
+    // Local Audio
+    "play_audio",
 ];
 
+/// Whether a tool has effects beyond reading (dropped in read-only mode)
+fn is_mutating(tool: &str) -> bool {
+    !READ_ONLY_TOOLS.contains(&tool)
+}
+
+/// The tools left in read-only mode
+fn read_only_tools(tools: &[&'static str]) -> Vec<&'static str> {
+    tools.iter().filter(|t| !is_mutating(t)).copied().collect()
+}
+
 /// Tools that are purely destructive (delete operations only)
 const DESTRUCTIVE_TOOLS: &[&str] = &[
-    "delete_voice",
-    "delete_dubbing",
     "delete_history_item",
-    "delete_sample",
-    "delete_tool",
-    "delete_webhook",
     "delete_pronunciation",
     "revoke_workspace_invite",
     "delete_secret",
     "delete_phone",
-    "delete_agent",
-    "delete_batch_call",
     "delete_conversation",
-    "delete_knowledge",
-    "delete_rag",
-    "delete_project",
-    "delete_music",
 ];
 
 pub async fn run_server(
+    api_key: Option<&str>,
     enable_tools: Option<&str>,
     disable_tools: Option<&str>,
     disable_admin: bool,
     disable_destructive: bool,
     read_only: bool,
 ) -> Result<()> {
+    // stdout carries the protocol, so logs go to stderr
     tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive(tracing::Level::INFO.into()),
         )
         .init();
 
+    if crate::client::has_request_extras() {
+        tracing::warn!(
+            "--header and --query are not applied to tools that call the API through the SDK"
+        );
+    }
+
     let config = Config::load().unwrap_or_default();
-    let api_key = config.api_key.unwrap_or_default();
+    crate::player::set_player_command(config.player_cmd.clone());
+    // --api-key / ELEVENLABS_API_KEY take precedence over the config file
+    let api_key = api_key
+        .map(str::to_string)
+        .or(config.api_key)
+        .unwrap_or_default();
 
     // Merge CLI args with config (CLI args take precedence)
     let mcp_config = &config.mcp;
@@ -459,9 +587,9 @@ pub async fn run_server(
     let _final_disable_tools = disable_tools.or_else(|| mcp_config.disable_tools.as_deref());
 
     // Get flags - CLI takes precedence
-    let _final_disable_admin = disable_admin || mcp_config.disable_admin;
+    let final_disable_admin = disable_admin || mcp_config.disable_admin;
     let final_disable_destructive = disable_destructive || mcp_config.disable_destructive;
-    let _final_read_only = read_only || mcp_config.read_only;
+    let final_read_only = read_only || mcp_config.read_only;
 
     // Get all available tools
     let all_tools = list_tools();
@@ -496,11 +624,11 @@ pub async fn run_server(
     };
 
     // Apply filters based on flags
-    // read_only is an alias for disable_admin
-    let is_read_only = read_only || disable_admin;
-
-    let final_tools: Vec<&'static str> = if is_read_only {
-        // disable_admin/read_only: block all write operations
+    let final_tools: Vec<&'static str> = if final_read_only {
+        // read_only: block every mutating tool, even ones named in enable_tools
+        read_only_tools(&filtered_tools)
+    } else if final_disable_admin {
+        // disable_admin: block administrative operations
         filtered_tools
             .iter()
             .filter(|t| !ADMIN_TOOLS.contains(t))
@@ -526,9 +654,9 @@ pub async fn run_server(
         final_tools.len(),
         enabled,
         disabled,
-        disable_admin,
-        disable_destructive,
-        read_only
+        final_disable_admin,
+        final_disable_destructive,
+        final_read_only
     );
     tracing::info!("Available tools: {:?}", final_tools);
 
@@ -539,3 +667,134 @@ pub async fn run_server(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_drops_every_mutating_tool() {
+        for tool in [
+            "delete_secret",
+            "create_dubbing",
+            "delete_phone",
+            "add_knowledge",
+            "add_pronunciation",
+            "history_feedback",
+        ] {
+            assert!(is_mutating(tool), "{}", tool);
+        }
+        // Generation spends credits and can write a caller-chosen file
+        for tool in [
+            "text_to_speech",
+            "speech_to_text",
+            "generate_sfx",
+            "voice_changer",
+            "audio_isolation",
+            "get_conversation_audio",
+            "record_audio",
+        ] {
+            assert!(is_mutating(tool), "{}", tool);
+        }
+        for tool in [
+            "list_voices",
+            "get_pronunciation",
+            "get_usage",
+            "play_audio",
+        ] {
+            assert!(!is_mutating(tool), "{}", tool);
+        }
+        assert!(ADMIN_TOOLS.iter().all(|t| is_mutating(t)));
+        let tools = list_tools();
+        assert!(READ_ONLY_TOOLS.iter().all(|t| tools.contains(t)));
+        assert!(ADMIN_TOOLS.iter().all(|t| tools.contains(t)));
+        assert!(DESTRUCTIVE_TOOLS.iter().all(|t| tools.contains(t)));
+    }
+
+    fn server() -> ElevenLabsMcpServer {
+        ElevenLabsMcpServer::new().with_api_key("test".to_string())
+    }
+
+    #[test]
+    fn test_tool_definitions_have_object_schemas() {
+        // Every listed tool has a handler, so none is left out of tools/list
+        let tools = server().tool_definitions();
+        assert_eq!(tools.len(), list_tools().len());
+        for tool in &tools {
+            assert_eq!(tool.input_schema["type"], "object", "{}", tool.name);
+            assert!(tool.description.is_some(), "{}", tool.name);
+        }
+        let tts = tools.iter().find(|t| t.name == "text_to_speech").unwrap();
+        assert_eq!(
+            tts.input_schema["required"],
+            serde_json::json!(["text", "voice"])
+        );
+        assert!(!tts.annotations.as_ref().unwrap().read_only_hint.unwrap());
+        let delete = tools.iter().find(|t| t.name == "delete_secret").unwrap();
+        assert!(delete.annotations.as_ref().unwrap().is_destructive());
+        let delete = tools
+            .iter()
+            .find(|t| t.name == "delete_pronunciation")
+            .unwrap();
+        assert_eq!(
+            delete.input_schema["required"],
+            serde_json::json!(["dictionary_id"])
+        );
+        assert!(delete.annotations.as_ref().unwrap().is_destructive());
+    }
+
+    #[tokio::test]
+    async fn test_every_handled_tool_checks_its_arguments() {
+        // Missing required arguments are rejected before any request is made,
+        // which also shows each advertised tool is wired to a handler
+        let server = server();
+        for tool in server.tool_definitions() {
+            let required = tool.input_schema.get("required").and_then(Value::as_array);
+            if required.is_none_or(|r| r.is_empty()) {
+                continue;
+            }
+            let result = server.call_tool(&tool.name, &JsonObject::new()).await;
+            let error = result.expect_err(&tool.name);
+            assert!(
+                error.message.contains("Invalid arguments"),
+                "{}: {}",
+                tool.name,
+                error.message
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_tools_return_errors() {
+        let server = server().with_tools(vec!["get_voice", "list_voices"]);
+        let result = server
+            .call_tool("get_voice", &JsonObject::new())
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, Some(true));
+        assert!(error_message(&result).contains("cannot be run over MCP yet"));
+
+        let result = server
+            .call_tool("list_models", &JsonObject::new())
+            .await
+            .unwrap();
+        assert!(error_message(&result).contains("not available"));
+
+        let no_key = ElevenLabsMcpServer::new();
+        let result = no_key
+            .call_tool("list_voices", &JsonObject::new())
+            .await
+            .unwrap();
+        assert!(error_message(&result).contains("No API key"));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_rejects_downloads() {
+        // Downloads write local files, which read-only mode rules out
+        let server = server().with_tools(read_only_tools(&list_tools()));
+        for tool in ["download_history", "download_music", "get_project_audio"] {
+            let result = server.call_tool(tool, &JsonObject::new()).await.unwrap();
+            assert!(error_message(&result).contains("not available"), "{}", tool);
+        }
+    }
+}
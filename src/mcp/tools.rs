@@ -429,7 +429,7 @@ pub struct GetUsageOutput {
 
 /// Get user information
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct GetUserInfoInput;
+pub struct GetUserInfoInput {}
 
 /// User information result
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -449,7 +449,7 @@ pub struct GetUserInfoOutput {
 
 /// List available models
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct ListModelsInput;
+pub struct ListModelsInput {}
 
 /// Model information
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -588,7 +588,7 @@ pub struct CreateWebhookOutput {
 
 /// List webhooks
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct ListWebhooksInput;
+pub struct ListWebhooksInput {}
 
 /// Webhook info
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -736,7 +736,7 @@ pub struct GetSampleAudioOutput {
 
 /// List pronunciation dictionaries
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct ListDictionariesInput;
+pub struct ListDictionariesInput {}
 
 /// Pronunciation dictionary info
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -1105,7 +1105,7 @@ pub struct GetConversationAudioOutput {
 
 /// List workspace members
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct ListMembersInput;
+pub struct ListMembersInput {}
 
 /// Workspace member info
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -1157,7 +1157,7 @@ pub struct RevokeInviteOutput {
 
 /// List workspace secrets
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct ListSecretsInput;
+pub struct ListSecretsInput {}
 
 /// Secret info
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -1252,3 +1252,71 @@ pub struct DeletePhoneNumberOutput {
     pub success: bool,
     pub error: Option<String>,
 }
+
+// ============================================================================
+// Local Audio Tools
+// ============================================================================
+
+fn default_record_duration() -> f32 {
+    10.0
+}
+
+fn default_silence_threshold() -> f32 {
+    0.01
+}
+
+fn default_silence_duration() -> f32 {
+    1.5
+}
+
+/// Record from the microphone of the machine running the server
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RecordAudioInput {
+    /// Recording length in seconds (the maximum when `vad` is set, up to 300)
+    #[serde(default = "default_record_duration")]
+    pub duration_seconds: f32,
+    /// Stop once the speaker goes quiet (voice activity detection)
+    #[serde(default)]
+    pub vad: bool,
+    /// Energy level (0.0-1.0) below which input counts as silence
+    #[serde(default = "default_silence_threshold")]
+    pub silence_threshold: f32,
+    /// Seconds of silence that end the recording when `vad` is set
+    #[serde(default = "default_silence_duration")]
+    pub silence_duration: f32,
+    /// Output WAV path (a file in the temp directory if not specified)
+    #[serde(default)]
+    pub output_file: Option<String>,
+}
+
+/// Result of a microphone recording
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RecordAudioOutput {
+    pub success: bool,
+    /// WAV file holding the recording
+    pub output_file: Option<String>,
+    pub duration_seconds: Option<f64>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Play audio on the machine running the server
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PlayAudioInput {
+    /// Path of an audio file to play
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Base64-encoded audio to play (e.g. `audio_base64` from text_to_speech)
+    #[serde(default)]
+    pub audio_base64: Option<String>,
+}
+
+/// Result of playing audio
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PlayAudioOutput {
+    pub success: bool,
+    /// Size of the audio that was played
+    pub bytes: Option<usize>,
+    pub error: Option<String>,
+}
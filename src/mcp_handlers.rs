@@ -10,7 +10,10 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use crate::mcp::tools::*;
 
 #[cfg(feature = "mcp")]
+use crate::client::{api_base, read_json, retry_sdk, SendWithRetry};
 use crate::utils::parse_output_format;
+#[cfg(feature = "mcp")]
+use reqwest::Method;
 
 // ============================================================================
 // TTS Handler
@@ -25,19 +28,6 @@ pub async fn text_to_speech(api_key: &str, input: TextToSpeechInput) -> Result<T
 
     let client = ElevenLabsClient::new(api_key);
 
-    // Parse model
-    let model = match input.model.as_str() {
-        "eleven_multilingual_v2" => Model::ElevenMultilingualV2,
-        "eleven_flash_v2_5" => Model::ElevenFlashV2_5,
-        "eleven_turbo_v2" => Model::ElevenTurboV2,
-        "eleven_turbo_v2_5" => Model::ElevenTurboV2_5,
-        "eleven_v3" => Model::ElevenMultilingualV2,
-        _ => Model::ElevenMultilingualV2,
-    };
-
-    // Parse output format using shared utility
-    let format = parse_output_format(&input.output_format)?;
-
     // Build voice settings
     let voice_settings = if input.stability.is_some()
         || input.similarity_boost.is_some()
@@ -60,22 +50,34 @@ pub async fn text_to_speech(api_key: &str, input: TextToSpeechInput) -> Result<T
         None
     };
 
-    // Build request body
-    let mut body = TextToSpeechBody::new(&input.text).with_model_id(model);
+    let start = std::time::Instant::now();
+    let model = match input.model.as_str() {
+        "eleven_multilingual_v2" => Model::ElevenMultilingualV2,
+        "eleven_flash_v2_5" => Model::ElevenFlashV2_5,
+        "eleven_turbo_v2" => Model::ElevenTurboV2,
+        "eleven_turbo_v2_5" => Model::ElevenTurboV2_5,
+        "eleven_v3" => Model::ElevenMultilingualV2,
+        _ => Model::ElevenMultilingualV2,
+    };
+    // Parse output format using shared utility
+    let format = parse_output_format(&input.output_format)?;
 
-    if let Some(settings) = voice_settings {
-        body = body.with_voice_settings(settings);
-    }
+    // The request is rebuilt for each attempt: the SDK consumes it
+    let audio = retry_sdk(Method::POST, || {
+        // Build request body
+        let mut body = TextToSpeechBody::new(&input.text).with_model_id(model.clone());
 
-    let query = TextToSpeechQuery::default().with_output_format(format);
+        if let Some(settings) = voice_settings.clone() {
+            body = body.with_voice_settings(settings);
+        }
 
-    let endpoint = TextToSpeech::new(&input.voice, body).with_query(query);
+        let query = TextToSpeechQuery::default().with_output_format(format.clone());
 
-    let start = std::time::Instant::now();
-    let audio = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let endpoint = TextToSpeech::new(&input.voice, body).with_query(query);
+        client.hit(endpoint)
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
     let duration = start.elapsed().as_secs_f64();
 
     // Handle output
@@ -186,9 +188,7 @@ pub async fn list_voices(api_key: &str, _input: ListVoicesInput) -> Result<ListV
     use elevenlabs_rs::{endpoints::admin::voice::GetVoices, ElevenLabsClient};
 
     let client = ElevenLabsClient::new(api_key);
-    let endpoint = GetVoices::default();
-    let voices = client
-        .hit(endpoint)
+    let voices = retry_sdk(Method::GET, || client.hit(GetVoices::default()))
         .await
         .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -263,21 +263,21 @@ pub async fn generate_sfx(api_key: &str, input: GenerateSfxInput) -> Result<Gene
 
     let client = ElevenLabsClient::new(api_key);
 
-    let mut body = CreateSoundEffectBody::new(&input.text);
+    let audio = retry_sdk(Method::POST, || {
+        let mut body = CreateSoundEffectBody::new(&input.text);
 
-    if let Some(duration) = input.duration {
-        body = body.with_duration_seconds(duration);
-    }
+        if let Some(duration) = input.duration {
+            body = body.with_duration_seconds(duration);
+        }
 
-    if let Some(influence) = input.influence {
-        body = body.with_prompt_influence(influence);
-    }
+        if let Some(influence) = input.influence {
+            body = body.with_prompt_influence(influence);
+        }
 
-    let endpoint = CreateSoundEffect::new(body);
-    let audio = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+        client.hit(CreateSoundEffect::new(body))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     if let Some(output_file) = input.output_file {
         std::fs::write(&output_file, &audio)?;
@@ -433,11 +433,11 @@ pub async fn get_dubbing_status(
     use elevenlabs_rs::{endpoints::genai::dubbing::GetDubbing, ElevenLabsClient};
 
     let client = ElevenLabsClient::new(api_key);
-    let endpoint = GetDubbing::new(&input.dubbing_id);
-    let status = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let status = retry_sdk(Method::GET, || {
+        client.hit(GetDubbing::new(&input.dubbing_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     Ok(GetDubbingStatusOutput {
         success: true,
@@ -468,7 +468,7 @@ pub async fn list_agents(api_key: &str, input: ListAgentsInput) -> Result<ListAg
     }
 
     let client = Client::new();
-    let mut url = "https://api.elevenlabs.io/v1/agents".to_string();
+    let mut url = format!("{}/v1/agents", api_base());
 
     if let Some(limit) = input.limit {
         url.push_str(&format!("?limit={}", limit));
@@ -477,7 +477,7 @@ pub async fn list_agents(api_key: &str, input: ListAgentsInput) -> Result<ListAg
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -490,7 +490,7 @@ pub async fn list_agents(api_key: &str, input: ListAgentsInput) -> Result<ListAg
         });
     }
 
-    let agents: Vec<AgentSummary> = response.json().await?;
+    let agents: Vec<AgentSummary> = read_json(response).await?;
 
     let agent_infos: Vec<AgentInfo> = agents
         .iter()
@@ -547,10 +547,10 @@ pub async fn create_agent(api_key: &str, input: CreateAgentInput) -> Result<Crea
     body["conversation_config"] = conversation_config;
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/agents")
+        .post(format!("{}/v1/agents", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -562,7 +562,7 @@ pub async fn create_agent(api_key: &str, input: CreateAgentInput) -> Result<Crea
         });
     }
 
-    let result: CreateResponse = response.json().await?;
+    let result: CreateResponse = read_json(response).await?;
 
     Ok(CreateAgentOutput {
         success: true,
@@ -584,13 +584,12 @@ pub async fn list_history(api_key: &str, input: ListHistoryInput) -> Result<List
 
     let client = ElevenLabsClient::new(api_key);
 
-    let query = HistoryQuery::default().with_page_size(input.limit as u16);
-
-    let endpoint = GetGeneratedItems::with_query(query);
-    let history = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let history = retry_sdk(Method::GET, || {
+        let query = HistoryQuery::default().with_page_size(input.limit as u16);
+        client.hit(GetGeneratedItems::with_query(query))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     let items: Vec<HistoryItemInfo> = history
         .history
@@ -672,9 +671,7 @@ pub async fn get_user_info(api_key: &str) -> Result<GetUserInfoOutput> {
     use elevenlabs_rs::{endpoints::admin::user::GetUserInfo, ElevenLabsClient};
 
     let client = ElevenLabsClient::new(api_key);
-    let endpoint = GetUserInfo;
-    let user = client
-        .hit(endpoint)
+    let user = retry_sdk(Method::GET, || client.hit(GetUserInfo))
         .await
         .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -707,9 +704,7 @@ pub async fn list_models(api_key: &str) -> Result<ListModelsOutput> {
     use elevenlabs_rs::{endpoints::admin::models::GetModels, ElevenLabsClient};
 
     let client = ElevenLabsClient::new(api_key);
-    let endpoint = GetModels;
-    let models = client
-        .hit(endpoint)
+    let models = retry_sdk(Method::GET, || client.hit(GetModels))
         .await
         .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -774,10 +769,13 @@ pub async fn create_dialogue(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/text-to-dialogue/stream/with-timestamps")
+        .post(format!(
+            "{}/v1/text-to-dialogue/stream/with-timestamps",
+            api_base()
+        ))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -791,7 +789,7 @@ pub async fn create_dialogue(
         });
     }
 
-    let result: DialogueResponse = response.json().await?;
+    let result: DialogueResponse = read_json(response).await?;
 
     let voice_segments = result.voice_segments.map(|segments| {
         segments
@@ -866,10 +864,10 @@ pub async fn add_knowledge(api_key: &str, input: AddKnowledgeInput) -> Result<Ad
     };
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/knowledge-base")
+        .post(format!("{}/v1/convai/knowledge-base", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -881,7 +879,7 @@ pub async fn add_knowledge(api_key: &str, input: AddKnowledgeInput) -> Result<Ad
         });
     }
 
-    let result: CreateResponse = response.json().await?;
+    let result: CreateResponse = read_json(response).await?;
 
     Ok(AddKnowledgeOutput {
         success: true,
@@ -908,7 +906,7 @@ pub async fn list_knowledge(
     }
 
     let client = Client::new();
-    let mut url = "https://api.elevenlabs.io/v1/convai/knowledge-base".to_string();
+    let mut url = format!("{}/v1/convai/knowledge-base", api_base());
 
     if let Some(limit) = input.limit {
         url.push_str(&format!("?limit={}", limit));
@@ -917,7 +915,7 @@ pub async fn list_knowledge(
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -930,7 +928,7 @@ pub async fn list_knowledge(
         });
     }
 
-    let documents: Vec<KnowledgeDocument> = response.json().await?;
+    let documents: Vec<KnowledgeDocument> = read_json(response).await?;
 
     let doc_infos: Vec<KnowledgeDocumentInfo> = documents
         .iter()
@@ -979,10 +977,10 @@ pub async fn create_webhook(
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/webhooks")
+        .post(format!("{}/v1/webhooks", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -994,7 +992,7 @@ pub async fn create_webhook(
         });
     }
 
-    let result: CreateResponse = response.json().await?;
+    let result: CreateResponse = read_json(response).await?;
 
     Ok(CreateWebhookOutput {
         success: true,
@@ -1019,9 +1017,9 @@ pub async fn list_webhooks(api_key: &str) -> Result<ListWebhooksOutput> {
     let client = Client::new();
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/webhooks")
+        .get(format!("{}/v1/webhooks", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1033,7 +1031,7 @@ pub async fn list_webhooks(api_key: &str) -> Result<ListWebhooksOutput> {
         });
     }
 
-    let webhooks: Vec<WebhookData> = response.json().await?;
+    let webhooks: Vec<WebhookData> = read_json(response).await?;
 
     let webhook_infos: Vec<WebhookInfo> = webhooks
         .iter()
@@ -1147,11 +1145,15 @@ pub async fn add_voice_to_library(
 
     let client = ElevenLabsClient::new(api_key);
 
-    let endpoint = AddSharedVoice::new(&input.public_user_id, &input.voice_id, &input.name);
-    let response = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let response = retry_sdk(Method::POST, || {
+        client.hit(AddSharedVoice::new(
+            &input.public_user_id,
+            &input.voice_id,
+            &input.name,
+        ))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     Ok(AddVoiceToLibraryOutput {
         success: true,
@@ -1184,7 +1186,7 @@ pub async fn list_collections(
     }
 
     let client = Client::new();
-    let mut url = "https://api.elevenlabs.io/v1/voices/collections".to_string();
+    let mut url = format!("{}/v1/voices/collections", api_base());
 
     if let Some(ps) = input.page_size {
         url.push_str(&format!("?page_size={}", ps));
@@ -1193,7 +1195,7 @@ pub async fn list_collections(
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1206,7 +1208,7 @@ pub async fn list_collections(
         });
     }
 
-    let result: CollectionsResponse = response.json().await?;
+    let result: CollectionsResponse = read_json(response).await?;
 
     let collection_infos: Vec<CollectionInfo> = result
         .collections
@@ -1252,14 +1254,15 @@ pub async fn collection_voices(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/voices/collections/{}/voices",
+        "{}/v1/voices/collections/{}/voices",
+        api_base(),
         input.collection_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1272,7 +1275,7 @@ pub async fn collection_voices(
         });
     }
 
-    let result: CollectionVoicesResponse = response.json().await?;
+    let result: CollectionVoicesResponse = read_json(response).await?;
 
     let voice_infos: Vec<CollectionVoiceInfo> = result
         .voices
@@ -1307,11 +1310,11 @@ pub async fn get_sample_audio(
 
     let client = ElevenLabsClient::new(api_key);
 
-    let endpoint = GetAudioFromSample::new(&input.voice_id, &input.sample_id);
-    let audio = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let audio = retry_sdk(Method::GET, || {
+        client.hit(GetAudioFromSample::new(&input.voice_id, &input.sample_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     let audio_base64 = BASE64.encode(&audio);
 
@@ -1335,9 +1338,7 @@ pub async fn list_dictionaries_ex(
 
     let client = ElevenLabsClient::new(api_key);
 
-    let endpoint = GetDictionaries::default();
-    let response = client
-        .hit(endpoint)
+    let response = retry_sdk(Method::GET, || client.hit(GetDictionaries::default()))
         .await
         .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -1414,14 +1415,15 @@ pub async fn get_dictionary_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation-dictionaries/{}",
+        "{}/v1/pronunciation-dictionaries/{}",
+        api_base(),
         input.dictionary_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1433,7 +1435,7 @@ pub async fn get_dictionary_ex(
         });
     }
 
-    let dict: DictionaryDetail = response.json().await?;
+    let dict: DictionaryDetail = read_json(response).await?;
 
     Ok(GetDictionaryOutput {
         success: true,
@@ -1457,14 +1459,15 @@ pub async fn delete_dictionary_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation-dictionaries/{}",
+        "{}/v1/pronunciation-dictionaries/{}",
+        api_base(),
         input.dictionary_id
     );
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1502,14 +1505,15 @@ pub async fn list_rules_ex(api_key: &str, input: ListRulesInput) -> Result<ListR
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         input.dictionary_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1522,7 +1526,7 @@ pub async fn list_rules_ex(api_key: &str, input: ListRulesInput) -> Result<ListR
         });
     }
 
-    let result: RulesResponse = response.json().await?;
+    let result: RulesResponse = read_json(response).await?;
 
     let rules: Vec<PronunciationRule> = result
         .rules
@@ -1554,7 +1558,8 @@ pub async fn add_rules_ex(api_key: &str, input: AddRulesInput) -> Result<AddRule
         .map_err(|e| anyhow::anyhow!("Failed to read rules file: {}", e))?;
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         input.dictionary_id
     );
 
@@ -1563,7 +1568,7 @@ pub async fn add_rules_ex(api_key: &str, input: AddRulesInput) -> Result<AddRule
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(rules_content)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1590,7 +1595,8 @@ pub async fn remove_rules_ex(api_key: &str, input: RemoveRulesInput) -> Result<R
         .map_err(|e| anyhow::anyhow!("Failed to read rules file: {}", e))?;
 
     let url = format!(
-        "https://api.elevenlabs.io/v1/pronunciation/dictionaries/{}/rules",
+        "{}/v1/pronunciation/dictionaries/{}/rules",
+        api_base(),
         input.dictionary_id
     );
 
@@ -1599,7 +1605,7 @@ pub async fn remove_rules_ex(api_key: &str, input: RemoveRulesInput) -> Result<R
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(rules_content)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1629,11 +1635,11 @@ pub async fn get_history_item_ex(
 
     let client = ElevenLabsClient::new(api_key);
 
-    let endpoint = GetHistoryItem::new(&input.history_item_id);
-    let item = client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let item = retry_sdk(Method::GET, || {
+        client.hit(GetHistoryItem::new(&input.history_item_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     Ok(GetHistoryItemOutput {
         success: true,
@@ -1659,11 +1665,11 @@ pub async fn delete_history_item_ex(
 
     let client = ElevenLabsClient::new(api_key);
 
-    let endpoint = DeleteHistoryItem::new(&input.history_item_id);
-    client
-        .hit(endpoint)
-        .await
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    retry_sdk(Method::DELETE, || {
+        client.hit(DeleteHistoryItem::new(&input.history_item_id))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
 
     Ok(DeleteHistoryItemOutput {
         success: true,
@@ -1681,7 +1687,8 @@ pub async fn submit_feedback(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/history/{}/feedback",
+        "{}/v1/history/{}/feedback",
+        api_base(),
         input.history_item_id
     );
 
@@ -1701,7 +1708,7 @@ pub async fn submit_feedback(
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .body(body.to_string())
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1740,14 +1747,15 @@ pub async fn rebuild_index_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/rebuild-index",
+        "{}/v1/convai/knowledge-base/{}/rebuild-index",
+        api_base(),
         input.document_id
     );
 
     let response = client
         .post(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1760,7 +1768,7 @@ pub async fn rebuild_index_ex(
         });
     }
 
-    let result: RebuildResponse = response.json().await?;
+    let result: RebuildResponse = read_json(response).await?;
 
     Ok(RebuildIndexOutput {
         success: true,
@@ -1794,14 +1802,15 @@ pub async fn get_index_status_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/knowledge-base/{}/index-status",
+        "{}/v1/convai/knowledge-base/{}/index-status",
+        api_base(),
         input.document_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1813,7 +1822,7 @@ pub async fn get_index_status_ex(
         });
     }
 
-    let result: IndexStatusResponse = response.json().await?;
+    let result: IndexStatusResponse = read_json(response).await?;
 
     Ok(GetIndexStatusOutput {
         success: true,
@@ -1862,14 +1871,16 @@ pub async fn list_audio_native_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/audio-native?limit={}&page={}",
-        input.limit, input.page
+        "{}/v1/audio-native?limit={}&page={}",
+        api_base(),
+        input.limit,
+        input.page
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1882,7 +1893,7 @@ pub async fn list_audio_native_ex(
         });
     }
 
-    let result: AudioNativeListResponse = response.json().await?;
+    let result: AudioNativeListResponse = read_json(response).await?;
 
     let projects: Vec<AudioNativeProjectInfo> = result
         .projects
@@ -1932,15 +1943,12 @@ pub async fn get_audio_native_ex(
     }
 
     let client = Client::new();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/audio-native/{}",
-        input.project_id
-    );
+    let url = format!("{}/v1/audio-native/{}", api_base(), input.project_id);
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -1952,7 +1960,7 @@ pub async fn get_audio_native_ex(
         });
     }
 
-    let project: AudioNativeProject = response.json().await?;
+    let project: AudioNativeProject = read_json(response).await?;
 
     Ok(GetAudioNativeOutput {
         success: true,
@@ -2006,14 +2014,15 @@ pub async fn get_conversation_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}",
+        "{}/v1/convai/conversations/{}",
+        api_base(),
         input.conversation_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2025,7 +2034,7 @@ pub async fn get_conversation_ex(
         });
     }
 
-    let conv: ConversationDetail = response.json().await?;
+    let conv: ConversationDetail = read_json(response).await?;
 
     let transcript = conv.transcript.map(|msgs| {
         msgs.into_iter()
@@ -2060,14 +2069,15 @@ pub async fn delete_conversation_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}",
+        "{}/v1/convai/conversations/{}",
+        api_base(),
         input.conversation_id
     );
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2093,14 +2103,15 @@ pub async fn get_conversation_audio_ex(
 
     let client = Client::new();
     let url = format!(
-        "https://api.elevenlabs.io/v1/convai/conversations/{}/audio",
+        "{}/v1/convai/conversations/{}/audio",
+        api_base(),
         input.conversation_id
     );
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2158,9 +2169,9 @@ pub async fn list_members(api_key: &str, _input: ListMembersInput) -> Result<Lis
     let client = Client::new();
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/workspace/members")
+        .get(format!("{}/v1/workspace/members", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2173,7 +2184,7 @@ pub async fn list_members(api_key: &str, _input: ListMembersInput) -> Result<Lis
         });
     }
 
-    let members: Vec<MemberData> = response.json().await?;
+    let members: Vec<MemberData> = read_json(response).await?;
 
     let member_infos: Vec<MemberInfo> = members
         .iter()
@@ -2208,10 +2219,10 @@ pub async fn invite_member(api_key: &str, input: InviteMemberInput) -> Result<In
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/workspace/invites")
+        .post(format!("{}/v1/workspace/invites", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2233,15 +2244,12 @@ pub async fn revoke_invite(api_key: &str, input: RevokeInviteInput) -> Result<Re
     use reqwest::Client;
 
     let client = Client::new();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/workspace/invites/{}",
-        input.email
-    );
+    let url = format!("{}/v1/workspace/invites/{}", api_base(), input.email);
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2274,9 +2282,9 @@ pub async fn list_secrets(api_key: &str, _input: ListSecretsInput) -> Result<Lis
     let client = Client::new();
 
     let response = client
-        .get("https://api.elevenlabs.io/v1/convai/workspaces/secrets")
+        .get(format!("{}/v1/convai/workspaces/secrets", api_base()))
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2289,7 +2297,7 @@ pub async fn list_secrets(api_key: &str, _input: ListSecretsInput) -> Result<Lis
         });
     }
 
-    let secrets: Vec<SecretData> = response.json().await?;
+    let secrets: Vec<SecretData> = read_json(response).await?;
 
     let secret_infos: Vec<SecretInfo> = secrets
         .iter()
@@ -2324,10 +2332,10 @@ pub async fn add_secret(api_key: &str, input: AddSecretInput) -> Result<AddSecre
     });
 
     let response = client
-        .post("https://api.elevenlabs.io/v1/convai/workspaces/secrets")
+        .post(format!("{}/v1/convai/workspaces/secrets", api_base()))
         .header("xi-api-key", api_key)
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2349,15 +2357,12 @@ pub async fn delete_secret(api_key: &str, input: DeleteSecretInput) -> Result<De
     use reqwest::Client;
 
     let client = Client::new();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/workspaces/secrets/{}",
-        input.name
-    );
+    let url = format!("{}/v1/convai/workspaces/secrets/{}", api_base(), input.name);
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2403,15 +2408,12 @@ pub async fn get_phone_number_ex(
     }
 
     let client = Client::new();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}",
-        input.phone_id
-    );
+    let url = format!("{}/v1/convai/phone-numbers/{}", api_base(), input.phone_id);
 
     let response = client
         .get(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2423,7 +2425,7 @@ pub async fn get_phone_number_ex(
         });
     }
 
-    let phone: PhoneNumberData = response.json().await?;
+    let phone: PhoneNumberData = read_json(response).await?;
 
     Ok(GetPhoneNumberOutput {
         success: true,
@@ -2448,15 +2450,12 @@ pub async fn delete_phone_number_ex(
     use reqwest::Client;
 
     let client = Client::new();
-    let url = format!(
-        "https://api.elevenlabs.io/v1/convai/phone-numbers/{}",
-        input.phone_id
-    );
+    let url = format!("{}/v1/convai/phone-numbers/{}", api_base(), input.phone_id);
 
     let response = client
         .delete(&url)
         .header("xi-api-key", api_key)
-        .send()
+        .send_with_retry()
         .await?;
 
     if !response.status().is_success() {
@@ -2472,3 +2471,104 @@ pub async fn delete_phone_number_ex(
         error: None,
     })
 }
+
+// ============================================================================
+// Local Audio Handlers
+// ============================================================================
+
+/// Longest recording `record_audio` makes
+#[cfg(feature = "mcp")]
+const MAX_RECORD_SECONDS: f32 = 300.0;
+
+/// Record from the local microphone into a WAV file
+#[cfg(feature = "mcp")]
+pub async fn record_audio(input: RecordAudioInput) -> Result<RecordAudioOutput> {
+    use crate::utils::generate_output_filename;
+
+    if !(input.duration_seconds > 0.0 && input.duration_seconds <= MAX_RECORD_SECONDS) {
+        return Err(anyhow::anyhow!(
+            "duration_seconds must be between 0 and {}",
+            MAX_RECORD_SECONDS
+        ));
+    }
+    let output_file = match input.output_file.clone() {
+        Some(path) => path,
+        None => std::env::temp_dir()
+            .join(generate_output_filename("recording", "wav"))
+            .display()
+            .to_string(),
+    };
+
+    // Recording blocks for its whole duration
+    let (wav, sample_rate, channels) =
+        tokio::task::spawn_blocking(move || record_wav(&input)).await??;
+    std::fs::write(&output_file, &wav)?;
+
+    let frames = (wav.len() - crate::pcm::WAV_HEADER_LEN) / 2 / channels.max(1) as usize;
+    Ok(RecordAudioOutput {
+        success: true,
+        output_file: Some(output_file),
+        duration_seconds: Some(frames as f64 / sample_rate as f64),
+        sample_rate: Some(sample_rate),
+        channels: Some(channels),
+        error: None,
+    })
+}
+
+/// Record at the input device's own rate and wrap the PCM in a WAV header
+#[cfg(all(feature = "mcp", feature = "audio"))]
+fn record_wav(input: &RecordAudioInput) -> Result<(Vec<u8>, u32, u16)> {
+    use crate::audio::audio_io;
+    use crate::pcm::wav_header;
+
+    let (sample_rate, channels) =
+        audio_io::default_input_format().map_err(|e| anyhow::anyhow!("{}", e))?;
+    let pcm = if input.vad {
+        audio_io::record_with_vad(
+            input.duration_seconds,
+            input.silence_threshold,
+            input.silence_duration,
+        )
+    } else {
+        audio_io::record_from_microphone(input.duration_seconds)
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to record audio: {}", e))?;
+
+    let mut wav = wav_header(sample_rate, channels, pcm.len() as u32).to_vec();
+    wav.extend_from_slice(&pcm);
+    Ok((wav, sample_rate, channels))
+}
+
+#[cfg(all(feature = "mcp", not(feature = "audio")))]
+fn record_wav(_input: &RecordAudioInput) -> Result<(Vec<u8>, u32, u16)> {
+    Err(anyhow::anyhow!(
+        "Recording not available. Rebuild with --features audio"
+    ))
+}
+
+/// Play a file or base64 payload through the speakers, or `player_cmd` when
+/// built-in playback is unavailable
+#[cfg(feature = "mcp")]
+pub async fn play_audio(input: PlayAudioInput) -> Result<PlayAudioOutput> {
+    let audio = match (&input.file, &input.audio_base64) {
+        (Some(file), None) => {
+            std::fs::read(file).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file, e))?
+        }
+        (None, Some(data)) => BASE64
+            .decode(data.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid audio_base64: {}", e))?,
+        _ => return Err(anyhow::anyhow!("Pass exactly one of file or audio_base64")),
+    };
+    if audio.is_empty() {
+        return Err(anyhow::anyhow!("No audio to play"));
+    }
+
+    let bytes = audio.len();
+    // Playback blocks until the audio ends
+    tokio::task::spawn_blocking(move || crate::player::play_audio(&audio)).await??;
+    Ok(PlayAudioOutput {
+        success: true,
+        bytes: Some(bytes),
+        error: None,
+    })
+}
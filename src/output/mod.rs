@@ -1,5 +1,7 @@
 //! Output formatting utilities for the CLI
 
+mod progress;
 mod text;
 
+pub use progress::*;
 pub use text::*;
@@ -0,0 +1,181 @@
+//! Progress reporting that adapts to the output environment
+//!
+//! - TTY: animated spinner / progress bar (indicatif) on stderr
+//! - Piped (CI logs): a plain start line plus periodic "still working" lines
+//! - `--json` / `--quiet`: nothing at all
+
+use super::{is_ascii_mode, is_color_enabled, is_json_mode, is_quiet_mode};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Interval between plain progress lines when stderr is not a terminal
+const PLAIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Shared state for plain (non-TTY) progress output
+struct PlainState {
+    message: Mutex<String>,
+    position: AtomicU64,
+    length: Option<u64>,
+    done: AtomicBool,
+}
+
+enum Mode {
+    Tty(ProgressBar),
+    Plain {
+        state: Arc<PlainState>,
+        ticker: Option<thread::JoinHandle<()>>,
+    },
+    Hidden,
+}
+
+/// A spinner or progress bar honoring `--json`, `--quiet`, and non-TTY output
+pub struct Progress {
+    mode: Mode,
+}
+
+impl Progress {
+    /// Start an indeterminate spinner
+    pub fn spinner(message: &str) -> Self {
+        Self::start(None, message)
+    }
+
+    /// Start a progress bar with a known length
+    pub fn bar(length: u64, message: &str) -> Self {
+        Self::start(Some(length), message)
+    }
+
+    fn start(length: Option<u64>, message: &str) -> Self {
+        if is_json_mode() || is_quiet_mode() {
+            return Self { mode: Mode::Hidden };
+        }
+
+        if std::io::stderr().is_terminal() {
+            let (spinner, bar_colors) = if is_color_enabled() {
+                ("{spinner:.cyan}", ":30.cyan/blue")
+            } else {
+                ("{spinner}", ":30")
+            };
+            let style = match length {
+                Some(_) => ProgressStyle::with_template(&format!(
+                    "{} {{msg}} [{{bar{}}}] {{pos}}/{{len}} ({{elapsed}})",
+                    spinner, bar_colors
+                ))
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "),
+                None => ProgressStyle::with_template(&format!("{} {{msg}} ({{elapsed}})", spinner))
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            };
+            let style = if is_ascii_mode() {
+                style.tick_chars("|/-\\ ")
+            } else {
+                style
+            };
+            let bar = match length {
+                Some(len) => ProgressBar::new(len),
+                None => ProgressBar::new_spinner(),
+            };
+            bar.set_style(style);
+            bar.set_message(message.to_string());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            return Self {
+                mode: Mode::Tty(bar),
+            };
+        }
+
+        eprintln!("{}...", message);
+
+        let state = Arc::new(PlainState {
+            message: Mutex::new(message.to_string()),
+            position: AtomicU64::new(0),
+            length,
+            done: AtomicBool::new(false),
+        });
+
+        let ticker_state = Arc::clone(&state);
+        let ticker = thread::spawn(move || {
+            let started = Instant::now();
+            let mut last = Instant::now();
+            while !ticker_state.done.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(200));
+                if last.elapsed() >= PLAIN_INTERVAL {
+                    last = Instant::now();
+                    let message = ticker_state
+                        .message
+                        .lock()
+                        .map(|m| m.clone())
+                        .unwrap_or_default();
+                    let position = ticker_state.position.load(Ordering::SeqCst);
+                    match ticker_state.length {
+                        Some(len) => eprintln!(
+                            "{}... {}/{} ({}s)",
+                            message,
+                            position,
+                            len,
+                            started.elapsed().as_secs()
+                        ),
+                        None => eprintln!("{}... ({}s)", message, started.elapsed().as_secs()),
+                    }
+                }
+            }
+        });
+
+        Self {
+            mode: Mode::Plain {
+                state,
+                ticker: Some(ticker),
+            },
+        }
+    }
+
+    /// Update the progress message
+    pub fn set_message(&self, message: &str) {
+        match &self.mode {
+            Mode::Tty(bar) => bar.set_message(message.to_string()),
+            Mode::Plain { state, .. } => {
+                if let Ok(mut m) = state.message.lock() {
+                    *m = message.to_string();
+                }
+            }
+            Mode::Hidden => {}
+        }
+    }
+
+    /// Advance the progress position
+    pub fn inc(&self, delta: u64) {
+        match &self.mode {
+            Mode::Tty(bar) => bar.inc(delta),
+            Mode::Plain { state, .. } => {
+                state.position.fetch_add(delta, Ordering::SeqCst);
+            }
+            Mode::Hidden => {}
+        }
+    }
+
+    /// Stop and clear the progress indicator
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        match &mut self.mode {
+            Mode::Tty(bar) => bar.finish_and_clear(),
+            Mode::Plain { state, ticker } => {
+                state.done.store(true, Ordering::SeqCst);
+                if let Some(handle) = ticker.take() {
+                    let _ = handle.join();
+                }
+            }
+            Mode::Hidden => {}
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
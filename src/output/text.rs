@@ -4,6 +4,9 @@ use colored::*;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 static JSON_MODE: AtomicBool = AtomicBool::new(false);
+static QUIET_MODE: AtomicBool = AtomicBool::new(false);
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+static STATUS_TO_STDERR: AtomicBool = AtomicBool::new(false);
 
 /// Set JSON output mode globally
 pub fn set_json_mode(enabled: bool) {
@@ -15,15 +18,80 @@ pub fn is_json_mode() -> bool {
     JSON_MODE.load(Ordering::SeqCst)
 }
 
-/// Print success message with green checkmark (or JSON output)
+/// Set quiet mode globally (suppresses info messages and progress)
+pub fn set_quiet_mode(enabled: bool) {
+    QUIET_MODE.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if quiet mode is enabled
+pub fn is_quiet_mode() -> bool {
+    QUIET_MODE.load(Ordering::SeqCst)
+}
+
+/// Send every status line to stderr, for commands that write their data
+/// (such as audio) to stdout
+pub fn set_status_to_stderr(enabled: bool) {
+    STATUS_TO_STDERR.store(enabled, Ordering::SeqCst);
+}
+
+/// Print a status line on stdout, or on stderr when stdout carries data
+fn print_status(line: String) {
+    if STATUS_TO_STDERR.load(Ordering::SeqCst) {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Use ASCII fallbacks instead of unicode status symbols
+pub fn set_ascii_mode(enabled: bool) {
+    ASCII_MODE.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if ASCII symbol mode is enabled
+pub fn is_ascii_mode() -> bool {
+    ASCII_MODE.load(Ordering::SeqCst)
+}
+
+/// Configure colored output: `Some(true)` forces color, `Some(false)` disables it,
+/// `None` uses auto-detection (honoring `NO_COLOR`)
+pub fn set_color_mode(enabled: Option<bool>) {
+    match enabled {
+        Some(on) => colored::control::set_override(on),
+        None => {
+            if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                colored::control::set_override(false);
+            } else {
+                colored::control::unset_override();
+            }
+        }
+    }
+}
+
+/// Check whether colored output is currently enabled
+pub fn is_color_enabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Pick the unicode symbol or its ASCII fallback
+pub fn symbol(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if is_ascii_mode() {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+/// Print success message with green checkmark (or JSON on stderr, keeping
+/// stdout for the command's data)
 pub fn print_success(message: &str) {
     if is_json_mode() {
-        println!(
+        eprintln!(
             "{}",
             serde_json::json!({"status": "success", "message": message})
         );
     } else {
-        println!("{} {}", "✓".green(), message);
+        print_status(format!("{} {}", symbol("✓", "[ok]").green(), message));
     }
 }
 
@@ -35,30 +103,31 @@ pub fn print_error(message: &str) {
             serde_json::json!({"status": "error", "message": message})
         );
     } else {
-        eprintln!("{} {}", "✗".red(), message);
+        eprintln!("{} {}", symbol("✗", "[error]").red(), message);
     }
 }
 
-/// Print info message with blue info symbol (or JSON output)
+/// Print info message with blue info symbol (or JSON on stderr, keeping
+/// stdout for the command's single JSON document)
 pub fn print_info(message: &str) {
     if is_json_mode() {
-        println!(
+        eprintln!(
             "{}",
             serde_json::json!({"status": "info", "message": message})
         );
-    } else {
-        println!("{} {}", "ℹ".blue(), message);
+    } else if !is_quiet_mode() {
+        print_status(format!("{} {}", symbol("ℹ", "[info]").blue(), message));
     }
 }
 
-/// Print warning message with yellow warning symbol (or JSON output)
+/// Print warning message with yellow warning symbol (or JSON on stderr)
 pub fn print_warning(message: &str) {
     if is_json_mode() {
-        println!(
+        eprintln!(
             "{}",
             serde_json::json!({"status": "warning", "message": message})
         );
     } else {
-        println!("{} {}", "⚠".yellow(), message);
+        print_status(format!("{} {}", symbol("⚠", "[warn]").yellow(), message));
     }
 }
@@ -0,0 +1,185 @@
+//! Platform directories for config, caches and data
+//!
+//! - config: `config.toml` (settings the user edits)
+//! - cache: files rebuilt from the API on demand (search index); safe to delete
+//! - data: state the CLI cannot recreate (error log, rate-limit history)
+//!
+//! On Linux these follow the XDG base directories (`~/.config/elevenlabs-cli`,
+//! `~/.cache/elevenlabs-cli`, `~/.local/share/elevenlabs-cli`). Earlier
+//! versions kept everything under a directory named `cli`; those files are
+//! moved on first run.
+
+use crate::output::{print_info, print_warning};
+use directories::ProjectDirs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Kinds of files the CLI keeps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DirKind {
+    Config,
+    Cache,
+    Data,
+}
+
+/// Files moved out of the pre-XDG layout: (old directory, file name, new directory)
+const LEGACY_FILES: &[(DirKind, &str, DirKind)] = &[
+    (DirKind::Config, "config.toml", DirKind::Config),
+    (DirKind::Data, "search-index.json", DirKind::Cache),
+    (DirKind::Data, "rate-limits.json", DirKind::Data),
+    (DirKind::Data, "errors.log", DirKind::Data),
+];
+
+fn project_dirs() -> Option<&'static ProjectDirs> {
+    static DIRS: OnceLock<Option<ProjectDirs>> = OnceLock::new();
+    DIRS.get_or_init(|| ProjectDirs::from("io", "elevenlabs", "elevenlabs-cli"))
+        .as_ref()
+}
+
+fn legacy_project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("com", "elevenlabs", "cli")
+}
+
+fn dir_of(dirs: &ProjectDirs, kind: DirKind) -> &Path {
+    match kind {
+        DirKind::Config => dirs.config_dir(),
+        DirKind::Cache => dirs.cache_dir(),
+        DirKind::Data => dirs.data_local_dir(),
+    }
+}
+
+/// Directory for one kind of file
+pub fn dir(kind: DirKind) -> Option<PathBuf> {
+    project_dirs().map(|dirs| dir_of(dirs, kind).to_path_buf())
+}
+
+/// Path of the config file
+pub fn config_file() -> Option<PathBuf> {
+    dir(DirKind::Config).map(|d| d.join("config.toml"))
+}
+
+/// Path of a cache file (rebuildable)
+pub fn cache_file(name: &str) -> Option<PathBuf> {
+    dir(DirKind::Cache).map(|d| d.join(name))
+}
+
+/// Path of a data file (kept across runs)
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    dir(DirKind::Data).map(|d| d.join(name))
+}
+
+/// Move files from the pre-XDG layout; never overwrites files already in place
+pub fn migrate_legacy_layout() {
+    let (Some(legacy), Some(current)) = (legacy_project_dirs(), project_dirs()) else {
+        return;
+    };
+    let moves: Vec<(PathBuf, PathBuf)> = LEGACY_FILES
+        .iter()
+        .map(|&(from, name, to)| {
+            (
+                dir_of(&legacy, from).join(name),
+                dir_of(current, to).join(name),
+            )
+        })
+        .collect();
+
+    for (from, to) in plan_moves(&moves) {
+        match move_file(&from, &to) {
+            Ok(()) => print_info(&format!("Moved {} to {}", from.display(), to.display())),
+            Err(e) => print_warning(&format!(
+                "Could not move {} to {}: {}",
+                from.display(),
+                to.display(),
+                e
+            )),
+        }
+    }
+
+    // Drop the old directories (and stale lock files) once nothing else is left
+    for kind in [DirKind::Config, DirKind::Data, DirKind::Cache] {
+        let old = dir_of(&legacy, kind);
+        if dir_of(current, kind) != old {
+            remove_if_only_locks(old);
+        }
+    }
+}
+
+/// Moves whose source exists and whose target does not
+fn plan_moves(moves: &[(PathBuf, PathBuf)]) -> Vec<(PathBuf, PathBuf)> {
+    moves
+        .iter()
+        .filter(|(from, to)| from != to && from.is_file() && !to.exists())
+        .cloned()
+        .collect()
+}
+
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // rename fails across filesystems; fall back to copy and delete
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn remove_if_only_locks(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let entries: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    if entries
+        .iter()
+        .all(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "lock"))
+    {
+        for lock in &entries {
+            let _ = std::fs::remove_file(lock);
+        }
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_plan_and_move_legacy_files() {
+        let dir = tempdir().unwrap();
+        let old = dir.path().join("cli");
+        let new = dir.path().join("elevenlabs-cli");
+        std::fs::create_dir_all(&old).unwrap();
+        std::fs::create_dir_all(&new).unwrap();
+        std::fs::write(old.join("config.toml"), "default_voice = \"Brian\"").unwrap();
+        std::fs::write(old.join("errors.log"), "old").unwrap();
+        std::fs::write(new.join("errors.log"), "new").unwrap();
+        std::fs::write(old.join("config.toml.lock"), "").unwrap();
+
+        let moves = vec![
+            (old.join("config.toml"), new.join("config.toml")),
+            (old.join("errors.log"), new.join("errors.log")),
+            (old.join("missing.json"), new.join("missing.json")),
+        ];
+        let planned = plan_moves(&moves);
+        assert_eq!(planned, moves[..1].to_vec());
+
+        for (from, to) in planned {
+            move_file(&from, &to).unwrap();
+        }
+        assert!(new.join("config.toml").exists());
+        assert_eq!(
+            std::fs::read_to_string(new.join("errors.log")).unwrap(),
+            "new"
+        );
+
+        // The old errors.log is kept, so the directory stays
+        remove_if_only_locks(&old);
+        assert!(old.join("errors.log").exists());
+        std::fs::remove_file(old.join("errors.log")).unwrap();
+        remove_if_only_locks(&old);
+        assert!(!old.exists());
+    }
+}
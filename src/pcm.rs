@@ -0,0 +1,395 @@
+//! Helpers for raw 16-bit little-endian PCM audio and WAV containers
+//!
+//! The API returns `pcm_<rate>` output as headerless mono samples; these
+//! helpers convert between bytes and samples, resample, wrap them in a WAV
+//! header and encode the 8-bit G.711 telephony formats. Local audio files are
+//! read with [`decode_file`]: 16-bit WAV directly, anything else through ffmpeg.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Size of the canonical PCM WAV header in bytes
+pub const WAV_HEADER_LEN: usize = 44;
+
+/// Build a 44-byte WAV header for 16-bit PCM audio
+pub fn wav_header(sample_rate: u32, channels: u16, data_len: u32) -> [u8; WAV_HEADER_LEN] {
+    let block_align = channels * 2;
+    let byte_rate = sample_rate * block_align as u32;
+
+    let mut header = [0u8; WAV_HEADER_LEN];
+    header[0..4].copy_from_slice(b"RIFF");
+    header[4..8].copy_from_slice(&(36 + data_len).to_le_bytes());
+    header[8..16].copy_from_slice(b"WAVEfmt ");
+    header[16..20].copy_from_slice(&16u32.to_le_bytes());
+    header[20..22].copy_from_slice(&1u16.to_le_bytes()); // PCM
+    header[22..24].copy_from_slice(&channels.to_le_bytes());
+    header[24..28].copy_from_slice(&sample_rate.to_le_bytes());
+    header[28..32].copy_from_slice(&byte_rate.to_le_bytes());
+    header[32..34].copy_from_slice(&block_align.to_le_bytes());
+    header[34..36].copy_from_slice(&16u16.to_le_bytes()); // bits per sample
+    header[36..40].copy_from_slice(b"data");
+    header[40..44].copy_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+/// Wrap raw mono 16-bit PCM in a WAV container
+pub fn pcm16_to_wav(pcm: &[u8], sample_rate: u32) -> Vec<u8> {
+    let mut wav = Vec::with_capacity(WAV_HEADER_LEN + pcm.len());
+    wav.extend_from_slice(&wav_header(sample_rate, 1, pcm.len() as u32));
+    wav.extend_from_slice(pcm);
+    wav
+}
+
+/// Mono samples of an audio file at `rate`. MP3 and other formats are
+/// decoded with ffmpeg; `purpose` names the flag that needed it.
+pub fn decode_file(path: &Path, rate: u32, purpose: &str) -> Result<Vec<i16>> {
+    let data = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if let Some((samples, source_rate)) = parse_wav(&data) {
+        return Ok(resample(&samples, source_rate, rate));
+    }
+
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(path)
+        .args(["-ac", "1", "-ar", &rate.to_string(), "-f", "s16le", "-"])
+        .output()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "ffmpeg not found in PATH. Install ffmpeg to use {}",
+                purpose
+            )
+        })?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg failed on {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(samples_from_pcm16(&output.stdout))
+}
+
+/// Mono samples and sample rate of a 16-bit PCM WAV file (channels are
+/// averaged). `None` for other containers and sample formats.
+pub fn parse_wav(data: &[u8]) -> Option<(Vec<i16>, u32)> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return None;
+    }
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let len = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().ok()?) as usize;
+        let body = &data[pos + 8..(pos + 8).saturating_add(len).min(data.len())];
+        if id == b"fmt " && body.len() >= 16 {
+            let tag = u16::from_le_bytes([body[0], body[1]]);
+            let channels = u16::from_le_bytes([body[2], body[3]]);
+            let rate = u32::from_le_bytes(body[4..8].try_into().ok()?);
+            let bits = u16::from_le_bytes([body[14], body[15]]);
+            // 0xFFFE is WAVE_FORMAT_EXTENSIBLE, which ffmpeg writes for PCM too
+            if !(tag == 1 || tag == 0xFFFE) || bits != 16 || channels == 0 {
+                return None;
+            }
+            format = Some((channels as usize, rate));
+        } else if id == b"data" {
+            let (channels, rate) = format?;
+            let samples = samples_from_pcm16(body)
+                .chunks_exact(channels)
+                .map(|frame| {
+                    (frame.iter().map(|&s| s as i32).sum::<i32>() / channels as i32) as i16
+                })
+                .collect();
+            return Some((samples, rate));
+        }
+        // Chunks are padded to an even length
+        pos += 8 + len + (len & 1);
+    }
+    None
+}
+
+/// Sample rate of a `pcm_<rate>` output format, if it is one
+pub fn pcm_sample_rate(format: &str) -> Option<u32> {
+    format.strip_prefix("pcm_")?.parse().ok()
+}
+
+/// Sample rate of a `pcm_<rate>` or `wav_<rate>` format
+pub fn linear_pcm_rate(format: &str) -> Option<u32> {
+    pcm_sample_rate(format).or_else(|| format.strip_prefix("wav_")?.parse().ok())
+}
+
+/// Sample rate of a format that can be derived from PCM without an encoder
+pub fn local_rate(format: &str) -> Option<u32> {
+    match format {
+        "ulaw_8000" | "alaw_8000" => Some(8_000),
+        _ => linear_pcm_rate(format),
+    }
+}
+
+/// Convert PCM samples to a locally derived format
+pub fn encode_local(samples: &[i16], source_rate: u32, format: &str) -> Option<Vec<u8>> {
+    let rate = local_rate(format)?;
+    let samples = resample(samples, source_rate, rate);
+    Some(match format.split('_').next()? {
+        "pcm" => samples_to_pcm16(&samples),
+        "wav" => pcm16_to_wav(&samples_to_pcm16(&samples), rate),
+        "ulaw" => samples.iter().map(|&s| linear_to_ulaw(s)).collect(),
+        "alaw" => samples.iter().map(|&s| linear_to_alaw(s)).collect(),
+        _ => return None,
+    })
+}
+
+/// Decode little-endian 16-bit PCM bytes into samples (a trailing odd byte is ignored)
+pub fn samples_from_pcm16(pcm: &[u8]) -> Vec<i16> {
+    pcm.chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect()
+}
+
+/// Encode samples as little-endian 16-bit PCM bytes
+pub fn samples_to_pcm16(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+/// Average interleaved channels into mono
+pub fn downmix(samples: &[i16], channels: u16) -> Vec<i16> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels as usize)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / frame.len() as i32) as i16)
+        .collect()
+}
+
+/// Resample mono audio with linear interpolation.
+///
+/// When downsampling, each output sample averages the input samples it covers
+/// so content above the new Nyquist rate does not alias back as noise.
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || from_rate == 0 || to_rate == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let step = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / step).round().max(1.0) as usize;
+    let last = samples.len() - 1;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let value = if step > 1.0 {
+                let start = (pos - step / 2.0).ceil().max(0.0) as usize;
+                let end = ((pos + step / 2.0).floor() as usize).min(last);
+                let window = &samples[start.min(end)..=end];
+                window.iter().map(|&s| s as f64).sum::<f64>() / window.len() as f64
+            } else {
+                let index = (pos.floor() as usize).min(last);
+                let next = (index + 1).min(last);
+                let frac = pos - index as f64;
+                samples[index] as f64 * (1.0 - frac) + samples[next] as f64 * frac
+            };
+            value.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+        })
+        .collect()
+}
+
+/// Encode one sample as G.711 μ-law (`ulaw_8000`)
+pub fn linear_to_ulaw(sample: i16) -> u8 {
+    const BIAS: i32 = 0x84;
+    const CLIP: i32 = 32_635;
+
+    let mut value = sample as i32;
+    let sign = if value < 0 {
+        value = -value;
+        0x80
+    } else {
+        0
+    };
+    value = value.min(CLIP) + BIAS;
+
+    let mut exponent = 7;
+    let mut mask = 0x4000;
+    while exponent > 0 && value & mask == 0 {
+        exponent -= 1;
+        mask >>= 1;
+    }
+    let mantissa = (value >> (exponent + 3)) & 0x0F;
+    !(sign | (exponent << 4) | mantissa) as u8
+}
+
+/// Encode one sample as G.711 A-law (`alaw_8000`)
+pub fn linear_to_alaw(sample: i16) -> u8 {
+    // Segment end points for the 13-bit magnitude
+    const SEGMENT_END: [i32; 8] = [0x1F, 0x3F, 0x7F, 0xFF, 0x1FF, 0x3FF, 0x7FF, 0xFFF];
+
+    let mut value = (sample as i32) >> 3;
+    let mask = if value >= 0 {
+        0xD5
+    } else {
+        value = -value - 1;
+        0x55
+    };
+
+    let Some(segment) = SEGMENT_END.iter().position(|&end| value <= end) else {
+        return 0x7F ^ mask;
+    };
+    let shift = if segment < 2 { 1 } else { segment };
+    (((segment as i32) << 4) | ((value >> shift) & 0x0F)) as u8 ^ mask
+}
+
+/// Make a seamless loop by crossfading the last `fade_len` samples into the start.
+///
+/// The result is `fade_len` samples shorter than the input; its end flows directly
+/// into its beginning when played on repeat. Uses an equal-power curve.
+pub fn crossfade_loop(samples: &[i16], fade_len: usize) -> Vec<i16> {
+    if fade_len == 0 || samples.len() < fade_len * 2 {
+        return samples.to_vec();
+    }
+
+    let tail_start = samples.len() - fade_len;
+    let mut out = Vec::with_capacity(tail_start);
+    for i in 0..fade_len {
+        let t = (i as f32 + 0.5) / fade_len as f32;
+        let fade_in = (t * std::f32::consts::FRAC_PI_2).sin();
+        let fade_out = (t * std::f32::consts::FRAC_PI_2).cos();
+        let mixed = samples[tail_start + i] as f32 * fade_out + samples[i] as f32 * fade_in;
+        out.push(mixed.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+    }
+    out.extend_from_slice(&samples[fade_len..tail_start]);
+    out
+}
+
+/// Length of the fade at each end of a generated tone
+const TONE_FADE_SECS: f64 = 0.005;
+
+/// Number of samples in `secs` of audio
+fn sample_count(secs: f64, sample_rate: u32) -> usize {
+    (secs.max(0.0) * sample_rate as f64).round() as usize
+}
+
+/// A sine tone at `amplitude` (0-1 of full scale).
+///
+/// Both ends fade over a few milliseconds so the tone starts and stops
+/// without a click.
+pub fn sine_tone(freq: f64, secs: f64, sample_rate: u32, amplitude: f64) -> Vec<i16> {
+    let len = sample_count(secs, sample_rate);
+    let fade = sample_count(TONE_FADE_SECS, sample_rate)
+        .min(len / 2)
+        .max(1) as f64;
+    let peak = amplitude.clamp(0.0, 1.0) * i16::MAX as f64;
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            let envelope = (i as f64 / fade).min((len - i) as f64 / fade).min(1.0);
+            let value = (2.0 * std::f64::consts::PI * freq * t).sin() * peak * envelope;
+            value.round() as i16
+        })
+        .collect()
+}
+
+/// Digital silence
+pub fn silence(secs: f64, sample_rate: u32) -> Vec<i16> {
+    vec![0; sample_count(secs, sample_rate)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pcm16_to_wav_header() {
+        let wav = pcm16_to_wav(&[1, 0, 2, 0], 16_000);
+        assert_eq!(wav.len(), WAV_HEADER_LEN + 4);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[28..32].try_into().unwrap()), 32_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 4);
+    }
+
+    #[test]
+    fn test_downmix() {
+        assert_eq!(downmix(&[100, 300, -50, 50], 2), [200, 0]);
+        assert_eq!(downmix(&[1, 2], 1), [1, 2]);
+    }
+
+    #[test]
+    fn test_parse_wav() {
+        let wav = pcm16_to_wav(&samples_to_pcm16(&[100, -100, 300]), 8_000);
+        assert_eq!(parse_wav(&wav), Some((vec![100, -100, 300], 8_000)));
+
+        let mut stereo = wav_header(16_000, 2, 8).to_vec();
+        stereo.extend_from_slice(&samples_to_pcm16(&[100, 300, -50, -150]));
+        assert_eq!(parse_wav(&stereo), Some((vec![200, -100], 16_000)));
+
+        assert_eq!(parse_wav(b"ID3\x03\x00 not a wav"), None);
+    }
+
+    #[test]
+    fn test_pcm_sample_rate() {
+        assert_eq!(pcm_sample_rate("pcm_44100"), Some(44_100));
+        assert_eq!(pcm_sample_rate("mp3_44100_128"), None);
+    }
+
+    #[test]
+    fn test_sample_roundtrip() {
+        let samples = vec![0, 1, -1, i16::MAX, i16::MIN];
+        assert_eq!(samples_from_pcm16(&samples_to_pcm16(&samples)), samples);
+    }
+
+    #[test]
+    fn test_crossfade_loop() {
+        let samples: Vec<i16> = (0..100).map(|i| i as i16 * 100).collect();
+        let looped = crossfade_loop(&samples, 10);
+        assert_eq!(looped.len(), 90);
+        // Start is dominated by the old tail so the wrap-around is continuous
+        assert!((looped[0] - samples[90]).abs() < 1_000);
+        // End of the fade region is dominated by the original head
+        assert!((looped[9] - samples[9]).abs() < (looped[9] - samples[99]).abs());
+        assert_eq!(&looped[10..], &samples[10..90]);
+
+        assert_eq!(crossfade_loop(&samples, 0), samples);
+        assert_eq!(crossfade_loop(&samples[..10], 10), &samples[..10]);
+    }
+
+    #[test]
+    fn test_tone_and_silence() {
+        let tone = sine_tone(1_000.0, 0.5, 8_000, 0.5);
+        assert_eq!(tone.len(), 4_000);
+        // Faded ends, half of full scale in the middle
+        assert_eq!(tone[0], 0);
+        assert!(tone[tone.len() - 1].abs() < 3_000);
+        let peak = tone.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        assert!((16_000..=16_384).contains(&peak));
+
+        assert_eq!(silence(1.5, 8_000), vec![0; 12_000]);
+        assert!(sine_tone(440.0, 0.0, 8_000, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_resample() {
+        let samples: Vec<i16> = (0..480).map(|i| (i % 48) as i16 * 100).collect();
+        let down = resample(&samples, 48_000, 16_000);
+        assert_eq!(down.len(), 160);
+        // A constant signal survives in both directions
+        let flat = vec![1_000i16; 100];
+        assert!(resample(&flat, 44_100, 8_000).iter().all(|&s| s == 1_000));
+        let up = resample(&flat, 8_000, 16_000);
+        assert_eq!(up.len(), 200);
+        assert!(up.iter().all(|&s| s == 1_000));
+        // Linear interpolation between neighbours
+        assert_eq!(resample(&[0, 100], 8_000, 16_000), vec![0, 50, 100, 100]);
+        assert_eq!(resample(&samples, 16_000, 16_000), samples);
+    }
+
+    #[test]
+    fn test_g711_encoding() {
+        assert_eq!(linear_to_ulaw(0), 0xFF);
+        assert_eq!(linear_to_ulaw(-1), 0x7F);
+        assert_eq!(linear_to_ulaw(i16::MAX), 0x80);
+        assert_eq!(linear_to_ulaw(i16::MIN), 0x00);
+        assert_eq!(linear_to_alaw(0), 0xD5);
+        assert_eq!(linear_to_alaw(-8), 0x55);
+        assert_eq!(linear_to_alaw(i16::MAX), 0xAA);
+        assert_eq!(linear_to_alaw(i16::MIN), 0x2A);
+    }
+}
@@ -0,0 +1,155 @@
+//! Audio playback for `--play`, with an external player fallback
+//!
+//! Built-in playback uses the `audio` feature's device I/O when it is compiled
+//! in and the SDK's player otherwise. When no output device is available, the
+//! `player_cmd` config value (e.g. `mpv -`) is run.
+//! A `--fx` chain is rendered into the audio just before it is played.
+
+use crate::effects::{apply_effects, Effect};
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// External player command from config (`player_cmd`), set once at startup
+static PLAYER_COMMAND: OnceLock<Option<String>> = OnceLock::new();
+
+pub fn set_player_command(command: Option<String>) {
+    let _ = PLAYER_COMMAND.set(command.filter(|c| !c.trim().is_empty()));
+}
+
+fn player_command() -> Option<&'static str> {
+    PLAYER_COMMAND.get().and_then(|c| c.as_deref())
+}
+
+/// Effects from `--fx`, set once at startup
+static PLAYBACK_EFFECTS: OnceLock<Vec<Effect>> = OnceLock::new();
+
+pub fn set_playback_effects(effects: Vec<Effect>) {
+    let _ = PLAYBACK_EFFECTS.set(effects);
+}
+
+/// Whether `--fx` was given. Effects are rendered over a whole clip, so
+/// streaming playback is skipped in favour of [`play_audio`] when they are.
+#[cfg(feature = "audio")]
+pub fn has_playback_effects() -> bool {
+    PLAYBACK_EFFECTS
+        .get()
+        .is_some_and(|effects| !effects.is_empty())
+}
+
+/// Play audio bytes, falling back to the configured external player
+///
+/// Built-in playback is tried first; when no output device is available,
+/// `player_cmd` runs instead.
+pub fn play_audio(data: &[u8]) -> Result<()> {
+    let processed;
+    let data = match PLAYBACK_EFFECTS.get().filter(|effects| !effects.is_empty()) {
+        Some(effects) => {
+            processed = apply_effects(data, effects)?;
+            processed.as_slice()
+        }
+        None => data,
+    };
+    #[cfg(feature = "audio")]
+    let builtin_error = match crate::audio::audio_io::play_to_speaker(data) {
+        Ok(()) => return Ok(()),
+        Err(e) => e.to_string(),
+    };
+    #[cfg(not(feature = "audio"))]
+    let builtin_error = match elevenlabs_rs::utils::play(bytes::Bytes::copy_from_slice(data)) {
+        Ok(()) => return Ok(()),
+        Err(e) => e.to_string(),
+    };
+    match player_command() {
+        Some(command) => play_with_command(command, data),
+        None => Err(anyhow::anyhow!(
+            "{}. Or set an external player: elevenlabs-cli config set player_cmd \"mpv -\"",
+            builtin_error
+        )),
+    }
+}
+
+/// Arguments for an external player: `-` reads audio from stdin, `{}` is
+/// replaced by a temporary file path, and otherwise the path is appended
+fn player_args(command: &str, file: Option<&str>) -> Result<(String, Vec<String>)> {
+    let mut parts = command.split_whitespace().map(str::to_string);
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("player_cmd is empty"))?;
+    let mut args: Vec<String> = parts.collect();
+    if let Some(file) = file {
+        if args.iter().any(|a| a.contains("{}")) {
+            args = args.iter().map(|a| a.replace("{}", file)).collect();
+        } else {
+            args.push(file.to_string());
+        }
+    }
+    Ok((program, args))
+}
+
+fn play_with_command(command: &str, data: &[u8]) -> Result<()> {
+    let uses_stdin = command.split_whitespace().skip(1).any(|a| a == "-");
+    let status = if uses_stdin {
+        let (program, args) = player_args(command, None)?;
+        let mut child = Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start player '{}'", program))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The player may exit early (e.g. when skipped); that is not an error
+            let _ = stdin.write_all(data);
+        }
+        child.wait()?
+    } else {
+        let mut file = tempfile::Builder::new()
+            .prefix("elevenlabs-play-")
+            .tempfile()?;
+        file.write_all(data)?;
+        file.flush()?;
+        let path = file.path().to_string_lossy().to_string();
+        let (program, args) = player_args(command, Some(&path))?;
+        Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::null())
+            .status()
+            .with_context(|| format!("Failed to start player '{}'", program))?
+    };
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Player '{}' exited with {}",
+            command,
+            status
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_args() {
+        let (program, args) = player_args("mpv --no-video -", None).unwrap();
+        assert_eq!(program, "mpv");
+        assert_eq!(args, vec!["--no-video", "-"]);
+
+        let (_, args) = player_args("afplay", Some("/tmp/a.mp3")).unwrap();
+        assert_eq!(args, vec!["/tmp/a.mp3"]);
+
+        let (_, args) = player_args("vlc --play-and-exit {}", Some("/tmp/a.mp3")).unwrap();
+        assert_eq!(args, vec!["--play-and-exit", "/tmp/a.mp3"]);
+
+        assert!(player_args("  ", None).is_err());
+    }
+
+    #[test]
+    fn test_play_with_command() {
+        assert!(play_with_command("cat -", b"audio").is_ok());
+        assert!(play_with_command("false", b"audio").is_err());
+        assert!(play_with_command("definitely-not-a-player-xyz", b"audio").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Interactive prompts and the global `--no-input` switch
+//!
+//! Every prompt goes through here so `--no-input` can turn it into an
+//! [`InputRequired`] error, which exits with [`EXIT_INPUT_REQUIRED`] instead
+//! of waiting on stdin.
+
+use anyhow::Result;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Exit status when a prompt was refused because of `--no-input`
+pub const EXIT_INPUT_REQUIRED: i32 = 3;
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+
+/// Turn prompts into errors (`--no-input`)
+pub fn set_no_input(enabled: bool) {
+    NO_INPUT.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if prompts are disabled
+pub fn is_no_input() -> bool {
+    NO_INPUT.load(Ordering::SeqCst)
+}
+
+/// A prompt that could not be shown because input is disabled
+#[derive(Debug)]
+pub struct InputRequired {
+    pub prompt: String,
+}
+
+impl std::error::Error for InputRequired {}
+
+impl fmt::Display for InputRequired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Input required ({}) but --no-input is set; pass --yes or give the value as an argument",
+            self.prompt
+        )
+    }
+}
+
+/// Fail with [`InputRequired`] if prompting is disabled
+pub fn ensure_input_allowed(prompt: &str) -> Result<()> {
+    if is_no_input() {
+        return Err(InputRequired {
+            prompt: prompt.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Ask a yes/no question; the default answer is no
+pub fn confirm(prompt: impl Into<String>) -> Result<bool> {
+    let prompt = prompt.into();
+    ensure_input_allowed(&prompt)?;
+    Ok(dialoguer::Confirm::new()
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?)
+}
+
+/// Read a line of text
+pub fn input(prompt: &str) -> Result<String> {
+    ensure_input_allowed(prompt)?;
+    Ok(dialoguer::Input::<String>::new()
+        .with_prompt(prompt)
+        .interact_text()?)
+}
+
+/// Read a secret without echoing it
+pub fn password(prompt: &str) -> Result<String> {
+    ensure_input_allowed(prompt)?;
+    Ok(dialoguer::Password::new().with_prompt(prompt).interact()?)
+}
+
+/// Whether an error came from a refused prompt
+pub fn is_input_required(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<InputRequired>().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompts_fail_without_input() {
+        set_no_input(true);
+        let error = confirm("Are you sure?").unwrap_err();
+        assert!(is_input_required(&error));
+        assert!(error.to_string().contains("Are you sure?"));
+        assert!(is_input_required(
+            &input("elevenlabs").unwrap_err().context("Interactive mode")
+        ));
+        set_no_input(false);
+        assert!(ensure_input_allowed("Continue?").is_ok());
+    }
+}
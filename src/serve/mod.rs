@@ -0,0 +1,613 @@
+//! Local REST API server (`elevenlabs serve`)
+//!
+//! Exposes a few core operations over HTTP so scripts in any language can go
+//! through one local gateway that holds the API key:
+//!
+//! - `POST /tts`    JSON body (same fields as the MCP `text_to_speech` tool), returns audio
+//! - `POST /sfx`    JSON body (same fields as the MCP `generate_sfx` tool), returns audio
+//! - `POST /stt`    raw audio body, options as query parameters, returns JSON
+//! - `GET /voices`  returns JSON
+//! - `GET /health`  liveness probe (no auth, not rate limited)
+//!
+//! OpenAI-compatible `POST /v1/audio/speech` and `POST /v1/audio/transcriptions`
+//! routes are served as well (see [`openai`]).
+//!
+//! Generated audio and voice listings are cached in memory, and all requests
+//! share one token-bucket rate limit.
+//!
+//! A bearer token is required unless `--no-auth` is given. So that web pages
+//! open in the user's browser cannot use the server, JSON routes only accept
+//! `Content-Type: application/json` (which browsers must preflight) and API
+//! routes reject `Host` headers that don't name the bound address (DNS
+//! rebinding).
+
+mod openai;
+
+use crate::cli::ServeArgs;
+use crate::mcp::tools::{GenerateSfxInput, ListVoicesInput, SpeechToTextInput, TextToSpeechInput};
+use crate::mcp_handlers;
+use crate::output::{print_info, print_success, print_warning};
+use crate::utils::MAX_FILE_SIZE;
+use crate::validation::preflight_tts;
+use anyhow::{Context, Result};
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Query, Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use colored::*;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// Token bucket shared by all clients
+struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    fn per_minute(rate: u32, now: Instant) -> Self {
+        let capacity = f64::from(rate.max(1));
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / 60.0,
+            last: now,
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    fn try_acquire(&mut self, now: Instant) -> std::result::Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - self.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_sec))
+        }
+    }
+}
+
+/// A response body kept in the cache
+#[derive(Clone)]
+struct CachedResponse {
+    content_type: &'static str,
+    body: Bytes,
+    stored_at: Instant,
+}
+
+/// Small in-memory cache with a size cap and TTL; oldest entries are evicted first
+struct ResponseCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<u64, CachedResponse>,
+    order: VecDeque<u64>,
+}
+
+impl ResponseCache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: u64, now: Instant) -> Option<CachedResponse> {
+        let expired = match self.entries.get(&key) {
+            Some(entry) => now.saturating_duration_since(entry.stored_at) > self.ttl,
+            None => return None,
+        };
+        if expired {
+            self.entries.remove(&key);
+            self.order.retain(|k| *k != key);
+            return None;
+        }
+        self.entries.get(&key).cloned()
+    }
+
+    fn insert(&mut self, key: u64, content_type: &'static str, body: Bytes, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.contains_key(&key) {
+            self.order.retain(|k| *k != key);
+        }
+        while self.entries.len() >= self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.entries.insert(
+            key,
+            CachedResponse {
+                content_type,
+                body,
+                stored_at: now,
+            },
+        );
+        self.order.push_back(key);
+    }
+}
+
+struct ServerState {
+    api_key: String,
+    token: Option<String>,
+    /// Host names accepted in the `Host` header (`None` for a wildcard bind)
+    hosts: Option<Vec<String>>,
+    port: u16,
+    limiter: Mutex<RateLimiter>,
+    cache: Mutex<ResponseCache>,
+}
+
+impl ServerState {
+    fn cached(&self, key: u64) -> Option<CachedResponse> {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|mut cache| cache.get(key, Instant::now()))
+    }
+
+    fn store(&self, key: u64, content_type: &'static str, body: Bytes) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(key, content_type, body, Instant::now());
+        }
+    }
+}
+
+type SharedState = Arc<ServerState>;
+
+pub async fn run_server(args: ServeArgs, api_key: String) -> Result<()> {
+    let addr = format!("{}:{}", args.host, args.port);
+    if args.token.is_none() {
+        if !args.no_auth {
+            return Err(anyhow::anyhow!(
+                "serve needs --token (or ELEVENLABS_SERVE_TOKEN); pass --no-auth to serve without one"
+            ));
+        }
+        print_warning(&format!(
+            "Listening on {} without a token; anyone who can reach it can spend your credits",
+            args.host
+        ));
+    }
+
+    let state = Arc::new(ServerState {
+        api_key,
+        token: args.token,
+        hosts: allowed_hosts(&args.host),
+        port: args.port,
+        limiter: Mutex::new(RateLimiter::per_minute(args.rate, Instant::now())),
+        cache: Mutex::new(ResponseCache::new(
+            args.cache_size,
+            Duration::from_secs(args.cache_ttl),
+        )),
+    });
+
+    let api = Router::new()
+        .route("/tts", post(tts))
+        .route("/sfx", post(sfx))
+        .route("/stt", post(stt))
+        .route("/voices", get(voices))
+        .route("/v1/audio/speech", post(openai::speech))
+        .route("/v1/audio/transcriptions", post(openai::transcriptions))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), guard));
+
+    let app = Router::new()
+        .route("/health", get(|| async { Json(json!({ "status": "ok" })) }))
+        .merge(api)
+        .layer(DefaultBodyLimit::max(MAX_FILE_SIZE as usize))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+
+    print_success(&format!("Serving on {}", format!("http://{}", addr).cyan()));
+    print_info("Endpoints: POST /tts, POST /sfx, POST /stt, GET /voices, GET /health");
+    print_info(&format!(
+        "OpenAI-compatible base URL: {}",
+        format!("http://{}/v1", addr).cyan()
+    ));
+    print_info("Press Ctrl+C to stop");
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("Server error")?;
+
+    print_info("Server stopped");
+    Ok(())
+}
+
+/// JSON error response
+fn error_response(status: StatusCode, message: impl std::fmt::Display) -> Response {
+    (status, Json(json!({ "error": message.to_string() }))).into_response()
+}
+
+/// Host names a request may address a server bound to `bind_host` by;
+/// `None` when it listens on every interface
+fn allowed_hosts(bind_host: &str) -> Option<Vec<String>> {
+    match bind_host.trim_start_matches('[').trim_end_matches(']') {
+        "0.0.0.0" | "::" => None,
+        "127.0.0.1" | "localhost" | "::1" => {
+            Some(["127.0.0.1", "localhost", "::1"].map(String::from).to_vec())
+        }
+        host => Some(vec![host.to_ascii_lowercase()]),
+    }
+}
+
+/// Whether a `Host` header names this server (port 80 when it has none)
+fn host_matches(host: &str, hosts: &[String], port: u16) -> bool {
+    let (name, host_port) = match host.rsplit_once(':') {
+        Some((name, p)) if !name.is_empty() && !p.contains(']') => (name, p.parse::<u16>().ok()),
+        _ => (host, Some(80)),
+    };
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    host_port == Some(port) && hosts.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// Whether a request body is declared as JSON; browsers can't send that
+/// cross-origin without a preflight
+fn is_json(headers: &HeaderMap) -> bool {
+    mime_type(headers).is_some_and(|mime| mime == "application/json")
+}
+
+/// The request's media type, lowercased and without parameters
+fn mime_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+}
+
+/// Host check, authentication and rate limiting applied to every API route
+async fn guard(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    if let Some(hosts) = &state.hosts {
+        let host = request
+            .headers()
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok());
+        if !host.is_some_and(|host| host_matches(host, hosts, state.port)) {
+            return error_response(
+                StatusCode::FORBIDDEN,
+                "Host header does not match the server address",
+            );
+        }
+    }
+
+    if let Some(token) = &state.token {
+        let provided = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if provided != Some(token.as_str()) {
+            return error_response(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token");
+        }
+    }
+
+    let allowed = match state.limiter.lock() {
+        Ok(mut limiter) => limiter.try_acquire(Instant::now()),
+        Err(_) => Ok(()),
+    };
+    if let Err(wait) = allowed {
+        let mut response = error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+        let retry_after = wait.as_secs().max(1).to_string();
+        if let Ok(value) = HeaderValue::from_str(&retry_after) {
+            response.headers_mut().insert(header::RETRY_AFTER, value);
+        }
+        return response;
+    }
+
+    next.run(request).await
+}
+
+/// Cache key for a route and request body
+fn cache_key(route: &str, body: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    route.hash(&mut hasher);
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Content type for an ElevenLabs output format identifier
+fn audio_content_type(format: &str) -> &'static str {
+    match format.split('_').next().unwrap_or("") {
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "opus" => "audio/ogg",
+        "ulaw" | "alaw" => "audio/basic",
+        _ => "application/octet-stream",
+    }
+}
+
+fn body_response(cached: bool, content_type: &'static str, body: Bytes) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(
+        X_CACHE,
+        HeaderValue::from_static(if cached { "HIT" } else { "MISS" }),
+    );
+    (headers, body).into_response()
+}
+
+fn decode_audio(audio_base64: Option<String>) -> Result<Bytes> {
+    let encoded = audio_base64.ok_or_else(|| anyhow::anyhow!("No audio returned"))?;
+    Ok(Bytes::from(BASE64.decode(encoded)?))
+}
+
+async fn tts(State(state): State<SharedState>, headers: HeaderMap, body: Bytes) -> Response {
+    if !is_json(&headers) {
+        return error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Content-Type must be application/json",
+        );
+    }
+    let mut input: TextToSpeechInput = match serde_json::from_slice(&body) {
+        Ok(input) => input,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, format!("Invalid body: {}", e)),
+    };
+    if let Err(e) = preflight_tts(&input.text, &input.model, &input.output_format, None) {
+        return error_response(StatusCode::BAD_REQUEST, e);
+    }
+    // Clients get the audio in the response; never write files on the server
+    input.output_file = None;
+
+    let key = cache_key("tts", &body);
+    if let Some(hit) = state.cached(key) {
+        return body_response(true, hit.content_type, hit.body);
+    }
+
+    let content_type = audio_content_type(&input.output_format);
+    let audio = mcp_handlers::text_to_speech(&state.api_key, input)
+        .await
+        .and_then(|output| decode_audio(output.audio_base64));
+    match audio {
+        Ok(audio) => {
+            state.store(key, content_type, audio.clone());
+            body_response(false, content_type, audio)
+        }
+        Err(e) => error_response(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+async fn sfx(State(state): State<SharedState>, headers: HeaderMap, body: Bytes) -> Response {
+    if !is_json(&headers) {
+        return error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Content-Type must be application/json",
+        );
+    }
+    let mut input: GenerateSfxInput = match serde_json::from_slice(&body) {
+        Ok(input) => input,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, format!("Invalid body: {}", e)),
+    };
+    if input.text.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "'text' must not be empty");
+    }
+    input.output_file = None;
+
+    let key = cache_key("sfx", &body);
+    if let Some(hit) = state.cached(key) {
+        return body_response(true, hit.content_type, hit.body);
+    }
+
+    let audio = mcp_handlers::generate_sfx(&state.api_key, input)
+        .await
+        .and_then(|output| decode_audio(output.audio_base64));
+    match audio {
+        Ok(audio) => {
+            state.store(key, "audio/mpeg", audio.clone());
+            body_response(false, "audio/mpeg", audio)
+        }
+        Err(e) => error_response(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+/// Query parameters accepted by `POST /stt`
+#[derive(Debug, Deserialize)]
+struct SttQuery {
+    model: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    diarize: bool,
+    num_speakers: Option<u32>,
+    timestamps: Option<String>,
+}
+
+/// Content types a browser may send cross-origin without a preflight
+const SIMPLE_CONTENT_TYPES: &[&str] = &[
+    "text/plain",
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+];
+
+/// File extension for an uploaded audio content type
+fn extension_for_content_type(content_type: Option<&str>) -> &'static str {
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    match mime.as_str() {
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/mp4" | "audio/x-m4a" | "audio/aac" => "m4a",
+        "audio/webm" | "video/webm" => "webm",
+        "video/mp4" => "mp4",
+        _ => "mp3",
+    }
+}
+
+/// Store an uploaded audio body in a temporary file for the STT handler
+fn write_upload(body: &[u8], extension: &str) -> std::io::Result<tempfile::NamedTempFile> {
+    let mut file = tempfile::Builder::new()
+        .prefix("elevenlabs-serve-")
+        .suffix(&format!(".{}", extension))
+        .tempfile()?;
+    file.write_all(body)?;
+    Ok(file)
+}
+
+async fn stt(
+    State(state): State<SharedState>,
+    Query(query): Query<SttQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if body.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Request body must contain audio");
+    }
+    if mime_type(&headers).is_none_or(|mime| SIMPLE_CONTENT_TYPES.contains(&mime.as_str())) {
+        return error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Content-Type must name the audio type (for example audio/mpeg)",
+        );
+    }
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let extension = extension_for_content_type(content_type);
+
+    let upload = match write_upload(&body, extension) {
+        Ok(file) => file,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    let input = SpeechToTextInput {
+        file: upload.path().to_string_lossy().to_string(),
+        model: query.model.unwrap_or_else(|| "scribe_v1".to_string()),
+        language: query.language,
+        diarize: query.diarize,
+        num_speakers: query.num_speakers,
+        timestamps: query.timestamps.unwrap_or_else(|| "word".to_string()),
+    };
+
+    match mcp_handlers::speech_to_text(&state.api_key, input).await {
+        Ok(output) => Json(output).into_response(),
+        Err(e) => error_response(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+async fn voices(State(state): State<SharedState>) -> Response {
+    let key = cache_key("voices", b"");
+    if let Some(hit) = state.cached(key) {
+        return body_response(true, hit.content_type, hit.body);
+    }
+
+    let result = mcp_handlers::list_voices(&state.api_key, ListVoicesInput { detailed: false })
+        .await
+        .and_then(|output| Ok(serde_json::to_vec(&output)?));
+    match result {
+        Ok(body) => {
+            let body = Bytes::from(body);
+            state.store(key, "application/json", body.clone());
+            body_response(false, "application/json", body)
+        }
+        Err(e) => error_response(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_refills() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::per_minute(2, start);
+        assert!(limiter.try_acquire(start).is_ok());
+        assert!(limiter.try_acquire(start).is_ok());
+
+        let wait = limiter.try_acquire(start).unwrap_err();
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+
+        assert!(limiter.try_acquire(start + Duration::from_secs(30)).is_ok());
+    }
+
+    #[test]
+    fn test_response_cache_ttl_and_eviction() {
+        let now = Instant::now();
+        let mut cache = ResponseCache::new(2, Duration::from_secs(10));
+        cache.insert(1, "audio/mpeg", Bytes::from_static(b"a"), now);
+        cache.insert(2, "audio/mpeg", Bytes::from_static(b"b"), now);
+        cache.insert(3, "audio/mpeg", Bytes::from_static(b"c"), now);
+
+        assert!(cache.get(1, now).is_none());
+        assert_eq!(cache.get(3, now).unwrap().body, Bytes::from_static(b"c"));
+        assert!(cache.get(2, now + Duration::from_secs(11)).is_none());
+
+        let mut disabled = ResponseCache::new(0, Duration::from_secs(10));
+        disabled.insert(1, "audio/mpeg", Bytes::from_static(b"a"), now);
+        assert!(disabled.get(1, now).is_none());
+    }
+
+    #[test]
+    fn test_content_types() {
+        assert_eq!(audio_content_type("mp3_44100_128"), "audio/mpeg");
+        assert_eq!(audio_content_type("wav_24000"), "audio/wav");
+        assert_eq!(audio_content_type("pcm_16000"), "application/octet-stream");
+        assert_eq!(extension_for_content_type(Some("audio/wav")), "wav");
+        assert_eq!(
+            extension_for_content_type(Some("audio/ogg; codecs=opus")),
+            "ogg"
+        );
+        assert_eq!(extension_for_content_type(None), "mp3");
+    }
+
+    #[test]
+    fn test_json_content_type_is_required() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_json(&headers));
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        assert!(!is_json(&headers));
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("Application/JSON; charset=utf-8"),
+        );
+        assert!(is_json(&headers));
+    }
+
+    #[test]
+    fn test_host_must_match_bound_address() {
+        let loopback = allowed_hosts("127.0.0.1").unwrap();
+        assert!(host_matches("127.0.0.1:3123", &loopback, 3123));
+        assert!(host_matches("localhost:3123", &loopback, 3123));
+        assert!(host_matches("[::1]:3123", &loopback, 3123));
+        assert!(!host_matches("localhost:8080", &loopback, 3123));
+        assert!(!host_matches("attacker.example:3123", &loopback, 3123));
+        assert!(!host_matches("attacker.example", &loopback, 80));
+        assert!(host_matches("localhost", &loopback, 80));
+
+        let lan = allowed_hosts("192.168.1.20").unwrap();
+        assert!(host_matches("192.168.1.20:3123", &lan, 3123));
+        assert!(!host_matches("localhost:3123", &lan, 3123));
+
+        assert!(allowed_hosts("0.0.0.0").is_none());
+    }
+}
@@ -0,0 +1,343 @@
+//! OpenAI-compatible audio routes
+//!
+//! Lets tooling built for the OpenAI API switch to ElevenLabs by pointing its
+//! base URL at `http://<host>:<port>/v1`. Requests are translated onto the
+//! same handlers as the native routes:
+//!
+//! - `POST /v1/audio/speech`: OpenAI voices and models map onto premade
+//!   ElevenLabs voices and models; ElevenLabs voice IDs and `eleven_*` models
+//!   pass through unchanged. `speed` and `instructions` are accepted but ignored.
+//! - `POST /v1/audio/transcriptions`: multipart upload transcribed with Scribe;
+//!   `json`, `text` and `verbose_json` response formats are supported.
+
+use super::{
+    body_response, cache_key, decode_audio, extension_for_content_type, is_json, write_upload,
+    SharedState,
+};
+use crate::mcp::tools::{SpeechToTextInput, SpeechToTextOutput, TextToSpeechInput};
+use crate::mcp_handlers;
+use crate::validation::{preflight_tts, AUDIO_EXTENSIONS};
+use axum::body::Bytes;
+use axum::extract::{Multipart, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use serde_json::json;
+
+/// OpenAI voice names and the premade ElevenLabs voices standing in for them
+const VOICE_MAP: &[(&str, &str)] = &[
+    ("alloy", "EXAVITQu4vr4xnSDxMaL"),   // Sarah
+    ("ash", "IKne3meq5aSn9XLyUdCD"),     // Charlie
+    ("ballad", "onwK4e9ZLuTAKqWW03F9"),  // Daniel
+    ("coral", "Xb7hH8MSUJpSbSDYk0k2"),   // Alice
+    ("echo", "TxGEqnHWrfWFTfGW9XjX"),    // Josh
+    ("fable", "JBFqnCBsd6RMkjVDRZzb"),   // George
+    ("nova", "21m00Tcm4TlvDq8ikWAM"),    // Rachel
+    ("onyx", "pNInz6obpgDQGcFmaJgB"),    // Adam
+    ("sage", "pFZP5JQG7iQjIQuC4Bku"),    // Lily
+    ("shimmer", "XB0fDUnXU5powFXDhCwa"), // Charlotte
+    ("verse", "nPczCjzI2devNBz1zQrb"),   // Brian
+];
+
+/// Error body in the shape OpenAI clients expect
+fn openai_error(status: StatusCode, message: impl std::fmt::Display) -> Response {
+    let kind = if status.is_client_error() {
+        "invalid_request_error"
+    } else {
+        "api_error"
+    };
+    (
+        status,
+        Json(json!({ "error": { "message": message.to_string(), "type": kind } })),
+    )
+        .into_response()
+}
+
+/// Map an OpenAI voice name onto a voice ID; anything else is used as-is
+fn map_voice(voice: &str) -> String {
+    VOICE_MAP
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(voice))
+        .map(|(_, id)| id.to_string())
+        .unwrap_or_else(|| voice.to_string())
+}
+
+/// Map an OpenAI speech model onto an ElevenLabs model
+fn map_speech_model(model: &str) -> String {
+    if model.starts_with("eleven_") {
+        return model.to_string();
+    }
+    match model {
+        "tts-1" => "eleven_flash_v2_5",
+        _ => "eleven_multilingual_v2",
+    }
+    .to_string()
+}
+
+/// Map an OpenAI `response_format` onto an ElevenLabs output format
+fn map_speech_format(format: Option<&str>) -> Result<&'static str, String> {
+    match format.unwrap_or("mp3") {
+        "mp3" => Ok("mp3_44100_128"),
+        "opus" => Ok("opus_48000_64"),
+        "wav" => Ok("wav_44100"),
+        // OpenAI PCM is 24 kHz 16-bit little-endian, which ElevenLabs matches
+        "pcm" => Ok("pcm_24000"),
+        other => Err(format!(
+            "response_format '{}' is not supported. Use mp3, opus, wav or pcm",
+            other
+        )),
+    }
+}
+
+/// Map an OpenAI transcription model onto a Scribe model
+fn map_transcription_model(model: Option<&str>) -> String {
+    match model {
+        Some(model) if model.starts_with("scribe_") => model.to_string(),
+        _ => "scribe_v1".to_string(),
+    }
+}
+
+/// Request body for `POST /v1/audio/speech`
+#[derive(Debug, Deserialize)]
+struct SpeechRequest {
+    model: String,
+    input: String,
+    voice: String,
+    #[serde(default)]
+    response_format: Option<String>,
+}
+
+pub(super) async fn speech(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if !is_json(&headers) {
+        return openai_error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Content-Type must be application/json",
+        );
+    }
+    let request: SpeechRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => return openai_error(StatusCode::BAD_REQUEST, format!("Invalid body: {}", e)),
+    };
+    let output_format = match map_speech_format(request.response_format.as_deref()) {
+        Ok(format) => format,
+        Err(e) => return openai_error(StatusCode::BAD_REQUEST, e),
+    };
+
+    let input = TextToSpeechInput {
+        text: request.input,
+        voice: map_voice(&request.voice),
+        model: map_speech_model(&request.model),
+        output_format: output_format.to_string(),
+        stability: None,
+        similarity_boost: None,
+        style: None,
+        speaker_boost: false,
+        output_file: None,
+    };
+    if let Err(e) = preflight_tts(&input.text, &input.model, &input.output_format, None) {
+        return openai_error(StatusCode::BAD_REQUEST, e);
+    }
+
+    let key = cache_key("openai-speech", &body);
+    if let Some(hit) = state.cached(key) {
+        return body_response(true, hit.content_type, hit.body);
+    }
+
+    let content_type = super::audio_content_type(output_format);
+    let audio = mcp_handlers::text_to_speech(&state.api_key, input)
+        .await
+        .and_then(|output| decode_audio(output.audio_base64));
+    match audio {
+        Ok(audio) => {
+            state.store(key, content_type, audio.clone());
+            body_response(false, content_type, audio)
+        }
+        Err(e) => openai_error(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+/// Fields collected from the transcription multipart form
+#[derive(Debug, Default)]
+struct TranscriptionForm {
+    file: Option<(Bytes, &'static str)>,
+    model: Option<String>,
+    language: Option<String>,
+    response_format: Option<String>,
+}
+
+async fn read_form(mut multipart: Multipart) -> Result<TranscriptionForm, String> {
+    let mut form = TranscriptionForm::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            let extension = field
+                .file_name()
+                .and_then(|f| f.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()))
+                .and_then(|ext| {
+                    AUDIO_EXTENSIONS
+                        .iter()
+                        .find(|known| **known == ext)
+                        .copied()
+                })
+                .unwrap_or_else(|| extension_for_content_type(field.content_type()));
+            let data = field.bytes().await.map_err(|e| e.to_string())?;
+            form.file = Some((data, extension));
+        } else {
+            // Unknown fields (prompt, temperature, ...) are read and ignored
+            let value = field.text().await.map_err(|e| e.to_string())?;
+            match name.as_str() {
+                "model" => form.model = Some(value),
+                "language" => form.language = Some(value),
+                "response_format" => form.response_format = Some(value),
+                _ => {}
+            }
+        }
+    }
+    Ok(form)
+}
+
+/// Supported transcription `response_format` values
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TranscriptionFormat {
+    Json,
+    Text,
+    VerboseJson,
+}
+
+impl TranscriptionFormat {
+    fn parse(format: Option<&str>) -> Result<Self, String> {
+        match format.unwrap_or("json") {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "verbose_json" => Ok(Self::VerboseJson),
+            other => Err(format!(
+                "response_format '{}' is not supported. Use json, text or verbose_json",
+                other
+            )),
+        }
+    }
+}
+
+/// Render a transcription in the requested OpenAI response format
+fn render_transcription(output: SpeechToTextOutput, format: TranscriptionFormat) -> Response {
+    let text = output.text.unwrap_or_default();
+    match format {
+        TranscriptionFormat::Json => Json(json!({ "text": text })).into_response(),
+        TranscriptionFormat::Text => text.into_response(),
+        TranscriptionFormat::VerboseJson => {
+            let words: Vec<_> = output
+                .words
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|w| !w.text.trim().is_empty())
+                .map(|w| json!({ "word": w.text, "start": w.start, "end": w.end }))
+                .collect();
+            let duration = words
+                .iter()
+                .filter_map(|w| w["end"].as_f64())
+                .fold(0.0, f64::max);
+            Json(json!({
+                "task": "transcribe",
+                "language": output.language_code,
+                "duration": duration,
+                "text": text,
+                "words": words,
+            }))
+            .into_response()
+        }
+    }
+}
+
+pub(super) async fn transcriptions(
+    State(state): State<SharedState>,
+    multipart: Multipart,
+) -> Response {
+    let form = match read_form(multipart).await {
+        Ok(form) => form,
+        Err(e) => return openai_error(StatusCode::BAD_REQUEST, format!("Invalid form: {}", e)),
+    };
+    let Some((data, extension)) = form.file else {
+        return openai_error(StatusCode::BAD_REQUEST, "Missing 'file' field");
+    };
+    let response_format = match TranscriptionFormat::parse(form.response_format.as_deref()) {
+        Ok(format) => format,
+        Err(e) => return openai_error(StatusCode::BAD_REQUEST, e),
+    };
+
+    let upload = match write_upload(&data, extension) {
+        Ok(file) => file,
+        Err(e) => return openai_error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    let input = SpeechToTextInput {
+        file: upload.path().to_string_lossy().to_string(),
+        model: map_transcription_model(form.model.as_deref()),
+        language: form.language,
+        diarize: false,
+        num_speakers: None,
+        timestamps: "word".to_string(),
+    };
+
+    match mcp_handlers::speech_to_text(&state.api_key, input).await {
+        Ok(output) => render_transcription(output, response_format),
+        Err(e) => openai_error(StatusCode::BAD_GATEWAY, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tools::WordTimestamp;
+
+    #[test]
+    fn test_speech_mappings() {
+        assert_eq!(map_voice("Nova"), "21m00Tcm4TlvDq8ikWAM");
+        assert_eq!(map_voice("custom_voice_id"), "custom_voice_id");
+        assert_eq!(map_speech_model("tts-1"), "eleven_flash_v2_5");
+        assert_eq!(map_speech_model("tts-1-hd"), "eleven_multilingual_v2");
+        assert_eq!(map_speech_model("eleven_v3"), "eleven_v3");
+        assert_eq!(map_speech_format(None).unwrap(), "mp3_44100_128");
+        assert_eq!(map_speech_format(Some("pcm")).unwrap(), "pcm_24000");
+        assert!(map_speech_format(Some("flac")).is_err());
+        assert_eq!(map_transcription_model(Some("whisper-1")), "scribe_v1");
+        assert_eq!(
+            map_transcription_model(Some("scribe_v1_base")),
+            "scribe_v1_base"
+        );
+    }
+
+    #[test]
+    fn test_transcription_formats() {
+        let output = || SpeechToTextOutput {
+            success: true,
+            text: Some("hi there".to_string()),
+            language_code: Some("en".to_string()),
+            language_probability: Some(0.9),
+            words: Some(vec![WordTimestamp {
+                text: "hi".to_string(),
+                start: Some(0.0),
+                end: Some(0.4),
+                speaker_id: None,
+            }]),
+            error: None,
+        };
+        assert_eq!(
+            TranscriptionFormat::parse(None).unwrap(),
+            TranscriptionFormat::Json
+        );
+        assert!(TranscriptionFormat::parse(Some("srt")).is_err());
+
+        let text = render_transcription(output(), TranscriptionFormat::Text);
+        assert_eq!(text.status(), StatusCode::OK);
+        let verbose = render_transcription(output(), TranscriptionFormat::VerboseJson);
+        assert_eq!(
+            verbose.headers()[axum::http::header::CONTENT_TYPE],
+            "application/json"
+        );
+    }
+}
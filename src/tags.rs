@@ -0,0 +1,126 @@
+//! ID3 metadata tagging for generated audio files
+//!
+//! Used by `tts --tag` and `history download --tag` so that large audio
+//! libraries stay navigable in regular music players.
+
+use anyhow::{Context, Result};
+use id3::{frame::Comment, Tag, TagLike, Version};
+use std::path::Path;
+
+/// Maximum number of characters of source text used as the track title
+const TITLE_MAX_CHARS: usize = 60;
+
+/// Metadata embedded into an audio file
+#[derive(Debug, Default, Clone)]
+pub struct AudioTags {
+    /// Track title (usually an excerpt of the source text)
+    pub title: Option<String>,
+    /// Artist (the voice name)
+    pub artist: Option<String>,
+    /// Album (the project name)
+    pub album: Option<String>,
+    /// Free-form comment (model ID, request ID)
+    pub comment: Option<String>,
+}
+
+/// Build a track title from source text: first non-empty line, truncated
+pub fn title_from_text(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+
+    if line.chars().count() > TITLE_MAX_CHARS {
+        let excerpt: String = line.chars().take(TITLE_MAX_CHARS).collect();
+        format!("{}...", excerpt.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Check whether a file can carry ID3 tags (MP3 only)
+pub fn supports_id3(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("mp3"))
+        .unwrap_or(false)
+}
+
+/// Write ID3v2.4 tags to an MP3 file, replacing any existing tag
+pub fn write_id3_tags(path: &Path, tags: &AudioTags) -> Result<()> {
+    let mut tag = Tag::new();
+
+    if let Some(title) = &tags.title {
+        tag.set_title(title.as_str());
+    }
+    if let Some(artist) = &tags.artist {
+        tag.set_artist(artist.as_str());
+    }
+    if let Some(album) = &tags.album {
+        tag.set_album(album.as_str());
+    }
+    if let Some(comment) = &tags.comment {
+        tag.add_frame(Comment {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: comment.clone(),
+        });
+    }
+
+    tag.write_to_path(path, Version::Id3v24)
+        .with_context(|| format!("Failed to write ID3 tags to {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_from_text_short() {
+        assert_eq!(title_from_text("Hello world"), "Hello world");
+        assert_eq!(title_from_text("\n  First line\nSecond"), "First line");
+    }
+
+    #[test]
+    fn test_title_from_text_truncates() {
+        let text = "a".repeat(100);
+        let title = title_from_text(&text);
+        assert_eq!(title.chars().count(), TITLE_MAX_CHARS + 3);
+        assert!(title.ends_with("..."));
+    }
+
+    #[test]
+    fn test_supports_id3() {
+        assert!(supports_id3(Path::new("out.mp3")));
+        assert!(supports_id3(Path::new("OUT.MP3")));
+        assert!(!supports_id3(Path::new("out.wav")));
+        assert!(!supports_id3(Path::new("out")));
+    }
+
+    #[test]
+    fn test_write_id3_tags_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tagged.mp3");
+        std::fs::write(&path, [0xFFu8, 0xFB, 0x90, 0x00]).unwrap();
+
+        let tags = AudioTags {
+            title: Some("Hello".to_string()),
+            artist: Some("Brian".to_string()),
+            album: Some("Podcast".to_string()),
+            comment: Some("model: eleven_multilingual_v2".to_string()),
+        };
+        write_id3_tags(&path, &tags).unwrap();
+
+        let read = Tag::read_from_path(&path).unwrap();
+        assert_eq!(read.title(), Some("Hello"));
+        assert_eq!(read.artist(), Some("Brian"));
+        assert_eq!(read.album(), Some("Podcast"));
+        assert_eq!(
+            read.comments().next().unwrap().text,
+            "model: eleven_multilingual_v2"
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! Input text cleaning for `tts --clean-input`
+//!
+//! Markdown, HTML, links and emoji read aloud verbatim ("hash hash
+//! Installation", "h t t p s colon slash slash"), so they are stripped or
+//! rewritten into plain sentences before synthesis.
+
+use crate::cli::CleanRule;
+use crate::output::print_info;
+use anyhow::Result;
+use std::sync::OnceLock;
+
+/// Rules from config (`clean_input`), used when no --clean-input is given
+static DEFAULT_RULES: OnceLock<Vec<CleanRule>> = OnceLock::new();
+
+pub fn set_default_rules(rules: Vec<CleanRule>) {
+    let _ = DEFAULT_RULES.set(rules);
+}
+
+/// Rules to apply: the flag if given, otherwise the configured default
+fn effective_rules(rules: &[CleanRule]) -> Vec<CleanRule> {
+    let rules = if rules.is_empty() {
+        DEFAULT_RULES.get().map(Vec::as_slice).unwrap_or_default()
+    } else {
+        rules
+    };
+    if rules.contains(&CleanRule::None) {
+        Vec::new()
+    } else {
+        rules.to_vec()
+    }
+}
+
+/// Clean input text with the given (or configured) rules
+///
+/// Returns the text unchanged when no rules apply, and fails when cleaning
+/// leaves nothing to synthesize.
+pub fn clean_input_text(text: &str, rules: &[CleanRule]) -> Result<String> {
+    let rules = effective_rules(rules);
+    if rules.is_empty() {
+        return Ok(text.to_string());
+    }
+    let cleaned = clean_text(text, &rules);
+    if cleaned.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No text left to synthesize after cleaning input"
+        ));
+    }
+    if cleaned.len() != text.len() {
+        print_info(&format!(
+            "Cleaned input: {} -> {} characters",
+            text.chars().count(),
+            cleaned.chars().count()
+        ));
+    }
+    Ok(cleaned)
+}
+
+fn clean_text(text: &str, rules: &[CleanRule]) -> String {
+    let mut text = text.replace("\r\n", "\n");
+    // HTML first: Markdown may embed tags, and links inside tags are attributes
+    if rules.contains(&CleanRule::Html) {
+        text = strip_html(&text);
+    }
+    // Markdown before URLs so `[label](url)` keeps its label
+    if rules.contains(&CleanRule::Markdown) {
+        text = strip_markdown(&text);
+    }
+    if rules.contains(&CleanRule::Urls) {
+        text = strip_urls(&text);
+    }
+    if rules.contains(&CleanRule::Emojis) {
+        text = text.chars().filter(|c| !is_emoji(*c)).collect();
+    }
+    normalize_whitespace(&text)
+}
+
+/// Plain text from an HTML fragment (feed item bodies)
+pub(crate) fn html_to_text(html: &str) -> String {
+    clean_text(html, &[CleanRule::Html])
+}
+
+/// Trim lines, collapse runs of spaces and blank lines
+fn normalize_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 0 { "\n\n" } else { "\n" });
+        }
+        blank_run = 0;
+        out.push_str(&line);
+    }
+    out
+}
+
+fn strip_markdown(text: &str) -> String {
+    let mut out = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+
+        // Fenced code blocks are dropped entirely
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+
+        if is_table_separator(trimmed) {
+            continue;
+        }
+        if is_rule_line(trimmed) {
+            out.push(String::new());
+            continue;
+        }
+
+        let mut content = trimmed;
+        while let Some(rest) = content.strip_prefix('>') {
+            content = rest.trim_start();
+        }
+
+        let heading = content.starts_with('#');
+        if heading {
+            content = content.trim_start_matches('#').trim();
+        }
+        content = strip_list_marker(content);
+
+        let mut line = if content.starts_with('|') {
+            // Table row: read cells as a list
+            content
+                .trim_matches('|')
+                .split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            content.to_string()
+        };
+        line = strip_inline_markdown(&line);
+
+        // Headings read as their own sentence
+        if heading && !line.is_empty() && !line.ends_with(['.', '!', '?', ':']) {
+            line.push('.');
+        }
+        out.push(line);
+    }
+    out.join("\n")
+}
+
+fn is_rule_line(line: &str) -> bool {
+    let compact: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && matches!(compact[0], '-' | '*' | '_')
+        && compact.iter().all(|&c| c == compact[0])
+}
+
+fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|')
+        && line.contains('-')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// Remove a leading bullet (`-`, `*`, `+`) or number (`1.`, `1)`)
+fn strip_list_marker(line: &str) -> &str {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return rest.trim_start();
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return rest.trim_start();
+        }
+    }
+    line
+}
+
+/// Links and images become their label; emphasis and code markers are dropped
+fn strip_inline_markdown(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+
+        let link_start = match (c, next) {
+            ('!', Some('[')) => Some(i + 1),
+            ('[', _) => Some(i),
+            _ => None,
+        };
+        if let Some((label, end)) = link_start.and_then(|start| parse_link(&chars, start)) {
+            out.push_str(&label);
+            i = end;
+            continue;
+        }
+
+        let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        let spaced = |c: Option<char>| c.is_none_or(char::is_whitespace);
+        let drop = match c {
+            '`' => true,
+            // `a * b` stays; `*bold*` and `**bold**` lose their markers
+            '*' => !(spaced(prev) && spaced(next)),
+            // snake_case identifiers keep their underscores
+            '_' => !(is_word(prev) && is_word(next)),
+            '~' => next == Some('~') || prev == Some('~'),
+            _ => false,
+        };
+        if !drop {
+            out.push(c);
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Parse `[label](target)` starting at `[`; returns the label and the index after `)`
+fn parse_link(chars: &[char], open: usize) -> Option<(String, usize)> {
+    let close = open + chars[open..].iter().position(|&c| c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + 1 + chars[close + 1..].iter().position(|&c| c == ')')?;
+    Some((chars[open + 1..close].iter().collect(), end + 1))
+}
+
+/// Tags that start a new line when removed
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "br",
+    "div",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "table",
+    "blockquote",
+    "section",
+    "article",
+    "hr",
+];
+
+fn strip_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        let is_tag = after
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        let Some(end) = after.find('>').filter(|_| is_tag) else {
+            out.push_str(&rest[..=start]);
+            rest = after;
+            continue;
+        };
+        out.push_str(&rest[..start]);
+
+        let tag = &after[..end];
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        rest = &after[end + 1..];
+
+        // Script and style bodies are not text
+        if !tag.starts_with('/') && (name == "script" || name == "style") {
+            let closing = format!("</{}", name);
+            rest = match rest
+                .as_bytes()
+                .windows(closing.len())
+                .position(|w| w.eq_ignore_ascii_case(closing.as_bytes()))
+            {
+                Some(pos) => rest[pos..]
+                    .find('>')
+                    .map(|gt| &rest[pos + gt + 1..])
+                    .unwrap_or(""),
+                None => "",
+            };
+            continue;
+        }
+        if BLOCK_TAGS.contains(&name.as_str()) {
+            out.push('\n');
+        } else {
+            out.push(' ');
+        }
+    }
+    out.push_str(rest);
+    decode_entities(&out)
+}
+
+/// Decode named and numeric (`&#8217;`, `&#x2019;`) character references
+pub(crate) fn decode_entities(text: &str) -> String {
+    const NAMED: &[(&str, &str)] = &[
+        ("nbsp", " "),
+        ("amp", "&"),
+        ("lt", "<"),
+        ("gt", ">"),
+        ("quot", "\""),
+        ("apos", "'"),
+        ("mdash", "\u{2014}"),
+        ("ndash", "\u{2013}"),
+        ("hellip", "\u{2026}"),
+        ("lsquo", "\u{2018}"),
+        ("rsquo", "\u{2019}"),
+        ("ldquo", "\u{201C}"),
+        ("rdquo", "\u{201D}"),
+    ];
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| {
+                let name = &rest[1..1 + end];
+                let value = match name.strip_prefix('#') {
+                    Some(code) => {
+                        let number = match code.strip_prefix(['x', 'X']) {
+                            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                            None => code.parse().ok(),
+                        };
+                        number.and_then(char::from_u32).map(String::from)
+                    }
+                    None => NAMED
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, v)| v.to_string()),
+                };
+                value.map(|v| (v, end + 2))
+            });
+        match decoded {
+            Some((value, len)) => {
+                out.push_str(&value);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_urls(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let mut words: Vec<String> = Vec::new();
+            for word in line.split(' ') {
+                let body = word.trim_start_matches(['(', '<', '[', '"', '\'']);
+                let lower = body.to_lowercase();
+                if !["http://", "https://", "www."]
+                    .iter()
+                    .any(|p| lower.starts_with(p))
+                {
+                    words.push(word.to_string());
+                    continue;
+                }
+                // Keep sentence punctuation that followed the link
+                let trailing = &body[body.trim_end_matches(URL_TRAILING).len()..];
+                if let Some(last) = words.iter_mut().rev().find(|w| !w.is_empty()) {
+                    if !last.ends_with(URL_TRAILING) {
+                        last.push_str(trailing);
+                    }
+                }
+            }
+            words.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Punctuation after a link that belongs to the sentence
+const URL_TRAILING: &[char] = &['.', ',', ';', ':', '!', '?'];
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, flags, skin tones
+            | 0x2600..=0x27BF // symbols and dingbats
+            | 0x2300..=0x23FF // watches, hourglasses
+            | 0x2B00..=0x2BFF // stars, arrows
+            | 0xFE0F // emoji presentation selector
+            | 0x200D // zero-width joiner
+            | 0x20E3 // keycap
+            | 0xE0020..=0xE007F // tag sequences
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_becomes_plain_sentences() {
+        let input = "# Getting started\n\nInstall the **CLI** with `cargo`:\n\n```sh\ncargo install elevenlabs-cli\n```\n\n- Read the [docs](https://example.com/docs)\n- Set `api_key`\n\n---\n\n> Note: *snake_case* names stay.\n";
+        let cleaned = clean_text(input, &[CleanRule::Markdown]);
+        assert_eq!(
+            cleaned,
+            "Getting started.\n\nInstall the CLI with cargo:\n\nRead the docs\nSet api_key\n\nNote: snake_case names stay."
+        );
+    }
+
+    #[test]
+    fn test_markdown_tables_and_math() {
+        let input = "| Plan | Price |\n|------|------:|\n| Pro | $99 |\n\n2 * 3 = 6";
+        assert_eq!(
+            clean_text(input, &[CleanRule::Markdown]),
+            "Plan, Price\nPro, $99\n\n2 * 3 = 6"
+        );
+    }
+
+    #[test]
+    fn test_urls_removed_keeping_punctuation() {
+        let input = "See https://example.com/a?b=1. Or (www.example.org) now, http://x.io, ok";
+        assert_eq!(clean_text(input, &[CleanRule::Urls]), "See. Or now, ok");
+    }
+
+    #[test]
+    fn test_html_tags_entities_and_scripts() {
+        let input = "<h1>Title</h1><p>Fish &amp; chips &lt;3</p><script>alert(1)</script><p>a<b>b</b>c</p> 1 < 2";
+        assert_eq!(
+            clean_text(input, &[CleanRule::Html]),
+            "Title\n\nFish & chips <3\n\na b c\n1 < 2"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("It&#8217;s &amp;lt; &#x2014; AT&T &bogus;"),
+            "It\u{2019}s &lt; \u{2014} AT&T &bogus;"
+        );
+    }
+
+    #[test]
+    fn test_emojis_removed() {
+        let input = "Launch day 🚀🎉! Thumbs 👍🏽 up ❤️";
+        assert_eq!(
+            clean_text(input, &[CleanRule::Emojis]),
+            "Launch day ! Thumbs up"
+        );
+    }
+
+    #[test]
+    fn test_none_disables_rules() {
+        assert!(effective_rules(&[CleanRule::Markdown, CleanRule::None]).is_empty());
+        assert_eq!(
+            clean_input_text("# keep", &[CleanRule::None]).unwrap(),
+            "# keep"
+        );
+        assert!(clean_input_text("```\ncode\n```", &[CleanRule::Markdown]).is_err());
+    }
+}
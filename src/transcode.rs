@@ -0,0 +1,245 @@
+//! Converting local audio the API can't read before it is uploaded
+//!
+//! Voice cloning and speech-to-text accept common containers only. Inputs are
+//! identified by their leading bytes rather than their extension, and AIFF,
+//! AMR, CAF, Sun AU and WMA files, as well as WAV files with a compressed codec
+//! or a sample rate outside 8-48 kHz, are converted to a temporary file with a
+//! notice instead of being sent on to an opaque API error. 16-bit WAV is
+//! resampled natively; everything else goes through ffmpeg. Formats that
+//! aren't recognised are uploaded unchanged.
+
+use crate::output::print_info;
+use crate::pcm::{parse_wav, pcm16_to_wav, resample, samples_to_pcm16};
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Read;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Sample rates the upload endpoints accept for WAV
+const SAMPLE_RATES: RangeInclusive<u32> = 8_000..=48_000;
+
+/// Rate converted files are written at
+const TARGET_RATE: u32 = 44_100;
+
+/// Bytes read to identify a file; enough for a WAV header with extra chunks
+const SNIFF_LEN: u64 = 4096;
+
+/// Extensions of formats that are converted rather than uploaded as they are
+pub const CONVERTIBLE_EXTENSIONS: &[&str] = &["aif", "aiff", "aifc", "amr", "au", "caf", "wma"];
+
+/// What to do with a file, judged from its leading bytes
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    Upload,
+    /// Convert first; describes what was found
+    Convert(String),
+}
+
+fn inspect(header: &[u8]) -> Verdict {
+    let form = header.get(8..12);
+    let found = if header.starts_with(b"RIFF") && form == Some(b"WAVE") {
+        return inspect_wav(header);
+    } else if header.starts_with(b"FORM") && matches!(form, Some(b"AIFF" | b"AIFC")) {
+        "AIFF"
+    } else if header.starts_with(b"#!AMR") {
+        "AMR"
+    } else if header.starts_with(b"caff") {
+        "CAF"
+    } else if header.starts_with(b".snd") {
+        "Sun AU"
+    } else if header.starts_with(&[0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11]) {
+        "WMA"
+    } else {
+        return Verdict::Upload;
+    };
+    Verdict::Convert(found.to_string())
+}
+
+/// Check the codec and sample rate in a WAV file's `fmt ` chunk
+fn inspect_wav(header: &[u8]) -> Verdict {
+    let mut pos = 12;
+    while pos + 8 <= header.len() {
+        let id = &header[pos..pos + 4];
+        let len = u32::from_le_bytes([
+            header[pos + 4],
+            header[pos + 5],
+            header[pos + 6],
+            header[pos + 7],
+        ]) as usize;
+        if id == b"fmt " {
+            let Some(body) = header.get(pos + 8..pos + 16) else {
+                break;
+            };
+            let tag = u16::from_le_bytes([body[0], body[1]]);
+            let rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+            // Integer PCM, float, or WAVE_FORMAT_EXTENSIBLE
+            if !matches!(tag, 1 | 3 | 0xFFFE) {
+                return Verdict::Convert(format!("WAV with codec 0x{:04x}", tag));
+            }
+            if !SAMPLE_RATES.contains(&rate) {
+                return Verdict::Convert(format!("{} Hz WAV", rate));
+            }
+            break;
+        }
+        pos += 8 + len + (len & 1);
+    }
+    Verdict::Upload
+}
+
+/// A file ready to upload: the original, or a converted copy that is
+/// removed when this is dropped
+pub struct UploadFile {
+    path: PathBuf,
+    _dir: Option<TempDir>,
+}
+
+impl UploadFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Convert `path` to a format the API accepts if it needs it. Missing
+/// files are passed through for the usual pre-flight checks to report.
+pub fn prepare_upload(path: &Path) -> Result<UploadFile> {
+    let unchanged = || UploadFile {
+        path: path.to_path_buf(),
+        _dir: None,
+    };
+    if !path.is_file() {
+        return Ok(unchanged());
+    }
+    let mut header = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(SNIFF_LEN).read_to_end(&mut header))
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let Verdict::Convert(found) = inspect(&header) else {
+        return Ok(unchanged());
+    };
+
+    let dir = tempfile::Builder::new()
+        .prefix("elevenlabs-upload-")
+        .tempdir()?;
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "audio".to_string());
+    let data = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let converted = match parse_wav(&data) {
+        Some((samples, rate)) => {
+            let converted = dir.path().join(format!("{}.wav", stem));
+            let pcm = samples_to_pcm16(&resample(&samples, rate, TARGET_RATE));
+            std::fs::write(&converted, pcm16_to_wav(&pcm, TARGET_RATE))?;
+            converted
+        }
+        None => {
+            let converted = dir.path().join(format!("{}.flac", stem));
+            ffmpeg_to_flac(path, &converted)?;
+            converted
+        }
+    };
+    print_info(&format!(
+        "{} is {}, which the API doesn't accept; converted it to {} Hz {}",
+        path.display(),
+        found,
+        TARGET_RATE,
+        if converted.extension().is_some_and(|e| e == "wav") {
+            "WAV"
+        } else {
+            "FLAC"
+        }
+    ));
+    Ok(UploadFile {
+        path: converted,
+        _dir: Some(dir),
+    })
+}
+
+fn ffmpeg_to_flac(input: &Path, output: &Path) -> Result<()> {
+    let result = Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-i"])
+        .arg(input)
+        .args(["-ac", "1", "-ar", &TARGET_RATE.to_string()])
+        .arg(output)
+        .output()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "ffmpeg not found in PATH. Install ffmpeg to convert {}",
+                input.display()
+            )
+        })?;
+    if !result.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg could not convert {}: {}",
+            input.display(),
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcm::wav_header;
+
+    fn wav_with(tag: u16, rate: u32) -> Vec<u8> {
+        let mut wav = wav_header(rate, 1, 0).to_vec();
+        wav[20..22].copy_from_slice(&tag.to_le_bytes());
+        wav
+    }
+
+    #[test]
+    fn test_inspect() {
+        assert_eq!(inspect(&wav_with(1, 44_100)), Verdict::Upload);
+        assert_eq!(inspect(&wav_with(3, 48_000)), Verdict::Upload);
+        assert_eq!(
+            inspect(&wav_with(1, 96_000)),
+            Verdict::Convert("96000 Hz WAV".to_string())
+        );
+        assert_eq!(
+            inspect(&wav_with(0x11, 8_000)),
+            Verdict::Convert("WAV with codec 0x0011".to_string())
+        );
+        assert_eq!(
+            inspect(b"FORM\0\0\0\0AIFFCOMM"),
+            Verdict::Convert("AIFF".to_string())
+        );
+        assert_eq!(inspect(b"#!AMR\n"), Verdict::Convert("AMR".to_string()));
+        assert_eq!(inspect(b"ID3\x04\0"), Verdict::Upload);
+        assert_eq!(inspect(b"fLaC"), Verdict::Upload);
+        assert_eq!(inspect(b""), Verdict::Upload);
+    }
+
+    #[test]
+    fn test_prepare_upload_resamples_wav_natively() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("take.wav");
+        let pcm = samples_to_pcm16(&[1_000; 960]);
+        let mut wav = wav_header(96_000, 1, pcm.len() as u32).to_vec();
+        wav.extend_from_slice(&pcm);
+        std::fs::write(&path, wav).unwrap();
+
+        let upload = prepare_upload(&path).unwrap();
+        assert_ne!(upload.path(), path);
+        assert_eq!(upload.path().file_name().unwrap(), "take.wav");
+        let (samples, rate) = parse_wav(&std::fs::read(upload.path()).unwrap()).unwrap();
+        assert_eq!(rate, TARGET_RATE);
+        assert_eq!(samples.len(), 441);
+
+        let converted = upload.path().to_path_buf();
+        drop(upload);
+        assert!(!converted.exists());
+    }
+
+    #[test]
+    fn test_prepare_upload_keeps_supported_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("take.mp3");
+        std::fs::write(&path, b"ID3\x04\0\0\0\0\0\0").unwrap();
+        assert_eq!(prepare_upload(&path).unwrap().path(), path);
+    }
+}
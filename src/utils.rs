@@ -1,9 +1,12 @@
 //! Utility functions for the ElevenLabs CLI
 
-use anyhow::Result;
+use crate::cli::Organize;
+use anyhow::{Context, Result};
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Default HTTP request timeout in seconds
 pub const DEFAULT_TIMEOUT_SECS: u64 = 300;
@@ -14,6 +17,9 @@ pub const MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
 /// Maximum text length for TTS (50,000 characters)
 pub const MAX_TTS_TEXT_LENGTH: usize = 50_000;
 
+/// How long to wait for another process to release a state file lock
+const FILE_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Write bytes to a file
 pub fn write_bytes_to_file(bytes: &[u8], path: &Path) -> Result<()> {
     let mut file = File::create(path)?;
@@ -21,6 +27,61 @@ pub fn write_bytes_to_file(bytes: &[u8], path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Replace a file through a temporary file in the same directory, so readers
+/// and crashed writers never leave it half-written
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut temp = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Failed to create a temporary file in {}", dir.display()))?;
+    temp.write_all(contents)?;
+    temp.as_file().sync_all()?;
+    temp.persist(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Run `f` while holding an exclusive advisory lock on `<path>.lock`, so
+/// concurrent CLI processes take turns on read-modify-write updates
+pub fn with_file_lock<T>(path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let mut lock_name = path.file_name().unwrap_or_default().to_os_string();
+    lock_name.push(".lock");
+    let lock_path = path.with_file_name(lock_name);
+    if let Some(dir) = lock_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let lock_file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+
+    let started = Instant::now();
+    loop {
+        match lock_file.try_lock() {
+            Ok(()) => break,
+            Err(std::fs::TryLockError::WouldBlock) if started.elapsed() < FILE_LOCK_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(std::fs::TryLockError::WouldBlock) => {
+                return Err(anyhow::anyhow!(
+                    "Timed out waiting for another elevenlabs process to release {}",
+                    lock_path.display()
+                ));
+            }
+            Err(std::fs::TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", lock_path.display()));
+            }
+        }
+    }
+    // The lock is released when `lock_file` is dropped
+    f()
+}
+
 /// Get file extension from output format
 pub fn format_to_extension(format: &str) -> &str {
     if format.starts_with("mp3") {
@@ -82,6 +143,12 @@ pub fn parse_output_format(format: &str) -> Result<elevenlabs_rs::OutputFormat>
 pub fn get_input_text(text: Option<String>, file: Option<String>) -> Result<String> {
     match (text, file) {
         (Some(t), _) => Ok(t),
+        (_, Some(f)) if f == "-" => {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+                .context("Failed to read text from stdin")?;
+            Ok(content)
+        }
         (_, Some(f)) => {
             let content = std::fs::read_to_string(&f)?;
             Ok(content)
@@ -92,14 +159,131 @@ pub fn get_input_text(text: Option<String>, file: Option<String>) -> Result<Stri
 
 /// Generate default output filename
 pub fn generate_output_filename(prefix: &str, extension: &str) -> String {
+    format!("{}_{}.{}", prefix, unix_now(), extension)
+}
+
+/// Where generated files without an explicit output path are written
+#[derive(Debug, Clone, Default)]
+pub struct OutputLayout {
+    /// Base directory (current directory when unset)
+    pub dir: Option<PathBuf>,
+    /// Subdirectory levels, outermost first
+    pub organize: Vec<Organize>,
+    /// Project name used by `by-project`
+    pub project: Option<String>,
+}
+
+static OUTPUT_LAYOUT: OnceLock<OutputLayout> = OnceLock::new();
+
+/// Set the output layout for this run (from `--output-dir`/`--organize` or config)
+pub fn set_output_layout(layout: OutputLayout) {
+    let _ = OUTPUT_LAYOUT.set(layout);
+}
+
+impl OutputLayout {
+    /// Directory for a file generated at `now_secs` with the given voice
+    fn directory(&self, voice: Option<&str>, now_secs: u64) -> PathBuf {
+        let mut dir = self.dir.clone().unwrap_or_default();
+        for level in &self.organize {
+            let name = match level {
+                Organize::Date => {
+                    let (year, month) = year_month(now_secs);
+                    format!("{:04}-{:02}", year, month)
+                }
+                Organize::Voice => voice.unwrap_or("unknown-voice").to_string(),
+                Organize::Project => self.project.as_deref().unwrap_or("unsorted").to_string(),
+            };
+            dir.push(sanitize_path_component(&name));
+        }
+        dir
+    }
+}
+
+/// Default path for a generated file, placed according to the output layout.
+/// Creates the target directory when needed.
+pub fn default_output_path(filename: &str, voice: Option<&str>) -> Result<String> {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    let timestamp = SystemTime::now()
+    let Some(layout) = OUTPUT_LAYOUT.get() else {
+        return Ok(filename.to_string());
+    };
+    let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
+    let dir = layout.directory(voice, now);
+    if dir.as_os_str().is_empty() {
+        return Ok(filename.to_string());
+    }
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+    Ok(dir.join(filename).to_string_lossy().to_string())
+}
 
-    format!("{}_{}.{}", prefix, timestamp, extension)
+/// Expand a leading `~` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    let home = || directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf());
+    if path == "~" {
+        if let Some(home) = home() {
+            return home;
+        }
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = home() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Make a name safe to use as a single directory component
+pub fn sanitize_path_component(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match cleaned.as_str() {
+        "" | "." | ".." => "_".to_string(),
+        _ => cleaned,
+    }
+}
+
+/// Days since the Unix epoch for a civil (UTC) date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Inverse of `year_month`, from the same date algorithms
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil (UTC) year and month for a Unix timestamp
+fn year_month(unix_secs: u64) -> (i64, u32) {
+    let (year, month, _) = civil_date(unix_secs);
+    (year, month)
+}
+
+/// Civil (UTC) year, month and day for a Unix timestamp
+pub fn civil_date(unix_secs: u64) -> (i64, u32, u32) {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms
+    let days = (unix_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
 }
 
 /// Check if file exists and confirm overwrite
@@ -114,14 +298,10 @@ pub fn confirm_overwrite(path: &Path, assume_yes: bool) -> Result<bool> {
 
     crate::output::print_warning(&format!("File '{}' already exists.", path.display()));
 
-    if let Ok(confirm) = dialoguer::Confirm::new()
-        .with_prompt("Do you want to overwrite?")
-        .default(false)
-        .interact()
-    {
-        Ok(confirm)
-    } else {
-        Ok(false)
+    match crate::prompt::confirm("Do you want to overwrite?") {
+        Ok(confirm) => Ok(confirm),
+        Err(e) if crate::prompt::is_input_required(&e) => Err(e),
+        Err(_) => Ok(false),
     }
 }
 
@@ -164,11 +344,247 @@ pub fn validate_text_length(text: &str) -> Result<()> {
     Ok(())
 }
 
+/// Parse a relative duration such as `30d`, `12h`, `2w`, `45m` or `90s` (bare numbers are days)
+pub fn parse_duration_spec(spec: &str) -> Result<std::time::Duration> {
+    let spec = spec.trim();
+    let split = spec
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(spec.len());
+    let (number, unit) = spec.split_at(split);
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid duration '{}'. Use e.g. 30d, 12h, 2w", spec))?;
+
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid duration unit '{}'. Valid units are: s, m, h, d, w",
+                unit
+            ))
+        }
+    };
+
+    let secs = value
+        .checked_mul(multiplier)
+        .ok_or_else(|| anyhow::anyhow!("Duration '{}' is too long", spec))?;
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Parse a point in time as Unix seconds: a `YYYY-MM-DD` date (UTC midnight) or a
+/// relative duration such as `7d` meaning that long before `now_unix`
+pub fn parse_time_spec(spec: &str, now_unix: u64) -> Result<u64> {
+    let spec = spec.trim();
+    let parts: Vec<&str> = spec.split('-').collect();
+    if let [year, month, day] = parts.as_slice() {
+        let date = (
+            year.parse::<i64>(),
+            month.parse::<u32>(),
+            day.parse::<u32>(),
+        );
+        return match date {
+            (Ok(year @ 1970..=9999), Ok(month @ 1..=12), Ok(day @ 1..=31)) => {
+                Ok((days_from_civil(year, month, day) * 86_400) as u64)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Invalid date '{}'. Use YYYY-MM-DD or a relative time such as 7d",
+                spec
+            )),
+        };
+    }
+    let ago = parse_duration_spec(spec)?;
+    Ok(now_unix.saturating_sub(ago.as_secs()))
+}
+
+/// What one check of a polled operation found
+#[derive(Debug, PartialEq)]
+pub enum PollStatus<T> {
+    /// Still running; the text (a status such as `dubbing (40%)`) is shown
+    /// next to the spinner
+    Pending(String),
+    Done(T),
+}
+
+/// Run `check` every `interval` until it reports [`PollStatus::Done`],
+/// showing `message` and the last status on a spinner. Fails when a check
+/// fails or once `timeout` has passed.
+pub async fn poll_until<T, F, Fut>(
+    message: &str,
+    interval: Duration,
+    timeout: Option<Duration>,
+    mut check: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<PollStatus<T>>>,
+{
+    let started = Instant::now();
+    let mut progress = None;
+    loop {
+        let status = match check().await? {
+            PollStatus::Done(value) => return Ok(value),
+            PollStatus::Pending(status) => status,
+        };
+        if timeout.is_some_and(|t| started.elapsed() >= t) {
+            return Err(anyhow::anyhow!(
+                "Timed out after {}s: {} (still {})",
+                started.elapsed().as_secs(),
+                message,
+                status
+            ));
+        }
+        progress
+            .get_or_insert_with(|| crate::output::Progress::spinner(message))
+            .set_message(&format!("{}: {}", message, status));
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Seconds since the Unix epoch (0 if the clock is set before it)
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format an elapsed time as a compact relative string, e.g. `45s ago`, `3h ago`, `12d ago`
+pub fn format_relative_time(elapsed_secs: u64) -> String {
+    match elapsed_secs {
+        0..=9 => "just now".to_string(),
+        10..=59 => format!("{}s ago", elapsed_secs),
+        60..=3_599 => format!("{}m ago", elapsed_secs / 60),
+        3_600..=86_399 => format!("{}h ago", elapsed_secs / 3_600),
+        _ => format!("{}d ago", elapsed_secs / 86_400),
+    }
+}
+
+/// Escape a value for inclusion in a CSV row
+pub fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Match `text` against a pattern where `*` stands for any run of characters
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return text == pattern;
+    }
+    if !text.starts_with(first) || text.len() < first.len() + last.len() {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    if !text.ends_with(last) {
+        return false;
+    }
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Infer a JSON value for a dynamic variable: booleans and numbers keep their type,
+/// everything else is sent as a string. A value is only a number when it reads
+/// back the same, so IDs like "00123" or "+15551234" stay strings.
+pub fn dynamic_var_value(value: &str) -> serde_json::Value {
+    match value {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ => {
+            let number = value
+                .parse::<i64>()
+                .map(serde_json::Number::from)
+                .ok()
+                .or_else(|| {
+                    value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                })
+                .filter(|n| n.to_string() == value);
+            match number {
+                Some(n) => serde_json::Value::Number(n),
+                None => serde_json::Value::String(value.to_string()),
+            }
+        }
+    }
+}
+
+/// Parse repeated `key=value` agent dynamic variables into a JSON object
+pub fn parse_dynamic_vars(vars: &[String]) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let mut map = serde_json::Map::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid dynamic variable '{}'. Use key=value", var))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Invalid dynamic variable '{}': key must not be empty",
+                var
+            ));
+        }
+        map.insert(key.to_string(), dynamic_var_value(value));
+    }
+    Ok(map)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("/docs/*", "/docs/intro"));
+        assert!(wildcard_match("*/changelog*", "/docs/changelog/2024"));
+        assert!(wildcard_match("/a*b*c", "/a-b-c"));
+        assert!(!wildcard_match("/a*b*c", "/a-c"));
+        assert!(!wildcard_match("/docs/*", "/blog/docs/x"));
+        assert!(!wildcard_match("/ab*ba", "/aba"));
+        assert!(wildcard_match("/exact", "/exact"));
+    }
+
+    #[test]
+    fn test_locked_updates_do_not_lose_writes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state").join("counter.txt");
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..20 {
+                        with_file_lock(&path, || {
+                            let count: u32 = std::fs::read_to_string(&path)
+                                .map(|s| s.trim().parse().unwrap())
+                                .unwrap_or(0);
+                            write_atomic(&path, (count + 1).to_string().as_bytes())
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "160");
+        assert!(dir.path().join("state").join("counter.txt.lock").exists());
+    }
+
     #[test]
     fn test_format_to_extension() {
         assert_eq!(format_to_extension("mp3_44100_128"), "mp3");
@@ -227,4 +643,131 @@ mod tests {
         let long_text = "x".repeat(60_000);
         assert!(validate_text_length(&long_text).is_err());
     }
+
+    #[test]
+    fn test_parse_duration_spec() {
+        assert_eq!(parse_duration_spec("90s").unwrap().as_secs(), 90);
+        assert_eq!(parse_duration_spec("12h").unwrap().as_secs(), 12 * 3600);
+        assert_eq!(parse_duration_spec("30d").unwrap().as_secs(), 30 * 86400);
+        assert_eq!(parse_duration_spec("2w").unwrap().as_secs(), 14 * 86400);
+        assert!(parse_duration_spec("d").is_err());
+        assert!(parse_duration_spec("10y").is_err());
+        assert!(parse_duration_spec("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_format_relative_time() {
+        assert_eq!(format_relative_time(3), "just now");
+        assert_eq!(format_relative_time(45), "45s ago");
+        assert_eq!(format_relative_time(125), "2m ago");
+        assert_eq!(format_relative_time(3 * 3_600 + 5), "3h ago");
+        assert_eq!(format_relative_time(12 * 86_400), "12d ago");
+    }
+
+    #[test]
+    fn test_output_layout_directory() {
+        // 2025-06-15 12:00:00 UTC
+        let june = 1_749_988_800;
+        assert_eq!(year_month(june), (2025, 6));
+        assert_eq!(year_month(0), (1970, 1));
+        assert_eq!(year_month(951_782_400), (2000, 2)); // 2000-02-29
+        assert_eq!(civil_date(951_782_400), (2000, 2, 29));
+        assert_eq!(civil_date(0), (1970, 1, 1));
+
+        let layout = OutputLayout {
+            dir: Some(PathBuf::from("library")),
+            organize: vec![Organize::Date, Organize::Voice],
+            project: None,
+        };
+        assert_eq!(
+            layout.directory(Some("Rachel"), june),
+            Path::new("library").join("2025-06").join("Rachel")
+        );
+        assert_eq!(
+            layout.directory(Some("a/b"), june),
+            Path::new("library").join("2025-06").join("a_b")
+        );
+
+        let by_project = OutputLayout {
+            organize: vec![Organize::Project],
+            ..Default::default()
+        };
+        assert_eq!(by_project.directory(None, june), Path::new("unsorted"));
+        assert_eq!(
+            OutputLayout::default().directory(None, june),
+            PathBuf::new()
+        );
+    }
+
+    #[test]
+    fn test_parse_time_spec() {
+        let now = 1_749_988_800; // 2025-06-15 12:00:00 UTC
+        assert_eq!(parse_time_spec("2025-06-15", now).unwrap(), 1_749_945_600);
+        assert_eq!(parse_time_spec("1970-01-01", now).unwrap(), 0);
+        assert_eq!(parse_time_spec("2000-02-29", now).unwrap(), 951_782_400);
+        assert_eq!(parse_time_spec("7d", now).unwrap(), now - 7 * 86_400);
+        assert_eq!(parse_time_spec("12h", now).unwrap(), now - 12 * 3_600);
+        assert!(parse_time_spec("2025-13-01", now).is_err());
+        assert!(parse_time_spec("9223372036854775807-01-01", now).is_err());
+        assert!(parse_time_spec("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_parse_dynamic_vars() {
+        let vars = vec![
+            "customer_name=Ada Lovelace".to_string(),
+            "balance=42.5".to_string(),
+            "orders=3".to_string(),
+            "vip=true".to_string(),
+            "note=a=b".to_string(),
+        ];
+        let map = parse_dynamic_vars(&vars).unwrap();
+        assert_eq!(map["customer_name"], "Ada Lovelace");
+        assert_eq!(map["balance"], 42.5);
+        assert_eq!(map["orders"], 3);
+        assert_eq!(map["vip"], true);
+        assert_eq!(map["note"], "a=b");
+
+        assert_eq!(dynamic_var_value("00123"), "00123");
+        assert_eq!(dynamic_var_value("+15551234"), "+15551234");
+        assert_eq!(dynamic_var_value("1.50"), "1.50");
+        assert_eq!(dynamic_var_value("-7"), -7);
+
+        assert!(parse_dynamic_vars(&["missing".to_string()]).is_err());
+        assert!(parse_dynamic_vars(&["=value".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_poll_until() {
+        let mut checks = 0;
+        let value = poll_until("Waiting", Duration::ZERO, None, || {
+            checks += 1;
+            let status = if checks < 3 {
+                PollStatus::Pending("running".to_string())
+            } else {
+                PollStatus::Done(checks)
+            };
+            async move { Ok(status) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, 3);
+
+        let err = poll_until(
+            "Waiting for dub",
+            Duration::ZERO,
+            Some(Duration::ZERO),
+            || async { Ok(PollStatus::<()>::Pending("dubbing".to_string())) },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Waiting for dub (still dubbing)"));
+    }
 }
@@ -1,5 +1,7 @@
 //! Input validation utilities
 
+mod preflight;
 mod voice_settings;
 
+pub use preflight::*;
 pub use voice_settings::*;
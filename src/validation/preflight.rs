@@ -0,0 +1,268 @@
+//! Pre-flight validation run by generation commands before any network call
+//!
+//! These checks catch common argument mistakes (missing files, wrong model
+//! family, text over the model limit) locally instead of spending a request
+//! and credits on an API error.
+
+use crate::output::print_warning;
+use crate::utils::{validate_file_size, validate_text_length};
+use anyhow::Result;
+use std::path::Path;
+
+/// Audio/video file extensions accepted by upload endpoints
+pub const AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "wav", "m4a", "aac", "ogg", "opus", "flac", "webm", "mp4", "mov", "avi", "mkv",
+];
+
+/// Output formats accepted by the generation endpoints
+const OUTPUT_FORMATS: &[&str] = &[
+    "mp3_22050_32",
+    "mp3_44100_32",
+    "mp3_44100_64",
+    "mp3_44100_96",
+    "mp3_44100_128",
+    "mp3_44100_192",
+    "pcm_8000",
+    "pcm_16000",
+    "pcm_22050",
+    "pcm_24000",
+    "pcm_44100",
+    "pcm_48000",
+    "ulaw_8000",
+    "alaw_8000",
+    "opus_48000_32",
+    "opus_48000_64",
+    "opus_48000_96",
+    "opus_48000_128",
+    "opus_48000_192",
+    "wav_8000",
+    "wav_16000",
+    "wav_22050",
+    "wav_24000",
+    "wav_44100",
+    "wav_48000",
+];
+
+/// Speech-to-speech models (voice changer only)
+const STS_MODELS: &[&str] = &["eleven_english_sts_v2", "eleven_multilingual_sts_v2"];
+
+/// Models that only support English
+const ENGLISH_ONLY_MODELS: &[&str] = &[
+    "eleven_monolingual_v1",
+    "eleven_turbo_v2",
+    "eleven_flash_v2",
+];
+
+/// Flash models stream at reduced sample rates and reject 48 kHz PCM
+const FLASH_MODELS: &[&str] = &["eleven_flash_v2", "eleven_flash_v2_5"];
+
+/// Maximum characters per request for a model
+pub fn model_char_limit(model: &str) -> usize {
+    match model {
+        "eleven_v3" => 5_000,
+        "eleven_multilingual_v2" | "eleven_monolingual_v1" | "eleven_multilingual_v1" => 10_000,
+        "eleven_turbo_v2" | "eleven_flash_v2" => 30_000,
+        "eleven_turbo_v2_5" | "eleven_flash_v2_5" => 40_000,
+        _ => crate::utils::MAX_TTS_TEXT_LENGTH,
+    }
+}
+
+/// Approximate credits charged per character of text for a model
+pub fn model_credits_per_char(model: &str) -> f64 {
+    if model.starts_with("eleven_flash_") || model.starts_with("eleven_turbo_") {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Validate an input audio/video file: exists, is a file, non-empty and within
+/// the size limit. An unfamiliar extension only gets a warning, since the API
+/// reads more formats than the list names.
+pub fn validate_input_file(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(anyhow::anyhow!("Not a file: {}", path.display()));
+    }
+
+    validate_file_size(path)?;
+
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !AUDIO_EXTENSIONS.contains(&ext.as_str()) {
+        print_warning(&format!(
+            "File type '{}' of {} may not be supported. Known types: {}",
+            ext,
+            path.display(),
+            AUDIO_EXTENSIONS.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate an output format identifier
+pub fn validate_output_format(format: &str) -> Result<()> {
+    if !OUTPUT_FORMATS.contains(&format) {
+        return Err(anyhow::anyhow!(
+            "Invalid output format '{}'. Valid formats are: {}",
+            format,
+            OUTPUT_FORMATS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Validate that a model can be used for text-to-speech
+pub fn validate_tts_model(model: &str) -> Result<()> {
+    if STS_MODELS.contains(&model) {
+        return Err(anyhow::anyhow!(
+            "Model '{}' is a speech-to-speech model and cannot be used for text-to-speech",
+            model
+        ));
+    }
+    Ok(())
+}
+
+/// Validate that a model can be used for speech-to-speech (voice changer)
+pub fn validate_sts_model(model: &str) -> Result<()> {
+    if !STS_MODELS.contains(&model) {
+        return Err(anyhow::anyhow!(
+            "Model '{}' does not support speech-to-speech. Valid models are: {}",
+            model,
+            STS_MODELS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Validate output format/model compatibility
+pub fn validate_format_for_model(format: &str, model: &str) -> Result<()> {
+    if FLASH_MODELS.contains(&model) && format == "pcm_48000" {
+        return Err(anyhow::anyhow!(
+            "Output format '{}' is not supported by '{}'. Use pcm_44100 or lower",
+            format,
+            model
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a language code against the model's language support
+pub fn validate_language_for_model(language: Option<&str>, model: &str) -> Result<()> {
+    if let Some(lang) = language {
+        if ENGLISH_ONLY_MODELS.contains(&model) && !lang.eq_ignore_ascii_case("en") {
+            return Err(anyhow::anyhow!(
+                "Model '{}' only supports English; use a multilingual model for '{}'",
+                model,
+                lang
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate text length against the model's per-request limit
+pub fn validate_text_for_model(text: &str, model: &str) -> Result<()> {
+    validate_text_length(text)?;
+
+    let limit = model_char_limit(model);
+    let len = text.chars().count();
+    if len > limit {
+        return Err(anyhow::anyhow!(
+            "Text too long for '{}': {} characters (max: {} characters)",
+            model,
+            len,
+            limit
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a WebSocket `chunk_length_schedule` (characters buffered before each chunk)
+pub fn validate_chunk_schedule(schedule: &[u32]) -> Result<()> {
+    if let Some(value) = schedule.iter().find(|v| !(50..=500).contains(*v)) {
+        return Err(anyhow::anyhow!(
+            "Chunk schedule value {} is out of range. Each value must be between 50 and 500",
+            value
+        ));
+    }
+    Ok(())
+}
+
+/// Run all text-to-speech pre-flight checks
+pub fn preflight_tts(text: &str, model: &str, format: &str, language: Option<&str>) -> Result<()> {
+    validate_tts_model(model)?;
+    validate_output_format(format)?;
+    validate_format_for_model(format, model)?;
+    validate_language_for_model(language, model)?;
+    validate_text_for_model(text, model)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_input_file() {
+        let dir = tempdir().unwrap();
+
+        assert!(validate_input_file(&dir.path().join("missing.mp3")).is_err());
+        assert!(validate_input_file(dir.path()).is_err());
+
+        let empty = dir.path().join("empty.mp3");
+        std::fs::write(&empty, b"").unwrap();
+        assert!(validate_input_file(&empty).is_err());
+
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, b"hello").unwrap();
+        assert!(validate_input_file(&text).is_ok());
+
+        let audio = dir.path().join("clip.WAV");
+        std::fs::write(&audio, b"RIFF").unwrap();
+        assert!(validate_input_file(&audio).is_ok());
+    }
+
+    #[test]
+    fn test_model_families() {
+        assert!(validate_tts_model("eleven_multilingual_v2").is_ok());
+        assert!(validate_tts_model("eleven_multilingual_sts_v2").is_err());
+        assert!(validate_sts_model("eleven_english_sts_v2").is_ok());
+        assert!(validate_sts_model("eleven_flash_v2_5").is_err());
+        assert_eq!(model_credits_per_char("eleven_flash_v2_5"), 0.5);
+        assert_eq!(model_credits_per_char("eleven_multilingual_v2"), 1.0);
+    }
+
+    #[test]
+    fn test_format_and_language_compatibility() {
+        assert!(validate_output_format("mp3_44100_128").is_ok());
+        assert!(validate_output_format("mp3_1").is_err());
+        assert!(validate_format_for_model("pcm_48000", "eleven_flash_v2_5").is_err());
+        assert!(validate_format_for_model("pcm_44100", "eleven_flash_v2_5").is_ok());
+        assert!(validate_language_for_model(Some("es"), "eleven_turbo_v2").is_err());
+        assert!(validate_language_for_model(Some("en"), "eleven_turbo_v2").is_ok());
+        assert!(validate_language_for_model(Some("es"), "eleven_turbo_v2_5").is_ok());
+    }
+
+    #[test]
+    fn test_validate_chunk_schedule() {
+        assert!(validate_chunk_schedule(&[]).is_ok());
+        assert!(validate_chunk_schedule(&[50, 120, 500]).is_ok());
+        assert!(validate_chunk_schedule(&[49]).is_err());
+        assert!(validate_chunk_schedule(&[120, 501]).is_err());
+    }
+
+    #[test]
+    fn test_validate_text_for_model() {
+        let text = "x".repeat(6_000);
+        assert!(validate_text_for_model(&text, "eleven_v3").is_err());
+        assert!(validate_text_for_model(&text, "eleven_flash_v2_5").is_ok());
+        assert!(validate_text_for_model("", "eleven_flash_v2_5").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Terminal waveform previews of downloaded audio (`--waveform`)
+//!
+//! The audio is decoded to 8 kHz mono (MP3s through ffmpeg). Each terminal
+//! column shows the loudest sample in its slice of the recording, scaled to
+//! the loudest in the file, so long silences, talk-over and clipping stand
+//! out before listening.
+
+use crate::output::is_json_mode;
+use crate::pcm::decode_file;
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::path::Path;
+
+/// Rate audio is decoded at; plenty for a preview
+const PREVIEW_RATE: u32 = 8_000;
+
+/// Width used when the terminal width is unknown
+const DEFAULT_WIDTH: usize = 80;
+
+/// Windows quieter than this count as silence
+const SILENCE_DBFS: f64 = -40.0;
+
+/// Window used to measure silence
+const SILENCE_WINDOW_SECS: f64 = 0.05;
+
+/// Floor for levels of digital silence (the range of 16-bit audio)
+const MIN_DBFS: f64 = -96.0;
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Levels of a whole recording
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WaveformSummary {
+    pub duration_secs: f64,
+    pub peak_dbfs: f64,
+    pub rms_dbfs: f64,
+    /// Share of the recording below the silence threshold, in percent
+    pub silent_percent: f64,
+}
+
+/// Print a waveform of `path` with its duration and levels
+pub fn print_waveform(path: &Path) -> Result<()> {
+    let rate = PREVIEW_RATE;
+    let samples = decode_file(path, rate, "--waveform")?;
+    if samples.is_empty() {
+        return Err(anyhow::anyhow!("No audio in {}", path.display()));
+    }
+    let summary = summarize(&samples, rate);
+    if is_json_mode() {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    let width = terminal_width();
+    println!();
+    println!("{}", render(&samples, width));
+    println!("{}", time_axis(summary.duration_secs, width).dimmed());
+    println!(
+        "Duration {} · peak {:.1} dBFS · RMS {:.1} dBFS · {:.0}% silence",
+        format_time(summary.duration_secs).bold(),
+        summary.peak_dbfs,
+        summary.rms_dbfs,
+        summary.silent_percent
+    );
+    Ok(())
+}
+
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse::<usize>().ok())
+        .filter(|&c| c >= 20)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+fn dbfs(level: f64) -> f64 {
+    if level <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * (level / 32_768.0).log10()).max(MIN_DBFS)
+}
+
+fn rms(samples: &[i16]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = samples.iter().map(|&s| (s as f64).powi(2)).sum();
+    (sum / samples.len() as f64).sqrt()
+}
+
+fn summarize(samples: &[i16], rate: u32) -> WaveformSummary {
+    let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    let window = ((SILENCE_WINDOW_SECS * rate as f64) as usize).max(1);
+    let windows = samples.chunks(window).count();
+    let silent = samples
+        .chunks(window)
+        .filter(|w| dbfs(rms(w)) < SILENCE_DBFS)
+        .count();
+    WaveformSummary {
+        duration_secs: samples.len() as f64 / rate as f64,
+        peak_dbfs: dbfs(peak as f64),
+        rms_dbfs: dbfs(rms(samples)),
+        silent_percent: silent as f64 * 100.0 / windows.max(1) as f64,
+    }
+}
+
+/// One bar per column: height from the column's peak relative to the file's,
+/// dimmed where the column is silent and red where it clips
+fn render(samples: &[i16], width: usize) -> String {
+    let overall = samples
+        .iter()
+        .map(|s| s.unsigned_abs())
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    columns(samples, width)
+        .map(|column| {
+            let peak = column.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0) as f64;
+            let level = ((peak / overall) * BARS.len() as f64).ceil() as usize;
+            let bar = BARS[level.clamp(1, BARS.len()) - 1].to_string();
+            if peak >= i16::MAX as f64 {
+                bar.red().to_string()
+            } else if dbfs(rms(column)) < SILENCE_DBFS {
+                bar.dimmed().to_string()
+            } else {
+                bar.green().to_string()
+            }
+        })
+        .collect()
+}
+
+/// Split the samples into at most `width` equal slices
+fn columns(samples: &[i16], width: usize) -> impl Iterator<Item = &[i16]> {
+    let size = samples.len().div_ceil(width.max(1)).max(1);
+    samples.chunks(size)
+}
+
+/// Start, middle and end times spread over `width` columns
+fn time_axis(duration: f64, width: usize) -> String {
+    let start = format_time(0.0);
+    let middle = format_time(duration / 2.0);
+    let end = format_time(duration);
+    let used = start.len() + middle.len() + end.len();
+    if width < used + 2 {
+        return format!("{:<w$}", start, w = width.saturating_sub(end.len())) + &end;
+    }
+    let left = (width / 2)
+        .saturating_sub(middle.len() / 2 + start.len())
+        .min(width - used);
+    let right = width - used - left;
+    format!(
+        "{}{}{}{}{}",
+        start,
+        " ".repeat(left),
+        middle,
+        " ".repeat(right),
+        end
+    )
+}
+
+fn format_time(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    if secs >= 3_600 {
+        format!(
+            "{}:{:02}:{:02}",
+            secs / 3_600,
+            (secs % 3_600) / 60,
+            secs % 60
+        )
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        // One second of a loud tone, then one second of silence
+        let mut samples = crate::pcm::sine_tone(440.0, 1.0, 8_000, 0.5);
+        samples.extend(crate::pcm::silence(1.0, 8_000));
+        let summary = summarize(&samples, 8_000);
+        assert_eq!(summary.duration_secs, 2.0);
+        assert!((summary.peak_dbfs + 6.0).abs() < 0.2);
+        assert!((summary.silent_percent - 50.0).abs() < 3.0);
+        assert_eq!(summarize(&[0; 100], 8_000).peak_dbfs, MIN_DBFS);
+    }
+
+    #[test]
+    fn test_render_width_and_levels() {
+        colored::control::set_override(false);
+        let mut samples = vec![0i16; 400];
+        samples.extend(vec![i16::MAX / 2; 400]);
+        let line = render(&samples, 8);
+        assert_eq!(line.chars().count(), 8);
+        assert!(line.starts_with("▁▁▁▁"));
+        assert!(line.ends_with("████"));
+    }
+
+    #[test]
+    fn test_time_axis() {
+        let axis = time_axis(125.0, 40);
+        assert_eq!(axis.len(), 40);
+        assert!(axis.starts_with("0:00"));
+        assert!(axis.contains("1:02") || axis.contains("1:03"));
+        assert!(axis.ends_with("2:05"));
+        assert_eq!(format_time(3_725.0), "1:02:05");
+    }
+}
@@ -296,3 +296,518 @@ fn test_stt_requires_file() {
     // STT without file should fail
     assert!(!run_cli(&["stt"], 0));
 }
+
+#[test]
+fn test_history_download_tag_help() {
+    let output = Command::new(cargo_bin())
+        .args(["history", "download", "--help"])
+        .output()
+        .expect("Failed to run CLI");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--tag"));
+    assert!(stdout.contains("--album"));
+}
+
+#[test]
+fn test_workspace_audit_help() {
+    assert!(run_cli(&["workspace", "audit", "--help"], 0));
+}
+
+#[test]
+fn test_workspace_secret_help() {
+    assert!(run_cli(&["workspace", "secret", "update", "--help"], 0));
+    assert!(run_cli(&["workspace", "secret", "where-used", "--help"], 0));
+}
+
+#[test]
+fn test_pronunciation_diff_help() {
+    assert!(run_cli(&["pronunciation", "diff", "--help"], 0));
+}
+
+#[test]
+fn test_completions_install_to_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(cargo_bin())
+        .args([
+            "completions",
+            "install",
+            "--shell",
+            "fish",
+            "--dir",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run CLI");
+
+    assert!(output.status.success());
+    assert!(dir.path().join("elevenlabs.fish").exists());
+}
+
+#[test]
+fn test_completions_print_script() {
+    assert!(run_cli(&["completions", "bash"], 0));
+}
+
+#[test]
+fn test_completions_ignore_a_broken_config() {
+    let home = tempfile::tempdir().unwrap();
+    let config = home.path().join("elevenlabs-cli");
+    std::fs::create_dir_all(&config).unwrap();
+    std::fs::write(config.join("config.toml"), "default_voice = [").unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(cargo_bin())
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path())
+            .output()
+            .expect("Failed to run CLI")
+    };
+    assert!(!run(&["config", "paths"]).status.success());
+    let output = run(&["completions", "bash"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("complete"));
+}
+
+#[test]
+fn test_daemon_once_processes_queue() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("01-ok.json"),
+        r#"{"command": "completions", "args": ["bash"]}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("02-bad.json"), r#"{"command": "daemon"}"#).unwrap();
+
+    assert!(run_cli(
+        &[
+            "daemon",
+            "--queue-dir",
+            dir.path().to_str().unwrap(),
+            "--once"
+        ],
+        0
+    ));
+
+    assert!(dir.path().join("done/01-ok.json").exists());
+    let manifest = std::fs::read_to_string(dir.path().join("done/01-ok.result.json")).unwrap();
+    assert!(manifest.contains("\"status\": \"succeeded\""));
+    assert!(dir.path().join("failed/02-bad.json").exists());
+    assert!(dir.path().join("failed/02-bad.result.json").exists());
+}
+
+#[test]
+fn test_support_bundle_redacts_api_key() {
+    let home = tempfile::tempdir().unwrap();
+    let bundle = home.path().join("bundle.zip");
+    let api_key = "sk_supportbundletestkey0123456789";
+
+    let output = Command::new(cargo_bin())
+        .args(["support-bundle", "-o", bundle.to_str().unwrap()])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("ELEVENLABS_API_KEY", api_key)
+        .output()
+        .expect("Failed to run CLI");
+    assert!(output.status.success());
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+    for name in [
+        "version.txt",
+        "config.toml",
+        "environment.txt",
+        "errors.log",
+    ] {
+        let mut contents = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name(name).unwrap(), &mut contents).unwrap();
+        assert!(!contents.contains(api_key));
+    }
+}
+
+#[test]
+fn test_config_profiles() {
+    let home = tempfile::tempdir().unwrap();
+    let work_key = "sk_profileworkkey0123456789";
+    let run = |args: &[&str], profile: Option<&str>| {
+        let mut command = Command::new(cargo_bin());
+        command
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env_remove("ELEVENLABS_API_KEY")
+            .env_remove("ELEVENLABS_PROFILE");
+        if let Some(profile) = profile {
+            command.env("ELEVENLABS_PROFILE", profile);
+        }
+        command.output().expect("Failed to run CLI")
+    };
+
+    for args in [
+        ["config", "profile", "add", "work", "--key", work_key].as_slice(),
+        &[
+            "config",
+            "profile",
+            "add",
+            "personal",
+            "--default-voice",
+            "Rachel",
+        ],
+        &["config", "profile", "use", "work"],
+    ] {
+        let output = run(args, None);
+        assert!(output.status.success(), "{:?}", output);
+    }
+    assert!(!run(&["config", "profile", "use", "missing"], None)
+        .status
+        .success());
+
+    let output = run(&["--json", "config", "profile", "list"], None);
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list[0]["name"], "personal");
+    assert_eq!(list[0]["in_use"], false);
+    assert_eq!(list[1]["name"], "work");
+    assert_eq!(list[1]["in_use"], true);
+    assert_eq!(list[1]["api_key"], true);
+
+    let output = run(&["--json", "config", "profile", "list"], Some("personal"));
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list[0]["in_use"], true);
+    let output = run(&["config", "show", "--profile", "personal"], None);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("default_voice: Rachel"));
+    assert!(!run(&["config", "show", "--profile", "nope"], None)
+        .status
+        .success());
+
+    let bundle = home.path().join("bundle.zip");
+    let output = run(&["support-bundle", "-o", bundle.to_str().unwrap()], None);
+    assert!(output.status.success());
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+    let mut config = String::new();
+    std::io::Read::read_to_string(&mut archive.by_name("config.toml").unwrap(), &mut config)
+        .unwrap();
+    assert!(config.contains("[profiles.work]"));
+    assert!(!config.contains(work_key));
+
+    let output = run(&["config", "profile", "remove", "work"], None);
+    assert!(output.status.success());
+    let output = run(&["--json", "config", "profile", "list"], None);
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_config_set_key() {
+    let home = tempfile::tempdir().unwrap();
+    let config_dir = home.path().join("config");
+    let run = |args: &[&str]| {
+        Command::new(cargo_bin())
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", &config_dir)
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env_remove("ELEVENLABS_API_KEY")
+            .env_remove("ELEVENLABS_PROFILE")
+            .output()
+            .expect("Failed to run CLI")
+    };
+
+    let output = run(&["config", "set-key", "sk_plaintextkey", "--plaintext"]);
+    assert!(output.status.success(), "{:?}", output);
+    let config_file = config_dir.join("elevenlabs-cli").join("config.toml");
+    let written = std::fs::read_to_string(&config_file).unwrap();
+    assert!(written.contains("api_key = \"sk_plaintextkey\""));
+    assert!(!run(&["config", "set-key", " ", "--plaintext"])
+        .status
+        .success());
+    assert!(!run(&["--no-input", "config", "set-key"]).status.success());
+
+    // A key recorded as being in the keyring that can't be read explains why
+    std::fs::write(&config_file, "api_key_in_keyring = true\n").unwrap();
+    let output = run(&["user", "info"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("keyring"), "{}", stderr);
+}
+
+#[test]
+fn test_no_input_fails_instead_of_prompting() {
+    let home = tempfile::tempdir().unwrap();
+    let existing = home.path().join("existing.mp3");
+    std::fs::write(&existing, b"audio").unwrap();
+
+    for args in [
+        vec!["history", "delete", "item_1", "--no-input"],
+        vec![
+            "--no-input",
+            "history",
+            "download",
+            "item_1",
+            "-o",
+            existing.to_str().unwrap(),
+        ],
+    ] {
+        let output = Command::new(cargo_bin())
+            .args(&args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env("ELEVENLABS_API_KEY", "test-api-key")
+            .output()
+            .expect("Failed to run CLI");
+        assert_eq!(output.status.code(), Some(3), "args: {:?}", args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--no-input"), "stderr: {}", stderr);
+    }
+    assert_eq!(std::fs::read(&existing).unwrap(), b"audio");
+}
+
+#[test]
+fn test_audio_generators_work_without_api_key() {
+    let home = tempfile::tempdir().unwrap();
+    let beep = home.path().join("beep.wav");
+    let pad = home.path().join("pad.ulaw");
+
+    for args in [
+        vec!["audio", "tone", "--freq", "440", "--duration", "2", "-o"],
+        vec![
+            "audio",
+            "silence",
+            "--duration",
+            "1.5",
+            "--format",
+            "ulaw_8000",
+            "-o",
+        ],
+    ] {
+        let target = if args[1] == "tone" { &beep } else { &pad };
+        let output = Command::new(cargo_bin())
+            .args(&args)
+            .arg(target)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env_remove("ELEVENLABS_API_KEY")
+            .output()
+            .expect("Failed to run CLI");
+        assert!(output.status.success(), "args: {:?}", args);
+    }
+
+    let wav = std::fs::read(&beep).unwrap();
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(wav.len(), 44 + 2 * 44_100 * 2);
+    let ulaw = std::fs::read(&pad).unwrap();
+    assert_eq!(ulaw, vec![0xFF; 12_000]);
+}
+
+#[test]
+fn test_voice_score_works_without_api_key() {
+    let home = tempfile::tempdir().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(cargo_bin())
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env_remove("ELEVENLABS_API_KEY")
+            .output()
+            .expect("Failed to run CLI")
+    };
+    let low = home.path().join("low.wav");
+    let high = home.path().join("high.wav");
+    for (freq, path) in [("220", &low), ("2000", &high)] {
+        let output = run(&[
+            "audio",
+            "tone",
+            "--freq",
+            freq,
+            "-o",
+            path.to_str().unwrap(),
+        ]);
+        assert!(output.status.success());
+    }
+
+    let output = run(&[
+        "--json",
+        "voice",
+        "score",
+        "--reference",
+        low.to_str().unwrap(),
+        "--candidate",
+        low.to_str().unwrap(),
+    ]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let same: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(same["similarity"], 100.0);
+    assert!((same["reference"]["pitch"]["median_hz"].as_f64().unwrap() - 220.0).abs() < 10.0);
+
+    let output = run(&[
+        "--json",
+        "voice",
+        "score",
+        "--reference",
+        low.to_str().unwrap(),
+        "--candidate",
+        high.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    let different: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(different["similarity"].as_f64().unwrap() < 50.0);
+}
+
+#[test]
+fn test_concurrent_config_set_keeps_every_key() {
+    let home = tempfile::tempdir().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(cargo_bin())
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("XDG_DATA_HOME", home.path().join("data"))
+            .env("ELEVENLABS_API_KEY", "test-api-key")
+            .env("ELEVENLABS_API_KEY", "test-api-key")
+            .spawn()
+            .expect("Failed to run CLI")
+    };
+
+    let settings = [
+        ("default_voice", "Brian"),
+        ("default_model", "eleven_flash_v2_5"),
+        ("output_dir", "/tmp/concurrent-out"),
+        ("player_cmd", "mpv -"),
+    ];
+    let children: Vec<_> = settings
+        .iter()
+        .map(|(key, value)| run(&["config", "set", key, value]))
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    let output = Command::new(cargo_bin())
+        .args(["config", "show"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .output()
+        .expect("Failed to run CLI");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for (_, value) in settings {
+        assert!(stdout.contains(value), "missing {} in:\n{}", value, stdout);
+    }
+}
+
+#[test]
+fn test_legacy_files_move_to_xdg_directories() {
+    let home = tempfile::tempdir().unwrap();
+    let legacy_config = home.path().join("config").join("cli");
+    let legacy_data = home.path().join("data").join("cli");
+    std::fs::create_dir_all(&legacy_config).unwrap();
+    std::fs::create_dir_all(&legacy_data).unwrap();
+    std::fs::write(
+        legacy_config.join("config.toml"),
+        "default_voice = \"Legacy\"\n",
+    )
+    .unwrap();
+    std::fs::write(legacy_data.join("search-index.json"), "{}").unwrap();
+
+    let output = Command::new(cargo_bin())
+        .args(["--json", "config", "paths"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .output()
+        .expect("Failed to run CLI");
+    assert!(output.status.success());
+
+    let paths: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let notices: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(notices.len(), 2, "{:?}", notices);
+    assert!(notices
+        .iter()
+        .all(|n| n["status"] == "info" && n["message"].as_str().unwrap().starts_with("Moved ")));
+    let config = home.path().join("config/elevenlabs-cli/config.toml");
+    assert_eq!(paths["config"], config.display().to_string());
+    assert_eq!(
+        paths["cache"],
+        home.path()
+            .join("cache/elevenlabs-cli")
+            .display()
+            .to_string()
+    );
+    assert!(std::fs::read_to_string(&config).unwrap().contains("Legacy"));
+    assert!(home
+        .path()
+        .join("cache/elevenlabs-cli/search-index.json")
+        .exists());
+    assert!(!legacy_config.exists());
+    assert!(!legacy_data.exists());
+}
+
+#[test]
+fn test_usage_projects_groups_ledger_entries() {
+    let home = tempfile::tempdir().unwrap();
+    let data = home.path().join("data/elevenlabs-cli");
+    std::fs::create_dir_all(&data).unwrap();
+    std::fs::write(
+        data.join("generations.jsonl"),
+        concat!(
+            r#"{"timestamp":1,"command":"tts","voice":"Brian","model":"m","characters":100,"project_id":"launch"}"#,
+            "\n",
+            r#"{"timestamp":2,"command":"tts","voice":"Brian","model":"m","characters":7}"#,
+            "\n",
+            r#"{"timestamp":3,"command":"tts","voice":"Adam","model":"m","characters":50,"project_id":"launch","metadata":{"chapter":"2"}}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(cargo_bin())
+        .args(["--json", "usage", "projects"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .output()
+        .expect("Failed to run CLI");
+    assert!(output.status.success());
+
+    let totals: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(totals.as_array().unwrap().len(), 1);
+    assert_eq!(totals[0]["project_id"], "launch");
+    assert_eq!(totals[0]["generations"], 2);
+    assert_eq!(totals[0]["characters"], 150);
+}
+
+#[test]
+fn test_subcommand_short_flags_shadow_globals() {
+    for (args, flag) in [
+        (["dub", "create", "--help"], "-f, --file"),
+        (["converse", "feedback", "--help"], "-f, --feedback"),
+        (["agent", "widget-avatar", "--help"], "-a, --avatar-file"),
+    ] {
+        let output = Command::new(cargo_bin())
+            .args(args)
+            .output()
+            .expect("Failed to run CLI");
+        assert!(output.status.success(), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stdout).contains(flag));
+    }
+}
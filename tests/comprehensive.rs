@@ -33,6 +33,16 @@ fn fail(args: &[&str]) -> bool {
     !ok(args)
 }
 
+/// Run CLI and check it fails with `message` on stderr
+fn fail_with(args: &[&str], message: &str) -> bool {
+    Command::new(bin())
+        .args(args)
+        .env("ELEVENLABS_API_KEY", "test")
+        .output()
+        .map(|o| !o.status.success() && String::from_utf8_lossy(&o.stderr).contains(message))
+        .unwrap_or(false)
+}
+
 // ============================================================================
 // Main Commands - 31 commands
 // ============================================================================
@@ -46,6 +56,18 @@ fn h_stt() {
     assert!(ok(&["stt", "--help"]));
 }
 #[test]
+fn h_stt_live() {
+    assert!(ok(&["stt", "live", "--help"]));
+}
+#[test]
+fn h_ivr_build() {
+    assert!(ok(&["ivr", "build", "--help"]));
+}
+#[test]
+fn h_audio_tone() {
+    assert!(ok(&["audio", "tone", "--help"]));
+}
+#[test]
 fn h_voice() {
     assert!(ok(&["voice", "--help"]));
 }
@@ -66,6 +88,18 @@ fn h_dub() {
     assert!(ok(&["dub", "--help"]));
 }
 #[test]
+fn h_dub_burn_captions() {
+    assert!(ok(&["dub", "burn-captions", "--help"]));
+}
+#[test]
+fn h_dub_estimate() {
+    assert!(ok(&["dub", "estimate", "--help"]));
+}
+#[test]
+fn h_dub_redo() {
+    assert!(ok(&["dub", "redo", "--help"]));
+}
+#[test]
 fn h_history() {
     assert!(ok(&["history", "--help"]));
 }
@@ -122,6 +156,70 @@ fn h_agent() {
     assert!(ok(&["agent", "--help"]));
 }
 #[test]
+fn h_agent_branch_promote() {
+    assert!(ok(&["agent", "branch", "promote", "--help"]));
+}
+#[test]
+fn h_agent_languages_set() {
+    assert!(ok(&["agent", "languages", "set", "--help"]));
+}
+#[test]
+fn h_agent_transfer_set() {
+    assert!(ok(&["agent", "transfer", "set", "--help"]));
+}
+#[test]
+fn h_agent_privacy_set() {
+    assert!(ok(&["agent", "privacy", "set", "--help"]));
+}
+#[test]
+fn h_workspace_privacy_set() {
+    assert!(ok(&["workspace", "privacy", "set", "--help"]));
+}
+#[test]
+fn h_voice_verify() {
+    assert!(ok(&["voice", "verify", "--help"]));
+}
+#[test]
+fn h_voice_score() {
+    assert!(ok(&["voice", "score", "--help"]));
+}
+#[test]
+fn h_schedule_add() {
+    assert!(ok(&["schedule", "add", "--help"]));
+}
+#[test]
+fn h_usage_alert() {
+    assert!(ok(&["usage", "alert", "--help"]));
+}
+#[test]
+fn h_projects_get_watch() {
+    assert!(ok(&["projects", "get", "p1", "--watch", "--help"]));
+}
+#[test]
+fn h_jobs_wait() {
+    assert!(ok(&["jobs", "wait", "--help"]));
+}
+#[test]
+fn h_api() {
+    assert!(ok(&["api", "--help"]));
+}
+#[test]
+fn h_voice_defaults_set() {
+    assert!(ok(&["voice", "defaults", "set", "--help"]));
+}
+#[test]
+fn h_config_profile_add() {
+    assert!(ok(&["config", "profile", "add", "--help"]));
+}
+#[test]
+fn h_config_set_key() {
+    assert!(ok(&["config", "set-key", "--help"]));
+}
+#[test]
+fn h_dub_transcript() {
+    assert!(ok(&["dub", "transcript", "--help"]));
+}
+#[test]
 fn h_converse() {
     assert!(ok(&["converse", "--help"]));
 }
@@ -195,6 +293,10 @@ fn h_voice_fine_tune() {
     assert!(ok(&["voice", "fine-tune", "--help"]));
 }
 #[test]
+fn h_voice_label_bulk() {
+    assert!(ok(&["voice", "label", "bulk", "--help"]));
+}
+#[test]
 fn h_voice_edit() {
     assert!(ok(&["voice", "edit", "--help"]));
 }
@@ -224,6 +326,10 @@ fn h_history_get() {
     assert!(ok(&["history", "get", "--help"]));
 }
 #[test]
+fn h_history_feedback_batch() {
+    assert!(ok(&["history", "feedback-batch", "--help"]));
+}
+#[test]
 fn h_history_delete() {
     assert!(ok(&["history", "delete", "--help"]));
 }
@@ -232,6 +338,10 @@ fn h_history_download() {
     assert!(ok(&["history", "download", "--help"]));
 }
 #[test]
+fn h_history_sync() {
+    assert!(ok(&["history", "sync", "--help"]));
+}
+#[test]
 fn h_history_feedback() {
     assert!(ok(&["history", "feedback", "--help"]));
 }
@@ -248,6 +358,10 @@ fn h_models_list() {
 fn h_models_rates() {
     assert!(ok(&["models", "rates", "--help"]));
 }
+#[test]
+fn h_models_cost() {
+    assert!(ok(&["models", "cost", "--help"]));
+}
 
 // ============================================================================
 // Pronunciation Subcommands - 7
@@ -390,6 +504,10 @@ fn h_converse_chat() {
     assert!(ok(&["converse", "chat", "--help"]));
 }
 #[test]
+fn h_converse_talk() {
+    assert!(ok(&["converse", "talk", "--help"]));
+}
+#[test]
 fn h_converse_list() {
     assert!(ok(&["converse", "list", "--help"]));
 }
@@ -427,6 +545,10 @@ fn h_agent_summaries() {
     assert!(ok(&["agent", "summaries", "--help"]));
 }
 #[test]
+fn h_agent_overview() {
+    assert!(ok(&["agent", "overview", "--help"]));
+}
+#[test]
 fn h_agent_get() {
     assert!(ok(&["agent", "get", "--help"]));
 }
@@ -467,9 +589,25 @@ fn h_agent_batch_status() {
     assert!(ok(&["agent", "batch-status", "--help"]));
 }
 #[test]
+fn h_agent_batch_create() {
+    assert!(ok(&["agent", "batch-create", "--help"]));
+}
+#[test]
 fn h_agent_batch_delete() {
     assert!(ok(&["agent", "batch-delete", "--help"]));
 }
+#[test]
+fn h_agent_smoke() {
+    assert!(ok(&["agent", "smoke", "--help"]));
+}
+#[test]
+fn h_agent_export() {
+    assert!(ok(&["agent", "export", "--help"]));
+}
+#[test]
+fn h_agent_import() {
+    assert!(ok(&["agent", "import", "--help"]));
+}
 
 // ============================================================================
 // Projects Subcommands - 6
@@ -538,6 +676,10 @@ fn h_knowledge_add_from_file() {
     assert!(ok(&["knowledge", "add-from-file", "--help"]));
 }
 #[test]
+fn h_knowledge_update() {
+    assert!(ok(&["knowledge", "update", "--help"]));
+}
+#[test]
 fn h_knowledge_get() {
     assert!(ok(&["knowledge", "get", "--help"]));
 }
@@ -550,6 +692,14 @@ fn h_knowledge_delete() {
 // Music Subcommands - 3
 // ============================================================================
 
+#[test]
+fn h_support_bundle() {
+    assert!(ok(&["support-bundle", "--help"]));
+}
+#[test]
+fn h_daemon() {
+    assert!(ok(&["daemon", "--help"]));
+}
 #[test]
 fn h_music_generate() {
     assert!(ok(&["music", "generate", "--help"]));
@@ -570,6 +720,14 @@ fn h_music_download() {
 fn h_music_delete() {
     assert!(ok(&["music", "delete", "--help"]));
 }
+#[test]
+fn h_music_favorite() {
+    assert!(ok(&["music", "favorite", "--help"]));
+}
+#[test]
+fn h_music_tag() {
+    assert!(ok(&["music", "tag", "--help"]));
+}
 
 // ============================================================================
 // Webhook Subcommands - 3
@@ -597,6 +755,10 @@ fn h_library_list() {
     assert!(ok(&["library", "list", "--help"]));
 }
 #[test]
+fn h_library_publish() {
+    assert!(ok(&["library", "publish", "--help"]));
+}
+#[test]
 fn h_library_saved() {
     assert!(ok(&["library", "saved", "--help"]));
 }
@@ -642,6 +804,10 @@ fn h_tools_get() {
 fn h_tools_delete() {
     assert!(ok(&["tools", "delete", "--help"]));
 }
+#[test]
+fn h_tools_import_openapi() {
+    assert!(ok(&["tools", "import-openapi", "--help"]));
+}
 
 // ============================================================================
 // Dialogue - 1
@@ -669,6 +835,34 @@ fn h_user_subscription() {
 // Config - 3
 // ============================================================================
 
+#[test]
+fn h_export() {
+    assert!(ok(&["export", "--help"]));
+}
+#[test]
+fn h_import() {
+    assert!(ok(&["import", "--help"]));
+}
+#[test]
+fn h_tts_feed() {
+    assert!(ok(&["tts", "feed", "--help"]));
+}
+#[test]
+fn h_tts_session() {
+    assert!(ok(&["tts", "session", "--help"]));
+}
+#[test]
+fn h_limits() {
+    assert!(ok(&["limits", "--help"]));
+}
+#[test]
+fn h_config_paths() {
+    assert!(ok(&["config", "paths", "--help"]));
+}
+#[test]
+fn h_voice_design_iterate() {
+    assert!(ok(&["voice-design", "iterate", "--help"]));
+}
 #[test]
 fn h_config_show() {
     assert!(ok(&["config", "show", "--help"]));
@@ -694,6 +888,201 @@ fn e_tts() {
 fn e_stt() {
     assert!(fail(&["stt"]));
 }
+#[test]
+fn e_stt_live_vad_threshold_out_of_range() {
+    assert!(fail(&["stt", "live", "--vad-threshold", "1.5"]));
+}
+#[test]
+fn e_stt_chunk_duration_without_stream() {
+    assert!(fail(&["stt", "talk.mp3", "--chunk-duration", "30"]));
+}
+#[test]
+fn e_audio_tone_bad_format() {
+    assert!(fail(&[
+        "audio",
+        "tone",
+        "--format",
+        "mp3_44100_128",
+        "-o",
+        "x.mp3"
+    ]));
+}
+#[test]
+fn e_ivr_build_bad_format() {
+    assert!(fail(&[
+        "ivr",
+        "build",
+        "prompts.yaml",
+        "--format",
+        "mp3_44100_128"
+    ]));
+}
+#[test]
+fn e_tts_meta_without_value() {
+    assert!(fail(&["tts", "Hello", "--meta", "chapter"]));
+}
+#[test]
+fn e_history_list_unknown_column() {
+    assert!(fail(&["history", "list", "--columns", "id,size"]));
+}
+#[test]
+fn e_agent_languages_set_bad_pair() {
+    assert!(fail(&[
+        "agent",
+        "languages",
+        "set",
+        "agent_1",
+        "--voice-for",
+        "es"
+    ]));
+}
+#[test]
+fn e_agent_transfer_set_without_target() {
+    assert!(fail(&[
+        "agent",
+        "transfer",
+        "set",
+        "agent_1",
+        "--condition",
+        "x"
+    ]));
+}
+#[test]
+fn e_agent_privacy_set_bad_bool() {
+    assert!(fail(&[
+        "agent",
+        "privacy",
+        "set",
+        "agent_1",
+        "--audio-save",
+        "maybe"
+    ]));
+}
+#[test]
+fn e_voice_verify_bad_duration() {
+    assert!(fail_with(
+        &["voice", "verify", "voice_1", "--duration", "0"],
+        "--duration must be between 0 and 60 seconds"
+    ));
+}
+#[test]
+fn e_schedule_add_bad_cron() {
+    assert!(fail(&[
+        "schedule",
+        "add",
+        "--cron",
+        "0 25 * * *",
+        "--cmd",
+        "usage alert"
+    ]));
+}
+#[test]
+fn e_projects_get_watch_zero_interval() {
+    assert!(fail(&[
+        "projects",
+        "get",
+        "p1",
+        "--watch",
+        "--interval",
+        "0"
+    ]));
+}
+#[test]
+fn e_negative_retry_delay() {
+    assert!(fail(&["usage", "alert", "--retry-delay=-1"]));
+}
+#[test]
+fn e_tts_fallback_model_not_tts() {
+    assert!(fail(&[
+        "tts",
+        "hello",
+        "--fallback-model",
+        "eleven_multilingual_sts_v2"
+    ]));
+}
+#[test]
+fn e_jobs_wait_zero_interval() {
+    assert!(fail(&["jobs", "wait", "dub1", "--interval", "0"]));
+}
+#[test]
+fn e_dub_create_interval_without_wait() {
+    assert!(fail(&[
+        "dub",
+        "create",
+        "--file",
+        "a.mp3",
+        "-s",
+        "en",
+        "-t",
+        "de",
+        "--interval",
+        "5"
+    ]));
+}
+#[test]
+fn e_rag_rebuild_wait_zero_interval() {
+    assert!(fail(&[
+        "rag",
+        "rebuild",
+        "-d",
+        "doc1",
+        "--wait",
+        "--interval",
+        "0"
+    ]));
+}
+#[test]
+fn e_jobs_list_unknown_kind() {
+    assert!(fail(&["jobs", "list", "--kind", "tts"]));
+}
+#[test]
+fn e_api_unknown_method() {
+    assert!(fail(&["api", "TRACE", "/v1/voices"]));
+}
+#[test]
+fn e_header_without_colon() {
+    assert!(fail(&["api", "GET", "/v1/voices", "--header", "X-Trace"]));
+}
+#[test]
+fn e_voice_defaults_set_without_settings() {
+    assert!(fail(&["voice", "defaults", "set", "Rachel"]));
+}
+#[test]
+fn e_voice_defaults_set_out_of_range() {
+    assert!(fail(&[
+        "voice",
+        "defaults",
+        "set",
+        "Rachel",
+        "--stability",
+        "1.5"
+    ]));
+}
+#[test]
+fn e_tts_missing_casting_file() {
+    assert!(fail(&[
+        "tts",
+        "@Narrator: hello",
+        "--casting",
+        "/nonexistent/casting.yaml"
+    ]));
+}
+#[test]
+fn e_stt_invalid_language_hint() {
+    assert!(fail(&["stt", "talk.mp3", "--languages", "en,english"]));
+}
+
+#[test]
+fn e_stt_split_by_language_without_stream() {
+    assert!(fail(&[
+        "stt",
+        "talk.mp3",
+        "--split-by-language",
+        "-o",
+        "out.txt"
+    ]));
+}
+
 #[test]
 fn e_voice_get() {
     assert!(fail(&["voice", "get"]));
@@ -707,6 +1096,53 @@ fn e_history_get() {
     assert!(fail(&["history", "get"]));
 }
 #[test]
+fn e_dub_estimate_needs_credit_rate() {
+    assert!(fail_with(
+        &[
+            "dub",
+            "estimate",
+            "talk.mp4",
+            "-t",
+            "es",
+            "--duration",
+            "90"
+        ],
+        "--credits-per-minute"
+    ));
+}
+#[test]
+fn e_dub_create_missing_speaker_map() {
+    let dir = tempfile::tempdir().unwrap();
+    let media = dir.path().join("talk.mp3");
+    std::fs::write(&media, b"ID3").unwrap();
+    assert!(fail(&[
+        "dub",
+        "create",
+        "--file",
+        media.to_str().unwrap(),
+        "-s",
+        "en",
+        "-t",
+        "es",
+        "--speaker-map",
+        "/nonexistent/speakers.yaml",
+    ]));
+}
+#[test]
+fn e_history_sync_no_dir() {
+    assert!(fail(&["history", "sync"]));
+}
+#[test]
+fn e_library_publish_bad_category() {
+    assert!(fail(&[
+        "library",
+        "publish",
+        "voice_1",
+        "--category",
+        "premade"
+    ]));
+}
+#[test]
 fn e_history_feedback() {
     assert!(fail(&["history", "feedback"]));
 }
@@ -731,10 +1167,28 @@ fn e_converse_chat() {
     assert!(fail(&["converse", "chat"]));
 }
 #[test]
+fn e_converse_talk_no_agent() {
+    assert!(fail(&["converse", "talk"]));
+}
+#[test]
+fn e_converse_talk_signed_url_and_token() {
+    assert!(fail(&[
+        "converse",
+        "talk",
+        "agent_1",
+        "--use-signed-url",
+        "--token"
+    ]));
+}
+#[test]
 fn e_agent_get() {
     assert!(fail(&["agent", "get"]));
 }
 #[test]
+fn e_agent_import_no_file() {
+    assert!(fail(&["agent", "import"]));
+}
+#[test]
 fn e_projects_get() {
     assert!(fail(&["projects", "get"]));
 }
@@ -759,6 +1213,176 @@ fn e_audio_native_get() {
     assert!(fail(&["audio-native", "get"]));
 }
 #[test]
+fn e_converse_chat_missing_prompt_file() {
+    assert!(fail(&[
+        "converse",
+        "chat",
+        "--agent",
+        "agent_123",
+        "--system-prompt-file",
+        "/nonexistent/prompt.txt",
+    ]));
+}
+#[test]
+fn e_converse_chat_bad_dynamic_var() {
+    assert!(fail(&[
+        "converse",
+        "chat",
+        "--agent",
+        "agent_123",
+        "--dynamic-var",
+        "missing_equals",
+    ]));
+}
+#[test]
+fn e_converse_chat_signed_url_conflicts_with_token() {
+    assert!(fail(&[
+        "converse",
+        "chat",
+        "--agent",
+        "agent_123",
+        "--use-signed-url",
+        "--token",
+    ]));
+}
+#[test]
+fn e_tts_sentence_pause_too_long() {
+    assert!(fail(&["tts", "Hello. World.", "--sentence-pause", "5s"]));
+}
+#[test]
+fn e_tts_phoneme_invalid() {
+    assert!(fail(&["tts", "Hello", "--phoneme", "hello"]));
+}
+#[test]
+fn e_tts_delivery_requires_v3() {
+    assert!(fail_with(
+        &["tts", "Hello", "--delivery", "whisper"],
+        "Use --model eleven_v3"
+    ));
+}
+#[test]
+fn e_sfx_crossfade_requires_wav() {
+    assert!(fail(&[
+        "sfx",
+        "rain",
+        "--crossfade",
+        "500",
+        "-o",
+        "rain.mp3"
+    ]));
+}
+#[test]
+fn e_invalid_organize() {
+    assert!(fail(&["--organize", "by-month", "tts", "Hello"]));
+}
+#[test]
+fn e_agent_simulate_message_and_script() {
+    assert!(fail(&[
+        "agent", "simulate", "a1", "-m", "Hi", "--script", "s.json"
+    ]));
+}
+#[test]
+fn e_tts_stream_latency_out_of_range() {
+    assert!(fail(&[
+        "tts-stream",
+        "Hi",
+        "--optimize-streaming-latency",
+        "5"
+    ]));
+}
+#[test]
+fn e_tts_stream_chunk_schedule_out_of_range() {
+    assert!(fail(&["tts-stream", "Hi", "--chunk-schedule", "10,120"]));
+}
+#[test]
+fn e_tts_stream_chunk_schedule_with_sink() {
+    assert!(fail(&[
+        "tts-stream",
+        "Hi",
+        "--chunk-schedule",
+        "50,120",
+        "--sink",
+        "fifo:/tmp/tts.pcm"
+    ]));
+}
+#[test]
+fn e_tts_stream_stdout_with_output() {
+    assert!(fail(&["tts-stream", "Hi", "--stdout", "--output", "a.mp3"]));
+}
+#[test]
+fn e_fx_unknown_effect() {
+    assert!(fail(&["tts", "Hi", "--play", "--fx", "flanger=0.5"]));
+}
+#[test]
+fn e_fx_out_of_range() {
+    assert!(fail(&["tts", "Hi", "--play", "--fx", "pitch=-24"]));
+}
+#[test]
+fn e_tts_stream_udp_sink_needs_raw_format() {
+    assert!(fail(&["tts-stream", "Hi", "--sink", "udp:127.0.0.1:5004"]));
+}
+#[test]
+fn e_tts_stream_unknown_sink() {
+    assert!(fail(&[
+        "tts-stream",
+        "Hi",
+        "--sink",
+        "tcp:127.0.0.1:5004",
+        "--output-format",
+        "ulaw_8000"
+    ]));
+}
+#[test]
+fn e_search_invalid_type() {
+    assert!(fail(&["search", "pirate", "--type", "dragons"]));
+}
+#[test]
+fn e_export_invalid_section() {
+    assert!(fail(&["export", "--only", "history"]));
+}
+#[test]
+fn e_import_missing_dir() {
+    assert!(fail(&["import"]));
+}
+#[test]
+fn e_tts_invalid_clean_input() {
+    assert!(fail(&["tts", "Hello", "--clean-input", "markdown,smileys"]));
+}
+#[test]
+fn e_tts_url_with_text() {
+    assert!(fail(&["tts", "Hello", "--url", "https://example.com/post"]));
+}
+#[test]
+fn e_knowledge_sitemap_with_crawl_depth() {
+    assert!(fail(&[
+        "knowledge",
+        "add-from-url",
+        "--url",
+        "https://example.com",
+        "--name",
+        "Docs",
+        "--sitemap",
+        "--crawl-depth",
+        "2",
+    ]));
+}
+#[test]
+fn e_knowledge_update_no_rebuild_with_wait() {
+    assert!(fail(&[
+        "knowledge",
+        "update",
+        "doc1",
+        "--file",
+        "faq.md",
+        "--no-rebuild",
+        "--wait",
+    ]));
+}
+#[test]
+fn e_models_cost_without_input() {
+    assert!(fail(&["models", "cost", "--model", "eleven_turbo_v2_5"]));
+}
+#[test]
 fn e_invalid() {
     assert!(fail(&["nonexistent"]));
 }
@@ -775,3 +1399,15 @@ fn g_help() {
 fn g_version() {
     assert!(ok(&["--version"]));
 }
+#[test]
+fn g_quiet() {
+    assert!(ok(&["--quiet", "--help"]));
+}
+#[test]
+fn g_color_never() {
+    assert!(ok(&["--color", "never", "--ascii", "--help"]));
+}
+#[test]
+fn e_color_invalid() {
+    assert!(fail(&["--color", "sometimes", "--help"]));
+}
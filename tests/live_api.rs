@@ -0,0 +1,81 @@
+//! Smoke tests against the real ElevenLabs API
+//!
+//! Only built with `--features live-api` and ignored by default, since they
+//! need a real key and spend (a few) credits:
+//!
+//! ```sh
+//! ELEVENLABS_API_KEY=... cargo test --features live-api --test live_api -- --ignored
+//! ```
+#![cfg(feature = "live-api")]
+
+use std::process::Output;
+
+/// The API key to test with, or `None` to skip
+fn api_key() -> Option<String> {
+    let key = std::env::var("ELEVENLABS_API_KEY").ok()?;
+    (!key.trim().is_empty()).then_some(key)
+}
+
+/// Run the CLI with the real API, or `None` when no key is configured
+fn run_cli(args: &[&str]) -> Option<Output> {
+    let Some(key) = api_key() else {
+        eprintln!("ELEVENLABS_API_KEY is not set; skipping live test");
+        return None;
+    };
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args(args)
+        .env("ELEVENLABS_API_KEY", key)
+        .env_remove("ELEVENLABS_API_BASE_URL")
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to run CLI");
+    Some(output)
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "stdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+#[ignore = "needs ELEVENLABS_API_KEY"]
+fn live_user_info() {
+    if let Some(output) = run_cli(&["user", "info"]) {
+        assert_success(&output);
+    }
+}
+
+#[test]
+#[ignore = "needs ELEVENLABS_API_KEY"]
+fn live_models_list() {
+    if let Some(output) = run_cli(&["models", "list"]) {
+        assert_success(&output);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("eleven_"));
+    }
+}
+
+#[test]
+#[ignore = "needs ELEVENLABS_API_KEY"]
+fn live_voice_list() {
+    if let Some(output) = run_cli(&["voice", "list"]) {
+        assert_success(&output);
+    }
+}
+
+#[test]
+#[ignore = "needs ELEVENLABS_API_KEY; spends a few credits"]
+fn live_tts_short_phrase() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("smoke.mp3");
+    let file = file.to_str().unwrap();
+    if let Some(output) = run_cli(&["tts", "Hi.", "-o", file, "--yes"]) {
+        assert_success(&output);
+        let size = std::fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+        assert!(size > 0, "no audio written");
+    }
+}
@@ -0,0 +1,3459 @@
+//! Command logic tests against a mocked ElevenLabs API
+//!
+//! Each test starts a wiremock server and points the CLI at it through
+//! `ELEVENLABS_API_BASE_URL`, so request shapes and response handling are
+//! verified without network access or a real API key.
+
+use serde_json::json;
+use std::process::Output;
+use wiremock::matchers::{
+    body_json, body_partial_json, body_string_contains, header, method, path, query_param,
+    query_param_is_missing,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Run the CLI against the mock server with an isolated config directory
+async fn run_cli(server: &MockServer, args: &[&str]) -> Output {
+    let home = tempfile::tempdir().expect("Failed to create temp dir");
+    run_cli_in(server, home.path(), args).await
+}
+
+/// Run the CLI with a given home directory, so local state carries across runs
+async fn run_cli_in(server: &MockServer, home: &std::path::Path, args: &[&str]) -> Output {
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args(args)
+        .env("ELEVENLABS_API_BASE_URL", server.uri())
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home)
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .expect("Failed to run CLI")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[tokio::test]
+async fn history_feedback_posts_rating() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/history/item_1/feedback"))
+        .and(header("xi-api-key", "test-api-key"))
+        .and(body_json(
+            json!({ "thumbs_up": true, "feedback": "clear voice" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "history",
+            "feedback",
+            "item_1",
+            "--thumbs-up",
+            "--feedback",
+            "clear voice",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn history_feedback_batch_reports_failures() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/history/good/feedback"))
+        .and(body_json(json!({ "thumbs_up": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/history/missing/feedback"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("ratings.csv");
+    std::fs::write(&csv, "history_item_id,rating\ngood,down\nmissing,up\n").unwrap();
+
+    let output = run_cli(
+        &server,
+        &["history", "feedback-batch", csv.to_str().unwrap()],
+    )
+    .await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn history_sync_downloads_only_new_items() {
+    let server = MockServer::start().await;
+    let item = |id: &str, date: u64| {
+        json!({
+            "history_item_id": id,
+            "date_unix": date,
+            "voice_id": "voice_1",
+            "voice_name": "Rachel",
+            "text": format!("Item {}", id)
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/v1/history"))
+        .and(query_param("voice_id", "voice_1"))
+        .and(query_param("start_after_history_item_id", "h2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "history": [item("h3", 1_600_000_000)],
+            "last_history_item_id": "h3",
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/history"))
+        .and(query_param("voice_id", "voice_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "history": [item("h1", 1_700_000_200), item("h2", 1_700_000_100)],
+            "last_history_item_id": "h2",
+            "has_more": true
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/history/h1/audio"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "audio/mpeg")
+                .set_body_bytes(b"h1 audio".to_vec()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/history/h3/audio"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"h3 audio".to_vec()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // h2 was fetched earlier with `history download`
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("h2.mp3"), b"h2 audio").unwrap();
+    let sync_dir = dir.path().to_str().unwrap();
+    let args = [
+        "history",
+        "sync",
+        sync_dir,
+        "--voice",
+        "voice_1",
+        "--since",
+        "2020-09-14",
+    ];
+    let output = run_cli(&server, &args).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Downloaded 1 new item(s)"));
+    assert_eq!(
+        std::fs::read(dir.path().join("h1.mp3")).unwrap(),
+        b"h1 audio"
+    );
+
+    let index: Vec<serde_json::Value> =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("index.json")).unwrap())
+            .unwrap();
+    let files: Vec<&str> = index.iter().map(|i| i["file"].as_str().unwrap()).collect();
+    assert_eq!(files, ["h1.mp3", "h2.mp3"]);
+    assert_eq!(index[0]["voice_name"], "Rachel");
+
+    // Nothing new the second time
+    let output = run_cli(&server, &args).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Downloaded 0 new item(s)"));
+}
+
+#[tokio::test]
+async fn dub_create_applies_speaker_map() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/dubbing"))
+        .and(body_string_contains("dubbing_studio"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "dubbing_id": "dub_1",
+            "expected_duration_sec": 12.0
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "dubbing_id": "dub_1", "status": "dubbed" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/resource/dub_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "target_languages": ["es"],
+            "speaker_tracks": {
+                "spk_a": { "id": "spk_a", "speaker_name": "", "segments": ["s1"] },
+                "spk_b": { "id": "spk_b", "speaker_name": "Host", "segments": ["s2"] }
+            },
+            "speaker_segments": {
+                "s1": { "start_time": 0.0 },
+                "s2": { "start_time": 3.0 }
+            },
+            "renders": { "r1": { "id": "r1", "language": "es", "status": "complete" } }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/dubbing/resource/dub_1/speaker/spk_a"))
+        .and(body_json(
+            json!({ "voice_id": "voice_1", "languages": ["es"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/dubbing/resource/dub_1/speaker/spk_b"))
+        .and(body_json(
+            json!({ "voice_id": "track-clone", "languages": ["es"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/dubbing/resource/dub_1/dub"))
+        .and(body_json(
+            json!({ "segments": ["s1", "s2"], "languages": ["es"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "version": 2 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/dubbing/resource/dub_1/render/es"))
+        .and(body_json(json!({ "render_type": "mp3" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "render_id": "r1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let media = dir.path().join("talk.mp3");
+    std::fs::write(&media, b"ID3\x04\0\0\0\0\0\0").unwrap();
+    let map = dir.path().join("speakers.yaml");
+    std::fs::write(&map, "1: voice_1\nHost: original\n").unwrap();
+
+    let output = run_cli(
+        &server,
+        &[
+            "dub",
+            "create",
+            "--file",
+            media.to_str().unwrap(),
+            "--source-lang",
+            "en",
+            "--target-lang",
+            "es",
+            "--speaker-map",
+            map.to_str().unwrap(),
+            "--wait",
+            "--interval",
+            "1",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("Assigned voices to 2 speaker(s); rendering es"));
+    assert!(out.contains("Rendered es"), "{}", out);
+}
+
+#[tokio::test]
+async fn api_errors_include_request_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1/branches"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .insert_header("request-id", "req_abc123")
+                .set_body_json(json!({ "detail": { "status": "agent_not_found" } })),
+        )
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["agent", "branch", "list", "agent_1"]).await;
+    assert!(!output.status.success());
+    let err = stderr(&output);
+    assert!(err.contains("req_abc123"), "stderr: {}", err);
+    assert!(err.contains("agent_not_found"), "stderr: {}", err);
+}
+
+#[tokio::test]
+async fn agent_branch_promote_resolves_names() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1/branches"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "branch_id": "br_main", "name": "Main" },
+            { "branch_id": "br_staging", "name": "staging" },
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/agents/agent_1/branches/br_staging/merge"))
+        .and(body_json(json!({ "target_branch_id": "br_main" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &["agent", "branch", "promote", "agent_1", "Staging"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn agent_branch_promote_unknown_branch_makes_no_changes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1/branches"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "branch_id": "br_main", "name": "main" },
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["agent", "branch", "promote", "agent_1", "nope"]).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not found"));
+}
+
+#[tokio::test]
+async fn agent_transfer_set_adds_to_existing_rules() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agent_id": "agent_1",
+            "conversation_config": { "agent": { "prompt": { "built_in_tools": {
+                "transfer_to_agent": {
+                    "name": "transfer_to_agent",
+                    "description": "",
+                    "params": {
+                        "system_tool_type": "transfer_to_agent",
+                        "transfers": [{ "agent_id": "agent_sales", "condition": "buying" }]
+                    }
+                }
+            }}}}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/agents/agent_1"))
+        .and(body_partial_json(json!({
+            "conversation_config": { "agent": { "prompt": { "built_in_tools": {
+                "transfer_to_agent": { "params": { "transfers": [
+                    { "agent_id": "agent_sales", "condition": "buying" },
+                    { "agent_id": "agent_billing", "condition": "billing question" },
+                ]}}
+            }}}}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "agent",
+            "transfer",
+            "set",
+            "agent_1",
+            "--to-agent",
+            "agent_billing",
+            "--condition",
+            "billing question",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn agent_languages_set_writes_presets() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agent_id": "agent_1",
+            "conversation_config": {
+                "tts": { "model_id": "eleven_turbo_v2_5" },
+                "language_presets": {
+                    "fr": { "overrides": { "agent": { "first_message": "Bonjour" } } }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/agents/agent_1"))
+        .and(body_json(json!({
+            "conversation_config": { "language_presets": {
+                "fr": null,
+                "es": { "overrides": {
+                    "agent": { "first_message": "Hola" },
+                    "tts": { "voice_id": "voice_es" }
+                }}
+            }}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "agent",
+            "languages",
+            "set",
+            "agent_1",
+            "--add",
+            "es",
+            "--remove",
+            "fr",
+            "--voice-for",
+            "es=voice_es",
+            "--first-message-for",
+            "es=Hola",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn workspace_privacy_set_patches_every_agent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [
+                { "agent_id": "agent_1", "name": "Support" },
+                { "agent_id": "agent_2", "name": "Sales" },
+            ],
+            "has_more": true,
+            "next_cursor": "page_2"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .and(query_param("cursor", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "agent_id": "agent_3", "name": "Billing" }],
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    for agent_id in ["agent_1", "agent_2", "agent_3"] {
+        Mock::given(method("PATCH"))
+            .and(path(format!("/v1/agents/{}", agent_id)))
+            .and(body_json(json!({
+                "platform_settings": {
+                    "privacy": { "retention_days": 30, "record_voice": false }
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let output = run_cli(
+        &server,
+        &[
+            "--yes",
+            "workspace",
+            "privacy",
+            "set",
+            "--retention-days",
+            "30",
+            "--audio-save",
+            "false",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn tools_import_openapi_rerun_skips_tools_created_earlier() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/tools"))
+        .and(body_partial_json(
+            json!({ "tool_config": { "name": "getOrder" } }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "tool_1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/tools"))
+        .and(body_partial_json(
+            json!({ "tool_config": { "name": "listOrders" } }),
+        ))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/tools"))
+        .and(body_partial_json(
+            json!({ "tool_config": { "name": "listOrders" } }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "tool_2" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let spec = home.path().join("spec.yaml");
+    std::fs::write(
+        &spec,
+        r#"openapi: 3.0.0
+servers:
+  - url: https://shop.example/api
+paths:
+  /v1/orders/{order_id}:
+    get:
+      operationId: getOrder
+      parameters:
+        - { name: order_id, in: path, required: true, schema: { type: string } }
+  /v1/orders:
+    get:
+      operationId: listOrders
+"#,
+    )
+    .unwrap();
+    let args = ["tools", "import-openapi", spec.to_str().unwrap()];
+
+    // Without retries the 500 fails the first run
+    let first = run_cli_in(
+        &server,
+        home.path(),
+        &[&args[..], &["--max-retries", "0"]].concat(),
+    )
+    .await;
+    assert!(!first.status.success());
+
+    let second = run_cli_in(&server, home.path(), &args).await;
+    assert!(second.status.success(), "stderr: {}", stderr(&second));
+    let out = stdout(&second);
+    assert!(
+        out.contains("tool_1") && out.contains("tool_2"),
+        "stdout: {}",
+        out
+    );
+    let jobs = home.path().join("data/elevenlabs-cli/jobs");
+    assert_eq!(std::fs::read_dir(jobs).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn voice_verify_submits_recording_for_captcha() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices/pvc/voice_1/captcha"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "text": "Purple owls sing" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/voices/pvc/voice_1/captcha"))
+        .and(body_string_contains("name=\"recording\""))
+        .and(body_string_contains("RIFF-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "ok" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let recording = home.path().join("take.wav");
+    std::fs::write(&recording, b"RIFF-test").unwrap();
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "voice",
+            "verify",
+            "voice_1",
+            "--recording",
+            recording.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(stdout(&output).contains("Purple owls sing"));
+}
+
+#[tokio::test]
+async fn converse_audio_waveform_prints_duration_and_levels() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let tone = home.path().join("tone.wav");
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "audio",
+            "tone",
+            "--duration",
+            "2",
+            "-o",
+            tone.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/conversations/conv_1/audio"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(std::fs::read(&tone).unwrap()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let download = home.path().join("call.wav");
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "converse",
+            "audio",
+            "conv_1",
+            "-o",
+            download.to_str().unwrap(),
+            "--waveform",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("Duration 0:02"), "stdout: {}", out);
+    assert!(out.contains("0% silence"), "stdout: {}", out);
+    assert!(out.contains('█'), "stdout: {}", out);
+}
+
+#[tokio::test]
+async fn usage_alert_fails_at_threshold() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "character_count": 85000,
+            "character_limit": 100000
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["usage", "alert", "--threshold", "80"]).await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("85.0%"),
+        "stderr: {}",
+        stderr(&output)
+    );
+
+    let output = run_cli(&server, &["usage", "alert", "--threshold", "90"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "0")
+                .set_body_string("too_many_concurrent_requests"),
+        )
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "character_count": 10,
+            "character_limit": 100
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["usage", "alert", "--retry-delay", "0"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(
+        stderr(&output).contains("retrying in 0.0s (2/3)"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn retries_stop_at_max_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "usage",
+            "alert",
+            "--max-retries",
+            "1",
+            "--retry-delay",
+            "0.01",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn rate_limited_posts_are_retried() {
+    // A 429 is returned before any work is done, so a POST is safe to resend
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian/stream"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "0")
+                .set_body_string("system_busy"),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian/stream"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1u8, 0, 2, 0]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "tts-stream",
+            "Hello there",
+            "--voice",
+            "voice_brian",
+            "--output-format",
+            "pcm_16000",
+            "--stdout",
+            "--retry-delay",
+            "0",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(output.stdout, [1u8, 0, 2, 0]);
+}
+
+#[tokio::test]
+async fn failed_posts_are_not_retried() {
+    // The server may have processed (and billed) the request before failing
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/new-endpoint"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &["api", "post", "/v1/new-endpoint", "--retry-delay", "0"],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(!stderr(&output).contains("retrying"));
+}
+
+#[tokio::test]
+async fn api_command_passes_headers_and_query() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/new-endpoint"))
+        .and(query_param("page_size", "5"))
+        .and(header("xi-api-key", "test-api-key"))
+        .and(header("x-beta", "on"))
+        .and(body_json(json!({ "name": "demo" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "new_1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "api",
+            "post",
+            "/v1/new-endpoint",
+            "--data",
+            r#"{"name": "demo"}"#,
+            "--query",
+            "page_size=5",
+            "--header",
+            "X-Beta: on",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let body: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(body["id"], "new_1");
+}
+
+#[tokio::test]
+async fn warn_unknown_fields_reports_new_response_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/webhooks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": "wh1",
+            "name": "Alerts",
+            "url": "https://example.com/hook",
+            "events": ["usage"],
+            "signing_version": 2
+        }])))
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["webhook", "list"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(!stderr(&output).contains("Unknown fields"));
+
+    let output = run_cli(&server, &["webhook", "list", "--warn-unknown-fields"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(
+        stderr(&output).contains("Unknown fields in /v1/webhooks response: [].signing_version"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn knowledge_update_relinks_agents_before_deleting() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let file = home.path().join("faq.md");
+    std::fs::write(&file, "# FAQ\nNew answers").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "kb_old",
+            "name": "FAQ",
+            "type": "file",
+            "created_at": "2026-01-01"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_old/dependent-agents"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "id": "agent_1", "name": "Support" }],
+            "has_more": true,
+            "next_cursor": "page_2"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_old/dependent-agents"))
+        .and(query_param("cursor", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "id": "agent_2", "name": "Sales" }],
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .and(body_json(json!({
+            "name": "FAQ",
+            "type": "text",
+            "content": "# FAQ\nNew answers"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "kb_new" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    for agent_id in ["agent_1", "agent_2"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/agents/{}", agent_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "agent_id": agent_id,
+                "conversation_config": { "agent": { "prompt": { "knowledge_base": [
+                    { "id": "kb_old", "name": "FAQ", "type": "file", "usage_mode": "auto" }
+                ]}}}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(format!("/v1/agents/{}", agent_id)))
+            .and(body_json(json!({
+                "conversation_config": { "agent": { "prompt": { "knowledge_base": [
+                    { "id": "kb_new", "name": "FAQ", "type": "text", "usage_mode": "auto" }
+                ]}}}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("DELETE"))
+        .and(path("/v1/convai/knowledge-base/kb_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base/kb_new/rebuild-index"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "rag_1",
+            "status": "created"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "knowledge",
+            "update",
+            "kb_old",
+            "--file",
+            file.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(
+        stdout(&output).contains("kb_new"),
+        "stdout: {}",
+        stdout(&output)
+    );
+}
+
+#[tokio::test]
+async fn knowledge_update_refuses_url_documents() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let file = home.path().join("faq.md");
+    std::fs::write(&file, "New answers").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_url"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "kb_url",
+            "name": "Docs",
+            "type": "url",
+            "url": "https://example.com/docs"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "kb_new" })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "knowledge",
+            "update",
+            "kb_url",
+            "--file",
+            file.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("URL document"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn knowledge_update_keeps_old_document_for_indirect_dependents() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let file = home.path().join("faq.md");
+    std::fs::write(&file, "New answers").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "kb_old",
+            "name": "FAQ",
+            "type": "text"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_old/dependent-agents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "id": "agent_1" }],
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "kb_new" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agent_id": "agent_1",
+            "conversation_config": { "agent": { "prompt": { "knowledge_base": [] }}}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/convai/knowledge-base/kb_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "knowledge",
+            "update",
+            "kb_old",
+            "--file",
+            file.to_str().unwrap(),
+            "--no-rebuild",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("could not be relinked"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn json_flag_prints_a_single_document() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/phone-numbers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "phone_numbers": [{
+                "phone_number_id": "pn1",
+                "phone_number": "+14155550123",
+                "label": "Support",
+                "provider": "twilio"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/workspaces/secrets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "phone", "list"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let phones: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap_or_else(|e| {
+        panic!(
+            "stdout is not one JSON document ({}): {}",
+            e,
+            stdout(&output)
+        )
+    });
+    assert_eq!(phones[0]["phone_number_id"], "pn1");
+    assert_eq!(phones[0]["label"], "Support");
+    assert!(!stdout(&output).contains('\u{1b}'));
+
+    // Empty lists are still a document, not an info message
+    let output = run_cli(&server, &["--json", "workspace", "secrets"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let secrets: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(secrets, json!([]));
+}
+
+#[tokio::test]
+async fn project_conversion_is_journaled_and_waited_on() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/v1/projects/p1/convert"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "default"
+        })))
+        .mount(&server)
+        .await;
+
+    let output = run_cli_in(&server, home.path(), &["projects", "convert", "p1"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["--json", "jobs", "list", "--no-refresh"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list[0]["id"], "p1");
+    assert_eq!(list[0]["kind"], "project");
+    assert_eq!(list[0]["phase"], "running");
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["jobs", "wait", "p1", "--interval", "1"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["--json", "jobs", "list", "--active", "--no-refresh"],
+    )
+    .await;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap(),
+        json!([])
+    );
+
+    let output = run_cli_in(&server, home.path(), &["jobs", "status", "other"]).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not in the journal"));
+}
+
+#[tokio::test]
+async fn projects_convert_wait_fails_when_conversion_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/projects/p1/convert"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "converting"
+        })))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "conversion_failed"
+        })))
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &["projects", "convert", "p1", "--wait", "--interval", "1"],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("Job 'p1' failed"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn voice_defaults_apply_to_dialogue() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("dialogue.mp3");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-dialogue/stream/with-timestamps"))
+        .and(body_partial_json(json!({"settings": {"stability": 0.5}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"audio_base64": "AAAA"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    for args in [
+        ["voice", "defaults", "set", "Rachel", "--stability", "0.5"].as_slice(),
+        &["voice", "defaults", "set", "rachel", "--style", "0.3"],
+    ] {
+        let output = run_cli_in(&server, home.path(), args).await;
+        assert!(output.status.success(), "stderr: {}", stderr(&output));
+    }
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["--json", "voice", "defaults", "list"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list, json!({"Rachel": {"stability": 0.5, "style": 0.3}}));
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "dialogue",
+            "--inputs",
+            "Hello there:Rachel",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn dialogue_casting_maps_characters_to_voices() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+    let casting = home.path().join("casting.yaml");
+    std::fs::write(
+        &casting,
+        "characters:\n  Aeryn:\n    voice: v-aeryn\n    stability: 0.5\n    dictionary:\n      Aeryn: Air-in\n",
+    )
+    .unwrap();
+    let out = home.path().join("dialogue.mp3");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-dialogue/stream/with-timestamps"))
+        .and(body_partial_json(json!({
+            "inputs": [
+                {"text": "I am Air-in", "voice_id": "v-aeryn"},
+                {"text": "Hi Aeryn", "voice_id": "v-other"}
+            ],
+            "settings": {"stability": 0.5}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"audio_base64": "AAAA"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "dialogue",
+            "--casting",
+            casting.to_str().unwrap(),
+            "--inputs",
+            "I am Aeryn:aeryn,Hi Aeryn:v-other",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(stdout(&output).contains("'v-other' is not in the casting file"));
+}
+
+#[tokio::test]
+async fn stt_retries_detection_outside_language_hints() {
+    let server = MockServer::start().await;
+    let transcript = |code: &str, text: &str, logprob: f64| {
+        json!({
+            "language_code": code,
+            "language_probability": 0.6,
+            "text": text,
+            "words": [{ "text": text, "start": 0.0, "end": 0.5, "type": "word", "logprob": logprob }]
+        })
+    };
+    Mock::given(method("POST"))
+        .and(path("/v1/speech-to-text"))
+        .and(body_string_contains("name=\"language_code\"\r\n\r\nen\r\n"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(transcript("eng", "Hallo", -2.0)))
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/speech-to-text"))
+        .and(body_string_contains("name=\"language_code\"\r\n\r\nde\r\n"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(transcript("deu", "Hallo", -0.1)))
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/speech-to-text"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(transcript("nld", "Hallo", -0.5)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let audio = home.path().join("talk.wav");
+    std::fs::write(&audio, b"RIFF-test").unwrap();
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "stt",
+            audio.to_str().unwrap(),
+            "--languages",
+            "en,de",
+            "--format",
+            "json",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("not in --languages"));
+    assert!(out.contains("\"language_code\": \"deu\""));
+}
+
+#[tokio::test]
+async fn dub_download_and_transcript_pick_the_language() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "dubbing_id": "dub_1",
+            "name": "Interview",
+            "status": "dubbed",
+            "target_languages": ["es", "de"]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1/audio/de"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"dubbed-media".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1/transcript/es"))
+        .and(query_param("format_type", "webvtt"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("WEBVTT\n\n00:00.000 --> 00:01.000\nHola\n"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let media = home.path().join("interview.de.mp4");
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "dub",
+            "download",
+            "dub_1",
+            "--language",
+            "de",
+            "-o",
+            media.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(std::fs::read(&media).unwrap(), b"dubbed-media");
+    assert!(!home.path().join("interview.de.mp4.part").exists());
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["dub", "transcript", "dub_1", "--format", "vtt"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(stdout(&output).contains("Hola"));
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "dub",
+            "download",
+            "dub_1",
+            "--language",
+            "fr",
+            "-o",
+            "x.mp4",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("no 'fr' track"));
+}
+
+#[tokio::test]
+async fn dub_download_yes_skips_the_status_prompt() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "dubbing_id": "dub_1",
+            "name": "Interview",
+            "status": "dubbing",
+            "target_languages": ["es"]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/dub_1/audio/es"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"partial-media".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let media = home.path().join("interview.es.mp4");
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "--yes",
+            "dub",
+            "download",
+            "dub_1",
+            "-o",
+            media.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(std::fs::read(&media).unwrap(), b"partial-media");
+}
+
+#[tokio::test]
+async fn schedule_add_list_and_remove() {
+    let server = MockServer::start().await;
+    let home = tempfile::tempdir().unwrap();
+
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "schedule",
+            "add",
+            "--cron",
+            "0 9 * * *",
+            "--cmd",
+            "usage alert --threshold 80",
+            "--name",
+            "quota",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let output = run_cli_in(&server, home.path(), &["--json", "schedule", "list"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list[0]["id"], "1");
+    assert_eq!(list[0]["name"], "quota");
+    assert_eq!(
+        list[0]["args"],
+        json!(["usage", "alert", "--threshold", "80"])
+    );
+    assert!(list[0]["next_run"].as_u64().unwrap() % 86_400 == 9 * 3_600);
+
+    let output = run_cli_in(&server, home.path(), &["schedule", "rm", "1"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let output = run_cli_in(&server, home.path(), &["--json", "schedule", "list"]).await;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap(),
+        json!([])
+    );
+}
+
+#[tokio::test]
+async fn projects_list_shows_chapters_and_last_snapshot() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "projects": [{"project_id": "p1", "name": "Book", "state": "default"}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "default",
+            "default_paragraph_voice_id": "voice_1",
+            "chapters": [
+                {"chapter_id": "c1", "name": "One", "state": "default"},
+                {"chapter_id": "c2", "name": "Two", "state": "converting", "conversion_progress": 0.4}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1/snapshots"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "snapshots": [
+                {"project_snapshot_id": "s1", "created_at_unix": 1_700_000_000u64},
+                {"project_snapshot_id": "s2", "created_at_unix": 1_700_000_500u64}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "projects", "list"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(result[0]["state"], "converting");
+    assert_eq!(result[0]["chapters"], 2);
+    assert_eq!(result[0]["converting_chapters"], 1);
+    assert_eq!(result[0]["default_voice_id"], "voice_1");
+    assert_eq!(result[0]["last_snapshot_unix"], 1_700_000_500u64);
+}
+
+#[tokio::test]
+async fn projects_get_watch_follows_conversion_until_done() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "converting",
+            "chapters": [{"chapter_id": "c1", "name": "One", "state": "converting"}]
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "project_id": "p1",
+            "name": "Book",
+            "state": "default",
+            "chapters": [{"chapter_id": "c1", "name": "One", "state": "default"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &["projects", "get", "p1", "--watch", "--interval", "1"],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(
+        stdout(&output).contains("ready"),
+        "stdout: {}",
+        stdout(&output)
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn converse_list_sends_filters_and_filters_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/conversations"))
+        .and(query_param("call_successful", "failure"))
+        .and(query_param("page_size", "5"))
+        .and(query_param("call_start_after_unix", "1748736000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "conversations": [
+                { "conversation_id": "conv_failed", "status": "failed", "call_successful": "failure" },
+                { "conversation_id": "conv_done", "status": "done", "call_successful": "failure" },
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "converse",
+            "list",
+            "--call-successful",
+            "false",
+            "--since",
+            "2025-06-01",
+            "-l",
+            "5",
+            "--status",
+            "failed",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("conv_failed"), "stdout: {}", out);
+    assert!(!out.contains("conv_done"), "stdout: {}", out);
+}
+
+#[tokio::test]
+async fn agent_simulate_script_fails_on_failed_criteria() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/agents/agent_1/simulate-conversation"))
+        .and(body_partial_json(json!({
+            "new_turns_limit": 2,
+            "simulation_specification": {
+                "tool_mock_config": {
+                    "lookup_order": { "default_return_value": "shipped", "default_is_error": false }
+                }
+            },
+            "extra_evaluation_criteria": [{ "id": "resolved" }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "simulated_conversation": [
+                { "role": "user", "message": "Where is my order?" },
+                { "role": "agent", "message": "Let me check.", "tool_calls": [{ "tool_name": "lookup_order" }] }
+            ],
+            "analysis": {
+                "call_successful": "failure",
+                "evaluation_criteria_results": {
+                    "resolved": { "criteria_id": "resolved", "result": "failure", "rationale": "No status given" }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("script.json");
+    std::fs::write(
+        &script,
+        json!({
+            "turns": [{ "message": "Where is my order?" }],
+            "tool_mocks": { "lookup_order": { "result": "shipped" } }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let output = run_cli(
+        &server,
+        &[
+            "agent",
+            "simulate",
+            "agent_1",
+            "--script",
+            script.to_str().unwrap(),
+            "--criterion",
+            "resolved=The agent gave the order status",
+            "--max-turns",
+            "2",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("No status given"));
+    assert!(stderr(&output).contains("failed 1 of 1"));
+}
+
+#[tokio::test]
+async fn agent_smoke_reports_missing_references() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/agents/agent_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agent_id": "agent_1",
+            "name": "Support",
+            "conversation_config": {
+                "tts": { "voice_id": "voice_1" },
+                "agent": { "prompt": {
+                    "prompt": "You answer support questions.",
+                    "knowledge_base": [{ "id": "kb_gone", "name": "FAQ", "type": "url" }],
+                    "tool_ids": ["tool_1"]
+                }}
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices/voice_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "voice_id": "voice_1" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "detail": "not found" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/tools/tool_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "tool_1" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/agents/agent_1/simulate-conversation"))
+        .and(body_partial_json(json!({ "new_turns_limit": 1 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "simulated_conversation": [
+                { "role": "user", "message": "Hello, can you help me?" },
+                { "role": "agent", "message": "Of course! What do you need?" }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "agent", "smoke", "agent_1"]).await;
+    assert!(!output.status.success());
+    let report = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(report["passed"], false);
+    let statuses: Vec<(&str, &str)> = report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| (c["check"].as_str().unwrap(), c["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("Agent config", "pass"),
+            ("Voice voice_1", "pass"),
+            ("Knowledge base 'FAQ'", "fail"),
+            ("Tool tool_1", "pass"),
+            ("Simulated turn", "pass"),
+        ]
+    );
+    assert!(report["checks"][2]["fix"].is_string());
+}
+
+#[tokio::test]
+async fn agent_export_then_import_drops_missing_references() {
+    let server = MockServer::start().await;
+    let conversation_config = json!({
+        "tts": { "voice_id": "voice_1" },
+        "agent": { "prompt": {
+            "prompt": "You answer support questions.",
+            "knowledge_base": [{ "id": "kb_1", "name": "FAQ", "type": "url" }],
+            "tool_ids": ["tool_1", "tool_gone"]
+        }}
+    });
+    Mock::given(method("GET"))
+        .and(path("/v1/agents/agent_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agent_id": "agent_1",
+            "name": "Support",
+            "conversation_config": conversation_config,
+            "metadata": { "created_at_unix_secs": 1700000000 }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices/voice_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "voice_id": "voice_1" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base/kb_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "kb_1" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/tools/tool_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "tool_1" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/tools/tool_gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "detail": "not found" })))
+        .mount(&server)
+        .await;
+    let mut imported_config = conversation_config.clone();
+    imported_config["agent"]["prompt"]["tool_ids"] = json!(["tool_1"]);
+    Mock::given(method("POST"))
+        .and(path("/v1/agents"))
+        .and(body_json(json!({
+            "name": "Support (staging)",
+            "conversation_config": imported_config
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "agent_id": "agent_2" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("agent.yaml");
+    let file = file.to_str().unwrap();
+    let output = run_cli(&server, &["agent", "export", "agent_1", "-o", file]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let exported = std::fs::read_to_string(file).unwrap();
+    assert!(exported.contains("name: Support"));
+    assert!(!exported.contains("agent_1"));
+    assert!(!exported.contains("metadata"));
+
+    std::fs::write(file, "name: Edited\n").unwrap();
+    let output = run_cli(
+        &server,
+        &["agent", "export", "agent_1", "-o", file, "--no-input"],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("--yes"), "{}", stderr(&output));
+    assert_eq!(std::fs::read_to_string(file).unwrap(), "name: Edited\n");
+    let output = run_cli(
+        &server,
+        &["agent", "export", "agent_1", "-o", file, "--yes"],
+    )
+    .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(std::fs::read_to_string(file).unwrap(), exported);
+
+    let output = run_cli(&server, &["agent", "import", file]).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("tool tool_gone"));
+    assert!(stderr(&output).contains("--drop-missing"));
+
+    let output = run_cli(
+        &server,
+        &[
+            "--json",
+            "agent",
+            "import",
+            file,
+            "--name",
+            "Support (staging)",
+            "--drop-missing",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["agent_id"], "agent_2");
+}
+
+#[tokio::test]
+async fn library_publish_shares_voice_with_listing() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices/voice_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "voice_id": "voice_1",
+            "name": "Narrator",
+            "category": "professional",
+            "labels": { "accent": "american" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/voices/voice_1/share"))
+        .and(body_json(json!({
+            "category": "professional",
+            "name": "Narrator",
+            "labels": { "accent": "british", "gender": "male" },
+            "notice_period": 30,
+            "enabled_in_library": true
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "review_status": "pending" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "library",
+            "publish",
+            "voice_1",
+            "--labels",
+            "accent=british,gender=male",
+            "--notice-period",
+            "30",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Voice submitted to the library"));
+    assert!(stdout(&output).contains("pending"));
+}
+
+#[tokio::test]
+async fn voice_label_bulk_updates_matching_voices_only() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "voices": [
+                { "voice_id": "v_clone", "name": "Hero", "category": "cloned", "labels": { "accent": "british" } },
+                { "voice_id": "v_made", "name": "Adam", "category": "premade", "labels": {} },
+                { "voice_id": "v_gen", "name": "Villain", "category": "generated", "labels": {} }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/voices/v_clone/edit"))
+        .and(body_string_contains(
+            r#"{"accent":"british","project":"game1"}"#,
+        ))
+        .and(body_string_contains("Hero"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "ok" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "voice",
+            "label",
+            "bulk",
+            "--filter",
+            "category=cloned",
+            "--set",
+            "project=game1",
+            "--yes",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+}
+
+#[tokio::test]
+async fn search_index_is_kept_per_api_key() {
+    let server = MockServer::start().await;
+    for (endpoint, body) in [
+        ("/v1/voices", json!({ "voices": [] })),
+        ("/v1/agents", json!([])),
+        ("/v1/history", json!({ "history": [], "has_more": false })),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            // Once per key; the repeated search uses the first key's index
+            .expect(2)
+            .mount(&server)
+            .await;
+    }
+
+    let home = tempfile::tempdir().unwrap();
+    for args in [
+        &["search", "pirate"][..],
+        &["search", "pirate"],
+        &["--api-key", "other-api-key", "search", "pirate"],
+    ] {
+        let output = run_cli_in(&server, home.path(), args).await;
+        assert!(output.status.success(), "stderr: {}", stderr(&output));
+    }
+}
+
+#[tokio::test]
+async fn search_builds_index_and_matches_across_kinds() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "voices": [
+                { "voice_id": "v_pirate", "name": "Pirate Pete", "labels": { "accent": "british" } },
+                { "voice_id": "v_calm", "name": "Calm Clara" }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "agent_id": "ag_1", "name": "Support" }
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "history": [
+                { "history_item_id": "h_1", "voice_name": "Brian", "text": "Arr, the pirate ship sails" }
+            ],
+            "has_more": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "search", "pirate"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    // Status lines go to stderr, so stdout is exactly the results
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<&str> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["v_pirate", "h_1"]);
+}
+
+#[tokio::test]
+async fn search_indexes_agents_on_later_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "voices": [] })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/history"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "history": [], "has_more": false })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "agent_id": "ag_pirate", "name": "Pirate Helpdesk" }],
+            "has_more": false
+        })))
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "agent_id": "ag_1", "name": "Support" }],
+            "has_more": true,
+            "next_cursor": "page2"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "search", "pirate"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["id"], "ag_pirate", "{}", results);
+}
+
+#[tokio::test]
+async fn export_writes_backup_directory_and_manifest() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "voices": [
+                {
+                    "voice_id": "v_clone", "name": "Hero", "category": "cloned",
+                    "samples": [{ "sample_id": "s_1", "file_name": "take1.mp3" }]
+                },
+                { "voice_id": "v_made", "name": "Adam", "category": "premade" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices/v_clone/samples/s_1/audio"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"ID3audio".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "agent_id": "ag_1", "name": "Support" }],
+            "has_more": true,
+            "next_cursor": "page_2"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .and(query_param("cursor", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "agents": [{ "agent_id": "ag_2", "name": "Sales" }],
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    for (id, name) in [("ag_1", "Support"), ("ag_2", "Sales")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/agents/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "agent_id": id, "name": name, "conversation_config": { "agent": {} }
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/v1/webhooks"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("backup");
+    let output = run_cli(
+        &server,
+        &[
+            "export",
+            "--out",
+            out.to_str().unwrap(),
+            "--only",
+            "voices,agents,webhooks",
+            "--max-retries",
+            "0",
+        ],
+    )
+    .await;
+    // The webhook failure is reported, but the other sections are still written
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("1 error"),
+        "stderr: {}",
+        stderr(&output)
+    );
+
+    assert_eq!(
+        std::fs::read(out.join("voices/v_clone/samples/s_1_take1.mp3")).unwrap(),
+        b"ID3audio"
+    );
+    assert!(!out.join("voices/v_made").exists());
+    let agent: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("agents/ag_1.json")).unwrap()).unwrap();
+    assert!(agent["conversation_config"].is_object());
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+    let ids: Vec<&str> = manifest["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["v_clone", "ag_1", "ag_2"]);
+    assert_eq!(manifest["errors"][0]["section"], "webhooks");
+}
+
+#[tokio::test]
+async fn import_reuses_existing_and_remaps_agent_references() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "voices": [{ "voice_id": "v_there", "name": "Hero" }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/convai/knowledge-base"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "documents": [] })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/agents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "agents": [] })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/voices/add"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .and(body_json(json!({
+            "name": "FAQ", "type": "url", "url": "https://example.com/faq"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "kb_new" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/agents"))
+        .and(body_partial_json(json!({
+            "name": "Support",
+            "conversation_config": {
+                "tts": { "voice_id": "v_there" },
+                "agent": { "prompt": { "knowledge_base": [{ "id": "kb_new" }] } }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "agent_id": "ag_new" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let backup = dir.path();
+    let write = |relative: &str, value: serde_json::Value| {
+        let file = backup.join(relative);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, value.to_string()).unwrap();
+    };
+    write(
+        "voices/v_old/voice.json",
+        json!({ "voice_id": "v_old", "name": "Hero" }),
+    );
+    write(
+        "knowledge/kb_old.json",
+        json!({ "id": "kb_old", "name": "FAQ", "type": "url", "url": "https://example.com/faq" }),
+    );
+    write(
+        "agents/ag_old.json",
+        json!({
+            "agent_id": "ag_old",
+            "name": "Support",
+            "conversation_config": {
+                "tts": { "voice_id": "v_old" },
+                "agent": { "prompt": { "knowledge_base": [{ "id": "kb_old", "type": "url" }] } }
+            }
+        }),
+    );
+    write(
+        "manifest.json",
+        json!({
+            "format_version": 1,
+            "cli_version": "0.0.0",
+            "created_at": 0,
+            "items": [
+                { "section": "voices", "id": "v_old", "name": "Hero", "path": "voices/v_old/voice.json" },
+                { "section": "knowledge", "id": "kb_old", "name": "FAQ", "path": "knowledge/kb_old.json" },
+                { "section": "agents", "id": "ag_old", "name": "Support", "path": "agents/ag_old.json" }
+            ]
+        }),
+    );
+
+    let output = run_cli(
+        &server,
+        &[
+            "--json",
+            "import",
+            backup.to_str().unwrap(),
+            "--skip-existing",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let results = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .unwrap();
+    let mapping: Vec<(&str, &str, &str)> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["old_id"].as_str().unwrap(),
+                r["new_id"].as_str().unwrap(),
+                r["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        mapping,
+        vec![
+            ("v_old", "v_there", "exists"),
+            ("kb_old", "kb_new", "created"),
+            ("ag_old", "ag_new", "created"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn audio_native_create_from_url_uploads_extracted_article() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/blog/post"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            r#"<html><head><title>Launch notes | Blog</title><meta name="author" content="Ada"></head>
+            <body><nav><a href="/">Home</a></nav>
+            <article><h1>Launch notes</h1>
+            <p>Today we shipped the new importer, after a long beta with many teams.</p>
+            <p>It handles larger files, retries on failure, and reports progress clearly.</p>
+            </article><footer><p>Copyright and legal notices, all rights reserved here.</p></footer></body></html>"#,
+            "text/html",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/audio-native"))
+        .and(body_string_contains("Today we shipped the new importer"))
+        .and(body_string_contains("Launch notes"))
+        .and(body_string_contains("Ada"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "project_id": "an_1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/audio-native"))
+        .and(body_string_contains("Copyright"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let page = format!("{}/blog/post", server.uri());
+    let output = run_cli(
+        &server,
+        &["audio-native", "create", "--name", "Launch", "--url", &page],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(stdout(&output).contains("an_1"));
+}
+
+#[tokio::test]
+async fn tts_feed_narrates_latest_items_and_skips_existing() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feed.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example Blog</title>
+    <item>
+      <title>New importer</title>
+      <pubDate>Tue, 10 Jun 2025 12:00:00 +0000</pubDate>
+      <content:encoded><![CDATA[<p>Today we shipped the new importer.</p>]]></content:encoded>
+    </item>
+    <item>
+      <title>Older post</title>
+      <description>Not narrated</description>
+    </item>
+  </channel>
+</rss>"#,
+            "application/rss+xml",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "voices": [{ "voice_id": "voice_brian", "name": "Brian" }] }),
+            ),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian"))
+        .and(body_string_contains("New importer."))
+        .and(body_string_contains("Today we shipped the new importer."))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"audio".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out_dir = dir.path().to_str().unwrap();
+    let feed = format!("{}/feed.xml", server.uri());
+    let args = ["tts", "feed", &feed, "--latest", "1", "--out-dir", out_dir];
+    let output = run_cli(&server, &args).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let episode = std::fs::read(dir.path().join("2025-06-10-new-importer.mp3")).unwrap();
+    assert!(episode.starts_with(b"ID3"), "episode should carry ID3 tags");
+    assert!(episode.ends_with(b"audio"));
+
+    // A second run finds the episode on disk and makes no TTS request
+    let output = run_cli(&server, &args).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(stdout(&output).contains("Already narrated"));
+}
+
+#[tokio::test]
+async fn ivr_build_writes_ulaw_prompts_and_manifest() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "voices": [
+            { "voice_id": "voice_rachel", "name": "Rachel" },
+            { "voice_id": "voice_adam", "name": "Adam" }
+        ] })))
+        .mount(&server)
+        .await;
+    // 0.5s of 8 kHz silence as 16-bit PCM
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_rachel"))
+        .and(query_param("output_format", "pcm_8000"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 8_000]))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_adam"))
+        .and(body_string_contains("We are closed."))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 16_000]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let prompts = dir.path().join("prompts.yaml");
+    std::fs::write(
+        &prompts,
+        "prompts:\n  - id: welcome\n    text: Thanks for calling.\n  - id: closed\n    text: We are closed.\n    voice: Adam\n",
+    )
+    .unwrap();
+    let out_dir = dir.path().join("ivr");
+    let output = run_cli(
+        &server,
+        &[
+            "ivr",
+            "build",
+            prompts.to_str().unwrap(),
+            "--voice",
+            "Rachel",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    // μ-law silence is 0xFF, one byte per sample
+    assert_eq!(
+        std::fs::read(out_dir.join("welcome.ulaw")).unwrap(),
+        vec![0xFF; 4_000]
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out_dir.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["encoding"], "ulaw");
+    assert_eq!(manifest["sample_rate"], 8_000);
+    assert_eq!(manifest["prompts"][0]["file"], "welcome.ulaw");
+    assert_eq!(manifest["prompts"][0]["duration_secs"], 0.5);
+    assert_eq!(manifest["prompts"][1]["voice_id"], "voice_adam");
+    assert_eq!(manifest["prompts"][1]["duration_secs"], 1.0);
+}
+
+#[tokio::test]
+async fn dub_redo_regenerates_only_segments_in_range() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/dubbing/resource/dub_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "dub_1",
+            "target_languages": ["es", "fr"],
+            "speaker_segments": {
+                "seg_a": { "id": "seg_a", "start_time": 100.0, "end_time": 125.0, "text": "Before" },
+                "seg_b": { "id": "seg_b", "start_time": 128.0, "end_time": 135.0, "text": "Inside" },
+                "seg_c": { "id": "seg_c", "start_time": 138.0, "end_time": 150.0, "text": "Also inside" },
+                "seg_d": { "id": "seg_d", "start_time": 170.0, "end_time": 180.0, "text": "After" }
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/dubbing/resource/dub_1/dub"))
+        .and(body_json(
+            json!({ "segments": ["seg_b", "seg_c"], "languages": ["es"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "version": 2 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/dubbing/resource/dub_1/render/es"))
+        .and(body_json(json!({ "render_type": "mp4" })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "version": 2, "render_id": "render_9" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "--json",
+            "dub",
+            "redo",
+            "dub_1",
+            "--range",
+            "00:02:10-00:02:40",
+            "--lang",
+            "es",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(result["renders"][0]["render_id"], "render_9");
+    assert_eq!(result["segments"].as_array().unwrap().len(), 2);
+
+    let output = run_cli(
+        &server,
+        &["dub", "redo", "dub_1", "-r", "2:10-2:40", "--lang", "de"],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("no 'de' track"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn knowledge_add_from_url_crawls_matching_pages() {
+    let server = MockServer::start().await;
+    let site = server.uri();
+    let html = |body: &str| {
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/html")
+            .set_body_string(format!("<html><body>{}</body></html>", body))
+    };
+    Mock::given(method("GET"))
+        .and(path("/docs/"))
+        .respond_with(html(&format!(
+            r#"<a href="a#intro">A</a> <a href="b">B</a> <a href="/blog/x">Blog</a>
+               <a href="https://other.example/docs/z">Elsewhere</a> <a href="{}/docs/a">A again</a>"#,
+            site
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/docs/a"))
+        .respond_with(html(r#"<a href="/docs/deeper">Too deep</a>"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sitemap.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            r#"<urlset><url><loc>{0}/docs/a</loc></url><url><loc>{0}/blog/x</loc></url></urlset>"#,
+            site
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .and(body_partial_json(
+            json!({ "type": "url", "name": "Docs - docs/a" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "doc_a" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/knowledge-base"))
+        .and(body_partial_json(
+            json!({ "type": "url", "name": "Docs - docs" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "doc_root" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let root = format!("{}/docs/", site);
+    let output = run_cli(
+        &server,
+        &[
+            "--json",
+            "knowledge",
+            "add-from-url",
+            "--url",
+            &root,
+            "--name",
+            "Docs",
+            "--crawl-depth",
+            "1",
+            "--include",
+            "/docs/*",
+            "--exclude",
+            "*/b",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    let ids: Vec<&str> = result["pages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["doc_root", "doc_a"]);
+
+    // Sitemap dry runs only list pages
+    let output = run_cli(
+        &server,
+        &[
+            "knowledge",
+            "add-from-url",
+            "--url",
+            &site,
+            "--name",
+            "Docs",
+            "--sitemap",
+            "--exclude",
+            "/blog/*",
+            "--dry-run",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(
+        out.contains("/docs/a") && !out.contains("/blog/x"),
+        "stdout: {}",
+        out
+    );
+    assert!(out.contains("1 page(s) would be added"), "stdout: {}", out);
+}
+
+#[tokio::test]
+async fn models_cost_uses_model_rate_and_plan_prices() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "model_id": "eleven_multilingual_v2", "model_rates": { "character_cost_multiplier": 1.0 } },
+            { "model_id": "eleven_turbo_v2_5", "model_rates": { "character_cost_multiplier": 0.5 } }
+        ])))
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let book = home.path().join("book.txt");
+    std::fs::write(&book, "é".repeat(1_000)).unwrap();
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "--json",
+            "models",
+            "cost",
+            "--model",
+            "eleven_turbo_v2_5",
+            "--file",
+            book.to_str().unwrap(),
+            "--tier",
+            "pro",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(result["characters"], 1_000);
+    assert_eq!(result["credits"], 500);
+    assert_eq!(result["plans"][0]["tier"], "pro");
+    let cost = result["plans"][0]["cost_usd"].as_f64().unwrap();
+    assert!((cost - 0.099).abs() < 1e-9, "cost: {}", cost);
+
+    let output = run_cli(
+        &server,
+        &[
+            "models",
+            "cost",
+            "--model",
+            "eleven_v9",
+            "--characters",
+            "10",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Unknown model 'eleven_v9'"));
+}
+
+#[tokio::test]
+async fn tools_import_openapi_creates_webhook_tools() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/convai/tools"))
+        .and(body_partial_json(json!({
+            "tool_config": {
+                "type": "webhook",
+                "name": "getOrder",
+                "api_schema": {
+                    "url": "https://shop.example/api/v1/orders/{order_id}",
+                    "method": "GET",
+                    "path_params_schema": { "order_id": { "type": "string" } }
+                }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "tool_1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let spec = home.path().join("spec.yaml");
+    std::fs::write(
+        &spec,
+        r#"openapi: 3.0.0
+servers:
+  - url: https://shop.example/api
+paths:
+  /v1/orders/{order_id}:
+    get:
+      operationId: getOrder
+      summary: Look up an order
+      parameters:
+        - { name: order_id, in: path, required: true, schema: { type: string } }
+  /v1/health:
+    get:
+      operationId: health
+"#,
+    )
+    .unwrap();
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &[
+            "tools",
+            "import-openapi",
+            spec.to_str().unwrap(),
+            "--filter",
+            "/v1/orders*",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert!(
+        stdout(&output).contains("tool_1"),
+        "stdout: {}",
+        stdout(&output)
+    );
+}
+
+#[tokio::test]
+async fn limits_reports_tier_and_records_concurrency_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("maximum-concurrent-requests", "5")
+                .insert_header("current-concurrent-requests", "1")
+                .set_body_json(json!({
+                    "tier": "creator",
+                    "character_count": 2500,
+                    "character_limit": 100000,
+                    "voice_limit": 30
+                })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(&server, &["--json", "limits"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(result["tier"], "creator");
+    assert_eq!(result["concurrency_limit"], 5);
+    assert_eq!(result["observed"]["max_concurrent"], 5);
+    assert_eq!(result["observed"]["peak_concurrent"], 1);
+    assert_eq!(result["observed"]["throttled"], 0);
+}
+
+#[tokio::test]
+async fn successful_responses_record_concurrency_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("maximum-concurrent-requests", "3")
+                .insert_header("current-concurrent-requests", "2")
+                .set_body_json(json!([])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/user/subscription"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "tier": "starter" })))
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let output = run_cli_in(&server, home.path(), &["api", "get", "/v1/models"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let output = run_cli_in(&server, home.path(), &["--json", "limits"]).await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["observed"]["max_concurrent"], 3);
+    assert_eq!(result["observed"]["peak_concurrent"], 2);
+}
+
+#[tokio::test]
+async fn voice_design_iterate_rates_remixes_and_saves() {
+    let server = MockServer::start().await;
+    let preview = |id: &str| json!({ "generated_voice_id": id, "audio_base_64": "YXVkaW8=", "duration_secs": 2.5 });
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-voice/design"))
+        .and(body_partial_json(
+            json!({ "voice_description": "old sailor", "auto_generate_text": true }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "previews": [preview("gen_a"), preview("gen_b")],
+            "text": "Generated sample text"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-voice/gen_b/remix"))
+        .and(body_partial_json(json!({
+            "voice_description": "more gravelly",
+            "text": "Generated sample text"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "previews": [preview("gen_c")] })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-voice"))
+        .and(body_json(json!({
+            "voice_name": "Captain",
+            "voice_description": "more gravelly",
+            "generated_voice_id": "gen_c"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "voice_id": "voice_9" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = tempfile::tempdir().unwrap();
+    let out_dir = home.path().join("previews");
+    let out = out_dir.to_str().unwrap();
+    let steps: [&[&str]; 4] = [
+        &[
+            "voice-design",
+            "iterate",
+            "--prompt",
+            "old sailor",
+            "--out-dir",
+            out,
+        ],
+        &[
+            "voice-design",
+            "iterate",
+            "--rate",
+            "1.2=4",
+            "--remix",
+            "1.2",
+            "--prompt",
+            "more gravelly",
+            "--out-dir",
+            out,
+        ],
+        &["voice-design", "iterate", "--rate", "2.1=5"],
+        &[
+            "voice-design",
+            "iterate",
+            "--save",
+            "2.1",
+            "--name",
+            "Captain",
+        ],
+    ];
+    for step in steps {
+        let output = run_cli_in(&server, home.path(), step).await;
+        assert!(
+            output.status.success(),
+            "{:?} stderr: {}",
+            step,
+            stderr(&output)
+        );
+    }
+    assert_eq!(
+        std::fs::read(out_dir.join("2.1_gen_c.mp3")).unwrap(),
+        b"audio"
+    );
+
+    let output = run_cli_in(&server, home.path(), &["--json", "voice-design", "iterate"]).await;
+    let session: serde_json::Value = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON output");
+    assert_eq!(session["rounds"][0]["previews"][1]["rating"], 4);
+    assert_eq!(session["rounds"][1]["remix_of"], "gen_b");
+    assert_eq!(session["saved"]["voice_id"], "voice_9");
+
+    // A saved session takes no more rounds
+    let output = run_cli_in(
+        &server,
+        home.path(),
+        &["voice-design", "iterate", "--prompt", "again"],
+    )
+    .await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn tts_stream_chunk_schedule_streams_over_websocket() {
+    use base64::Engine as _;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+    let ws_server = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut uri = None;
+        // The callback signature is fixed by tungstenite
+        #[allow(clippy::result_large_err)]
+        let mut ws = tokio_tungstenite::accept_hdr_async(tcp, |req: &Request, resp: Response| {
+            uri = Some(req.uri().to_string());
+            Ok(resp)
+        })
+        .await
+        .unwrap();
+
+        let mut messages = Vec::new();
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let message: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let done = message["text"] == "";
+            messages.push(message);
+            if done {
+                break;
+            }
+        }
+        let audio = base64::engine::general_purpose::STANDARD.encode("streamed");
+        for reply in [json!({ "audio": audio }), json!({ "isFinal": true })] {
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+        }
+        (uri.unwrap(), messages)
+    });
+
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("speech.mp3");
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args([
+            "tts-stream",
+            "Hello",
+            "--chunk-schedule",
+            "50,120",
+            "--output",
+        ])
+        .arg(&out)
+        .env("ELEVENLABS_WS_BASE_URL", &ws_url)
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .expect("Failed to run CLI");
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let (uri, messages) = ws_server.await.unwrap();
+    assert!(uri.starts_with("/v1/text-to-speech/Brian/stream-input?model_id="));
+    assert_eq!(
+        messages[0]["generation_config"]["chunk_length_schedule"],
+        json!([50, 120])
+    );
+    assert_eq!(messages[1]["text"], "Hello ");
+    assert_eq!(std::fs::read(&out).unwrap(), b"streamed");
+}
+
+#[tokio::test]
+async fn tts_session_speaks_each_line_over_one_websocket() {
+    use base64::Engine as _;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "voices": [{ "voice_id": "voice_brian", "name": "Brian" }] }),
+            ),
+        )
+        .mount(&server)
+        .await;
+
+    // Answers every closed context with its ID as the audio bytes
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+    let ws_server = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut handshake = None;
+        // The callback signature is fixed by tungstenite
+        #[allow(clippy::result_large_err)]
+        let mut ws = tokio_tungstenite::accept_hdr_async(tcp, |req: &Request, resp: Response| {
+            handshake = Some((
+                req.uri().to_string(),
+                req.headers()
+                    .get("xi-api-key")
+                    .map(|v| v.to_str().unwrap().to_string()),
+            ));
+            Ok(resp)
+        })
+        .await
+        .unwrap();
+
+        let mut texts = Vec::new();
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let message: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if message["close_socket"] == json!(true) {
+                break;
+            }
+            let context = message["context_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            if message["close_context"] == json!(true) {
+                let audio = base64::engine::general_purpose::STANDARD.encode(&context);
+                for reply in [
+                    json!({ "audio": audio, "contextId": context }),
+                    json!({ "isFinal": true, "contextId": context }),
+                ] {
+                    ws.send(Message::Text(reply.to_string())).await.unwrap();
+                }
+            } else if message["flush"] == json!(true) {
+                texts.push(message["text"].as_str().unwrap().trim().to_string());
+            }
+        }
+        // A second connection would mean the session did not reuse the socket
+        let reconnected =
+            tokio::time::timeout(std::time::Duration::from_millis(200), listener.accept())
+                .await
+                .is_ok();
+        (handshake.unwrap(), texts, reconnected)
+    });
+
+    let home = tempfile::tempdir().unwrap();
+    let out_dir = home.path().join("lines");
+    let mut child = tokio::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args(["tts", "session", "--no-play", "--out-dir"])
+        .arg(&out_dir)
+        .env("ELEVENLABS_API_BASE_URL", server.uri())
+        .env("ELEVENLABS_WS_BASE_URL", &ws_url)
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to run CLI");
+    let mut stdin = child.stdin.take().unwrap();
+    stdin
+        .write_all(b"Hello there\n\nSecond line\n/quit\nNever spoken\n")
+        .await
+        .unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().await.unwrap();
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let ((uri, api_key), texts, reconnected) = ws_server.await.unwrap();
+    assert!(uri.starts_with(
+        "/v1/text-to-speech/voice_brian/multi-stream-input?model_id=eleven_flash_v2_5"
+    ));
+    assert_eq!(api_key.as_deref(), Some("test-api-key"));
+    assert_eq!(texts, vec!["Hello there", "Second line"]);
+    assert!(!reconnected);
+
+    assert_eq!(
+        std::fs::read(out_dir.join("001.mp3")).unwrap(),
+        b"utterance-1"
+    );
+    assert_eq!(
+        std::fs::read(out_dir.join("002.mp3")).unwrap(),
+        b"utterance-2"
+    );
+    assert!(!out_dir.join("003.mp3").exists());
+    assert!(stdout(&output).contains("Spoke 2 line(s)"));
+}
+
+#[tokio::test]
+async fn converse_chat_non_interactive_prints_json_transcript() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    // Greets, then echoes every user message
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+    let agent = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        let mut received = Vec::new();
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let replies = match event["type"].as_str() {
+                Some("conversation_initiation_client_data") => vec![
+                    json!({
+                        "type": "conversation_initiation_metadata",
+                        "conversation_initiation_metadata_event": { "conversation_id": "conv_1" }
+                    }),
+                    json!({
+                        "type": "agent_response",
+                        "agent_response_event": { "agent_response": "Hi, how can I help?" }
+                    }),
+                ],
+                Some("user_message") => {
+                    let text = event["text"].as_str().unwrap().to_string();
+                    received.push(text.clone());
+                    vec![json!({
+                        "type": "agent_response",
+                        "agent_response_event": { "agent_response": format!("Echo: {}", text) }
+                    })]
+                }
+                _ => Vec::new(),
+            };
+            for reply in replies {
+                ws.send(Message::Text(reply.to_string())).await.unwrap();
+            }
+        }
+        received
+    });
+
+    let home = tempfile::tempdir().unwrap();
+    let messages = home.path().join("messages.txt");
+    std::fs::write(&messages, "What are your hours?\n\n").unwrap();
+    let server = MockServer::start().await;
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args([
+            "--json",
+            "converse",
+            "chat",
+            "--agent-id",
+            "agent_1",
+            "--non-interactive",
+            "--message",
+            "Hello",
+            "--messages-file",
+        ])
+        .arg(&messages)
+        .env("ELEVENLABS_API_BASE_URL", server.uri())
+        .env("ELEVENLABS_WS_BASE_URL", &ws_url)
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .expect("Failed to run CLI");
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    let transcript = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .filter_map(Result::ok)
+        .last()
+        .expect("JSON transcript");
+    assert_eq!(transcript["conversation_id"], "conv_1");
+    assert_eq!(transcript["complete"], true);
+    let messages: Vec<(String, String)> = transcript["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["role"].as_str().unwrap().to_string(),
+                m["text"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            ("agent", "Hi, how can I help?"),
+            ("user", "Hello"),
+            ("agent", "Echo: Hello"),
+            ("user", "What are your hours?"),
+            ("agent", "Echo: What are your hours?"),
+        ]
+        .map(|(role, text)| (role.to_string(), text.to_string()))
+    );
+    assert_eq!(
+        agent.await.unwrap(),
+        vec!["Hello".to_string(), "What are your hours?".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn tts_stream_stdout_writes_only_audio() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian/stream"))
+        .and(query_param("output_format", "pcm_16000"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1u8, 0, 2, 0, 3, 0]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = run_cli(
+        &server,
+        &[
+            "tts-stream",
+            "Hello there",
+            "--voice",
+            "voice_brian",
+            "--output-format",
+            "pcm_16000",
+            "--stdout",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(output.stdout, [1u8, 0, 2, 0, 3, 0]);
+}
+
+#[tokio::test]
+async fn tts_stream_stdout_over_websocket_keeps_status_on_stderr() {
+    use base64::Engine as _;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+    let ws_server = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let message: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if message["text"] == "" {
+                break;
+            }
+        }
+        let audio = base64::engine::general_purpose::STANDARD.encode("streamed");
+        for reply in [json!({ "audio": audio }), json!({ "isFinal": true })] {
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+        }
+    });
+
+    let home = tempfile::tempdir().unwrap();
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_elevenlabs-cli"))
+        .args([
+            "tts-stream",
+            "Hello",
+            "--chunk-schedule",
+            "50,120",
+            "--stdout",
+        ])
+        .env("ELEVENLABS_WS_BASE_URL", &ws_url)
+        .env("ELEVENLABS_API_KEY", "test-api-key")
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("XDG_DATA_HOME", home.path().join("data"))
+        .env("NO_COLOR", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .expect("Failed to run CLI");
+    ws_server.await.unwrap();
+
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(output.stdout, b"streamed");
+    assert!(
+        stderr(&output).contains("Streaming speech"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[tokio::test]
+async fn tts_stream_fifo_sink_replaces_a_regular_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian/stream"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1u8, 0, 2, 0]))
+        .mount(&server)
+        .await;
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("tts.pcm");
+    std::fs::write(&file, [9u8; 16]).unwrap();
+    let sink = format!("fifo:{}", file.display());
+
+    let output = run_cli(
+        &server,
+        &[
+            "tts-stream",
+            "Hello there",
+            "--voice",
+            "voice_brian",
+            "--output-format",
+            "pcm_16000",
+            "--sink",
+            &sink,
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+    assert_eq!(std::fs::read(&file).unwrap(), [1u8, 0, 2, 0]);
+}
+
+#[tokio::test]
+async fn tts_stream_rtp_sink_sends_paced_packets() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/text-to-speech/voice_brian/stream"))
+        .and(query_param("output_format", "ulaw_8000"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x7fu8; 400]))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let sink = format!("rtp:{}", receiver.local_addr().unwrap());
+
+    let output = run_cli(
+        &server,
+        &[
+            "tts-stream",
+            "Hello there",
+            "--voice",
+            "voice_brian",
+            "--output-format",
+            "ulaw_8000",
+            "--sink",
+            &sink,
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "stderr: {}", stderr(&output));
+
+    // 400 bytes of 8 kHz μ-law: two 20 ms packets and a 10 ms remainder
+    let mut packets = Vec::new();
+    let mut buf = [0u8; 2048];
+    while let Ok(Ok(len)) = tokio::time::timeout(
+        std::time::Duration::from_millis(200),
+        receiver.recv(&mut buf),
+    )
+    .await
+    {
+        packets.push(buf[..len].to_vec());
+    }
+    let sizes: Vec<usize> = packets.iter().map(Vec::len).collect();
+    assert_eq!(sizes, [172, 172, 92]);
+    assert_eq!(packets[0][0], 0x80);
+    assert_eq!(
+        packets[0][1], 0x80,
+        "first packet has the marker bit and PCMU"
+    );
+    assert_eq!(packets[1][1], 0);
+    let sequence = |p: &[u8]| u16::from_be_bytes([p[2], p[3]]);
+    assert_eq!(sequence(&packets[1]), sequence(&packets[0]).wrapping_add(1));
+    let timestamp = |p: &[u8]| u32::from_be_bytes([p[4], p[5], p[6], p[7]]);
+    assert_eq!(
+        timestamp(&packets[1]),
+        timestamp(&packets[0]).wrapping_add(160)
+    );
+}